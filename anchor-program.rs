@@ -1,15 +1,111 @@
 // Generated Anchor Program by SolD Parser
 // Safety-first token launch program with fraud protection
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, Transfer};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Burn, FreezeAccount, Mint, MintTo, SetAuthority, ThawAccount, TokenAccount, TokenInterface};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token::spl_token;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::extension::transfer_fee::instruction as transfer_fee_instruction;
+use spl_token_2022::extension::transfer_hook::instruction as transfer_hook_instruction;
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, update_metadata_accounts_v2, CreateMetadataAccountsV3, Metadata,
+    UpdateMetadataAccountsV2,
+};
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use pyth_sdk_solana::state::SolanaPriceAccount;
 
 declare_id!("So1DLaunchProgram11111111111111111111111111");
 
-const FEE_RECIPIENT: &str = "GR8TuDpbnDvuLzW4JBCLjbeLvGFs1p21XBytLx6rA7XD";
 const MIN_TIMELOCK_DURATION: i64 = 8_640_000; // 100 days in seconds
+const TEST_MIN_TIMELOCK_DURATION: i64 = 3_600; // 1 hour, devnet/localnet only
 const MAX_INSURANCE_WALLETS: usize = 10;
 const MAX_INSURANCE_LIMIT: u8 = 50; // 50%
+const MAX_URI_LEN: usize = 200;
+const SQUADS_PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+const MAX_BATCH_SIZE: usize = 20; // launches per batch instruction
+const MAX_ANNOTATIONS: usize = 16;
+const MAX_ANNOTATION_KEY_LEN: usize = 16;
+const MAX_JUSTIFICATION_LEN: usize = 200;
+const PROPOSAL_EXPIRY: i64 = 7 * 86_400; // 7 days in seconds
+const WITHDRAWAL_COOLDOWN: i64 = 86_400; // 24 hours in seconds
+const MAX_TRANSFER_FEE_BPS: u16 = 500; // 5%, also caps the launch transfer tax
+const MAX_CREATOR_LAUNCHES: usize = 32;
+const REGISTRY_PAGE_SIZE: u64 = 64; // launches per RegistryPage
+const MAX_PROPOSAL_DESCRIPTION_LEN: usize = 200;
+const GOVERNANCE_VOTING_PERIOD: i64 = 3 * 86_400; // 3 days in seconds
+const GOVERNANCE_QUORUM_PCT: u64 = 10; // % of total supply that must vote
+const GOVERNANCE_PROPOSER_PCT: u64 = 1; // % of total supply needed to propose
+// AMMs the launch PDA may sign liquidity deposits for (Raydium CPMM, Raydium AMM v4)
+const AMM_PROGRAM_IDS: [&str; 2] = [
+    "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+    "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+];
+const MAX_FRAUD_ORACLES: usize = 8;
+const YIELD_VENUE_PROGRAM_IDS: [&str; 1] = [
+    "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD", // Marinade liquid staking
+];
+// Pyth oracle programs that own price accounts (mainnet, devnet)
+const PYTH_PROGRAM_IDS: [&str; 2] = [
+    "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH",
+    "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s",
+];
+const MAX_PRICE_AGE_SECS: u64 = 60; // staler Pyth prices are not observed
+const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+// sold_transfer_hook (transfer-hook-program.rs), the transfer hook of Token-2022 launch mints
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("So1DTransferHook111111111111111111111111111");
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224]; // spl-account-compression verify_leaf
+const APPEAL_BOND: u64 = 1_000_000_000; // 1 SOL
+const APPEAL_REVIEW_PERIOD: i64 = 7 * 86_400; // 7 days in seconds
+const MAX_RECOVERY_GUARDIANS: usize = 7;
+const MAX_ARBITERS: usize = 7;
+const MAX_RELOCKS: u32 = 5;
+const RECOVERY_CHALLENGE_PERIOD: i64 = 14 * 86_400; // 14 days in seconds
+const INSURANCE_CHANGE_DELAY: i64 = 7 * 86_400; // 7 days in seconds
+const MIN_INSURANCE_COLLATERAL: u64 = 1_000_000_000; // 1 SOL staked per acting insurance wallet
+const MAX_TRADING_PAUSE: i64 = 7 * 86_400; // 7 days, ceiling for any pause policy
+const MAX_DEX_ALLOWLIST: usize = 4; // pool authorities a launch can allowlist
+const MAX_DEX_ALLOWLIST_DAYS: u16 = 30; // longest post-unlock allowlist window
+const MAX_RECOVERY_ACCOUNTS: usize = 4; // token accounts emergency withdrawals may pay out to
+const MAX_TOKEN_NAME_LEN: usize = 50; // Metaplex name limit
+const MAX_TOKEN_SYMBOL_LEN: usize = 10; // Metaplex symbol limit
+const MAX_TOKEN_DECIMALS: u8 = 9;
+const METADATA_UPDATE_INTERVAL: i64 = 30 * 86_400; // 30 days between metadata proposals
+const METADATA_VETO_WINDOW: i64 = 3 * 86_400; // 3 days for escrow to veto
+// Fraud scores are fixed-point basis points: 0 (clean) to 10_000 (certain fraud)
+const MAX_FRAUD_SCORE_BPS: u16 = 10_000;
+const HIGH_RISK_SCORE_BPS: u16 = 7_000;
+const AUTO_SUSPEND_SCORE_BPS: u16 = 9_000; // applied to the time-weighted score
+const FRAUD_SCORE_HISTORY_LEN: usize = 16; // recent updates kept per launch
+const FRAUD_SCORE_WINDOW: i64 = 6 * 3_600; // 6 hours averaged for auto-suspension
+const FRAUD_SCORE_MIN_SPAN: i64 = 3_600; // 1 hour of history before it can suspend
+const LOW_RISK_SCORE_BPS: u16 = 2_000; // oracle scores at or below this earn the low-risk discount
+const LOW_RISK_DISCOUNT_BPS: u16 = 1_000;
+const REPEAT_CREATOR_DISCOUNT_BPS: u16 = 1_000;
+const HIGH_RISK_SURCHARGE_BPS: u16 = 5_000;
+const LEGACY_FRAUD_SCORE_EXTRA_BYTES: usize = 2; // f32 score -> u16 bps
+const LAUNCH_VERSION: u8 = 19; // v19 moves TokenLaunch to the zero-copy layout, see migrate_launch
+const FEE_SHARDS: u8 = 8; // per-launch trading fee accounts, see transfer_tokens
+const SALE_STATS_SHARD: u8 = FEE_SHARDS; // LaunchStats shard for presale and curve trades
+const MAX_AIRDROP_RECIPIENTS: usize = 16; // two accounts each; bounded by transaction size
+const AIRDROP_TRANSFER_COMPUTE_UNITS: u64 = 25_000; // transfer_checked plus a transfer hook
+const MAX_LAUNCH_CLASSES: usize = 8;
+const MAX_FEE_RECIPIENTS: usize = 5; // weighted recipients of the protocol fee share
+const MAX_REFERRAL_BPS: u16 = 5_000; // referrers never take more than half a launch fee
+const REWARD_PRECISION: u128 = 1_000_000_000_000; // scale of per-share reward accumulators
+const MIN_CLASS_TIMELOCK_DURATION: i64 = 30 * 86_400; // 30 days, floor for any launch class
+const MAX_REFLECTION_BPS: u16 = 5_000; // at most half of trading fees go to holders
+const MAX_DIVIDEND_HOLDERS: usize = 10; // three accounts each per distribute_dividends
+const CRANK_REWARD_LAMPORTS: u64 = 50_000; // per successful crank_launch, from the launch's fees
+const BOOTSTRAP_RING_SIZE: usize = 16; // recent buyers tracked for the bootstrap cooldown
+const MAX_BOOTSTRAP_FEE_MULTIPLIER: u8 = 10;
+const MAX_TEAM_ALLOCATION_PCT: u64 = 20; // % of total supply creators may carve out for the team
 
 #[program]
 pub mod sold_token_launch {
@@ -20,176 +116,430 @@ pub mod sold_token_launch {
         ctx: Context<InitializeLaunch>,
         params: LaunchParams,
     ) -> Result<()> {
-        let launch = &mut ctx.accounts.token_launch;
-        let clock = Clock::get()?;
-
-        // Validate parameters
-        require!(
-            params.timelock_duration >= MIN_TIMELOCK_DURATION,
-            TokenLaunchError::TimelockTooShort
-        );
-        require!(
-            params.insurance_wallets.len() <= MAX_INSURANCE_WALLETS,
-            TokenLaunchError::TooManyInsuranceWallets
-        );
-        require!(
-            params.insurance_limit <= MAX_INSURANCE_LIMIT,
-            TokenLaunchError::InsuranceLimitTooHigh
-        );
-
-        // Initialize launch state
-        launch.creator = ctx.accounts.creator.key();
-        launch.token_mint = ctx.accounts.token_mint.key();
-        launch.token_name = params.token_name;
-        launch.token_symbol = params.token_symbol;
-        launch.total_supply = params.total_supply;
-        launch.timelock_end = clock.unix_timestamp + params.timelock_duration;
-        launch.insurance_wallets = params.insurance_wallets;
-        launch.insurance_limit = params.insurance_limit;
-        launch.logo_nft = params.logo_nft;
-        launch.fraud_score = params.fraud_score;
-        launch.fees_collected = 0;
-        launch.is_active = true;
-        launch.relock_count = 0;
-        launch.total_withdrawn = 0;
-
-        // Calculate and collect launch fee
-        let base_fee: u64 = 10_000_000; // 0.01 SOL
-        let insurance_fee = (launch.insurance_wallets.len() as u64) * 10_000_000; // 0.01 SOL per wallet
-        let logo_fee = if launch.logo_nft.is_some() { 5_000_000 } else { 0 }; // 0.005 SOL
-        let total_fee = base_fee + insurance_fee + logo_fee;
-
-        // Transfer fee to recipient
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.fee_recipient.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(cpi_context, total_fee)?;
-
-        launch.fees_collected = total_fee;
-
-        msg!("Token launch initialized: {} ({})", launch.token_name, launch.token_symbol);
-        msg!("Timelock expires: {}", launch.timelock_end);
-        msg!("Fraud score: {:.2}", launch.fraud_score);
-        msg!("Fee collected: {} lamports", total_fee);
+        process_initialize_launch(ctx, params, Vec::new())
+    }
 
-        Ok(())
+    /// Initialize a launch from versioned parameters: the core fields plus
+    /// any optional features. Same accounts and rules as `initialize_launch`.
+    pub fn initialize_launch_v2(
+        ctx: Context<InitializeLaunch>,
+        params: LaunchParamsV2,
+    ) -> Result<()> {
+        let recovery_accounts = params.recovery_accounts();
+        process_initialize_launch(ctx, params.into_params()?, recovery_accounts)
     }
 
-    /// Create and mint the initial token supply
+    /// Mint the whole supply of a fresh mint into the vault, then revoke the
+    /// mint authority and any freeze authority the creator holds
     pub fn create_token(
         ctx: Context<CreateToken>,
         decimals: u8,
     ) -> Result<()> {
-        let launch = &ctx.accounts.token_launch;
+        {
+            let launch = &mut ctx.accounts.token_launch.load_mut()?;
+            launch.verify_creator(&ctx.accounts.creator.key())?;
+            launch.vault = ctx.accounts.vault.key();
+            launch.metadata = ctx.accounts.metadata.key();
+        }
+        validation::decimals(ctx.accounts.token_mint.decimals)?;
+        require!(decimals == ctx.accounts.token_mint.decimals, TokenLaunchError::InvalidDecimals);
+        let launch = &ctx.accounts.token_launch.load()?;
         
-        // Mint initial supply to creator
+        // Mint initial supply into the launch-owned vault
         let cpi_accounts = MintTo {
             mint: ctx.accounts.token_mint.to_account_info(),
-            to: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
             authority: ctx.accounts.creator.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         
-        token::mint_to(cpi_ctx, launch.total_supply)?;
+        token_interface::mint_to(cpi_ctx, launch.total_supply)?;
+
+        // Create Metaplex metadata so wallets display name, symbol and image.
+        // The launch PDA is update authority so later changes stay program-gated.
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        let cpi_accounts = CreateMetadataAccountsV3 {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            mint_authority: ctx.accounts.creator.to_account_info(),
+            payer: ctx.accounts.creator.to_account_info(),
+            update_authority: ctx.accounts.token_launch.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.metadata_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let data = DataV2 {
+            name: launch.token_name().to_string(),
+            symbol: launch.token_symbol().to_string(),
+            uri: launch.metadata_uri().to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+        create_metadata_accounts_v3(cpi_ctx, data, true, true, None)?;
+
+        // Fix the supply: nobody can mint past total_supply
+        token_interface::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.creator.to_account_info(),
+                    account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
 
-        msg!("Minted {} tokens to creator", launch.total_supply);
+        // Only the launch PDA may freeze holders (see add_to_blacklist)
+        let creator = ctx.accounts.creator.key();
+        match Option::<Pubkey>::from(ctx.accounts.token_mint.freeze_authority) {
+            None => {}
+            Some(authority) if authority == ctx.accounts.token_launch.key() => {}
+            Some(authority) if authority == creator => token_interface::set_authority(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: ctx.accounts.creator.to_account_info(),
+                        account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                    },
+                ),
+                spl_token_2022::instruction::AuthorityType::FreezeAccount,
+                None,
+            )?,
+            Some(_) => return err!(TokenLaunchError::FreezeAuthorityNotAllowed),
+        }
+
+        emit!(TokensMinted {
+            token_mint: launch.token_mint,
+            vault: launch.vault,
+            metadata: launch.metadata,
+            amount: launch.total_supply,
+            decimals: ctx.accounts.token_mint.decimals,
+        });
+
+        msg!("Minted {} tokens to launch vault {}", launch.total_supply, launch.vault);
+        msg!("Metadata account: {}", launch.metadata);
         Ok(())
     }
 
-    /// Transfer tokens (only after timelock expires)
-    pub fn transfer_tokens(
-        ctx: Context<TransferTokens>,
+    /// Transfer tokens (only after timelock expires). The launch is only
+    /// read and the trading fee lands in one of the launch's fee shards, so
+    /// transfers of the same mint that pick different shards run in
+    /// parallel. Clients should spread load, e.g. `payer[0] % FEE_SHARDS`.
+    pub fn transfer_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferTokens<'info>>,
         amount: u64,
+        shard: u8,
     ) -> Result<()> {
-        let launch = &ctx.accounts.token_launch;
+        let launch = &ctx.accounts.token_launch.load()?;
         let clock = Clock::get()?;
 
         // Check if launch is active
-        require!(launch.is_active, TokenLaunchError::LaunchInactive);
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            !launch.is_trading_paused(clock.unix_timestamp),
+            TokenLaunchError::TradingPaused
+        );
 
         // Check if timelock has expired
         require!(
             clock.unix_timestamp >= launch.timelock_end,
             TokenLaunchError::TimelockActive
         );
+        launch.check_unlock_condition()?;
+
+        // Anti-whale: cap the size of a single transfer
+        launch.check_max_tx(amount)?;
+
+        // Just after unlock, only trade against allowlisted pools
+        launch.check_dex_counterparty(
+            &ctx.accounts.from_token_account.owner,
+            &ctx.accounts.to_token_account.owner,
+            clock.unix_timestamp,
+        )?;
+
+        // Collect trading fee (2x Solana base fee) into the fee shard
+        let mut trading_fee = ctx.accounts.config.fees_for(launch)?.trading_fee;
+        let opens_holder = ctx.accounts.to_token_account.amount == 0;
+        let closes_holder = ctx.accounts.from_token_account.amount == amount;
 
-        // Collect trading fee (2x Solana base fee)
-        let trading_fee: u64 = 10_000; // ~0.00001 SOL
+        // Anti-sniper: inside the bootstrap window, cap each slot's volume,
+        // hold each buyer to a cooldown and charge an elevated fee
+        if let Some(bootstrap) = launch.bootstrap() {
+            let guard_info = ctx
+                .accounts
+                .bootstrap_guard
+                .as_ref()
+                .ok_or(TokenLaunchError::BootstrapGuardRequired)?
+                .to_account_info();
+            require_keys_eq!(*guard_info.owner, crate::ID, TokenLaunchError::BootstrapGuardRequired);
+            let mut guard = BootstrapGuard::try_deserialize(&mut &guard_info.try_borrow_data()?[..])?;
+            if guard.is_open(&bootstrap, clock.slot) {
+                require!(guard_info.is_writable, TokenLaunchError::BootstrapGuardRequired);
+                guard.record_transfer(&bootstrap, ctx.accounts.to_token_account.owner, amount, clock.slot)?;
+                guard.try_serialize(&mut &mut guard_info.try_borrow_mut_data()?[..])?;
+                trading_fee = math::mul(trading_fee, bootstrap.fee_multiplier as u64)?;
+            }
+        }
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: ctx.accounts.payer.to_account_info(),
-                to: ctx.accounts.fee_recipient.to_account_info(),
+                to: ctx.accounts.fee_shard.to_account_info(),
             },
         );
         anchor_lang::system_program::transfer(cpi_context, trading_fee)?;
+        let fee_shard = &mut ctx.accounts.fee_shard;
+        fee_shard.launch = ctx.accounts.token_launch.key();
+        fee_shard.shard = shard;
+        fee_shard.bump = ctx.bumps.fee_shard;
+        fee_shard.record_fee(trading_fee)?;
 
-        // Execute token transfer
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.from_token_account.to_account_info(),
-            to: ctx.accounts.to_token_account.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
+        // A session key spends through its Delegation, which is the source
+        // account's SPL delegate and signs the transfer
+        if let Some(delegation) = &mut ctx.accounts.delegation {
+            delegation.spend(&ctx.accounts.authority.key(), amount, clock.unix_timestamp)?;
+        }
+        let from_key = ctx.accounts.from_token_account.key();
+        let delegation_bump = ctx.accounts.delegation.as_ref().map(|delegation| [delegation.bump]);
+        let delegation_seeds = delegation_bump
+            .as_ref()
+            .map(|bump| [seeds::DELEGATION, from_key.as_ref(), bump]);
+        let signer_seeds: Vec<&[&[u8]]> = delegation_seeds.iter().map(|seeds| &seeds[..]).collect();
+        let authority = match &ctx.accounts.delegation {
+            Some(delegation) => delegation.to_account_info(),
+            None => ctx.accounts.authority.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        token::transfer(cpi_ctx, amount)?;
 
-        msg!("Transferred {} tokens (fee: {} lamports)", amount, trading_fee);
+        // Token tax, deducted from the amount and paid to the treasury
+        let TransferTaxPreview { tax, net_amount, .. } = launch.preview_transfer_tax(amount)?;
+        if tax > 0 {
+            let treasury_token_account = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(TokenLaunchError::TreasuryTokenAccountRequired)?;
+            require_keys_eq!(
+                treasury_token_account.owner,
+                seeds::find_treasury().0,
+                TokenLaunchError::TreasuryTokenAccountRequired
+            );
+            transfer_launch_tokens(
+                LaunchTokenTransfer {
+                    token_program: &ctx.accounts.token_program,
+                    from: ctx.accounts.from_token_account.to_account_info(),
+                    mint: &ctx.accounts.token_mint,
+                    to: treasury_token_account.to_account_info(),
+                    authority: authority.clone(),
+                    additional_accounts: ctx.remaining_accounts,
+                    signer_seeds: &signer_seeds,
+                },
+                tax,
+            )?;
+        }
+
+        // Execute token transfer
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.from_token_account.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.to_token_account.to_account_info(),
+                authority,
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds: &signer_seeds,
+            },
+            net_amount,
+        )?;
+
+        // Anti-whale: the destination may not exceed the max wallet share.
+        // Returns to the launch vault are exempt.
+        if ctx.accounts.to_token_account.owner != ctx.accounts.token_launch.key() {
+            ctx.accounts.to_token_account.reload()?;
+            launch.check_max_wallet(ctx.accounts.to_token_account.amount)?;
+        }
+
+        let stats = &mut ctx.accounts.launch_stats;
+        stats.launch = ctx.accounts.token_launch.key();
+        stats.shard = shard;
+        stats.bump = ctx.bumps.launch_stats;
+        stats.record_transfer(amount, trading_fee, tax, opens_holder, closes_holder, clock.unix_timestamp)?;
+
+        emit!(TokensTransferred {
+            token_mint: launch.token_mint,
+            from: ctx.accounts.from_token_account.key(),
+            to: ctx.accounts.to_token_account.key(),
+            amount,
+            fee_paid: trading_fee,
+        });
+
+        msg!("Transferred {} tokens (fee: {} lamports, shard {})", amount, trading_fee, shard);
+        Ok(())
+    }
+
+    /// Release tokens from the launch vault (creator only, after the
+    /// timelock and within the unlock schedule). Split out of
+    /// `transfer_tokens` because it is the one transfer path that writes
+    /// launch state.
+    pub fn release_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            clock.unix_timestamp >= launch.timelock_end,
+            TokenLaunchError::TimelockActive
+        );
+        launch.check_unlock_condition()?;
+        launch.check_max_tx(amount)?;
+        launch.check_unlock(amount, clock.unix_timestamp)?;
+
+        let trading_fee = ctx.accounts.config.fees_for(&launch)?.trading_fee;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, trading_fee)?;
+        ctx.accounts.treasury.record_fee(trading_fee)?;
+
+        // The launch PDA signs for its own vault
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.to_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        if ctx.accounts.to_token_account.owner != ctx.accounts.token_launch.key() {
+            ctx.accounts.to_token_account.reload()?;
+            launch.check_max_wallet(ctx.accounts.to_token_account.amount)?;
+        }
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.fees_collected = math::add(launch.fees_collected, trading_fee)?;
+        launch.unlocked_so_far = math::add(launch.unlocked_so_far, amount)?;
+
+        emit!(TokensTransferred {
+            token_mint: launch.token_mint,
+            from: ctx.accounts.vault.key(),
+            to: ctx.accounts.to_token_account.key(),
+            amount,
+            fee_paid: trading_fee,
+        });
+
+        msg!("Released {} tokens from the vault (fee: {} lamports)", amount, trading_fee);
         Ok(())
     }
 
     /// Emergency withdrawal by authorized insurance wallets
-    pub fn emergency_withdraw(
-        ctx: Context<EmergencyWithdraw>,
+    pub fn emergency_withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, EmergencyWithdraw<'info>>,
         amount: u64,
         justification: String,
     ) -> Result<()> {
-        let launch = &mut ctx.accounts.token_launch;
+        let mut launch = ctx.accounts.token_launch.load_mut()?;
         let caller = ctx.accounts.authority.key();
 
         // Verify caller is authorized insurance wallet
         require!(
-            launch.insurance_wallets.contains(&caller),
+            launch.insurance_wallets().contains(&caller),
             TokenLaunchError::UnauthorizedInsurance
         );
+        require!(
+            justification.len() <= MAX_JUSTIFICATION_LEN,
+            TokenLaunchError::JustificationTooLong
+        );
 
-        // Check withdrawal limit
-        let max_withdraw = (launch.total_supply * launch.insurance_limit as u64) / 100;
+        // Launches with an M-of-N threshold must use the proposal flow
         require!(
-            launch.total_withdrawn + amount <= max_withdraw,
-            TokenLaunchError::ExceedsInsuranceLimit
+            launch.required_approvals() <= 1,
+            TokenLaunchError::MultisigRequired
         );
+        ctx.accounts.insurance_collateral.check_staked()?;
+        launch.check_recovery_account(&ctx.accounts.to_token_account.key())?;
+
+        // Check withdrawal limit
+        let max_withdraw = launch.check_insurance_withdrawal(amount)?;
+
+        // Enforce the per-wallet cap and cooldown
+        let clock = Clock::get()?;
+        let wallet_record = &mut ctx.accounts.wallet_record;
+        wallet_record.launch = ctx.accounts.token_launch.key();
+        wallet_record.wallet = caller;
+        wallet_record.record_withdrawal(amount, launch.per_wallet_insurance_cap(), clock.unix_timestamp)?;
 
         // Collect higher fee for emergency withdrawals
-        let emergency_fee: u64 = 50_000; // 0.00005 SOL
+        let emergency_fee = ctx.accounts.config.fees_for(&launch)?.emergency_fee;
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.fee_recipient.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
             },
         );
         anchor_lang::system_program::transfer(cpi_context, emergency_fee)?;
-
-        // Execute emergency withdrawal
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.from_token_account.to_account_info(),
-            to: ctx.accounts.to_token_account.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        token::transfer(cpi_ctx, amount)?;
+        ctx.accounts.treasury.record_fee(emergency_fee)?;
+        launch.fees_collected = math::add(launch.fees_collected, emergency_fee)?;
 
         // Update withdrawal tracking
-        launch.total_withdrawn += amount;
+        launch.total_withdrawn = math::add(launch.total_withdrawn, amount)?;
+        ctx.accounts.withdrawal_record.set_inner(WithdrawalRecord {
+            launch: ctx.accounts.token_launch.key(),
+            seq: launch.next_withdrawal_record_seq()?,
+            wallet: caller,
+            amount,
+            destination: ctx.accounts.to_token_account.key(),
+            withdrawn_at: clock.unix_timestamp,
+            proposal_id: None,
+            justification: justification.clone(),
+            bump: ctx.bumps.withdrawal_record,
+        });
+
+        // Execute emergency withdrawal from the vault, signed by the launch PDA
+        drop(launch);
+        let launch = ctx.accounts.token_launch.load()?;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.to_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        emit!(EmergencyWithdrawal {
+            token_mint: launch.token_mint,
+            insurance_wallet: caller,
+            amount,
+            justification: justification.clone(),
+            remaining_limit: launch.get_remaining_insurance_limit(),
+        });
 
         msg!("Emergency withdrawal: {} tokens", amount);
         msg!("Justification: {}", justification);
@@ -204,35 +554,68 @@ pub mod sold_token_launch {
         new_duration: i64,
         reason: String,
     ) -> Result<()> {
-        let launch = &mut ctx.accounts.token_launch;
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
         let clock = Clock::get()?;
 
-        // Only authorized escrow can relock
-        require!(
-            ctx.accounts.escrow_authority.key() == FEE_RECIPIENT.parse().unwrap(),
-            TokenLaunchError::UnauthorizedRelock
-        );
+        // Only authorized escrow (or an arbiter quorum) can relock
+        ctx.accounts.config.check_escrow(
+            &ctx.accounts.escrow_authority.key(),
+            ctx.accounts.arbitration.as_deref(),
+            &ctx.accounts.token_launch.key(),
+            &ArbitrationAction::Relock { new_duration },
+            clock.unix_timestamp,
+            TokenLaunchError::UnauthorizedRelock,
+        )?;
+        if let Some(arbitration) = &mut ctx.accounts.arbitration {
+            arbitration.executed = true;
+        }
 
-        // Validate new duration
-        require!(
-            new_duration >= MIN_TIMELOCK_DURATION,
-            TokenLaunchError::TimelockTooShort
-        );
+        // Relocks may only extend the lock, a limited number of times, and
+        // past the creator's consent horizon only with their co-signature
+        let preview = launch.preview_relock(
+            new_duration,
+            ctx.accounts.config.cluster,
+            ctx.accounts.config.fees_for(launch)?.relock_fee,
+            clock.unix_timestamp,
+        )?;
+        let new_timelock_end = preview.new_timelock_end;
+        if preview.needs_creator_consent {
+            let creator = ctx
+                .accounts
+                .creator
+                .as_ref()
+                .ok_or(TokenLaunchError::CreatorConsentRequired)?;
+            launch.verify_creator(&creator.key())?;
+        }
 
         // Collect relock fee
-        let relock_fee: u64 = 20_000_000; // 0.02 SOL
+        let relock_fee = ctx.accounts.config.fees_for(launch)?.relock_fee;
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: ctx.accounts.escrow_authority.to_account_info(),
-                to: ctx.accounts.fee_recipient.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
             },
         );
         anchor_lang::system_program::transfer(cpi_context, relock_fee)?;
+        ctx.accounts.treasury.record_fee(relock_fee)?;
+        launch.fees_collected = math::add(launch.fees_collected, relock_fee)?;
 
         // Update timelock
-        launch.timelock_end = clock.unix_timestamp + new_duration;
-        launch.relock_count += 1;
+        let old_timelock_end = launch.timelock_end;
+        launch.timelock_end = new_timelock_end;
+        launch.relock_count = launch
+            .relock_count
+            .checked_add(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+
+        emit!(TokensRelocked {
+            token_mint: launch.token_mint,
+            old_timelock_end,
+            new_timelock_end,
+            reason: reason.clone(),
+            relock_count: launch.relock_count,
+        });
 
         msg!("Tokens relocked until: {}", launch.timelock_end);
         msg!("Relock reason: {}", reason);
@@ -241,29 +624,63 @@ pub mod sold_token_launch {
         Ok(())
     }
 
-    /// Update fraud score (AI service only)
+    /// Update fraud score (registered fraud oracles only). The oracle names
+    /// the registered model that produced the score and must present its hash.
     pub fn update_fraud_score(
         ctx: Context<UpdateFraudScore>,
-        new_score: f32,
+        new_score: u16,
+        model_version: u32,
+        model_hash: [u8; 32],
     ) -> Result<()> {
-        let launch = &mut ctx.accounts.token_launch;
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let clock = Clock::get()?;
 
         // Validate fraud score range
         require!(
-            new_score >= 0.0 && new_score <= 1.0,
+            new_score <= MAX_FRAUD_SCORE_BPS,
             TokenLaunchError::InvalidFraudScore
         );
+        ctx.accounts.scoring_model.verify(&model_hash)?;
+
+        let history = &mut ctx.accounts.score_history;
+        history.launch = ctx.accounts.token_launch.key();
+        history.bump = ctx.bumps.score_history;
+        history.record(new_score, ctx.accounts.ai_authority.key(), clock.unix_timestamp);
 
         let old_score = launch.fraud_score;
+        let was_high_risk = launch.is_high_risk();
         launch.fraud_score = new_score;
+        launch.score_model_version = model_version;
+        launch.score_model_hash = model_hash;
+        launch.emit_risk_change(was_high_risk, old_score)?;
+
+        // A launch turning high-risk blots its creator's history
+        if !was_high_risk && launch.is_high_risk() {
+            if let Some(profile) = &mut ctx.accounts.creator_profile {
+                profile.record_flag()?;
+            }
+        }
 
-        // Auto-suspend if fraud score too high
-        if new_score > 0.9 {
-            launch.is_active = false;
-            msg!("Launch auto-suspended due to high fraud score: {:.2}", new_score);
+        // Auto-suspend if the time-weighted score is too high; a single
+        // reading only counts for as long as it stands
+        let smoothed = history.smoothed_score(clock.unix_timestamp);
+        let auto_suspended = history.should_suspend(clock.unix_timestamp);
+        if auto_suspended {
+            launch.set_active(false);
+            launch.emit_active_change("auto-suspended: high fraud score".to_string())?;
+            msg!("Launch auto-suspended due to high fraud score: {} bps", smoothed.unwrap_or(new_score));
         }
 
-        msg!("Fraud score updated: {:.2} -> {:.2}", old_score, new_score);
+        emit!(FraudScoreUpdated {
+            token_mint: launch.token_mint,
+            old_score,
+            new_score,
+            auto_suspended,
+            model_version,
+            model_hash,
+        });
+
+        msg!("Fraud score updated: {} -> {} bps (model v{})", old_score, new_score, model_version);
         Ok(())
     }
 
@@ -272,482 +689,16498 @@ pub mod sold_token_launch {
         ctx: Context<SuspendLaunch>,
         reason: String,
     ) -> Result<()> {
-        let launch = &mut ctx.accounts.token_launch;
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
         
-        // Only escrow can suspend
+        // Only escrow (or an arbiter quorum) can suspend
+        ctx.accounts.config.check_escrow(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.arbitration.as_deref(),
+            &ctx.accounts.token_launch.key(),
+            &ArbitrationAction::Suspend,
+            Clock::get()?.unix_timestamp,
+            TokenLaunchError::UnauthorizedSuspension,
+        )?;
+        if let Some(arbitration) = &mut ctx.accounts.arbitration {
+            arbitration.executed = true;
+        }
+
+        launch.set_active(false);
+        launch.emit_active_change(reason.clone())?;
+
+        emit!(LaunchSuspended {
+            token_mint: launch.token_mint,
+            reason: reason.clone(),
+            suspended_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Launch suspended: {}", reason);
+        Ok(())
+    }
+
+    /// Suspend many launches at once (escrow only)
+    /// Launch accounts are passed as writable remaining accounts
+    pub fn batch_suspend_launches<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchEscrowAction<'info>>,
+        reason: String,
+    ) -> Result<()> {
+        // Only escrow can suspend; arbiter panels vote per launch instead
         require!(
-            ctx.accounts.authority.key() == FEE_RECIPIENT.parse().unwrap(),
+            !ctx.accounts.config.arbitration_required,
+            TokenLaunchError::ArbitrationRequired
+        );
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.escrow_authority,
             TokenLaunchError::UnauthorizedSuspension
         );
+        check_batch_size(ctx.remaining_accounts.len())?;
 
-        launch.is_active = false;
+        let suspended_at = Clock::get()?.unix_timestamp;
+        for launch_info in ctx.remaining_accounts.iter() {
+            let launch = load_batch_launch(launch_info)?;
+            let mut launch = launch.load_mut()?;
+            launch.set_active(false);
+            launch.emit_active_change(reason.clone())?;
 
-        msg!("Launch suspended: {}", reason);
+            emit!(LaunchSuspended {
+                token_mint: launch.token_mint,
+                reason: reason.clone(),
+                suspended_at,
+            });
+
+            msg!("Launch suspended: {}", launch.token_mint);
+        }
+
+        msg!("Batch suspension of {} launches: {}", ctx.remaining_accounts.len(), reason);
         Ok(())
     }
-}
 
-// Account Contexts
-#[derive(Accounts)]
-pub struct InitializeLaunch<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    #[account(
-        init,
-        payer = creator,
-        space = TokenLaunch::space(),
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    /// CHECK: Token mint account
-    pub token_mint: Account<'info, Mint>,
-    
-    /// CHECK: Fee recipient address validated in instruction
-    #[account(
-        mut,
-        address = FEE_RECIPIENT.parse().unwrap()
-    )]
-    pub fee_recipient: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Update fraud scores for many launches at once (registered fraud oracles only)
+    /// Remaining accounts are (launch, score history) pairs; `new_scores[i]`
+    /// applies to the i-th pair. Histories are created by the launch's first
+    /// `update_fraud_score`.
+    pub fn batch_update_fraud_scores<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchFraudScoreUpdate<'info>>,
+        new_scores: Vec<u16>,
+        model_version: u32,
+        model_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.scoring_model.verify(&model_hash)?;
+        let pairs = batch_pairs(ctx.remaining_accounts, new_scores.len())?;
+        let now = Clock::get()?.unix_timestamp;
 
-#[derive(Accounts)]
-pub struct CreateToken<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    #[account(
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(
-        init_if_needed,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = creator
-    )]
-    pub creator_token_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        for (accounts, new_score) in pairs.zip(new_scores) {
+            let [launch_info, history_info] = accounts else {
+                unreachable!("chunks_exact(2) yields pairs");
+            };
+            // Validate fraud score range
+            require!(
+                new_score <= MAX_FRAUD_SCORE_BPS,
+                TokenLaunchError::InvalidFraudScore
+            );
 
-#[derive(Accounts)]
-pub struct TransferTokens<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    #[account(
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub from_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub to_token_account: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Fee recipient validated in instruction
-    #[account(mut, address = FEE_RECIPIENT.parse().unwrap())]
-    pub fee_recipient: AccountInfo<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+            let launch = load_batch_launch(launch_info)?;
+            let mut history = load_score_history(history_info, &launch.key())?;
+            let mut launch = launch.load_mut()?;
+            history.record(new_score, ctx.accounts.ai_authority.key(), now);
+            history.exit(&crate::ID)?;
 
-#[derive(Accounts)]
-pub struct EmergencyWithdraw<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    pub token_mint: Account<'info, Mint>,
-}
+            let old_score = launch.fraud_score;
+            let was_high_risk = launch.is_high_risk();
+            launch.fraud_score = new_score;
+            launch.score_model_version = model_version;
+            launch.score_model_hash = model_hash;
+            launch.emit_risk_change(was_high_risk, old_score)?;
 
-#[derive(Accounts)]
-pub struct SuspendLaunch<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    pub token_mint: Account<'info, Mint>,
-}
+            // Auto-suspend if the time-weighted score is too high
+            let auto_suspended = history.should_suspend(now);
+            if auto_suspended {
+                launch.set_active(false);
+                launch.emit_active_change("auto-suspended: high fraud score".to_string())?;
+                msg!(
+                    "Launch {} auto-suspended due to high fraud score: {} bps",
+                    launch.token_mint,
+                    history.smoothed_score(now).unwrap_or(new_score)
+                );
+            }
 
-// Data Structures
-#[account]
-pub struct TokenLaunch {
-    pub creator: Pubkey,                    // 32 bytes
-    pub token_mint: Pubkey,                 // 32 bytes
-    pub token_name: String,                 // 4 + max 50 bytes
-    pub token_symbol: String,               // 4 + max 10 bytes  
-    pub total_supply: u64,                  // 8 bytes
-    pub timelock_end: i64,                  // 8 bytes
-    pub insurance_wallets: Vec<Pubkey>,     // 4 + (32 * count) bytes
-    pub insurance_limit: u8,                // 1 byte
-    pub logo_nft: Option<Pubkey>,           // 33 bytes (32 + 1 for Option)
-    pub fraud_score: f32,                   // 4 bytes
-    pub fees_collected: u64,                // 8 bytes
-    pub is_active: bool,                    // 1 byte
-    pub relock_count: u32,                  // 4 bytes
-    pub total_withdrawn: u64,               // 8 bytes
-}
+            emit!(FraudScoreUpdated {
+                token_mint: launch.token_mint,
+                old_score,
+                new_score,
+                auto_suspended,
+                model_version,
+                model_hash,
+            });
 
-impl TokenLaunch {
-    pub fn space() -> usize {
-        8 +           // discriminator
-        32 +          // creator
-        32 +          // token_mint
-        (4 + 50) +    // token_name
-        (4 + 10) +    // token_symbol
-        8 +           // total_supply
-        8 +           // timelock_end
-        (4 + 32 * MAX_INSURANCE_WALLETS) + // insurance_wallets
-        1 +           // insurance_limit
-        33 +          // logo_nft (Option<Pubkey>)
-        4 +           // fraud_score
-        8 +           // fees_collected
-        1 +           // is_active
-        4 +           // relock_count
-        8 +           // total_withdrawn
-        64            // padding for future fields
+            msg!(
+                "Fraud score for {} updated: {} -> {} bps (model v{})",
+                launch.token_mint,
+                old_score,
+                new_score,
+                model_version
+            );
+        }
+
+        Ok(())
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct LaunchParams {
-    pub token_name: String,
-    pub token_symbol: String, 
-    pub total_supply: u64,
-    pub timelock_duration: i64,
-    pub insurance_wallets: Vec<Pubkey>,
-    pub insurance_limit: u8,
+    /// Record or overwrite an escrow annotation (e.g. "audit", "listing")
+    /// on the launch's companion annotations account
+    pub fn annotate_launch(
+        ctx: Context<AnnotateLaunch>,
+        key: String,
+        value_hash: [u8; 32],
+    ) -> Result<()> {
+        // Only escrow can annotate
+        require!(
+            ctx.accounts.escrow_authority.key() == ctx.accounts.config.escrow_authority,
+            TokenLaunchError::UnauthorizedAnnotation
+        );
+        require!(
+            !key.is_empty() && key.len() <= MAX_ANNOTATION_KEY_LEN,
+            TokenLaunchError::InvalidAnnotationKey
+        );
+
+        let annotations = &mut ctx.accounts.annotations;
+        let clock = Clock::get()?;
+        annotations.token_mint = ctx.accounts.token_launch.load()?.token_mint;
+        annotations.upsert(key.clone(), value_hash, clock.unix_timestamp)?;
+
+        msg!("Launch annotated: {} ({} entries)", key, annotations.entries.len());
+        Ok(())
+    }
+
+    /// Remove an escrow annotation from the launch's annotations account
+    pub fn remove_annotation(
+        ctx: Context<AnnotateLaunch>,
+        key: String,
+    ) -> Result<()> {
+        // Only escrow can annotate
+        require!(
+            ctx.accounts.escrow_authority.key() == ctx.accounts.config.escrow_authority,
+            TokenLaunchError::UnauthorizedAnnotation
+        );
+
+        let annotations = &mut ctx.accounts.annotations;
+        let before = annotations.entries.len();
+        annotations.entries.retain(|entry| entry.key != key);
+        require!(
+            annotations.entries.len() < before,
+            TokenLaunchError::AnnotationNotFound
+        );
+
+        msg!("Launch annotation removed: {}", key);
+        Ok(())
+    }
+
+    /// Initialize a new launch copying policy fields from an existing launch:
+    /// the accounts of `initialize_launch`, followed by the source launch.
+    /// KYC, referral, bootstrap guard and recovery accounts are handled as
+    /// for any other launch.
+    pub fn clone_launch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeLaunch<'info>>,
+        source_mint: Pubkey,
+        overrides: LaunchOverrides,
+    ) -> Result<()> {
+        let source_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(TokenLaunchError::SourceLaunchRequired)?;
+        require_keys_eq!(
+            source_info.key(),
+            seeds::find_launch(&source_mint).0,
+            TokenLaunchError::SourceLaunchRequired
+        );
+        let source = AccountLoader::<TokenLaunch>::try_from(source_info)?;
+        let source = source.load()?;
+
+        // Only healthy launches can serve as a template
+        require!(source.is_active(), TokenLaunchError::LaunchInactive);
+        require!(!source.is_high_risk(), TokenLaunchError::HighRiskTemplate);
+
+        let recovery_accounts = overrides.recovery_accounts.clone();
+        let params = source.clone_params(overrides);
+        drop(source);
+        process_initialize_launch(ctx, params, recovery_accounts)?;
+
+        msg!("Token launch cloned from {}", source_mint);
+        Ok(())
+    }
+
+    /// Store reusable launch terms (timelock, insurance, anti-whale limits
+    /// and fees) under the signer, for `initialize_from_template`
+    pub fn create_template(
+        ctx: Context<CreateLaunchTemplate>,
+        template_id: u64,
+        params: TemplateParams,
+    ) -> Result<()> {
+        let template = &mut ctx.accounts.template;
+        template.owner = ctx.accounts.owner.key();
+        template.template_id = template_id;
+        template.bump = ctx.bumps.template;
+        template.set(params, Clock::get()?.unix_timestamp)?;
+
+        emit!(LaunchTemplateUpdated {
+            template: template.key(),
+            owner: template.owner,
+            template_id,
+            version: template.version,
+        });
+        msg!("Launch template {} created", template_id);
+        Ok(())
+    }
+
+    /// Replace a template's terms and bump its version. Launches already
+    /// created from it keep the terms they were created with.
+    pub fn update_template(ctx: Context<UpdateLaunchTemplate>, params: TemplateParams) -> Result<()> {
+        let template = &mut ctx.accounts.template;
+        template.set(params, Clock::get()?.unix_timestamp)?;
+
+        emit!(LaunchTemplateUpdated {
+            template: template.key(),
+            owner: template.owner,
+            template_id: template.template_id,
+            version: template.version,
+        });
+        msg!("Launch template {} updated to version {}", template.template_id, template.version);
+        Ok(())
+    }
+
+    /// Initialize a launch from a template: the accounts of
+    /// `initialize_launch`, followed by the template. `params.template_version`
+    /// must match, so an update between signing and landing fails the launch
+    /// instead of changing its terms.
+    pub fn initialize_from_template<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeLaunch<'info>>,
+        params: TemplateLaunchParams,
+    ) -> Result<()> {
+        let template_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(TokenLaunchError::TemplateRequired)?;
+        let template = Account::<LaunchTemplate>::try_from(template_info)?;
+        require!(
+            template.version == params.template_version,
+            TokenLaunchError::TemplateVersionMismatch
+        );
+
+        let token_mint = ctx.accounts.token_mint.key();
+        let recovery_accounts = template.params.recovery_accounts.clone();
+        process_initialize_launch(ctx, template.launch_params(params), recovery_accounts)?;
+
+        emit!(LaunchCreatedFromTemplate {
+            token_mint,
+            template: template.key(),
+            template_version: template.version,
+        });
+        msg!("Launch created from template {} v{}", template.template_id, template.version);
+        Ok(())
+    }
+
+    /// Propose an emergency withdrawal (insurance wallets only)
+    /// The proposer's approval is recorded automatically
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        amount: u64,
+        justification: String,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let proposer = ctx.accounts.proposer.key();
+        let clock = Clock::get()?;
+
+        // Verify caller is authorized insurance wallet
+        require!(
+            launch.insurance_wallets().contains(&proposer),
+            TokenLaunchError::UnauthorizedInsurance
+        );
+        require!(
+            justification.len() <= MAX_JUSTIFICATION_LEN,
+            TokenLaunchError::JustificationTooLong
+        );
+        ctx.accounts.insurance_collateral.check_staked()?;
+        launch.check_recovery_account(&ctx.accounts.to_token_account.key())?;
+        launch.check_insurance_withdrawal(amount)?;
+
+        proposal.launch = ctx.accounts.token_launch.key();
+        proposal.proposal_id = launch.proposal_count;
+        proposal.proposer = proposer;
+        proposal.amount = amount;
+        proposal.destination = ctx.accounts.to_token_account.key();
+        proposal.justification = justification;
+        proposal.approvals = vec![proposer];
+        proposal.created_at = clock.unix_timestamp;
+        proposal.expires_at = math::add_secs(clock.unix_timestamp, PROPOSAL_EXPIRY)?;
+        proposal.executed = false;
+
+        launch.proposal_count = math::add(launch.proposal_count, 1)?;
+
+        emit!(InsuranceProposalCreated {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id: proposal.proposal_id,
+            proposer,
+            amount,
+            expires_at: proposal.expires_at,
+        });
+
+        msg!("Withdrawal proposal #{}: {} tokens", proposal.proposal_id, amount);
+        msg!("Justification: {}", proposal.justification);
+        msg!("Approvals: {}/{}", proposal.approvals.len(), launch.required_approvals());
+
+        Ok(())
+    }
+
+    /// Approve a pending emergency withdrawal proposal (insurance wallets only)
+    pub fn approve_withdrawal(
+        ctx: Context<ApproveWithdrawal>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let approver = ctx.accounts.approver.key();
+        let clock = Clock::get()?;
+
+        require!(
+            launch.insurance_wallets().contains(&approver),
+            TokenLaunchError::UnauthorizedInsurance
+        );
+        ctx.accounts.insurance_collateral.check_staked()?;
+        require!(!proposal.executed, TokenLaunchError::ProposalAlreadyExecuted);
+        require!(
+            clock.unix_timestamp < proposal.expires_at,
+            TokenLaunchError::ProposalExpired
+        );
+        require!(
+            !proposal.approvals.contains(&approver),
+            TokenLaunchError::AlreadyApproved
+        );
+
+        proposal.approvals.push(approver);
+
+        emit!(InsuranceProposalApproved {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id: proposal.proposal_id,
+            approver,
+            approvals: proposal.approvals.len() as u8,
+        });
+
+        msg!("Withdrawal proposal #{} approved by {}", proposal.proposal_id, approver);
+        msg!("Approvals: {}/{}", proposal.approvals.len(), launch.required_approvals());
+
+        Ok(())
+    }
+
+    /// Execute an emergency withdrawal proposal once the M-of-N threshold is met
+    pub fn execute_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteWithdrawal<'info>>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        let mut launch = ctx.accounts.token_launch.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(
+            launch.insurance_wallets().contains(&ctx.accounts.authority.key()),
+            TokenLaunchError::UnauthorizedInsurance
+        );
+        require!(!proposal.executed, TokenLaunchError::ProposalAlreadyExecuted);
+        require!(
+            clock.unix_timestamp < proposal.expires_at,
+            TokenLaunchError::ProposalExpired
+        );
+
+        // Approvals from wallets removed since proposing no longer count
+        let valid_approvals = proposal
+            .approvals
+            .iter()
+            .filter(|wallet| launch.insurance_wallets().contains(wallet))
+            .count();
+        require!(
+            valid_approvals >= launch.required_approvals(),
+            TokenLaunchError::InsufficientApprovals
+        );
+
+        // The destination may have been deregistered since proposing
+        launch.check_recovery_account(&proposal.destination)?;
+        let amount = proposal.amount;
+        let max_withdraw = launch.check_insurance_withdrawal(amount)?;
+
+        // The proposer's per-wallet cap and cooldown apply
+        let wallet_record = &mut ctx.accounts.wallet_record;
+        wallet_record.launch = ctx.accounts.token_launch.key();
+        wallet_record.wallet = proposal.proposer;
+        wallet_record.record_withdrawal(amount, launch.per_wallet_insurance_cap(), clock.unix_timestamp)?;
+
+        // Collect higher fee for emergency withdrawals
+        let emergency_fee = ctx.accounts.config.fees_for(&launch)?.emergency_fee;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, emergency_fee)?;
+        ctx.accounts.treasury.record_fee(emergency_fee)?;
+        launch.fees_collected = math::add(launch.fees_collected, emergency_fee)?;
+
+        // Execute emergency withdrawal from the vault, signed by the launch PDA.
+        // The launch signs the transfer, so it cannot stay mutably borrowed.
+        let mint_key = launch.token_mint;
+        drop(launch);
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::LAUNCH, mint_key.as_ref(), &bump]];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.to_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        let mut launch = ctx.accounts.token_launch.load_mut()?;
+
+        // Update withdrawal tracking
+        launch.total_withdrawn = math::add(launch.total_withdrawn, amount)?;
+        proposal.executed = true;
+        ctx.accounts.withdrawal_record.set_inner(WithdrawalRecord {
+            launch: ctx.accounts.token_launch.key(),
+            seq: launch.next_withdrawal_record_seq()?,
+            wallet: proposal.proposer,
+            amount,
+            destination: proposal.destination,
+            withdrawn_at: clock.unix_timestamp,
+            proposal_id: Some(proposal.proposal_id),
+            justification: proposal.justification.clone(),
+            bump: ctx.bumps.withdrawal_record,
+        });
+
+        emit!(InsuranceProposalExecuted {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id: proposal.proposal_id,
+            amount,
+            destination: proposal.destination,
+        });
+
+        msg!("Emergency withdrawal #{} executed: {} tokens", proposal.proposal_id, amount);
+        msg!("Justification: {}", proposal.justification);
+        msg!("Total withdrawn: {}/{}", launch.total_withdrawn, max_withdraw);
+
+        Ok(())
+    }
+
+    /// Stake SOL collateral into the launch's insurance pool (insurance
+    /// wallets only). A wallet must hold at least MIN_INSURANCE_COLLATERAL
+    /// before it can withdraw, propose or approve; the stake is slashable
+    /// if arbitration rules one of its withdrawals abusive and is locked
+    /// until the launch is closed.
+    pub fn stake_insurance_collateral(ctx: Context<StakeInsuranceCollateral>, amount: u64) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let wallet = ctx.accounts.wallet.key();
+        require!(
+            launch.insurance_wallets().contains(&wallet),
+            TokenLaunchError::UnauthorizedInsurance
+        );
+        require!(amount > 0, TokenLaunchError::InvalidCollateralAmount);
+
+        let pool = &mut ctx.accounts.collateral_pool;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.wallet.to_account_info(),
+                to: pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let collateral = &mut ctx.accounts.insurance_collateral;
+        collateral.launch = ctx.accounts.token_launch.key();
+        collateral.wallet = wallet;
+        collateral.amount = math::add(collateral.amount, amount)?;
+        collateral.staked_at = Clock::get()?.unix_timestamp;
+        collateral.bump = ctx.bumps.insurance_collateral;
+
+        pool.launch = ctx.accounts.token_launch.key();
+        pool.total_staked = math::add(pool.total_staked, amount)?;
+        pool.bump = ctx.bumps.collateral_pool;
+
+        emit!(InsuranceCollateralStaked {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            wallet,
+            amount,
+            total_staked: pool.total_staked,
+        });
+
+        msg!("Insurance collateral staked: {} lamports ({} for this wallet)", amount, collateral.amount);
+        Ok(())
+    }
+
+    /// Slash the collateral of the insurance wallet behind withdrawal
+    /// `withdrawal_seq` into the treasury, once it is ruled abusive. The
+    /// escrow authority rules alone until the config has an arbiter panel;
+    /// then a quorum-approved proposal for this withdrawal is required.
+    pub fn slash_insurance_collateral(
+        ctx: Context<SlashInsuranceCollateral>,
+        withdrawal_seq: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        ctx.accounts.config.check_escrow(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.arbitration.as_deref(),
+            &ctx.accounts.token_launch.key(),
+            &ArbitrationAction::SlashInsuranceCollateral { withdrawal_seq },
+            Clock::get()?.unix_timestamp,
+            TokenLaunchError::UnauthorizedArbiter,
+        )?;
+        if let Some(arbitration) = &mut ctx.accounts.arbitration {
+            arbitration.executed = true;
+        }
+
+        let collateral = &mut ctx.accounts.insurance_collateral;
+        let amount = collateral.amount;
+        require!(amount > 0, TokenLaunchError::NoInsuranceCollateral);
+        let pool = &mut ctx.accounts.collateral_pool;
+        pool.slash(amount)?;
+        collateral.amount = 0;
+
+        pool.sub_lamports(amount)?;
+        ctx.accounts.treasury.add_lamports(amount)?;
+        ctx.accounts.treasury.record_fee(amount)?;
+        launch.fees_collected = math::add(launch.fees_collected, amount)?;
+
+        emit!(InsuranceCollateralSlashed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            wallet: collateral.wallet,
+            withdrawal_seq,
+            amount,
+        });
+
+        msg!("Slashed {} lamports of insurance collateral from {}", amount, collateral.wallet);
+        Ok(())
+    }
+
+    /// Reclaim insurance collateral once the launch has been closed,
+    /// closing the wallet's collateral account
+    pub fn reclaim_insurance_collateral(ctx: Context<ReclaimInsuranceCollateral>) -> Result<()> {
+        let amount = ctx.accounts.insurance_collateral.amount;
+        let pool = &mut ctx.accounts.collateral_pool;
+        pool.release(amount)?;
+        pool.sub_lamports(amount)?;
+        ctx.accounts.wallet.add_lamports(amount)?;
+
+        msg!("Insurance collateral of {} lamports reclaimed", amount);
+        Ok(())
+    }
+
+    /// Idempotently create the associated token account for a transfer or
+    /// claim destination so recipients without an ATA don't fail opaquely
+    pub fn prepare_recipient(ctx: Context<PrepareRecipient>) -> Result<()> {
+        msg!(
+            "Recipient token account ready: {} (owner {})",
+            ctx.accounts.recipient_token_account.key(),
+            ctx.accounts.recipient.key()
+        );
+        Ok(())
+    }
+
+    /// Create a Token-2022 mint with the transfer-fee extension and the SolD
+    /// transfer hook program registered (enforces the timelock at the SPL level).
+    /// The launch PDA holds the fee authorities and the hook cannot be changed.
+    pub fn create_token2022_mint(
+        ctx: Context<CreateToken2022Mint>,
+        decimals: u8,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<()> {
+        require!(
+            transfer_fee_basis_points <= MAX_TRANSFER_FEE_BPS,
+            TokenLaunchError::TransferFeeTooHigh
+        );
+        validation::decimals(decimals)?;
+
+        let creator = ctx.accounts.creator.key();
+        let mint = ctx.accounts.token_mint.to_account_info();
+        let token_program_id = ctx.accounts.token_program.key();
+
+        // Allocate the mint with room for both extensions
+        let extensions = [ExtensionType::TransferFeeConfig, ExtensionType::TransferHook];
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+        let lamports = Rent::get()?.minimum_balance(space);
+        anchor_lang::system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            lamports,
+            space as u64,
+            &token_program_id,
+        )?;
+
+        // Extensions must be initialized before the mint itself
+        for ix in token2022_extension_instructions(&token_program_id, &mint.key(), transfer_fee_basis_points, maximum_fee)? {
+            invoke(&ix, std::slice::from_ref(&mint))?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::InitializeMint2 { mint },
+            ),
+            decimals,
+            &creator,
+            None,
+        )?;
+
+        msg!("Token-2022 mint created: {}", ctx.accounts.token_mint.key());
+        msg!("Transfer fee: {} bps (max {})", transfer_fee_basis_points, maximum_fee);
+        Ok(())
+    }
+
+    /// Wrap SOL held by the launch's SOL vault into its wSOL token account
+    /// so DEX CPIs that require wSOL can use it (creator only)
+    pub fn wrap_sol(ctx: Context<WrapSol>, amount: u64) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+
+        let vault_bump = [ctx.bumps.sol_vault];
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[seeds::SOL_VAULT, launch.token_mint.as_ref(), &vault_bump]];
+        wrap_sol_into(
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.wsol_vault.to_account_info(),
+            sol_vault_seeds,
+            amount,
+        )?;
+
+        msg!("Wrapped {} lamports into {}", amount, ctx.accounts.wsol_vault.key());
+        Ok(())
+    }
+
+    /// Unwrap the launch's wSOL token account back into its SOL vault,
+    /// closing the token account (creator only)
+    pub fn unwrap_sol(ctx: Context<UnwrapSol>) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+
+        let amount = ctx.accounts.wsol_vault.amount;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        unwrap_sol_into(
+            &ctx.accounts.token_program,
+            ctx.accounts.wsol_vault.to_account_info(),
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.token_launch.to_account_info(),
+            signer_seeds,
+        )?;
+
+        msg!("Unwrapped {} lamports into SOL vault", amount);
+        Ok(())
+    }
+
+    /// Nominate a new creator; takes effect once they accept (creator only).
+    /// Proposing again replaces any earlier nomination.
+    pub fn propose_creator_transfer(
+        ctx: Context<ProposeCreatorTransfer>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require_keys_neq!(new_creator, launch.creator, TokenLaunchError::InvalidNewCreator);
+        require_keys_neq!(new_creator, Pubkey::default(), TokenLaunchError::InvalidNewCreator);
+
+        launch.set_pending_creator(Some(new_creator))?;
+
+        emit!(CreatorshipTransferProposed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            creator: launch.creator,
+            pending_creator: new_creator,
+        });
+
+        msg!("Creatorship transfer proposed: {} -> {}", launch.creator, new_creator);
+        Ok(())
+    }
+
+    /// Withdraw a pending creator nomination (creator only)
+    pub fn cancel_creator_transfer(ctx: Context<CancelCreatorTransfer>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        let pending_creator = launch
+            .pending_creator()
+            .ok_or(TokenLaunchError::NoPendingCreator)?;
+        launch.set_pending_creator(None)?;
+
+        emit!(CreatorshipTransferCancelled {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            creator: launch.creator,
+            pending_creator,
+        });
+
+        msg!("Creatorship transfer to {} cancelled", pending_creator);
+        Ok(())
+    }
+
+    /// Accept a pending creatorship transfer, moving the launch between creator indexes.
+    /// A multisig vault accepting creatorship declares its Squads multisig here.
+    /// Every creator-gated instruction checks `launch.creator`, so the old key
+    /// loses its powers in the same transaction.
+    pub fn accept_creator_transfer(
+        ctx: Context<AcceptCreatorTransfer>,
+        creator_multisig: Option<CreatorMultisig>,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let new_creator = ctx.accounts.new_creator.key();
+
+        if let Some(multisig) = &creator_multisig {
+            require_keys_eq!(
+                new_creator,
+                multisig.vault_address(),
+                TokenLaunchError::InvalidCreatorMultisig
+            );
+        }
+
+        let old_creator = launch.creator;
+        ctx.accounts.old_creator_index.remove_launch(&launch.token_mint);
+        ctx.accounts.new_creator_index.add_launch(new_creator, launch.token_mint)?;
+
+        launch.creator = new_creator;
+        launch.set_creator_multisig(creator_multisig)?;
+        launch.set_pending_creator(None)?;
+
+        emit!(CreatorshipTransferred {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            old_creator,
+            new_creator,
+        });
+
+        msg!("Creatorship transferred: {} -> {}", old_creator, new_creator);
+        Ok(())
+    }
+
+    /// `propose_creator_transfer` under its original name
+    pub fn transfer_creatorship(
+        ctx: Context<ProposeCreatorTransfer>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        propose_creator_transfer(ctx, new_creator)
+    }
+
+    /// `accept_creator_transfer` under its original name
+    pub fn accept_creatorship(
+        ctx: Context<AcceptCreatorTransfer>,
+        creator_multisig: Option<CreatorMultisig>,
+    ) -> Result<()> {
+        accept_creator_transfer(ctx, creator_multisig)
+    }
+
+    /// Open a governance proposal to change launch parameters.
+    /// The creator, insurance wallets, and holders of 1% of supply may propose.
+    pub fn create_governance_proposal(
+        ctx: Context<CreateGovernanceProposal>,
+        action: GovernanceAction,
+        description: String,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let proposer = ctx.accounts.proposer.key();
+        let clock = Clock::get()?;
+
+        let holds_threshold = ctx.accounts.proposer_token_account.owner == proposer
+            && ctx.accounts.proposer_token_account.amount
+                >= math::pct(launch.total_supply, GOVERNANCE_PROPOSER_PCT)?;
+        require!(
+            proposer == launch.creator
+                || launch.insurance_wallets().contains(&proposer)
+                || holds_threshold,
+            TokenLaunchError::UnauthorizedProposer
+        );
+        require!(
+            description.len() <= MAX_PROPOSAL_DESCRIPTION_LEN,
+            TokenLaunchError::DescriptionTooLong
+        );
+        launch.validate_governance_action(&action)?;
+
+        proposal.launch = ctx.accounts.token_launch.key();
+        proposal.proposal_id = launch.governance_proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.description = description;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.voting_ends_at = math::add_secs(clock.unix_timestamp, GOVERNANCE_VOTING_PERIOD)?;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        launch.governance_proposal_count = math::add(launch.governance_proposal_count, 1)?;
+
+        emit!(ConfigChangeQueued {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id: proposal.proposal_id,
+            proposer,
+            action: proposal.action.clone(),
+            voting_ends_at: proposal.voting_ends_at,
+        });
+
+        msg!("Governance proposal #{} opened by {}", proposal.proposal_id, proposer);
+        msg!("Voting ends: {}", proposal.voting_ends_at);
+        Ok(())
+    }
+
+    /// Cast a token-weighted vote. Voting tokens are escrowed with the
+    /// proposal until voting ends so they cannot be reused to vote again.
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        _proposal_id: u64,
+        support: bool,
+        amount: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(amount > 0, TokenLaunchError::InvalidVoteAmount);
+        require!(
+            clock.unix_timestamp < ctx.accounts.proposal.voting_ends_at,
+            TokenLaunchError::VotingClosed
+        );
+
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.vote_escrow.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds: &[],
+            },
+            amount,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        if support {
+            proposal.yes_votes = math::add(proposal.yes_votes, amount)?;
+        } else {
+            proposal.no_votes = math::add(proposal.no_votes, amount)?;
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.support = support;
+        vote_record.amount = amount;
+        vote_record.withdrawn = false;
+
+        msg!("Vote on proposal #{}: {} with {} tokens", proposal.proposal_id, support, amount);
+        msg!("Tally: {} yes / {} no", proposal.yes_votes, proposal.no_votes);
+        Ok(())
+    }
+
+    /// Execute a governance proposal that passed quorum and majority (anyone)
+    pub fn execute_governance_proposal(
+        ctx: Context<ExecuteGovernanceProposal>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+
+        require!(!proposal.executed, TokenLaunchError::ProposalAlreadyExecuted);
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at,
+            TokenLaunchError::VotingOpen
+        );
+        require!(
+            proposal.has_passed(launch.total_supply),
+            TokenLaunchError::ProposalNotPassed
+        );
+
+        // Re-validate against current state, which may have changed since proposing
+        launch.validate_governance_action(&proposal.action)?;
+        launch.apply_governance_action(&proposal.action)?;
+        proposal.executed = true;
+
+        emit!(ConfigChangeExecuted {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id: proposal.proposal_id,
+            action: proposal.action.clone(),
+        });
+        if proposal.action == GovernanceAction::ReactivateLaunch {
+            launch.emit_active_change("reactivated by governance".to_string())?;
+        }
+
+        msg!("Governance proposal #{} executed", proposal.proposal_id);
+        Ok(())
+    }
+
+    /// Return escrowed voting tokens once voting has ended
+    pub fn withdraw_vote_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawVoteTokens<'info>>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let vote_record = &mut ctx.accounts.vote_record;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at,
+            TokenLaunchError::VotingOpen
+        );
+        require!(!vote_record.withdrawn, TokenLaunchError::VoteAlreadyWithdrawn);
+
+        let proposal_id = proposal.proposal_id.to_le_bytes();
+        let bump = [proposal.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            seeds::GOVERNANCE_PROPOSAL,
+            proposal.launch.as_ref(),
+            &proposal_id,
+            &bump,
+        ]];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vote_escrow.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: proposal.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            vote_record.amount,
+        )?;
+        vote_record.withdrawn = true;
+
+        msg!("Returned {} voting tokens to {}", vote_record.amount, vote_record.voter);
+        Ok(())
+    }
+
+    /// Buy a presale allocation with SOL. `allocation` is the buyer's
+    /// whitelisted cap, proven against the launch's Merkle root.
+    pub fn presale_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, PresaleBuy<'info>>,
+        amount: u64,
+        allocation: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let mut launch = ctx.accounts.token_launch.load_mut()?;
+        let buyer = ctx.accounts.buyer.key();
+        let clock = Clock::get()?;
+
+        let presale = launch.presale().ok_or(TokenLaunchError::PresaleNotConfigured)?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            !launch.presale_finalized() && clock.unix_timestamp < presale.end_time,
+            TokenLaunchError::PresaleClosed
+        );
+        require!(amount > 0, TokenLaunchError::InvalidPurchaseAmount);
+        require!(
+            verify_merkle_proof(&proof, presale.merkle_root, presale_leaf(&buyer, allocation)),
+            TokenLaunchError::InvalidMerkleProof
+        );
+
+        // Per-address and overall caps
+        let purchase = &mut ctx.accounts.purchase;
+        require!(
+            math::add(purchase.purchased, amount)? <= allocation,
+            TokenLaunchError::PresaleCapExceeded
+        );
+        require!(
+            math::add(launch.presale_sold, amount)? <= presale.allocation,
+            TokenLaunchError::PresaleCapExceeded
+        );
+
+        // Pay the fixed price into the launch's SOL vault
+        let cost = presale.cost(amount, ctx.accounts.token_mint.decimals)?;
+        let opens_holder = ctx.accounts.buyer_token_account.amount == 0;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+
+        // Record the sale before the launch signs the vault transfer, which
+        // needs it released
+        launch.presale_sold = math::add(launch.presale_sold, amount)?;
+        let presale_sold = launch.presale_sold;
+        let mint_key = launch.token_mint;
+        drop(launch);
+
+        // Deliver tokens from the vault, signed by the launch PDA
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::LAUNCH, mint_key.as_ref(), &bump]];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        purchase.launch = ctx.accounts.token_launch.key();
+        purchase.buyer = buyer;
+        purchase.purchased = math::add(purchase.purchased, amount)?;
+        purchase.lamports_paid = math::add(purchase.lamports_paid, cost)?;
+
+        let stats = &mut ctx.accounts.launch_stats;
+        stats.launch = ctx.accounts.token_launch.key();
+        stats.shard = SALE_STATS_SHARD;
+        stats.bump = ctx.bumps.launch_stats;
+        stats.record_sale(amount, cost, opens_holder, false, clock.unix_timestamp)?;
+
+        msg!("Presale purchase: {} tokens for {} lamports", amount, cost);
+        msg!("Presale sold: {}/{}", presale_sold, presale.allocation);
+        Ok(())
+    }
+
+    /// Close the presale once it has ended or sold out (creator only)
+    pub fn finalize_presale(ctx: Context<FinalizePresale>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let clock = Clock::get()?;
+
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        let presale = launch.presale().ok_or(TokenLaunchError::PresaleNotConfigured)?;
+        require!(!launch.presale_finalized(), TokenLaunchError::PresaleClosed);
+        require!(
+            clock.unix_timestamp >= presale.end_time || launch.presale_sold == presale.allocation,
+            TokenLaunchError::PresaleStillOpen
+        );
+
+        launch.set_presale_finalized(true);
+
+        msg!("Presale finalized: {}/{} tokens sold", launch.presale_sold, presale.allocation);
+        msg!("Proceeds held in SOL vault: {} lamports", ctx.accounts.sol_vault.lamports());
+        Ok(())
+    }
+
+    /// Open a bonding curve sale from the launch vault (creator only).
+    /// The allocation moves into the curve's own token vault so no other
+    /// vault path can spend it; the creator seeds the SOL reserve with its
+    /// rent-exempt minimum.
+    pub fn init_bonding_curve<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitBondingCurve<'info>>,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+        token_allocation: u64,
+        graduation_threshold: u64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            virtual_sol_reserves > 0
+                && virtual_token_reserves >= token_allocation
+                && token_allocation > 0
+                && token_allocation <= ctx.accounts.vault.amount
+                && graduation_threshold > 0,
+            TokenLaunchError::InvalidBondingCurve
+        );
+
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let reserve_lamports = ctx.accounts.sol_reserve.lamports();
+        if reserve_lamports < rent_exempt {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.sol_reserve.to_account_info(),
+                    },
+                ),
+                rent_exempt - reserve_lamports,
+            )?;
+        }
+
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.curve_vault.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            token_allocation,
+        )?;
+        // Sell only what arrived; a transfer fee may have withheld part of it
+        ctx.accounts.curve_vault.reload()?;
+        let for_sale = ctx.accounts.curve_vault.amount;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        let curve = &mut ctx.accounts.bonding_curve;
+        curve.launch = launch_key;
+        curve.virtual_sol_reserves = virtual_sol_reserves;
+        curve.virtual_token_reserves = virtual_token_reserves;
+        curve.real_sol_reserves = 0;
+        curve.real_token_reserves = for_sale;
+        curve.graduation_threshold = graduation_threshold;
+        curve.graduated = false;
+
+        msg!("Bonding curve opened: {} tokens for sale", for_sale);
+        msg!("Graduates at {} lamports", graduation_threshold);
+        Ok(())
+    }
+
+    /// Buy tokens from the bonding curve with slippage protection
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, CurveTrade<'info>>,
+        sol_in: u64,
+        min_tokens_out: u64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let curve = &mut ctx.accounts.bonding_curve;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(!curve.graduated, TokenLaunchError::CurveGraduated);
+
+        let tokens_out = curve.apply_buy(sol_in)?;
+        require!(tokens_out >= min_tokens_out, TokenLaunchError::SlippageExceeded);
+        let opens_holder = ctx.accounts.trader_token_account.amount == 0;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.trader.to_account_info(),
+                    to: ctx.accounts.sol_reserve.to_account_info(),
+                },
+            ),
+            sol_in,
+        )?;
+
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.curve_vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.trader_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            tokens_out,
+        )?;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        ctx.accounts.launch_stats.record_curve_trade(
+            launch_key,
+            ctx.bumps.launch_stats,
+            tokens_out,
+            sol_in,
+            opens_holder,
+            false,
+        )?;
+
+        msg!("Curve buy: {} lamports -> {} tokens", sol_in, tokens_out);
+        if curve.graduated {
+            msg!("Bonding curve graduated at {} lamports", curve.real_sol_reserves);
+        }
+        Ok(())
+    }
+
+    /// Sell tokens back into the bonding curve with slippage protection
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, CurveTrade<'info>>,
+        tokens_in: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let curve = &mut ctx.accounts.bonding_curve;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(!curve.graduated, TokenLaunchError::CurveGraduated);
+
+        let sol_out = curve.apply_sell(tokens_in)?;
+        require!(sol_out >= min_sol_out, TokenLaunchError::SlippageExceeded);
+        let closes_holder = ctx.accounts.trader_token_account.amount == tokens_in;
+
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.trader_token_account.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.curve_vault.to_account_info(),
+                authority: ctx.accounts.trader.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds: &[],
+            },
+            tokens_in,
+        )?;
+
+        let mint_key = ctx.accounts.token_mint.key();
+        let reserve_bump = [ctx.bumps.sol_reserve];
+        let reserve_seeds: &[&[&[u8]]] = &[&[seeds::CURVE_RESERVE, mint_key.as_ref(), &reserve_bump]];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.sol_reserve.to_account_info(),
+                    to: ctx.accounts.trader.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            sol_out,
+        )?;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        ctx.accounts.launch_stats.record_curve_trade(
+            launch_key,
+            ctx.bumps.launch_stats,
+            tokens_in,
+            sol_out,
+            false,
+            closes_holder,
+        )?;
+
+        msg!("Curve sell: {} tokens -> {} lamports", tokens_in, sol_out);
+        Ok(())
+    }
+
+    /// Migrate a graduated curve (permissionless). The SOL reserve above its
+    /// rent-exempt minimum moves to the launch SOL vault and the unsold
+    /// tokens return to the launch vault, where `create_and_lock_liquidity`
+    /// seeds the pool from them.
+    pub fn graduate_bonding_curve<'info>(
+        ctx: Context<'_, '_, '_, 'info, GraduateBondingCurve<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.bonding_curve.migrate()?;
+
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let lamports = ctx.accounts.sol_reserve.lamports().saturating_sub(rent_exempt);
+        let tokens = ctx.accounts.curve_vault.amount;
+
+        let mint_key = ctx.accounts.token_mint.key();
+        let reserve_bump = [ctx.bumps.sol_reserve];
+        let reserve_seeds: &[&[&[u8]]] = &[&[seeds::CURVE_RESERVE, mint_key.as_ref(), &reserve_bump]];
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.sol_reserve.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            lamports,
+        )?;
+
+        if tokens > 0 {
+            let launch = &ctx.accounts.token_launch.load()?;
+            let bump = [ctx.bumps.token_launch];
+            let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+            transfer_launch_tokens(
+                LaunchTokenTransfer {
+                    token_program: &ctx.accounts.token_program,
+                    from: ctx.accounts.curve_vault.to_account_info(),
+                    mint: &ctx.accounts.token_mint,
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                    additional_accounts: ctx.remaining_accounts,
+                    signer_seeds,
+                },
+                tokens,
+            )?;
+        }
+
+        msg!("Bonding curve migrated: {} lamports, {} tokens", lamports, tokens);
+        Ok(())
+    }
+
+    /// Seed an AMM pool from the launch vault and SOL vault, then lock the
+    /// LP tokens under the launch PDA until the timelock ends (creator only).
+    /// `amm_ix_data` and the remaining accounts form the AMM deposit/initialize
+    /// instruction; the launch PDA signs as the liquidity provider.
+    pub fn create_and_lock_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateAndLockLiquidity<'info>>,
+        token_amount: u64,
+        sol_amount: u64,
+        amm_ix_data: Vec<u8>,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            is_allowed_amm(&ctx.accounts.amm_program.key()),
+            TokenLaunchError::UnsupportedAmm
+        );
+
+        // Fund the launch's wSOL account for the SOL side of the pool
+        let mint_key = launch.token_mint;
+        let vault_bump = [ctx.bumps.sol_vault];
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[seeds::SOL_VAULT, mint_key.as_ref(), &vault_bump]];
+        wrap_sol_into(
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.wsol_vault.to_account_info(),
+            sol_vault_seeds,
+            sol_amount,
+        )?;
+        ctx.accounts.wsol_vault.reload()?;
+
+        let tokens_before = ctx.accounts.vault.amount;
+        let wsol_before = ctx.accounts.wsol_vault.amount;
+        let lp_before = ctx.accounts.lp_vault.amount;
+
+        // The launch PDA signs the deposit, so the AMM may only see the
+        // launch token accounts whose balances are checked below
+        let launch_key = ctx.accounts.token_launch.key();
+        check_launch_token_accounts(
+            ctx.remaining_accounts,
+            &launch_key,
+            &[ctx.accounts.vault.key(), ctx.accounts.wsol_vault.key(), ctx.accounts.lp_vault.key()],
+        )?;
+
+        // CPI into the AMM with the launch PDA as the depositing authority
+        let instruction = Instruction {
+            program_id: ctx.accounts.amm_program.key(),
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == launch_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: amm_ix_data,
+        };
+        let bump = [ctx.bumps.token_launch];
+        invoke_signed(
+            &instruction,
+            ctx.remaining_accounts,
+            &[&launch.signer_seeds(&bump)],
+        )?;
+
+        // The AMM may take no more than authorized and must mint LP tokens
+        ctx.accounts.vault.reload()?;
+        ctx.accounts.wsol_vault.reload()?;
+        ctx.accounts.lp_vault.reload()?;
+        let tokens_added = tokens_before.saturating_sub(ctx.accounts.vault.amount);
+        let lamports_added = wsol_before.saturating_sub(ctx.accounts.wsol_vault.amount);
+        let lp_received = ctx.accounts.lp_vault.amount.saturating_sub(lp_before);
+        require!(
+            tokens_added <= token_amount && lamports_added <= sol_amount,
+            TokenLaunchError::LiquidityOverdrawn
+        );
+        require!(lp_received > 0, TokenLaunchError::NoLiquidityMinted);
+
+        let lock = &mut ctx.accounts.liquidity_lock;
+        lock.launch = launch_key;
+        lock.amm_program = ctx.accounts.amm_program.key();
+        lock.lp_mint = ctx.accounts.lp_mint.key();
+        lock.lp_amount = lp_received;
+        lock.tokens_added = tokens_added;
+        lock.lamports_added = lamports_added;
+        lock.locked_at = Clock::get()?.unix_timestamp;
+
+        msg!("Liquidity added: {} tokens + {} lamports", tokens_added, lamports_added);
+        msg!("{} LP tokens locked until {}", lp_received, launch.timelock_end);
+        Ok(())
+    }
+
+    /// Release locked LP tokens to the creator once the timelock has ended
+    pub fn release_liquidity(ctx: Context<ReleaseLiquidity>) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            launch.is_timelock_expired(clock.unix_timestamp),
+            TokenLaunchError::TimelockActive
+        );
+
+        let amount = ctx.accounts.lp_vault.amount;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.lp_vault.to_account_info(),
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.creator_lp_account.to_account_info(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.lp_mint.decimals,
+        )?;
+        ctx.accounts.liquidity_lock.lp_amount = 0;
+
+        msg!("Released {} LP tokens to creator", amount);
+        Ok(())
+    }
+
+    /// Create the global config (program upgrade authority only)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        params: ConfigParams,
+        cluster: Cluster,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+        config.cluster = cluster;
+        config.apply(params)?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.bump = ctx.bumps.treasury;
+
+        msg!("Global config initialized by {} for {:?}", config.admin, config.cluster);
+        msg!("Fee recipient: {}", config.fee_recipient);
+        Ok(())
+    }
+
+    /// Update fee recipients, escrow authority, fees and treasury split (admin only)
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        params: ConfigParams,
+        new_admin: Option<Pubkey>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.apply(params)?;
+        if let Some(new_admin) = new_admin {
+            config.admin = new_admin;
+        }
+
+        msg!("Global config updated; admin: {}", config.admin);
+        Ok(())
+    }
+
+    /// Distribute a launch's undistributed fees from the treasury using the
+    /// configured protocol / insurance pool / creator rebate split (admin only).
+    /// With weighted fee recipients configured, the protocol share is split
+    /// across them, passed as writable remaining accounts in config order.
+    pub fn withdraw_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTreasury<'info>>,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let config = &ctx.accounts.config;
+
+        let amount = math::sub(launch.fees_collected, launch.fees_distributed)?;
+        require!(amount > 0, TokenLaunchError::NothingToDistribute);
+        let (protocol, insurance_pool, creator_rebate) = config.treasury_split.split(amount);
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.sub_lamports(amount)?;
+        if config.fee_recipients.is_empty() {
+            ctx.accounts.fee_recipient.add_lamports(protocol)?;
+        } else {
+            let shares = config.protocol_shares(protocol);
+            require!(
+                ctx.remaining_accounts.len() == shares.len(),
+                TokenLaunchError::FeeRecipientMismatch
+            );
+            for ((wallet, share), account) in shares.iter().zip(ctx.remaining_accounts) {
+                require_keys_eq!(account.key(), *wallet, TokenLaunchError::FeeRecipientMismatch);
+                require!(account.is_writable, TokenLaunchError::FeeRecipientMismatch);
+                account.add_lamports(*share)?;
+                msg!("Fee recipient {}: {} lamports", wallet, share);
+            }
+        }
+        ctx.accounts.insurance_pool.add_lamports(insurance_pool)?;
+        ctx.accounts.creator.add_lamports(creator_rebate)?;
+        treasury.total_distributed = math::add(treasury.total_distributed, amount)?;
+        launch.fees_distributed = math::add(launch.fees_distributed, amount)?;
+
+        msg!("Treasury distributed {} lamports for {}", amount, launch.token_mint);
+        msg!(
+            "Protocol: {}, insurance pool: {}, creator rebate: {}",
+            protocol,
+            insurance_pool,
+            creator_rebate
+        );
+        Ok(())
+    }
+
+    /// Create the fraud oracle registry with its initial oracle keys (admin only)
+    pub fn initialize_oracle_registry(
+        ctx: Context<InitializeOracleRegistry>,
+        oracles: Vec<Pubkey>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+        registry.bump = ctx.bumps.oracle_registry;
+        for oracle in oracles {
+            registry.add(oracle)?;
+        }
+
+        msg!("Oracle registry initialized with {} oracles", registry.oracles.len());
+        Ok(())
+    }
+
+    /// Authorize a new fraud oracle (admin only)
+    pub fn add_fraud_oracle(ctx: Context<ManageOracles>, oracle: Pubkey) -> Result<()> {
+        ctx.accounts.oracle_registry.add(oracle)?;
+
+        msg!("Fraud oracle added: {}", oracle);
+        Ok(())
+    }
+
+    /// Revoke a fraud oracle (admin only)
+    pub fn remove_fraud_oracle(ctx: Context<ManageOracles>, oracle: Pubkey) -> Result<()> {
+        ctx.accounts.oracle_registry.remove(&oracle)?;
+
+        msg!("Fraud oracle removed: {}", oracle);
+        Ok(())
+    }
+
+    /// Replace an oracle key in place, so there is no window where the
+    /// service is unauthorized or both keys are live (admin only)
+    pub fn rotate_fraud_oracle(
+        ctx: Context<ManageOracles>,
+        old_oracle: Pubkey,
+        new_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.oracle_registry.rotate(&old_oracle, new_oracle)?;
+
+        msg!("Fraud oracle rotated: {} -> {}", old_oracle, new_oracle);
+        Ok(())
+    }
+
+    /// Deploy idle SOL from the launch's SOL vault into an allowlisted yield
+    /// venue, up to the allowance granted by governance (creator only).
+    /// `venue_ix_data` and the remaining accounts form the venue's deposit
+    /// instruction; the launch PDA and SOL vault sign for it.
+    pub fn deploy_treasury_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeployTreasuryYield<'info>>,
+        amount: u64,
+        venue_ix_data: Vec<u8>,
+    ) -> Result<()> {
+        // The venue call may take the launch account, so it is not left
+        // borrowed across the CPI
+        let mint_key = {
+            let launch = ctx.accounts.token_launch.load()?;
+            launch.verify_creator(&ctx.accounts.creator.key())?;
+            require!(amount <= launch.yield_allowance, TokenLaunchError::YieldAllowanceExceeded);
+            launch.token_mint
+        };
+        let venue = ctx.accounts.venue_program.key();
+        require!(is_allowed_yield_venue(&venue), TokenLaunchError::UnsupportedYieldVenue);
+
+        // One venue per launch until the position is fully unwound
+        let position = &ctx.accounts.yield_position;
+        if position.principal > 0 {
+            require_keys_eq!(position.venue, venue, TokenLaunchError::UnsupportedYieldVenue);
+        }
+
+        let lamports_before = ctx.accounts.sol_vault.lamports();
+        let shares_before = ctx.accounts.yield_vault.amount;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        let sol_vault_key = ctx.accounts.sol_vault.key();
+        // The launch PDA signs; only the share account may be its token account
+        check_launch_token_accounts(ctx.remaining_accounts, &launch_key, &[ctx.accounts.yield_vault.key()])?;
+        let instruction = Instruction {
+            program_id: venue,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer
+                        || account.key() == launch_key
+                        || account.key() == sol_vault_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: venue_ix_data,
+        };
+        let bump = [ctx.bumps.token_launch];
+        let vault_bump = [ctx.bumps.sol_vault];
+        invoke_signed(
+            &instruction,
+            ctx.remaining_accounts,
+            &[
+                &[seeds::LAUNCH, mint_key.as_ref(), &bump],
+                &[seeds::SOL_VAULT, mint_key.as_ref(), &vault_bump],
+            ],
+        )?;
+
+        // The venue may spend no more than authorized and must issue shares
+        ctx.accounts.yield_vault.reload()?;
+        let lamports_spent = lamports_before.saturating_sub(ctx.accounts.sol_vault.lamports());
+        let shares_received = ctx.accounts.yield_vault.amount.saturating_sub(shares_before);
+        require!(lamports_spent <= amount, TokenLaunchError::YieldOverdrawn);
+        require!(shares_received > 0, TokenLaunchError::NoYieldSharesMinted);
+
+        let position = &mut ctx.accounts.yield_position;
+        position.launch = launch_key;
+        position.venue = venue;
+        position.share_mint = ctx.accounts.share_mint.key();
+        position.principal = math::add(position.principal, lamports_spent)?;
+        position.bump = ctx.bumps.yield_position;
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.yield_allowance = math::sub(launch.yield_allowance, lamports_spent)?;
+
+        msg!("Deployed {} lamports to {} for {} shares", lamports_spent, venue, shares_received);
+        Ok(())
+    }
+
+    /// Redeem yield venue shares back into the launch's SOL vault, as
+    /// approved by an executed `UnwindTreasuryYield` governance proposal.
+    /// `venue_ix_data` and the remaining accounts must be the venue call
+    /// the proposal pinned by hash; the proposal is closed once used.
+    pub fn unwind_treasury_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnwindTreasuryYield<'info>>,
+        _proposal_id: u64,
+        venue_ix_data: Vec<u8>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let GovernanceAction::UnwindTreasuryYield { shares, venue_call_hash } = proposal.action else {
+            return err!(TokenLaunchError::InvalidGovernanceAction);
+        };
+        require!(proposal.executed, TokenLaunchError::ProposalNotPassed);
+        require!(
+            yield_venue_call_hash(ctx.remaining_accounts, &venue_ix_data) == venue_call_hash,
+            TokenLaunchError::VenueCallMismatch
+        );
+
+        // The venue call may take the launch account, so it is not left
+        // borrowed across the CPI
+        let mint_key = ctx.accounts.token_launch.load()?.token_mint;
+        let lamports_before = ctx.accounts.sol_vault.lamports();
+        let shares_before = ctx.accounts.yield_vault.amount;
+        require!(shares > 0 && shares <= shares_before, TokenLaunchError::InvalidYieldShares);
+
+        let launch_key = ctx.accounts.token_launch.key();
+        check_launch_token_accounts(ctx.remaining_accounts, &launch_key, &[ctx.accounts.yield_vault.key()])?;
+        let instruction = Instruction {
+            program_id: ctx.accounts.venue_program.key(),
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == launch_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: venue_ix_data,
+        };
+        let bump = [ctx.bumps.token_launch];
+        invoke_signed(
+            &instruction,
+            ctx.remaining_accounts,
+            &[&[seeds::LAUNCH, mint_key.as_ref(), &bump]],
+        )?;
+
+        // The proceeds must land in the SOL vault and cover the principal
+        // the burned shares stood for
+        ctx.accounts.yield_vault.reload()?;
+        let shares_burned = shares_before.saturating_sub(ctx.accounts.yield_vault.amount);
+        let lamports_received = ctx.accounts.sol_vault.lamports().saturating_sub(lamports_before);
+        require!(shares_burned > 0 && shares_burned <= shares, TokenLaunchError::YieldOverdrawn);
+        require!(
+            lamports_received >= ctx.accounts.yield_position.principal_for(shares_burned, shares_before)?,
+            TokenLaunchError::SlippageExceeded
+        );
+
+        let position = &mut ctx.accounts.yield_position;
+        let earned = position.record_unwind(shares_burned, shares_before, lamports_received)?;
+
+        msg!("Unwound {} shares for {} lamports ({} yield)", shares_burned, lamports_received, earned);
+        msg!("Principal still deployed: {} lamports", position.principal);
+        Ok(())
+    }
+
+    /// Appeal a suspension, staking the appeal bond (creator only).
+    /// The escrow has until the review deadline to rule on it.
+    pub fn file_appeal(ctx: Context<FileAppeal>, reason: String) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(!launch.is_active(), TokenLaunchError::LaunchNotSuspended);
+        require!(
+            reason.len() <= MAX_JUSTIFICATION_LEN,
+            TokenLaunchError::JustificationTooLong
+        );
+
+        let appeal = &mut ctx.accounts.appeal;
+        require!(appeal.can_file(), TokenLaunchError::AppealPending);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: appeal.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, APPEAL_BOND)?;
+
+        let clock = Clock::get()?;
+        appeal.launch = ctx.accounts.token_launch.key();
+        appeal.appellant = ctx.accounts.creator.key();
+        appeal.bond = APPEAL_BOND;
+        appeal.reason = reason;
+        appeal.filed_at = clock.unix_timestamp;
+        appeal.review_deadline = math::add_secs(clock.unix_timestamp, APPEAL_REVIEW_PERIOD)?;
+        appeal.status = AppealStatus::Pending;
+        appeal.arbiter = None;
+        appeal.resolved_at = 0;
+        appeal.bump = ctx.bumps.appeal;
+
+        emit!(AppealFiled {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            appellant: appeal.appellant,
+            bond: appeal.bond,
+            review_deadline: appeal.review_deadline,
+        });
+
+        msg!("Appeal filed; review deadline {}", appeal.review_deadline);
+        Ok(())
+    }
+
+    /// Rule on a pending appeal (escrow only). A rejected appeal forfeits
+    /// the bond to the treasury; an upheld one lets the creator reactivate.
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>, upheld: bool) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.check_escrow(
+            &ctx.accounts.escrow_authority.key(),
+            ctx.accounts.arbitration.as_deref(),
+            &ctx.accounts.token_launch.key(),
+            &ArbitrationAction::ResolveAppeal { upheld },
+            clock.unix_timestamp,
+            TokenLaunchError::UnauthorizedArbiter,
+        )?;
+        if let Some(arbitration) = &mut ctx.accounts.arbitration {
+            arbitration.executed = true;
+        }
+
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let appeal = &mut ctx.accounts.appeal;
+        require!(appeal.status == AppealStatus::Pending, TokenLaunchError::AppealNotPending);
+        require!(
+            clock.unix_timestamp <= appeal.review_deadline,
+            TokenLaunchError::AppealReviewExpired
+        );
+
+        if upheld {
+            appeal.status = AppealStatus::Upheld;
+        } else {
+            // Slash the bond into the treasury as launch fees
+            let bond = appeal.bond;
+            appeal.sub_lamports(bond)?;
+            ctx.accounts.treasury.add_lamports(bond)?;
+            ctx.accounts.treasury.record_fee(bond)?;
+            launch.fees_collected = math::add(launch.fees_collected, bond)?;
+            appeal.bond = 0;
+            appeal.status = AppealStatus::Rejected;
+        }
+        appeal.arbiter = Some(ctx.accounts.escrow_authority.key());
+        appeal.resolved_at = clock.unix_timestamp;
+
+        emit!(AppealResolved {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            arbiter: ctx.accounts.escrow_authority.key(),
+            upheld,
+        });
+
+        msg!("Appeal {}", if upheld { "upheld" } else { "rejected; bond slashed" });
+        Ok(())
+    }
+
+    /// Reactivate a suspended launch after its appeal was upheld, or after
+    /// the review deadline passed without a ruling, refunding the bond (creator only)
+    pub fn reactivate_launch(ctx: Context<ReactivateLaunch>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+
+        let appeal = &mut ctx.accounts.appeal;
+        let clock = Clock::get()?;
+        require!(
+            appeal.allows_reactivation(clock.unix_timestamp),
+            TokenLaunchError::AppealNotUpheld
+        );
+
+        let bond = appeal.bond;
+        appeal.sub_lamports(bond)?;
+        ctx.accounts.creator.add_lamports(bond)?;
+        appeal.bond = 0;
+        appeal.status = AppealStatus::Reinstated;
+        if appeal.resolved_at == 0 {
+            appeal.resolved_at = clock.unix_timestamp;
+        }
+
+        launch.set_active(true);
+        launch.emit_active_change("reactivated on appeal".to_string())?;
+
+        msg!("Launch reactivated on appeal; {} lamports bond refunded", bond);
+        Ok(())
+    }
+
+    /// Stake SOL behind a launch as a third-party guarantee. The stake is
+    /// locked until the timelock expires and is paid out to holders if the
+    /// launch is ruled fraudulent.
+    pub fn underwrite(ctx: Context<Underwrite>, amount: u64) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let clock = Clock::get()?;
+        require!(amount > 0, TokenLaunchError::InvalidUnderwriteAmount);
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            !launch.is_timelock_expired(clock.unix_timestamp),
+            TokenLaunchError::TimelockExpired
+        );
+
+        let pool = &mut ctx.accounts.underwriting_pool;
+        require!(!pool.slashed, TokenLaunchError::UnderwritingSlashed);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.underwriter.to_account_info(),
+                to: pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let stake = &mut ctx.accounts.underwriter_stake;
+        if stake.amount == 0 {
+            pool.underwriter_count = pool
+                .underwriter_count
+                .checked_add(1)
+                .ok_or(TokenLaunchError::MathOverflow)?;
+        }
+        stake.launch = ctx.accounts.token_launch.key();
+        stake.underwriter = ctx.accounts.underwriter.key();
+        stake.amount = math::add(stake.amount, amount)?;
+        stake.staked_at = clock.unix_timestamp;
+        stake.bump = ctx.bumps.underwriter_stake;
+
+        pool.launch = ctx.accounts.token_launch.key();
+        pool.total_staked = math::add(pool.total_staked, amount)?;
+        pool.bump = ctx.bumps.underwriting_pool;
+
+        emit!(UnderwritingAdded {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            underwriter: stake.underwriter,
+            amount,
+            total_staked: pool.total_staked,
+        });
+
+        msg!("Underwritten {} lamports; total guarantee {}", amount, pool.total_staked);
+        Ok(())
+    }
+
+    /// Reclaim an underwriting stake once the timelock has expired without
+    /// a fraud ruling, closing the stake account
+    pub fn withdraw_underwriting(ctx: Context<WithdrawUnderwriting>) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+        require!(
+            launch.is_timelock_expired(clock.unix_timestamp),
+            TokenLaunchError::TimelockActive
+        );
+
+        let pool = &mut ctx.accounts.underwriting_pool;
+        require!(!pool.slashed, TokenLaunchError::UnderwritingSlashed);
+
+        let amount = ctx.accounts.underwriter_stake.amount;
+        pool.sub_lamports(amount)?;
+        ctx.accounts.underwriter.add_lamports(amount)?;
+        pool.total_staked = math::sub(pool.total_staked, amount)?;
+        pool.underwriter_count = pool
+            .underwriter_count
+            .checked_sub(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+
+        msg!("Underwriting stake of {} lamports withdrawn", amount);
+        Ok(())
+    }
+
+    /// Rule a suspended launch fraudulent and slash its underwriters (escrow only).
+    /// The whole pool becomes claimable by holders pro rata to the tokens
+    /// circulating outside the launch vault.
+    pub fn slash_underwriting(ctx: Context<SlashUnderwriting>) -> Result<()> {
+        require!(
+            ctx.accounts.escrow_authority.key() == ctx.accounts.config.escrow_authority,
+            TokenLaunchError::UnauthorizedArbiter
+        );
+
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        require!(!launch.is_active(), TokenLaunchError::LaunchNotSuspended);
+
+        let pool = &mut ctx.accounts.underwriting_pool;
+        require!(!pool.slashed, TokenLaunchError::UnderwritingSlashed);
+
+        let circulating = ctx
+            .accounts
+            .token_mint
+            .supply
+            .saturating_sub(ctx.accounts.vault.amount);
+        require!(circulating > 0, TokenLaunchError::NothingToDistribute);
+
+        pool.slashed = true;
+        pool.payout_pool = pool.total_staked;
+        pool.circulating_at_slash = circulating;
+
+        emit!(UnderwritersSlashed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            payout_pool: pool.payout_pool,
+            circulating_supply: circulating,
+        });
+
+        msg!("Underwriters slashed: {} lamports for {} circulating tokens", pool.payout_pool, circulating);
+        Ok(())
+    }
+
+    /// Burn tokens of a slashed launch for a pro-rata share of the
+    /// underwriting pool. Burning rather than transferring keeps claims
+    /// possible while the transfer hook blocks a suspended launch.
+    pub fn claim_guarantee(ctx: Context<ClaimGuarantee>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.underwriting_pool;
+        require!(pool.slashed, TokenLaunchError::UnderwritingNotSlashed);
+        require!(
+            amount > 0 && math::add(pool.tokens_redeemed, amount)? <= pool.circulating_at_slash,
+            TokenLaunchError::InvalidUnderwriteAmount
+        );
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let payout = pool.payout_for(amount);
+        pool.sub_lamports(payout)?;
+        ctx.accounts.holder.add_lamports(payout)?;
+        pool.tokens_redeemed = math::add(pool.tokens_redeemed, amount)?;
+
+        msg!("Burned {} tokens for {} lamports of underwriting", amount, payout);
+        Ok(())
+    }
+
+    /// Opt into social recovery by registering guardians and the number of
+    /// approvals needed to reassign the creator (creator only)
+    pub fn set_recovery_policy(
+        ctx: Context<SetRecoveryPolicy>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            !guardians.is_empty() && guardians.len() <= MAX_RECOVERY_GUARDIANS,
+            TokenLaunchError::InvalidRecoveryPolicy
+        );
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            TokenLaunchError::InvalidRecoveryPolicy
+        );
+        require!(
+            !guardians.contains(&launch.creator),
+            TokenLaunchError::InvalidRecoveryPolicy
+        );
+
+        let policy = &mut ctx.accounts.recovery_policy;
+        require!(policy.new_creator.is_none(), TokenLaunchError::RecoveryInProgress);
+        policy.launch = ctx.accounts.token_launch.key();
+        policy.guardians = guardians;
+        policy.threshold = threshold;
+        policy.approvals = Vec::new();
+        policy.quorum_reached_at = 0;
+        policy.bump = ctx.bumps.recovery_policy;
+
+        msg!("Recovery policy set: {}-of-{} guardians", threshold, policy.guardians.len());
+        Ok(())
+    }
+
+    /// Start recovering a launch to a new creator key (guardian only)
+    pub fn propose_recovery(ctx: Context<GuardianRecoveryAction>, new_creator: Pubkey) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let policy = &mut ctx.accounts.recovery_policy;
+        require!(policy.new_creator.is_none(), TokenLaunchError::RecoveryInProgress);
+        require_keys_neq!(new_creator, launch.creator, TokenLaunchError::InvalidNewCreator);
+
+        policy.new_creator = Some(new_creator);
+        policy.approve(ctx.accounts.guardian.key(), Clock::get()?.unix_timestamp)?;
+
+        msg!("Recovery to {} proposed by {}", new_creator, ctx.accounts.guardian.key());
+        Ok(())
+    }
+
+    /// Approve the pending recovery. Reaching the threshold opens the
+    /// challenge window (guardian only).
+    pub fn approve_recovery(ctx: Context<GuardianRecoveryAction>) -> Result<()> {
+        let policy = &mut ctx.accounts.recovery_policy;
+        require!(policy.new_creator.is_some(), TokenLaunchError::NoRecoveryPending);
+        policy.approve(ctx.accounts.guardian.key(), Clock::get()?.unix_timestamp)?;
+
+        msg!("Recovery approvals: {}/{}", policy.approvals.len(), policy.threshold);
+        if let Some(ends_at) = policy.challenge_ends_at() {
+            msg!("Challenge window open until {}", ends_at);
+        }
+        Ok(())
+    }
+
+    /// Challenge a pending recovery, proving the creator key is not lost (creator only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+
+        let policy = &mut ctx.accounts.recovery_policy;
+        require!(policy.new_creator.is_some(), TokenLaunchError::NoRecoveryPending);
+        policy.reset();
+
+        msg!("Recovery cancelled by creator");
+        Ok(())
+    }
+
+    /// Reassign the creator once the challenge window has passed unchallenged.
+    /// Anyone may execute; the executor pays for the new creator's index.
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let policy = &mut ctx.accounts.recovery_policy;
+        let clock = Clock::get()?;
+
+        let ends_at = policy.challenge_ends_at().ok_or(TokenLaunchError::RecoveryQuorumNotReached)?;
+        require!(clock.unix_timestamp >= ends_at, TokenLaunchError::ChallengeWindowOpen);
+        let new_creator = policy.new_creator.ok_or(TokenLaunchError::NoRecoveryPending)?;
+        require_keys_eq!(
+            ctx.accounts.new_creator.key(),
+            new_creator,
+            TokenLaunchError::NotPendingCreator
+        );
+
+        let old_creator = launch.creator;
+        ctx.accounts.old_creator_index.remove_launch(&launch.token_mint);
+        ctx.accounts.new_creator_index.add_launch(new_creator, launch.token_mint)?;
+
+        launch.creator = new_creator;
+        launch.set_creator_multisig(None)?;
+        launch.set_pending_creator(None)?;
+        policy.reset();
+
+        emit!(CreatorshipTransferred {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            old_creator,
+            new_creator,
+        });
+
+        msg!("Creatorship recovered: {} -> {}", old_creator, new_creator);
+        Ok(())
+    }
+
+    /// Reserve unlocked vault tokens for a beneficiary, either claimable at
+    /// once (airdrop) or vesting linearly after a cliff (creator only)
+    pub fn create_allocation<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateAllocation<'info>>,
+        beneficiary: Pubkey,
+        kind: ClaimKind,
+        amount: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            launch.is_timelock_expired(clock.unix_timestamp),
+            TokenLaunchError::TimelockActive
+        );
+        launch.check_unlock(amount, clock.unix_timestamp)?;
+        require!(amount > 0, TokenLaunchError::InvalidAllocation);
+        match kind {
+            ClaimKind::Airdrop => require!(
+                cliff_duration == 0 && vesting_duration == 0,
+                TokenLaunchError::InvalidAllocation
+            ),
+            ClaimKind::Vesting => require!(
+                cliff_duration >= 0 && vesting_duration > 0 && cliff_duration <= vesting_duration,
+                TokenLaunchError::InvalidAllocation
+            ),
+        }
+
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.allocation_vault.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        let allocation = &mut ctx.accounts.allocation;
+        allocation.launch = ctx.accounts.token_launch.key();
+        allocation.beneficiary = beneficiary;
+        allocation.kind = kind;
+        allocation.total = amount;
+        allocation.claimed = 0;
+        allocation.start = clock.unix_timestamp;
+        allocation.cliff_end = math::add_secs(clock.unix_timestamp, cliff_duration)?;
+        allocation.end = math::add_secs(clock.unix_timestamp, vesting_duration)?;
+        allocation.bump = ctx.bumps.allocation;
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.unlocked_so_far = math::add(launch.unlocked_so_far, amount)?;
+
+        msg!("Allocated {} tokens to {} ({:?})", amount, beneficiary, kind);
+        Ok(())
+    }
+
+    /// Settle several allocations for the signing beneficiary in one go.
+    /// `kinds[i]` describes remaining accounts `2i` (allocation) and `2i + 1`
+    /// (destination token account); any further remaining accounts are
+    /// forwarded to the transfer hook.
+    pub fn claim_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAll<'info>>,
+        kinds: Vec<ClaimKind>,
+    ) -> Result<()> {
+        check_batch_size(kinds.len())?;
+        let claim_accounts = kinds.len() * 2;
+        require!(
+            ctx.remaining_accounts.len() >= claim_accounts,
+            TokenLaunchError::InvalidBatchSize
+        );
+        let (pairs, hook_accounts) = ctx.remaining_accounts.split_at(claim_accounts);
+
+        let launch = &ctx.accounts.token_launch.load()?;
+        let beneficiary = ctx.accounts.beneficiary.key();
+        let now = Clock::get()?.unix_timestamp;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        let mut total_claimed = 0u64;
+
+        for (kind, pair) in kinds.iter().zip(pairs.chunks(2)) {
+            let mut allocation = load_allocation(&pair[0], ctx.accounts.token_launch.key(), beneficiary, *kind)?;
+            let destination = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+            require_keys_eq!(destination.mint, launch.token_mint, TokenLaunchError::InvalidBatchAccount);
+
+            let amount = allocation.claimable(now);
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_launch_tokens(
+                LaunchTokenTransfer {
+                    token_program: &ctx.accounts.token_program,
+                    from: ctx.accounts.allocation_vault.to_account_info(),
+                    mint: &ctx.accounts.token_mint,
+                    to: pair[1].clone(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                    additional_accounts: hook_accounts,
+                    signer_seeds,
+                },
+                amount,
+            )?;
+            allocation.claimed = math::add(allocation.claimed, amount)?;
+            allocation.exit(&crate::ID)?;
+            total_claimed = math::add(total_claimed, amount)?;
+
+            msg!("Claimed {} tokens ({:?})", amount, kind);
+        }
+
+        require!(total_claimed > 0, TokenLaunchError::NothingToClaim);
+        msg!("Total claimed: {} tokens across {} allocations", total_claimed, kinds.len());
+
+        // Reimburse the relayer's fee from the sponsor pool, if one is used
+        if let (Some(fee_payer), Some(pool), Some(record)) = (
+            ctx.accounts.fee_payer.as_ref(),
+            ctx.accounts.sponsor_pool.as_mut(),
+            ctx.accounts.sponsored_wallet.as_mut(),
+        ) {
+            record.launch = pool.launch;
+            record.wallet = beneficiary;
+            record.bump = present_bump(ctx.bumps.sponsored_wallet)?;
+
+            let available = lamports_above_rent(&pool.to_account_info())?;
+            let refund = pool.reimbursement_for(record.reimbursed, available);
+            if refund > 0 {
+                pool.sub_lamports(refund)?;
+                fee_payer.add_lamports(refund)?;
+                pool.total_reimbursed = math::add(pool.total_reimbursed, refund)?;
+                record.reimbursed = math::add(record.reimbursed, refund)?;
+                msg!("Reimbursed {} lamports to fee payer {}", refund, fee_payer.key());
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrite a launch created while fraud scores were stored as `f32`
+    /// into the basis-point layout. Permissionless: the conversion is
+    /// deterministic and only applies to accounts still sized for the
+    /// legacy layout, so it cannot run twice.
+    pub fn migrate_fraud_score(ctx: Context<MigrateFraudScore>) -> Result<()> {
+        let launch_info = ctx.accounts.token_launch.to_account_info();
+        let score = {
+            let mut data = launch_info.try_borrow_mut_data()?;
+            require!(
+                data.len() == LegacyTokenLaunch::space() + LEGACY_FRAUD_SCORE_EXTRA_BYTES,
+                TokenLaunchError::MigrationNotNeeded
+            );
+            migrate_legacy_fraud_score(&mut data)?
+        };
+        launch_info.realloc(LegacyTokenLaunch::space(), false)?;
+
+        msg!("Migrated fraud score for {}: {} bps", ctx.accounts.token_mint.key(), score);
+        Ok(())
+    }
+
+    /// Create or retune the launch's claim fee sponsorship (creator only).
+    /// Each sponsored claim reimburses up to `reimbursement_per_claim`
+    /// lamports, and no wallet receives more than `per_wallet_cap` in total.
+    pub fn configure_sponsorship(
+        ctx: Context<ConfigureSponsorship>,
+        reimbursement_per_claim: u64,
+        per_wallet_cap: u64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            reimbursement_per_claim > 0 && per_wallet_cap >= reimbursement_per_claim,
+            TokenLaunchError::InvalidSponsorship
+        );
+
+        let pool = &mut ctx.accounts.sponsor_pool;
+        pool.launch = ctx.accounts.token_launch.key();
+        pool.reimbursement_per_claim = reimbursement_per_claim;
+        pool.per_wallet_cap = per_wallet_cap;
+        pool.bump = ctx.bumps.sponsor_pool;
+
+        msg!("Claim sponsorship: {} lamports per claim, {} per wallet", reimbursement_per_claim, per_wallet_cap);
+        Ok(())
+    }
+
+    /// Add lamports to a launch's sponsor pool (anyone)
+    pub fn fund_sponsorship(ctx: Context<FundSponsorship>, amount: u64) -> Result<()> {
+        require!(amount > 0, TokenLaunchError::InvalidSponsorship);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.sponsor_pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let pool = &mut ctx.accounts.sponsor_pool;
+        pool.total_funded = math::add(pool.total_funded, amount)?;
+
+        msg!("Sponsor pool funded with {} lamports by {}", amount, ctx.accounts.sponsor.key());
+        Ok(())
+    }
+
+    /// Return unspent sponsorship lamports to the creator (creator only).
+    /// The pool keeps its rent-exempt minimum.
+    pub fn withdraw_sponsorship(ctx: Context<WithdrawSponsorship>, amount: u64) -> Result<()> {
+        ctx.accounts.token_launch.load()?.verify_creator(&ctx.accounts.creator.key())?;
+
+        let pool = &mut ctx.accounts.sponsor_pool;
+        let available = lamports_above_rent(&pool.to_account_info())?;
+        require!(amount > 0 && amount <= available, TokenLaunchError::InsufficientSponsorFunds);
+        pool.sub_lamports(amount)?;
+        ctx.accounts.creator.add_lamports(amount)?;
+
+        msg!("Withdrew {} lamports from the sponsor pool", amount);
+        Ok(())
+    }
+
+    /// Close a finished launch and reclaim its rent (creator only). The
+    /// hash of the launch's off-chain archive (`sold archive <mint>`) is
+    /// committed to a small permanent record so audits can still be checked
+    /// against the chain once the launch accounts are gone.
+    pub fn close_launch(
+        ctx: Context<CloseLaunch>,
+        archive_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let launch = ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            launch.is_timelock_expired(clock.unix_timestamp),
+            TokenLaunchError::TimelockActive
+        );
+        require!(ctx.accounts.vault.amount == 0, TokenLaunchError::LaunchNotEmpty);
+        require!(archive_hash != [0u8; 32], TokenLaunchError::InvalidArchiveHash);
+        // Closing refunds the bond, so a suspended launch must be slashed first
+        require!(launch.is_active() || launch.bond == 0, TokenLaunchError::BondNotSettled);
+
+        // Close the emptied vault, signed by the launch PDA
+        let mint_key = launch.token_mint;
+        drop(launch);
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::LAUNCH, mint_key.as_ref(), &bump]];
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        let mut launch = ctx.accounts.token_launch.load_mut()?;
+        ctx.accounts.creator_index.remove_launch(&launch.token_mint);
+
+        let seq = launch.next_event_seq()?;
+        let archive = &mut ctx.accounts.launch_archive;
+        archive.token_mint = launch.token_mint;
+        archive.creator = launch.creator;
+        archive.archive_hash = archive_hash;
+        archive.final_event_seq = seq;
+        archive.total_supply = launch.total_supply;
+        archive.total_withdrawn = launch.total_withdrawn;
+        archive.fees_collected = launch.fees_collected;
+        archive.closed_at = clock.unix_timestamp;
+        archive.bump = ctx.bumps.launch_archive;
+
+        emit!(LaunchArchived {
+            token_mint: launch.token_mint,
+            seq,
+            archive_hash,
+            closed_at: clock.unix_timestamp,
+        });
+
+        msg!("Launch {} closed; archive hash committed", launch.token_mint);
+        Ok(())
+    }
+
+    /// Upgrade a launch written under an older `TokenLaunch` layout.
+    /// Launches still in the Borsh layout used before v19 are rewritten
+    /// into the zero-copy layout, growing the account (the payer covers the
+    /// extra rent); later versions only need `version` bumped. Permissionless,
+    /// since the upgrade is deterministic and refuses accounts already current.
+    pub fn migrate_launch(ctx: Context<MigrateLaunch>) -> Result<()> {
+        let launch_info = ctx.accounts.token_launch.to_account_info();
+        let mut launch = read_launch_layout(&launch_info.try_borrow_data()?)?;
+        let from_version = upgrade_launch_layout(&mut launch)?;
+
+        if launch_info.data_len() != TokenLaunch::space() {
+            let required = Rent::get()?.minimum_balance(TokenLaunch::space());
+            let top_up = required.saturating_sub(launch_info.lamports());
+            if top_up > 0 {
+                let cpi_context = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: launch_info.clone(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_context, top_up)?;
+            }
+            launch_info.realloc(TokenLaunch::space(), false)?;
+        }
+        launch_info.try_borrow_mut_data()?[8..].copy_from_slice(bytemuck::bytes_of(&launch));
+
+        msg!(
+            "Migrated launch {} from layout v{} to v{}",
+            ctx.accounts.token_mint.key(),
+            from_version,
+            LAUNCH_VERSION
+        );
+        Ok(())
+    }
+
+    /// Fold the launch's fee shards back into the treasury and the launch's
+    /// fee total, so `withdraw_treasury` sees trading fees. Permissionless:
+    /// it only moves lamports the shards recorded as fees.
+    pub fn sweep_fee_shards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepFeeShards<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= FEE_SHARDS as usize,
+            TokenLaunchError::InvalidBatchAccount
+        );
+        let launch_key = ctx.accounts.token_launch.key();
+        let reflection_bps = ctx.accounts.token_launch.load()?.reflection_bps;
+
+        let mut swept = 0u64;
+        for shard_info in ctx.remaining_accounts.iter() {
+            let mut fee_shard = load_fee_shard(shard_info, launch_key)?;
+            let pending = fee_shard.take_pending();
+            if pending == 0 {
+                continue;
+            }
+            shard_info.sub_lamports(pending)?;
+            fee_shard.exit(&crate::ID)?;
+            swept = math::add(swept, pending)?;
+        }
+        require!(swept > 0, TokenLaunchError::NothingToDistribute);
+
+        // Reflection mode: the holders' slice goes to the dividend pool
+        let reflected = math::mul_div(swept, reflection_bps as u64, 10_000)?;
+        if reflected > 0 {
+            let pool = ctx
+                .accounts
+                .dividend_pool
+                .as_mut()
+                .ok_or_else(|| error!(TokenLaunchError::DividendPoolRequired))?;
+            pool.add_lamports(reflected)?;
+            pool.record_reflection(reflected)?;
+        }
+        let treasury_share = swept - reflected;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.add_lamports(treasury_share)?;
+        treasury.record_fee(treasury_share)?;
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.fees_collected = math::add(launch.fees_collected, treasury_share)?;
+
+        msg!(
+            "Swept {} lamports of trading fees for {} ({} reflected to holders)",
+            swept,
+            launch.token_mint,
+            reflected
+        );
+        Ok(())
+    }
+
+    /// Declare a fraud scoring model generation (admin only). Oracles must
+    /// cite a registered version and its hash with every score they push.
+    pub fn register_model(
+        ctx: Context<RegisterModel>,
+        version: u32,
+        model_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(version > 0, TokenLaunchError::InvalidScoringModel);
+        require!(model_hash != [0; 32], TokenLaunchError::InvalidScoringModel);
+
+        let model = &mut ctx.accounts.scoring_model;
+        model.version = version;
+        model.model_hash = model_hash;
+        model.registered_at = Clock::get()?.unix_timestamp;
+        model.bump = ctx.bumps.scoring_model;
+
+        emit!(ScoringModelRegistered {
+            version,
+            model_hash,
+            registered_at: model.registered_at,
+        });
+
+        msg!("Scoring model v{} registered", version);
+        Ok(())
+    }
+
+    /// Dry-run an emergency withdrawal by the signing insurance wallet
+    /// (or, for M-of-N launches, the proposal it would create) without
+    /// changing state. Runs the same limit, cap and cooldown checks and
+    /// returns the resulting figures as return data.
+    pub fn simulate_emergency_withdraw(
+        ctx: Context<SimulateEmergencyWithdraw>,
+        amount: u64,
+    ) -> Result<EmergencyWithdrawPreview> {
+        let clock = Clock::get()?;
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.preview_emergency_withdraw(
+            &ctx.accounts.authority.key(),
+            amount,
+            ctx.accounts.wallet_record.as_deref(),
+            ctx.accounts.config.fees_for(launch)?.emergency_fee,
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Dry-run a relock by the escrow authority without changing state,
+    /// returning the new unlock time, fee and whether creator consent is
+    /// needed
+    pub fn simulate_relock(
+        ctx: Context<SimulateRelock>,
+        new_duration: i64,
+    ) -> Result<RelockPreview> {
+        require!(
+            ctx.accounts.escrow_authority.key() == ctx.accounts.config.escrow_authority,
+            TokenLaunchError::UnauthorizedRelock
+        );
+        let clock = Clock::get()?;
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.preview_relock(
+            new_duration,
+            ctx.accounts.config.cluster,
+            ctx.accounts.config.fees_for(launch)?.relock_fee,
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Quote the transfer tax on `amount` without changing state, so UIs can
+    /// show the rate and what the recipient actually receives
+    pub fn simulate_transfer_tax(
+        ctx: Context<SimulateTransferTax>,
+        amount: u64,
+    ) -> Result<TransferTaxPreview> {
+        ctx.accounts.token_launch.load()?.preview_transfer_tax(amount)
+    }
+
+    /// Quote everything a launch with `params` would cost its creator (the
+    /// tiered launch fee, bond and the rent for each account the launch
+    /// creates) and what each transfer of its tokens will pay, without
+    /// changing state. Validates `params` the same way `initialize_launch`
+    /// does, so UIs need not copy the fee constants.
+    pub fn estimate_launch_fees(
+        ctx: Context<EstimateLaunchFees>,
+        params: LaunchParams,
+    ) -> Result<FeeEstimate> {
+        let accounts = &ctx.accounts;
+        let clock = Clock::get()?;
+        let class = accounts.config.launch_class(params.launch_class)?;
+        let referred = params.referrer.is_some();
+
+        let creator_profile = if accounts.creator_profile.data_is_empty() {
+            None
+        } else {
+            Some(CreatorProfile::try_deserialize(&mut &accounts.creator_profile.try_borrow_data()?[..])?)
+        };
+        let launch_registry = if accounts.launch_registry.data_is_empty() {
+            None
+        } else {
+            Some(LaunchRegistry::try_deserialize(&mut &accounts.launch_registry.try_borrow_data()?[..])?)
+        };
+        let token_mint = accounts.token_mint.as_ref().map(|mint| mint.to_account_info());
+
+        let mut launch = TokenLaunch::default();
+        launch.initialize(
+            accounts.creator.key(),
+            token_mint.as_ref().map_or_else(Pubkey::default, |mint| mint.key()),
+            params,
+            clock.unix_timestamp,
+            accounts.config.cluster,
+            class,
+        )?;
+        launch.set_repeat_creator(creator_profile.as_ref().is_some_and(|profile| profile.is_clean_repeat()));
+
+        let new_accounts = NewLaunchAccounts {
+            creator_index: accounts.creator_index.data_is_empty(),
+            creator_profile: creator_profile.is_none(),
+            launch_registry: launch_registry.is_none(),
+            // A page is created whenever the previous one has filled
+            registry_page: launch_registry.as_ref().map_or(0, |registry| registry.launch_count) % REGISTRY_PAGE_SIZE == 0,
+            vault_len: token_account_len(token_mint.as_ref(), false)?,
+            token_account_len: token_account_len(token_mint.as_ref(), true)?,
+        };
+        FeeEstimate::new(&accounts.config, &launch, class.as_ref(), referred, &Rent::get()?, &new_accounts)
+    }
+
+    /// Blacklist a wallet for this launch's transfers (creator, or anyone
+    /// presenting an executed `BlacklistWallet` governance proposal).
+    /// If the launch PDA holds the mint's freeze authority, the wallet's
+    /// token account can be frozen in the same step.
+    pub fn add_to_blacklist(
+        ctx: Context<AddToBlacklist>,
+        wallet: Pubkey,
+        reason: String,
+    ) -> Result<()> {
+        require!(reason.len() <= MAX_JUSTIFICATION_LEN, TokenLaunchError::JustificationTooLong);
+        let by_governance = authorize_blacklist_change(
+            &*ctx.accounts.token_launch.load()?,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance_proposal.as_deref(),
+            &GovernanceAction::BlacklistWallet { wallet },
+        )?;
+
+        let frozen = if let Some(token_account) = &ctx.accounts.token_account {
+            let launch = &ctx.accounts.token_launch.load()?;
+            require!(
+                ctx.accounts.token_mint.freeze_authority == Some(ctx.accounts.token_launch.key()).into(),
+                TokenLaunchError::FreezeAuthorityNotHeld
+            );
+            let bump = [ctx.bumps.token_launch];
+            let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+            token_interface::freeze_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                FreezeAccount {
+                    account: token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+            true
+        } else {
+            false
+        };
+
+        let clock = Clock::get()?;
+        let entry = &mut ctx.accounts.blacklist_entry;
+        entry.launch = ctx.accounts.token_launch.key();
+        entry.wallet = wallet;
+        entry.added_by = ctx.accounts.authority.key();
+        entry.added_at = clock.unix_timestamp;
+        entry.frozen = frozen;
+        entry.reason = reason;
+        entry.bump = ctx.bumps.blacklist_entry;
+
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        emit!(BlacklistChanged {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            wallet,
+            blacklisted: true,
+            frozen,
+            by_governance,
+        });
+
+        msg!("Wallet {} blacklisted{}", wallet, if frozen { " and frozen" } else { "" });
+        Ok(())
+    }
+
+    /// Lift a blacklist entry, thawing the wallet's token account if one is
+    /// passed (creator, or an executed `UnblacklistWallet` proposal)
+    pub fn remove_from_blacklist(
+        ctx: Context<RemoveFromBlacklist>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        let by_governance = authorize_blacklist_change(
+            &*ctx.accounts.token_launch.load()?,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance_proposal.as_deref(),
+            &GovernanceAction::UnblacklistWallet { wallet },
+        )?;
+
+        if let Some(token_account) = &ctx.accounts.token_account {
+            let launch = &ctx.accounts.token_launch.load()?;
+            let bump = [ctx.bumps.token_launch];
+            let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+            token_interface::thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        emit!(BlacklistChanged {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            wallet,
+            blacklisted: false,
+            frozen: false,
+            by_governance,
+        });
+
+        msg!("Wallet {} removed from blacklist", wallet);
+        Ok(())
+    }
+
+    /// Release vault tokens to many recipients in one transaction.
+    /// `remaining_accounts` holds one (recipient token account, recipient
+    /// owner's blacklist PDA) pair per entry in `amounts`, followed by any
+    /// transfer hook accounts shared by every transfer. The trading fee is
+    /// charged per recipient in a single treasury transfer.
+    pub fn airdrop_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AirdropBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            clock.unix_timestamp >= launch.timelock_end,
+            TokenLaunchError::TimelockActive
+        );
+        let total = airdrop_total(&launch, &amounts)?;
+        launch.check_unlock(total, clock.unix_timestamp)?;
+
+        let pair_accounts = amounts.len() * 2;
+        require!(
+            ctx.remaining_accounts.len() >= pair_accounts,
+            TokenLaunchError::AirdropAccountsMismatch
+        );
+        let (pairs, hook_accounts) = ctx.remaining_accounts.split_at(pair_accounts);
+
+        let fee = math::mul(ctx.accounts.config.fees_for(&launch)?.trading_fee, amounts.len() as u64)?;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, fee)?;
+        ctx.accounts.treasury.record_fee(fee)?;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        for (pair, &amount) in pairs.chunks_exact(2).zip(&amounts) {
+            // Stop cleanly rather than run out of compute mid-transfer
+            require!(
+                sol_remaining_compute_units() >= AIRDROP_TRANSFER_COMPUTE_UNITS,
+                TokenLaunchError::AirdropComputeExhausted
+            );
+
+            let (recipient, blacklist) = (&pair[0], &pair[1]);
+            require_keys_eq!(
+                *recipient.owner,
+                ctx.accounts.token_program.key(),
+                TokenLaunchError::AirdropAccountsMismatch
+            );
+            let recipient_account = TokenAccount::try_deserialize(&mut &recipient.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                recipient_account.mint,
+                ctx.accounts.token_mint.key(),
+                TokenLaunchError::AirdropAccountsMismatch
+            );
+            let (expected_blacklist, _) = seeds::find_blacklist(&launch_key, &recipient_account.owner);
+            require_keys_eq!(blacklist.key(), expected_blacklist, TokenLaunchError::AirdropAccountsMismatch);
+            require!(blacklist.data_is_empty(), TokenLaunchError::WalletBlacklisted);
+
+            transfer_launch_tokens(
+                LaunchTokenTransfer {
+                    token_program: &ctx.accounts.token_program,
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: &ctx.accounts.token_mint,
+                    to: recipient.clone(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                    additional_accounts: hook_accounts,
+                    signer_seeds,
+                },
+                amount,
+            )?;
+            if recipient_account.owner != launch_key {
+                launch.check_max_wallet(math::add(recipient_account.amount, amount)?)?;
+            }
+        }
+
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.fees_collected = math::add(launch.fees_collected, fee)?;
+        launch.unlocked_so_far = math::add(launch.unlocked_so_far, total)?;
+
+        emit!(AirdropDistributed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            recipients: amounts.len() as u32,
+            total_amount: total,
+            fee_paid: fee,
+        });
+
+        msg!("Airdropped {} tokens to {} recipients (fee: {} lamports)", total, amounts.len(), fee);
+        Ok(())
+    }
+
+    /// Replace the launch classes offered to new launches (admin only).
+    /// Existing launches keep the terms they launched under. Grows configs
+    /// created before launch classes existed.
+    pub fn set_launch_classes(
+        ctx: Context<SetLaunchClasses>,
+        classes: Vec<LaunchClass>,
+    ) -> Result<()> {
+        require!(classes.len() <= MAX_LAUNCH_CLASSES, TokenLaunchError::InvalidLaunchClass);
+        require!(classes.iter().all(LaunchClass::is_valid), TokenLaunchError::InvalidLaunchClass);
+
+        let config = &mut ctx.accounts.config;
+        config.launch_classes = classes;
+
+        for (index, class) in config.launch_classes.iter().enumerate() {
+            msg!(
+                "Launch class {}: min lock {}s, bond {} lamports, base fee {} lamports",
+                index,
+                class.min_timelock,
+                class.bond,
+                class.base_fee
+            );
+        }
+        Ok(())
+    }
+
+    /// Forfeit a suspended launch's class bond to the treasury (admin only)
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        require!(!launch.is_active(), TokenLaunchError::LaunchNotSuspended);
+        let amount = launch.bond;
+        require!(amount > 0, TokenLaunchError::NoBond);
+
+        ctx.accounts.token_launch.sub_lamports(amount)?;
+        ctx.accounts.treasury.add_lamports(amount)?;
+        ctx.accounts.treasury.record_fee(amount)?;
+        launch.fees_collected = math::add(launch.fees_collected, amount)?;
+        launch.bond = 0;
+
+        emit!(LaunchBondSlashed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            amount,
+        });
+
+        msg!("Slashed {} lamports launch bond into the treasury", amount);
+        Ok(())
+    }
+
+    /// Open a staking pool for the launch's token, funded with
+    /// `reward_amount` tokens from the creator and emitted evenly over
+    /// `duration` seconds. Stakes unlock `min_lock_duration` after each
+    /// deposit (creator only).
+    pub fn init_staking_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitStakingPool<'info>>,
+        reward_amount: u64,
+        duration: i64,
+        min_lock_duration: i64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            duration > 0 && min_lock_duration >= 0 && reward_amount >= duration as u64,
+            TokenLaunchError::InvalidStakingPool
+        );
+
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.creator_token_account.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.reward_vault.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds: &[],
+            },
+            reward_amount,
+        )?;
+
+        let pool = &mut ctx.accounts.staking_pool;
+        pool.launch = ctx.accounts.token_launch.key();
+        pool.stake_vault = ctx.accounts.stake_vault.key();
+        pool.reward_vault = ctx.accounts.reward_vault.key();
+        pool.reward_rate = reward_amount / duration as u64;
+        pool.start_time = clock.unix_timestamp;
+        pool.end_time = math::add_secs(clock.unix_timestamp, duration)?;
+        pool.last_update = clock.unix_timestamp;
+        pool.acc_reward_per_share = 0;
+        pool.total_staked = 0;
+        pool.min_lock_duration = min_lock_duration;
+        pool.bump = ctx.bumps.staking_pool;
+
+        msg!(
+            "Staking pool opened: {} reward tokens over {}s ({} per second)",
+            reward_amount,
+            duration,
+            pool.reward_rate
+        );
+        Ok(())
+    }
+
+    /// Lock tokens in the staking vault. Pending rewards are settled first
+    /// and the position's lock restarts from now.
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, TokenLaunchError::InvalidStakeAmount);
+        require!(ctx.accounts.token_launch.load()?.is_active(), TokenLaunchError::LaunchInactive);
+        let now = Clock::get()?.unix_timestamp;
+
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+        pool.update(now)?;
+        position.settle(pool)?;
+
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds: &[],
+            },
+            amount,
+        )?;
+
+        position.pool = pool.key();
+        position.owner = ctx.accounts.owner.key();
+        position.amount = math::add(position.amount, amount)?;
+        position.locked_until = math::add_secs(now, pool.min_lock_duration)?;
+        position.bump = ctx.bumps.stake_position;
+        position.checkpoint(pool);
+        pool.total_staked = math::add(pool.total_staked, amount)?;
+
+        emit!(StakeChanged {
+            token_mint: ctx.accounts.token_mint.key(),
+            owner: position.owner,
+            amount,
+            staked: true,
+            position_amount: position.amount,
+            total_staked: pool.total_staked,
+        });
+
+        msg!("Staked {} tokens, locked until {}", amount, position.locked_until);
+        Ok(())
+    }
+
+    /// Withdraw staked tokens once the position's lock has passed
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+        require!(amount > 0 && amount <= position.amount, TokenLaunchError::InvalidStakeAmount);
+        require!(now >= position.locked_until, TokenLaunchError::StakeLocked);
+
+        pool.update(now)?;
+        position.settle(pool)?;
+
+        let launch = &ctx.accounts.token_launch.load()?;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        position.amount -= amount;
+        position.checkpoint(pool);
+        pool.total_staked = math::sub(pool.total_staked, amount)?;
+
+        emit!(StakeChanged {
+            token_mint: launch.token_mint,
+            owner: position.owner,
+            amount,
+            staked: false,
+            position_amount: position.amount,
+            total_staked: pool.total_staked,
+        });
+
+        msg!("Unstaked {} tokens", amount);
+        Ok(())
+    }
+
+    /// Pay out a position's accrued staking rewards
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRewards<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.stake_position;
+
+        pool.update(now)?;
+        position.settle(pool)?;
+        position.checkpoint(pool);
+        let amount = position.pending_rewards;
+        require!(amount > 0, TokenLaunchError::NothingToClaim);
+
+        let launch = &ctx.accounts.token_launch.load()?;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+        position.pending_rewards = 0;
+
+        emit!(RewardsClaimed {
+            token_mint: launch.token_mint,
+            owner: position.owner,
+            amount,
+        });
+
+        msg!("Claimed {} staking reward tokens", amount);
+        Ok(())
+    }
+
+    /// Move assets sent to the launch by mistake to the recipient named in
+    /// an executed `RescueForeignAssets` proposal (anyone). Launch tokens
+    /// and the launch's tracked SOL are never touched, so re-running an
+    /// executed proposal only sweeps newly arrived foreign assets.
+    pub fn rescue_foreign_assets<'info>(
+        ctx: Context<'_, '_, '_, 'info, RescueForeignAssets<'info>>,
+        _proposal_id: u64,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let GovernanceAction::RescueForeignAssets { mint, recipient } = proposal.action else {
+            return err!(TokenLaunchError::InvalidGovernanceAction);
+        };
+        require!(proposal.executed, TokenLaunchError::ProposalNotPassed);
+        require_keys_eq!(
+            ctx.accounts.recipient.key(),
+            recipient,
+            TokenLaunchError::RescueAccountMismatch
+        );
+
+        let launch = ctx.accounts.token_launch.load()?;
+        let amount = match mint {
+            None => {
+                let info = ctx.accounts.token_launch.to_account_info();
+                let rent_exempt_minimum = Rent::get()?.minimum_balance(info.data_len());
+                let amount = launch.rescuable_lamports(info.lamports(), rent_exempt_minimum);
+                require!(amount > 0, TokenLaunchError::NothingToRescue);
+                info.sub_lamports(amount)?;
+                ctx.accounts.recipient.add_lamports(amount)?;
+                amount
+            }
+            Some(mint) => {
+                require_keys_neq!(mint, launch.token_mint, TokenLaunchError::ProtectedAsset);
+                let (Some(source), Some(foreign_mint), Some(destination)) = (
+                    ctx.accounts.source_token_account.as_ref(),
+                    ctx.accounts.foreign_mint.as_ref(),
+                    ctx.accounts.recipient_token_account.as_ref(),
+                ) else {
+                    return err!(TokenLaunchError::RescueAccountMismatch);
+                };
+                require!(
+                    foreign_mint.key() == mint
+                        && source.mint == mint
+                        && destination.mint == mint
+                        && destination.owner == recipient,
+                    TokenLaunchError::RescueAccountMismatch
+                );
+                require!(source.amount > 0, TokenLaunchError::NothingToRescue);
+
+                let bump = [ctx.bumps.token_launch];
+                let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+                transfer_launch_tokens(
+                    LaunchTokenTransfer {
+                        token_program: &ctx.accounts.token_program,
+                        from: source.to_account_info(),
+                        mint: foreign_mint,
+                        to: destination.to_account_info(),
+                        authority: ctx.accounts.token_launch.to_account_info(),
+                        additional_accounts: ctx.remaining_accounts,
+                        signer_seeds,
+                    },
+                    source.amount,
+                )?;
+                source.amount
+            }
+        };
+
+        let proposal_id = proposal.proposal_id;
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        emit!(ForeignAssetsRescued {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            proposal_id,
+            mint,
+            recipient,
+            amount,
+        });
+
+        msg!("Rescued {} foreign units to {}", amount, recipient);
+        Ok(())
+    }
+
+    /// Register where keepers deliver this launch's event callbacks: a
+    /// public `https://` URI, or a hash of an endpoint the keeper resolves
+    /// from its own registry. Empty URI and zero hash unregister (creator
+    /// only).
+    pub fn set_launch_webhook(
+        ctx: Context<SetLaunchWebhook>,
+        webhook_uri: String,
+        webhook_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.token_launch.load()?.verify_creator(&ctx.accounts.creator.key())?;
+        LaunchMetadata::validate_webhook(&webhook_uri)?;
+
+        let metadata = &mut ctx.accounts.launch_metadata;
+        metadata.token_mint = ctx.accounts.token_mint.key();
+        metadata.webhook_uri = webhook_uri;
+        metadata.webhook_hash = webhook_hash;
+        metadata.updated_at = Clock::get()?.unix_timestamp;
+        metadata.bump = ctx.bumps.launch_metadata;
+
+        emit!(WebhookUpdated {
+            token_mint: metadata.token_mint,
+            webhook_uri: metadata.webhook_uri.clone(),
+            webhook_hash,
+        });
+
+        msg!("Launch webhook {}", if metadata.has_webhook() { "registered" } else { "cleared" });
+        Ok(())
+    }
+
+    /// Turn on reflection mode: `reflection_bps` of every swept trading
+    /// fee goes to a dividend pool paid out to holders. Zero turns it off
+    /// (creator only).
+    pub fn enable_reflection(
+        ctx: Context<EnableReflection>,
+        reflection_bps: u16,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            reflection_bps <= MAX_REFLECTION_BPS,
+            TokenLaunchError::InvalidReflectionShare
+        );
+        launch.reflection_bps = reflection_bps;
+
+        let pool = &mut ctx.accounts.dividend_pool;
+        pool.launch = ctx.accounts.token_launch.key();
+        pool.bump = ctx.bumps.dividend_pool;
+
+        msg!("Reflection set to {} bps of trading fees", reflection_bps);
+        Ok(())
+    }
+
+    /// Open a holder's dividend claim. Only dividends folded in after
+    /// registering are owed to it.
+    pub fn register_dividend_claim(ctx: Context<RegisterDividendClaim>) -> Result<()> {
+        let claim = &mut ctx.accounts.dividend_claim;
+        claim.launch = ctx.accounts.token_launch.key();
+        claim.holder = ctx.accounts.holder.key();
+        claim.checkpoint = ctx.accounts.dividend_pool.acc_per_token;
+        claim.total_claimed = 0;
+        claim.bump = ctx.bumps.dividend_claim;
+
+        msg!("Dividend claim registered for {}", claim.holder);
+        Ok(())
+    }
+
+    /// Fold reflected fees into the per-token accumulator and pay a batch
+    /// of registered holders pro rata to their current balance
+    /// (permissionless). Tokens still in the launch vault do not earn.
+    pub fn distribute_dividends<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeDividends<'info>>,
+    ) -> Result<()> {
+        let holders = ctx.remaining_accounts;
+        require!(
+            !holders.is_empty()
+                && holders.len().is_multiple_of(3)
+                && holders.len() / 3 <= MAX_DIVIDEND_HOLDERS,
+            TokenLaunchError::InvalidBatchAccount
+        );
+        let launch_key = ctx.accounts.token_launch.key();
+        let token_mint = ctx.accounts.token_mint.key();
+        let token_program = *ctx.accounts.token_mint.to_account_info().owner;
+
+        let eligible_supply = ctx
+            .accounts
+            .token_mint
+            .supply
+            .saturating_sub(ctx.accounts.vault.amount);
+        let pool = &mut ctx.accounts.dividend_pool;
+        let folded = pool.fold(eligible_supply)?;
+
+        let mut paid = 0u64;
+        for holder in holders.chunks_exact(3) {
+            let (claim_info, token_info, wallet) = (&holder[0], &holder[1], &holder[2]);
+            require!(claim_info.is_writable && wallet.is_writable, TokenLaunchError::InvalidBatchAccount);
+            let mut claim = Account::<DividendClaim>::try_from(claim_info)?;
+            let (expected, _) = seeds::find_dividend_claim(&launch_key, &claim.holder);
+            require_keys_eq!(claim_info.key(), expected, TokenLaunchError::InvalidBatchAccount);
+            require_keys_eq!(wallet.key(), claim.holder, TokenLaunchError::InvalidBatchAccount);
+
+            require_keys_eq!(*token_info.owner, token_program, TokenLaunchError::InvalidBatchAccount);
+            let token_account = TokenAccount::try_deserialize(&mut &token_info.try_borrow_data()?[..])?;
+            require!(
+                token_account.mint == token_mint && token_account.owner == claim.holder,
+                TokenLaunchError::InvalidBatchAccount
+            );
+
+            let owed = pool.owed(token_account.amount, claim.checkpoint)?;
+            claim.checkpoint = pool.acc_per_token;
+            if owed > 0 {
+                pool.sub_lamports(owed)?;
+                wallet.add_lamports(owed)?;
+                claim.total_claimed = math::add(claim.total_claimed, owed)?;
+                paid = math::add(paid, owed)?;
+            }
+            claim.exit(&crate::ID)?;
+        }
+        pool.total_paid = math::add(pool.total_paid, paid)?;
+
+        emit!(DividendsDistributed {
+            token_mint,
+            folded,
+            paid,
+            holders: (holders.len() / 3) as u8,
+        });
+
+        msg!("Distributed {} lamports of dividends to {} holders", paid, holders.len() / 3);
+        Ok(())
+    }
+
+    /// Spend `sol_amount` of the launch's undistributed treasury fees on
+    /// its own token and burn what comes back (admin only). `amm_ix_data`
+    /// and the remaining accounts form an allowlisted AMM's exact-in swap
+    /// from the launch's wSOL account into the launch vault; the launch PDA
+    /// signs as the trader.
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuybackAndBurn<'info>>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        amm_ix_data: Vec<u8>,
+    ) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(
+            is_allowed_amm(&ctx.accounts.amm_program.key()),
+            TokenLaunchError::UnsupportedAmm
+        );
+        let available = math::sub(launch.fees_collected, launch.fees_distributed)?;
+        require!(
+            sol_amount > 0 && sol_amount <= available,
+            TokenLaunchError::BuybackExceedsFees
+        );
+
+        // Move the fees out of the treasury and wrap them for the swap
+        ctx.accounts.treasury.sub_lamports(sol_amount)?;
+        ctx.accounts.wsol_vault.add_lamports(sol_amount)?;
+        token_interface::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::SyncNative { account: ctx.accounts.wsol_vault.to_account_info() },
+        ))?;
+        ctx.accounts.wsol_vault.reload()?;
+
+        let tokens_before = ctx.accounts.vault.amount;
+        let wsol_before = ctx.accounts.wsol_vault.amount;
+
+        let launch_key = ctx.accounts.token_launch.key();
+        check_launch_token_accounts(
+            ctx.remaining_accounts,
+            &launch_key,
+            &[ctx.accounts.vault.key(), ctx.accounts.wsol_vault.key()],
+        )?;
+        let instruction = Instruction {
+            program_id: ctx.accounts.amm_program.key(),
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == launch_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: amm_ix_data,
+        };
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        invoke_signed(&instruction, ctx.remaining_accounts, signer_seeds)?;
+
+        // The AMM may spend no more than authorized and must pay out enough
+        ctx.accounts.vault.reload()?;
+        ctx.accounts.wsol_vault.reload()?;
+        let lamports_spent = wsol_before.saturating_sub(ctx.accounts.wsol_vault.amount);
+        let tokens_bought = ctx.accounts.vault.amount.saturating_sub(tokens_before);
+        require!(lamports_spent <= sol_amount, TokenLaunchError::LiquidityOverdrawn);
+        require!(
+            tokens_bought > 0 && tokens_bought >= min_tokens_out,
+            TokenLaunchError::SlippageExceeded
+        );
+
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.token_launch.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            tokens_bought,
+        )?;
+
+        // The whole amount left the treasury; any unspent wSOL stays with
+        // the launch
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_distributed = math::add(treasury.total_distributed, sol_amount)?;
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.fees_distributed = math::add(launch.fees_distributed, sol_amount)?;
+        launch.record_burn(tokens_bought)?;
+
+        emit!(BuybackBurned {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            lamports_spent,
+            tokens_burned: tokens_bought,
+            total_burned: launch.tokens_burned,
+        });
+
+        msg!("Buyback: {} lamports -> {} tokens burned", lamports_spent, tokens_bought);
+        msg!("Total burned: {}", launch.tokens_burned);
+        Ok(())
+    }
+
+    /// Permanently reduce supply by burning from the launch vault or the
+    /// creator's own token account (creator only). Insurance allowances
+    /// follow the reduced supply.
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(amount > 0, TokenLaunchError::InvalidBurnAmount);
+
+        let source = &ctx.accounts.source;
+        let from_vault = source.key() == launch.vault;
+        let bump = [ctx.bumps.token_launch];
+        let launch_seeds = launch.signer_seeds(&bump);
+        let (authority, signer_seeds): (AccountInfo, &[&[&[u8]]]) = if from_vault {
+            (ctx.accounts.token_launch.to_account_info(), &[&launch_seeds])
+        } else {
+            require_keys_eq!(source.owner, launch.creator, TokenLaunchError::InvalidBurnSource);
+            (ctx.accounts.creator.to_account_info(), &[])
+        };
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: source.to_account_info(),
+                    authority,
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.record_burn(amount)?;
+
+        emit!(TokensBurned {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            from_vault,
+            amount,
+            circulating_supply: launch.circulating_supply,
+        });
+
+        msg!("Burned {} tokens from the {}", amount, if from_vault { "vault" } else { "creator" });
+        msg!("Circulating supply: {}", launch.circulating_supply);
+        Ok(())
+    }
+
+    /// Observe the launch's Pyth price feed for its unlock condition
+    /// (permissionless). Keepers call this every few slots; the condition
+    /// latches once the price has stayed above the threshold for
+    /// `min_slots`, after which the timelock alone gates unlocks.
+    pub fn update_unlock_condition(ctx: Context<UpdateUnlockCondition>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let Some(UnlockCondition::PythPriceAbove { feed, threshold, expo, .. }) = launch.unlock_condition() else {
+            return err!(TokenLaunchError::InvalidUnlockCondition);
+        };
+        require!(!launch.condition_met(), TokenLaunchError::InvalidUnlockCondition);
+
+        let price_feed = &ctx.accounts.price_feed;
+        require_keys_eq!(price_feed.key(), feed, TokenLaunchError::InvalidPriceFeed);
+        require!(is_pyth_program(price_feed.owner), TokenLaunchError::InvalidPriceFeed);
+        let price = SolanaPriceAccount::account_info_to_feed(price_feed)
+            .map_err(|_| error!(TokenLaunchError::InvalidPriceFeed))?;
+
+        let clock = Clock::get()?;
+        let price = price
+            .get_price_no_older_than(clock.unix_timestamp, MAX_PRICE_AGE_SECS)
+            .ok_or(TokenLaunchError::StalePrice)?
+            .scale_to_exponent(expo)
+            .ok_or(TokenLaunchError::StalePrice)?;
+        let holds = price.price >= threshold;
+
+        if launch.observe_unlock_condition(holds, clock.slot) {
+            emit!(UnlockConditionMet {
+                token_mint: launch.token_mint,
+                seq: launch.next_event_seq()?,
+                slot: clock.slot,
+                price: price.price,
+            });
+            msg!("Unlock condition met at slot {}", clock.slot);
+        } else {
+            msg!("Price {} vs threshold {}, holding since slot {}", price.price, threshold, launch.condition_since_slot);
+        }
+        Ok(())
+    }
+
+    /// Advance a launch state machine that no longer needs a privileged
+    /// caller (permissionless): mark due unlock tranches released, close an
+    /// expired withdrawal proposal, or reactivate a launch whose appeal
+    /// the escrow never ruled on. The cranker is paid a small incentive
+    /// from the launch's undistributed treasury fees.
+    pub fn crank_launch(ctx: Context<CrankLaunch>, action: CrankAction) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let clock = Clock::get()?;
+
+        match action {
+            CrankAction::ReleaseTranches => {
+                let schedule = launch.unlock_schedule().ok_or(TokenLaunchError::NothingToCrank)?;
+                let due = schedule.tranches_due(launch.timelock_end, clock.unix_timestamp);
+                require!(due > launch.tranches_released, TokenLaunchError::NothingToCrank);
+                launch.tranches_released = due;
+                msg!("Unlock tranches released: {}", due);
+            }
+            CrankAction::ExpireProposal => {
+                let (Some(proposal), Some(proposer)) = (&ctx.accounts.proposal, &ctx.accounts.proposer) else {
+                    return err!(TokenLaunchError::CrankAccountMismatch);
+                };
+                require_keys_eq!(proposer.key(), proposal.proposer, TokenLaunchError::CrankAccountMismatch);
+                require!(
+                    !proposal.executed && clock.unix_timestamp >= proposal.expires_at,
+                    TokenLaunchError::NothingToCrank
+                );
+                proposal.close(proposer.to_account_info())?;
+                msg!("Expired withdrawal proposal #{} closed", proposal.proposal_id);
+            }
+            CrankAction::SuspendOnScore => {
+                let history = ctx.accounts.score_history.as_ref().ok_or(TokenLaunchError::CrankAccountMismatch)?;
+                require!(
+                    launch.is_active() && history.should_suspend(clock.unix_timestamp),
+                    TokenLaunchError::NothingToCrank
+                );
+                launch.set_active(false);
+                launch.emit_active_change("auto-suspended: high fraud score".to_string())?;
+                msg!(
+                    "Launch auto-suspended due to high fraud score: {} bps",
+                    history.smoothed_score(clock.unix_timestamp).unwrap_or_default()
+                );
+            }
+            CrankAction::ReactivateLaunch => {
+                let (Some(appeal), Some(appellant)) = (&mut ctx.accounts.appeal, &ctx.accounts.appellant) else {
+                    return err!(TokenLaunchError::CrankAccountMismatch);
+                };
+                require_keys_eq!(appellant.key(), appeal.appellant, TokenLaunchError::CrankAccountMismatch);
+                require!(
+                    !launch.is_active()
+                        && appeal.status == AppealStatus::Pending
+                        && appeal.allows_reactivation(clock.unix_timestamp),
+                    TokenLaunchError::NothingToCrank
+                );
+
+                let bond = appeal.bond;
+                appeal.sub_lamports(bond)?;
+                appellant.add_lamports(bond)?;
+                appeal.bond = 0;
+                appeal.status = AppealStatus::Reinstated;
+                appeal.resolved_at = clock.unix_timestamp;
+
+                launch.set_active(true);
+                launch.emit_active_change("reactivated after appeal deadline".to_string())?;
+                msg!("Launch reactivated; {} lamports bond refunded", bond);
+            }
+        }
+
+        let reward = launch.crank_reward();
+        if reward > 0 {
+            let treasury = &mut ctx.accounts.treasury;
+            treasury.sub_lamports(reward)?;
+            ctx.accounts.cranker.add_lamports(reward)?;
+            treasury.total_distributed = math::add(treasury.total_distributed, reward)?;
+            launch.fees_distributed = math::add(launch.fees_distributed, reward)?;
+        }
+
+        emit!(LaunchCranked {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            action,
+            cranker: ctx.accounts.cranker.key(),
+            reward,
+        });
+
+        msg!("Cranker paid {} lamports", reward);
+        Ok(())
+    }
+
+    /// Attest that `creator` passed KYC (registered attestor only). An
+    /// attestor program signs with the PDA registered in the config.
+    /// `expires_at` of 0 never expires.
+    pub fn attest_creator(ctx: Context<AttestCreator>, creator: Pubkey, expires_at: i64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(
+            config.kyc_attestor == Some(ctx.accounts.attestor.key()),
+            TokenLaunchError::UnauthorizedAttestor
+        );
+        let clock = Clock::get()?;
+        require!(
+            expires_at == 0 || expires_at > clock.unix_timestamp,
+            TokenLaunchError::KycAttestationExpired
+        );
+
+        let attestation = &mut ctx.accounts.kyc_attestation;
+        attestation.creator = creator;
+        attestation.attestor = ctx.accounts.attestor.key();
+        attestation.attested_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.kyc_attestation;
+
+        emit!(CreatorAttested {
+            creator,
+            attestor: attestation.attestor,
+            expires_at,
+        });
+
+        msg!("KYC attestation for {} by {}", creator, attestation.attestor);
+        Ok(())
+    }
+
+    /// Revoke a creator's KYC attestation, refunding its rent (registered
+    /// attestor only). Existing launches are unaffected.
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, creator: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.config.kyc_attestor == Some(ctx.accounts.attestor.key()),
+            TokenLaunchError::UnauthorizedAttestor
+        );
+
+        emit!(CreatorAttestationRevoked {
+            creator,
+            attestor: ctx.accounts.attestor.key(),
+        });
+
+        msg!("KYC attestation for {} revoked", creator);
+        Ok(())
+    }
+
+    /// Open a SOL raise for `token_allocation` tokens from the launch vault
+    /// (creator only). Contributions are held in the raise PDA until it
+    /// finalizes: at or above the soft cap tokens are claimed pro-rata,
+    /// below it every contributor is refunded.
+    pub fn init_raise(ctx: Context<InitRaise>, params: RaiseParams) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        let clock = Clock::get()?;
+        require!(
+            params.is_valid(clock.unix_timestamp) && params.token_allocation <= ctx.accounts.vault.amount,
+            TokenLaunchError::InvalidRaise
+        );
+
+        let raise = &mut ctx.accounts.raise;
+        raise.launch = ctx.accounts.token_launch.key();
+        raise.token_allocation = params.token_allocation;
+        raise.soft_cap = params.soft_cap;
+        raise.hard_cap = params.hard_cap;
+        raise.start_time = params.start_time;
+        raise.end_time = params.end_time;
+        raise.total_contributed = 0;
+        raise.contributor_count = 0;
+        raise.state = RaiseState::Open;
+        raise.bump = ctx.bumps.raise;
+
+        msg!("Raise opened: {} tokens, soft cap {} / hard cap {} lamports", params.token_allocation, params.soft_cap, params.hard_cap);
+        msg!("Window: {} to {}", params.start_time, params.end_time);
+        Ok(())
+    }
+
+    /// Deposit SOL into an open raise, up to its hard cap
+    pub fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
+        let raise = &mut ctx.accounts.raise;
+        let clock = Clock::get()?;
+        require!(ctx.accounts.token_launch.load()?.is_active(), TokenLaunchError::LaunchInactive);
+        require!(raise.is_open(clock.unix_timestamp), TokenLaunchError::RaiseClosed);
+        require!(amount > 0, TokenLaunchError::InvalidPurchaseAmount);
+        require!(
+            math::add(raise.total_contributed, amount)? <= raise.hard_cap,
+            TokenLaunchError::RaiseHardCapExceeded
+        );
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.contributor.to_account_info(),
+                    to: raise.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let contribution = &mut ctx.accounts.contribution;
+        if contribution.amount == 0 {
+            contribution.raise = raise.key();
+            contribution.contributor = ctx.accounts.contributor.key();
+            contribution.bump = ctx.bumps.contribution;
+            raise.contributor_count = raise
+                .contributor_count
+                .checked_add(1)
+                .ok_or(TokenLaunchError::MathOverflow)?;
+        }
+        contribution.amount = math::add(contribution.amount, amount)?;
+        raise.total_contributed = math::add(raise.total_contributed, amount)?;
+
+        emit!(RaiseContributed {
+            token_mint: ctx.accounts.token_mint.key(),
+            contributor: contribution.contributor,
+            amount,
+            total_contributed: raise.total_contributed,
+        });
+
+        msg!("Contributed {} lamports; raise total {}/{}", amount, raise.total_contributed, raise.hard_cap);
+        Ok(())
+    }
+
+    /// Close a raise once its window ends or it hits the hard cap
+    /// (permissionless). A successful raise moves its proceeds to the
+    /// launch's SOL vault; a failed one keeps them for refunds.
+    pub fn finalize_raise(ctx: Context<FinalizeRaise>) -> Result<()> {
+        let raise = &mut ctx.accounts.raise;
+        let clock = Clock::get()?;
+        require!(raise.state == RaiseState::Open, TokenLaunchError::RaiseClosed);
+        require!(
+            clock.unix_timestamp >= raise.end_time || raise.total_contributed == raise.hard_cap,
+            TokenLaunchError::RaiseStillOpen
+        );
+
+        raise.state = raise.outcome();
+        if raise.state == RaiseState::Succeeded {
+            let proceeds = raise.total_contributed;
+            raise.sub_lamports(proceeds)?;
+            ctx.accounts.sol_vault.add_lamports(proceeds)?;
+        }
+
+        emit!(RaiseFinalized {
+            token_mint: ctx.accounts.token_mint.key(),
+            succeeded: raise.state == RaiseState::Succeeded,
+            total_contributed: raise.total_contributed,
+            contributor_count: raise.contributor_count,
+        });
+
+        msg!("Raise {:?}: {} lamports from {} contributors", raise.state, raise.total_contributed, raise.contributor_count);
+        Ok(())
+    }
+
+    /// Claim a contributor's pro-rata share of a successful raise's tokens
+    pub fn claim_raise_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRaiseTokens<'info>>,
+    ) -> Result<()> {
+        let raise = &ctx.accounts.raise;
+        require!(raise.state == RaiseState::Succeeded, TokenLaunchError::RaiseNotSucceeded);
+        let amount = raise.tokens_for(ctx.accounts.contribution.amount)?;
+        require!(amount > 0, TokenLaunchError::NothingToClaim);
+
+        let launch = &ctx.accounts.token_launch.load()?;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.contributor_token_account.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        msg!("Raise claim: {} tokens for {} lamports contributed", amount, ctx.accounts.contribution.amount);
+        Ok(())
+    }
+
+    /// Refund a contribution to a raise that missed its soft cap, closing
+    /// the contribution account
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        let raise = &mut ctx.accounts.raise;
+        require!(raise.state == RaiseState::Failed, TokenLaunchError::RaiseNotFailed);
+
+        let amount = ctx.accounts.contribution.amount;
+        raise.sub_lamports(amount)?;
+        ctx.accounts.contributor.add_lamports(amount)?;
+
+        msg!("Refunded {} lamports to {}", amount, ctx.accounts.contributor.key());
+        Ok(())
+    }
+
+    /// Carve a team or advisor allocation out of the launch vault into the
+    /// beneficiary's own vesting vault (creator only). Team tokens vest on
+    /// their own schedule, independent of the public unlock schedule, but
+    /// the cliff may not end before the public timelock, and team
+    /// allocations are capped at `MAX_TEAM_ALLOCATION_PCT` of supply.
+    pub fn create_team_allocation<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateTeamAllocation<'info>>,
+        beneficiary: Pubkey,
+        amount: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        let launch = ctx.accounts.token_launch.load()?;
+        let clock = Clock::get()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+        require!(amount > 0, TokenLaunchError::InvalidAllocation);
+        require!(
+            cliff_duration >= 0 && vesting_duration > 0 && cliff_duration <= vesting_duration,
+            TokenLaunchError::InvalidAllocation
+        );
+        let cliff_end = math::add_secs(clock.unix_timestamp, cliff_duration)?;
+        require!(cliff_end >= launch.timelock_end, TokenLaunchError::TeamCliffBeforeTimelock);
+        let team_allocated = math::add(launch.team_allocated, amount)?;
+        require!(
+            team_allocated <= math::pct(launch.total_supply, MAX_TEAM_ALLOCATION_PCT)?,
+            TokenLaunchError::TeamAllocationCapExceeded
+        );
+
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.team_vault.to_account_info(),
+                authority: ctx.accounts.token_launch.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        let allocation = &mut ctx.accounts.team_allocation;
+        allocation.launch = ctx.accounts.token_launch.key();
+        allocation.beneficiary = beneficiary;
+        allocation.total = amount;
+        allocation.claimed = 0;
+        allocation.start = clock.unix_timestamp;
+        allocation.cliff_end = cliff_end;
+        allocation.end = math::add_secs(clock.unix_timestamp, vesting_duration)?;
+        allocation.bump = ctx.bumps.team_allocation;
+        drop(launch);
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.team_allocated = team_allocated;
+
+        emit!(TeamAllocationCreated {
+            token_mint: ctx.accounts.token_mint.key(),
+            beneficiary,
+            amount,
+            cliff_end,
+            end: allocation.end,
+        });
+
+        msg!("Team allocation: {} tokens to {}", amount, beneficiary);
+        msg!("Cliff ends {}, fully vested {}", cliff_end, allocation.end);
+        Ok(())
+    }
+
+    /// Claim the vested part of the signer's team allocation
+    pub fn claim_team_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimTeamTokens<'info>>,
+    ) -> Result<()> {
+        require!(ctx.accounts.token_launch.load()?.is_active(), TokenLaunchError::LaunchInactive);
+        let allocation = &ctx.accounts.team_allocation;
+        let now = Clock::get()?.unix_timestamp;
+        let amount = allocation.claimable(now);
+        require!(amount > 0, TokenLaunchError::NothingToClaim);
+
+        let bump = [allocation.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            seeds::TEAM_ALLOCATION,
+            allocation.launch.as_ref(),
+            allocation.beneficiary.as_ref(),
+            &bump,
+        ]];
+        transfer_launch_tokens(
+            LaunchTokenTransfer {
+                token_program: &ctx.accounts.token_program,
+                from: ctx.accounts.team_vault.to_account_info(),
+                mint: &ctx.accounts.token_mint,
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: allocation.to_account_info(),
+                additional_accounts: ctx.remaining_accounts,
+                signer_seeds,
+            },
+            amount,
+        )?;
+
+        let allocation = &mut ctx.accounts.team_allocation;
+        allocation.claimed = math::add(allocation.claimed, amount)?;
+
+        msg!("Claimed {} team tokens ({}/{})", amount, allocation.claimed, allocation.total);
+        Ok(())
+    }
+
+    /// Prove a launch's compressed NFT logo (permissionless): the leaf at
+    /// the claimed tree and index must be the Bubblegum asset owned by the
+    /// creator, checked by CPI into spl-account-compression's `verify_leaf`.
+    /// Proof nodes are passed as remaining accounts.
+    pub fn verify_logo_cnft<'info>(
+        ctx: Context<'_, '_, '_, 'info, VerifyLogoCnft<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        delegate: Pubkey,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let logo = launch.logo_cnft().ok_or(TokenLaunchError::InvalidLogo)?;
+        let compression_program = ctx.accounts.compression_program.key();
+        require_keys_eq!(
+            compression_program,
+            ACCOUNT_COMPRESSION_PROGRAM_ID,
+            TokenLaunchError::InvalidLogo
+        );
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        require_keys_eq!(merkle_tree.key(), logo.tree, TokenLaunchError::InvalidLogo);
+        require_keys_eq!(*merkle_tree.owner, compression_program, TokenLaunchError::InvalidLogo);
+
+        let asset_id = logo.asset_id(&BUBBLEGUM_PROGRAM_ID);
+        let leaf = logo.leaf_hash(&asset_id, &launch.creator, &delegate, &data_hash, &creator_hash);
+
+        let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&leaf);
+        data.extend_from_slice(&logo.leaf_index.to_le_bytes());
+        let instruction = Instruction {
+            program_id: compression_program,
+            accounts: std::iter::once(&merkle_tree)
+                .chain(ctx.remaining_accounts)
+                .map(|account| AccountMeta::new_readonly(account.key(), false))
+                .collect(),
+            data,
+        };
+        let mut account_infos = vec![merkle_tree];
+        account_infos.extend_from_slice(ctx.remaining_accounts);
+        invoke(&instruction, &account_infos)?;
+
+        launch.set_logo_verified(true);
+        emit!(LogoVerified {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            tree: logo.tree,
+            leaf_index: logo.leaf_index,
+            asset_id,
+        });
+
+        msg!("Compressed logo verified: asset {} (leaf {})", asset_id, logo.leaf_index);
+        Ok(())
+    }
+
+    /// Replace the weighted recipients of the protocol fee share (admin
+    /// only). An empty list pays `fee_recipient` alone.
+    pub fn set_fee_recipients(
+        ctx: Context<SetFeeRecipients>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        require!(FeeRecipient::is_valid_set(&recipients), TokenLaunchError::InvalidFeeRecipients);
+
+        let config = &mut ctx.accounts.config;
+        config.fee_recipients = recipients;
+
+        for recipient in &config.fee_recipients {
+            msg!("Fee recipient {}: {} bps", recipient.wallet, recipient.bps);
+        }
+        Ok(())
+    }
+
+    /// Queue an insurance wallet addition or removal (creator only). It
+    /// takes effect after INSURANCE_CHANGE_DELAY; proposing again replaces
+    /// any change still pending.
+    pub fn propose_insurance_change(
+        ctx: Context<ProposeInsuranceChange>,
+        change: InsuranceChange,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        launch.validate_governance_action(&change.as_governance_action())?;
+
+        let effective_at = math::add_secs(Clock::get()?.unix_timestamp, INSURANCE_CHANGE_DELAY)?;
+        launch.set_pending_insurance_change(Some(PendingInsuranceChange { change, effective_at }))?;
+
+        emit!(InsuranceChangeProposed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            change,
+            effective_at,
+        });
+
+        msg!("Insurance change {:?} proposed, effective at {}", change, effective_at);
+        Ok(())
+    }
+
+    /// Apply the pending insurance change once its delay has passed
+    /// (permissionless). It is re-validated against the current wallets.
+    pub fn execute_insurance_change(ctx: Context<ExecuteInsuranceChange>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let pending = launch
+            .pending_insurance_change()
+            .ok_or(TokenLaunchError::NoPendingInsuranceChange)?;
+        require!(
+            Clock::get()?.unix_timestamp >= pending.effective_at,
+            TokenLaunchError::InsuranceChangeDelayActive
+        );
+
+        let action = pending.change.as_governance_action();
+        launch.validate_governance_action(&action)?;
+        launch.apply_governance_action(&action)?;
+        launch.set_pending_insurance_change(None)?;
+
+        emit!(InsuranceChangeExecuted {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            change: pending.change,
+        });
+
+        msg!("Insurance change {:?} executed", pending.change);
+        Ok(())
+    }
+
+    /// Pause trading for up to the config's max pause (creator only).
+    /// Pauses are capped in number and spaced by a cooldown.
+    pub fn pause_trading(ctx: Context<SetTradingPause>, duration: i64) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        let policy = ctx.accounts.config.pause_policy;
+        launch.pause_trading(&policy, duration, Clock::get()?.unix_timestamp)?;
+
+        emit!(TradingPaused {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            paused_until: launch.paused_until,
+            pauses_left: policy.max_pauses - launch.pause_count,
+        });
+
+        msg!("Trading paused until {}", launch.paused_until);
+        Ok(())
+    }
+
+    /// Resume trading before the pause runs out (creator only)
+    pub fn resume_trading(ctx: Context<SetTradingPause>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        launch.resume_trading(Clock::get()?.unix_timestamp)?;
+
+        emit!(TradingResumed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+        });
+
+        msg!("Trading resumed");
+        Ok(())
+    }
+
+    /// Set the pool authorities transfers must trade against for the first
+    /// `days` after unlock (creator and escrow co-sign). The window can only
+    /// be changed before the timelock ends; pools can be updated any time.
+    pub fn set_dex_allowlist(
+        ctx: Context<SetDexAllowlist>,
+        pools: Vec<Pubkey>,
+        days: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.escrow_authority.key() == ctx.accounts.config.escrow_authority,
+            TokenLaunchError::UnauthorizedEscrow
+        );
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+        require!(
+            pools.len() <= MAX_DEX_ALLOWLIST
+                && days <= MAX_DEX_ALLOWLIST_DAYS
+                && (days == 0 || !pools.is_empty()),
+            TokenLaunchError::InvalidDexAllowlist
+        );
+        if days != launch.dex_allowlist_days {
+            require!(
+                !launch.is_timelock_expired(Clock::get()?.unix_timestamp),
+                TokenLaunchError::DexAllowlistLocked
+            );
+        }
+
+        launch.set_dex_allowlist(&pools)?;
+        launch.dex_allowlist_days = days;
+
+        emit!(DexAllowlistUpdated {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            pools: launch.dex_allowlist().to_vec(),
+            days,
+        });
+
+        msg!("DEX allowlist: {} pools for {} days after unlock", launch.dex_allowlist().len(), days);
+        Ok(())
+    }
+
+    /// Let a session key (e.g. a market-making bot) call `transfer_tokens`
+    /// from the owner's account, up to `amount` tokens until `expires_at`.
+    /// Approving again replaces the delegate and allowance.
+    pub fn approve_delegate(
+        ctx: Context<ApproveDelegate>,
+        delegate: Pubkey,
+        amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        require_keys_neq!(delegate, owner, TokenLaunchError::InvalidDelegation);
+        require!(
+            amount > 0 && expires_at > Clock::get()?.unix_timestamp,
+            TokenLaunchError::InvalidDelegation
+        );
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.owner = owner;
+        delegation.token_account = ctx.accounts.token_account.key();
+        delegation.delegate = delegate;
+        delegation.remaining = amount;
+        delegation.expires_at = expires_at;
+        delegation.bump = ctx.bumps.delegation;
+
+        // The delegation PDA becomes the account's SPL delegate for the cap
+        token_interface::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Approve {
+                    to: ctx.accounts.token_account.to_account_info(),
+                    delegate: delegation.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(DelegateApproved {
+            token_mint: ctx.accounts.token_mint.key(),
+            token_account: delegation.token_account,
+            delegate,
+            amount,
+            expires_at,
+        });
+
+        msg!("Delegate {} approved for {} tokens until {}", delegate, amount, expires_at);
+        Ok(())
+    }
+
+    /// Revoke a session key and close its delegation (owner only)
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Revoke {
+                source: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ))?;
+
+        let delegation = &ctx.accounts.delegation;
+        emit!(DelegateRevoked {
+            token_mint: ctx.accounts.token_mint.key(),
+            token_account: delegation.token_account,
+            delegate: delegation.delegate,
+            unspent: delegation.remaining,
+        });
+
+        msg!("Delegate {} revoked", delegation.delegate);
+        Ok(())
+    }
+
+    /// Replace the arbiter panel (admin only). A non-empty panel makes
+    /// suspensions, relocks and appeal rulings need `threshold` arbiter
+    /// approvals instead of the escrow key; an empty one restores the key.
+    pub fn set_arbiter_panel(
+        ctx: Context<SetArbiterPanel>,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ArbiterPanel::is_valid(&arbiters, threshold),
+            TokenLaunchError::InvalidArbiterPanel
+        );
+
+        let panel = &mut ctx.accounts.arbiter_panel;
+        panel.arbiters = arbiters;
+        panel.threshold = threshold;
+        panel.bump = ctx.bumps.arbiter_panel;
+        ctx.accounts.config.arbitration_required = threshold > 0;
+
+        msg!("Arbiter panel: {}-of-{}", threshold, panel.arbiters.len());
+        Ok(())
+    }
+
+    /// Propose an escrow action against a launch (arbiters only). The
+    /// proposer's approval counts toward the threshold.
+    pub fn propose_arbitration(
+        ctx: Context<ProposeArbitration>,
+        launch: Pubkey,
+        action: ArbitrationAction,
+    ) -> Result<()> {
+        let panel = &mut ctx.accounts.arbiter_panel;
+        let proposer = ctx.accounts.arbiter.key();
+        panel.verify_arbiter(&proposer)?;
+        let clock = Clock::get()?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = panel.proposal_count;
+        proposal.launch = launch;
+        proposal.action = action;
+        proposal.proposer = proposer;
+        proposal.approvals = vec![proposer];
+        proposal.threshold = panel.threshold;
+        proposal.expires_at = math::add_secs(clock.unix_timestamp, PROPOSAL_EXPIRY)?;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        panel.proposal_count = math::add(panel.proposal_count, 1)?;
+
+        emit!(ArbitrationProposed {
+            proposal_id: proposal.proposal_id,
+            launch,
+            action,
+            proposer,
+            expires_at: proposal.expires_at,
+        });
+
+        msg!("Arbitration #{}: {:?} on {}", proposal.proposal_id, action, launch);
+        msg!("Approvals: {}/{}", proposal.approvals.len(), proposal.threshold);
+        Ok(())
+    }
+
+    /// Approve a pending arbitration proposal (arbiters only)
+    pub fn approve_arbitration(ctx: Context<ApproveArbitration>) -> Result<()> {
+        let arbiter = ctx.accounts.arbiter.key();
+        ctx.accounts.arbiter_panel.verify_arbiter(&arbiter)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.approve(arbiter, Clock::get()?.unix_timestamp)?;
+
+        emit!(ArbitrationApproved {
+            proposal_id: proposal.proposal_id,
+            arbiter,
+            approvals: proposal.approvals.len() as u8,
+        });
+
+        msg!("Approvals: {}/{}", proposal.approvals.len(), proposal.threshold);
+        Ok(())
+    }
+
+    /// Queue a name, symbol or URI change for the token's Metaplex metadata
+    /// (creator only). At most one proposal per METADATA_UPDATE_INTERVAL;
+    /// escrow can veto it until METADATA_VETO_WINDOW has passed.
+    pub fn update_launch_metadata(
+        ctx: Context<UpdateLaunchMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.verify_creator(&ctx.accounts.creator.key())?;
+
+        let update = &mut ctx.accounts.metadata_update;
+        update.launch = ctx.accounts.token_launch.key();
+        update.bump = ctx.bumps.metadata_update;
+        update.propose(name, symbol, uri, Clock::get()?.unix_timestamp)?;
+
+        emit!(MetadataUpdateProposed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            name: update.name.clone(),
+            symbol: update.symbol.clone(),
+            uri: update.uri.clone(),
+            effective_at: update.effective_at,
+        });
+
+        msg!("Metadata update proposed, effective at {}", update.effective_at);
+        Ok(())
+    }
+
+    /// Veto a pending metadata update inside its veto window (escrow only)
+    pub fn veto_metadata_update(ctx: Context<VetoMetadataUpdate>) -> Result<()> {
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        let clock = Clock::get()?;
+        ctx.accounts.config.check_escrow(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.arbitration.as_deref(),
+            &ctx.accounts.token_launch.key(),
+            &ArbitrationAction::VetoMetadataUpdate,
+            clock.unix_timestamp,
+            TokenLaunchError::UnauthorizedArbiter,
+        )?;
+        if let Some(arbitration) = &mut ctx.accounts.arbitration {
+            arbitration.executed = true;
+        }
+
+        let update = &mut ctx.accounts.metadata_update;
+        require!(update.pending, TokenLaunchError::NoPendingMetadataUpdate);
+        require!(
+            clock.unix_timestamp < update.effective_at,
+            TokenLaunchError::MetadataVetoWindowClosed
+        );
+        update.pending = false;
+
+        emit!(MetadataUpdateVetoed {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+        });
+
+        msg!("Metadata update vetoed");
+        Ok(())
+    }
+
+    /// Write the pending metadata update to Metaplex once its veto window
+    /// has passed (permissionless). The launch PDA signs as update authority.
+    pub fn apply_metadata_update(ctx: Context<ApplyMetadataUpdate>) -> Result<()> {
+        let update = &mut ctx.accounts.metadata_update;
+        update.check_applicable(Clock::get()?.unix_timestamp)?;
+
+        let launch = ctx.accounts.token_launch.load()?;
+        let bump = [ctx.bumps.token_launch];
+        let signer_seeds: &[&[&[u8]]] = &[&launch.signer_seeds(&bump)];
+        let cpi_accounts = UpdateMetadataAccountsV2 {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            update_authority: ctx.accounts.token_launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.metadata_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let data = DataV2 {
+            name: update.name.clone(),
+            symbol: update.symbol.clone(),
+            uri: update.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+        update_metadata_accounts_v2(cpi_ctx, None, Some(data), None, None)?;
+        drop(launch);
+
+        let launch = &mut ctx.accounts.token_launch.load_mut()?;
+        launch.set_token_name(&update.name)?;
+        launch.set_token_symbol(&update.symbol)?;
+        launch.set_metadata_uri(&update.uri)?;
+        update.pending = false;
+
+        emit!(LaunchMetadataUpdated {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            name: update.name.clone(),
+            symbol: update.symbol.clone(),
+            uri: update.uri.clone(),
+        });
+
+        msg!("Metadata updated: {} ({})", launch.token_name(), launch.token_symbol());
+        Ok(())
+    }
+}
+
+// Account Contexts
+#[derive(Accounts)]
+pub struct InitializeLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = TokenLaunch::space(),
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorIndex::space(),
+        seeds = [seeds::CREATOR_INDEX, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_index: Account<'info, CreatorIndex>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::space(),
+        seeds = [seeds::CREATOR_PROFILE, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = LaunchRegistry::space(),
+        seeds = [seeds::LAUNCH_REGISTRY],
+        bump
+    )]
+    pub launch_registry: Account<'info, LaunchRegistry>,
+    
+    /// The page the next registry entry lands in
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RegistryPage::space(),
+        seeds = [seeds::REGISTRY_PAGE, &launch_registry.next_page().to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+    
+    /// CHECK: Token mint account
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Receives the referral share of the launch fee; must match `params.referrer`
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+    
+    /// Required while the config names a KYC attestor
+    #[account(
+        seeds = [seeds::KYC_ATTESTATION, creator.key().as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// Required when the launch has a bootstrap window
+    #[account(
+        init,
+        payer = creator,
+        space = BootstrapGuard::space(),
+        seeds = [seeds::BOOTSTRAP_GUARD, token_launch.key().as_ref()],
+        bump
+    )]
+    pub bootstrap_guard: Option<Account<'info, BootstrapGuard>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateToken<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    /// The creator must hold mint authority, with nothing minted yet, so the
+    /// vault receives the entire supply
+    #[account(
+        mut,
+        mint::authority = creator,
+        constraint = token_mint.supply == 0 @ TokenLaunchError::MintAlreadyHasSupply
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = token_launch,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Metadata PDA, created by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), token_mint.key().as_ref()],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub metadata: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, shard: u8)]
+pub struct TransferTokens<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Read-only so transfers of one mint do not serialize on the launch
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// The authority's own account; vault releases go through release_tokens
+    #[account(
+        mut,
+        token::mint = token_mint,
+        constraint = from_token_account.owner != token_launch.key() @ TokenLaunchError::VaultMismatch
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Blacklist PDA for the source owner; must not exist
+    #[account(
+        seeds = [seeds::BLACKLIST, token_launch.key().as_ref(), from_token_account.owner.as_ref()],
+        bump,
+        constraint = from_blacklist.data_is_empty() @ TokenLaunchError::WalletBlacklisted
+    )]
+    pub from_blacklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Blacklist PDA for the destination owner; must not exist
+    #[account(
+        seeds = [seeds::BLACKLIST, token_launch.key().as_ref(), to_token_account.owner.as_ref()],
+        bump,
+        constraint = to_blacklist.data_is_empty() @ TokenLaunchError::WalletBlacklisted
+    )]
+    pub to_blacklist: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeShard::space(),
+        seeds = [seeds::FEE_SHARD, token_launch.key().as_ref(), &[shard]],
+        bump,
+        constraint = shard < FEE_SHARDS @ TokenLaunchError::InvalidFeeShard
+    )]
+    pub fee_shard: Account<'info, FeeShard>,
+    
+    /// Activity counters for the same shard
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = LaunchStats::space(),
+        seeds = [seeds::LAUNCH_STATS, token_launch.key().as_ref(), &[shard]],
+        bump
+    )]
+    pub launch_stats: Account<'info, LaunchStats>,
+    
+    /// Treasury-owned account for the mint, required when the launch
+    /// charges a transfer tax
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: The launch's bootstrap guard, required when it has a bootstrap
+    /// window. Writable while the window is open; read-only afterwards so
+    /// transfers parallelize again.
+    #[account(seeds = [seeds::BOOTSTRAP_GUARD, token_launch.key().as_ref()], bump)]
+    pub bootstrap_guard: Option<UncheckedAccount<'info>>,
+    
+    /// Session key delegation for the source account; `authority` is then
+    /// the delegate key rather than the owner
+    #[account(
+        mut,
+        seeds = [seeds::DELEGATION, from_token_account.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTokens<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch,
+        has_one = vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Blacklist PDA for the destination owner; must not exist
+    #[account(
+        seeds = [seeds::BLACKLIST, token_launch.key().as_ref(), to_token_account.owner.as_ref()],
+        bump,
+        constraint = to_blacklist.data_is_empty() @ TokenLaunchError::WalletBlacklisted
+    )]
+    pub to_blacklist: UncheckedAccount<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch,
+        has_one = vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = InsuranceWalletRecord::space(),
+        seeds = [seeds::INSURANCE_WALLET, token_launch.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub wallet_record: Account<'info, InsuranceWalletRecord>,
+    
+    #[account(
+        seeds = [seeds::INSURANCE_COLLATERAL, token_launch.key().as_ref(), authority.key().as_ref()],
+        bump = insurance_collateral.bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = WithdrawalRecord::space(),
+        seeds = [
+            seeds::WITHDRAWAL_RECORD,
+            token_launch.key().as_ref(),
+            &token_launch.load()?.withdrawal_record_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal_record: Account<'info, WithdrawalRecord>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RelockTokens<'info> {
+    #[account(mut)]
+    pub escrow_authority: Signer<'info>,
+    
+    /// Creator co-signature, required for relocks past the consent horizon
+    pub creator: Option<Signer<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Quorum-approved arbitration proposal, required once the config
+    /// has an arbiter panel
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbitration.proposal_id.to_le_bytes()],
+        bump = arbitration.bump
+    )]
+    pub arbitration: Option<Account<'info, ArbitrationProposal>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_score: u16, model_version: u32)]
+pub struct UpdateFraudScore<'info> {
+    #[account(mut)]
+    pub ai_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::ORACLE_REGISTRY],
+        bump = oracle_registry.bump,
+        constraint = oracle_registry.is_authorized(&ai_authority.key()) @ TokenLaunchError::UnauthorizedOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [seeds::SCORING_MODEL, &model_version.to_le_bytes()],
+        bump = scoring_model.bump
+    )]
+    pub scoring_model: Account<'info, ScoringModel>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// The creator's history; pass it whenever it exists so a launch
+    /// turning high-risk is recorded against them
+    #[account(
+        mut,
+        seeds = [seeds::CREATOR_PROFILE, token_launch.load()?.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Option<Account<'info, CreatorProfile>>,
+    
+    #[account(
+        init_if_needed,
+        payer = ai_authority,
+        space = FraudScoreHistory::space(),
+        seeds = [seeds::FRAUD_SCORE_HISTORY, token_launch.key().as_ref()],
+        bump
+    )]
+    pub score_history: Account<'info, FraudScoreHistory>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SuspendLaunch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Quorum-approved arbitration proposal, required once the config
+    /// has an arbiter panel
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbitration.proposal_id.to_le_bytes()],
+        bump = arbitration.bump
+    )]
+    pub arbitration: Option<Account<'info, ArbitrationProposal>>,
+}
+
+#[derive(Accounts)]
+pub struct BatchEscrowAction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    // remaining_accounts: writable TokenLaunch PDAs
+}
+
+#[derive(Accounts)]
+#[instruction(new_scores: Vec<u16>, model_version: u32)]
+pub struct BatchFraudScoreUpdate<'info> {
+    pub ai_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::ORACLE_REGISTRY],
+        bump = oracle_registry.bump,
+        constraint = oracle_registry.is_authorized(&ai_authority.key()) @ TokenLaunchError::UnauthorizedOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    #[account(
+        seeds = [seeds::SCORING_MODEL, &model_version.to_le_bytes()],
+        bump = scoring_model.bump
+    )]
+    pub scoring_model: Account<'info, ScoringModel>,
+    // remaining_accounts: writable (TokenLaunch, FraudScoreHistory) PDA pairs
+}
+
+#[derive(Accounts)]
+pub struct AnnotateLaunch<'info> {
+    #[account(mut)]
+    pub escrow_authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init_if_needed,
+        payer = escrow_authority,
+        space = LaunchAnnotations::space(),
+        seeds = [seeds::ANNOTATIONS, token_mint.key().as_ref()],
+        bump
+    )]
+    pub annotations: Account<'info, LaunchAnnotations>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateLaunchTemplate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = LaunchTemplate::space(),
+        seeds = [seeds::LAUNCH_TEMPLATE, owner.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, LaunchTemplate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLaunchTemplate<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = owner @ TokenLaunchError::UnauthorizedTemplateOwner,
+        seeds = [seeds::LAUNCH_TEMPLATE, owner.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, LaunchTemplate>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = WithdrawalProposal::space(),
+        seeds = [
+            seeds::WITHDRAWAL_PROPOSAL,
+            token_launch.key().as_ref(),
+            &token_launch.load()?.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    #[account(
+        seeds = [seeds::INSURANCE_COLLATERAL, token_launch.key().as_ref(), proposer.key().as_ref()],
+        bump = insurance_collateral.bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(token::mint = token_mint)]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveWithdrawal<'info> {
+    pub approver: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::WITHDRAWAL_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    #[account(
+        seeds = [seeds::INSURANCE_COLLATERAL, token_launch.key().as_ref(), approver.key().as_ref()],
+        bump = insurance_collateral.bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::WITHDRAWAL_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = InsuranceWalletRecord::space(),
+        seeds = [seeds::INSURANCE_WALLET, token_launch.key().as_ref(), proposal.proposer.as_ref()],
+        bump
+    )]
+    pub wallet_record: Account<'info, InsuranceWalletRecord>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = WithdrawalRecord::space(),
+        seeds = [
+            seeds::WITHDRAWAL_RECORD,
+            token_launch.key().as_ref(),
+            &token_launch.load()?.withdrawal_record_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal_record: Account<'info, WithdrawalRecord>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = proposal.destination)]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeInsuranceCollateral<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = InsuranceCollateralPool::space(),
+        seeds = [seeds::INSURANCE_COLLATERAL_POOL, token_launch.key().as_ref()],
+        bump
+    )]
+    pub collateral_pool: Account<'info, InsuranceCollateralPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = InsuranceCollateral::space(),
+        seeds = [seeds::INSURANCE_COLLATERAL, token_launch.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(withdrawal_seq: u64)]
+pub struct SlashInsuranceCollateral<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::WITHDRAWAL_RECORD, token_launch.key().as_ref(), &withdrawal_seq.to_le_bytes()],
+        bump = withdrawal_record.bump
+    )]
+    pub withdrawal_record: Account<'info, WithdrawalRecord>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_COLLATERAL_POOL, token_launch.key().as_ref()],
+        bump = collateral_pool.bump
+    )]
+    pub collateral_pool: Account<'info, InsuranceCollateralPool>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::INSURANCE_COLLATERAL,
+            token_launch.key().as_ref(),
+            withdrawal_record.wallet.as_ref()
+        ],
+        bump = insurance_collateral.bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Quorum-approved arbitration proposal, required once the config
+    /// has an arbiter panel
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbitration.proposal_id.to_le_bytes()],
+        bump = arbitration.bump
+    )]
+    pub arbitration: Option<Account<'info, ArbitrationProposal>>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimInsuranceCollateral<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    /// CHECK: Closed by close_launch; only its address seeds the accounts below
+    #[account(seeds = [seeds::LAUNCH, token_mint.key().as_ref()], bump)]
+    pub token_launch: UncheckedAccount<'info>,
+    
+    /// CHECK: Only its address seeds the launch and archive
+    pub token_mint: UncheckedAccount<'info>,
+    
+    /// Written by close_launch, so its existence proves the launch is closed
+    #[account(seeds = [seeds::ARCHIVE, token_mint.key().as_ref()], bump = launch_archive.bump)]
+    pub launch_archive: Account<'info, LaunchArchive>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_COLLATERAL_POOL, token_launch.key().as_ref()],
+        bump = collateral_pool.bump
+    )]
+    pub collateral_pool: Account<'info, InsuranceCollateralPool>,
+    
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [seeds::INSURANCE_COLLATERAL, token_launch.key().as_ref(), wallet.key().as_ref()],
+        bump = insurance_collateral.bump
+    )]
+    pub insurance_collateral: Account<'info, InsuranceCollateral>,
+}
+
+#[derive(Accounts)]
+pub struct PrepareRecipient<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Any wallet may receive tokens; only used as the ATA owner
+    pub recipient: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateToken2022Mint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// New mint keypair, created and initialized in the instruction
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
+    
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = native_mint,
+        token::authority = token_launch,
+        seeds = [seeds::WSOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub wsol_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(address = spl_token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::WSOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub wsol_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spl_token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeCreatorTransfer<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCreatorTransfer<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCreatorTransfer<'info> {
+    #[account(mut)]
+    pub new_creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        constraint = token_launch.load()?.pending_creator() == Some(new_creator.key())
+            @ TokenLaunchError::NotPendingCreator
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CREATOR_INDEX, token_launch.load()?.creator.as_ref()],
+        bump
+    )]
+    pub old_creator_index: Account<'info, CreatorIndex>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_creator,
+        space = CreatorIndex::space(),
+        seeds = [seeds::CREATOR_INDEX, new_creator.key().as_ref()],
+        bump
+    )]
+    pub new_creator_index: Account<'info, CreatorIndex>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = GovernanceProposal::space(),
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &token_launch.load()?.governance_proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        token::mint = token_mint,
+        token::authority = proposal,
+        seeds = [seeds::VOTE_ESCROW, proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(token::mint = token_mint)]
+    pub proposer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::space(),
+        seeds = [seeds::VOTE_RECORD, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VOTE_ESCROW, proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = voter)]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteGovernanceProposal<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct WithdrawVoteTokens<'info> {
+    pub voter: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VOTE_RECORD, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VOTE_ESCROW, proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = voter)]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct PresaleBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PresalePurchase::space(),
+        seeds = [seeds::PRESALE_PURCHASE, token_launch.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase: Account<'info, PresalePurchase>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = buyer)]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = LaunchStats::space(),
+        seeds = [seeds::LAUNCH_STATS, token_launch.key().as_ref(), &[SALE_STATS_SHARD]],
+        bump
+    )]
+    pub launch_stats: Account<'info, LaunchStats>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizePresale<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitBondingCurve<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = BondingCurve::space(),
+        seeds = [seeds::BONDING_CURVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CURVE_RESERVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_reserve: SystemAccount<'info>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = token_launch,
+        seeds = [seeds::CURVE_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub curve_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CurveTrade<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::BONDING_CURVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CURVE_RESERVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_reserve: SystemAccount<'info>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CURVE_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub curve_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = trader)]
+    pub trader_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = LaunchStats::space(),
+        seeds = [seeds::LAUNCH_STATS, token_launch.key().as_ref(), &[SALE_STATS_SHARD]],
+        bump
+    )]
+    pub launch_stats: Account<'info, LaunchStats>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GraduateBondingCurve<'info> {
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::BONDING_CURVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CURVE_RESERVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_reserve: SystemAccount<'info>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CURVE_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub curve_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAndLockLiquidity<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = native_mint,
+        token::authority = token_launch,
+        seeds = [seeds::WSOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub wsol_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+    
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = lp_mint,
+        token::authority = token_launch,
+        seeds = [seeds::LP_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = LiquidityLock::space(),
+        seeds = [seeds::LIQUIDITY_LOCK, token_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    
+    /// CHECK: Checked against the AMM allowlist in instruction
+    pub amm_program: AccountInfo<'info>,
+    
+    #[account(address = spl_token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLiquidity<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LIQUIDITY_LOCK, token_mint.key().as_ref()],
+        bump,
+        has_one = lp_mint
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LP_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = lp_mint)]
+    pub creator_lp_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = GlobalConfig::space(),
+        seeds = [seeds::CONFIG],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Treasury::space(),
+        seeds = [seeds::TREASURY],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SoldTokenLaunch>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ TokenLaunchError::UnauthorizedAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin,
+        has_one = fee_recipient,
+        has_one = insurance_pool
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Protocol share destination, matched against global config
+    #[account(mut)]
+    pub fee_recipient: AccountInfo<'info>,
+    
+    /// CHECK: Insurance pool share destination, matched against global config
+    #[account(mut)]
+    pub insurance_pool: AccountInfo<'info>,
+    
+    /// CHECK: Creator rebate destination, matched against the launch
+    #[account(mut, address = token_launch.load()?.creator)]
+    pub creator: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = OracleRegistry::space(),
+        seeds = [seeds::ORACLE_REGISTRY],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOracles<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::ORACLE_REGISTRY], bump = oracle_registry.bump)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct DeployTreasuryYield<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    /// Venue share token (e.g. mSOL)
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = share_mint,
+        token::authority = token_launch,
+        seeds = [seeds::YIELD_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = YieldPosition::space(),
+        seeds = [seeds::YIELD_POSITION, token_mint.key().as_ref()],
+        bump
+    )]
+    pub yield_position: Account<'info, YieldPosition>,
+    
+    /// CHECK: Checked against the yield venue allowlist in instruction
+    pub venue_program: AccountInfo<'info>,
+    
+    #[account(address = spl_token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct UnwindTreasuryYield<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Closed on use, so one approval unwinds once
+    #[account(
+        mut,
+        close = executor,
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::YIELD_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::YIELD_POSITION, token_mint.key().as_ref()],
+        bump = yield_position.bump
+    )]
+    pub yield_position: Account<'info, YieldPosition>,
+    
+    /// CHECK: Must be the venue the position was deployed to
+    #[account(address = yield_position.venue @ TokenLaunchError::UnsupportedYieldVenue)]
+    pub venue_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FileAppeal<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = AppealState::space(),
+        seeds = [seeds::APPEAL, token_launch.key().as_ref()],
+        bump
+    )]
+    pub appeal: Account<'info, AppealState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAppeal<'info> {
+    pub escrow_authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::APPEAL, token_launch.key().as_ref()],
+        bump = appeal.bump
+    )]
+    pub appeal: Account<'info, AppealState>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Quorum-approved arbitration proposal, required once the config
+    /// has an arbiter panel
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbitration.proposal_id.to_le_bytes()],
+        bump = arbitration.bump
+    )]
+    pub arbitration: Option<Account<'info, ArbitrationProposal>>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::APPEAL, token_launch.key().as_ref()],
+        bump = appeal.bump
+    )]
+    pub appeal: Account<'info, AppealState>,
+}
+
+#[derive(Accounts)]
+pub struct Underwrite<'info> {
+    #[account(mut)]
+    pub underwriter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = underwriter,
+        space = UnderwritingPool::space(),
+        seeds = [seeds::UNDERWRITING, token_launch.key().as_ref()],
+        bump
+    )]
+    pub underwriting_pool: Account<'info, UnderwritingPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = underwriter,
+        space = UnderwriterStake::space(),
+        seeds = [seeds::UNDERWRITER, token_launch.key().as_ref(), underwriter.key().as_ref()],
+        bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnderwriting<'info> {
+    #[account(mut)]
+    pub underwriter: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::UNDERWRITING, token_launch.key().as_ref()],
+        bump = underwriting_pool.bump
+    )]
+    pub underwriting_pool: Account<'info, UnderwritingPool>,
+    
+    #[account(
+        mut,
+        close = underwriter,
+        seeds = [seeds::UNDERWRITER, token_launch.key().as_ref(), underwriter.key().as_ref()],
+        bump = underwriter_stake.bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+}
+
+#[derive(Accounts)]
+pub struct SlashUnderwriting<'info> {
+    pub escrow_authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::UNDERWRITING, token_launch.key().as_ref()],
+        bump = underwriting_pool.bump
+    )]
+    pub underwriting_pool: Account<'info, UnderwritingPool>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimGuarantee<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = holder
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::UNDERWRITING, token_launch.key().as_ref()],
+        bump = underwriting_pool.bump
+    )]
+    pub underwriting_pool: Account<'info, UnderwritingPool>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetRecoveryPolicy<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RecoveryPolicy::space(),
+        seeds = [seeds::RECOVERY, token_launch.key().as_ref()],
+        bump
+    )]
+    pub recovery_policy: Account<'info, RecoveryPolicy>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianRecoveryAction<'info> {
+    #[account(constraint = recovery_policy.guardians.contains(&guardian.key()) @ TokenLaunchError::UnauthorizedGuardian)]
+    pub guardian: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::RECOVERY, token_launch.key().as_ref()],
+        bump = recovery_policy.bump
+    )]
+    pub recovery_policy: Account<'info, RecoveryPolicy>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::RECOVERY, token_launch.key().as_ref()],
+        bump = recovery_policy.bump
+    )]
+    pub recovery_policy: Account<'info, RecoveryPolicy>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    /// CHECK: Must match the recovery's new creator
+    pub new_creator: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::RECOVERY, token_launch.key().as_ref()],
+        bump = recovery_policy.bump
+    )]
+    pub recovery_policy: Account<'info, RecoveryPolicy>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CREATOR_INDEX, token_launch.load()?.creator.as_ref()],
+        bump
+    )]
+    pub old_creator_index: Account<'info, CreatorIndex>,
+    
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = CreatorIndex::space(),
+        seeds = [seeds::CREATOR_INDEX, new_creator.key().as_ref()],
+        bump
+    )]
+    pub new_creator_index: Account<'info, CreatorIndex>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey, kind: ClaimKind)]
+pub struct CreateAllocation<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = token_launch,
+        seeds = [seeds::ALLOCATION_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub allocation_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = Allocation::space(),
+        seeds = [seeds::ALLOCATION, token_launch.key().as_ref(), beneficiary.as_ref(), &[kind as u8]],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+    pub beneficiary: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ALLOCATION_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub allocation_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Relayer paying the transaction fee, reimbursed from the sponsor pool
+    #[account(mut)]
+    pub fee_payer: Option<Signer<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SPONSOR_POOL, token_launch.key().as_ref()],
+        bump = sponsor_pool.bump
+    )]
+    pub sponsor_pool: Option<Account<'info, SponsorPool>>,
+    
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = SponsoredWallet::space(),
+        seeds = [seeds::SPONSORED_WALLET, token_launch.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub sponsored_wallet: Option<Account<'info, SponsoredWallet>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (Allocation PDA, destination token account) pairs,
+    // then transfer hook accounts
+}
+
+#[derive(Accounts)]
+pub struct MigrateFraudScore<'info> {
+    /// CHECK: Legacy TokenLaunch layout that no longer deserializes; the
+    /// handler checks the discriminator and size before rewriting it
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureSponsorship<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = SponsorPool::space(),
+        seeds = [seeds::SPONSOR_POOL, token_launch.key().as_ref()],
+        bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundSponsorship<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SPONSOR_POOL, token_launch.key().as_ref()],
+        bump = sponsor_pool.bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSponsorship<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SPONSOR_POOL, token_launch.key().as_ref()],
+        bump = sponsor_pool.bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = creator,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CREATOR_INDEX, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_index: Account<'info, CreatorIndex>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = LaunchArchive::space(),
+        seeds = [seeds::ARCHIVE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub launch_archive: Account<'info, LaunchArchive>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateLaunch<'info> {
+    /// CHECK: May be sized for an older TokenLaunch layout that does not
+    /// deserialize yet; the handler resizes it before decoding
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepFeeShards<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Required when the launch reflects fees to holders
+    #[account(mut, constraint = dividend_pool.launch == token_launch.key() @ TokenLaunchError::DividendPoolRequired)]
+    pub dividend_pool: Option<Account<'info, DividendPool>>,
+    // remaining_accounts: the launch's FeeShard PDAs, writable
+}
+
+#[derive(Accounts)]
+#[instruction(version: u32)]
+pub struct RegisterModel<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = ScoringModel::space(),
+        seeds = [seeds::SCORING_MODEL, &version.to_le_bytes()],
+        bump
+    )]
+    pub scoring_model: Account<'info, ScoringModel>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateEmergencyWithdraw<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    /// The wallet's withdrawal record, if it has withdrawn before
+    #[account(
+        seeds = [seeds::INSURANCE_WALLET, token_launch.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub wallet_record: Option<Account<'info, InsuranceWalletRecord>>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateRelock<'info> {
+    pub escrow_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateTransferTax<'info> {
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct EstimateLaunchFees<'info> {
+    /// CHECK: The prospective creator; only its PDAs are read
+    pub creator: UncheckedAccount<'info>,
+    
+    /// CHECK: Creator index PDA; charged for if it does not exist yet
+    #[account(seeds = [seeds::CREATOR_INDEX, creator.key().as_ref()], bump)]
+    pub creator_index: UncheckedAccount<'info>,
+    
+    /// CHECK: Creator profile PDA; read for the repeat-creator discount
+    #[account(seeds = [seeds::CREATOR_PROFILE, creator.key().as_ref()], bump)]
+    pub creator_profile: UncheckedAccount<'info>,
+    
+    /// CHECK: Launch registry PDA; read to tell whether a new page is needed
+    #[account(seeds = [seeds::LAUNCH_REGISTRY], bump)]
+    pub launch_registry: UncheckedAccount<'info>,
+    
+    /// The mint to be launched, if it exists yet; its extensions size the
+    /// vault and holder token accounts
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddToBlacklist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Executed proposal authorizing this change, when not signed by the creator
+    #[account(constraint = governance_proposal.launch == token_launch.key() @ TokenLaunchError::InvalidGovernanceAction)]
+    pub governance_proposal: Option<Account<'info, GovernanceProposal>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = BlacklistEntry::space(),
+        seeds = [seeds::BLACKLIST, token_launch.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+    
+    /// The wallet's token account to freeze, if any
+    #[account(mut, token::mint = token_mint, token::authority = wallet)]
+    pub token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RemoveFromBlacklist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Executed proposal authorizing this change, when not signed by the creator
+    #[account(constraint = governance_proposal.launch == token_launch.key() @ TokenLaunchError::InvalidGovernanceAction)]
+    pub governance_proposal: Option<Account<'info, GovernanceProposal>>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [seeds::BLACKLIST, token_launch.key().as_ref(), wallet.as_ref()],
+        bump = blacklist_entry.bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+    
+    /// The wallet's token account to thaw, if it was frozen
+    #[account(mut, token::mint = token_mint, token::authority = wallet)]
+    pub token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AirdropBatch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch,
+        has_one = vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetLaunchClasses<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin,
+        realloc = GlobalConfig::space(),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct InitStakingPool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = StakingPool::space(),
+        seeds = [seeds::STAKING_POOL, token_launch.key().as_ref()],
+        bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = token_launch,
+        seeds = [seeds::STAKE_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = token_launch,
+        seeds = [seeds::REWARD_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = creator)]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKING_POOL, token_launch.key().as_ref()],
+        bump = staking_pool.bump,
+        has_one = stake_vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = StakePosition::space(),
+        seeds = [seeds::STAKE_POSITION, staking_pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    
+    #[account(mut)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = owner)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKING_POOL, token_launch.key().as_ref()],
+        bump = staking_pool.bump,
+        has_one = stake_vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE_POSITION, staking_pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    
+    #[account(mut)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKING_POOL, token_launch.key().as_ref()],
+        bump = staking_pool.bump,
+        has_one = reward_vault @ TokenLaunchError::VaultMismatch
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE_POSITION, staking_pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    
+    #[account(mut)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct RescueForeignAssets<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [
+            seeds::GOVERNANCE_PROPOSAL,
+            token_launch.key().as_ref(),
+            &proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    /// CHECK: Must match the proposal's recipient
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// Token rescues only: the launch PDA's account holding the foreign mint
+    #[account(mut, token::authority = token_launch)]
+    pub source_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub foreign_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetLaunchWebhook<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = LaunchMetadata::space(),
+        seeds = [seeds::LAUNCH_METADATA, token_mint.key().as_ref()],
+        bump
+    )]
+    pub launch_metadata: Account<'info, LaunchMetadata>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableReflection<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = DividendPool::space(),
+        seeds = [seeds::DIVIDEND_POOL, token_launch.key().as_ref()],
+        bump
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterDividendClaim<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::DIVIDEND_POOL, token_launch.key().as_ref()],
+        bump = dividend_pool.bump
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+    
+    #[account(
+        init,
+        payer = holder,
+        space = DividendClaim::space(),
+        seeds = [seeds::DIVIDEND_CLAIM, token_launch.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub dividend_claim: Account<'info, DividendClaim>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeDividends<'info> {
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::DIVIDEND_POOL, token_launch.key().as_ref()],
+        bump = dividend_pool.bump
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+    // remaining_accounts: per holder, its DividendClaim PDA (writable), token
+    // account and wallet (writable)
+}
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = native_mint,
+        token::authority = token_launch,
+        seeds = [seeds::WSOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub wsol_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Checked against the AMM allowlist in instruction
+    pub amm_program: AccountInfo<'info>,
+    
+    #[account(address = spl_token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// The launch vault or one of the creator's token accounts
+    #[account(mut, token::mint = token_mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUnlockCondition<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: must be the condition's feed and owned by a Pyth program, checked in the handler
+    pub price_feed: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankLaunch<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, seeds = [seeds::TREASURY], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Required for `ExpireProposal`
+    #[account(
+        mut,
+        constraint = proposal.launch == token_launch.key() @ TokenLaunchError::CrankAccountMismatch
+    )]
+    pub proposal: Option<Account<'info, WithdrawalProposal>>,
+    
+    /// CHECK: Rent refund destination, matched against the proposal's proposer
+    #[account(mut)]
+    pub proposer: Option<UncheckedAccount<'info>>,
+    
+    /// Required for `ReactivateLaunch`
+    #[account(
+        mut,
+        seeds = [seeds::APPEAL, token_launch.key().as_ref()],
+        bump = appeal.bump
+    )]
+    pub appeal: Option<Account<'info, AppealState>>,
+    
+    /// CHECK: Bond refund destination, matched against the appeal's appellant
+    #[account(mut)]
+    pub appellant: Option<UncheckedAccount<'info>>,
+    
+    /// Required for `SuspendOnScore`
+    #[account(
+        seeds = [seeds::FRAUD_SCORE_HISTORY, token_launch.key().as_ref()],
+        bump = score_history.bump
+    )]
+    pub score_history: Option<Account<'info, FraudScoreHistory>>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AttestCreator<'info> {
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = KycAttestation::space(),
+        seeds = [seeds::KYC_ATTESTATION, creator.as_ref()],
+        bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct RevokeAttestation<'info> {
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        close = attestor,
+        seeds = [seeds::KYC_ATTESTATION, creator.as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+}
+
+#[derive(Accounts)]
+pub struct InitRaise<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = Raise::space(),
+        seeds = [seeds::RAISE, token_mint.key().as_ref()],
+        bump
+    )]
+    pub raise: Account<'info, Raise>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::RAISE, token_mint.key().as_ref()],
+        bump = raise.bump
+    )]
+    pub raise: Account<'info, Raise>,
+    
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = Contribution::space(),
+        seeds = [seeds::CONTRIBUTION, raise.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRaise<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::RAISE, token_mint.key().as_ref()],
+        bump = raise.bump
+    )]
+    pub raise: Account<'info, Raise>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SOL_VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRaiseTokens<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    #[account(
+        seeds = [seeds::RAISE, token_mint.key().as_ref()],
+        bump = raise.bump
+    )]
+    pub raise: Account<'info, Raise>,
+    
+    /// Closed on claim, so each contribution claims once
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [seeds::CONTRIBUTION, raise.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = contributor)]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::RAISE, token_mint.key().as_ref()],
+        bump = raise.bump
+    )]
+    pub raise: Account<'info, Raise>,
+    
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [seeds::CONTRIBUTION, raise.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateTeamAllocation<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VAULT, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = TeamAllocation::space(),
+        seeds = [seeds::TEAM_ALLOCATION, token_launch.key().as_ref(), beneficiary.as_ref()],
+        bump
+    )]
+    pub team_allocation: Account<'info, TeamAllocation>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = team_allocation,
+        seeds = [seeds::TEAM_VAULT, team_allocation.key().as_ref()],
+        bump
+    )]
+    pub team_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTeamTokens<'info> {
+    pub beneficiary: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TEAM_ALLOCATION, token_launch.key().as_ref(), beneficiary.key().as_ref()],
+        bump = team_allocation.bump
+    )]
+    pub team_allocation: Account<'info, TeamAllocation>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TEAM_VAULT, team_allocation.key().as_ref()],
+        bump
+    )]
+    pub team_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_mint)]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyLogoCnft<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: The logo's concurrent Merkle tree, matched against the launch
+    /// and owned by spl-account-compression, checked in the handler
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: spl-account-compression, checked in the handler
+    pub compression_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeRecipients<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin,
+        realloc = GlobalConfig::space(),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeInsuranceChange<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteInsuranceChange<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct SetTradingPause<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetDexAllowlist<'info> {
+    pub creator: Signer<'info>,
+    
+    pub escrow_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = token_mint @ TokenLaunchError::LaunchMintMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = owner)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Delegation::space(),
+        seeds = [seeds::DELEGATION, token_account.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut, token::mint = token_mint, token::authority = owner)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [seeds::DELEGATION, token_account.key().as_ref()],
+        bump = delegation.bump,
+        has_one = owner @ TokenLaunchError::UnauthorizedDelegate
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetArbiterPanel<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ TokenLaunchError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ArbiterPanel::space(),
+        seeds = [seeds::ARBITER_PANEL],
+        bump
+    )]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeArbitration<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+    
+    #[account(mut, seeds = [seeds::ARBITER_PANEL], bump = arbiter_panel.bump)]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+    
+    #[account(
+        init,
+        payer = arbiter,
+        space = ArbitrationProposal::space(),
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbiter_panel.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, ArbitrationProposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveArbitration<'info> {
+    pub arbiter: Signer<'info>,
+    
+    #[account(seeds = [seeds::ARBITER_PANEL], bump = arbiter_panel.bump)]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, ArbitrationProposal>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLaunchMetadata<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Persists between proposals so the rate limit survives a veto
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = MetadataUpdate::space(),
+        seeds = [seeds::METADATA_UPDATE, token_launch.key().as_ref()],
+        bump
+    )]
+    pub metadata_update: Account<'info, MetadataUpdate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VetoMetadataUpdate<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::METADATA_UPDATE, token_launch.key().as_ref()],
+        bump = metadata_update.bump
+    )]
+    pub metadata_update: Account<'info, MetadataUpdate>,
+    
+    /// Quorum-approved arbitration proposal, required once the config
+    /// has an arbiter panel
+    #[account(
+        mut,
+        seeds = [seeds::ARBITRATION_PROPOSAL, &arbitration.proposal_id.to_le_bytes()],
+        bump = arbitration.bump
+    )]
+    pub arbitration: Option<Account<'info, ArbitrationProposal>>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyMetadataUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = metadata @ TokenLaunchError::MetadataMismatch
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::METADATA_UPDATE, token_launch.key().as_ref()],
+        bump = metadata_update.bump
+    )]
+    pub metadata_update: Account<'info, MetadataUpdate>,
+    
+    /// CHECK: Metaplex metadata PDA recorded by create_token
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+// Data Structures
+/// Launch state, read in place through `AccountLoader` (`#[account(zero_copy)]`).
+/// Every field is fixed width and the struct has no padding: strings and
+/// lists are arrays with a length byte, flags are `u8`, and each optional
+/// setting is a slot holding its Borsh `Option` encoding. Read and write
+/// those through the accessors below rather than the raw fields.
+///
+/// A CPI the launch account takes part in (the launch PDA signing for its
+/// vault, say) fails while the account is mutably borrowed, so handlers
+/// drop their `load_mut` guard before one and read through `load`.
+///
+/// New fields are carved out of `reserved`, so they read back as zero on
+/// existing accounts and `migrate_launch` only has to bump `version`.
+/// Launches written before v19 are in the Borsh `LegacyTokenLaunch` layout
+/// and `migrate_launch` rewrites them into this one.
+#[account(zero_copy)]
+pub struct TokenLaunch {
+    pub creator: Pubkey,
+    pub token_mint: Pubkey,
+    pub total_supply: u64,
+    pub timelock_end: i64,
+    pub fees_collected: u64,
+    pub total_withdrawn: u64,
+    pub timelock_duration: i64,
+    pub proposal_count: u64,
+    pub governance_proposal_count: u64,
+    pub event_seq: u64,
+    pub presale_sold: u64,
+    pub fees_distributed: u64,
+    pub yield_allowance: u64,
+    pub unlocked_so_far: u64,
+    pub launched_at: i64,
+    pub withdrawal_record_count: u64,
+    pub min_timelock_duration: i64,         // 0 = global minimum
+    pub bond: u64,                          // held in this account
+    pub tokens_burned: u64,                 // cumulative burns
+    pub circulating_supply: u64,            // total_supply less burns
+    pub condition_since_slot: u64,          // first slot of the current run above (0 = below)
+    pub team_allocated: u64,                // moved into team vesting accounts
+    pub paused_until: i64,                  // trading paused before this time
+    pub relock_count: u32,
+    pub score_model_version: u32,           // 0 = unattributed
+    pub fraud_score: u16,                   // basis points
+    pub reflection_bps: u16,                // share of trading fees paid to holders
+    pub tranches_released: u16,             // unlock schedule tranches marked by crank_launch
+    pub transfer_tax_bps: u16,              // token tax on transfer_tokens, to the treasury
+    pub dex_allowlist_days: u16,            // post-unlock window; 0 = off
+    pub version: u8,                        // layout version, see migrate_launch
+    pub insurance_limit: u8,
+    pub withdrawal_threshold: u8,
+    pub pause_count: u8,                    // pauses used under the config's PausePolicy
+    pub is_active: u8,                      // flags: 0 or 1
+    pub presale_finalized: u8,
+    pub condition_met: u8,                  // latched once the unlock condition has held
+    pub logo_verified: u8,                  // logo_cnft proven by verify_logo_cnft
+    pub repeat_creator: u8,                 // creator had a clean history at launch
+    pub token_name_len: u8,
+    pub token_symbol_len: u8,
+    pub metadata_uri_len: u8,
+    pub insurance_wallets_len: u8,
+    pub dex_allowlist_len: u8,
+    pub recovery_accounts_len: u8,
+    pub vault: Pubkey,
+    pub metadata: Pubkey,
+    pub score_model_hash: [u8; 32],
+    pub token_name: [u8; MAX_TOKEN_NAME_LEN],
+    pub token_symbol: [u8; MAX_TOKEN_SYMBOL_LEN],
+    pub metadata_uri: [u8; MAX_URI_LEN],
+    pub insurance_wallets: [Pubkey; MAX_INSURANCE_WALLETS],
+    pub dex_allowlist: [Pubkey; MAX_DEX_ALLOWLIST],       // pool authorities
+    pub recovery_accounts: [Pubkey; MAX_RECOVERY_ACCOUNTS], // emergency withdrawal destinations
+    // Optional settings, 1 + the value's size each
+    pub logo_nft: [u8; 33],                 // Option<Pubkey>
+    pub creator_multisig: [u8; 34],         // Option<CreatorMultisig>
+    pub pending_creator: [u8; 33],          // Option<Pubkey>
+    pub max_tx_amount: [u8; 9],             // Option<u64>
+    pub max_wallet_pct: [u8; 2],            // Option<u8>
+    pub presale: [u8; 57],                  // Option<PresaleConfig>
+    pub unlock_schedule: [u8; 4],           // Option<UnlockSchedule>
+    pub relock_consent_days: [u8; 3],       // Option<u16>
+    pub launch_class: [u8; 2],              // Option<u8>, None = global terms
+    pub unlock_condition: [u8; 54],         // Option<UnlockCondition>
+    pub bootstrap: [u8; 26],                // Option<BootstrapConfig>
+    pub logo_cnft: [u8; 37],                // Option<CompressedLogo>, alternative to logo_nft
+    pub pending_insurance_change: [u8; 42], // Option<PendingInsuranceChange>
+    pub reserved: [u8; 123],                // for future fields
+}
+
+/// Read a fixed-width string field
+fn str_slot(bytes: &[u8], len: u8) -> &str {
+    bytes
+        .get(..len as usize)
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Write `value` into a fixed-width string field, returning its length,
+/// or None if it does not fit
+fn write_str_slot(bytes: &mut [u8], value: &str) -> Option<u8> {
+    let len = value.len();
+    if len > bytes.len() {
+        return None;
+    }
+    bytes.fill(0);
+    bytes[..len].copy_from_slice(value.as_bytes());
+    u8::try_from(len).ok()
+}
+
+/// The first `len` keys of a fixed-width key list
+fn key_slots(keys: &[Pubkey], len: u8) -> &[Pubkey] {
+    &keys[..(len as usize).min(keys.len())]
+}
+
+/// Write `values` into a fixed-width key list, returning its length, or
+/// None if they do not fit
+fn write_key_slots(keys: &mut [Pubkey], values: &[Pubkey]) -> Option<u8> {
+    if values.len() > keys.len() {
+        return None;
+    }
+    keys.fill(Pubkey::default());
+    keys[..values.len()].copy_from_slice(values);
+    u8::try_from(values.len()).ok()
+}
+
+/// Decode an optional setting from its slot
+fn option_slot<T: AnchorDeserialize>(slot: &[u8]) -> Option<T> {
+    Option::<T>::deserialize(&mut &slot[..]).ok().flatten()
+}
+
+/// Encode an optional setting into its slot, zeroing the unused tail
+fn write_option_slot<T: AnchorSerialize>(slot: &mut [u8], value: &Option<T>) -> Result<()> {
+    slot.fill(0);
+    value
+        .serialize(&mut &mut slot[..])
+        .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotSerialize))
+}
+
+impl TokenLaunch {
+    pub fn space() -> usize {
+        8 + std::mem::size_of::<TokenLaunch>() // discriminator + fixed layout
+    }
+
+    pub fn token_name(&self) -> &str {
+        str_slot(&self.token_name, self.token_name_len)
+    }
+
+    pub fn set_token_name(&mut self, name: &str) -> Result<()> {
+        self.token_name_len =
+            write_str_slot(&mut self.token_name, name).ok_or(TokenLaunchError::InvalidTokenName)?;
+        Ok(())
+    }
+
+    pub fn token_symbol(&self) -> &str {
+        str_slot(&self.token_symbol, self.token_symbol_len)
+    }
+
+    pub fn set_token_symbol(&mut self, symbol: &str) -> Result<()> {
+        self.token_symbol_len =
+            write_str_slot(&mut self.token_symbol, symbol).ok_or(TokenLaunchError::InvalidTokenSymbol)?;
+        Ok(())
+    }
+
+    pub fn metadata_uri(&self) -> &str {
+        str_slot(&self.metadata_uri, self.metadata_uri_len)
+    }
+
+    pub fn set_metadata_uri(&mut self, uri: &str) -> Result<()> {
+        self.metadata_uri_len =
+            write_str_slot(&mut self.metadata_uri, uri).ok_or(TokenLaunchError::MetadataUriTooLong)?;
+        Ok(())
+    }
+
+    pub fn insurance_wallets(&self) -> &[Pubkey] {
+        key_slots(&self.insurance_wallets, self.insurance_wallets_len)
+    }
+
+    pub fn set_insurance_wallets(&mut self, wallets: &[Pubkey]) -> Result<()> {
+        self.insurance_wallets_len = write_key_slots(&mut self.insurance_wallets, wallets)
+            .ok_or(TokenLaunchError::TooManyInsuranceWallets)?;
+        Ok(())
+    }
+
+    pub fn dex_allowlist(&self) -> &[Pubkey] {
+        key_slots(&self.dex_allowlist, self.dex_allowlist_len)
+    }
+
+    pub fn set_dex_allowlist(&mut self, pools: &[Pubkey]) -> Result<()> {
+        self.dex_allowlist_len = write_key_slots(&mut self.dex_allowlist, pools)
+            .ok_or(TokenLaunchError::InvalidDexAllowlist)?;
+        Ok(())
+    }
+
+    pub fn recovery_accounts(&self) -> &[Pubkey] {
+        key_slots(&self.recovery_accounts, self.recovery_accounts_len)
+    }
+
+    pub fn set_recovery_account_list(&mut self, accounts: &[Pubkey]) -> Result<()> {
+        self.recovery_accounts_len = write_key_slots(&mut self.recovery_accounts, accounts)
+            .ok_or(TokenLaunchError::InvalidRecoveryAccounts)?;
+        Ok(())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active != 0
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.is_active = u8::from(active);
+    }
+
+    pub fn presale_finalized(&self) -> bool {
+        self.presale_finalized != 0
+    }
+
+    pub fn set_presale_finalized(&mut self, finalized: bool) {
+        self.presale_finalized = u8::from(finalized);
+    }
+
+    pub fn condition_met(&self) -> bool {
+        self.condition_met != 0
+    }
+
+    pub fn set_condition_met(&mut self, met: bool) {
+        self.condition_met = u8::from(met);
+    }
+
+    pub fn logo_verified(&self) -> bool {
+        self.logo_verified != 0
+    }
+
+    pub fn set_logo_verified(&mut self, verified: bool) {
+        self.logo_verified = u8::from(verified);
+    }
+
+    pub fn repeat_creator(&self) -> bool {
+        self.repeat_creator != 0
+    }
+
+    pub fn set_repeat_creator(&mut self, repeat: bool) {
+        self.repeat_creator = u8::from(repeat);
+    }
+
+    pub fn logo_nft(&self) -> Option<Pubkey> {
+        option_slot(&self.logo_nft)
+    }
+
+    pub fn set_logo_nft(&mut self, logo_nft: Option<Pubkey>) -> Result<()> {
+        write_option_slot(&mut self.logo_nft, &logo_nft)
+    }
+
+    pub fn creator_multisig(&self) -> Option<CreatorMultisig> {
+        option_slot(&self.creator_multisig)
+    }
+
+    pub fn set_creator_multisig(&mut self, multisig: Option<CreatorMultisig>) -> Result<()> {
+        write_option_slot(&mut self.creator_multisig, &multisig)
+    }
+
+    pub fn pending_creator(&self) -> Option<Pubkey> {
+        option_slot(&self.pending_creator)
+    }
+
+    pub fn set_pending_creator(&mut self, pending: Option<Pubkey>) -> Result<()> {
+        write_option_slot(&mut self.pending_creator, &pending)
+    }
+
+    pub fn max_tx_amount(&self) -> Option<u64> {
+        option_slot(&self.max_tx_amount)
+    }
+
+    pub fn set_max_tx_amount(&mut self, amount: Option<u64>) -> Result<()> {
+        write_option_slot(&mut self.max_tx_amount, &amount)
+    }
+
+    pub fn max_wallet_pct(&self) -> Option<u8> {
+        option_slot(&self.max_wallet_pct)
+    }
+
+    pub fn set_max_wallet_pct(&mut self, pct: Option<u8>) -> Result<()> {
+        write_option_slot(&mut self.max_wallet_pct, &pct)
+    }
+
+    pub fn presale(&self) -> Option<PresaleConfig> {
+        option_slot(&self.presale)
+    }
+
+    pub fn set_presale(&mut self, presale: Option<PresaleConfig>) -> Result<()> {
+        write_option_slot(&mut self.presale, &presale)
+    }
+
+    pub fn unlock_schedule(&self) -> Option<UnlockSchedule> {
+        option_slot(&self.unlock_schedule)
+    }
+
+    pub fn set_unlock_schedule(&mut self, schedule: Option<UnlockSchedule>) -> Result<()> {
+        write_option_slot(&mut self.unlock_schedule, &schedule)
+    }
+
+    pub fn relock_consent_days(&self) -> Option<u16> {
+        option_slot(&self.relock_consent_days)
+    }
+
+    pub fn set_relock_consent_days(&mut self, days: Option<u16>) -> Result<()> {
+        write_option_slot(&mut self.relock_consent_days, &days)
+    }
+
+    pub fn launch_class(&self) -> Option<u8> {
+        option_slot(&self.launch_class)
+    }
+
+    pub fn set_launch_class(&mut self, class: Option<u8>) -> Result<()> {
+        write_option_slot(&mut self.launch_class, &class)
+    }
+
+    pub fn unlock_condition(&self) -> Option<UnlockCondition> {
+        option_slot(&self.unlock_condition)
+    }
+
+    pub fn set_unlock_condition(&mut self, condition: Option<UnlockCondition>) -> Result<()> {
+        write_option_slot(&mut self.unlock_condition, &condition)
+    }
+
+    pub fn bootstrap(&self) -> Option<BootstrapConfig> {
+        option_slot(&self.bootstrap)
+    }
+
+    pub fn set_bootstrap(&mut self, bootstrap: Option<BootstrapConfig>) -> Result<()> {
+        write_option_slot(&mut self.bootstrap, &bootstrap)
+    }
+
+    pub fn logo_cnft(&self) -> Option<CompressedLogo> {
+        option_slot(&self.logo_cnft)
+    }
+
+    pub fn set_logo_cnft(&mut self, logo: Option<CompressedLogo>) -> Result<()> {
+        write_option_slot(&mut self.logo_cnft, &logo)
+    }
+
+    pub fn pending_insurance_change(&self) -> Option<PendingInsuranceChange> {
+        option_slot(&self.pending_insurance_change)
+    }
+
+    pub fn set_pending_insurance_change(&mut self, change: Option<PendingInsuranceChange>) -> Result<()> {
+        write_option_slot(&mut self.pending_insurance_change, &change)
+    }
+
+    /// Rewrite a launch decoded from the Borsh layout used before v19,
+    /// keeping its version so `upgrade_launch_layout` applies the fixes
+    /// due since
+    pub fn from_legacy(legacy: &LegacyTokenLaunch) -> Result<Self> {
+        let mut launch = <Self as bytemuck::Zeroable>::zeroed();
+        launch.creator = legacy.creator;
+        launch.token_mint = legacy.token_mint;
+        launch.set_token_name(&legacy.token_name)?;
+        launch.set_token_symbol(&legacy.token_symbol)?;
+        launch.total_supply = legacy.total_supply;
+        launch.timelock_end = legacy.timelock_end;
+        launch.set_insurance_wallets(&legacy.insurance_wallets)?;
+        launch.insurance_limit = legacy.insurance_limit;
+        launch.set_logo_nft(legacy.logo_nft)?;
+        launch.fraud_score = legacy.fraud_score;
+        launch.fees_collected = legacy.fees_collected;
+        launch.set_active(legacy.is_active);
+        launch.relock_count = legacy.relock_count;
+        launch.total_withdrawn = legacy.total_withdrawn;
+        launch.timelock_duration = legacy.timelock_duration;
+        launch.vault = legacy.vault;
+        launch.withdrawal_threshold = legacy.withdrawal_threshold;
+        launch.proposal_count = legacy.proposal_count;
+        launch.set_metadata_uri(&legacy.metadata_uri)?;
+        launch.metadata = legacy.metadata;
+        launch.set_creator_multisig(legacy.creator_multisig)?;
+        launch.set_pending_creator(legacy.pending_creator)?;
+        launch.governance_proposal_count = legacy.governance_proposal_count;
+        launch.set_max_tx_amount(legacy.max_tx_amount)?;
+        launch.set_max_wallet_pct(legacy.max_wallet_pct)?;
+        launch.event_seq = legacy.event_seq;
+        launch.set_presale(legacy.presale)?;
+        launch.presale_sold = legacy.presale_sold;
+        launch.set_presale_finalized(legacy.presale_finalized);
+        launch.fees_distributed = legacy.fees_distributed;
+        launch.yield_allowance = legacy.yield_allowance;
+        launch.set_unlock_schedule(legacy.unlock_schedule)?;
+        launch.unlocked_so_far = legacy.unlocked_so_far;
+        launch.launched_at = legacy.launched_at;
+        launch.set_relock_consent_days(legacy.relock_consent_days)?;
+        launch.version = legacy.version;
+        launch.score_model_version = legacy.score_model_version;
+        launch.score_model_hash = legacy.score_model_hash;
+        launch.withdrawal_record_count = legacy.withdrawal_record_count;
+        launch.set_launch_class(legacy.launch_class)?;
+        launch.min_timelock_duration = legacy.min_timelock_duration;
+        launch.bond = legacy.bond;
+        launch.reflection_bps = legacy.reflection_bps;
+        launch.tokens_burned = legacy.tokens_burned;
+        launch.circulating_supply = legacy.circulating_supply;
+        launch.set_unlock_condition(legacy.unlock_condition)?;
+        launch.condition_since_slot = legacy.condition_since_slot;
+        launch.set_condition_met(legacy.condition_met);
+        launch.tranches_released = legacy.tranches_released;
+        launch.set_bootstrap(legacy.bootstrap)?;
+        launch.transfer_tax_bps = legacy.transfer_tax_bps;
+        launch.team_allocated = legacy.team_allocated;
+        launch.set_logo_cnft(legacy.logo_cnft)?;
+        launch.set_logo_verified(legacy.logo_verified);
+        launch.set_pending_insurance_change(legacy.pending_insurance_change)?;
+        launch.paused_until = legacy.paused_until;
+        launch.pause_count = legacy.pause_count;
+        launch.set_repeat_creator(legacy.repeat_creator);
+        launch.set_dex_allowlist(&legacy.dex_allowlist)?;
+        launch.dex_allowlist_days = legacy.dex_allowlist_days;
+        launch.set_recovery_account_list(&legacy.recovery_accounts)?;
+        Ok(launch)
+    }
+}
+
+/// The Borsh `TokenLaunch` layout of versions before 19, kept to decode
+/// accounts `migrate_launch` has not yet rewritten. It shares
+/// `TokenLaunch`'s discriminator. Every account was allocated at the
+/// fixed `space()` regardless of string lengths, and fields were only
+/// ever appended.
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct LegacyTokenLaunch {
+    pub creator: Pubkey,                    // 32 bytes
+    pub token_mint: Pubkey,                 // 32 bytes
+    pub token_name: String,                 // 4 + max 50 bytes
+    pub token_symbol: String,               // 4 + max 10 bytes  
+    pub total_supply: u64,                  // 8 bytes
+    pub timelock_end: i64,                  // 8 bytes
+    pub insurance_wallets: Vec<Pubkey>,     // 4 + (32 * count) bytes
+    pub insurance_limit: u8,                // 1 byte
+    pub logo_nft: Option<Pubkey>,           // 33 bytes (32 + 1 for Option)
+    pub fraud_score: u16,                   // 2 bytes (basis points)
+    pub fees_collected: u64,                // 8 bytes
+    pub is_active: bool,                    // 1 byte
+    pub relock_count: u32,                  // 4 bytes
+    pub total_withdrawn: u64,               // 8 bytes
+    pub timelock_duration: i64,             // 8 bytes
+    pub vault: Pubkey,                      // 32 bytes
+    pub withdrawal_threshold: u8,           // 1 byte
+    pub proposal_count: u64,                // 8 bytes
+    pub metadata_uri: String,               // 4 + max 200 bytes
+    pub metadata: Pubkey,                   // 32 bytes
+    pub creator_multisig: Option<CreatorMultisig>,// 34 bytes (1 + 32 + 1)
+    pub pending_creator: Option<Pubkey>,    // 33 bytes (32 + 1 for Option)
+    pub governance_proposal_count: u64,     // 8 bytes
+    pub max_tx_amount: Option<u64>,         // 1 + 8 bytes
+    pub max_wallet_pct: Option<u8>,         // 1 + 1 bytes
+    pub event_seq: u64,                     // 8 bytes
+    pub presale: Option<PresaleConfig>,     // 1 + 72 bytes
+    pub presale_sold: u64,                  // 8 bytes
+    pub presale_finalized: bool,            // 1 byte
+    pub fees_distributed: u64,              // 8 bytes
+    pub yield_allowance: u64,               // 8 bytes
+    pub unlock_schedule: Option<UnlockSchedule>,// 4 bytes (3 + 1 for Option)
+    pub unlocked_so_far: u64,               // 8 bytes
+    pub launched_at: i64,                   // 8 bytes
+    pub relock_consent_days: Option<u16>,   // 1 + 2 bytes
+    pub version: u8,                        // 1 byte (0 = before versioning)
+    pub score_model_version: u32,           // 4 bytes (0 = unattributed)
+    pub score_model_hash: [u8; 32],         // 32 bytes
+    pub withdrawal_record_count: u64,       // 8 bytes
+    pub launch_class: Option<u8>,           // 1 + 1 bytes (None = global terms)
+    pub min_timelock_duration: i64,         // 8 bytes (0 = global minimum)
+    pub bond: u64,                          // 8 bytes, held in this account
+    pub reflection_bps: u16,                // 2 bytes, share of trading fees paid to holders
+    pub tokens_burned: u64,                 // 8 bytes, cumulative burns
+    pub circulating_supply: u64,            // 8 bytes, total_supply less burns
+    pub unlock_condition: Option<UnlockCondition>, // 1 + 53 bytes
+    pub condition_since_slot: u64,          // 8 bytes, first slot of the current run above (0 = below)
+    pub condition_met: bool,                // 1 byte, latched once the condition has held
+    pub tranches_released: u16,             // 2 bytes, unlock schedule tranches marked by crank_launch
+    pub bootstrap: Option<BootstrapConfig>, // 1 + 25 bytes
+    pub transfer_tax_bps: u16,              // 2 bytes, token tax on transfer_tokens, to the treasury
+    pub team_allocated: u64,                // 8 bytes, moved into team vesting accounts
+    pub logo_cnft: Option<CompressedLogo>,  // 1 + 36 bytes, alternative to logo_nft
+    pub logo_verified: bool,                // 1 byte, logo_cnft proven by verify_logo_cnft
+    pub pending_insurance_change: Option<PendingInsuranceChange>, // 1 + 41 bytes
+    pub paused_until: i64,                  // 8 bytes, trading paused before this time
+    pub pause_count: u8,                    // 1 byte, pauses used under the config's PausePolicy
+    pub repeat_creator: bool,               // 1 byte, creator had a clean history at launch
+    pub dex_allowlist: Vec<Pubkey>,         // 4 + 32 * MAX_DEX_ALLOWLIST bytes, pool authorities
+    pub dex_allowlist_days: u16,            // 2 bytes, post-unlock window; 0 = off
+    pub recovery_accounts: Vec<Pubkey>,     // 4 + 32 * MAX_RECOVERY_ACCOUNTS bytes, emergency withdrawal destinations
+}
+
+impl LegacyTokenLaunch {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // creator
+        32 +          // token_mint
+        (4 + MAX_TOKEN_NAME_LEN) + // token_name
+        (4 + MAX_TOKEN_SYMBOL_LEN) + // token_symbol
+        8 +           // total_supply
+        8 +           // timelock_end
+        (4 + 32 * MAX_INSURANCE_WALLETS) + // insurance_wallets
+        1 +           // insurance_limit
+        33 +          // logo_nft (Option<Pubkey>)
+        2 +           // fraud_score
+        8 +           // fees_collected
+        1 +           // is_active
+        4 +           // relock_count
+        8 +           // total_withdrawn
+        8 +           // timelock_duration
+        32 +          // vault
+        1 +           // withdrawal_threshold
+        8 +           // proposal_count
+        (4 + MAX_URI_LEN) +// metadata_uri
+        32 +          // metadata
+        (1 + CreatorMultisig::SIZE) +// creator_multisig
+        33 +          // pending_creator
+        8 +           // governance_proposal_count
+        (1 + 8) +     // max_tx_amount
+        (1 + 1) +     // max_wallet_pct
+        8 +           // event_seq
+        (1 + PresaleConfig::SIZE) +// presale
+        8 +           // presale_sold
+        1 +           // presale_finalized
+        8 +           // fees_distributed
+        8 +           // yield_allowance
+        1 + UnlockSchedule::SIZE +// unlock_schedule
+        8 +           // unlocked_so_far
+        8 +           // launched_at
+        1 + 2 +       // relock_consent_days
+        1 +           // version
+        4 +           // score_model_version
+        32 +          // score_model_hash
+        8 +           // withdrawal_record_count
+        1 + 1 +       // launch_class
+        8 +           // min_timelock_duration
+        8 +           // bond
+        2 +           // reflection_bps
+        8 +           // tokens_burned
+        8 +           // circulating_supply
+        (1 + UnlockCondition::SIZE) + // unlock_condition
+        8 +           // condition_since_slot
+        1 +           // condition_met
+        2 +           // tranches_released
+        (1 + BootstrapConfig::SIZE) + // bootstrap
+        2 +           // transfer_tax_bps
+        8 +           // team_allocated
+        (1 + CompressedLogo::SIZE) + // logo_cnft
+        1 +           // logo_verified
+        (1 + PendingInsuranceChange::SIZE) + // pending_insurance_change
+        8 +           // paused_until
+        1 +           // pause_count
+        1 +           // repeat_creator
+        (4 + 32 * MAX_DEX_ALLOWLIST) + // dex_allowlist
+        2 +           // dex_allowlist_days
+        (4 + 32 * MAX_RECOVERY_ACCOUNTS) + // recovery_accounts
+        32            // padding for future fields
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchParams {
+    pub token_name: String,
+    pub token_symbol: String, 
+    pub total_supply: u64,
+    pub timelock_duration: i64,
+    pub insurance_wallets: Vec<Pubkey>,
+    pub insurance_limit: u8,
+    pub logo_nft: Option<Pubkey>,
+    pub fraud_score: u16,
+    pub withdrawal_threshold: u8,
+    pub metadata_uri: String,
+    pub creator_multisig: Option<CreatorMultisig>,
+    pub max_tx_amount: Option<u64>,
+    pub max_wallet_pct: Option<u8>,
+    pub presale: Option<PresaleConfig>,
+    pub unlock_schedule: Option<UnlockSchedule>,
+    pub relock_consent_days: Option<u16>,
+    /// Index into the config's launch classes; None uses the global terms
+    pub launch_class: Option<u8>,
+    /// Wallet that referred this launch, paid a share of the launch fee
+    pub referrer: Option<Pubkey>,
+    /// Extra condition, on top of the timelock, before tokens unlock
+    pub unlock_condition: Option<UnlockCondition>,
+    /// Anti-sniper limits on transfers right after the timelock ends
+    pub bootstrap: Option<BootstrapConfig>,
+    /// Token tax on each `transfer_tokens`, in bps of the amount, paid to
+    /// the treasury's token account (0 = none)
+    pub transfer_tax_bps: u16,
+    /// Compressed NFT logo, in place of `logo_nft`
+    pub logo_cnft: Option<CompressedLogo>,
+}
+
+/// Versioned launch parameters: the fields every launch needs, plus a
+/// list of optional features. New features become new `LaunchOption`
+/// variants appended at the end, so existing clients keep encoding the
+/// same bytes when a feature is added.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchParamsV2 {
+    pub token_name: String,
+    pub token_symbol: String,
+    pub total_supply: u64,
+    pub timelock_duration: i64,
+    pub insurance_wallets: Vec<Pubkey>,
+    pub insurance_limit: u8,
+    pub fraud_score: u16,
+    pub withdrawal_threshold: u8,
+    pub metadata_uri: String,
+    /// Each feature at most once, in any order
+    pub options: Vec<LaunchOption>,
+}
+
+/// One optional launch feature. Append new variants only; reordering
+/// changes the encoding existing clients rely on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum LaunchOption {
+    Logo(Pubkey),
+    LogoCnft(CompressedLogo),
+    CreatorMultisig(CreatorMultisig),
+    AntiWhale { max_tx_amount: Option<u64>, max_wallet_pct: Option<u8> },
+    Presale(PresaleConfig),
+    Vesting(UnlockSchedule),
+    RelockConsent { days: u16 },
+    LaunchClass(u8),
+    Referrer(Pubkey),
+    UnlockCondition(UnlockCondition),
+    Bootstrap(BootstrapConfig),
+    TransferTax { bps: u16 },
+    /// Token accounts emergency withdrawals may pay out to
+    RecoveryAccounts(Vec<Pubkey>),
+}
+
+impl LaunchOption {
+    fn apply(self, params: &mut LaunchParams) {
+        match self {
+            LaunchOption::Logo(nft) => params.logo_nft = Some(nft),
+            LaunchOption::LogoCnft(logo) => params.logo_cnft = Some(logo),
+            LaunchOption::CreatorMultisig(multisig) => params.creator_multisig = Some(multisig),
+            LaunchOption::AntiWhale { max_tx_amount, max_wallet_pct } => {
+                params.max_tx_amount = max_tx_amount;
+                params.max_wallet_pct = max_wallet_pct;
+            }
+            LaunchOption::Presale(presale) => params.presale = Some(presale),
+            LaunchOption::Vesting(schedule) => params.unlock_schedule = Some(schedule),
+            LaunchOption::RelockConsent { days } => params.relock_consent_days = Some(days),
+            LaunchOption::LaunchClass(class) => params.launch_class = Some(class),
+            LaunchOption::Referrer(referrer) => params.referrer = Some(referrer),
+            LaunchOption::UnlockCondition(condition) => params.unlock_condition = Some(condition),
+            LaunchOption::Bootstrap(bootstrap) => params.bootstrap = Some(bootstrap),
+            LaunchOption::TransferTax { bps } => params.transfer_tax_bps = bps,
+            // Not part of the V1 parameters; see LaunchParamsV2::recovery_accounts
+            LaunchOption::RecoveryAccounts(_) => {}
+        }
+    }
+}
+
+impl LaunchParamsV2 {
+    /// Lower to the V1 parameters both instructions initialize from.
+    /// Features not listed keep their V1 "off" value.
+    pub fn into_params(self) -> Result<LaunchParams> {
+        let mut params = LaunchParams {
+            token_name: self.token_name,
+            token_symbol: self.token_symbol,
+            total_supply: self.total_supply,
+            timelock_duration: self.timelock_duration,
+            insurance_wallets: self.insurance_wallets,
+            insurance_limit: self.insurance_limit,
+            logo_nft: None,
+            fraud_score: self.fraud_score,
+            withdrawal_threshold: self.withdrawal_threshold,
+            metadata_uri: self.metadata_uri,
+            creator_multisig: None,
+            max_tx_amount: None,
+            max_wallet_pct: None,
+            presale: None,
+            unlock_schedule: None,
+            relock_consent_days: None,
+            launch_class: None,
+            referrer: None,
+            unlock_condition: None,
+            bootstrap: None,
+            transfer_tax_bps: 0,
+            logo_cnft: None,
+        };
+        for (i, option) in self.options.iter().enumerate() {
+            require!(
+                !self.options[..i]
+                    .iter()
+                    .any(|earlier| std::mem::discriminant(earlier) == std::mem::discriminant(option)),
+                TokenLaunchError::DuplicateLaunchOption
+            );
+        }
+        for option in self.options {
+            option.apply(&mut params);
+        }
+        Ok(params)
+    }
+
+    /// Accounts listed in a `RecoveryAccounts` option, registered on the
+    /// launch once it is initialized
+    pub fn recovery_accounts(&self) -> Vec<Pubkey> {
+        self.options
+            .iter()
+            .find_map(|option| match option {
+                LaunchOption::RecoveryAccounts(accounts) => Some(accounts.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A Bubblegum compressed NFT used as a launch logo, identified by its
+/// tree and leaf. Claimed at launch; `verify_logo_cnft` proves it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CompressedLogo {
+    pub tree: Pubkey,
+    pub leaf_index: u32,
+}
+
+impl CompressedLogo {
+    pub const SIZE: usize = 32 + 4;
+
+    /// Bubblegum asset id: [b"asset", tree, nonce LE] under Bubblegum.
+    /// Bubblegum mints use the leaf index as the nonce.
+    pub fn asset_id(&self, bubblegum_program: &Pubkey) -> Pubkey {
+        let nonce = self.leaf_index as u64;
+        Pubkey::find_program_address(&[b"asset", self.tree.as_ref(), &nonce.to_le_bytes()], bubblegum_program).0
+    }
+
+    /// Bubblegum `LeafSchema::V1` hash of the logo leaf owned by `owner`
+    pub fn leaf_hash(
+        &self,
+        asset_id: &Pubkey,
+        owner: &Pubkey,
+        delegate: &Pubkey,
+        data_hash: &[u8; 32],
+        creator_hash: &[u8; 32],
+    ) -> [u8; 32] {
+        let nonce = self.leaf_index as u64;
+        anchor_lang::solana_program::keccak::hashv(&[
+            &[1u8], // LeafSchema version
+            asset_id.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+            &nonce.to_le_bytes(),
+            data_hash,
+            creator_hash,
+        ])
+        .0
+    }
+}
+
+/// Anti-sniper limits for the first `window_slots` slots of trading after
+/// the timelock ends, enforced by `transfer_tokens` via the launch's
+/// `BootstrapGuard`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct BootstrapConfig {
+    pub window_slots: u64,
+    pub max_buy_per_slot: u64,  // raw tokens transferred across all buyers in one slot
+    pub cooldown_slots: u64,    // between transfers to the same wallet
+    pub fee_multiplier: u8,     // applied to the trading fee
+}
+
+impl BootstrapConfig {
+    pub const SIZE: usize = 8 + 8 + 8 + 1;
+
+    pub fn is_valid(&self) -> bool {
+        self.window_slots > 0
+            && self.max_buy_per_slot > 0
+            && self.cooldown_slots <= self.window_slots
+            && (1..=MAX_BOOTSTRAP_FEE_MULTIPLIER).contains(&self.fee_multiplier)
+    }
+}
+
+/// Oracle condition that must hold, in addition to the timelock, before a
+/// launch's tokens unlock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum UnlockCondition {
+    /// The Pyth `feed` price stays at or above `threshold` x 10^`expo` for
+    /// `min_slots` slots, as observed by `update_unlock_condition`
+    PythPriceAbove { feed: Pubkey, threshold: i64, expo: i32, min_slots: u64 },
+}
+
+impl UnlockCondition {
+    pub const SIZE: usize = 1 + 32 + 8 + 4 + 8; // tag + largest variant
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            UnlockCondition::PythPriceAbove { threshold, min_slots, .. } => *threshold > 0 && *min_slots > 0,
+        }
+    }
+}
+
+/// Gradual vault release after the timelock: `percent_per_period` of the
+/// total supply unlocks at `timelock_end` and again every `period_days`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct UnlockSchedule {
+    pub percent_per_period: u8,
+    pub period_days: u16,
+}
+
+impl UnlockSchedule {
+    pub const SIZE: usize = 1 + 2;
+
+    pub fn is_valid(&self) -> bool {
+        self.percent_per_period > 0 && self.percent_per_period <= 100 && self.period_days > 0
+    }
+
+    /// Tranches unlocked by `current_timestamp`: the first at `timelock_end`,
+    /// then one per period until the whole supply is unlocked
+    pub fn tranches_due(&self, timelock_end: i64, current_timestamp: i64) -> u16 {
+        if current_timestamp < timelock_end {
+            return 0;
+        }
+        let period = self.period_days as i64 * constants::SECONDS_PER_DAY;
+        let periods = (current_timestamp.saturating_sub(timelock_end) / period + 1) as u64;
+        let total = 100u64.div_ceil(self.percent_per_period as u64);
+        periods.min(total) as u16
+    }
+
+    /// Raw tokens unlocked by `current_timestamp` out of `total_supply`
+    pub fn unlocked_amount(&self, total_supply: u64, timelock_end: i64, current_timestamp: i64) -> u64 {
+        if current_timestamp < timelock_end {
+            return 0;
+        }
+        let period = self.period_days as i64 * constants::SECONDS_PER_DAY;
+        let periods = (current_timestamp.saturating_sub(timelock_end) / period + 1) as u128;
+        let unlocked = (total_supply as u128 * self.percent_per_period as u128).saturating_mul(periods) / 100;
+        unlocked.min(total_supply as u128) as u64
+    }
+}
+
+/// Whitelisted presale run before the timelock ends. Buyers prove their
+/// per-address allocation against `merkle_root` and pay a fixed SOL price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct PresaleConfig {
+    pub merkle_root: [u8; 32],
+    pub price_lamports: u64,   // per whole token (10^decimals raw units)
+    pub allocation: u64,       // total raw tokens offered
+    pub end_time: i64,
+}
+
+impl PresaleConfig {
+    pub const SIZE: usize = 32 + 8 + 8 + 8;
+
+    /// Lamports owed for `amount` raw tokens, rounded up
+    pub fn cost(&self, amount: u64, decimals: u8) -> Result<u64> {
+        let scale = 10u128.pow(decimals as u32);
+        let cost = (amount as u128 * self.price_lamports as u128).div_ceil(scale);
+        u64::try_from(cost).map_err(|_| error!(TokenLaunchError::PresaleCapExceeded))
+    }
+}
+
+/// Squads multisig whose vault PDA acts as the launch creator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CreatorMultisig {
+    pub multisig: Pubkey,
+    pub vault_index: u8,
+}
+
+impl CreatorMultisig {
+    pub const SIZE: usize = 32 + 1;
+
+    /// Squads v4 vault PDA: ["multisig", multisig, "vault", vault_index]
+    pub fn vault_address(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"multisig", self.multisig.as_ref(), b"vault", &[self.vault_index]],
+            &SQUADS_PROGRAM_ID,
+        )
+        .0
+    }
+}
+
+/// Per-token fields supplied when cloning an existing launch's policy.
+/// `None` keeps the source launch's value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchOverrides {
+    pub token_name: String,
+    pub token_symbol: String,
+    pub total_supply: u64,
     pub logo_nft: Option<Pubkey>,
-    pub fraud_score: f32,
+    pub fraud_score: u16,
+    pub timelock_duration: Option<i64>,
+    pub insurance_limit: Option<u8>,
+    pub metadata_uri: String,
+    /// Paid the referral share; must match the `referrer` account
+    pub referrer: Option<Pubkey>,
+    /// Registered as the clone's recovery accounts
+    pub recovery_accounts: Vec<Pubkey>,
+}
+
+/// Launch terms a template fixes for every launch created from it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TemplateParams {
+    pub timelock_duration: i64,
+    pub insurance_wallets: Vec<Pubkey>,
+    pub insurance_limit: u8,
+    pub withdrawal_threshold: u8,
+    /// Registered as the launch's recovery accounts
+    pub recovery_accounts: Vec<Pubkey>,
+    pub max_tx_amount: Option<u64>,
+    pub max_wallet_pct: Option<u8>,
+    pub relock_consent_days: Option<u16>,
+    pub launch_class: Option<u8>,
+    pub transfer_tax_bps: u16,
+}
+
+impl TemplateParams {
+    pub const SIZE: usize = 8 + (4 + 32 * MAX_INSURANCE_WALLETS) + 1 + 1 + (4 + 32 * MAX_RECOVERY_ACCOUNTS) + 9 + 2 + 3 + 2 + 2;
+
+    /// Checks that don't depend on the launch. The timelock, class and
+    /// tax are validated when a launch uses the template.
+    pub fn is_valid(&self) -> bool {
+        self.insurance_wallets.len() <= MAX_INSURANCE_WALLETS
+            && self.insurance_limit <= MAX_INSURANCE_LIMIT
+            && self.withdrawal_threshold as usize <= self.insurance_wallets.len()
+            && self.recovery_accounts.len() <= MAX_RECOVERY_ACCOUNTS
+            && !matches!(self.max_wallet_pct, Some(0) | Some(101..))
+    }
+}
+
+/// The per-launch half of a template launch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TemplateLaunchParams {
+    /// Template version the creator signed against
+    pub template_version: u32,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub total_supply: u64,
+    pub fraud_score: u16,
+    pub metadata_uri: String,
+    pub referrer: Option<Pubkey>,
+}
+
+/// Reusable launch terms, for launchpads and serial launchers
+#[account]
+pub struct LaunchTemplate {
+    pub owner: Pubkey,                      // 32 bytes, may update the template
+    pub template_id: u64,                   // 8 bytes, chosen by the owner
+    pub version: u32,                       // 4 bytes, bumped on every update
+    pub params: TemplateParams,             // TemplateParams::SIZE bytes
+    pub updated_at: i64,                    // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl LaunchTemplate {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // owner
+        8 +           // template_id
+        4 +           // version
+        TemplateParams::SIZE + // params
+        8 +           // updated_at
+        1             // bump
+    }
+
+    /// Store new terms as the next version
+    pub fn set(&mut self, params: TemplateParams, current_timestamp: i64) -> Result<()> {
+        require!(params.is_valid(), TokenLaunchError::InvalidTemplate);
+        self.version = self.version.checked_add(1).ok_or(TokenLaunchError::MathOverflow)?;
+        self.params = params;
+        self.updated_at = current_timestamp;
+        Ok(())
+    }
+
+    /// Full launch parameters for one launch created from this template
+    pub fn launch_params(&self, launch: TemplateLaunchParams) -> LaunchParams {
+        let terms = &self.params;
+        LaunchParams {
+            token_name: launch.token_name,
+            token_symbol: launch.token_symbol,
+            total_supply: launch.total_supply,
+            timelock_duration: terms.timelock_duration,
+            insurance_wallets: terms.insurance_wallets.clone(),
+            insurance_limit: terms.insurance_limit,
+            logo_nft: None,
+            fraud_score: launch.fraud_score,
+            withdrawal_threshold: terms.withdrawal_threshold,
+            metadata_uri: launch.metadata_uri,
+            creator_multisig: None,
+            max_tx_amount: terms.max_tx_amount,
+            max_wallet_pct: terms.max_wallet_pct,
+            presale: None,
+            unlock_schedule: None,
+            relock_consent_days: terms.relock_consent_days,
+            launch_class: terms.launch_class,
+            referrer: launch.referrer,
+            unlock_condition: None,
+            bootstrap: None,
+            transfer_tax_bps: terms.transfer_tax_bps,
+            logo_cnft: None,
+        }
+    }
+}
+
+/// Escrow-controlled key/value channel for off-chain services,
+/// kept separate from creator-controlled launch metadata
+#[account]
+pub struct LaunchAnnotations {
+    pub token_mint: Pubkey,                 // 32 bytes
+    pub entries: Vec<Annotation>,           // 4 + (Annotation::SIZE * count) bytes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub key: String,                        // 4 + max 16 bytes
+    pub value_hash: [u8; 32],               // 32 bytes
+    pub updated_at: i64,                    // 8 bytes
+}
+
+impl Annotation {
+    pub const SIZE: usize = (4 + MAX_ANNOTATION_KEY_LEN) + 32 + 8;
+}
+
+impl LaunchAnnotations {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // token_mint
+        (4 + Annotation::SIZE * MAX_ANNOTATIONS) // entries
+    }
+
+    /// Insert a new entry or overwrite the value of an existing key
+    pub fn upsert(&mut self, key: String, value_hash: [u8; 32], timestamp: i64) -> Result<()> {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.key == key) {
+            entry.value_hash = value_hash;
+            entry.updated_at = timestamp;
+            return Ok(());
+        }
+
+        require!(
+            self.entries.len() < MAX_ANNOTATIONS,
+            TokenLaunchError::TooManyAnnotations
+        );
+        self.entries.push(Annotation {
+            key,
+            value_hash,
+            updated_at: timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Annotation> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+/// M-of-N emergency withdrawal proposal raised by an insurance wallet
+#[account]
+pub struct WithdrawalProposal {
+    pub launch: Pubkey,                     // 32 bytes
+    pub proposal_id: u64,                   // 8 bytes
+    pub proposer: Pubkey,                   // 32 bytes
+    pub amount: u64,                        // 8 bytes
+    pub destination: Pubkey,                // 32 bytes
+    pub justification: String,              // 4 + max 200 bytes
+    pub approvals: Vec<Pubkey>,             // 4 + (32 * count) bytes
+    pub created_at: i64,                    // 8 bytes
+    pub expires_at: i64,                    // 8 bytes
+    pub executed: bool,                     // 1 byte
+}
+
+impl WithdrawalProposal {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // proposal_id
+        32 +          // proposer
+        8 +           // amount
+        32 +          // destination
+        (4 + MAX_JUSTIFICATION_LEN) + // justification
+        (4 + 32 * MAX_INSURANCE_WALLETS) + // approvals
+        8 +           // created_at
+        8 +           // expires_at
+        1             // executed
+    }
+}
+
+/// Per-insurance-wallet withdrawal tracking for cap and cooldown enforcement
+#[account]
+pub struct InsuranceWalletRecord {
+    pub launch: Pubkey,                     // 32 bytes
+    pub wallet: Pubkey,                     // 32 bytes
+    pub total_withdrawn: u64,               // 8 bytes
+    pub withdrawal_count: u32,              // 4 bytes
+    pub last_withdrawal_at: i64,            // 8 bytes
+}
+
+impl InsuranceWalletRecord {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // wallet
+        8 +           // total_withdrawn
+        4 +           // withdrawal_count
+        8             // last_withdrawal_at
+    }
+
+    /// Apply a withdrawal against this wallet's cap and cooldown
+    pub fn record_withdrawal(&mut self, amount: u64, cap: u64, current_timestamp: i64) -> Result<()> {
+        require!(
+            self.withdrawal_count == 0
+                || current_timestamp >= math::add_secs(self.last_withdrawal_at, WITHDRAWAL_COOLDOWN)?,
+            TokenLaunchError::WithdrawalCooldownActive
+        );
+        let total_withdrawn = math::add(self.total_withdrawn, amount)?;
+        require!(total_withdrawn <= cap, TokenLaunchError::ExceedsWalletLimit);
+
+        self.total_withdrawn = total_withdrawn;
+        self.withdrawal_count = self
+            .withdrawal_count
+            .checked_add(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+        self.last_withdrawal_at = current_timestamp;
+        Ok(())
+    }
+}
+
+/// SOL collateral staked by a launch's insurance wallets
+#[account]
+pub struct InsuranceCollateralPool {
+    pub launch: Pubkey,                     // 32 bytes
+    pub total_staked: u64,                  // 8 bytes
+    pub total_slashed: u64,                 // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl InsuranceCollateralPool {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // total_staked
+        8 +           // total_slashed
+        1             // bump
+    }
+
+    /// Move `amount` of staked collateral to the slashed total
+    pub fn slash(&mut self, amount: u64) -> Result<()> {
+        self.release(amount)?;
+        self.total_slashed = math::add(self.total_slashed, amount)?;
+        Ok(())
+    }
+
+    /// Take `amount` out of the staked total, on reclaim or slash
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.total_staked = math::sub(self.total_staked, amount)?;
+        Ok(())
+    }
+}
+
+/// One insurance wallet's collateral behind a launch
+#[account]
+pub struct InsuranceCollateral {
+    pub launch: Pubkey,                     // 32 bytes
+    pub wallet: Pubkey,                     // 32 bytes
+    pub amount: u64,                        // 8 bytes
+    pub staked_at: i64,                     // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl InsuranceCollateral {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // wallet
+        8 +           // amount
+        8 +           // staked_at
+        1             // bump
+    }
+
+    /// The wallet has enough at stake to act as insurance
+    pub fn check_staked(&self) -> Result<()> {
+        require!(
+            self.amount >= MIN_INSURANCE_COLLATERAL,
+            TokenLaunchError::InsufficientInsuranceCollateral
+        );
+        Ok(())
+    }
+}
+
+/// Launches currently owned by a creator, keyed by creator wallet
+#[account]
+pub struct CreatorIndex {
+    pub creator: Pubkey,                    // 32 bytes
+    pub launches: Vec<Pubkey>,              // 4 + (32 * count) bytes
+}
+
+impl CreatorIndex {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // creator
+        (4 + 32 * MAX_CREATOR_LAUNCHES) // launches
+    }
+
+    pub fn add_launch(&mut self, creator: Pubkey, token_mint: Pubkey) -> Result<()> {
+        require!(
+            self.launches.len() < MAX_CREATOR_LAUNCHES,
+            TokenLaunchError::CreatorIndexFull
+        );
+        self.creator = creator;
+        if !self.launches.contains(&token_mint) {
+            self.launches.push(token_mint);
+        }
+        Ok(())
+    }
+
+    pub fn remove_launch(&mut self, token_mint: &Pubkey) {
+        self.launches.retain(|mint| mint != token_mint);
+    }
+}
+
+/// M-of-N arbiters that replace the single escrow key for suspensions,
+/// relocks and appeal rulings once set
+#[account]
+pub struct ArbiterPanel {
+    pub arbiters: Vec<Pubkey>,              // 4 + (32 * count) bytes
+    pub threshold: u8,                      // 1 byte
+    pub proposal_count: u64,                // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl ArbiterPanel {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        (4 + 32 * MAX_ARBITERS) + // arbiters
+        1 +           // threshold
+        8 +           // proposal_count
+        1             // bump
+    }
+
+    /// Distinct arbiters with a reachable, non-zero threshold; an empty
+    /// panel with threshold 0 turns arbitration off
+    pub fn is_valid(arbiters: &[Pubkey], threshold: u8) -> bool {
+        let distinct = arbiters
+            .iter()
+            .enumerate()
+            .all(|(i, arbiter)| !arbiters[..i].contains(arbiter));
+        (arbiters.is_empty() && threshold == 0)
+            || (arbiters.len() <= MAX_ARBITERS
+                && distinct
+                && threshold > 0
+                && threshold as usize <= arbiters.len())
+    }
+
+    pub fn verify_arbiter(&self, signer: &Pubkey) -> Result<()> {
+        require!(self.arbiters.contains(signer), TokenLaunchError::UnauthorizedArbiter);
+        Ok(())
+    }
+}
+
+/// Escrow action an arbiter panel votes on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ArbitrationAction {
+    Suspend,
+    Relock { new_duration: i64 },
+    ResolveAppeal { upheld: bool },
+    VetoMetadataUpdate,
+    SlashInsuranceCollateral { withdrawal_seq: u64 },
+}
+
+/// A panel vote on one escrow action against one launch. Once approvals
+/// reach the threshold anyone can execute the action, exactly once.
+#[account]
+pub struct ArbitrationProposal {
+    pub proposal_id: u64,                   // 8 bytes
+    pub launch: Pubkey,                     // 32 bytes
+    pub action: ArbitrationAction,          // 1 + 8 bytes
+    pub proposer: Pubkey,                   // 32 bytes
+    pub approvals: Vec<Pubkey>,             // 4 + (32 * count) bytes
+    pub threshold: u8,                      // 1 byte, the panel's at proposal time
+    pub expires_at: i64,                    // 8 bytes
+    pub executed: bool,                     // 1 byte
+    pub bump: u8,                           // 1 byte
+}
+
+impl ArbitrationProposal {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        8 +           // proposal_id
+        32 +          // launch
+        (1 + 8) +     // action
+        32 +          // proposer
+        (4 + 32 * MAX_ARBITERS) + // approvals
+        1 +           // threshold
+        8 +           // expires_at
+        1 +           // executed
+        1             // bump
+    }
+
+    pub fn approve(&mut self, arbiter: Pubkey, current_timestamp: i64) -> Result<()> {
+        require!(!self.executed, TokenLaunchError::ProposalAlreadyExecuted);
+        require!(current_timestamp < self.expires_at, TokenLaunchError::ProposalExpired);
+        require!(!self.approvals.contains(&arbiter), TokenLaunchError::AlreadyApproved);
+        self.approvals.push(arbiter);
+        Ok(())
+    }
+
+    /// The proposal authorizes `action` on `launch` right now
+    pub fn check_approved(&self, launch: &Pubkey, action: &ArbitrationAction, current_timestamp: i64) -> Result<()> {
+        require!(
+            self.launch == *launch && self.action == *action,
+            TokenLaunchError::ArbitrationMismatch
+        );
+        require!(!self.executed, TokenLaunchError::ProposalAlreadyExecuted);
+        require!(current_timestamp < self.expires_at, TokenLaunchError::ProposalExpired);
+        require!(
+            self.approvals.len() >= self.threshold as usize,
+            TokenLaunchError::InsufficientApprovals
+        );
+        Ok(())
+    }
+}
+
+/// Session key allowance: `delegate` may call `transfer_tokens` from
+/// `token_account` for up to `remaining` tokens until `expires_at`. The PDA
+/// is the token account's SPL delegate and signs those transfers.
+#[account]
+pub struct Delegation {
+    pub owner: Pubkey,                      // 32 bytes
+    pub token_account: Pubkey,              // 32 bytes
+    pub delegate: Pubkey,                   // 32 bytes
+    pub remaining: u64,                     // 8 bytes
+    pub expires_at: i64,                    // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl Delegation {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // owner
+        32 +          // token_account
+        32 +          // delegate
+        8 +           // remaining
+        8 +           // expires_at
+        1             // bump
+    }
+
+    /// Charge a delegated transfer of `amount` against the allowance
+    pub fn spend(&mut self, delegate: &Pubkey, amount: u64, current_timestamp: i64) -> Result<()> {
+        require_keys_eq!(*delegate, self.delegate, TokenLaunchError::UnauthorizedDelegate);
+        require!(current_timestamp < self.expires_at, TokenLaunchError::DelegationExpired);
+        self.remaining = self
+            .remaining
+            .checked_sub(amount)
+            .ok_or(TokenLaunchError::DelegationCapExceeded)?;
+        Ok(())
+    }
+}
+
+/// A creator's launch history, feeding the repeat creator fee discount
+#[account]
+pub struct CreatorProfile {
+    pub creator: Pubkey,                    // 32 bytes
+    pub launches_created: u32,              // 4 bytes
+    pub flagged_launches: u32,              // 4 bytes, launches that turned high-risk
+    pub bump: u8,                           // 1 byte
+}
+
+impl CreatorProfile {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // creator
+        4 +           // launches_created
+        4 +           // flagged_launches
+        1 +           // bump
+        16            // padding for future fields
+    }
+
+    /// Has launched before without any launch turning high-risk
+    pub fn is_clean_repeat(&self) -> bool {
+        self.launches_created > 0 && self.flagged_launches == 0
+    }
+
+    pub fn record_launch(&mut self, creator: Pubkey, bump: u8) -> Result<()> {
+        self.creator = creator;
+        self.bump = bump;
+        self.launches_created = self
+            .launches_created
+            .checked_add(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_flag(&mut self) -> Result<()> {
+        self.flagged_launches = self
+            .flagged_launches
+            .checked_add(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Append-only count of every launch ever created. Entries live in
+/// fixed-size `RegistryPage`s, so indexers can enumerate launches by
+/// walking pages 0..=`next_page()` instead of scanning program accounts.
+#[account]
+pub struct LaunchRegistry {
+    pub launch_count: u64,                  // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl LaunchRegistry {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        8 +           // launch_count
+        1 +           // bump
+        16            // padding for future fields
+    }
+
+    /// Page holding the entry at `index`
+    pub fn page_of(index: u64) -> u64 {
+        index / REGISTRY_PAGE_SIZE
+    }
+
+    /// Page the next launch is recorded in
+    pub fn next_page(&self) -> u64 {
+        Self::page_of(self.launch_count)
+    }
+
+    /// Append a new launch to `page`, which must be `next_page()`
+    pub fn register(
+        &mut self,
+        page: &mut RegistryPage,
+        bump: u8,
+        page_bump: u8,
+        entry: RegistryEntry,
+    ) -> Result<()> {
+        if page.entries.is_empty() {
+            page.page = self.next_page();
+            page.bump = page_bump;
+        }
+        require!(
+            page.page == self.next_page() && (page.entries.len() as u64) < REGISTRY_PAGE_SIZE,
+            TokenLaunchError::RegistryPageMismatch
+        );
+        page.entries.push(entry);
+        self.bump = bump;
+        self.launch_count = self
+            .launch_count
+            .checked_add(1)
+            .ok_or(TokenLaunchError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// One launch as recorded at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RegistryEntry {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+}
+
+impl RegistryEntry {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/// Up to `REGISTRY_PAGE_SIZE` launches in creation order; page `n` holds
+/// registry indexes `n * REGISTRY_PAGE_SIZE..(n + 1) * REGISTRY_PAGE_SIZE`
+#[account]
+pub struct RegistryPage {
+    pub page: u64,                          // 8 bytes
+    pub entries: Vec<RegistryEntry>,        // 4 + 72 * REGISTRY_PAGE_SIZE bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl RegistryPage {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        8 +           // page
+        (4 + RegistryEntry::SIZE * REGISTRY_PAGE_SIZE as usize) + // entries
+        1             // bump
+    }
+}
+
+/// Insurance wallet rotation, applied by `execute_insurance_change` once
+/// `INSURANCE_CHANGE_DELAY` has passed so holders can react first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum InsuranceChange {
+    Add { wallet: Pubkey },
+    Remove { wallet: Pubkey },
+    AddRecoveryAccount { account: Pubkey },
+    RemoveRecoveryAccount { account: Pubkey },
+}
+
+impl InsuranceChange {
+    /// The governance action with the same effect and validity rules
+    pub fn as_governance_action(&self) -> GovernanceAction {
+        match *self {
+            InsuranceChange::Add { wallet } => GovernanceAction::AddInsuranceWallet { wallet },
+            InsuranceChange::Remove { wallet } => GovernanceAction::RemoveInsuranceWallet { wallet },
+            InsuranceChange::AddRecoveryAccount { account } => GovernanceAction::AddRecoveryAccount { account },
+            InsuranceChange::RemoveRecoveryAccount { account } => GovernanceAction::RemoveRecoveryAccount { account },
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct PendingInsuranceChange {
+    pub change: InsuranceChange,
+    pub effective_at: i64,
+}
+
+impl PendingInsuranceChange {
+    pub const SIZE: usize = (1 + 32) + 8;
+}
+
+/// Launch parameter changes that token holders can vote on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum GovernanceAction {
+    SetInsuranceLimit { insurance_limit: u8 },
+    AddInsuranceWallet { wallet: Pubkey },
+    RemoveInsuranceWallet { wallet: Pubkey },
+    ReactivateLaunch,
+    ApproveYieldDeployment { amount: u64 },
+    BlacklistWallet { wallet: Pubkey },
+    UnblacklistWallet { wallet: Pubkey },
+    /// Move assets outside the launch's accounting to `recipient`: the
+    /// launch PDA's token accounts of a foreign `mint`, or with `None` the
+    /// launch account's lamports above rent and the class bond
+    RescueForeignAssets { mint: Option<Pubkey>, recipient: Pubkey },
+    AddRecoveryAccount { account: Pubkey },
+    RemoveRecoveryAccount { account: Pubkey },
+    /// Redeem up to `shares` of the yield position through the venue call
+    /// whose accounts and data hash to `venue_call_hash`
+    UnwindTreasuryYield { shares: u64, venue_call_hash: [u8; 32] },
+}
+
+impl GovernanceAction {
+    pub const SIZE: usize = 1 + 33 + 32; // tag + largest variant
+}
+
+/// Token-weighted governance proposal for a launch
+#[account]
+pub struct GovernanceProposal {
+    pub launch: Pubkey,                     // 32 bytes
+    pub proposal_id: u64,                   // 8 bytes
+    pub proposer: Pubkey,                   // 32 bytes
+    pub action: GovernanceAction,           // 66 bytes
+    pub description: String,                // 4 + max 200 bytes
+    pub yes_votes: u64,                     // 8 bytes
+    pub no_votes: u64,                      // 8 bytes
+    pub voting_ends_at: i64,                // 8 bytes
+    pub executed: bool,                     // 1 byte
+    pub bump: u8,                           // 1 byte
+}
+
+impl GovernanceProposal {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // proposal_id
+        32 +          // proposer
+        GovernanceAction::SIZE + // action
+        (4 + MAX_PROPOSAL_DESCRIPTION_LEN) + // description
+        8 +           // yes_votes
+        8 +           // no_votes
+        8 +           // voting_ends_at
+        1 +           // executed
+        1             // bump
+    }
+
+    /// Passed if turnout reaches quorum and yes votes hold the majority
+    pub fn has_passed(&self, total_supply: u64) -> bool {
+        let turnout = self.yes_votes as u128 + self.no_votes as u128;
+        let quorum = total_supply as u128 * GOVERNANCE_QUORUM_PCT as u128 / 100;
+        turnout >= quorum && self.yes_votes > self.no_votes
+    }
+}
+
+/// One voter's ballot on a governance proposal
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,                   // 32 bytes
+    pub voter: Pubkey,                      // 32 bytes
+    pub support: bool,                      // 1 byte
+    pub amount: u64,                        // 8 bytes
+    pub withdrawn: bool,                    // 1 byte
+}
+
+impl VoteRecord {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // proposal
+        32 +          // voter
+        1 +           // support
+        8 +           // amount
+        1             // withdrawn
+    }
+}
+
+/// Presale purchases made by one buyer, for per-address cap enforcement
+#[account]
+pub struct PresalePurchase {
+    pub launch: Pubkey,                     // 32 bytes
+    pub buyer: Pubkey,                      // 32 bytes
+    pub purchased: u64,                     // 8 bytes
+    pub lamports_paid: u64,                 // 8 bytes
+}
+
+impl PresalePurchase {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // buyer
+        8 +           // purchased
+        8             // lamports_paid
+    }
+}
+
+/// Bonding curve sale state. Prices follow a constant product over the
+/// virtual reserves; real reserves track what the curve actually holds.
+#[account]
+pub struct BondingCurve {
+    pub launch: Pubkey,                     // 32 bytes
+    pub virtual_sol_reserves: u64,          // 8 bytes
+    pub virtual_token_reserves: u64,        // 8 bytes
+    pub real_sol_reserves: u64,             // 8 bytes
+    pub real_token_reserves: u64,           // 8 bytes
+    pub graduation_threshold: u64,          // 8 bytes
+    pub graduated: bool,                    // 1 byte
+}
+
+impl BondingCurve {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // virtual_sol_reserves
+        8 +           // virtual_token_reserves
+        8 +           // real_sol_reserves
+        8 +           // real_token_reserves
+        8 +           // graduation_threshold
+        1             // graduated
+    }
+
+    /// Account for a buy, returning tokens out. Graduates the curve once
+    /// its real SOL reserves reach the threshold.
+    pub fn apply_buy(&mut self, sol_in: u64) -> Result<u64> {
+        let tokens_out = bonding_curve::buy_quote(self.virtual_sol_reserves, self.virtual_token_reserves, sol_in)
+            .ok_or(TokenLaunchError::InvalidBondingCurve)?;
+        require!(tokens_out > 0, TokenLaunchError::SlippageExceeded);
+        require!(
+            tokens_out <= self.real_token_reserves,
+            TokenLaunchError::CurveSoldOut
+        );
+
+        self.virtual_sol_reserves = math::add(self.virtual_sol_reserves, sol_in)?;
+        self.virtual_token_reserves = math::sub(self.virtual_token_reserves, tokens_out)?;
+        self.real_sol_reserves = math::add(self.real_sol_reserves, sol_in)?;
+        self.real_token_reserves = math::sub(self.real_token_reserves, tokens_out)?;
+        if self.real_sol_reserves >= self.graduation_threshold {
+            self.graduated = true;
+        }
+        Ok(tokens_out)
+    }
+
+    /// Account for a sell, returning lamports out
+    pub fn apply_sell(&mut self, tokens_in: u64) -> Result<u64> {
+        let sol_out = bonding_curve::sell_quote(self.virtual_sol_reserves, self.virtual_token_reserves, tokens_in)
+            .ok_or(TokenLaunchError::InvalidBondingCurve)?;
+        require!(sol_out <= self.real_sol_reserves, TokenLaunchError::InvalidBondingCurve);
+
+        self.virtual_sol_reserves = math::sub(self.virtual_sol_reserves, sol_out)?;
+        self.virtual_token_reserves = math::add(self.virtual_token_reserves, tokens_in)?;
+        self.real_sol_reserves = math::sub(self.real_sol_reserves, sol_out)?;
+        self.real_token_reserves = math::add(self.real_token_reserves, tokens_in)?;
+        Ok(sol_out)
+    }
+
+    /// Account for migrating a graduated curve. Clears the real reserves so
+    /// the migration runs once; the caller sweeps the actual balances.
+    pub fn migrate(&mut self) -> Result<()> {
+        require!(self.graduated, TokenLaunchError::CurveNotGraduated);
+        require!(
+            self.real_sol_reserves > 0 || self.real_token_reserves > 0,
+            TokenLaunchError::CurveAlreadyMigrated
+        );
+        self.real_sol_reserves = 0;
+        self.real_token_reserves = 0;
+        Ok(())
+    }
+}
+
+/// LP position created from launch supply, held by the launch PDA
+#[account]
+pub struct LiquidityLock {
+    pub launch: Pubkey,                     // 32 bytes
+    pub amm_program: Pubkey,                // 32 bytes
+    pub lp_mint: Pubkey,                    // 32 bytes
+    pub lp_amount: u64,                     // 8 bytes
+    pub tokens_added: u64,                  // 8 bytes
+    pub lamports_added: u64,                // 8 bytes
+    pub locked_at: i64,                     // 8 bytes
+}
+
+impl LiquidityLock {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // amm_program
+        32 +          // lp_mint
+        8 +           // lp_amount
+        8 +           // tokens_added
+        8 +           // lamports_added
+        8             // locked_at
+    }
+}
+
+/// Protocol fees in lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FeeSchedule {
+    pub base_fee: u64,
+    pub insurance_fee: u64,   // per insurance wallet
+    pub logo_fee: u64,
+    pub relock_fee: u64,
+    pub trading_fee: u64,
+    pub emergency_fee: u64,
+}
+
+impl FeeSchedule {
+    pub const SIZE: usize = 6 * 8;
+
+    /// Every fee scaled by `tier_bps` (10_000 = unchanged)
+    pub fn tiered(&self, tier_bps: u16) -> Result<FeeSchedule> {
+        let scale = |fee: u64| math::mul_div(fee, tier_bps as u64, 10_000);
+        Ok(FeeSchedule {
+            base_fee: scale(self.base_fee)?,
+            insurance_fee: scale(self.insurance_fee)?,
+            logo_fee: scale(self.logo_fee)?,
+            relock_fee: scale(self.relock_fee)?,
+            trading_fee: scale(self.trading_fee)?,
+            emergency_fee: scale(self.emergency_fee)?,
+        })
+    }
+
+    pub fn launch_fee(&self, launch: &TokenLaunch) -> Result<u64> {
+        let insurance_fee = math::mul(launch.insurance_wallets().len() as u64, self.insurance_fee)?;
+        let has_logo = launch.logo_nft().is_some() || launch.logo_cnft().is_some();
+        let logo_fee = if has_logo { self.logo_fee } else { 0 };
+
+        math::add(math::add(self.base_fee, insurance_fee)?, logo_fee)
+    }
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            base_fee: constants::BASE_FEE_LAMPORTS,
+            insurance_fee: constants::INSURANCE_FEE_LAMPORTS,
+            logo_fee: constants::LOGO_FEE_LAMPORTS,
+            relock_fee: constants::RELOCK_FEE_LAMPORTS,
+            trading_fee: constants::TRADING_FEE_LAMPORTS,
+            emergency_fee: constants::EMERGENCY_FEE_LAMPORTS,
+        }
+    }
+}
+
+/// Operator-controlled settings shared by every launch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigParams {
+    pub fee_recipient: Pubkey,
+    pub escrow_authority: Pubkey,
+    pub fees: FeeSchedule,
+    pub insurance_pool: Pubkey,
+    pub treasury_split: TreasurySplit,
+    /// Share of a referred launch's fee paid to the referrer, in basis points
+    pub referral_bps: u16,
+    /// Authority (a key, or an attestor program's PDA) whose KYC attestation
+    /// creators need before `initialize_launch`; `None` disables the gate
+    pub kyc_attestor: Option<Pubkey>,
+    /// Limits on creators pausing their launch's trading
+    pub pause_policy: PausePolicy,
+}
+
+/// Global program configuration, replacing compile-time fee constants
+#[account]
+pub struct GlobalConfig {
+    pub admin: Pubkey,                      // 32 bytes
+    pub fee_recipient: Pubkey,              // 32 bytes
+    pub escrow_authority: Pubkey,           // 32 bytes
+    pub fees: FeeSchedule,                  // 48 bytes
+    pub bump: u8,                           // 1 byte
+    pub insurance_pool: Pubkey,             // 32 bytes
+    pub treasury_split: TreasurySplit,      // 6 bytes
+    pub cluster: Cluster,                   // 1 byte, fixed at initialization
+    pub launch_classes: Vec<LaunchClass>,   // 4 + 24 * MAX_LAUNCH_CLASSES bytes
+    pub referral_bps: u16,                  // 2 bytes
+    pub kyc_attestor: Option<Pubkey>,       // 33 bytes
+    pub fee_recipients: Vec<FeeRecipient>,  // 4 + 34 * MAX_FEE_RECIPIENTS bytes
+    pub pause_policy: PausePolicy,          // 17 bytes
+    pub arbitration_required: bool,         // 1 byte, escrow actions need an ArbiterPanel quorum
+}
+
+impl GlobalConfig {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // admin
+        32 +          // fee_recipient
+        32 +          // escrow_authority
+        FeeSchedule::SIZE + // fees
+        1 +           // bump
+        32 +          // insurance_pool
+        TreasurySplit::SIZE + // treasury_split
+        1 +           // cluster
+        (4 + LaunchClass::SIZE * MAX_LAUNCH_CLASSES) + // launch_classes
+        2 +           // referral_bps
+        33 +          // kyc_attestor
+        (4 + FeeRecipient::SIZE * MAX_FEE_RECIPIENTS) + // fee_recipients
+        PausePolicy::SIZE + // pause_policy
+        1 +           // arbitration_required
+        6             // padding for future fields
+    }
+
+    /// Look up a launch's selected class, if it selected one
+    pub fn launch_class(&self, index: Option<u8>) -> Result<Option<LaunchClass>> {
+        index
+            .map(|index| {
+                self.launch_classes
+                    .get(index as usize)
+                    .copied()
+                    .ok_or_else(|| error!(TokenLaunchError::UnknownLaunchClass))
+            })
+            .transpose()
+    }
+
+    /// The fee schedule a launch pays, tiered by its risk and its creator's
+    /// history (see `TokenLaunch::fee_tier_bps`)
+    pub fn fees_for(&self, launch: &TokenLaunch) -> Result<FeeSchedule> {
+        self.fees.tiered(launch.fee_tier_bps())
+    }
+
+    /// Tiered launch fee, with the class base fee replacing the schedule's
+    pub fn launch_fee(&self, launch: &TokenLaunch, class: Option<&LaunchClass>) -> Result<u64> {
+        let fees = match class {
+            Some(class) => FeeSchedule { base_fee: class.base_fee, ..self.fees },
+            None => self.fees,
+        };
+        fees.tiered(launch.fee_tier_bps())?.launch_fee(launch)
+    }
+
+    pub fn apply(&mut self, params: ConfigParams) -> Result<()> {
+        require!(params.treasury_split.is_valid(), TokenLaunchError::InvalidTreasurySplit);
+        require!(params.referral_bps <= MAX_REFERRAL_BPS, TokenLaunchError::InvalidReferralShare);
+        require!(params.pause_policy.is_valid(), TokenLaunchError::InvalidPausePolicy);
+
+        self.fee_recipient = params.fee_recipient;
+        self.escrow_authority = params.escrow_authority;
+        self.fees = params.fees;
+        self.insurance_pool = params.insurance_pool;
+        self.treasury_split = params.treasury_split;
+        self.referral_bps = params.referral_bps;
+        self.kyc_attestor = params.kyc_attestor;
+        self.pause_policy = params.pause_policy;
+        Ok(())
+    }
+
+    /// Authorize an escrow action on `launch`. Without an arbiter panel the
+    /// escrow authority signs alone (failing with `error`); with one, an
+    /// arbitration proposal for exactly this action must have reached quorum.
+    pub fn check_escrow(
+        &self,
+        signer: &Pubkey,
+        arbitration: Option<&ArbitrationProposal>,
+        launch: &Pubkey,
+        action: &ArbitrationAction,
+        current_timestamp: i64,
+        error: TokenLaunchError,
+    ) -> Result<()> {
+        if !self.arbitration_required {
+            require_keys_eq!(*signer, self.escrow_authority, error);
+            return Ok(());
+        }
+        arbitration
+            .ok_or(TokenLaunchError::ArbitrationRequired)?
+            .check_approved(launch, action, current_timestamp)
+    }
+
+    /// When KYC is required, the creator must hold an unexpired attestation
+    /// from the currently registered attestor
+    pub fn check_kyc(&self, attestation: Option<&KycAttestation>, current_timestamp: i64) -> Result<()> {
+        let Some(attestor) = self.kyc_attestor else {
+            return Ok(());
+        };
+        let attestation = attestation.ok_or(TokenLaunchError::KycAttestationRequired)?;
+        require_keys_eq!(attestation.attestor, attestor, TokenLaunchError::KycAttestationRequired);
+        require!(
+            !attestation.is_expired(current_timestamp),
+            TokenLaunchError::KycAttestationExpired
+        );
+        Ok(())
+    }
+
+    /// Split the protocol share of distributed fees across the weighted fee
+    /// recipients, or pay it all to `fee_recipient` if none are set.
+    /// Rounding dust goes to the first recipient.
+    pub fn protocol_shares(&self, amount: u64) -> Vec<(Pubkey, u64)> {
+        if self.fee_recipients.is_empty() {
+            return vec![(self.fee_recipient, amount)];
+        }
+        let mut shares: Vec<(Pubkey, u64)> = self
+            .fee_recipients
+            .iter()
+            .map(|recipient| (recipient.wallet, (amount as u128 * recipient.bps as u128 / 10_000) as u64))
+            .collect();
+        let dust = amount - shares.iter().map(|(_, share)| share).sum::<u64>();
+        shares[0].1 += dust;
+        shares
+    }
+
+    /// Split a referred launch fee into (referrer share, treasury share)
+    pub fn referral_split(&self, fee: u64) -> Result<(u64, u64)> {
+        let referral = math::mul_div(fee, self.referral_bps as u64, 10_000)?;
+        Ok((referral, fee - referral))
+    }
+}
+
+/// Cluster a deployment runs on. Set once when the config is created, so an
+/// admin cannot later relax a mainnet deployment. Configs written before the
+/// field existed read it from zeroed padding as `Mainnet`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Devnet,
+    Localnet,
+}
+
+impl Cluster {
+    /// Whether test-mode parameters such as short timelocks are accepted
+    pub fn allows_test_mode(&self) -> bool {
+        *self != Cluster::Mainnet
+    }
+
+    /// Check a lock duration. Locks under the production minimum are test
+    /// mode, rejected on mainnet, and never shorter than an hour anywhere.
+    pub fn check_timelock(&self, duration: i64) -> Result<()> {
+        self.check_timelock_min(duration, MIN_TIMELOCK_DURATION)
+    }
+
+    /// `check_timelock` against a launch class's production minimum
+    pub fn check_timelock_min(&self, duration: i64, min_duration: i64) -> Result<()> {
+        require!(
+            duration >= TEST_MIN_TIMELOCK_DURATION,
+            TokenLaunchError::TimelockTooShort
+        );
+        if duration < min_duration {
+            require!(self.allows_test_mode(), TokenLaunchError::InvalidNetwork);
+        }
+        Ok(())
+    }
+}
+
+/// Terms an operator offers for a kind of launch, e.g. shorter locks for
+/// game utility tokens and longer ones for memecoins. A launch selects one
+/// by index and keeps the terms it launched under.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct LaunchClass {
+    pub min_timelock: i64,
+    pub bond: u64,            // lamports held in the launch until it closes
+    pub base_fee: u64,        // replaces FeeSchedule::base_fee
+}
+
+impl LaunchClass {
+    pub const SIZE: usize = 8 + 8 + 8;
+
+    pub fn is_valid(&self) -> bool {
+        self.min_timelock >= MIN_CLASS_TIMELOCK_DURATION
+    }
+}
+
+/// How often and how long a creator may pause trading on their launch, e.g.
+/// to respond to an exploit. Bounded so a pause can't become a soft rug;
+/// the zeroed default disables pausing.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PausePolicy {
+    /// Pauses allowed over a launch's lifetime
+    pub max_pauses: u8,
+    /// Longest single pause, in seconds
+    pub max_pause_secs: i64,
+    /// Minimum gap between the end of one pause and the next, in seconds
+    pub cooldown_secs: i64,
+}
+
+impl PausePolicy {
+    pub const SIZE: usize = 1 + 8 + 8;
+
+    pub fn is_valid(&self) -> bool {
+        (0..=MAX_TRADING_PAUSE).contains(&self.max_pause_secs) && self.cooldown_secs >= 0
+    }
+}
+
+/// Split of distributed treasury fees, in basis points summing to 10_000
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct TreasurySplit {
+    pub protocol_bps: u16,
+    pub insurance_pool_bps: u16,
+    pub creator_rebate_bps: u16,
+}
+
+impl TreasurySplit {
+    pub const SIZE: usize = 3 * 2;
+
+    pub fn is_valid(&self) -> bool {
+        self.protocol_bps as u32 + self.insurance_pool_bps as u32 + self.creator_rebate_bps as u32
+            == 10_000
+    }
+
+    /// Split `amount` into (protocol, insurance pool, creator rebate).
+    /// Rounding dust goes to the protocol share.
+    pub fn split(&self, amount: u64) -> (u64, u64, u64) {
+        let share = |bps: u16| (amount as u128 * bps as u128 / 10_000) as u64;
+        let insurance_pool = share(self.insurance_pool_bps);
+        let creator_rebate = share(self.creator_rebate_bps);
+        (amount - insurance_pool - creator_rebate, insurance_pool, creator_rebate)
+    }
+}
+
+/// One weighted recipient of the protocol fee share, e.g. a partner
+/// launchpad
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    pub bps: u16,
+}
+
+impl FeeRecipient {
+    pub const SIZE: usize = 32 + 2;
+
+    /// Up to `MAX_FEE_RECIPIENTS` distinct wallets with positive weights
+    /// summing to 100%. An empty list pays `fee_recipient` alone.
+    pub fn is_valid_set(recipients: &[FeeRecipient]) -> bool {
+        if recipients.is_empty() {
+            return true;
+        }
+        let distinct = recipients
+            .iter()
+            .enumerate()
+            .all(|(i, r)| recipients[..i].iter().all(|other| other.wallet != r.wallet));
+        recipients.len() <= MAX_FEE_RECIPIENTS
+            && distinct
+            && recipients.iter().all(|r| r.bps > 0)
+            && recipients.iter().map(|r| r.bps as u32).sum::<u32>() == 10_000
+    }
+}
+
+/// Program-owned account that accumulates protocol fees
+#[account]
+pub struct Treasury {
+    pub total_collected: u64,               // 8 bytes
+    pub total_distributed: u64,             // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl Treasury {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        8 +           // total_collected
+        8 +           // total_distributed
+        1             // bump
+    }
+
+    pub fn record_fee(&mut self, amount: u64) -> Result<()> {
+        self.total_collected = math::add(self.total_collected, amount)?;
+        Ok(())
+    }
+}
+
+/// Admin-managed set of keys allowed to push fraud scores
+#[account]
+pub struct OracleRegistry {
+    pub oracles: Vec<Pubkey>,               // 4 + (32 * count) bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl OracleRegistry {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        4 + (32 * MAX_FRAUD_ORACLES) + // oracles
+        1             // bump
+    }
+
+    pub fn is_authorized(&self, oracle: &Pubkey) -> bool {
+        self.oracles.contains(oracle)
+    }
+
+    pub fn add(&mut self, oracle: Pubkey) -> Result<()> {
+        require!(!self.is_authorized(&oracle), TokenLaunchError::OracleAlreadyRegistered);
+        require!(self.oracles.len() < MAX_FRAUD_ORACLES, TokenLaunchError::OracleRegistryFull);
+        self.oracles.push(oracle);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, oracle: &Pubkey) -> Result<()> {
+        let index = self
+            .oracles
+            .iter()
+            .position(|o| o == oracle)
+            .ok_or(TokenLaunchError::OracleNotRegistered)?;
+        self.oracles.remove(index);
+        Ok(())
+    }
+
+    pub fn rotate(&mut self, old_oracle: &Pubkey, new_oracle: Pubkey) -> Result<()> {
+        require!(!self.is_authorized(&new_oracle), TokenLaunchError::OracleAlreadyRegistered);
+        let slot = self
+            .oracles
+            .iter_mut()
+            .find(|o| *o == old_oracle)
+            .ok_or(TokenLaunchError::OracleNotRegistered)?;
+        *slot = new_oracle;
+        Ok(())
+    }
+}
+
+/// SOL from a launch's SOL vault deployed into a yield venue
+#[account]
+pub struct YieldPosition {
+    pub launch: Pubkey,                     // 32 bytes
+    pub venue: Pubkey,                      // 32 bytes
+    pub share_mint: Pubkey,                 // 32 bytes
+    pub principal: u64,                     // 8 bytes
+    pub realized_yield: u64,                // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl YieldPosition {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // venue
+        32 +          // share_mint
+        8 +           // principal
+        8 +           // realized_yield
+        1             // bump
+    }
+
+    /// Principal backing `shares_burned`, pro rata to the shares held
+    /// before the unwind
+    pub fn principal_for(&self, shares_burned: u64, shares_before: u64) -> Result<u64> {
+        if shares_burned >= shares_before {
+            Ok(self.principal)
+        } else {
+            math::mul_div(self.principal, shares_burned, shares_before)
+        }
+    }
+
+    /// Retire the principal backing `shares_burned` and return the yield
+    /// earned on top of it
+    pub fn record_unwind(&mut self, shares_burned: u64, shares_before: u64, lamports_received: u64) -> Result<u64> {
+        let principal_returned = self.principal_for(shares_burned, shares_before)?;
+        let earned = lamports_received.saturating_sub(principal_returned);
+        self.principal = math::sub(self.principal, principal_returned)?;
+        self.realized_yield = math::add(self.realized_yield, earned)?;
+        Ok(earned)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AppealStatus {
+    None,
+    Pending,
+    Upheld,
+    Rejected,
+    Reinstated,
+}
+
+/// A creator's appeal against a launch suspension, holding the appeal bond
+#[account]
+pub struct AppealState {
+    pub launch: Pubkey,                     // 32 bytes
+    pub appellant: Pubkey,                  // 32 bytes
+    pub bond: u64,                          // 8 bytes
+    pub reason: String,                     // 4 + max 200 bytes
+    pub filed_at: i64,                      // 8 bytes
+    pub review_deadline: i64,               // 8 bytes
+    pub status: AppealStatus,               // 1 byte
+    pub arbiter: Option<Pubkey>,            // 33 bytes
+    pub resolved_at: i64,                   // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl AppealState {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // appellant
+        8 +           // bond
+        (4 + MAX_JUSTIFICATION_LEN) + // reason
+        8 +           // filed_at
+        8 +           // review_deadline
+        1 +           // status
+        33 +          // arbiter
+        8 +           // resolved_at
+        1             // bump
+    }
+
+    /// A new appeal may be filed unless one is awaiting a ruling or reactivation
+    pub fn can_file(&self) -> bool {
+        matches!(
+            self.status,
+            AppealStatus::None | AppealStatus::Rejected | AppealStatus::Reinstated
+        )
+    }
+
+    /// Upheld appeals allow reactivation, as do pending appeals the escrow
+    /// failed to rule on before the review deadline
+    pub fn allows_reactivation(&self, current_timestamp: i64) -> bool {
+        match self.status {
+            AppealStatus::Upheld => true,
+            AppealStatus::Pending => current_timestamp > self.review_deadline,
+            _ => false,
+        }
+    }
+}
+
+/// SOL staked by third-party underwriters behind a launch
+#[account]
+pub struct UnderwritingPool {
+    pub launch: Pubkey,                     // 32 bytes
+    pub total_staked: u64,                  // 8 bytes
+    pub underwriter_count: u32,             // 4 bytes
+    pub slashed: bool,                      // 1 byte
+    pub payout_pool: u64,                   // 8 bytes
+    pub circulating_at_slash: u64,          // 8 bytes
+    pub tokens_redeemed: u64,               // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl UnderwritingPool {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // total_staked
+        4 +           // underwriter_count
+        1 +           // slashed
+        8 +           // payout_pool
+        8 +           // circulating_at_slash
+        8 +           // tokens_redeemed
+        1             // bump
+    }
+
+    /// Lamports owed for burning `amount` tokens after a slash
+    pub fn payout_for(&self, amount: u64) -> u64 {
+        if self.circulating_at_slash == 0 {
+            return 0;
+        }
+        (self.payout_pool as u128 * amount as u128 / self.circulating_at_slash as u128) as u64
+    }
+}
+
+/// One underwriter's stake behind a launch
+#[account]
+pub struct UnderwriterStake {
+    pub launch: Pubkey,                     // 32 bytes
+    pub underwriter: Pubkey,                // 32 bytes
+    pub amount: u64,                        // 8 bytes
+    pub staked_at: i64,                     // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl UnderwriterStake {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // underwriter
+        8 +           // amount
+        8 +           // staked_at
+        1             // bump
+    }
+}
+
+/// Opt-in social recovery: a quorum of guardians can reassign the creator
+/// after a challenge window in which the current creator may cancel
+#[account]
+pub struct RecoveryPolicy {
+    pub launch: Pubkey,                     // 32 bytes
+    pub guardians: Vec<Pubkey>,             // 4 + (32 * count) bytes
+    pub threshold: u8,                      // 1 byte
+    pub new_creator: Option<Pubkey>,        // 33 bytes
+    pub approvals: Vec<Pubkey>,             // 4 + (32 * count) bytes
+    pub quorum_reached_at: i64,             // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl RecoveryPolicy {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        (4 + 32 * MAX_RECOVERY_GUARDIANS) + // guardians
+        1 +           // threshold
+        33 +          // new_creator
+        (4 + 32 * MAX_RECOVERY_GUARDIANS) + // approvals
+        8 +           // quorum_reached_at
+        1             // bump
+    }
+
+    /// Record a guardian approval, starting the challenge window once the
+    /// threshold is reached
+    pub fn approve(&mut self, guardian: Pubkey, current_timestamp: i64) -> Result<()> {
+        require!(!self.approvals.contains(&guardian), TokenLaunchError::AlreadyApproved);
+        self.approvals.push(guardian);
+        if self.quorum_reached_at == 0 && self.approvals.len() >= self.threshold as usize {
+            self.quorum_reached_at = current_timestamp;
+        }
+        Ok(())
+    }
+
+    /// End of the challenge window, once the guardian quorum is reached
+    pub fn challenge_ends_at(&self) -> Option<i64> {
+        (self.quorum_reached_at > 0).then(|| self.quorum_reached_at.saturating_add(RECOVERY_CHALLENGE_PERIOD))
+    }
+
+    pub fn reset(&mut self) {
+        self.new_creator = None;
+        self.approvals.clear();
+        self.quorum_reached_at = 0;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ClaimKind {
+    Vesting,
+    Airdrop,
+}
+
+/// Tokens reserved in the allocation vault for one beneficiary
+#[account]
+pub struct Allocation {
+    pub launch: Pubkey,                     // 32 bytes
+    pub beneficiary: Pubkey,                // 32 bytes
+    pub kind: ClaimKind,                    // 1 byte
+    pub total: u64,                         // 8 bytes
+    pub claimed: u64,                       // 8 bytes
+    pub start: i64,                         // 8 bytes
+    pub cliff_end: i64,                     // 8 bytes
+    pub end: i64,                           // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl Allocation {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // beneficiary
+        1 +           // kind
+        8 +           // total
+        8 +           // claimed
+        8 +           // start
+        8 +           // cliff_end
+        8 +           // end
+        1             // bump
+    }
+
+    pub fn vested(&self, current_timestamp: i64) -> u64 {
+        linear_vested(self.total, self.start, self.cliff_end, self.end, current_timestamp)
+    }
+
+    pub fn claimable(&self, current_timestamp: i64) -> u64 {
+        self.vested(current_timestamp).saturating_sub(self.claimed)
+    }
+}
+
+/// Sponsor-funded lamports that reimburse the transaction fees of claim
+/// callers, so holders without SOL can still claim through a relayer
+#[account]
+pub struct SponsorPool {
+    pub launch: Pubkey,                     // 32 bytes
+    pub reimbursement_per_claim: u64,       // 8 bytes
+    pub per_wallet_cap: u64,                // 8 bytes
+    pub total_funded: u64,                  // 8 bytes
+    pub total_reimbursed: u64,              // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl SponsorPool {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // reimbursement_per_claim
+        8 +           // per_wallet_cap
+        8 +           // total_funded
+        8 +           // total_reimbursed
+        1             // bump
+    }
+
+    /// Reimbursement for one claim by a wallet that has already received
+    /// `already_reimbursed`, limited by what the pool holds
+    pub fn reimbursement_for(&self, already_reimbursed: u64, available: u64) -> u64 {
+        self.reimbursement_per_claim
+            .min(self.per_wallet_cap.saturating_sub(already_reimbursed))
+            .min(available)
+    }
+}
+
+/// Running total of fee reimbursements paid to one claimant
+#[account]
+pub struct SponsoredWallet {
+    pub launch: Pubkey,                     // 32 bytes
+    pub wallet: Pubkey,                     // 32 bytes
+    pub reimbursed: u64,                    // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl SponsoredWallet {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // wallet
+        8 +           // reimbursed
+        1             // bump
+    }
+}
+
+/// Permanent record left behind when a launch is closed: the hash of its
+/// off-chain archive plus the final totals the archive must reconcile with
+#[account]
+pub struct LaunchArchive {
+    pub token_mint: Pubkey,                 // 32 bytes
+    pub creator: Pubkey,                    // 32 bytes
+    pub archive_hash: [u8; 32],             // 32 bytes
+    pub final_event_seq: u64,               // 8 bytes
+    pub total_supply: u64,                  // 8 bytes
+    pub total_withdrawn: u64,               // 8 bytes
+    pub fees_collected: u64,                // 8 bytes
+    pub closed_at: i64,                     // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl LaunchArchive {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // token_mint
+        32 +          // creator
+        32 +          // archive_hash
+        8 +           // final_event_seq
+        8 +           // total_supply
+        8 +           // total_withdrawn
+        8 +           // fees_collected
+        8 +           // closed_at
+        1             // bump
+    }
+}
+
+/// One of a launch's trading fee accounts. Transfers pick a shard so they
+/// do not all write the same account; `sweep_fee_shards` moves the pending
+/// lamports on to the treasury.
+#[account]
+pub struct FeeShard {
+    pub launch: Pubkey,                     // 32 bytes
+    pub shard: u8,                          // 1 byte
+    pub pending: u64,                       // 8 bytes
+    pub total_collected: u64,               // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl FeeShard {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        1 +           // shard
+        8 +           // pending
+        8 +           // total_collected
+        1             // bump
+    }
+
+    pub fn record_fee(&mut self, amount: u64) -> Result<()> {
+        self.pending = math::add(self.pending, amount)?;
+        self.total_collected = math::add(self.total_collected, amount)?;
+        Ok(())
+    }
+
+    /// Clear and return the fees not yet swept to the treasury
+    pub fn take_pending(&mut self) -> u64 {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Trading activity for one shard of a launch. Transfers update the shard
+/// matching their fee shard and sales update `SALE_STATS_SHARD`, so the
+/// counters add no write contention; `LaunchStats::total` sums the shards.
+/// Holder counts are estimates: an account emptied and refilled counts as
+/// a new holder each time, and wallets with several accounts count once
+/// per account.
+#[account]
+#[derive(Default)]
+pub struct LaunchStats {
+    pub launch: Pubkey,                     // 32 bytes
+    pub shard: u8,                          // 1 byte
+    pub volume: u128,                       // 16 bytes, tokens moved by transfers and sales
+    pub transfer_count: u64,                // 8 bytes
+    pub sale_count: u64,                    // 8 bytes, presale and curve trades
+    pub sale_lamports: u64,                 // 8 bytes, SOL paid into or out of sales
+    pub holders_added: u64,                 // 8 bytes, trades into an empty account
+    pub holders_removed: u64,               // 8 bytes, trades that emptied an account
+    pub last_trade_at: i64,                 // 8 bytes
+    pub trading_fees: u64,                  // 8 bytes, lamports
+    pub tax_collected: u64,                 // 8 bytes, tokens paid to the treasury
+    pub bump: u8,                           // 1 byte
+}
+
+impl LaunchStats {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        1 +           // shard
+        16 +          // volume
+        8 +           // transfer_count
+        8 +           // sale_count
+        8 +           // sale_lamports
+        8 +           // holders_added
+        8 +           // holders_removed
+        8 +           // last_trade_at
+        8 +           // trading_fees
+        8 +           // tax_collected
+        1             // bump
+    }
+
+    pub fn record_transfer(
+        &mut self,
+        amount: u64,
+        fee: u64,
+        tax: u64,
+        opens_holder: bool,
+        closes_holder: bool,
+        now: i64,
+    ) -> Result<()> {
+        self.transfer_count = math::add(self.transfer_count, 1)?;
+        self.trading_fees = math::add(self.trading_fees, fee)?;
+        self.tax_collected = math::add(self.tax_collected, tax)?;
+        self.record_trade(amount, opens_holder, closes_holder, now)
+    }
+
+    pub fn record_sale(&mut self, tokens: u64, lamports: u64, opens_holder: bool, closes_holder: bool, now: i64) -> Result<()> {
+        self.sale_count = math::add(self.sale_count, 1)?;
+        self.sale_lamports = math::add(self.sale_lamports, lamports)?;
+        self.record_trade(tokens, opens_holder, closes_holder, now)
+    }
+
+    /// A bonding curve buy or sell, filling in the shard on first use
+    pub fn record_curve_trade(
+        &mut self,
+        launch: Pubkey,
+        bump: u8,
+        tokens: u64,
+        lamports: u64,
+        opens_holder: bool,
+        closes_holder: bool,
+    ) -> Result<()> {
+        self.launch = launch;
+        self.shard = SALE_STATS_SHARD;
+        self.bump = bump;
+        self.record_sale(tokens, lamports, opens_holder, closes_holder, Clock::get()?.unix_timestamp)
+    }
+
+    fn record_trade(&mut self, tokens: u64, opens_holder: bool, closes_holder: bool, now: i64) -> Result<()> {
+        self.volume = self.volume.checked_add(tokens as u128).ok_or(TokenLaunchError::MathOverflow)?;
+        self.holders_added = math::add(self.holders_added, opens_holder as u64)?;
+        self.holders_removed = math::add(self.holders_removed, closes_holder as u64)?;
+        self.last_trade_at = self.last_trade_at.max(now);
+        Ok(())
+    }
+
+    /// The launch's totals across `shards`, as `shard` u8::MAX
+    pub fn total<'a>(shards: impl IntoIterator<Item = &'a LaunchStats>) -> LaunchStats {
+        shards.into_iter().fold(LaunchStats { shard: u8::MAX, ..Default::default() }, |total, shard| LaunchStats {
+            launch: shard.launch,
+            shard: u8::MAX,
+            volume: total.volume.saturating_add(shard.volume),
+            transfer_count: total.transfer_count.saturating_add(shard.transfer_count),
+            sale_count: total.sale_count.saturating_add(shard.sale_count),
+            sale_lamports: total.sale_lamports.saturating_add(shard.sale_lamports),
+            holders_added: total.holders_added.saturating_add(shard.holders_added),
+            holders_removed: total.holders_removed.saturating_add(shard.holders_removed),
+            last_trade_at: total.last_trade_at.max(shard.last_trade_at),
+            trading_fees: total.trading_fees.saturating_add(shard.trading_fees),
+            tax_collected: total.tax_collected.saturating_add(shard.tax_collected),
+            bump: 0,
+        })
+    }
+
+    /// Estimated current holders
+    pub fn holders(&self) -> u64 {
+        self.holders_added.saturating_sub(self.holders_removed)
+    }
+}
+
+/// A registered fraud scoring model generation. Versions are write-once,
+/// so a score's `score_model_version` always resolves to the same hash.
+#[account]
+pub struct ScoringModel {
+    pub version: u32,                       // 4 bytes
+    pub model_hash: [u8; 32],               // 32 bytes
+    pub registered_at: i64,                 // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl ScoringModel {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        4 +           // version
+        32 +          // model_hash
+        8 +           // registered_at
+        1             // bump
+    }
+
+    /// Check an oracle's declared model hash against the registered one
+    pub fn verify(&self, model_hash: &[u8; 32]) -> Result<()> {
+        require!(self.model_hash == *model_hash, TokenLaunchError::ModelHashMismatch);
+        Ok(())
+    }
+}
+
+/// One fraud score update in a launch's history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FraudScoreSample {
+    pub score: u16,
+    pub timestamp: i64,
+    pub oracle: Pubkey,
+}
+
+impl FraudScoreSample {
+    pub const SIZE: usize = 2 + 8 + 32;
+}
+
+/// The launch's most recent fraud score updates, in a ring buffer.
+/// Auto-suspension reads their time-weighted average rather than the
+/// latest update, so one noisy oracle reading cannot suspend a launch.
+#[account]
+#[derive(Default)]
+pub struct FraudScoreHistory {
+    pub launch: Pubkey,                     // 32 bytes
+    pub samples: [FraudScoreSample; FRAUD_SCORE_HISTORY_LEN], // 42 * 16 bytes
+    pub count: u8,                          // 1 byte, samples recorded (at most the ring size)
+    pub cursor: u8,                         // 1 byte, next ring slot to overwrite
+    pub bump: u8,                           // 1 byte
+}
+
+impl FraudScoreHistory {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        FraudScoreSample::SIZE * FRAUD_SCORE_HISTORY_LEN + // samples
+        1 +           // count
+        1 +           // cursor
+        1             // bump
+    }
+
+    pub fn record(&mut self, score: u16, oracle: Pubkey, timestamp: i64) {
+        self.samples[self.cursor as usize] = FraudScoreSample { score, timestamp, oracle };
+        self.cursor = ((self.cursor as usize + 1) % FRAUD_SCORE_HISTORY_LEN) as u8;
+        self.count = self.count.saturating_add(1).min(FRAUD_SCORE_HISTORY_LEN as u8);
+    }
+
+    /// The `i`-th recorded sample, oldest first
+    pub fn sample(&self, i: usize) -> &FraudScoreSample {
+        let oldest = if (self.count as usize) < FRAUD_SCORE_HISTORY_LEN { 0 } else { self.cursor as usize };
+        &self.samples[(oldest + i) % FRAUD_SCORE_HISTORY_LEN]
+    }
+
+    /// Average score over the FRAUD_SCORE_WINDOW before `now`, each update
+    /// weighted by how long it stood (the latest stands until `now`). None
+    /// until the history covers FRAUD_SCORE_MIN_SPAN of the window.
+    pub fn smoothed_score(&self, now: i64) -> Option<u16> {
+        let window_start = now.saturating_sub(FRAUD_SCORE_WINDOW);
+        let count = self.count as usize;
+        let mut weighted: u128 = 0;
+        let mut span: i64 = 0;
+        for i in 0..count {
+            let sample = self.sample(i);
+            let end = if i + 1 < count { self.sample(i + 1).timestamp.min(now) } else { now };
+            let start = sample.timestamp.max(window_start);
+            if end > start {
+                weighted += sample.score as u128 * (end - start) as u128;
+                span += end - start;
+            }
+        }
+        (span >= FRAUD_SCORE_MIN_SPAN).then(|| (weighted / span as u128) as u16)
+    }
+
+    pub fn should_suspend(&self, now: i64) -> bool {
+        self.smoothed_score(now).is_some_and(|score| score > AUTO_SUSPEND_SCORE_BPS)
+    }
+}
+
+/// Outcome of `simulate_emergency_withdraw`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct EmergencyWithdrawPreview {
+    pub amount: u64,
+    pub fee: u64,
+    /// The launch needs M-of-N approval, so this must go through a proposal
+    pub requires_proposal: bool,
+    pub required_approvals: u8,
+    /// Launch-wide insurance allowance left after this withdrawal
+    pub remaining_limit: u64,
+    /// This wallet's total withdrawn after this withdrawal, and its cap
+    pub wallet_withdrawn: u64,
+    pub wallet_cap: u64,
+    /// Earliest time this wallet could withdraw again
+    pub next_withdrawal_at: i64,
+}
+
+/// Outcome of `simulate_relock`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RelockPreview {
+    pub new_timelock_end: i64,
+    pub fee: u64,
+    pub needs_creator_consent: bool,
+    /// Relocks left after this one
+    pub relocks_remaining: u32,
+}
+
+/// Outcome of `simulate_transfer_tax`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TransferTaxPreview {
+    pub tax_bps: u16,
+    /// Tokens paid to the treasury
+    pub tax: u64,
+    /// Tokens the recipient receives
+    pub net_amount: u64,
+}
+
+/// Outcome of `estimate_launch_fees`, in lamports unless noted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct FeeEstimate {
+    /// Multiplier applied to every fee (see `TokenLaunch::fee_tier_bps`)
+    pub fee_tier_bps: u16,
+    pub base_fee: u64,
+    pub insurance_fee: u64,     // across all insurance wallets
+    pub logo_fee: u64,
+    /// Sum of the above, including the referrer's share
+    pub launch_fee: u64,
+    pub referral_fee: u64,
+    /// Class bond, held in the launch account until it closes
+    pub bond: u64,
+    /// Rent-exempt minimums for the accounts the creator funds
+    pub launch_rent: u64,
+    pub creator_accounts_rent: u64, // index and profile, on a first launch
+    pub registry_rent: u64,         // registry and a new page, when one is due
+    pub bootstrap_guard_rent: u64,
+    pub vault_rent: u64,
+    /// Everything the creator pays across `initialize_launch` and `create_token`
+    pub total: u64,
+    /// Rent for each holder's associated token account
+    pub token_account_rent: u64,
+    /// Trading fee per transfer, and inside the bootstrap window
+    pub trading_fee: u64,
+    pub bootstrap_trading_fee: Option<u64>,
+    /// Paid once by the transfer that opens each fee shard
+    pub fee_shard_rent: u64,
+    /// Paid once by the transfer that opens each stats shard, alongside
+    /// its fee shard
+    pub launch_stats_rent: u64,
+    pub transfer_tax_bps: u16,
+}
+
+/// Accounts a launch would create, for `FeeEstimate::new`
+pub struct NewLaunchAccounts {
+    pub creator_index: bool,
+    pub creator_profile: bool,
+    pub launch_registry: bool,
+    pub registry_page: bool,
+    /// Sizes of the launch vault and of a holder's associated token account
+    pub vault_len: usize,
+    pub token_account_len: usize,
+}
+
+impl FeeEstimate {
+    /// Fees for `launch`, as `initialize_launch` would leave it, using the
+    /// same fee schedule and referral split the program charges
+    pub fn new(
+        config: &GlobalConfig,
+        launch: &TokenLaunch,
+        class: Option<&LaunchClass>,
+        referred: bool,
+        rent: &Rent,
+        new_accounts: &NewLaunchAccounts,
+    ) -> Result<FeeEstimate> {
+        let rent_if = |created: bool, space: usize| if created { rent.minimum_balance(space) } else { 0 };
+        let fees = config.fees_for(launch)?;
+        let base_fee = match class {
+            Some(class) => math::mul_div(class.base_fee, launch.fee_tier_bps() as u64, 10_000)?,
+            None => fees.base_fee,
+        };
+        let insurance_fee = math::mul(launch.insurance_wallets().len() as u64, fees.insurance_fee)?;
+        let launch_fee = config.launch_fee(launch, class)?;
+        let referral_fee = if referred { config.referral_split(launch_fee)?.0 } else { 0 };
+
+        let launch_rent = rent.minimum_balance(TokenLaunch::space());
+        let creator_accounts_rent = math::add(
+            rent_if(new_accounts.creator_index, CreatorIndex::space()),
+            rent_if(new_accounts.creator_profile, CreatorProfile::space()),
+        )?;
+        let registry_rent = math::add(
+            rent_if(new_accounts.launch_registry, LaunchRegistry::space()),
+            rent_if(new_accounts.registry_page, RegistryPage::space()),
+        )?;
+        let bootstrap_guard_rent = rent_if(launch.bootstrap().is_some(), BootstrapGuard::space());
+        let vault_rent = rent.minimum_balance(new_accounts.vault_len);
+        let total = [launch.bond, launch_rent, creator_accounts_rent, registry_rent, bootstrap_guard_rent, vault_rent]
+            .into_iter()
+            .try_fold(launch_fee, math::add)?;
+
+        Ok(FeeEstimate {
+            fee_tier_bps: launch.fee_tier_bps(),
+            base_fee,
+            insurance_fee,
+            logo_fee: launch_fee - base_fee - insurance_fee,
+            launch_fee,
+            referral_fee,
+            bond: launch.bond,
+            launch_rent,
+            creator_accounts_rent,
+            registry_rent,
+            bootstrap_guard_rent,
+            vault_rent,
+            total,
+            token_account_rent: rent.minimum_balance(new_accounts.token_account_len),
+            trading_fee: fees.trading_fee,
+            bootstrap_trading_fee: launch
+                .bootstrap()
+                .map(|bootstrap| math::mul(fees.trading_fee, bootstrap.fee_multiplier as u64))
+                .transpose()?,
+            fee_shard_rent: rent.minimum_balance(FeeShard::space()),
+            launch_stats_rent: rent.minimum_balance(LaunchStats::space()),
+            transfer_tax_bps: launch.transfer_tax_bps,
+        })
+    }
+}
+
+/// Permanent log entry for one insurance withdrawal, direct or executed
+/// from a proposal, so the full history survives transaction log pruning
+#[account]
+pub struct WithdrawalRecord {
+    pub launch: Pubkey,                     // 32 bytes
+    pub seq: u64,                           // 8 bytes
+    pub wallet: Pubkey,                     // 32 bytes
+    pub amount: u64,                        // 8 bytes
+    pub destination: Pubkey,                // 32 bytes
+    pub withdrawn_at: i64,                  // 8 bytes
+    pub proposal_id: Option<u64>,           // 1 + 8 bytes
+    pub justification: String,              // 4 + max 200 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl WithdrawalRecord {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // seq
+        32 +          // wallet
+        8 +           // amount
+        32 +          // destination
+        8 +           // withdrawn_at
+        (1 + 8) +     // proposal_id
+        (4 + MAX_JUSTIFICATION_LEN) + // justification
+        1             // bump
+    }
+}
+
+/// Marks a wallet as barred from this launch's transfers. Transfers check
+/// that the PDA for each side does not exist.
+#[account]
+pub struct BlacklistEntry {
+    pub launch: Pubkey,                     // 32 bytes
+    pub wallet: Pubkey,                     // 32 bytes
+    pub added_by: Pubkey,                   // 32 bytes
+    pub added_at: i64,                      // 8 bytes
+    pub frozen: bool,                       // 1 byte
+    pub reason: String,                     // 4 + max 200 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl BlacklistEntry {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // wallet
+        32 +          // added_by
+        8 +           // added_at
+        1 +           // frozen
+        (4 + MAX_JUSTIFICATION_LEN) + // reason
+        1             // bump
+    }
+}
+
+/// Reward emissions for stakers of a launch's token. Rewards accrue per
+/// staked token through `acc_reward_per_share`; periods with nothing
+/// staked emit nothing and leave those rewards in the vault.
+#[account]
+pub struct StakingPool {
+    pub launch: Pubkey,                     // 32 bytes
+    pub stake_vault: Pubkey,                // 32 bytes
+    pub reward_vault: Pubkey,               // 32 bytes
+    pub reward_rate: u64,                   // 8 bytes, reward tokens per second
+    pub start_time: i64,                    // 8 bytes
+    pub end_time: i64,                      // 8 bytes
+    pub last_update: i64,                   // 8 bytes
+    pub acc_reward_per_share: u128,         // 16 bytes, scaled by REWARD_PRECISION
+    pub total_staked: u64,                  // 8 bytes
+    pub min_lock_duration: i64,             // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl StakingPool {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // stake_vault
+        32 +          // reward_vault
+        8 +           // reward_rate
+        8 +           // start_time
+        8 +           // end_time
+        8 +           // last_update
+        16 +          // acc_reward_per_share
+        8 +           // total_staked
+        8 +           // min_lock_duration
+        1             // bump
+    }
+
+    /// Accrue emissions from `last_update` up to `now` (capped at the end
+    /// of the emission period)
+    pub fn update(&mut self, now: i64) -> Result<()> {
+        let until = now.min(self.end_time);
+        if until > self.last_update && self.total_staked > 0 {
+            let emitted = (until - self.last_update) as u128 * self.reward_rate as u128;
+            let per_share = emitted * REWARD_PRECISION / self.total_staked as u128;
+            self.acc_reward_per_share = self
+                .acc_reward_per_share
+                .checked_add(per_share)
+                .ok_or_else(|| error!(TokenLaunchError::MathOverflow))?;
+        }
+        self.last_update = self.last_update.max(until);
+        Ok(())
+    }
+}
+
+/// One holder's stake in a launch's staking pool
+#[account]
+pub struct StakePosition {
+    pub pool: Pubkey,                       // 32 bytes
+    pub owner: Pubkey,                      // 32 bytes
+    pub amount: u64,                        // 8 bytes
+    pub reward_debt: u128,                  // 16 bytes, acc_reward_per_share * amount at last settle
+    pub pending_rewards: u64,               // 8 bytes
+    pub locked_until: i64,                  // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl StakePosition {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // pool
+        32 +          // owner
+        8 +           // amount
+        16 +          // reward_debt
+        8 +           // pending_rewards
+        8 +           // locked_until
+        1             // bump
+    }
+
+    fn accrued(&self, pool: &StakingPool) -> u128 {
+        self.amount as u128 * pool.acc_reward_per_share
+    }
+
+    /// Move rewards earned since the last checkpoint into `pending_rewards`.
+    /// Call after `StakingPool::update`.
+    pub fn settle(&mut self, pool: &StakingPool) -> Result<()> {
+        let earned = (self.accrued(pool).saturating_sub(self.reward_debt) / REWARD_PRECISION) as u64;
+        self.pending_rewards = math::add(self.pending_rewards, earned)?;
+        Ok(())
+    }
+
+    /// Record the settled point after `amount` changes
+    pub fn checkpoint(&mut self, pool: &StakingPool) {
+        self.reward_debt = self.accrued(pool);
+    }
+}
+
+/// Creator-controlled companion to the launch for off-chain routing; the
+/// keeper reads `webhook_uri` / `webhook_hash` to deliver event callbacks
+#[account]
+pub struct LaunchMetadata {
+    pub token_mint: Pubkey,                 // 32 bytes
+    pub webhook_uri: String,                // 4 + max 200 bytes
+    pub webhook_hash: [u8; 32],             // 32 bytes, sha256 of a keeper-registered endpoint
+    pub updated_at: i64,                    // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl LaunchMetadata {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // token_mint
+        (4 + MAX_URI_LEN) + // webhook_uri
+        32 +          // webhook_hash
+        8 +           // updated_at
+        1 +           // bump
+        64            // padding for future routing fields
+    }
+
+    /// Public endpoints must be https; empty clears the URI
+    pub fn validate_webhook(uri: &str) -> Result<()> {
+        require!(
+            uri.len() <= MAX_URI_LEN && (uri.is_empty() || uri.starts_with("https://")),
+            TokenLaunchError::InvalidWebhook
+        );
+        Ok(())
+    }
+
+    pub fn has_webhook(&self) -> bool {
+        !self.webhook_uri.is_empty() || self.webhook_hash != [0u8; 32]
+    }
+}
+
+/// Trading fees reflected to holders, paid out through a cumulative
+/// lamports-per-token accumulator
+#[account]
+pub struct DividendPool {
+    pub launch: Pubkey,                     // 32 bytes
+    pub acc_per_token: u128,                // 16 bytes, lamports per token scaled by REWARD_PRECISION
+    pub undistributed: u64,                 // 8 bytes, reflected but not yet folded in
+    pub total_reflected: u64,               // 8 bytes
+    pub total_paid: u64,                    // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl DividendPool {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        16 +          // acc_per_token
+        8 +           // undistributed
+        8 +           // total_reflected
+        8 +           // total_paid
+        1             // bump
+    }
+
+    pub fn record_reflection(&mut self, amount: u64) -> Result<()> {
+        self.undistributed = math::add(self.undistributed, amount)?;
+        self.total_reflected = math::add(self.total_reflected, amount)?;
+        Ok(())
+    }
+
+    /// Spread undistributed lamports over `eligible_supply` tokens. Rounding
+    /// dust stays undistributed for the next fold. Returns the amount folded.
+    pub fn fold(&mut self, eligible_supply: u64) -> Result<u64> {
+        if self.undistributed == 0 || eligible_supply == 0 {
+            return Ok(0);
+        }
+        let per_token = self.undistributed as u128 * REWARD_PRECISION / eligible_supply as u128;
+        let folded = (per_token * eligible_supply as u128 / REWARD_PRECISION) as u64;
+        self.acc_per_token = self
+            .acc_per_token
+            .checked_add(per_token)
+            .ok_or_else(|| error!(TokenLaunchError::MathOverflow))?;
+        self.undistributed -= folded;
+        Ok(folded)
+    }
+
+    /// Lamports owed to `balance` tokens since a claim's `checkpoint`
+    pub fn owed(&self, balance: u64, checkpoint: u128) -> Result<u64> {
+        let per_token = self.acc_per_token.saturating_sub(checkpoint);
+        u64::try_from(balance as u128 * per_token / REWARD_PRECISION)
+            .map_err(|_| error!(TokenLaunchError::MathOverflow))
+    }
+}
+
+/// A wallet's latest transfer in the bootstrap window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RecentBuyer {
+    pub wallet: Pubkey,
+    pub slot: u64,
+}
+
+impl RecentBuyer {
+    pub const SIZE: usize = 32 + 8;
+}
+
+/// Per-launch anti-sniper state for the bootstrap window: the current
+/// slot's volume and a ring buffer of recent buyers for the cooldown
+#[account]
+pub struct BootstrapGuard {
+    pub launch: Pubkey,                     // 32 bytes
+    pub start_slot: u64,                    // 8 bytes, first traded slot after unlock (0 = not started)
+    pub current_slot: u64,                  // 8 bytes
+    pub slot_volume: u64,                   // 8 bytes
+    pub recent_buyers: [RecentBuyer; BOOTSTRAP_RING_SIZE], // 40 * 16 bytes
+    pub cursor: u8,                         // 1 byte, next ring slot to overwrite
+    pub bump: u8,                           // 1 byte
+}
+
+impl BootstrapGuard {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // start_slot
+        8 +           // current_slot
+        8 +           // slot_volume
+        RecentBuyer::SIZE * BOOTSTRAP_RING_SIZE + // recent_buyers
+        1 +           // cursor
+        1             // bump
+    }
+
+    /// The window opens with the first transfer after unlock and lasts
+    /// `window_slots` slots
+    pub fn is_open(&self, config: &BootstrapConfig, slot: u64) -> bool {
+        self.start_slot == 0 || slot < self.start_slot.saturating_add(config.window_slots)
+    }
+
+    /// Apply the window's limits to a transfer of `amount` to `buyer`
+    pub fn record_transfer(&mut self, config: &BootstrapConfig, buyer: Pubkey, amount: u64, slot: u64) -> Result<()> {
+        let volume = if self.current_slot == slot { self.slot_volume } else { 0 };
+        let volume = math::add(volume, amount)?;
+        require!(volume <= config.max_buy_per_slot, TokenLaunchError::BootstrapSlotCapExceeded);
+
+        match self.recent_buyers.iter_mut().find(|entry| entry.wallet == buyer) {
+            Some(entry) => {
+                require!(
+                    slot >= entry.slot.saturating_add(config.cooldown_slots),
+                    TokenLaunchError::BootstrapCooldown
+                );
+                entry.slot = slot;
+            }
+            None => {
+                self.recent_buyers[self.cursor as usize] = RecentBuyer { wallet: buyer, slot };
+                self.cursor = ((self.cursor as usize + 1) % BOOTSTRAP_RING_SIZE) as u8;
+            }
+        }
+        if self.start_slot == 0 {
+            self.start_slot = slot;
+        }
+        self.current_slot = slot;
+        self.slot_volume = volume;
+        Ok(())
+    }
+}
+
+/// A holder's position in a launch's dividend pool
+#[account]
+pub struct DividendClaim {
+    pub launch: Pubkey,                     // 32 bytes
+    pub holder: Pubkey,                     // 32 bytes
+    pub checkpoint: u128,                   // 16 bytes, acc_per_token at last payout
+    pub total_claimed: u64,                 // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl DividendClaim {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // holder
+        16 +          // checkpoint
+        8 +           // total_claimed
+        1             // bump
+    }
+}
+
+/// State transition requested from `crank_launch`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrankAction {
+    /// Mark unlock schedule tranches that have come due as released
+    ReleaseTranches,
+    /// Close an expired, unexecuted withdrawal proposal, refunding its rent
+    ExpireProposal,
+    /// Reactivate a suspended launch whose appeal passed its review deadline
+    ReactivateLaunch,
+    /// Suspend an active launch whose time-weighted fraud score has risen
+    /// past the auto-suspend threshold since its last score update
+    SuspendOnScore,
+}
+
+/// A registered attestor's statement that a creator passed KYC. Checked by
+/// `initialize_launch` while the config names a KYC attestor.
+#[account]
+pub struct KycAttestation {
+    pub creator: Pubkey,                    // 32 bytes
+    pub attestor: Pubkey,                   // 32 bytes
+    pub attested_at: i64,                   // 8 bytes
+    pub expires_at: i64,                    // 8 bytes, 0 = never
+    pub bump: u8,                           // 1 byte
+}
+
+impl KycAttestation {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // creator
+        32 +          // attestor
+        8 +           // attested_at
+        8 +           // expires_at
+        1             // bump
+    }
+
+    pub fn is_expired(&self, current_timestamp: i64) -> bool {
+        self.expires_at != 0 && current_timestamp >= self.expires_at
+    }
+}
+
+/// Terms of a SOL raise, passed to `init_raise`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RaiseParams {
+    pub token_allocation: u64, // raw tokens split pro-rata among contributors
+    pub soft_cap: u64,         // lamports needed for the raise to succeed
+    pub hard_cap: u64,         // lamports after which contributions stop
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+impl RaiseParams {
+    pub fn is_valid(&self, current_timestamp: i64) -> bool {
+        self.token_allocation > 0
+            && self.soft_cap > 0
+            && self.hard_cap >= self.soft_cap
+            && self.start_time < self.end_time
+            && self.end_time > current_timestamp
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RaiseState {
+    Open,
+    Succeeded,
+    Failed,
+}
+
+/// SOL raise for a launch. Contributions are held in this account's
+/// lamports until `finalize_raise`.
+#[account]
+pub struct Raise {
+    pub launch: Pubkey,                     // 32 bytes
+    pub token_allocation: u64,              // 8 bytes
+    pub soft_cap: u64,                      // 8 bytes
+    pub hard_cap: u64,                      // 8 bytes
+    pub start_time: i64,                    // 8 bytes
+    pub end_time: i64,                      // 8 bytes
+    pub total_contributed: u64,             // 8 bytes
+    pub contributor_count: u32,             // 4 bytes
+    pub state: RaiseState,                  // 1 byte
+    pub bump: u8,                           // 1 byte
+}
+
+impl Raise {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        8 +           // token_allocation
+        8 +           // soft_cap
+        8 +           // hard_cap
+        8 +           // start_time
+        8 +           // end_time
+        8 +           // total_contributed
+        4 +           // contributor_count
+        1 +           // state
+        1             // bump
+    }
+
+    pub fn is_open(&self, current_timestamp: i64) -> bool {
+        self.state == RaiseState::Open
+            && current_timestamp >= self.start_time
+            && current_timestamp < self.end_time
+    }
+
+    /// Final state once contributions stop
+    pub fn outcome(&self) -> RaiseState {
+        if self.total_contributed >= self.soft_cap {
+            RaiseState::Succeeded
+        } else {
+            RaiseState::Failed
+        }
+    }
+
+    /// Pro-rata tokens for `contributed` lamports, rounded down
+    pub fn tokens_for(&self, contributed: u64) -> Result<u64> {
+        math::mul_div(self.token_allocation, contributed, self.total_contributed)
+    }
+}
+
+/// One wallet's SOL in a raise
+#[account]
+pub struct Contribution {
+    pub raise: Pubkey,                      // 32 bytes
+    pub contributor: Pubkey,                // 32 bytes
+    pub amount: u64,                        // 8 bytes, lamports
+    pub bump: u8,                           // 1 byte
+}
+
+impl Contribution {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // raise
+        32 +          // contributor
+        8 +           // amount
+        1             // bump
+    }
+}
+
+/// A team or advisor allocation held in its own vault, vesting on its own
+/// schedule rather than the public unlock schedule
+#[account]
+pub struct TeamAllocation {
+    pub launch: Pubkey,                     // 32 bytes
+    pub beneficiary: Pubkey,                // 32 bytes
+    pub total: u64,                         // 8 bytes
+    pub claimed: u64,                       // 8 bytes
+    pub start: i64,                         // 8 bytes
+    pub cliff_end: i64,                     // 8 bytes
+    pub end: i64,                           // 8 bytes
+    pub bump: u8,                           // 1 byte
+}
+
+impl TeamAllocation {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        32 +          // beneficiary
+        8 +           // total
+        8 +           // claimed
+        8 +           // start
+        8 +           // cliff_end
+        8 +           // end
+        1             // bump
+    }
+
+    pub fn claimable(&self, current_timestamp: i64) -> u64 {
+        linear_vested(self.total, self.start, self.cliff_end, self.end, current_timestamp)
+            .saturating_sub(self.claimed)
+    }
+}
+
+/// A creator's pending change to the token's Metaplex name, symbol and
+/// URI. The account outlives each proposal so `last_proposed_at` keeps
+/// rate-limiting the launch even after a veto.
+#[account]
+pub struct MetadataUpdate {
+    pub launch: Pubkey,                     // 32 bytes
+    pub name: String,                       // 4 + max 50 bytes
+    pub symbol: String,                     // 4 + max 10 bytes
+    pub uri: String,                        // 4 + max 200 bytes
+    pub pending: bool,                      // 1 byte
+    pub effective_at: i64,                  // 8 bytes, end of the veto window
+    pub last_proposed_at: i64,              // 8 bytes, 0 before the first proposal
+    pub bump: u8,                           // 1 byte
+}
+
+impl MetadataUpdate {
+    pub fn space() -> usize {
+        8 +           // discriminator
+        32 +          // launch
+        (4 + MAX_TOKEN_NAME_LEN) + // name
+        (4 + MAX_TOKEN_SYMBOL_LEN) + // symbol
+        (4 + MAX_URI_LEN) + // uri
+        1 +           // pending
+        8 +           // effective_at
+        8 +           // last_proposed_at
+        1             // bump
+    }
+
+    /// Replace any pending update and restart the veto window
+    pub fn propose(&mut self, name: String, symbol: String, uri: String, current_timestamp: i64) -> Result<()> {
+        validation::token_name(&name)?;
+        validation::token_symbol(&symbol)?;
+        require!(uri.len() <= MAX_URI_LEN, TokenLaunchError::MetadataUriTooLong);
+        if self.last_proposed_at != 0 {
+            require!(
+                current_timestamp >= math::add_secs(self.last_proposed_at, METADATA_UPDATE_INTERVAL)?,
+                TokenLaunchError::MetadataUpdateTooSoon
+            );
+        }
+        self.name = name;
+        self.symbol = symbol;
+        self.uri = uri;
+        self.pending = true;
+        self.effective_at = math::add_secs(current_timestamp, METADATA_VETO_WINDOW)?;
+        self.last_proposed_at = current_timestamp;
+        Ok(())
+    }
+
+    pub fn check_applicable(&self, current_timestamp: i64) -> Result<()> {
+        require!(self.pending, TokenLaunchError::NoPendingMetadataUpdate);
+        require!(
+            current_timestamp >= self.effective_at,
+            TokenLaunchError::MetadataVetoWindowActive
+        );
+        Ok(())
+    }
+}
+
+// Custom Errors
+#[error_code]
+pub enum TokenLaunchError {
+    #[msg("Timelock duration must be at least 100 days")]
+    TimelockTooShort,
+    
+    #[msg("Timelock is still active, transfers not allowed")]
+    TimelockActive,
+    
+    #[msg("Too many insurance wallets (max 10)")]
+    TooManyInsuranceWallets,
+    
+    #[msg("Insurance limit cannot exceed 50%")]
+    InsuranceLimitTooHigh,
+    
+    #[msg("Caller is not authorized insurance wallet")]
+    UnauthorizedInsurance,
+    
+    #[msg("Amount exceeds insurance withdrawal limit")]
+    ExceedsInsuranceLimit,
+    
+    #[msg("Only escrow authority can relock tokens")]
+    UnauthorizedRelock,
+    
+    #[msg("Only escrow authority can suspend launch")]
+    UnauthorizedSuspension,
+    
+    #[msg("Fraud score must be between 0.0 and 1.0")]
+    InvalidFraudScore,
+    
+    #[msg("Token launch has been suspended")]
+    LaunchInactive,
+    
+    #[msg("Insufficient fee payment")]
+    InsufficientFee,
+    
+    #[msg("Invalid network for this operation")]
+    InvalidNetwork,
+    
+    #[msg("Batch must contain between 1 and 20 launches with matching arguments")]
+    InvalidBatchSize,
+    
+    #[msg("Batch account is not a writable launch account")]
+    InvalidBatchAccount,
+
+    #[msg("Only escrow authority can annotate launches")]
+    UnauthorizedAnnotation,
+    
+    #[msg("Annotation key must be 1-16 bytes")]
+    InvalidAnnotationKey,
+    
+    #[msg("Too many annotations on this launch (max 16)")]
+    TooManyAnnotations,
+    
+    #[msg("Annotation key not found")]
+    AnnotationNotFound,
+
+    #[msg("High-risk launches cannot be used as a template")]
+    HighRiskTemplate,
+    
+    #[msg("Only the launch creator can perform this action")]
+    UnauthorizedCreator,
+
+    #[msg("Withdrawal threshold cannot exceed the number of insurance wallets")]
+    InvalidWithdrawalThreshold,
+    
+    #[msg("This launch requires multi-signature withdrawal proposals")]
+    MultisigRequired,
+    
+    #[msg("Justification too long (max 200 bytes)")]
+    JustificationTooLong,
+    
+    #[msg("Withdrawal proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Withdrawal proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Insurance wallet has already approved this proposal")]
+    AlreadyApproved,
+    
+    #[msg("Not enough insurance wallet approvals to execute")]
+    InsufficientApprovals,
+
+    #[msg("Insurance wallet must wait 24 hours between withdrawals")]
+    WithdrawalCooldownActive,
+    
+    #[msg("Amount exceeds this insurance wallet's withdrawal limit")]
+    ExceedsWalletLimit,
+
+    #[msg("Transfer fee cannot exceed 500 basis points")]
+    TransferFeeTooHigh,
+    
+    #[msg("Transfer hook can only be invoked during a token transfer")]
+    NotTransferring,
+    
+    #[msg("Metadata URI too long (max 200 bytes)")]
+    MetadataUriTooLong,
+    
+    #[msg("Creator does not match the declared multisig vault")]
+    InvalidCreatorMultisig,
+
+    #[msg("New creator must differ from the current creator")]
+    InvalidNewCreator,
+    
+    #[msg("Signer is not the pending creator")]
+    NotPendingCreator,
+    
+    #[msg("No creatorship transfer is pending")]
+    NoPendingCreator,
+    
+    #[msg("Creator index is full (max 32 launches)")]
+    CreatorIndexFull,
+
+    #[msg("Only the creator, insurance wallets, or holders of 1% of supply can propose")]
+    UnauthorizedProposer,
+    
+    #[msg("Description too long (max 200 bytes)")]
+    DescriptionTooLong,
+    
+    #[msg("Governance action is not valid for the current launch state")]
+    InvalidGovernanceAction,
+    
+    #[msg("Vote amount must be greater than zero")]
+    InvalidVoteAmount,
+    
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    
+    #[msg("Voting period is still open")]
+    VotingOpen,
+    
+    #[msg("Proposal did not reach quorum and majority")]
+    ProposalNotPassed,
+    
+    #[msg("Voting tokens have already been withdrawn")]
+    VoteAlreadyWithdrawn,
+
+    #[msg("Transfer exceeds the launch's max transaction amount")]
+    MaxTxExceeded,
+    
+    #[msg("Transfer would push the destination over the max wallet share")]
+    MaxWalletExceeded,
+    
+    #[msg("Max wallet percentage must be between 1 and 100")]
+    InvalidMaxWalletPct,
+
+    #[msg("Launch has no presale configured")]
+    PresaleNotConfigured,
+    
+    #[msg("Presale end must be in the future and before the timelock ends, with a non-zero allocation")]
+    InvalidPresale,
+    
+    #[msg("Presale is closed")]
+    PresaleClosed,
+    
+    #[msg("Presale is still open")]
+    PresaleStillOpen,
+    
+    #[msg("Merkle proof does not match the presale whitelist")]
+    InvalidMerkleProof,
+    
+    #[msg("Purchase exceeds the presale allocation")]
+    PresaleCapExceeded,
+    
+    #[msg("Purchase amount must be greater than zero")]
+    InvalidPurchaseAmount,
+
+    #[msg("Invalid bonding curve parameters or reserves")]
+    InvalidBondingCurve,
+    
+    #[msg("Bonding curve has graduated; trade on the open market")]
+    CurveGraduated,
+    
+    #[msg("Bonding curve has no tokens left for this purchase")]
+    CurveSoldOut,
+    
+    #[msg("Trade output is below the minimum accepted")]
+    SlippageExceeded,
+
+    #[msg("AMM program is not on the liquidity allowlist")]
+    UnsupportedAmm,
+    
+    #[msg("AMM took more tokens or SOL than authorized")]
+    LiquidityOverdrawn,
+    
+    #[msg("AMM did not mint any LP tokens")]
+    NoLiquidityMinted,
+
+    #[msg("Only the config admin can perform this action")]
+    UnauthorizedAdmin,
+
+    #[msg("Treasury split must sum to 10000 basis points")]
+    InvalidTreasurySplit,
+    
+    #[msg("Launch has no undistributed fees")]
+    NothingToDistribute,
+
+    #[msg("Signer is not a registered fraud oracle")]
+    UnauthorizedOracle,
+
+    #[msg("Oracle is already registered")]
+    OracleAlreadyRegistered,
+
+    #[msg("Oracle is not registered")]
+    OracleNotRegistered,
+
+    #[msg("Oracle registry is full (max 8 oracles)")]
+    OracleRegistryFull,
+
+    #[msg("Yield venue is not on the allowlist")]
+    UnsupportedYieldVenue,
+
+    #[msg("Amount exceeds the governance-approved yield allowance")]
+    YieldAllowanceExceeded,
+
+    #[msg("Yield venue moved more than authorized")]
+    YieldOverdrawn,
+
+    #[msg("Yield venue issued no shares")]
+    NoYieldSharesMinted,
+
+    #[msg("Invalid yield share amount")]
+    InvalidYieldShares,
+
+    #[msg("Signer is not the escrow authority or a panel arbiter")]
+    UnauthorizedArbiter,
+
+    #[msg("Launch is not suspended")]
+    LaunchNotSuspended,
+
+    #[msg("An appeal is already pending for this launch")]
+    AppealPending,
+
+    #[msg("No pending appeal to resolve")]
+    AppealNotPending,
+
+    #[msg("Appeal review deadline has passed")]
+    AppealReviewExpired,
+
+    #[msg("Appeal has not been upheld")]
+    AppealNotUpheld,
+
+    #[msg("Underwriting amount must be greater than zero")]
+    InvalidUnderwriteAmount,
+
+    #[msg("Underwriting for this launch has been slashed")]
+    UnderwritingSlashed,
+
+    #[msg("Underwriting for this launch has not been slashed")]
+    UnderwritingNotSlashed,
+
+    #[msg("Timelock has already expired")]
+    TimelockExpired,
+
+    #[msg("Unlock schedule needs 1-100% per period and a period of at least one day")]
+    InvalidUnlockSchedule,
+
+    #[msg("Amount exceeds the tokens unlocked so far")]
+    UnlockLimitExceeded,
+
+    #[msg("Recovery needs 1-7 guardians other than the creator and a threshold no larger than the guardian count")]
+    InvalidRecoveryPolicy,
+
+    #[msg("A recovery is already in progress")]
+    RecoveryInProgress,
+
+    #[msg("No recovery is pending")]
+    NoRecoveryPending,
+
+    #[msg("Signer is not a recovery guardian")]
+    UnauthorizedGuardian,
+
+    #[msg("Recovery has not reached its guardian quorum")]
+    RecoveryQuorumNotReached,
+
+    #[msg("Recovery challenge window is still open")]
+    ChallengeWindowOpen,
+
+    #[msg("Invalid allocation amount or vesting schedule")]
+    InvalidAllocation,
+
+    #[msg("Nothing to claim yet")]
+    NothingToClaim,
+
+    #[msg("Launch has reached the maximum number of relocks (5)")]
+    TooManyRelocks,
+
+    #[msg("Relock must extend the current timelock end")]
+    RelockMustExtend,
+
+    #[msg("Relock past the consent horizon needs the creator's co-signature")]
+    CreatorConsentRequired,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Division by zero")]
+    DivisionByZero,
+
+    #[msg("Launch account is already in the current layout")]
+    MigrationNotNeeded,
+
+    #[msg("Account is not a legacy TokenLaunch")]
+    InvalidLegacyAccount,
+
+    #[msg("Sponsorship needs a positive reimbursement within the per-wallet cap")]
+    InvalidSponsorship,
+
+    #[msg("Sponsor pool does not hold enough lamports")]
+    InsufficientSponsorFunds,
+
+    #[msg("Launch vault still holds tokens")]
+    LaunchNotEmpty,
+
+    #[msg("Archive hash must be set")]
+    InvalidArchiveHash,
+
+    #[msg("Token mint does not belong to this launch")]
+    LaunchMintMismatch,
+
+    #[msg("Token account is not the launch vault")]
+    VaultMismatch,
+
+    #[msg("Fee shard index out of range")]
+    InvalidFeeShard,
+
+    #[msg("Scoring model needs a non-zero version and hash")]
+    InvalidScoringModel,
+
+    #[msg("Model hash does not match the registered scoring model")]
+    ModelHashMismatch,
+
+    #[msg("Wallet is blacklisted for this launch")]
+    WalletBlacklisted,
+
+    #[msg("Launch does not hold the mint's freeze authority")]
+    FreezeAuthorityNotHeld,
+
+    #[msg("Airdrop recipient accounts do not match the amounts")]
+    AirdropAccountsMismatch,
+
+    #[msg("Too many airdrop recipients for one transaction")]
+    AirdropBatchTooLarge,
+
+    #[msg("Not enough compute left for the next airdrop transfer")]
+    AirdropComputeExhausted,
+
+    #[msg("Airdrop amounts must be greater than zero")]
+    InvalidAirdropAmount,
+
+    #[msg("Launch class does not exist")]
+    UnknownLaunchClass,
+
+    #[msg("Invalid launch classes")]
+    InvalidLaunchClass,
+
+    #[msg("Launch bond must be slashed before a suspended launch closes")]
+    BondNotSettled,
+
+    #[msg("Launch has no bond")]
+    NoBond,
+
+    #[msg("Referrer account does not match the declared referrer")]
+    ReferrerMismatch,
+
+    #[msg("Creators cannot refer their own launch")]
+    SelfReferral,
+
+    #[msg("Referral share exceeds the maximum")]
+    InvalidReferralShare,
+
+    #[msg("Invalid staking pool parameters")]
+    InvalidStakingPool,
+
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+
+    #[msg("Stake is still locked")]
+    StakeLocked,
+
+    #[msg("Launch tokens and tracked SOL cannot be rescued")]
+    ProtectedAsset,
+
+    #[msg("Rescue accounts do not match the proposal")]
+    RescueAccountMismatch,
+
+    #[msg("Nothing to rescue")]
+    NothingToRescue,
+
+    #[msg("Webhook URI must be https and at most 200 bytes")]
+    InvalidWebhook,
+
+    #[msg("Reflection share exceeds 5000 bps")]
+    InvalidReflectionShare,
+
+    #[msg("Launch reflects fees; pass its dividend pool")]
+    DividendPoolRequired,
+
+    #[msg("Buyback exceeds the launch's undistributed treasury fees")]
+    BuybackExceedsFees,
+
+    #[msg("Burn amount must be positive")]
+    InvalidBurnAmount,
+
+    #[msg("Tokens can only be burned from the launch vault or the creator's account")]
+    InvalidBurnSource,
+
+    #[msg("Invalid unlock condition")]
+    InvalidUnlockCondition,
+
+    #[msg("Unlock condition has not held long enough")]
+    UnlockConditionPending,
+
+    #[msg("Price feed is not the launch's Pyth feed")]
+    InvalidPriceFeed,
+
+    #[msg("Pyth price is stale or unrepresentable")]
+    StalePrice,
+
+    #[msg("Nothing to crank for this action")]
+    NothingToCrank,
+
+    #[msg("Crank action is missing or was passed the wrong accounts")]
+    CrankAccountMismatch,
+
+    #[msg("Invalid bootstrap window configuration")]
+    InvalidBootstrapConfig,
+
+    #[msg("Pass the launch's bootstrap guard, writable while the window is open")]
+    BootstrapGuardRequired,
+
+    #[msg("Bootstrap window: this slot's transfer cap is reached")]
+    BootstrapSlotCapExceeded,
+
+    #[msg("Bootstrap window: wallet is in its cooldown")]
+    BootstrapCooldown,
+
+    #[msg("Launch charges a transfer tax; pass the treasury's token account")]
+    TreasuryTokenAccountRequired,
+
+    #[msg("Only the registered KYC attestor may do this")]
+    UnauthorizedAttestor,
+
+    #[msg("Creator needs a KYC attestation from the registered attestor")]
+    KycAttestationRequired,
+
+    #[msg("KYC attestation has expired")]
+    KycAttestationExpired,
+
+    #[msg("Raise needs a positive allocation, soft cap <= hard cap and a future window")]
+    InvalidRaise,
+
+    #[msg("Raise is not accepting contributions")]
+    RaiseClosed,
+
+    #[msg("Raise window has not ended")]
+    RaiseStillOpen,
+
+    #[msg("Contribution exceeds the raise's hard cap")]
+    RaiseHardCapExceeded,
+
+    #[msg("Raise did not reach its soft cap")]
+    RaiseNotSucceeded,
+
+    #[msg("Raise reached its soft cap; claim tokens instead")]
+    RaiseNotFailed,
+
+    #[msg("Team vesting cliff must not end before the launch timelock")]
+    TeamCliffBeforeTimelock,
+
+    #[msg("Team allocations exceed the cap on total supply")]
+    TeamAllocationCapExceeded,
+
+    #[msg("Launch logo claim is invalid or does not match the compressed NFT")]
+    InvalidLogo,
+
+    #[msg("Fee recipients must be at most 5 distinct wallets with weights summing to 10000 bps")]
+    InvalidFeeRecipients,
+
+    #[msg("Pass the configured fee recipients, writable and in order")]
+    FeeRecipientMismatch,
+
+    #[msg("Registry page does not match the registry's next page")]
+    RegistryPageMismatch,
+
+    #[msg("No insurance change is pending")]
+    NoPendingInsuranceChange,
+
+    #[msg("Insurance change delay has not passed yet")]
+    InsuranceChangeDelayActive,
+
+    #[msg("Pause policy durations must be non-negative and at most 7 days")]
+    InvalidPausePolicy,
+
+    #[msg("Trading is paused by the creator")]
+    TradingPaused,
+
+    #[msg("Trading is not paused")]
+    TradingNotPaused,
+
+    #[msg("Pause duration exceeds the maximum allowed")]
+    PauseTooLong,
+
+    #[msg("Launch has used all of its trading pauses")]
+    PauseLimitReached,
+
+    #[msg("Pause cooldown has not passed since the last pause")]
+    PauseCooldownActive,
+
+    #[msg("Only the escrow authority can co-sign this change")]
+    UnauthorizedEscrow,
+
+    #[msg("DEX allowlist takes at most 4 pools and 30 days, and a window needs a pool")]
+    InvalidDexAllowlist,
+
+    #[msg("DEX allowlist window can't change after unlock")]
+    DexAllowlistLocked,
+
+    #[msg("During the DEX window transfers must go through an allowlisted pool")]
+    DexCounterpartyNotAllowed,
+
+    #[msg("Delegation needs a positive cap, a future expiry and a delegate other than the owner")]
+    InvalidDelegation,
+
+    #[msg("Signer is not the delegation's delegate or owner")]
+    UnauthorizedDelegate,
+
+    #[msg("Delegation has expired")]
+    DelegationExpired,
+
+    #[msg("Transfer exceeds the delegation's remaining allowance")]
+    DelegationCapExceeded,
+
+    #[msg("Arbiter panel needs distinct arbiters (at most 7) and a threshold between 1 and their number")]
+    InvalidArbiterPanel,
+
+    #[msg("An arbiter panel is configured; this action needs an approved arbitration proposal")]
+    ArbitrationRequired,
+
+    #[msg("Arbitration proposal is for a different launch or action")]
+    ArbitrationMismatch,
+
+    #[msg("Token name is empty, too long or has invalid characters")]
+    InvalidTokenName,
+
+    #[msg("Token symbol must be 1-10 ASCII letters or digits")]
+    InvalidTokenSymbol,
+
+    #[msg("Total supply must be greater than zero")]
+    ZeroTotalSupply,
+
+    #[msg("Token decimals exceed the maximum of 9")]
+    InvalidDecimals,
+
+    #[msg("Insurance wallet listed more than once")]
+    DuplicateInsuranceWallet,
+
+    #[msg("Metadata can only be updated once per interval")]
+    MetadataUpdateTooSoon,
+
+    #[msg("No metadata update is pending")]
+    NoPendingMetadataUpdate,
+
+    #[msg("Metadata update is still inside its veto window")]
+    MetadataVetoWindowActive,
+
+    #[msg("Metadata update veto window has closed")]
+    MetadataVetoWindowClosed,
+
+    #[msg("Metadata account does not match the launch")]
+    MetadataMismatch,
+
+    #[msg("Launch option listed more than once")]
+    DuplicateLaunchOption,
+
+    #[msg("Insurance collateral amount must be greater than zero")]
+    InvalidCollateralAmount,
+
+    #[msg("Insurance wallet has not staked the minimum collateral")]
+    InsufficientInsuranceCollateral,
+
+    #[msg("Insurance wallet has no collateral to slash")]
+    NoInsuranceCollateral,
+
+    #[msg("Recovery accounts must be distinct, non-default and at most 4")]
+    InvalidRecoveryAccounts,
+
+    #[msg("Emergency withdrawals may only go to a registered recovery account")]
+    UnregisteredRecoveryAccount,
+
+    #[msg("Template terms are out of bounds")]
+    InvalidTemplate,
+
+    #[msg("Only the template owner can update it")]
+    UnauthorizedTemplateOwner,
+
+    #[msg("Launch template account missing")]
+    TemplateRequired,
+
+    #[msg("Template was updated since this launch was signed")]
+    TemplateVersionMismatch,
+
+    #[msg("CPI accounts include a launch-owned token account it may not use")]
+    UnexpectedLaunchAccount,
+
+    #[msg("Venue accounts or data differ from the approved call")]
+    VenueCallMismatch,
+
+    #[msg("Bonding curve has not graduated yet")]
+    CurveNotGraduated,
+
+    #[msg("Bonding curve was already migrated")]
+    CurveAlreadyMigrated,
+
+    #[msg("Mint already has supply outside the launch vault")]
+    MintAlreadyHasSupply,
+
+    #[msg("Mint freeze authority must be unset, the creator or the launch PDA")]
+    FreezeAuthorityNotAllowed,
+    #[msg("The source launch must follow the launch accounts")]
+    SourceLaunchRequired,
+}
+
+// Helper Functions
+
+/// Shared body of `initialize_launch`, `initialize_launch_v2`,
+/// `initialize_from_template` and `clone_launch`
+fn process_initialize_launch(
+    ctx: Context<InitializeLaunch>,
+    params: LaunchParams,
+    recovery_accounts: Vec<Pubkey>,
+) -> Result<()> {
+    let mut launch = ctx.accounts.token_launch.load_init()?;
+    let clock = Clock::get()?;
+    let class = ctx.accounts.config.launch_class(params.launch_class)?;
+
+    // The referrer account must be the declared referrer, and not the creator
+    let referrer = params.referrer;
+    require!(
+        ctx.accounts.referrer.as_ref().map(|account| account.key()) == referrer,
+        TokenLaunchError::ReferrerMismatch
+    );
+    require!(referrer != Some(ctx.accounts.creator.key()), TokenLaunchError::SelfReferral);
+
+    // Deployments that advertise doxxed creators require a KYC attestation
+    ctx.accounts
+        .config
+        .check_kyc(ctx.accounts.kyc_attestation.as_deref(), clock.unix_timestamp)?;
+
+    // A bootstrap window needs its guard, created alongside the launch
+    require!(
+        ctx.accounts.bootstrap_guard.is_some() == params.bootstrap.is_some(),
+        TokenLaunchError::BootstrapGuardRequired
+    );
+    if let Some(guard) = &mut ctx.accounts.bootstrap_guard {
+        guard.launch = ctx.accounts.token_launch.key();
+        guard.bump = present_bump(ctx.bumps.bootstrap_guard)?;
+    }
+
+    // Validate parameters and initialize launch state
+    launch.initialize(
+        ctx.accounts.creator.key(),
+        ctx.accounts.token_mint.key(),
+        params,
+        clock.unix_timestamp,
+        ctx.accounts.config.cluster,
+        class,
+    )?;
+    launch.set_recovery_accounts(recovery_accounts, ctx.accounts.token_mint.to_account_info().owner)?;
+    ctx.accounts.creator_index.add_launch(launch.creator, launch.token_mint)?;
+    launch.set_repeat_creator(ctx.accounts.creator_profile.is_clean_repeat());
+    ctx.accounts.creator_profile.record_launch(launch.creator, ctx.bumps.creator_profile)?;
+    ctx.accounts.launch_registry.register(
+        &mut ctx.accounts.registry_page,
+        ctx.bumps.launch_registry,
+        ctx.bumps.registry_page,
+        RegistryEntry {
+            token_mint: launch.token_mint,
+            creator: launch.creator,
+            created_at: clock.unix_timestamp,
+        },
+    )?;
+
+    // Calculate and collect launch fee, less any referrer's share
+    let total_fee = ctx.accounts.config.launch_fee(&launch, class.as_ref())?;
+    let (referral_fee, treasury_fee) = match referrer {
+        Some(_) => ctx.accounts.config.referral_split(total_fee)?,
+        None => (0, total_fee),
+    };
+
+    // Transfer fee to recipient
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, treasury_fee)?;
+    ctx.accounts.treasury.record_fee(treasury_fee)?;
+
+    launch.fees_collected = treasury_fee;
+
+    if let Some(referrer) = &ctx.accounts.referrer {
+        if referral_fee > 0 {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: referrer.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, referral_fee)?;
+        }
+        emit!(ReferralPaid {
+            token_mint: launch.token_mint,
+            seq: launch.next_event_seq()?,
+            referrer: referrer.key(),
+            amount: referral_fee,
+        });
+        msg!("Referral fee: {} lamports to {}", referral_fee, referrer.key());
+    }
+
+    // The class bond stays in the launch account until it closes
+    let bond = launch.bond;
+    drop(launch);
+    if bond > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.token_launch.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, bond)?;
+        msg!("Launch bond posted: {} lamports", bond);
+    }
+
+    let launch = ctx.accounts.token_launch.load()?;
+    emit!(LaunchCreated {
+        token_mint: launch.token_mint,
+        creator: launch.creator,
+        token_name: launch.token_name().to_string(),
+        token_symbol: launch.token_symbol().to_string(),
+        total_supply: launch.total_supply,
+        timelock_end: launch.timelock_end,
+        fraud_score: launch.fraud_score,
+        fee_paid: total_fee,
+    });
+
+    msg!("Token launch initialized: {} ({})", launch.token_name(), launch.token_symbol());
+    msg!("Timelock expires: {}", launch.timelock_end);
+    msg!("Fraud score: {} bps", launch.fraud_score);
+    msg!("Fee collected: {} lamports", total_fee);
+
+    Ok(())
+}
+
+impl TokenLaunch {
+    /// Validate launch parameters and write the initial launch state
+    pub fn initialize(
+        &mut self,
+        creator: Pubkey,
+        token_mint: Pubkey,
+        params: LaunchParams,
+        current_timestamp: i64,
+        cluster: Cluster,
+        class: Option<LaunchClass>,
+    ) -> Result<()> {
+        let min_timelock = class.map_or(MIN_TIMELOCK_DURATION, |class| class.min_timelock);
+        cluster.check_timelock_min(params.timelock_duration, min_timelock)?;
+        validation::launch_params(&params)?;
+        require!(
+            params.insurance_wallets.len() <= MAX_INSURANCE_WALLETS,
+            TokenLaunchError::TooManyInsuranceWallets
+        );
+        require!(
+            params.insurance_limit <= MAX_INSURANCE_LIMIT,
+            TokenLaunchError::InsuranceLimitTooHigh
+        );
+        require!(
+            params.withdrawal_threshold as usize <= params.insurance_wallets.len(),
+            TokenLaunchError::InvalidWithdrawalThreshold
+        );
+        require!(
+            params.metadata_uri.len() <= MAX_URI_LEN,
+            TokenLaunchError::MetadataUriTooLong
+        );
+        if let Some(pct) = params.max_wallet_pct {
+            require!(pct > 0 && pct <= 100, TokenLaunchError::InvalidMaxWalletPct);
+        }
+        if let Some(schedule) = &params.unlock_schedule {
+            require!(schedule.is_valid(), TokenLaunchError::InvalidUnlockSchedule);
+        }
+        if let Some(condition) = &params.unlock_condition {
+            require!(condition.is_valid(), TokenLaunchError::InvalidUnlockCondition);
+        }
+        if let Some(bootstrap) = &params.bootstrap {
+            require!(bootstrap.is_valid(), TokenLaunchError::InvalidBootstrapConfig);
+        }
+        require!(
+            params.transfer_tax_bps <= MAX_TRANSFER_FEE_BPS,
+            TokenLaunchError::TransferFeeTooHigh
+        );
+        require!(
+            params.logo_nft.is_none() || params.logo_cnft.is_none(),
+            TokenLaunchError::InvalidLogo
+        );
+        require!(
+            params.fraud_score <= MAX_FRAUD_SCORE_BPS,
+            TokenLaunchError::InvalidFraudScore
+        );
+        if let Some(presale) = &params.presale {
+            require!(
+                presale.end_time > current_timestamp
+                    && presale.end_time < math::add_secs(current_timestamp, params.timelock_duration)?,
+                TokenLaunchError::InvalidPresale
+            );
+            require!(
+                presale.allocation > 0 && presale.allocation <= params.total_supply,
+                TokenLaunchError::InvalidPresale
+            );
+        }
+        // A multisig creator must sign as the declared Squads vault PDA
+        if let Some(multisig) = &params.creator_multisig {
+            require_keys_eq!(
+                creator,
+                multisig.vault_address(),
+                TokenLaunchError::InvalidCreatorMultisig
+            );
+        }
+
+        self.creator = creator;
+        self.token_mint = token_mint;
+        self.set_token_name(&params.token_name)?;
+        self.set_token_symbol(&params.token_symbol)?;
+        self.total_supply = params.total_supply;
+        self.timelock_end = math::add_secs(current_timestamp, params.timelock_duration)?;
+        self.launched_at = current_timestamp;
+        self.set_insurance_wallets(&params.insurance_wallets)?;
+        self.insurance_limit = params.insurance_limit;
+        self.set_logo_nft(params.logo_nft)?;
+        self.fraud_score = params.fraud_score;
+        self.fees_collected = 0;
+        self.fees_distributed = 0;
+        self.yield_allowance = 0;
+        self.set_active(true);
+        self.relock_count = 0;
+        self.total_withdrawn = 0;
+        self.timelock_duration = params.timelock_duration;
+        self.withdrawal_threshold = params.withdrawal_threshold;
+        self.proposal_count = 0;
+        self.set_metadata_uri(&params.metadata_uri)?;
+        self.set_creator_multisig(params.creator_multisig)?;
+        self.set_max_tx_amount(params.max_tx_amount)?;
+        self.set_max_wallet_pct(params.max_wallet_pct)?;
+        self.set_presale(params.presale)?;
+        self.presale_sold = 0;
+        self.set_presale_finalized(false);
+        self.set_unlock_schedule(params.unlock_schedule)?;
+        self.unlocked_so_far = 0;
+        self.set_relock_consent_days(params.relock_consent_days)?;
+        self.version = LAUNCH_VERSION;
+        self.set_launch_class(params.launch_class)?;
+        self.min_timelock_duration = min_timelock;
+        self.bond = class.map_or(0, |class| class.bond);
+        self.reflection_bps = 0;
+        self.tokens_burned = 0;
+        self.circulating_supply = params.total_supply;
+        self.set_unlock_condition(params.unlock_condition)?;
+        self.condition_since_slot = 0;
+        self.set_condition_met(false);
+        self.tranches_released = 0;
+        self.set_bootstrap(params.bootstrap)?;
+        self.transfer_tax_bps = params.transfer_tax_bps;
+        self.team_allocated = 0;
+        self.set_logo_cnft(params.logo_cnft)?;
+        self.set_logo_verified(false);
+        self.set_pending_insurance_change(None)?;
+        self.paused_until = 0;
+        self.pause_count = 0;
+        self.set_repeat_creator(false);
+        self.set_dex_allowlist(&[])?;
+        self.dex_allowlist_days = 0;
+        self.set_recovery_account_list(&[])?;
+
+        Ok(())
+    }
+
+    /// Build launch parameters for a new token reusing this launch's policy
+    /// (lock duration and insurance council), with per-token overrides.
+    /// A bootstrap window carries over, so the clone must pass its
+    /// `bootstrap_guard`.
+    pub fn clone_params(&self, overrides: LaunchOverrides) -> LaunchParams {
+        LaunchParams {
+            token_name: overrides.token_name,
+            token_symbol: overrides.token_symbol,
+            total_supply: overrides.total_supply,
+            timelock_duration: overrides.timelock_duration.unwrap_or(self.timelock_duration),
+            insurance_wallets: self.insurance_wallets().to_vec(),
+            insurance_limit: overrides.insurance_limit.unwrap_or(self.insurance_limit),
+            logo_nft: overrides.logo_nft,
+            fraud_score: overrides.fraud_score,
+            withdrawal_threshold: self.withdrawal_threshold,
+            metadata_uri: overrides.metadata_uri,
+            creator_multisig: self.creator_multisig(),
+            max_tx_amount: self.max_tx_amount(),
+            max_wallet_pct: self.max_wallet_pct(),
+            presale: None,
+            unlock_schedule: self.unlock_schedule(),
+            relock_consent_days: self.relock_consent_days(),
+            launch_class: self.launch_class(),
+            referrer: overrides.referrer,
+            unlock_condition: self.unlock_condition(),
+            bootstrap: self.bootstrap(),
+            transfer_tax_bps: self.transfer_tax_bps,
+            logo_cnft: None,
+        }
+    }
+
+    /// Shortest lock this launch may be relocked to: its class minimum,
+    /// or the global minimum for launches without a class
+    pub fn min_timelock(&self) -> i64 {
+        if self.min_timelock_duration > 0 {
+            self.min_timelock_duration
+        } else {
+            MIN_TIMELOCK_DURATION
+        }
+    }
+
+    /// Check that `signer` holds creator powers. For multisig creators the
+    /// stored Squads vault derivation is re-verified, since the vault PDA
+    /// only appears as a signer when the Squads program executes a
+    /// transaction approved by the multisig.
+    pub fn verify_creator(&self, signer: &Pubkey) -> Result<()> {
+        require_keys_eq!(*signer, self.creator, TokenLaunchError::UnauthorizedCreator);
+        if let Some(multisig) = self.creator_multisig() {
+            require_keys_eq!(
+                *signer,
+                multisig.vault_address(),
+                TokenLaunchError::InvalidCreatorMultisig
+            );
+        }
+        Ok(())
+    }
+
+    /// PDA signer seeds for the launch account, which owns the vault
+    pub fn signer_seeds<'a>(&'a self, bump: &'a [u8; 1]) -> [&'a [u8]; 3] {
+        [seeds::LAUNCH, self.token_mint.as_ref(), bump]
+    }
+
+    /// Whether transfers are restricted to allowlisted pools: the first
+    /// `dex_allowlist_days` after the timelock ends
+    pub fn is_dex_window(&self, current_timestamp: i64) -> bool {
+        let window = self.dex_allowlist_days as i64 * constants::SECONDS_PER_DAY;
+        current_timestamp >= self.timelock_end
+            && current_timestamp < self.timelock_end.saturating_add(window)
+    }
+
+    /// Inside the DEX window one side of a transfer must be owned by an
+    /// allowlisted pool, so holders can't dump OTC around the official pool
+    pub fn check_dex_counterparty(&self, from_owner: &Pubkey, to_owner: &Pubkey, current_timestamp: i64) -> Result<()> {
+        if self.is_dex_window(current_timestamp) {
+            require!(
+                self.dex_allowlist().contains(from_owner) || self.dex_allowlist().contains(to_owner),
+                TokenLaunchError::DexCounterpartyNotAllowed
+            );
+        }
+        Ok(())
+    }
+
+    pub fn is_trading_paused(&self, current_timestamp: i64) -> bool {
+        current_timestamp < self.paused_until
+    }
+
+    /// Start a creator pause of `duration` seconds under `policy`
+    pub fn pause_trading(&mut self, policy: &PausePolicy, duration: i64, current_timestamp: i64) -> Result<()> {
+        require!(!self.is_trading_paused(current_timestamp), TokenLaunchError::TradingPaused);
+        require!(
+            duration > 0 && duration <= policy.max_pause_secs,
+            TokenLaunchError::PauseTooLong
+        );
+        require!(self.pause_count < policy.max_pauses, TokenLaunchError::PauseLimitReached);
+        require!(
+            self.pause_count == 0 || current_timestamp >= math::add_secs(self.paused_until, policy.cooldown_secs)?,
+            TokenLaunchError::PauseCooldownActive
+        );
+
+        self.paused_until = math::add_secs(current_timestamp, duration)?;
+        self.pause_count += 1;
+        Ok(())
+    }
+
+    /// End the current pause early; the cooldown runs from now
+    pub fn resume_trading(&mut self, current_timestamp: i64) -> Result<()> {
+        require!(self.is_trading_paused(current_timestamp), TokenLaunchError::TradingNotPaused);
+        self.paused_until = current_timestamp;
+        Ok(())
+    }
+
+    pub fn is_timelock_expired(&self, current_timestamp: i64) -> bool {
+        current_timestamp >= self.timelock_end
+    }
+    
+    /// Approvals needed to execute an emergency withdrawal (at least one)
+    pub fn required_approvals(&self) -> usize {
+        self.withdrawal_threshold.max(1) as usize
+    }
+
+    /// Ensure `amount` fits in the remaining insurance allowance.
+    /// Returns the total insurance allowance for logging.
+    pub fn check_insurance_withdrawal(&self, amount: u64) -> Result<u64> {
+        let max_withdraw = self.insurance_allowance();
+        require!(
+            math::add(self.total_withdrawn, amount)? <= max_withdraw,
+            TokenLaunchError::ExceedsInsuranceLimit
+        );
+        Ok(max_withdraw)
+    }
+
+    /// Total tokens the insurance wallets may withdraw, against the
+    /// supply left after burns. The product is taken in u128 so large
+    /// supplies cannot overflow.
+    pub fn insurance_allowance(&self) -> u64 {
+        (self.circulating() as u128 * self.insurance_limit.min(100) as u128 / 100) as u64
+    }
+
+    /// Supply left after burns. Launches not yet migrated to v7 never had
+    /// `circulating_supply` written, so it is derived for them.
+    pub fn circulating(&self) -> u64 {
+        if self.version < 7 {
+            self.total_supply.saturating_sub(self.tokens_burned)
+        } else {
+            self.circulating_supply
+        }
+    }
+
+    /// Tokens stay locked until a configured unlock condition has held
+    pub fn check_unlock_condition(&self) -> Result<()> {
+        require!(
+            self.unlock_condition().is_none() || self.condition_met(),
+            TokenLaunchError::UnlockConditionPending
+        );
+        Ok(())
+    }
+
+    /// Record one observation of the unlock condition at `slot`. A reading
+    /// below the threshold restarts the run; once it has held for
+    /// `min_slots` the condition latches. Returns whether it just latched.
+    pub fn observe_unlock_condition(&mut self, holds: bool, slot: u64) -> bool {
+        let Some(UnlockCondition::PythPriceAbove { min_slots, .. }) = self.unlock_condition() else {
+            return false;
+        };
+        if self.condition_met() {
+            return false;
+        }
+        if !holds {
+            self.condition_since_slot = 0;
+            return false;
+        }
+        if self.condition_since_slot == 0 {
+            self.condition_since_slot = slot;
+        }
+        self.set_condition_met(slot.saturating_sub(self.condition_since_slot) >= min_slots);
+        self.condition_met()
+    }
+
+    /// Incentive for a successful `crank_launch`, paid out of the launch's
+    /// undistributed treasury fees so cranking never dips into other launches'
+    pub fn crank_reward(&self) -> u64 {
+        self.fees_collected
+            .saturating_sub(self.fees_distributed)
+            .min(CRANK_REWARD_LAMPORTS)
+    }
+
+    /// Record a burn of `amount` tokens
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        self.circulating_supply = math::sub(self.circulating(), amount)?;
+        self.tokens_burned = math::add(self.tokens_burned, amount)?;
+        Ok(())
+    }
+
+    /// Maximum any single insurance wallet may withdraw: the insurance
+    /// allowance split evenly across the registered wallets
+    pub fn per_wallet_insurance_cap(&self) -> u64 {
+        let wallets = self.insurance_wallets().len().max(1) as u64;
+        self.insurance_allowance().div_ceil(wallets)
+    }
+
+    pub fn get_remaining_insurance_limit(&self) -> u64 {
+        self.insurance_allowance().saturating_sub(self.total_withdrawn)
+    }
+    
+    /// Launch fee under the default fee schedule
+    pub fn calculate_launch_fee(&self) -> Result<u64> {
+        FeeSchedule::default().launch_fee(self)
+    }
+    
+    pub fn is_high_risk(&self) -> bool {
+        self.fraud_score > HIGH_RISK_SCORE_BPS
+    }
+
+    /// Fee multiplier in bps. High-risk launches pay a surcharge; otherwise
+    /// an oracle-attributed low score and a clean repeat creator each earn
+    /// a discount. Creator-reported scores (model version 0) earn nothing.
+    pub fn fee_tier_bps(&self) -> u16 {
+        if self.is_high_risk() {
+            return 10_000 + HIGH_RISK_SURCHARGE_BPS;
+        }
+        let mut tier = 10_000;
+        if self.score_model_version != 0 && self.fraud_score <= LOW_RISK_SCORE_BPS {
+            tier -= LOW_RISK_DISCOUNT_BPS;
+        }
+        if self.repeat_creator() {
+            tier -= REPEAT_CREATOR_DISCOUNT_BPS;
+        }
+        tier
+    }
+    
+    pub fn days_until_unlock(&self, current_timestamp: i64) -> i64 {
+        if self.is_timelock_expired(current_timestamp) {
+            0
+        } else {
+            self.timelock_end.saturating_sub(current_timestamp) / 86400
+        }
+    }
+}
+
+/// Accounts and signer for `transfer_launch_tokens`. `signer_seeds` is
+/// empty when `authority` signs the transaction itself.
+pub struct LaunchTokenTransfer<'a, 'info> {
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub from: AccountInfo<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub to: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub additional_accounts: &'a [AccountInfo<'info>],
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+/// Transfer launch tokens with `transfer_checked`, which Token-2022 mints
+/// with transfer fee or transfer hook extensions require. Extra accounts
+/// needed by the transfer hook are resolved from `additional_accounts`.
+pub fn transfer_launch_tokens(transfer: LaunchTokenTransfer, amount: u64) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        transfer.token_program.key,
+        transfer.from,
+        transfer.mint.to_account_info(),
+        transfer.to,
+        transfer.authority,
+        transfer.additional_accounts,
+        amount,
+        transfer.mint.decimals,
+        transfer.signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Size of a token account for `mint`: the account extensions a Token-2022
+/// mint requires, plus `ImmutableOwner`, which the associated token program
+/// always adds there. Without a mint, a plain SPL token account.
+pub fn token_account_len(mint: Option<&AccountInfo>, associated: bool) -> Result<usize> {
+    let Some(mint) = mint.filter(|mint| *mint.owner == spl_token_2022::ID) else {
+        return Ok(spl_token::state::Account::LEN);
+    };
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let mut extensions = ExtensionType::get_required_init_account_extensions(&state.get_extension_types()?);
+    if associated {
+        extensions.push(ExtensionType::ImmutableOwner);
+    }
+    Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)?)
+}
+
+/// Extension setup for a launch's Token-2022 mint. The launch PDA holds the
+/// transfer-fee config and withdraw authorities; the transfer hook has no
+/// authority, so the SolD hook program stays registered for good. The hook
+/// is a separate program because this one signs transfers of launch mints,
+/// and Token-2022 calling back into it would be rejected as reentrancy.
+pub fn token2022_extension_instructions(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Result<[Instruction; 2]> {
+    let (launch, _) = seeds::find_launch(mint);
+    Ok([
+        transfer_fee_instruction::initialize_transfer_fee_config(
+            token_program_id,
+            mint,
+            Some(&launch),
+            Some(&launch),
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?,
+        transfer_hook_instruction::initialize(token_program_id, mint, None, Some(TRANSFER_HOOK_PROGRAM_ID))?,
+    ])
+}
+
+/// Move lamports from a program-signed SOL account into a wSOL token
+/// account and sync its token balance
+pub fn wrap_sol_into<'info>(
+    system_program: &Program<'info, System>,
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    wsol_account: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from,
+                to: wsol_account.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    token_interface::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        token_interface::SyncNative { account: wsol_account },
+    ))
+}
+
+/// Close a wSOL token account, returning wrapped lamports and rent to `destination`
+pub fn unwrap_sol_into<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    wsol_account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: wsol_account,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
+
+impl TokenLaunch {
+    /// Check a governance action can be applied to the current launch state
+    pub fn validate_governance_action(&self, action: &GovernanceAction) -> Result<()> {
+        match action {
+            GovernanceAction::SetInsuranceLimit { insurance_limit } => {
+                require!(
+                    *insurance_limit <= MAX_INSURANCE_LIMIT,
+                    TokenLaunchError::InsuranceLimitTooHigh
+                );
+            }
+            GovernanceAction::AddInsuranceWallet { wallet } => {
+                require!(
+                    self.insurance_wallets().len() < MAX_INSURANCE_WALLETS,
+                    TokenLaunchError::TooManyInsuranceWallets
+                );
+                require!(
+                    !self.insurance_wallets().contains(wallet),
+                    TokenLaunchError::InvalidGovernanceAction
+                );
+            }
+            GovernanceAction::RemoveInsuranceWallet { wallet } => {
+                require!(
+                    self.insurance_wallets().contains(wallet),
+                    TokenLaunchError::InvalidGovernanceAction
+                );
+                require!(
+                    (self.withdrawal_threshold as usize) < self.insurance_wallets().len(),
+                    TokenLaunchError::InvalidWithdrawalThreshold
+                );
+            }
+            GovernanceAction::ReactivateLaunch => {
+                require!(!self.is_active(), TokenLaunchError::InvalidGovernanceAction);
+            }
+            GovernanceAction::RescueForeignAssets { mint, recipient } => {
+                require!(*mint != Some(self.token_mint), TokenLaunchError::ProtectedAsset);
+                require!(*recipient != Pubkey::default(), TokenLaunchError::InvalidGovernanceAction);
+            }
+            GovernanceAction::AddRecoveryAccount { account } => {
+                require!(
+                    self.recovery_accounts().len() < MAX_RECOVERY_ACCOUNTS
+                        && !self.recovery_accounts().contains(account)
+                        && *account != Pubkey::default(),
+                    TokenLaunchError::InvalidRecoveryAccounts
+                );
+            }
+            GovernanceAction::RemoveRecoveryAccount { account } => {
+                require!(
+                    self.recovery_accounts().contains(account),
+                    TokenLaunchError::InvalidGovernanceAction
+                );
+            }
+            GovernanceAction::UnwindTreasuryYield { shares, .. } => {
+                require!(*shares > 0, TokenLaunchError::InvalidYieldShares);
+            }
+            GovernanceAction::ApproveYieldDeployment { .. }
+            | GovernanceAction::BlacklistWallet { .. }
+            | GovernanceAction::UnblacklistWallet { .. } => {}
+        }
+        Ok(())
+    }
+
+    pub fn apply_governance_action(&mut self, action: &GovernanceAction) -> Result<()> {
+        match action {
+            GovernanceAction::SetInsuranceLimit { insurance_limit } => {
+                self.insurance_limit = *insurance_limit;
+            }
+            GovernanceAction::AddInsuranceWallet { wallet } => {
+                let mut wallets = self.insurance_wallets().to_vec();
+                wallets.push(*wallet);
+                self.set_insurance_wallets(&wallets)?;
+            }
+            GovernanceAction::RemoveInsuranceWallet { wallet } => {
+                let wallets: Vec<Pubkey> = self.insurance_wallets().iter().filter(|w| *w != wallet).copied().collect();
+                self.set_insurance_wallets(&wallets)?;
+            }
+            GovernanceAction::ReactivateLaunch => {
+                self.set_active(true);
+            }
+            GovernanceAction::ApproveYieldDeployment { amount } => {
+                self.yield_allowance = *amount;
+            }
+            // Carried out by add_to_blacklist / remove_from_blacklist, which
+            // accept the executed proposal as authorization
+            GovernanceAction::BlacklistWallet { .. } | GovernanceAction::UnblacklistWallet { .. } => {}
+            // Carried out by rescue_foreign_assets
+            GovernanceAction::RescueForeignAssets { .. } => {}
+            GovernanceAction::AddRecoveryAccount { account } => {
+                let mut accounts = self.recovery_accounts().to_vec();
+                accounts.push(*account);
+                self.set_recovery_account_list(&accounts)?;
+            }
+            GovernanceAction::RemoveRecoveryAccount { account } => {
+                let accounts: Vec<Pubkey> = self.recovery_accounts().iter().filter(|a| *a != account).copied().collect();
+                self.set_recovery_account_list(&accounts)?;
+            }
+            // Carried out by unwind_treasury_yield
+            GovernanceAction::UnwindTreasuryYield { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Register the launch's initial recovery accounts. Without any, the
+    /// creator's associated token account for the mint is registered, so
+    /// emergency withdrawals are never left without a destination.
+    pub fn set_recovery_accounts(&mut self, mut accounts: Vec<Pubkey>, token_program: &Pubkey) -> Result<()> {
+        if accounts.is_empty() {
+            accounts.push(get_associated_token_address_with_program_id(
+                &self.creator,
+                &self.token_mint,
+                token_program,
+            ));
+        }
+        require!(
+            accounts.len() <= MAX_RECOVERY_ACCOUNTS
+                && !accounts.contains(&Pubkey::default())
+                && accounts.iter().enumerate().all(|(i, account)| !accounts[..i].contains(account)),
+            TokenLaunchError::InvalidRecoveryAccounts
+        );
+        self.set_recovery_account_list(&accounts)
+    }
+
+    /// Emergency withdrawals only pay out to a registered recovery account
+    pub fn check_recovery_account(&self, destination: &Pubkey) -> Result<()> {
+        require!(
+            self.recovery_accounts().contains(destination),
+            TokenLaunchError::UnregisteredRecoveryAccount
+        );
+        Ok(())
+    }
+
+    /// Lamports on the launch account beyond what it accounts for: the
+    /// rent-exempt minimum and any posted class bond
+    pub fn rescuable_lamports(&self, balance: u64, rent_exempt_minimum: u64) -> u64 {
+        balance
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.bond)
+    }
+}
+
+impl TokenLaunch {
+    /// Enforce the optional per-transfer cap
+    pub fn check_max_tx(&self, amount: u64) -> Result<()> {
+        if let Some(max_tx) = self.max_tx_amount() {
+            require!(amount <= max_tx, TokenLaunchError::MaxTxExceeded);
+        }
+        Ok(())
+    }
+
+    /// Relocks must push the timelock end later, up to `MAX_RELOCKS` times
+    pub fn check_relock(&self, new_timelock_end: i64) -> Result<()> {
+        require!(self.relock_count < MAX_RELOCKS, TokenLaunchError::TooManyRelocks);
+        require!(new_timelock_end > self.timelock_end, TokenLaunchError::RelockMustExtend);
+        Ok(())
+    }
+
+    /// Whether a relock ending at `new_timelock_end` keeps tokens locked
+    /// longer in total than the creator agreed to up front
+    pub fn relock_needs_consent(&self, new_timelock_end: i64) -> bool {
+        match self.relock_consent_days() {
+            Some(days) => {
+                new_timelock_end.saturating_sub(self.launched_at) > days as i64 * constants::SECONDS_PER_DAY
+            }
+            None => false,
+        }
+    }
+
+    /// Split a transfer of `amount` into the treasury's tax (rounded down)
+    /// and what the recipient receives
+    pub fn preview_transfer_tax(&self, amount: u64) -> Result<TransferTaxPreview> {
+        let tax = math::mul_div(amount, self.transfer_tax_bps as u64, 10_000)?;
+        Ok(TransferTaxPreview {
+            tax_bps: self.transfer_tax_bps,
+            tax,
+            net_amount: math::sub(amount, tax)?,
+        })
+    }
+
+    /// Validate a relock to `new_duration` from now and report its outcome.
+    /// Shared by `relock_tokens` and `simulate_relock` so the dry run
+    /// cannot drift from the real checks.
+    pub fn preview_relock(
+        &self,
+        new_duration: i64,
+        cluster: Cluster,
+        fee: u64,
+        current_timestamp: i64,
+    ) -> Result<RelockPreview> {
+        cluster.check_timelock_min(new_duration, self.min_timelock())?;
+        let new_timelock_end = math::add_secs(current_timestamp, new_duration)?;
+        self.check_relock(new_timelock_end)?;
+
+        Ok(RelockPreview {
+            new_timelock_end,
+            fee,
+            needs_creator_consent: self.relock_needs_consent(new_timelock_end),
+            relocks_remaining: MAX_RELOCKS - self.relock_count - 1,
+        })
+    }
+
+    /// Validate an insurance withdrawal by `wallet` against the launch limit
+    /// and a copy of the wallet's record, leaving both untouched
+    pub fn preview_emergency_withdraw(
+        &self,
+        wallet: &Pubkey,
+        amount: u64,
+        record: Option<&InsuranceWalletRecord>,
+        fee: u64,
+        current_timestamp: i64,
+    ) -> Result<EmergencyWithdrawPreview> {
+        require!(
+            self.insurance_wallets().contains(wallet),
+            TokenLaunchError::UnauthorizedInsurance
+        );
+        self.check_insurance_withdrawal(amount)?;
+
+        let mut record = record.cloned().unwrap_or(InsuranceWalletRecord {
+            launch: Pubkey::default(),
+            wallet: *wallet,
+            total_withdrawn: 0,
+            withdrawal_count: 0,
+            last_withdrawal_at: 0,
+        });
+        let wallet_cap = self.per_wallet_insurance_cap();
+        record.record_withdrawal(amount, wallet_cap, current_timestamp)?;
+
+        Ok(EmergencyWithdrawPreview {
+            amount,
+            fee,
+            requires_proposal: self.required_approvals() > 1,
+            required_approvals: self.required_approvals() as u8,
+            remaining_limit: self.insurance_allowance() - math::add(self.total_withdrawn, amount)?,
+            wallet_withdrawn: record.total_withdrawn,
+            wallet_cap,
+            next_withdrawal_at: math::add_secs(current_timestamp, WITHDRAWAL_COOLDOWN)?,
+        })
+    }
+
+    /// Check a vault release against the unlock schedule, if any
+    pub fn check_unlock(&self, amount: u64, current_timestamp: i64) -> Result<()> {
+        if let Some(schedule) = self.unlock_schedule() {
+            let unlocked = schedule.unlocked_amount(self.total_supply, self.timelock_end, current_timestamp);
+            require!(
+                math::add(self.unlocked_so_far, amount)? <= unlocked,
+                TokenLaunchError::UnlockLimitExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Enforce the optional cap on any one wallet's share of supply
+    pub fn check_max_wallet(&self, balance: u64) -> Result<()> {
+        if let Some(pct) = self.max_wallet_pct() {
+            let max_balance = (self.total_supply as u128 * pct as u128 / 100) as u64;
+            require!(balance <= max_balance, TokenLaunchError::MaxWalletExceeded);
+        }
+        Ok(())
+    }
+}
+
+impl TokenLaunch {
+    /// Advance the launch's event sequence number. Every granular state
+    /// event carries one so indexers can detect gaps and order replays.
+    pub fn next_event_seq(&mut self) -> Result<u64> {
+        self.event_seq = math::add(self.event_seq, 1)?;
+        Ok(self.event_seq)
+    }
+
+    /// Claim the next WithdrawalRecord sequence number. Records are
+    /// numbered from zero, so clients derive the next record's PDA from the
+    /// current `withdrawal_record_count`.
+    pub fn next_withdrawal_record_seq(&mut self) -> Result<u64> {
+        let seq = self.withdrawal_record_count;
+        self.withdrawal_record_count = math::add(seq, 1)?;
+        Ok(seq)
+    }
+
+    /// Emit a pause toggle for the launch's current `is_active` state
+    pub fn emit_active_change(&mut self, reason: String) -> Result<()> {
+        emit!(LaunchActiveChanged {
+            token_mint: self.token_mint,
+            seq: self.next_event_seq()?,
+            is_active: self.is_active(),
+            reason,
+        });
+        Ok(())
+    }
+
+    /// Emit a risk tier change if the latest fraud score moved the launch
+    /// across the high-risk threshold
+    pub fn emit_risk_change(&mut self, was_high_risk: bool, old_score: u16) -> Result<()> {
+        if self.is_high_risk() != was_high_risk {
+            emit!(RiskTierChanged {
+                token_mint: self.token_mint,
+                seq: self.next_event_seq()?,
+                old_score,
+                new_score: self.fraud_score,
+                high_risk: self.is_high_risk(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Merkle leaf for a presale whitelist entry: keccak(buyer || allocation LE)
+pub fn presale_leaf(buyer: &Pubkey, allocation: u64) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hashv(&[buyer.as_ref(), &allocation.to_le_bytes()]).0
+}
+
+/// Verify a Merkle proof built with sorted-pair keccak hashing
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            anchor_lang::solana_program::keccak::hashv(&[&node, sibling]).0
+        } else {
+            anchor_lang::solana_program::keccak::hashv(&[sibling, &node]).0
+        }
+    });
+    computed == root
+}
+
+/// Whether `program_id` is an AMM the launch PDA may provide liquidity to
+pub fn is_allowed_amm(program_id: &Pubkey) -> bool {
+    AMM_PROGRAM_IDS
+        .iter()
+        .any(|id| id.parse::<Pubkey>().map(|id| id == *program_id).unwrap_or(false))
+}
+
+/// Fail if `accounts` include a token account the launch PDA owns or is
+/// delegated on, other than those in `allowed`. Used before CPIs the launch
+/// signs with a caller-supplied account list, where any such account could
+/// otherwise be drawn from.
+pub fn check_launch_token_accounts(accounts: &[AccountInfo], launch: &Pubkey, allowed: &[Pubkey]) -> Result<()> {
+    for account in accounts {
+        if allowed.contains(account.key) || (*account.owner != spl_token::ID && *account.owner != spl_token_2022::ID) {
+            continue;
+        }
+        let data = account.try_borrow_data()?;
+        // Mints and other token program accounts don't hold launch funds
+        if let Ok(state) = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data) {
+            require!(
+                state.base.owner != *launch && Option::<Pubkey>::from(state.base.delegate) != Some(*launch),
+                TokenLaunchError::UnexpectedLaunchAccount
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Whether `program_id` is a Pyth oracle program
+pub fn is_pyth_program(program_id: &Pubkey) -> bool {
+    PYTH_PROGRAM_IDS
+        .iter()
+        .any(|id| id.parse::<Pubkey>().map(|id| id == *program_id).unwrap_or(false))
+}
+
+/// Whether `program_id` is an allowlisted yield venue
+pub fn is_allowed_yield_venue(program_id: &Pubkey) -> bool {
+    YIELD_VENUE_PROGRAM_IDS
+        .iter()
+        .any(|id| id.parse::<Pubkey>().map(|id| id == *program_id).unwrap_or(false))
+}
+
+/// Hash of a venue call: each account's key and writability, in order,
+/// then the instruction data. Governance approves unwinds by this hash.
+pub fn yield_venue_call_hash(accounts: &[AccountInfo], data: &[u8]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(accounts.len() * 33 + data.len());
+    for account in accounts {
+        preimage.extend_from_slice(account.key.as_ref());
+        preimage.push(account.is_writable as u8);
+    }
+    preimage.extend_from_slice(data);
+    anchor_lang::solana_program::keccak::hash(&preimage).0
+}
+
+/// Load a writable allocation from remaining accounts for `claim_all`,
+/// checking it is the canonical PDA owned by this launch and beneficiary
+pub fn load_allocation<'info>(
+    allocation_info: &'info AccountInfo<'info>,
+    launch: Pubkey,
+    beneficiary: Pubkey,
+    kind: ClaimKind,
+) -> Result<Account<'info, Allocation>> {
+    require!(allocation_info.is_writable, TokenLaunchError::InvalidBatchAccount);
+    let allocation = Account::<Allocation>::try_from(allocation_info)?;
+    require!(
+        allocation.launch == launch
+            && allocation.beneficiary == beneficiary
+            && allocation.kind == kind,
+        TokenLaunchError::InvalidBatchAccount
+    );
+
+    let (expected, _) = Pubkey::find_program_address(
+        &[seeds::ALLOCATION, launch.as_ref(), beneficiary.as_ref(), &[kind as u8]],
+        &crate::ID,
+    );
+    require_keys_eq!(allocation_info.key(), expected, TokenLaunchError::InvalidBatchAccount);
+
+    Ok(allocation)
+}
+
+/// Bump of an optional account that was passed. Anchor 0.29 reports it as
+/// `u8`, 0.30 as `Option<u8>` (`None` when the account is absent).
+pub fn present_bump(bump: impl Into<Option<u8>>) -> Result<u8> {
+    bump.into().ok_or_else(|| error!(ErrorCode::ConstraintSeeds))
+}
+
+/// Lamports an account holds above its rent-exempt minimum
+pub fn lamports_above_rent(info: &AccountInfo) -> Result<u64> {
+    let rent_floor = Rent::get()?.minimum_balance(info.data_len());
+    Ok(info.lamports().saturating_sub(rent_floor))
+}
+
+/// Convert a TokenLaunch serialized with the legacy `f32` fraud score into
+/// the basis-point layout in place: the score shrinks from four bytes to
+/// two and every later field moves down to close the gap. Returns the
+/// converted score.
+pub fn migrate_legacy_fraud_score(data: &mut [u8]) -> Result<u16> {
+    require!(
+        data.len() > 8 && data[..8] == <TokenLaunch as anchor_lang::Discriminator>::DISCRIMINATOR,
+        TokenLaunchError::InvalidLegacyAccount
+    );
+    let read_len = |data: &[u8], offset: usize| -> Result<usize> {
+        let bytes = data
+            .get(offset..offset + 4)
+            .ok_or(TokenLaunchError::InvalidLegacyAccount)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+
+    // Walk the fields ahead of fraud_score, which have not changed
+    let mut offset = 8 + 32 + 32;                      // discriminator, creator, token_mint
+    offset += 4 + read_len(data, offset)?;             // token_name
+    offset += 4 + read_len(data, offset)?;             // token_symbol
+    offset += 8 + 8;                                   // total_supply, timelock_end
+    offset += 4 + 32 * read_len(data, offset)?;        // insurance_wallets
+    offset += 1;                                       // insurance_limit
+    offset += match data.get(offset) {                 // logo_nft
+        Some(0) => 1,
+        Some(1) => 33,
+        _ => return err!(TokenLaunchError::InvalidLegacyAccount),
+    };
+    let legacy = data
+        .get(offset..offset + 4)
+        .ok_or(TokenLaunchError::InvalidLegacyAccount)?;
+
+    let legacy = f32::from_le_bytes(legacy.try_into().unwrap());
+    let score = (legacy.clamp(0.0, 1.0) * MAX_FRAUD_SCORE_BPS as f32).round() as u16;
+    data[offset..offset + 2].copy_from_slice(&score.to_le_bytes());
+    data.copy_within(offset + 4.., offset + 2);
+    let len = data.len();
+    data[len - LEGACY_FRAUD_SCORE_EXTRA_BYTES..].fill(0);
+    Ok(score)
+}
+
+/// Tokens vested by `current_timestamp` out of `total`: nothing before
+/// the cliff, then linear from `start` to `end`
+pub fn linear_vested(total: u64, start: i64, cliff_end: i64, end: i64, current_timestamp: i64) -> u64 {
+    if current_timestamp < cliff_end {
+        return 0;
+    }
+    if current_timestamp >= end {
+        return total;
+    }
+    let elapsed = (current_timestamp - start) as u128;
+    let duration = (end - start) as u128;
+    (total as u128 * elapsed / duration) as u64
+}
+
+/// Decode a launch account in either layout: the zero-copy layout, or the
+/// Borsh `LegacyTokenLaunch` written before v19. Legacy accounts allocated
+/// before later fields existed decode those fields as their zero defaults.
+pub fn read_launch_layout(data: &[u8]) -> Result<TokenLaunch> {
+    require!(
+        data.len() > 8 && data[..8] == <TokenLaunch as anchor_lang::Discriminator>::DISCRIMINATOR,
+        TokenLaunchError::InvalidLegacyAccount
+    );
+    if data.len() == TokenLaunch::space() {
+        return Ok(bytemuck::pod_read_unaligned(&data[8..]));
+    }
+    // Accounts still in the f32 fraud score layout need migrate_fraud_score first
+    require!(
+        data.len() <= LegacyTokenLaunch::space(),
+        TokenLaunchError::InvalidLegacyAccount
+    );
+    let mut legacy_data = data[8..].to_vec();
+    legacy_data.resize(LegacyTokenLaunch::space() - 8, 0);
+    let legacy = LegacyTokenLaunch::deserialize(&mut &legacy_data[..])
+        .map_err(|_| error!(TokenLaunchError::InvalidLegacyAccount))?;
+    TokenLaunch::from_legacy(&legacy)
+}
+
+/// Apply the fixes due since the launch's layout version and stamp the
+/// current one. Returns the version it was upgraded from.
+pub fn upgrade_launch_layout(launch: &mut TokenLaunch) -> Result<u8> {
+    require!(launch.version < LAUNCH_VERSION, TokenLaunchError::MigrationNotNeeded);
+
+    let from_version = launch.version;
+    if from_version < 7 {
+        launch.circulating_supply = launch.total_supply.saturating_sub(launch.tokens_burned);
+    }
+    launch.version = LAUNCH_VERSION;
+    Ok(from_version)
+}
+
+/// Load a writable fee shard from remaining accounts for
+/// `sweep_fee_shards`, checking it is the launch's canonical shard PDA
+pub fn load_fee_shard<'info>(
+    shard_info: &'info AccountInfo<'info>,
+    launch: Pubkey,
+) -> Result<Account<'info, FeeShard>> {
+    require!(shard_info.is_writable, TokenLaunchError::InvalidBatchAccount);
+    let fee_shard = Account::<FeeShard>::try_from(shard_info)?;
+    require!(fee_shard.launch == launch, TokenLaunchError::InvalidBatchAccount);
+
+    let (expected, _) = seeds::find_fee_shard(&launch, fee_shard.shard);
+    require_keys_eq!(shard_info.key(), expected, TokenLaunchError::InvalidBatchAccount);
+
+    Ok(fee_shard)
+}
+
+/// Authorize a blacklist change: either an executed governance proposal for
+/// exactly `action`, or the launch creator. Returns whether governance
+/// authorized it.
+pub fn authorize_blacklist_change(
+    launch: &TokenLaunch,
+    signer: &Pubkey,
+    proposal: Option<&GovernanceProposal>,
+    action: &GovernanceAction,
+) -> Result<bool> {
+    match proposal {
+        Some(proposal) => {
+            require!(
+                proposal.executed && proposal.action == *action,
+                TokenLaunchError::InvalidGovernanceAction
+            );
+            Ok(true)
+        }
+        None => {
+            launch.verify_creator(signer)?;
+            Ok(false)
+        }
+    }
+}
+
+/// Validate an airdrop batch's amounts and return their total
+pub fn airdrop_total(launch: &TokenLaunch, amounts: &[u64]) -> Result<u64> {
+    require!(!amounts.is_empty(), TokenLaunchError::AirdropAccountsMismatch);
+    require!(amounts.len() <= MAX_AIRDROP_RECIPIENTS, TokenLaunchError::AirdropBatchTooLarge);
+    let mut total: u64 = 0;
+    for &amount in amounts {
+        require!(amount > 0, TokenLaunchError::InvalidAirdropAmount);
+        launch.check_max_tx(amount)?;
+        total = math::add(total, amount)?;
+    }
+    Ok(total)
+}
+
+// Security Validations
+pub fn validate_wallet_authority(
+    wallet: &Pubkey,
+    authorized_wallets: &[Pubkey],
+) -> Result<()> {
+    require!(
+        authorized_wallets.contains(wallet),
+        TokenLaunchError::UnauthorizedInsurance
+    );
+    Ok(())
+}
+
+pub fn validate_fee_payment(expected: u64, paid: u64) -> Result<()> {
+    require!(paid >= expected, TokenLaunchError::InsufficientFee);
+    Ok(())
+}
+
+/// A batch acts on at least one and at most `MAX_BATCH_SIZE` launches
+pub fn check_batch_size(len: usize) -> Result<()> {
+    require!(len > 0 && len <= MAX_BATCH_SIZE, TokenLaunchError::InvalidBatchSize);
+    Ok(())
+}
+
+/// Split remaining accounts into `(launch, score history)` pairs, exactly
+/// `count` of them
+pub fn batch_pairs<T>(accounts: &[T], count: usize) -> Result<std::slice::ChunksExact<'_, T>> {
+    check_batch_size(count)?;
+    let pairs = accounts.chunks_exact(2);
+    require!(
+        pairs.remainder().is_empty() && pairs.len() == count,
+        TokenLaunchError::InvalidBatchSize
+    );
+    Ok(pairs)
+}
+
+/// Open a launch passed through remaining accounts for a batch action.
+/// `AccountLoader::try_from` checks program ownership and the account
+/// discriminator.
+pub fn load_batch_launch<'info>(
+    launch_info: &'info AccountInfo<'info>,
+) -> Result<AccountLoader<'info, TokenLaunch>> {
+    require!(launch_info.is_writable, TokenLaunchError::InvalidBatchAccount);
+    let launch = AccountLoader::<TokenLaunch>::try_from(launch_info)?;
+
+    // Reject anything that is not the canonical launch PDA for its mint
+    let (expected, _) = Pubkey::find_program_address(
+        &[seeds::LAUNCH, launch.load()?.token_mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(launch_info.key(), expected, TokenLaunchError::InvalidBatchAccount);
+
+    Ok(launch)
+}
+
+/// Deserialize a launch's fraud score history passed as a remaining account
+pub fn load_score_history<'info>(
+    history_info: &'info AccountInfo<'info>,
+    launch: &Pubkey,
+) -> Result<Account<'info, FraudScoreHistory>> {
+    require!(history_info.is_writable, TokenLaunchError::InvalidBatchAccount);
+    let (expected, _) = seeds::find_fraud_score_history(launch);
+    require_keys_eq!(history_info.key(), expected, TokenLaunchError::InvalidBatchAccount);
+    Account::<FraudScoreHistory>::try_from(history_info)
+}
+
+// Event Logging
+#[event]
+pub struct LaunchCreated {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub total_supply: u64,
+    pub timelock_end: i64,
+    pub fraud_score: u16,
+    pub fee_paid: u64,
+}
+
+#[event]
+pub struct TokensMinted {
+    pub token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub metadata: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct TokensTransferred {
+    pub token_mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub fee_paid: u64,
+}
+
+#[event] 
+pub struct EmergencyWithdrawal {
+    pub token_mint: Pubkey,
+    pub insurance_wallet: Pubkey,
+    pub amount: u64,
+    pub justification: String,
+    pub remaining_limit: u64,
+}
+
+#[event]
+pub struct InsuranceCollateralStaked {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct LaunchTemplateUpdated {
+    pub template: Pubkey,
+    pub owner: Pubkey,
+    pub template_id: u64,
+    pub version: u32,
+}
+
+#[event]
+pub struct LaunchCreatedFromTemplate {
+    pub token_mint: Pubkey,
+    pub template: Pubkey,
+    pub template_version: u32,
+}
+
+#[event]
+pub struct InsuranceCollateralSlashed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub wallet: Pubkey,
+    pub withdrawal_seq: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokensRelocked {
+    pub token_mint: Pubkey,
+    pub old_timelock_end: i64,
+    pub new_timelock_end: i64,
+    pub reason: String,
+    pub relock_count: u32,
+}
+
+#[event]
+pub struct FraudScoreUpdated {
+    pub token_mint: Pubkey,
+    pub old_score: u16,
+    pub new_score: u16,
+    pub auto_suspended: bool,
+    pub model_version: u32,
+    pub model_hash: [u8; 32],
+}
+
+#[event]
+pub struct LaunchSuspended {
+    pub token_mint: Pubkey,
+    pub reason: String,
+    pub suspended_at: i64,
+}
+
+// Granular state events, each stamped with the launch's event sequence number
+#[event]
+pub struct ConfigChangeQueued {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub action: GovernanceAction,
+}
+
+#[event]
+pub struct InsuranceProposalCreated {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct InsuranceProposalApproved {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct InsuranceProposalExecuted {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct LaunchActiveChanged {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub is_active: bool,
+    pub reason: String,
+}
+
+#[event]
+pub struct RiskTierChanged {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub old_score: u16,
+    pub new_score: u16,
+    pub high_risk: bool,
+}
+
+#[event]
+pub struct CreatorshipTransferProposed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub creator: Pubkey,
+    pub pending_creator: Pubkey,
+}
+
+#[event]
+pub struct CreatorshipTransferCancelled {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub creator: Pubkey,
+    pub pending_creator: Pubkey,
+}
+
+#[event]
+pub struct CreatorshipTransferred {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct AppealFiled {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub appellant: Pubkey,
+    pub bond: u64,
+    pub review_deadline: i64,
+}
+
+#[event]
+pub struct AppealResolved {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub arbiter: Pubkey,
+    pub upheld: bool,
+}
+
+#[event]
+pub struct UnderwritingAdded {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub underwriter: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct UnderwritersSlashed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub payout_pool: u64,
+    pub circulating_supply: u64,
+}
+
+#[event]
+pub struct LaunchArchived {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub archive_hash: [u8; 32],
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ScoringModelRegistered {
+    pub version: u32,
+    pub model_hash: [u8; 32],
+    pub registered_at: i64,
+}
+
+#[event]
+pub struct BlacklistChanged {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub wallet: Pubkey,
+    pub blacklisted: bool,
+    pub frozen: bool,
+    pub by_governance: bool,
+}
+
+#[event]
+pub struct AirdropDistributed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub recipients: u32,
+    pub total_amount: u64,
+    pub fee_paid: u64,
+}
+
+#[event]
+pub struct LaunchBondSlashed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralPaid {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeChanged {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked: bool,
+    pub position_amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ForeignAssetsRescued {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub proposal_id: u64,
+    /// None for SOL
+    pub mint: Option<Pubkey>,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WebhookUpdated {
+    pub token_mint: Pubkey,
+    pub webhook_uri: String,
+    pub webhook_hash: [u8; 32],
+}
+
+#[event]
+pub struct DividendsDistributed {
+    pub token_mint: Pubkey,
+    pub folded: u64,
+    pub paid: u64,
+    pub holders: u8,
+}
+
+#[event]
+pub struct BuybackBurned {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub lamports_spent: u64,
+    pub tokens_burned: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct TokensBurned {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub from_vault: bool,
+    pub amount: u64,
+    pub circulating_supply: u64,
+}
+
+#[event]
+pub struct UnlockConditionMet {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub slot: u64,
+    pub price: i64,
+}
+
+#[event]
+pub struct LaunchCranked {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub action: CrankAction,
+    pub cranker: Pubkey,
+    pub reward: u64,
+}
+
+#[event]
+pub struct CreatorAttested {
+    pub creator: Pubkey,
+    pub attestor: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct CreatorAttestationRevoked {
+    pub creator: Pubkey,
+    pub attestor: Pubkey,
+}
+
+#[event]
+pub struct RaiseContributed {
+    pub token_mint: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub total_contributed: u64,
+}
+
+#[event]
+pub struct RaiseFinalized {
+    pub token_mint: Pubkey,
+    pub succeeded: bool,
+    pub total_contributed: u64,
+    pub contributor_count: u32,
+}
+
+#[event]
+pub struct TeamAllocationCreated {
+    pub token_mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub cliff_end: i64,
+    pub end: i64,
+}
+
+#[event]
+pub struct LogoVerified {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub tree: Pubkey,
+    pub leaf_index: u32,
+    pub asset_id: Pubkey,
+}
+
+#[event]
+pub struct InsuranceChangeProposed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub change: InsuranceChange,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct InsuranceChangeExecuted {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub change: InsuranceChange,
+}
+
+#[event]
+pub struct TradingPaused {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub paused_until: i64,
+    pub pauses_left: u8,
+}
+
+#[event]
+pub struct TradingResumed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DexAllowlistUpdated {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub pools: Vec<Pubkey>,
+    pub days: u16,
+}
+
+#[event]
+pub struct DelegateApproved {
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub delegate: Pubkey,
+    pub unspent: u64,
+}
+
+#[event]
+pub struct ArbitrationProposed {
+    pub proposal_id: u64,
+    pub launch: Pubkey,
+    pub action: ArbitrationAction,
+    pub proposer: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ArbitrationApproved {
+    pub proposal_id: u64,
+    pub arbiter: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct MetadataUpdateProposed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct MetadataUpdateVetoed {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchMetadataUpdated {
+    pub token_mint: Pubkey,
+    pub seq: u64,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+// Constants for easy reference
+pub mod constants {
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+    pub const MIN_TIMELOCK_DAYS: i64 = 100;
+    pub const BASE_FEE_LAMPORTS: u64 = 10_000_000;      // 0.01 SOL
+    pub const INSURANCE_FEE_LAMPORTS: u64 = 10_000_000;  // 0.01 SOL per wallet
+    pub const LOGO_FEE_LAMPORTS: u64 = 5_000_000;       // 0.005 SOL
+    pub const RELOCK_FEE_LAMPORTS: u64 = 20_000_000;    // 0.02 SOL
+    pub const TRADING_FEE_LAMPORTS: u64 = 10_000;       // ~0.00001 SOL
+    pub const EMERGENCY_FEE_LAMPORTS: u64 = 50_000;     // 0.00005 SOL
+}
+
+// PDA seed registry. Every account constraint, signer seed and client
+// derivation uses these constants; `#[constant]` publishes them in the IDL.
+pub mod seeds {
+    use anchor_lang::prelude::*;
+    use super::ClaimKind;
+
+    /// TokenLaunch: [LAUNCH, token_mint]
+    #[constant]
+    pub const LAUNCH: &[u8] = b"launch";
+
+    /// Launch token vault: [VAULT, token_mint]
+    #[constant]
+    pub const VAULT: &[u8] = b"vault";
+
+    /// Launch SOL vault: [SOL_VAULT, token_mint]
+    #[constant]
+    pub const SOL_VAULT: &[u8] = b"sol_vault";
+
+    /// Launch wSOL account for liquidity: [WSOL_VAULT, token_mint]
+    #[constant]
+    pub const WSOL_VAULT: &[u8] = b"wsol_vault";
+
+    /// GlobalConfig singleton: [CONFIG]
+    #[constant]
+    pub const CONFIG: &[u8] = b"config";
+
+    /// Treasury singleton: [TREASURY]
+    #[constant]
+    pub const TREASURY: &[u8] = b"treasury";
+
+    /// OracleRegistry singleton: [ORACLE_REGISTRY]
+    #[constant]
+    pub const ORACLE_REGISTRY: &[u8] = b"oracle_registry";
+
+    /// CreatorIndex: [CREATOR_INDEX, creator]
+    #[constant]
+    pub const CREATOR_INDEX: &[u8] = b"creator_index";
+
+    /// InsuranceWalletRecord: [INSURANCE_WALLET, launch, wallet]
+    #[constant]
+    pub const INSURANCE_WALLET: &[u8] = b"insurance_wallet";
+
+    /// InsuranceCollateralPool: [INSURANCE_COLLATERAL_POOL, launch]
+    #[constant]
+    pub const INSURANCE_COLLATERAL_POOL: &[u8] = b"insurance_collateral_pool";
+
+    /// InsuranceCollateral: [INSURANCE_COLLATERAL, launch, wallet]
+    #[constant]
+    pub const INSURANCE_COLLATERAL: &[u8] = b"insurance_collateral";
+
+    /// WithdrawalProposal: [WITHDRAWAL_PROPOSAL, launch, proposal_id LE]
+    #[constant]
+    pub const WITHDRAWAL_PROPOSAL: &[u8] = b"withdrawal_proposal";
+
+    /// GovernanceProposal: [GOVERNANCE_PROPOSAL, launch, proposal_id LE]
+    #[constant]
+    pub const GOVERNANCE_PROPOSAL: &[u8] = b"governance_proposal";
+
+    /// Escrowed voting tokens: [VOTE_ESCROW, proposal]
+    #[constant]
+    pub const VOTE_ESCROW: &[u8] = b"vote_escrow";
+
+    /// VoteRecord: [VOTE_RECORD, proposal, voter]
+    #[constant]
+    pub const VOTE_RECORD: &[u8] = b"vote_record";
+
+    /// PresalePurchase: [PRESALE_PURCHASE, launch, buyer]
+    #[constant]
+    pub const PRESALE_PURCHASE: &[u8] = b"presale_purchase";
+
+    /// BondingCurve: [BONDING_CURVE, token_mint]
+    #[constant]
+    pub const BONDING_CURVE: &[u8] = b"bonding_curve";
+
+    /// TeamAllocation: [TEAM_ALLOCATION, launch, beneficiary]
+    #[constant]
+    pub const TEAM_ALLOCATION: &[u8] = b"team_allocation";
+
+    /// Team vesting token vault: [TEAM_VAULT, team_allocation]
+    #[constant]
+    pub const TEAM_VAULT: &[u8] = b"team_vault";
+
+    /// Raise: [RAISE, token_mint]
+    #[constant]
+    pub const RAISE: &[u8] = b"raise";
+
+    /// Contribution: [CONTRIBUTION, raise, contributor]
+    #[constant]
+    pub const CONTRIBUTION: &[u8] = b"contribution";
+
+    /// Bonding curve SOL reserve: [CURVE_RESERVE, token_mint]
+    #[constant]
+    pub const CURVE_RESERVE: &[u8] = b"curve_reserve";
+
+    /// Bonding curve token vault: [CURVE_VAULT, token_mint]
+    #[constant]
+    pub const CURVE_VAULT: &[u8] = b"curve_vault";
+
+    /// Locked LP tokens: [LP_VAULT, token_mint]
+    #[constant]
+    pub const LP_VAULT: &[u8] = b"lp_vault";
+
+    /// LiquidityLock: [LIQUIDITY_LOCK, token_mint]
+    #[constant]
+    pub const LIQUIDITY_LOCK: &[u8] = b"liquidity_lock";
+
+    /// LaunchAnnotations: [ANNOTATIONS, token_mint]
+    #[constant]
+    pub const ANNOTATIONS: &[u8] = b"annotations";
+
+    /// Transfer hook account list, under TRANSFER_HOOK_PROGRAM_ID:
+    /// [EXTRA_ACCOUNT_METAS, token_mint]
+    #[constant]
+    pub const EXTRA_ACCOUNT_METAS: &[u8] = b"extra-account-metas";
+
+    /// Yield venue shares: [YIELD_VAULT, token_mint]
+    #[constant]
+    pub const YIELD_VAULT: &[u8] = b"yield_vault";
+
+    /// YieldPosition: [YIELD_POSITION, token_mint]
+    #[constant]
+    pub const YIELD_POSITION: &[u8] = b"yield_position";
+
+    /// AppealState: [APPEAL, launch]
+    #[constant]
+    pub const APPEAL: &[u8] = b"appeal";
+
+    /// UnderwritingPool: [UNDERWRITING, launch]
+    #[constant]
+    pub const UNDERWRITING: &[u8] = b"underwriting";
+
+    /// UnderwriterStake: [UNDERWRITER, launch, underwriter]
+    #[constant]
+    pub const UNDERWRITER: &[u8] = b"underwriter";
+
+    /// RecoveryPolicy: [RECOVERY, launch]
+    #[constant]
+    pub const RECOVERY: &[u8] = b"recovery";
+
+    /// Allocation token vault: [ALLOCATION_VAULT, token_mint]
+    #[constant]
+    pub const ALLOCATION_VAULT: &[u8] = b"allocation_vault";
+
+    /// Allocation: [ALLOCATION, launch, beneficiary, kind]
+    #[constant]
+    pub const ALLOCATION: &[u8] = b"allocation";
+
+    /// LaunchArchive, kept after close: [ARCHIVE, token_mint]
+    #[constant]
+    pub const ARCHIVE: &[u8] = b"archive";
+
+    /// SponsorPool: [SPONSOR_POOL, launch]
+    #[constant]
+    pub const SPONSOR_POOL: &[u8] = b"sponsor_pool";
+
+    /// SponsoredWallet: [SPONSORED_WALLET, launch, wallet]
+    #[constant]
+    pub const SPONSORED_WALLET: &[u8] = b"sponsored_wallet";
+
+    /// FeeShard: [FEE_SHARD, launch, shard]
+    #[constant]
+    pub const FEE_SHARD: &[u8] = b"fee_shard";
+
+    /// LaunchStats: [LAUNCH_STATS, launch, shard]
+    #[constant]
+    pub const LAUNCH_STATS: &[u8] = b"launch_stats";
+
+    /// BlacklistEntry: [BLACKLIST, launch, wallet]
+    #[constant]
+    pub const BLACKLIST: &[u8] = b"blacklist";
+
+    /// StakingPool: [STAKING_POOL, launch]
+    #[constant]
+    pub const STAKING_POOL: &[u8] = b"staking_pool";
+
+    /// Staked token vault: [STAKE_VAULT, token_mint]
+    #[constant]
+    pub const STAKE_VAULT: &[u8] = b"stake_vault";
+
+    /// Staking reward vault: [REWARD_VAULT, token_mint]
+    #[constant]
+    pub const REWARD_VAULT: &[u8] = b"reward_vault";
+
+    /// StakePosition: [STAKE_POSITION, pool, owner]
+    #[constant]
+    pub const STAKE_POSITION: &[u8] = b"stake_position";
+
+    /// LaunchMetadata: [LAUNCH_METADATA, token_mint]
+    #[constant]
+    pub const LAUNCH_METADATA: &[u8] = b"launch_metadata";
+
+    /// ArbiterPanel: [ARBITER_PANEL]
+    #[constant]
+    pub const ARBITER_PANEL: &[u8] = b"arbiter_panel";
+
+    /// ArbitrationProposal: [ARBITRATION_PROPOSAL, proposal_id as u64 LE]
+    #[constant]
+    pub const ARBITRATION_PROPOSAL: &[u8] = b"arbitration_proposal";
+
+    /// Delegation: [DELEGATION, token_account]
+    #[constant]
+    pub const DELEGATION: &[u8] = b"delegation";
+
+    /// MetadataUpdate: [METADATA_UPDATE, launch]
+    #[constant]
+    pub const METADATA_UPDATE: &[u8] = b"metadata_update";
+
+    /// CreatorProfile: [CREATOR_PROFILE, creator]
+    #[constant]
+    pub const CREATOR_PROFILE: &[u8] = b"creator_profile";
+
+    /// LaunchRegistry: [LAUNCH_REGISTRY]
+    #[constant]
+    pub const LAUNCH_REGISTRY: &[u8] = b"launch_registry";
+
+    /// RegistryPage: [REGISTRY_PAGE, page as u64 LE]
+    #[constant]
+    pub const REGISTRY_PAGE: &[u8] = b"registry_page";
+
+    /// KycAttestation: [KYC_ATTESTATION, creator]
+    #[constant]
+    pub const KYC_ATTESTATION: &[u8] = b"kyc_attestation";
+
+    /// BootstrapGuard: [BOOTSTRAP_GUARD, launch]
+    #[constant]
+    pub const BOOTSTRAP_GUARD: &[u8] = b"bootstrap_guard";
+
+    /// DividendPool: [DIVIDEND_POOL, launch]
+    #[constant]
+    pub const DIVIDEND_POOL: &[u8] = b"dividend_pool";
+
+    /// DividendClaim: [DIVIDEND_CLAIM, launch, holder]
+    #[constant]
+    pub const DIVIDEND_CLAIM: &[u8] = b"dividend_claim";
+
+    /// WithdrawalRecord: [WITHDRAWAL_RECORD, launch, seq as u64 LE]
+    #[constant]
+    pub const WITHDRAWAL_RECORD: &[u8] = b"withdrawal_record";
+
+    /// FraudScoreHistory: [FRAUD_SCORE_HISTORY, launch]
+    #[constant]
+    pub const FRAUD_SCORE_HISTORY: &[u8] = b"fraud_score_history";
+
+    /// ScoringModel: [SCORING_MODEL, version as u32 LE]
+    #[constant]
+    pub const SCORING_MODEL: &[u8] = b"scoring_model";
+
+    /// LaunchTemplate: [LAUNCH_TEMPLATE, owner, template_id as u64 LE]
+    #[constant]
+    pub const LAUNCH_TEMPLATE: &[u8] = b"launch_template";
+
+    fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &crate::ID)
+    }
+
+    pub fn find_launch(token_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[LAUNCH, token_mint.as_ref()])
+    }
+
+    pub fn find_vault(token_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[VAULT, token_mint.as_ref()])
+    }
+
+    pub fn find_sol_vault(token_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[SOL_VAULT, token_mint.as_ref()])
+    }
+
+    pub fn find_config() -> (Pubkey, u8) {
+        find(&[CONFIG])
+    }
+
+    pub fn find_treasury() -> (Pubkey, u8) {
+        find(&[TREASURY])
+    }
+
+    pub fn find_oracle_registry() -> (Pubkey, u8) {
+        find(&[ORACLE_REGISTRY])
+    }
+
+    pub fn find_creator_index(creator: &Pubkey) -> (Pubkey, u8) {
+        find(&[CREATOR_INDEX, creator.as_ref()])
+    }
+
+    pub fn find_arbiter_panel() -> (Pubkey, u8) {
+        find(&[ARBITER_PANEL])
+    }
+
+    pub fn find_arbitration_proposal(proposal_id: u64) -> (Pubkey, u8) {
+        find(&[ARBITRATION_PROPOSAL, &proposal_id.to_le_bytes()])
+    }
+
+    pub fn find_delegation(token_account: &Pubkey) -> (Pubkey, u8) {
+        find(&[DELEGATION, token_account.as_ref()])
+    }
+
+    pub fn find_metadata_update(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[METADATA_UPDATE, launch.as_ref()])
+    }
+
+    pub fn find_creator_profile(creator: &Pubkey) -> (Pubkey, u8) {
+        find(&[CREATOR_PROFILE, creator.as_ref()])
+    }
+
+    pub fn find_launch_registry() -> (Pubkey, u8) {
+        find(&[LAUNCH_REGISTRY])
+    }
+
+    pub fn find_registry_page(page: u64) -> (Pubkey, u8) {
+        find(&[REGISTRY_PAGE, &page.to_le_bytes()])
+    }
+
+    pub fn find_insurance_wallet(launch: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+        find(&[INSURANCE_WALLET, launch.as_ref(), wallet.as_ref()])
+    }
+
+    pub fn find_insurance_collateral_pool(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[INSURANCE_COLLATERAL_POOL, launch.as_ref()])
+    }
+
+    pub fn find_insurance_collateral(launch: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+        find(&[INSURANCE_COLLATERAL, launch.as_ref(), wallet.as_ref()])
+    }
+
+    pub fn find_withdrawal_proposal(launch: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+        find(&[WITHDRAWAL_PROPOSAL, launch.as_ref(), &proposal_id.to_le_bytes()])
+    }
+
+    pub fn find_governance_proposal(launch: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+        find(&[GOVERNANCE_PROPOSAL, launch.as_ref(), &proposal_id.to_le_bytes()])
+    }
+
+    pub fn find_vote_record(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+        find(&[VOTE_RECORD, proposal.as_ref(), voter.as_ref()])
+    }
+
+    pub fn find_presale_purchase(launch: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+        find(&[PRESALE_PURCHASE, launch.as_ref(), buyer.as_ref()])
+    }
+
+    pub fn find_allocation(launch: &Pubkey, beneficiary: &Pubkey, kind: ClaimKind) -> (Pubkey, u8) {
+        find(&[ALLOCATION, launch.as_ref(), beneficiary.as_ref(), &[kind as u8]])
+    }
+
+    pub fn find_fee_shard(launch: &Pubkey, shard: u8) -> (Pubkey, u8) {
+        find(&[FEE_SHARD, launch.as_ref(), &[shard]])
+    }
+
+    pub fn find_launch_stats(launch: &Pubkey, shard: u8) -> (Pubkey, u8) {
+        find(&[LAUNCH_STATS, launch.as_ref(), &[shard]])
+    }
+
+    pub fn find_staking_pool(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[STAKING_POOL, launch.as_ref()])
+    }
+
+    pub fn find_stake_position(pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        find(&[STAKE_POSITION, pool.as_ref(), owner.as_ref()])
+    }
+
+    pub fn find_launch_metadata(token_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[LAUNCH_METADATA, token_mint.as_ref()])
+    }
+
+    pub fn find_team_allocation(launch: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+        find(&[TEAM_ALLOCATION, launch.as_ref(), beneficiary.as_ref()])
+    }
+
+    pub fn find_team_vault(team_allocation: &Pubkey) -> (Pubkey, u8) {
+        find(&[TEAM_VAULT, team_allocation.as_ref()])
+    }
+
+    pub fn find_raise(token_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[RAISE, token_mint.as_ref()])
+    }
+
+    pub fn find_contribution(raise: &Pubkey, contributor: &Pubkey) -> (Pubkey, u8) {
+        find(&[CONTRIBUTION, raise.as_ref(), contributor.as_ref()])
+    }
+
+    pub fn find_kyc_attestation(creator: &Pubkey) -> (Pubkey, u8) {
+        find(&[KYC_ATTESTATION, creator.as_ref()])
+    }
+
+    pub fn find_bootstrap_guard(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[BOOTSTRAP_GUARD, launch.as_ref()])
+    }
+
+    pub fn find_dividend_pool(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[DIVIDEND_POOL, launch.as_ref()])
+    }
+
+    pub fn find_dividend_claim(launch: &Pubkey, holder: &Pubkey) -> (Pubkey, u8) {
+        find(&[DIVIDEND_CLAIM, launch.as_ref(), holder.as_ref()])
+    }
+
+    pub fn find_blacklist(launch: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+        find(&[BLACKLIST, launch.as_ref(), wallet.as_ref()])
+    }
+
+    pub fn find_withdrawal_record(launch: &Pubkey, seq: u64) -> (Pubkey, u8) {
+        find(&[WITHDRAWAL_RECORD, launch.as_ref(), &seq.to_le_bytes()])
+    }
+
+    pub fn find_scoring_model(version: u32) -> (Pubkey, u8) {
+        find(&[SCORING_MODEL, &version.to_le_bytes()])
+    }
+
+    pub fn find_fraud_score_history(launch: &Pubkey) -> (Pubkey, u8) {
+        find(&[FRAUD_SCORE_HISTORY, launch.as_ref()])
+    }
+
+    pub fn find_launch_template(owner: &Pubkey, template_id: u64) -> (Pubkey, u8) {
+        find(&[LAUNCH_TEMPLATE, owner.as_ref(), &template_id.to_le_bytes()])
+    }
+}
+
+// Stable surface for programs that compose with launches (launchpad
+// routers, aggregators). With the `cpi` feature (which implies
+// `no-entrypoint`) Anchor generates `cpi::initialize_launch`,
+// `cpi::transfer_tokens` and their `cpi::accounts` structs; programs that
+// build the instructions themselves use the discriminators and account
+// layouts here. Tests pin both, so changing either is a breaking change.
+pub mod interface {
+    use anchor_lang::prelude::*;
+
+    /// sha256("global:initialize_launch")[..8]
+    pub const INITIALIZE_LAUNCH_DISCRIMINATOR: [u8; 8] = [90, 201, 220, 142, 112, 253, 100, 13];
+    /// sha256("global:initialize_launch_v2")[..8]
+    pub const INITIALIZE_LAUNCH_V2_DISCRIMINATOR: [u8; 8] = [176, 21, 196, 90, 68, 112, 226, 143];
+    /// sha256("global:initialize_from_template")[..8]
+    pub const INITIALIZE_FROM_TEMPLATE_DISCRIMINATOR: [u8; 8] = [204, 144, 64, 176, 23, 168, 183, 171];
+    /// sha256("global:transfer_tokens")[..8]
+    pub const TRANSFER_TOKENS_DISCRIMINATOR: [u8; 8] = [54, 180, 238, 175, 74, 85, 126, 188];
+
+    /// One account of an instruction, in the order the program expects.
+    /// An optional account left out is passed as the program id.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct AccountSpec {
+        pub name: &'static str,
+        pub is_signer: bool,
+        pub is_writable: bool,
+        pub is_optional: bool,
+    }
+
+    const fn account(name: &'static str, is_signer: bool, is_writable: bool, is_optional: bool) -> AccountSpec {
+        AccountSpec { name, is_signer, is_writable, is_optional }
+    }
+
+    /// Accounts of `initialize_launch` and `initialize_launch_v2`. Also
+    /// those of `initialize_from_template`, followed by the template.
+    pub const INITIALIZE_LAUNCH_ACCOUNTS: [AccountSpec; 13] = [
+        account("creator", true, true, false),
+        account("token_launch", false, true, false),
+        account("creator_index", false, true, false),
+        account("creator_profile", false, true, false),
+        account("launch_registry", false, true, false),
+        account("registry_page", false, true, false),
+        account("token_mint", false, false, false),
+        account("config", false, false, false),
+        account("treasury", false, true, false),
+        account("referrer", false, true, true),
+        account("kyc_attestation", false, false, true),
+        account("bootstrap_guard", false, true, true),
+        account("system_program", false, false, false),
+    ];
+
+    /// Accounts of `transfer_tokens`, followed by the transfer hook's
+    /// accounts for Token-2022 mints. `bootstrap_guard` must be passed
+    /// writable while the launch's bootstrap window is open.
+    pub const TRANSFER_TOKENS_ACCOUNTS: [AccountSpec; 16] = [
+        account("payer", true, true, false),
+        account("token_launch", false, false, false),
+        account("token_mint", false, false, false),
+        account("from_token_account", false, true, false),
+        account("to_token_account", false, true, false),
+        account("from_blacklist", false, false, false),
+        account("to_blacklist", false, false, false),
+        account("authority", true, false, false),
+        account("config", false, false, false),
+        account("fee_shard", false, true, false),
+        account("launch_stats", false, true, false),
+        account("treasury_token_account", false, true, true),
+        account("bootstrap_guard", false, false, true),
+        account("delegation", false, true, true),
+        account("token_program", false, false, false),
+        account("system_program", false, false, false),
+    ];
+
+    /// Metas for `accounts` with the given keys, in order; `None` leaves an
+    /// optional account out
+    pub fn account_metas(accounts: &[AccountSpec], keys: &[Option<Pubkey>]) -> Vec<AccountMeta> {
+        assert_eq!(accounts.len(), keys.len(), "one key per account");
+        accounts
+            .iter()
+            .zip(keys)
+            .map(|(spec, key)| match key {
+                Some(key) if spec.is_writable => AccountMeta::new(*key, spec.is_signer),
+                Some(key) => AccountMeta::new_readonly(*key, spec.is_signer),
+                None => {
+                    assert!(spec.is_optional, "{} is required", spec.name);
+                    AccountMeta::new_readonly(crate::ID, false)
+                }
+            })
+            .collect()
+    }
+}
+
+// Display helpers for client SDKs and frontends
+pub mod display {
+    use super::constants::SECONDS_PER_DAY;
+    use super::TokenLaunch;
+
+    pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    /// Lamports as a SOL amount, e.g. 10_000_000 -> "0.01"
+    pub fn lamports_to_sol(lamports: u64) -> String {
+        to_ui_amount(lamports, 9)
+    }
+
+    /// Parse a SOL amount such as "1.5" into lamports
+    pub fn sol_to_lamports(sol: &str) -> Option<u64> {
+        from_ui_amount(sol, 9)
+    }
+
+    /// Raw token amount as a UI amount using the mint's decimals,
+    /// without trailing zeros, e.g. (1_500_000, 6) -> "1.5"
+    pub fn to_ui_amount(raw: u64, decimals: u8) -> String {
+        let scale = 10u128.pow(decimals as u32);
+        let whole = raw as u128 / scale;
+        let frac = raw as u128 % scale;
+        if frac == 0 {
+            return whole.to_string();
+        }
+        let frac = format!("{:0width$}", frac, width = decimals as usize);
+        format!("{}.{}", whole, frac.trim_end_matches('0'))
+    }
+
+    /// Parse a UI amount into a raw token amount. Returns None for
+    /// malformed input, more fractional digits than `decimals`, or overflow.
+    pub fn from_ui_amount(ui: &str, decimals: u8) -> Option<u64> {
+        let (whole, frac) = match ui.split_once('.') {
+            Some((whole, frac)) => (whole, frac),
+            None => (ui, ""),
+        };
+        if (whole.is_empty() && frac.is_empty()) || frac.len() > decimals as usize {
+            return None;
+        }
+        if !whole.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let whole: u128 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+        let frac_raw: u128 = format!("{:0<width$}", frac, width = decimals as usize)
+            .parse()
+            .unwrap_or(0);
+        let raw = whole.checked_mul(10u128.pow(decimals as u32))? + frac_raw;
+        u64::try_from(raw).ok()
+    }
+
+    /// Remaining seconds as a countdown, e.g. "42 days left", "3 hours left"
+    pub fn format_remaining(seconds: i64) -> String {
+        if seconds <= 0 {
+            return "unlocked".to_string();
+        }
+        let (count, unit) = if seconds >= SECONDS_PER_DAY {
+            (seconds / SECONDS_PER_DAY, "day")
+        } else if seconds >= 3_600 {
+            (seconds / 3_600, "hour")
+        } else if seconds >= 60 {
+            (seconds / 60, "minute")
+        } else {
+            (seconds, "second")
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        format!("{} {}{} left", count, unit, plural)
+    }
+
+    /// Timelock countdown for a launch, consistent with `days_until_unlock`
+    pub fn unlock_countdown(launch: &TokenLaunch, current_timestamp: i64) -> String {
+        if launch.is_timelock_expired(current_timestamp) {
+            return "unlocked".to_string();
+        }
+        format_remaining(launch.timelock_end - current_timestamp)
+    }
+}
+
+// Bonding curve math for fair launches
+pub mod bonding_curve {
+    /// Tokens received for `sol_in` lamports on a constant-product curve
+    /// over virtual reserves. Rounds in the curve's favor.
+    pub fn buy_quote(virtual_sol: u64, virtual_tokens: u64, sol_in: u64) -> Option<u64> {
+        let k = virtual_sol as u128 * virtual_tokens as u128;
+        let new_sol = virtual_sol as u128 + sol_in as u128;
+        let new_tokens = div_ceil(k, new_sol)?;
+        u64::try_from((virtual_tokens as u128).checked_sub(new_tokens)?).ok()
+    }
+
+    /// Lamports received for selling `tokens_in` back into the curve.
+    /// Rounds in the curve's favor.
+    pub fn sell_quote(virtual_sol: u64, virtual_tokens: u64, tokens_in: u64) -> Option<u64> {
+        let k = virtual_sol as u128 * virtual_tokens as u128;
+        let new_tokens = virtual_tokens as u128 + tokens_in as u128;
+        let new_sol = div_ceil(k, new_tokens)?;
+        u64::try_from((virtual_sol as u128).checked_sub(new_sol)?).ok()
+    }
+
+    /// Current spot price in lamports per whole token
+    pub fn spot_price(virtual_sol: u64, virtual_tokens: u64, decimals: u8) -> u64 {
+        if virtual_tokens == 0 {
+            return 0;
+        }
+        let scale = 10u128.pow(decimals as u32);
+        (virtual_sol as u128 * scale / virtual_tokens as u128) as u64
+    }
+
+    fn div_ceil(numerator: u128, denominator: u128) -> Option<u128> {
+        if denominator == 0 {
+            return None;
+        }
+        Some(numerator.div_ceil(denominator))
+    }
+}
+
+// Checked arithmetic for on-chain accounting. Products are widened to
+// u128 and any overflow or underflow surfaces as `MathOverflow`.
+pub mod math {
+    use super::TokenLaunchError;
+    use anchor_lang::prelude::*;
+
+    pub fn add(a: u64, b: u64) -> Result<u64> {
+        a.checked_add(b).ok_or_else(|| error!(TokenLaunchError::MathOverflow))
+    }
+
+    pub fn sub(a: u64, b: u64) -> Result<u64> {
+        a.checked_sub(b).ok_or_else(|| error!(TokenLaunchError::MathOverflow))
+    }
+
+    pub fn mul(a: u64, b: u64) -> Result<u64> {
+        a.checked_mul(b).ok_or_else(|| error!(TokenLaunchError::MathOverflow))
+    }
+
+    /// `a * b / denominator`, rounded down
+    pub fn mul_div(a: u64, b: u64, denominator: u64) -> Result<u64> {
+        require!(denominator > 0, TokenLaunchError::DivisionByZero);
+        u64::try_from(a as u128 * b as u128 / denominator as u128)
+            .map_err(|_| error!(TokenLaunchError::MathOverflow))
+    }
+
+    /// `percent`% of `amount`, rounded down
+    pub fn pct(amount: u64, percent: u64) -> Result<u64> {
+        mul_div(amount, percent, 100)
+    }
+
+    /// Timestamp `seconds` after `timestamp`
+    pub fn add_secs(timestamp: i64, seconds: i64) -> Result<i64> {
+        timestamp.checked_add(seconds).ok_or_else(|| error!(TokenLaunchError::MathOverflow))
+    }
+}
+
+// Launch parameter checks shared by every path that writes token identity
+// or supply. Lengths are bytes, matching the space reserved on the launch.
+pub mod validation {
+    use super::{LaunchParams, TokenLaunchError, MAX_TOKEN_DECIMALS, MAX_TOKEN_NAME_LEN, MAX_TOKEN_SYMBOL_LEN};
+    use anchor_lang::prelude::*;
+
+    /// Non-empty, fits the launch, no control characters and no
+    /// leading or trailing whitespace
+    pub fn token_name(name: &str) -> Result<()> {
+        require!(
+            !name.is_empty()
+                && name.len() <= MAX_TOKEN_NAME_LEN
+                && name.trim() == name
+                && !name.chars().any(char::is_control),
+            TokenLaunchError::InvalidTokenName
+        );
+        Ok(())
+    }
+
+    /// Non-empty, fits the launch and ASCII letters or digits only
+    pub fn token_symbol(symbol: &str) -> Result<()> {
+        require!(
+            !symbol.is_empty()
+                && symbol.len() <= MAX_TOKEN_SYMBOL_LEN
+                && symbol.bytes().all(|b| b.is_ascii_alphanumeric()),
+            TokenLaunchError::InvalidTokenSymbol
+        );
+        Ok(())
+    }
+
+    pub fn total_supply(supply: u64) -> Result<()> {
+        require!(supply > 0, TokenLaunchError::ZeroTotalSupply);
+        Ok(())
+    }
+
+    pub fn decimals(decimals: u8) -> Result<()> {
+        require!(decimals <= MAX_TOKEN_DECIMALS, TokenLaunchError::InvalidDecimals);
+        Ok(())
+    }
+
+    /// A wallet listed twice would count twice toward withdrawal approvals
+    pub fn insurance_wallets(wallets: &[Pubkey]) -> Result<()> {
+        for (i, wallet) in wallets.iter().enumerate() {
+            require!(
+                !wallets[..i].contains(wallet),
+                TokenLaunchError::DuplicateInsuranceWallet
+            );
+        }
+        Ok(())
+    }
+
+    pub fn launch_params(params: &LaunchParams) -> Result<()> {
+        token_name(&params.token_name)?;
+        token_symbol(&params.token_symbol)?;
+        total_supply(params.total_supply)?;
+        insurance_wallets(&params.insurance_wallets)
+    }
+}
+
+/// Blank launch state, as `TokenLaunch::initialize` expects to find it
+impl Default for TokenLaunch {
+    fn default() -> Self {
+        let mut launch = <Self as bytemuck::Zeroable>::zeroed();
+        launch.set_active(true);
+        launch.version = LAUNCH_VERSION;
+        launch
+    }
 }
 
-// Custom Errors
-#[error_code]
-pub enum TokenLaunchError {
-    #[msg("Timelock duration must be at least 100 days")]
-    TimelockTooShort,
-    
-    #[msg("Timelock is still active, transfers not allowed")]
-    TimelockActive,
-    
-    #[msg("Too many insurance wallets (max 10)")]
-    TooManyInsuranceWallets,
-    
-    #[msg("Insurance limit cannot exceed 50%")]
-    InsuranceLimitTooHigh,
-    
-    #[msg("Caller is not authorized insurance wallet")]
-    UnauthorizedInsurance,
-    
-    #[msg("Amount exceeds insurance withdrawal limit")]
-    ExceedsInsuranceLimit,
-    
-    #[msg("Only escrow authority can relock tokens")]
-    UnauthorizedRelock,
-    
-    #[msg("Only escrow authority can suspend launch")]
-    UnauthorizedSuspension,
-    
-    #[msg("Fraud score must be between 0.0 and 1.0")]
-    InvalidFraudScore,
-    
-    #[msg("Token launch has been suspended")]
-    LaunchInactive,
-    
-    #[msg("Insufficient fee payment")]
-    InsufficientFee,
-    
-    #[msg("Invalid network for this operation")]
-    InvalidNetwork,
-}
+// Testing utilities (conditional compilation)
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A launch account as written before v19: the discriminator followed
+    /// by the Borsh `LegacyTokenLaunch`
+    pub fn legacy_launch_data(launch: &LegacyTokenLaunch) -> Vec<u8> {
+        let mut data = <TokenLaunch as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+        launch.serialize(&mut data).unwrap();
+        data
+    }
+    
+    pub fn create_test_launch_params() -> LaunchParams {
+        LaunchParams {
+            token_name: "TestToken".to_string(),
+            token_symbol: "TEST".to_string(),
+            total_supply: 1_000_000_000,
+            timelock_duration: constants::MIN_TIMELOCK_DAYS * constants::SECONDS_PER_DAY,
+            insurance_wallets: vec![],
+            insurance_limit: 5,
+            logo_nft: None,
+            fraud_score: 1_000,
+            withdrawal_threshold: 0,
+            metadata_uri: "https://example.com/test.json".to_string(),
+            creator_multisig: None,
+            max_tx_amount: None,
+            max_wallet_pct: None,
+            presale: None,
+            unlock_schedule: None,
+            relock_consent_days: None,
+            launch_class: None,
+            referrer: None,
+            unlock_condition: None,
+            bootstrap: None,
+            transfer_tax_bps: 0,
+            logo_cnft: None,
+        }
+    }
+    
+    #[test]
+    fn test_fee_calculation() {
+        let mut launch = TokenLaunch {
+            total_supply: 1000000,
+            insurance_limit: 10,
+            circulating_supply: 1000000,
+            ..Default::default()
+        };
+        launch.set_token_name("Test").unwrap();
+        launch.set_token_symbol("TST").unwrap();
+        launch.set_insurance_wallets(&[Pubkey::default(), Pubkey::default()]).unwrap(); // 2 wallets
+        launch.set_logo_nft(Some(Pubkey::default())).unwrap(); // Has logo
+        
+        let expected_fee = 10_000_000 + (2 * 10_000_000) + 5_000_000; // Base + Insurance + Logo
+        assert_eq!(launch.calculate_launch_fee().unwrap(), expected_fee);
+    }
+    
+    #[test] 
+    fn test_timelock_expiry() {
+        let launch = TokenLaunch {
+            timelock_end: 1000,
+            ..Default::default()
+        };
+        
+        assert!(!launch.is_timelock_expired(999));  // Not expired
+        assert!(launch.is_timelock_expired(1000));  // Exactly expired
+        assert!(launch.is_timelock_expired(1001));  // Past expiry
+    }
+    
+    #[test]
+    fn test_insurance_limit() {
+        let mut launch = TokenLaunch {
+            total_supply: 1000,
+            circulating_supply: 1000,
+            insurance_limit: 10, // 10%
+            total_withdrawn: 50,
+            ..Default::default()
+        };
+        
+        assert_eq!(launch.get_remaining_insurance_limit(), 50); // 100 - 50 = 50
+        
+        launch.total_withdrawn = 100;
+        assert_eq!(launch.get_remaining_insurance_limit(), 0); // Fully withdrawn
+
+        // Burns shrink the allowance with the supply
+        launch.total_withdrawn = 20;
+        launch.record_burn(400).unwrap();
+        assert_eq!(launch.circulating_supply, 600);
+        assert_eq!(launch.tokens_burned, 400);
+        assert_eq!(launch.get_remaining_insurance_limit(), 40); // 60 - 20
+        assert!(launch.record_burn(601).is_err());
+    }
+
+    #[test]
+    fn test_annotation_upsert() {
+        let mut annotations = LaunchAnnotations {
+            token_mint: Pubkey::default(),
+            entries: vec![],
+        };
+
+        annotations.upsert("audit".to_string(), [1; 32], 100).unwrap();
+        annotations.upsert("listing".to_string(), [2; 32], 200).unwrap();
+        annotations.upsert("audit".to_string(), [3; 32], 300).unwrap(); // Overwrite
+
+        assert_eq!(annotations.entries.len(), 2);
+        let audit = annotations.get("audit").unwrap();
+        assert_eq!(audit.value_hash, [3; 32]);
+        assert_eq!(audit.updated_at, 300);
+    }
+
+    #[test]
+    fn test_clone_params() {
+        let mut source = TokenLaunch {
+            insurance_limit: 20,
+            timelock_duration: 200 * constants::SECONDS_PER_DAY,
+            ..Default::default()
+        };
+        source.set_insurance_wallets(&[Pubkey::new_unique()]).unwrap();
+        source.set_bootstrap(Some(BootstrapConfig {
+            window_slots: 150,
+            max_buy_per_slot: 1_000,
+            cooldown_slots: 2,
+            fee_multiplier: 3,
+        })).unwrap();
+        let overrides = LaunchOverrides {
+            token_name: "Clone".to_string(),
+            token_symbol: "CLN".to_string(),
+            total_supply: 5_000,
+            logo_nft: None,
+            fraud_score: 1_000,
+            timelock_duration: None,
+            insurance_limit: Some(10),
+            metadata_uri: String::new(),
+            referrer: None,
+            recovery_accounts: Vec::new(),
+        };
+
+        let params = source.clone_params(overrides);
+        assert_eq!(params.insurance_wallets, source.insurance_wallets());
+        assert_eq!(params.timelock_duration, source.timelock_duration); // Inherited
+        assert_eq!(params.insurance_limit, 10); // Overridden
+        assert_eq!(params.total_supply, 5_000);
+        // Inherited, so clone_launch requires the guard like any launch
+        assert_eq!(params.bootstrap, source.bootstrap());
+    }
+
+    #[test]
+    fn test_verify_multisig_creator() {
+        let multisig = CreatorMultisig {
+            multisig: Pubkey::new_unique(),
+            vault_index: 0,
+        };
+        let vault = multisig.vault_address();
+        let mut launch = TokenLaunch {
+            creator: vault,
+            ..Default::default()
+        };
+        launch.set_creator_multisig(Some(multisig)).unwrap();
+
+        assert!(launch.verify_creator(&vault).is_ok());
+        assert!(launch.verify_creator(&Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_required_approvals() {
+        let mut launch = TokenLaunch {
+            withdrawal_threshold: 0,
+            ..Default::default()
+        };
+        assert_eq!(launch.required_approvals(), 1); // Single signer by default
+
+        launch.withdrawal_threshold = 3;
+        assert_eq!(launch.required_approvals(), 3);
+    }
+
+    #[test]
+    fn test_wallet_cooldown_and_cap() {
+        let mut record = InsuranceWalletRecord {
+            launch: Pubkey::default(),
+            wallet: Pubkey::default(),
+            total_withdrawn: 0,
+            withdrawal_count: 0,
+            last_withdrawal_at: 0,
+        };
+
+        assert!(record.record_withdrawal(40, 100, 1_000).is_ok());
+        assert!(record.record_withdrawal(10, 100, 1_000 + WITHDRAWAL_COOLDOWN - 1).is_err()); // Cooling down
+        assert!(record.record_withdrawal(70, 100, 1_000 + WITHDRAWAL_COOLDOWN).is_err()); // Over cap
+        assert!(record.record_withdrawal(60, 100, 1_000 + WITHDRAWAL_COOLDOWN).is_ok());
+        assert_eq!(record.total_withdrawn, 100);
+    }
+
+    #[test]
+    fn test_creator_index() {
+        let creator = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut index = CreatorIndex {
+            creator: Pubkey::default(),
+            launches: vec![],
+        };
+
+        index.add_launch(creator, mint).unwrap();
+        index.add_launch(creator, mint).unwrap(); // Idempotent
+        assert_eq!(index.launches, vec![mint]);
+
+        index.remove_launch(&mint);
+        assert!(index.launches.is_empty());
+    }
+
+    #[test]
+    fn test_governance_actions() {
+        let wallet = Pubkey::new_unique();
+        let mut launch = TokenLaunch::default();
+        launch.set_active(false);
+
+        let add = GovernanceAction::AddInsuranceWallet { wallet };
+        assert!(launch.validate_governance_action(&add).is_ok());
+        launch.apply_governance_action(&add).unwrap();
+        assert!(launch.validate_governance_action(&add).is_err()); // Duplicate
+
+        let limit = GovernanceAction::SetInsuranceLimit { insurance_limit: 51 };
+        assert!(launch.validate_governance_action(&limit).is_err());
+
+        launch.apply_governance_action(&GovernanceAction::ReactivateLaunch).unwrap();
+        assert!(launch.is_active());
+    }
+
+    #[test]
+    fn test_governance_quorum() {
+        let mut proposal = GovernanceProposal {
+            launch: Pubkey::default(),
+            proposal_id: 0,
+            proposer: Pubkey::default(),
+            action: GovernanceAction::ReactivateLaunch,
+            description: String::new(),
+            yes_votes: 60,
+            no_votes: 30,
+            voting_ends_at: 0,
+            executed: false,
+            bump: 0,
+        };
+
+        assert!(!proposal.has_passed(1_000)); // 9% turnout < 10% quorum
+        proposal.yes_votes = 80;
+        assert!(proposal.has_passed(1_000));
+    }
+
+    #[test]
+    fn test_anti_whale_limits() {
+        let mut launch = TokenLaunch {
+            total_supply: 1_000_000,
+            ..Default::default()
+        };
+
+        // No limits configured
+        assert!(launch.check_max_tx(u64::MAX).is_ok());
+        assert!(launch.check_max_wallet(1_000_000).is_ok());
+
+        launch.set_max_tx_amount(Some(10_000)).unwrap();
+        launch.set_max_wallet_pct(Some(2)).unwrap();
+        assert!(launch.check_max_tx(10_000).is_ok());
+        assert!(launch.check_max_tx(10_001).is_err());
+        assert!(launch.check_max_wallet(20_000).is_ok());
+        assert!(launch.check_max_wallet(20_001).is_err());
+    }
+
+    #[test]
+    fn test_event_sequence() {
+        let mut launch = TokenLaunch::default();
+        assert_eq!(launch.next_event_seq().unwrap(), 1);
+        assert_eq!(launch.next_event_seq().unwrap(), 2);
+
+        // Risk events only fire when the high-risk tier flips
+        launch.fraud_score = 5_000;
+        launch.emit_risk_change(false, 4_000).unwrap();
+        assert_eq!(launch.event_seq, 2);
+        launch.fraud_score = 8_000;
+        launch.emit_risk_change(false, 5_000).unwrap();
+        assert_eq!(launch.event_seq, 3);
+    }
+
+    #[test]
+    fn test_display_helpers() {
+        use super::display::*;
+
+        assert_eq!(lamports_to_sol(10_000_000), "0.01");
+        assert_eq!(sol_to_lamports("1.5"), Some(1_500_000_000));
+        assert_eq!(to_ui_amount(1_500_000, 6), "1.5");
+        assert_eq!(to_ui_amount(42, 0), "42");
+        assert_eq!(from_ui_amount("0.000001", 6), Some(1));
+        assert_eq!(from_ui_amount("1.0000001", 6), None);
+        assert_eq!(from_ui_amount("abc", 6), None);
+
+        let launch = TokenLaunch {
+            timelock_end: 42 * 86_400 + 100,
+            ..Default::default()
+        };
+        assert_eq!(unlock_countdown(&launch, 0), "42 days left");
+        assert_eq!(launch.days_until_unlock(0), 42);
+        assert_eq!(format_remaining(3_600), "1 hour left");
+        assert_eq!(unlock_countdown(&launch, launch.timelock_end), "unlocked");
+    }
+
+    #[test]
+    fn test_presale_merkle_proof() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let leaf_a = presale_leaf(&alice, 1_000);
+        let leaf_b = presale_leaf(&bob, 500);
+        let root = if leaf_a <= leaf_b {
+            anchor_lang::solana_program::keccak::hashv(&[&leaf_a, &leaf_b]).0
+        } else {
+            anchor_lang::solana_program::keccak::hashv(&[&leaf_b, &leaf_a]).0
+        };
+
+        assert!(verify_merkle_proof(&[leaf_b], root, leaf_a));
+        assert!(verify_merkle_proof(&[leaf_a], root, leaf_b));
+        // Claiming a larger allocation than whitelisted fails
+        assert!(!verify_merkle_proof(&[leaf_b], root, presale_leaf(&alice, 2_000)));
+
+        let presale = PresaleConfig {
+            merkle_root: root,
+            price_lamports: 1_000_000, // 0.001 SOL per token
+            allocation: 10_000_000_000,
+            end_time: 0,
+        };
+        assert_eq!(presale.cost(2_500_000_000, 9).unwrap(), 2_500_000);
+        assert_eq!(presale.cost(1, 9).unwrap(), 1); // Rounds up
+    }
+
+    #[test]
+    fn test_cpi_interface() {
+        use anchor_lang::{Discriminator, ToAccountMetas};
+        use interface::*;
+
+        assert_eq!(INITIALIZE_LAUNCH_DISCRIMINATOR, crate::instruction::InitializeLaunch::DISCRIMINATOR);
+        assert_eq!(INITIALIZE_LAUNCH_V2_DISCRIMINATOR, crate::instruction::InitializeLaunchV2::DISCRIMINATOR);
+        assert_eq!(INITIALIZE_FROM_TEMPLATE_DISCRIMINATOR, crate::instruction::InitializeFromTemplate::DISCRIMINATOR);
+        assert_eq!(TRANSFER_TOKENS_DISCRIMINATOR, crate::instruction::TransferTokens::DISCRIMINATOR);
+
+        // The published layouts match what Anchor derives from the contexts
+        let key = |i: u8| Pubkey::new_from_array([i; 32]);
+        let initialize = crate::accounts::InitializeLaunch {
+            creator: key(1),
+            token_launch: key(2),
+            creator_index: key(3),
+            creator_profile: key(4),
+            launch_registry: key(5),
+            registry_page: key(6),
+            token_mint: key(7),
+            config: key(8),
+            treasury: key(9),
+            referrer: Some(key(10)),
+            kyc_attestation: Some(key(11)),
+            bootstrap_guard: Some(key(12)),
+            system_program: key(13),
+        }
+        .to_account_metas(None);
+        let keys: Vec<_> = (1..=13).map(|i| Some(key(i))).collect();
+        assert_eq!(account_metas(&INITIALIZE_LAUNCH_ACCOUNTS, &keys), initialize);
+
+        let transfer = crate::accounts::TransferTokens {
+            payer: key(1),
+            token_launch: key(2),
+            token_mint: key(3),
+            from_token_account: key(4),
+            to_token_account: key(5),
+            from_blacklist: key(6),
+            to_blacklist: key(7),
+            authority: key(8),
+            config: key(9),
+            fee_shard: key(10),
+            launch_stats: key(11),
+            treasury_token_account: None,
+            bootstrap_guard: Some(key(13)),
+            delegation: None,
+            token_program: key(15),
+            system_program: key(16),
+        }
+        .to_account_metas(None);
+        let keys: Vec<_> = (1..=16).map(|i| if i == 12 || i == 14 { None } else { Some(key(i)) }).collect();
+        assert_eq!(account_metas(&TRANSFER_TOKENS_ACCOUNTS, &keys), transfer);
+    }
+
+    #[test]
+    fn test_bonding_curve() {
+        let mut curve = BondingCurve {
+            launch: Pubkey::default(),
+            virtual_sol_reserves: 30_000_000_000,       // 30 SOL
+            virtual_token_reserves: 1_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 800_000_000_000,
+            graduation_threshold: 85_000_000_000,       // 85 SOL
+            graduated: false,
+        };
+
+        let tokens = curve.apply_buy(1_000_000_000).unwrap();
+        assert!(tokens > 0 && tokens < 1_000_000_000_000 / 30);
+
+        // Selling straight back never returns more than was paid
+        let sol = curve.apply_sell(tokens).unwrap();
+        assert!(sol <= 1_000_000_000);
+        assert!(!curve.graduated);
+
+        // Large buy crosses the graduation threshold
+        curve.apply_buy(90_000_000_000).unwrap();
+        assert!(curve.graduated);
+    }
+
+    #[test]
+    fn test_bonding_curve_custody_and_migration() {
+        // The allocation sits in its own vault, apart from the launch vault
+        let mint = Pubkey::new_unique();
+        let (curve_vault, _) = Pubkey::find_program_address(&[seeds::CURVE_VAULT, mint.as_ref()], &crate::ID);
+        assert_ne!(curve_vault, seeds::find_vault(&mint).0);
+        assert_ne!(curve_vault, Pubkey::find_program_address(&[seeds::CURVE_RESERVE, mint.as_ref()], &crate::ID).0);
+
+        let mut curve = BondingCurve {
+            launch: Pubkey::default(),
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 800_000_000_000,
+            graduation_threshold: 85_000_000_000,
+            graduated: false,
+        };
+
+        // Nothing migrates while the curve is still trading
+        curve.apply_buy(1_000_000_000).unwrap();
+        assert!(curve.migrate().is_err());
+
+        curve.apply_buy(90_000_000_000).unwrap();
+        assert!(curve.graduated);
+        curve.migrate().unwrap();
+        assert_eq!((curve.real_sol_reserves, curve.real_token_reserves), (0, 0));
+
+        // A second migration is rejected
+        assert!(curve.migrate().is_err());
+        assert!(curve.graduated);
+    }
+
+    #[test]
+    fn test_token2022_extension_authorities() {
+        let mint = Pubkey::new_unique();
+        let launch = seeds::find_launch(&mint).0;
+        let [fee, hook] = token2022_extension_instructions(&spl_token_2022::ID, &mint, 100, 1_000).unwrap();
+
+        // InitializeTransferFeeConfig: [26, 0], then both authorities as
+        // COption<Pubkey> (tag byte, key), basis points and maximum fee
+        assert_eq!(fee.data[..2], [26, 0]);
+        assert_eq!(fee.data[2], 1);
+        assert_eq!(fee.data[3..35], launch.to_bytes());
+        assert_eq!(fee.data[35], 1);
+        assert_eq!(fee.data[36..68], launch.to_bytes());
+        assert_eq!(fee.data[68..], [100u16.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat());
+
+        // Transfer hook Initialize: [36, 0], then no authority (zero key)
+        // and the separate hook program
+        assert_eq!(hook.data[..2], [36, 0]);
+        assert_eq!(hook.data[2..34], [0; 32]);
+        assert_eq!(hook.data[34..66], TRANSFER_HOOK_PROGRAM_ID.to_bytes());
+        assert_ne!(TRANSFER_HOOK_PROGRAM_ID, crate::ID);
+    }
+
+    #[test]
+    fn test_amm_allowlist() {
+        let raydium_cpmm: Pubkey = AMM_PROGRAM_IDS[0].parse().unwrap();
+        assert!(is_allowed_amm(&raydium_cpmm));
+        assert!(!is_allowed_amm(&spl_token::ID));
+        assert!(!is_allowed_amm(&crate::ID));
+    }
+
+    #[test]
+    fn test_liquidity_accounts_exclude_other_launch_vaults() {
+        let launch = Pubkey::new_unique();
+        let token_account = |owner: Pubkey, delegate: Option<Pubkey>| {
+            let mut data = vec![0u8; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint: Pubkey::new_unique(),
+                owner,
+                amount: 1_000,
+                delegate: delegate.into(),
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            (Pubkey::new_unique(), data, 1_000_000u64)
+        };
+        let (vault, stake_vault, delegated, pool) = (
+            token_account(launch, None),
+            token_account(launch, None),
+            token_account(Pubkey::new_unique(), Some(launch)),
+            token_account(Pubkey::new_unique(), None),
+        );
+        let mut accounts = [vault, stake_vault, delegated, pool];
+        let token_program = spl_token::ID;
+        let infos: Vec<AccountInfo> = accounts
+            .iter_mut()
+            .map(|(key, data, lamports)| AccountInfo::new(key, false, true, lamports, data, &token_program, false, 0))
+            .collect();
+        let allowed = [*infos[0].key];
+
+        // The deposit vault and accounts the launch doesn't control pass
+        assert!(check_launch_token_accounts(&[infos[0].clone(), infos[3].clone()], &launch, &allowed).is_ok());
+        // Any other launch-owned or launch-delegated source fails
+        assert!(check_launch_token_accounts(&[infos[0].clone(), infos[1].clone()], &launch, &allowed).is_err());
+        assert!(check_launch_token_accounts(&[infos[2].clone()], &launch, &allowed).is_err());
+    }
+
+    #[test]
+    fn test_fee_schedule() {
+        let mut launch = TokenLaunch::default();
+        launch.set_insurance_wallets(&[Pubkey::default(); 2]).unwrap();
+        launch.set_logo_nft(Some(Pubkey::default())).unwrap();
+        assert_eq!(FeeSchedule::default().launch_fee(&launch).unwrap(), launch.calculate_launch_fee().unwrap());
+
+        let fees = FeeSchedule {
+            base_fee: 1,
+            insurance_fee: 10,
+            logo_fee: 100,
+            ..FeeSchedule::default()
+        };
+        assert_eq!(fees.launch_fee(&launch).unwrap(), 121);
+    }
+
+    #[test]
+    fn test_treasury_split() {
+        let split = TreasurySplit {
+            protocol_bps: 7_000,
+            insurance_pool_bps: 2_000,
+            creator_rebate_bps: 1_000,
+        };
+        assert!(split.is_valid());
+        assert_eq!(split.split(1_000_001), (700_001, 200_000, 100_000));
+
+        let bad = TreasurySplit { protocol_bps: 9_000, ..split };
+        assert!(!bad.is_valid());
+    }
+
+    #[test]
+    fn test_oracle_registry() {
+        let mut registry = OracleRegistry { oracles: vec![], bump: 0 };
+        let oracle = Pubkey::new_unique();
+        let replacement = Pubkey::new_unique();
+
+        registry.add(oracle).unwrap();
+        assert!(registry.add(oracle).is_err());
+        assert!(registry.is_authorized(&oracle));
+
+        registry.rotate(&oracle, replacement).unwrap();
+        assert!(!registry.is_authorized(&oracle));
+        assert!(registry.is_authorized(&replacement));
+        assert!(registry.rotate(&oracle, Pubkey::new_unique()).is_err());
+
+        registry.remove(&replacement).unwrap();
+        assert!(registry.remove(&replacement).is_err());
+
+        for _ in 0..MAX_FRAUD_ORACLES {
+            registry.add(Pubkey::new_unique()).unwrap();
+        }
+        assert!(registry.add(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_yield_position() {
+        let mut launch = TokenLaunch::default();
+        let approve = GovernanceAction::ApproveYieldDeployment { amount: 5_000 };
+        launch.validate_governance_action(&approve).unwrap();
+        launch.apply_governance_action(&approve).unwrap();
+        assert_eq!(launch.yield_allowance, 5_000);
+
+        let mut position = YieldPosition {
+            launch: Pubkey::new_unique(),
+            venue: Pubkey::new_unique(),
+            share_mint: Pubkey::new_unique(),
+            principal: 1_000,
+            realized_yield: 0,
+            bump: 0,
+        };
+        // Half the shares return 550 lamports against 500 of principal
+        assert_eq!(position.record_unwind(450, 900, 550).unwrap(), 50);
+        assert_eq!(position.principal, 500);
+        // The final unwind retires all remaining principal
+        assert_eq!(position.principal_for(450, 450).unwrap(), 500);
+        assert_eq!(position.record_unwind(450, 450, 540).unwrap(), 40);
+        assert_eq!(position.principal, 0);
+        assert_eq!(position.realized_yield, 90);
+
+        // Unwinds need an approved, non-empty share count
+        let unwind = GovernanceAction::UnwindTreasuryYield { shares: 0, venue_call_hash: [0; 32] };
+        assert!(launch.validate_governance_action(&unwind).is_err());
+        assert!(unwind.try_to_vec().unwrap().len() <= GovernanceAction::SIZE);
+
+        // The approved call pins the accounts, their writability and the data
+        let (key, other, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut other_lamports) = (0u64, 0u64);
+        let (mut data, mut other_data) = (Vec::new(), Vec::new());
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let swapped = AccountInfo::new(&other, false, true, &mut other_lamports, &mut other_data, &owner, false, 0);
+        let approved = yield_venue_call_hash(std::slice::from_ref(&account), &[1, 2]);
+        assert_eq!(yield_venue_call_hash(std::slice::from_ref(&account), &[1, 2]), approved);
+        assert_ne!(yield_venue_call_hash(std::slice::from_ref(&swapped), &[1, 2]), approved);
+        assert_ne!(yield_venue_call_hash(std::slice::from_ref(&account), &[1, 3]), approved);
+        let mut readonly = account.clone();
+        readonly.is_writable = false;
+        assert_ne!(yield_venue_call_hash(&[readonly], &[1, 2]), approved);
+
+        assert!(is_allowed_yield_venue(&YIELD_VENUE_PROGRAM_IDS[0].parse().unwrap()));
+        assert!(!is_allowed_yield_venue(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_appeal_lifecycle() {
+        let mut appeal = AppealState {
+            launch: Pubkey::new_unique(),
+            appellant: Pubkey::new_unique(),
+            bond: APPEAL_BOND,
+            reason: String::new(),
+            filed_at: 0,
+            review_deadline: APPEAL_REVIEW_PERIOD,
+            status: AppealStatus::None,
+            arbiter: None,
+            resolved_at: 0,
+            bump: 0,
+        };
+        assert!(appeal.can_file());
+
+        appeal.status = AppealStatus::Pending;
+        assert!(!appeal.can_file());
+        assert!(!appeal.allows_reactivation(APPEAL_REVIEW_PERIOD));
+        // An escrow that never rules cannot keep the launch suspended
+        assert!(appeal.allows_reactivation(APPEAL_REVIEW_PERIOD + 1));
+
+        appeal.status = AppealStatus::Rejected;
+        assert!(appeal.can_file());
+        assert!(!appeal.allows_reactivation(APPEAL_REVIEW_PERIOD + 1));
+
+        appeal.status = AppealStatus::Upheld;
+        assert!(!appeal.can_file());
+        assert!(appeal.allows_reactivation(0));
+    }
+
+    #[test]
+    fn test_underwriting_payout() {
+        let pool = UnderwritingPool {
+            launch: Pubkey::new_unique(),
+            total_staked: 10_000_000_000,
+            underwriter_count: 2,
+            slashed: true,
+            payout_pool: 10_000_000_000,
+            circulating_at_slash: 400_000,
+            tokens_redeemed: 0,
+            bump: 0,
+        };
+        // A quarter of the circulating supply claims a quarter of the pool
+        assert_eq!(pool.payout_for(100_000), 2_500_000_000);
+        assert_eq!(pool.payout_for(400_000), pool.payout_pool);
+
+        let empty = UnderwritingPool { circulating_at_slash: 0, ..pool };
+        assert_eq!(empty.payout_for(1), 0);
+    }
+
+    #[test]
+    fn test_unlock_schedule() {
+        let schedule = UnlockSchedule { percent_per_period: 25, period_days: 30 };
+        let period = 30 * constants::SECONDS_PER_DAY;
+        assert!(schedule.is_valid());
+        assert!(!UnlockSchedule { percent_per_period: 0, period_days: 30 }.is_valid());
+        assert!(!UnlockSchedule { percent_per_period: 25, period_days: 0 }.is_valid());
+
+        // First tranche at timelock_end, then one per period, capped at supply
+        assert_eq!(schedule.unlocked_amount(1_000, 100, 99), 0);
+        assert_eq!(schedule.unlocked_amount(1_000, 100, 100), 250);
+        assert_eq!(schedule.unlocked_amount(1_000, 100, 100 + period - 1), 250);
+        assert_eq!(schedule.unlocked_amount(1_000, 100, 100 + period), 500);
+        assert_eq!(schedule.unlocked_amount(1_000, 100, 100 + 10 * period), 1_000);
+
+        let mut launch = TokenLaunch {
+            total_supply: 1_000,
+            timelock_end: 100,
+            unlocked_so_far: 200,
+            ..TokenLaunch::default()
+        };
+        launch.set_unlock_schedule(Some(schedule)).unwrap();
+        assert!(launch.check_unlock(50, 100).is_ok());
+        assert!(launch.check_unlock(51, 100).is_err());
+    }
+
+    #[test]
+    fn test_recovery_quorum() {
+        let guardians: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut policy = RecoveryPolicy {
+            launch: Pubkey::new_unique(),
+            guardians: guardians.clone(),
+            threshold: 2,
+            new_creator: Some(Pubkey::new_unique()),
+            approvals: vec![],
+            quorum_reached_at: 0,
+            bump: 0,
+        };
+
+        policy.approve(guardians[0], 1_000).unwrap();
+        assert_eq!(policy.challenge_ends_at(), None);
+        assert!(policy.approve(guardians[0], 1_001).is_err());
+
+        policy.approve(guardians[1], 2_000).unwrap();
+        assert_eq!(policy.challenge_ends_at(), Some(2_000 + RECOVERY_CHALLENGE_PERIOD));
+        // Later approvals do not restart the window
+        policy.approve(guardians[2], 3_000).unwrap();
+        assert_eq!(policy.challenge_ends_at(), Some(2_000 + RECOVERY_CHALLENGE_PERIOD));
+
+        policy.reset();
+        assert!(policy.new_creator.is_none() && policy.approvals.is_empty());
+        assert_eq!(policy.challenge_ends_at(), None);
+    }
+
+    #[test]
+    fn test_allocation_vesting() {
+        let vesting = Allocation {
+            launch: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            kind: ClaimKind::Vesting,
+            total: 1_000,
+            claimed: 0,
+            start: 0,
+            cliff_end: 100,
+            end: 400,
+            bump: 0,
+        };
+        assert_eq!(vesting.claimable(99), 0);
+        assert_eq!(vesting.claimable(100), 250);
+        assert_eq!(vesting.claimable(400), 1_000);
+
+        let claimed = Allocation { claimed: 250, ..vesting.clone() };
+        assert_eq!(claimed.claimable(200), 250);
+
+        let airdrop = Allocation {
+            kind: ClaimKind::Airdrop,
+            cliff_end: 0,
+            end: 0,
+            ..vesting
+        };
+        assert_eq!(airdrop.claimable(0), 1_000);
+    }
+
+    #[test]
+    fn test_relock_rules() {
+        let day = constants::SECONDS_PER_DAY;
+        let mut launch = TokenLaunch {
+            launched_at: 0,
+            timelock_end: 100 * day,
+            ..Default::default()
+        };
+        launch.set_relock_consent_days(Some(180)).unwrap();
+
+        assert!(launch.check_relock(100 * day).is_err()); // Must extend
+        assert!(launch.check_relock(150 * day).is_ok());
+        assert!(!launch.relock_needs_consent(180 * day));
+        assert!(launch.relock_needs_consent(181 * day));
+
+        launch.relock_count = MAX_RELOCKS;
+        assert!(launch.check_relock(150 * day).is_err());
+
+        launch.set_relock_consent_days(None).unwrap();
+        assert!(!launch.relock_needs_consent(1_000 * day));
+    }
+
+    #[test]
+    fn test_checked_math() {
+        assert_eq!(math::add(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert!(math::add(u64::MAX, 1).is_err());
+        assert!(math::sub(0, 1).is_err());
+        assert!(math::mul_div(1, 1, 0).is_err());
+        assert!(math::add_secs(i64::MAX, 1).is_err());
+        assert_eq!(math::pct(u64::MAX, 50).unwrap(), u64::MAX / 2);
 
-// Helper Functions
-impl TokenLaunch {
-    pub fn is_timelock_expired(&self, current_timestamp: i64) -> bool {
-        current_timestamp >= self.timelock_end
+        // total_supply * insurance_limit would overflow u64 without widening
+        let launch = TokenLaunch {
+            total_supply: u64::MAX,
+            circulating_supply: u64::MAX,
+            insurance_limit: 50,
+            total_withdrawn: 0,
+            ..Default::default()
+        };
+        assert_eq!(launch.insurance_allowance(), u64::MAX / 2);
+        assert!(launch.check_insurance_withdrawal(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_seed_registry() {
+        let mint = Pubkey::new_unique();
+        let launch = TokenLaunch {
+            token_mint: mint,
+            ..Default::default()
+        };
+        let (address, bump) = seeds::find_launch(&mint);
+
+        // Signer seeds rebuild the same PDA the clients derive
+        let signer = Pubkey::create_program_address(&launch.signer_seeds(&[bump]), &crate::ID).unwrap();
+        assert_eq!(signer, address);
+        assert_eq!(seeds::LAUNCH, b"launch");
+        assert_ne!(seeds::find_vault(&mint).0, seeds::find_sol_vault(&mint).0);
+        assert_ne!(
+            seeds::find_withdrawal_proposal(&address, 0).0,
+            seeds::find_governance_proposal(&address, 0).0
+        );
+    }
+
+    #[test]
+    fn test_fraud_score_migration() {
+        let launch = LegacyTokenLaunch {
+            token_name: "Legacy".to_string(),
+            token_symbol: "OLD".to_string(),
+            insurance_wallets: vec![Pubkey::new_unique()],
+            logo_nft: Some(Pubkey::new_unique()),
+            fraud_score: 0,
+            fees_collected: 42,
+            ..Default::default()
+        };
+        let mut current = legacy_launch_data(&launch);
+        current.resize(LegacyTokenLaunch::space(), 0);
+
+        // Rebuild the legacy bytes: a 0.75 f32 where the u16 now sits
+        let offset = 8 + 32 + 32 + (4 + 6) + (4 + 3) + 16 + (4 + 32) + 1 + 33;
+        let mut legacy = current[..offset].to_vec();
+        legacy.extend_from_slice(&0.75f32.to_le_bytes());
+        legacy.extend_from_slice(&current[offset + 2..]);
+        legacy.extend_from_slice(&[0, 0]);
+
+        assert_eq!(migrate_legacy_fraud_score(&mut legacy).unwrap(), 7_500);
+        legacy.truncate(LegacyTokenLaunch::space());
+        let migrated = read_launch_layout(&legacy).unwrap();
+        assert_eq!(migrated.fraud_score, 7_500);
+        assert_eq!(migrated.fees_collected, 42);
+        assert_eq!(migrated.token_symbol(), "OLD");
+        assert!(migrated.is_high_risk());
+
+        let mut garbage = vec![0u8; 16];
+        assert!(migrate_legacy_fraud_score(&mut garbage).is_err());
+    }
+
+    #[test]
+    fn test_sponsor_reimbursement() {
+        let pool = SponsorPool {
+            launch: Pubkey::new_unique(),
+            reimbursement_per_claim: 10_000,
+            per_wallet_cap: 25_000,
+            total_funded: 1_000_000,
+            total_reimbursed: 0,
+            bump: 255,
+        };
+
+        assert_eq!(pool.reimbursement_for(0, 1_000_000), 10_000);
+        assert_eq!(pool.reimbursement_for(20_000, 1_000_000), 5_000); // Cap reached
+        assert_eq!(pool.reimbursement_for(25_000, 1_000_000), 0);
+        assert_eq!(pool.reimbursement_for(0, 3_000), 3_000); // Pool running dry
+    }
+
+    #[test]
+    fn test_launch_archive_space() {
+        let archive = LaunchArchive {
+            token_mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            archive_hash: [7u8; 32],
+            final_event_seq: 12,
+            total_supply: 1_000_000,
+            total_withdrawn: 50_000,
+            fees_collected: 30_000_000,
+            closed_at: 1_700_000_000,
+            bump: 254,
+        };
+        let mut data = Vec::new();
+        archive.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), LaunchArchive::space());
+    }
+
+    #[test]
+    fn test_launch_layout_upgrade() {
+        let launch = LegacyTokenLaunch {
+            token_name: "Old".to_string(),
+            total_supply: 1_000,
+            fees_collected: 7,
+            relock_consent_days: Some(30),
+            version: 0,
+            ..Default::default()
+        };
+        let mut data = legacy_launch_data(&launch);
+
+        // A pre-versioning account: everything from relock_consent_days on
+        // was never written, and realloc zero-fills the grown tail
+        let tail = (1 + 2) + 1 + 4 + 32 + 8 + (1 + 1) + 8 + 8 + 2 + 8 + 8 + 1 + 8 + 1 + 2 + 1 + 2 + 8 + 1 + 1 + 1 + 8 + 1 + 1 + 4 + 2 + 4;
+        data.truncate(data.len() - tail);
+        data.resize(LegacyTokenLaunch::space(), 0);
+
+        let mut upgraded = read_launch_layout(&data).unwrap();
+        assert_eq!(upgrade_launch_layout(&mut upgraded).unwrap(), 0);
+        assert_eq!(upgraded.version, LAUNCH_VERSION);
+        assert_eq!(upgraded.fees_collected, 7);
+        assert_eq!(upgraded.token_name(), "Old");
+        assert_eq!(upgraded.relock_consent_days(), None);
+        assert_eq!(upgraded.circulating_supply, 1_000);
+
+        // Rewritten accounts decode as they are and are left alone
+        let mut rewritten = <TokenLaunch as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+        rewritten.extend_from_slice(bytemuck::bytes_of(&upgraded));
+        assert_eq!(rewritten.len(), TokenLaunch::space());
+        let mut current = read_launch_layout(&rewritten).unwrap();
+        assert_eq!(current.token_name(), "Old");
+        assert!(upgrade_launch_layout(&mut current).is_err());
+    }
+
+    #[test]
+    fn test_zero_copy_launch_slots() {
+        let key = Pubkey::new_unique();
+        let mut launch = TokenLaunch::default();
+        assert_eq!(TokenLaunch::space(), 8 + std::mem::size_of::<TokenLaunch>());
+        assert!(TokenLaunch::space() > LegacyTokenLaunch::space());
+
+        // Every setting fits its slot at its largest encoding
+        launch.set_token_name(&"n".repeat(MAX_TOKEN_NAME_LEN)).unwrap();
+        launch.set_token_symbol(&"s".repeat(MAX_TOKEN_SYMBOL_LEN)).unwrap();
+        launch.set_metadata_uri(&"u".repeat(MAX_URI_LEN)).unwrap();
+        launch.set_insurance_wallets(&[key; MAX_INSURANCE_WALLETS]).unwrap();
+        launch.set_recovery_account_list(&[key; MAX_RECOVERY_ACCOUNTS]).unwrap();
+        launch.set_logo_nft(Some(key)).unwrap();
+        launch.set_creator_multisig(Some(CreatorMultisig { multisig: key, vault_index: 255 })).unwrap();
+        launch.set_pending_creator(Some(key)).unwrap();
+        launch.set_max_tx_amount(Some(u64::MAX)).unwrap();
+        launch.set_max_wallet_pct(Some(100)).unwrap();
+        launch.set_presale(Some(PresaleConfig { merkle_root: [1; 32], price_lamports: 1, allocation: 2, end_time: 3 })).unwrap();
+        launch.set_unlock_schedule(Some(UnlockSchedule { percent_per_period: 25, period_days: 30 })).unwrap();
+        launch.set_relock_consent_days(Some(180)).unwrap();
+        launch.set_launch_class(Some(2)).unwrap();
+        launch.set_unlock_condition(Some(UnlockCondition::PythPriceAbove { feed: key, threshold: 1, expo: -8, min_slots: 9 })).unwrap();
+        launch.set_bootstrap(Some(BootstrapConfig { window_slots: 1, max_buy_per_slot: 2, cooldown_slots: 3, fee_multiplier: 4 })).unwrap();
+        launch.set_logo_cnft(Some(CompressedLogo { tree: key, leaf_index: u32::MAX })).unwrap();
+        launch.set_pending_insurance_change(Some(PendingInsuranceChange {
+            change: InsuranceChange::RemoveRecoveryAccount { account: key },
+            effective_at: i64::MAX,
+        })).unwrap();
+
+        let decoded: TokenLaunch = bytemuck::pod_read_unaligned(bytemuck::bytes_of(&launch));
+        assert_eq!(decoded.token_name().len(), MAX_TOKEN_NAME_LEN);
+        assert_eq!(decoded.metadata_uri().len(), MAX_URI_LEN);
+        assert_eq!(decoded.insurance_wallets().len(), MAX_INSURANCE_WALLETS);
+        assert_eq!(decoded.max_tx_amount(), Some(u64::MAX));
+        assert_eq!(decoded.relock_consent_days(), Some(180));
+        assert_eq!(decoded.logo_cnft().unwrap().leaf_index, u32::MAX);
+        assert_eq!(decoded.pending_insurance_change().unwrap().effective_at, i64::MAX);
+        assert!(decoded.unlock_condition().is_some() && decoded.presale().is_some());
+
+        // Over-long values are refused rather than truncated
+        assert!(launch.set_token_symbol(&"s".repeat(MAX_TOKEN_SYMBOL_LEN + 1)).is_err());
+        assert!(launch.set_insurance_wallets(&[key; MAX_INSURANCE_WALLETS + 1]).is_err());
+        assert_eq!(launch.insurance_wallets().len(), MAX_INSURANCE_WALLETS);
+
+        // Clearing a setting decodes as unset
+        launch.set_presale(None).unwrap();
+        assert!(launch.presale().is_none());
+    }
+
+    #[test]
+    fn test_fee_shard_sweep() {
+        let mut shard = FeeShard {
+            launch: Pubkey::new_unique(),
+            shard: 3,
+            pending: 0,
+            total_collected: 0,
+            bump: 255,
+        };
+        shard.record_fee(10_000).unwrap();
+        shard.record_fee(10_000).unwrap();
+        assert_eq!(shard.take_pending(), 20_000);
+        assert_eq!(shard.take_pending(), 0);
+        assert_eq!(shard.total_collected, 20_000);
+
+        let (a, _) = seeds::find_fee_shard(&shard.launch, 0);
+        let (b, _) = seeds::find_fee_shard(&shard.launch, 1);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_launch_stats() {
+        let launch = Pubkey::new_unique();
+        let mut transfers = LaunchStats { launch, shard: 2, ..Default::default() };
+        transfers.record_transfer(1_000, 10_000, 50, true, false, 100).unwrap();
+        transfers.record_transfer(400, 10_000, 0, true, true, 90).unwrap();
+        assert_eq!(transfers.transfer_count, 2);
+        assert_eq!(transfers.volume, 1_400);
+        assert_eq!(transfers.trading_fees, 20_000);
+        assert_eq!(transfers.tax_collected, 50);
+        assert_eq!(transfers.holders(), 1);
+        assert_eq!(transfers.last_trade_at, 100); // Never moves backwards
+
+        let mut sales = LaunchStats { launch, shard: SALE_STATS_SHARD, ..Default::default() };
+        sales.record_sale(5_000, 2_000_000, true, false, 120).unwrap();
+
+        let total = LaunchStats::total([&transfers, &sales]);
+        assert_eq!(total.volume, 6_400);
+        assert_eq!((total.transfer_count, total.sale_count), (2, 1));
+        assert_eq!(total.sale_lamports, 2_000_000);
+        assert_eq!(total.holders(), 2);
+        assert_eq!(total.last_trade_at, 120);
+
+        // Sales never share a shard with transfers
+        assert_ne!(seeds::find_launch_stats(&launch, 0).0, seeds::find_launch_stats(&launch, SALE_STATS_SHARD).0);
+    }
+
+    #[test]
+    fn test_batch_shape() {
+        let keys = [Pubkey::new_unique(); 2 * MAX_BATCH_SIZE + 2];
+
+        assert_eq!(batch_pairs(&keys[..4], 2).unwrap().len(), 2);
+        assert_eq!(batch_pairs(&keys[..2 * MAX_BATCH_SIZE], MAX_BATCH_SIZE).unwrap().len(), MAX_BATCH_SIZE);
+
+        // More or fewer scores than pairs
+        assert_eq!(batch_pairs(&keys[..4], 3).unwrap_err(), TokenLaunchError::InvalidBatchSize.into());
+        assert_eq!(batch_pairs(&keys[..6], 2).unwrap_err(), TokenLaunchError::InvalidBatchSize.into());
+        // A launch without its history
+        assert_eq!(batch_pairs(&keys[..5], 2).unwrap_err(), TokenLaunchError::InvalidBatchSize.into());
+        assert_eq!(batch_pairs(&keys[..5], 3).unwrap_err(), TokenLaunchError::InvalidBatchSize.into());
+        // Empty and oversized batches
+        assert_eq!(batch_pairs(&keys[..0], 0).unwrap_err(), TokenLaunchError::InvalidBatchSize.into());
+        assert_eq!(
+            batch_pairs(&keys, MAX_BATCH_SIZE + 1).unwrap_err(),
+            TokenLaunchError::InvalidBatchSize.into()
+        );
+
+        assert!(check_batch_size(1).is_ok() && check_batch_size(MAX_BATCH_SIZE).is_ok());
+        assert!(check_batch_size(0).is_err() && check_batch_size(MAX_BATCH_SIZE + 1).is_err());
+    }
+
+    #[test]
+    fn test_batch_rejects_mismatched_pairs() {
+        let account = |launch: &TokenLaunch| {
+            let mut data = <TokenLaunch as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(bytemuck::bytes_of(launch));
+            (seeds::find_launch(&launch.token_mint).0, data, 1_000_000u64)
+        };
+        let history = |launch: &Pubkey| {
+            let mut data = Vec::new();
+            FraudScoreHistory { launch: *launch, ..Default::default() }.try_serialize(&mut data).unwrap();
+            (seeds::find_fraud_score_history(launch).0, data, 1_000_000u64)
+        };
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let launch_a = account(&TokenLaunch { token_mint: mint_a, ..Default::default() });
+        let launch_b_key = seeds::find_launch(&mint_b).0;
+        let mut moved = account(&TokenLaunch { token_mint: mint_a, ..Default::default() });
+        moved.0 = launch_b_key;
+        let (history_a, history_b) = (history(&launch_a.0), history(&launch_b_key));
+
+        let mut accounts = [launch_a, history_a, history_b, moved];
+        let owner = crate::ID;
+        let infos: Vec<AccountInfo> = accounts
+            .iter_mut()
+            .map(|(key, data, lamports)| AccountInfo::new(key, false, true, lamports, data, &owner, false, 0))
+            .collect();
+        let [launch_info, history_a_info, history_b_info, moved_info] = &infos[..] else {
+            unreachable!()
+        };
+
+        let launch = load_batch_launch(launch_info).unwrap();
+        assert!(load_score_history(history_a_info, &launch.key()).is_ok());
+
+        // Another launch's history cannot stand in for this launch's
+        assert_eq!(
+            load_score_history(history_b_info, &launch.key()).err(),
+            Some(TokenLaunchError::InvalidBatchAccount.into())
+        );
+
+        // Nor can a launch account sitting at another mint's PDA
+        assert_eq!(load_batch_launch(moved_info).err(), Some(TokenLaunchError::InvalidBatchAccount.into()));
+    }
+
+    #[test]
+    fn test_fraud_score_history() {
+        let oracle = Pubkey::new_unique();
+        let hour = 3_600;
+        let mut history = FraudScoreHistory::default();
+        history.record(1_000, oracle, 0);
+        assert_eq!(history.smoothed_score(hour - 1), None); // Too little history
+        assert_eq!(history.smoothed_score(hour), Some(1_000));
+
+        // A spike corrected a minute later barely moves the average
+        history.record(9_900, oracle, 10 * hour);
+        history.record(1_000, oracle, 10 * hour + 60);
+        assert!(!history.should_suspend(10 * hour + 60));
+        assert!(history.smoothed_score(11 * hour).unwrap() < 1_200);
+
+        // A sustained high score suspends once it dominates the window
+        history.record(9_500, oracle, 12 * hour);
+        assert!(!history.should_suspend(12 * hour));
+        assert!(history.should_suspend(12 * hour + FRAUD_SCORE_WINDOW));
+
+        // The ring keeps the latest samples, oldest first
+        for i in 0..FRAUD_SCORE_HISTORY_LEN as i64 {
+            history.record(2_000, oracle, 100 * hour + i);
+        }
+        assert_eq!(history.count as usize, FRAUD_SCORE_HISTORY_LEN);
+        assert_eq!(history.sample(0).timestamp, 100 * hour);
+        assert_eq!(history.smoothed_score(200 * hour), Some(2_000));
+        assert_eq!(FraudScoreHistory::space(), 8 + 32 + 42 * FRAUD_SCORE_HISTORY_LEN + 3);
+    }
+
+    #[test]
+    fn test_insurance_collateral() {
+        let launch = Pubkey::new_unique();
+        let mut collateral = InsuranceCollateral {
+            launch,
+            wallet: Pubkey::new_unique(),
+            amount: MIN_INSURANCE_COLLATERAL - 1,
+            staked_at: 0,
+            bump: 255,
+        };
+        assert!(collateral.check_staked().is_err());
+        collateral.amount = MIN_INSURANCE_COLLATERAL;
+        assert!(collateral.check_staked().is_ok());
+
+        let mut pool = InsuranceCollateralPool { launch, total_staked: 3 * MIN_INSURANCE_COLLATERAL, total_slashed: 0, bump: 255 };
+        pool.slash(MIN_INSURANCE_COLLATERAL).unwrap();
+        pool.release(2 * MIN_INSURANCE_COLLATERAL).unwrap();
+        assert_eq!((pool.total_staked, pool.total_slashed), (0, MIN_INSURANCE_COLLATERAL));
+        assert!(pool.release(1).is_err());
+
+        // The slash ruling must fit the proposal's reserved action space
+        let action = ArbitrationAction::SlashInsuranceCollateral { withdrawal_seq: u64::MAX };
+        assert_eq!(action.try_to_vec().unwrap().len(), 1 + 8);
+    }
+
+    #[test]
+    fn test_cluster_gating() {
+        let week = 7 * 86_400;
+        assert!(Cluster::Mainnet.check_timelock(MIN_TIMELOCK_DURATION).is_ok());
+        assert!(Cluster::Mainnet.check_timelock(week).is_err());
+        assert!(Cluster::Devnet.check_timelock(week).is_ok());
+        assert!(Cluster::Localnet.check_timelock(TEST_MIN_TIMELOCK_DURATION).is_ok());
+        assert!(Cluster::Devnet.check_timelock(60).is_err());
+        assert_eq!(Cluster::default(), Cluster::Mainnet);
+
+        // Zeroed padding in an older config decodes as mainnet
+        let cluster = Cluster::try_from_slice(&[0]).unwrap();
+        assert!(!cluster.allows_test_mode());
+    }
+
+    #[test]
+    fn test_scoring_model_verify() {
+        let model = ScoringModel {
+            version: 2,
+            model_hash: [7; 32],
+            registered_at: 0,
+            bump: 255,
+        };
+        assert!(model.verify(&[7; 32]).is_ok());
+        assert!(model.verify(&[8; 32]).is_err());
+        assert_ne!(seeds::find_scoring_model(1).0, seeds::find_scoring_model(2).0);
+    }
+
+    #[test]
+    fn test_governed_action_previews() {
+        let day = constants::SECONDS_PER_DAY;
+        let wallets = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut launch = TokenLaunch {
+            total_supply: 1_000,
+            circulating_supply: 1_000,
+            insurance_limit: 10,
+            withdrawal_threshold: 2,
+            timelock_end: 100 * day,
+            ..Default::default()
+        };
+        launch.set_insurance_wallets(&wallets).unwrap();
+        launch.set_relock_consent_days(Some(180)).unwrap();
+
+        let preview = launch.preview_emergency_withdraw(&wallets[0], 30, None, 50_000, 1_000).unwrap();
+        assert!(preview.requires_proposal);
+        assert_eq!(preview.remaining_limit, 70);
+        assert_eq!(preview.wallet_withdrawn, 30);
+        assert_eq!(preview.wallet_cap, 50);
+        assert_eq!(preview.next_withdrawal_at, 1_000 + WITHDRAWAL_COOLDOWN);
+        assert!(launch.preview_emergency_withdraw(&wallets[0], 60, None, 0, 1_000).is_err()); // Over wallet cap
+        assert!(launch.preview_emergency_withdraw(&Pubkey::new_unique(), 1, None, 0, 1_000).is_err());
+
+        let relock = launch.preview_relock(200 * day, Cluster::Mainnet, 20_000_000, 0).unwrap();
+        assert!(relock.needs_creator_consent);
+        assert_eq!(relock.relocks_remaining, MAX_RELOCKS - 1);
+        assert!(launch.preview_relock(7 * day, Cluster::Mainnet, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_withdrawal_record_numbering() {
+        let mut launch = TokenLaunch::default();
+        assert_eq!(launch.next_withdrawal_record_seq().unwrap(), 0);
+        assert_eq!(launch.next_withdrawal_record_seq().unwrap(), 1);
+        assert_eq!(launch.withdrawal_record_count, 2);
+
+        let record = WithdrawalRecord {
+            launch: Pubkey::new_unique(),
+            seq: 1,
+            wallet: Pubkey::new_unique(),
+            amount: 500,
+            destination: Pubkey::new_unique(),
+            withdrawn_at: 1_000,
+            proposal_id: Some(3),
+            justification: "x".repeat(MAX_JUSTIFICATION_LEN),
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        record.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), WithdrawalRecord::space());
+    }
+
+    #[test]
+    fn test_blacklist_authorization() {
+        let creator = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let launch = TokenLaunch { creator, ..Default::default() };
+        let action = GovernanceAction::BlacklistWallet { wallet };
+        let mut proposal = GovernanceProposal {
+            launch: Pubkey::new_unique(),
+            proposal_id: 0,
+            proposer: Pubkey::new_unique(),
+            action: action.clone(),
+            description: String::new(),
+            yes_votes: 0,
+            no_votes: 0,
+            voting_ends_at: 0,
+            executed: false,
+            bump: 255,
+        };
+
+        assert!(!authorize_blacklist_change(&launch, &creator, None, &action).unwrap());
+        assert!(authorize_blacklist_change(&launch, &wallet, None, &action).is_err());
+        assert!(authorize_blacklist_change(&launch, &wallet, Some(&proposal), &action).is_err()); // Not executed
+        proposal.executed = true;
+        assert!(authorize_blacklist_change(&launch, &wallet, Some(&proposal), &action).unwrap());
+        let unblock = GovernanceAction::UnblacklistWallet { wallet };
+        assert!(authorize_blacklist_change(&launch, &wallet, Some(&proposal), &unblock).is_err());
+    }
+
+    #[test]
+    fn test_airdrop_total() {
+        let mut launch = TokenLaunch::default();
+        launch.set_max_tx_amount(Some(500)).unwrap();
+
+        assert_eq!(airdrop_total(&launch, &[100, 200, 500]).unwrap(), 800);
+        assert!(airdrop_total(&launch, &[]).is_err());
+        assert!(airdrop_total(&launch, &[100, 0]).is_err());
+        assert!(airdrop_total(&launch, &[501]).is_err()); // Over max tx
+        assert!(airdrop_total(&launch, &[1; MAX_AIRDROP_RECIPIENTS + 1]).is_err());
+        assert!(airdrop_total(&TokenLaunch::default(), &[u64::MAX, 1]).is_err());
+    }
+
+    #[test]
+    fn test_launch_classes() {
+        let day = 86_400;
+        let game = LaunchClass { min_timelock: 30 * day, bond: 0, base_fee: 1_000 };
+        let meme = LaunchClass { min_timelock: 180 * day, bond: 5_000_000_000, base_fee: 50_000_000 };
+        assert!(game.is_valid() && meme.is_valid());
+        assert!(!LaunchClass { min_timelock: 29 * day, ..game }.is_valid());
+
+        let config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![game, meme],
+            referral_bps: 0,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: false,
+        };
+        assert_eq!(config.launch_class(None).unwrap(), None);
+        assert_eq!(config.launch_class(Some(1)).unwrap(), Some(meme));
+        assert!(config.launch_class(Some(2)).is_err());
+
+        // Class minimums replace the global one on mainnet
+        let mut params = create_test_launch_params();
+        params.timelock_duration = 30 * day;
+        params.launch_class = Some(0);
+        let mut launch = TokenLaunch::default();
+        launch.initialize(Pubkey::new_unique(), Pubkey::new_unique(), params.clone(), 0, Cluster::Mainnet, Some(game)).unwrap();
+        assert_eq!(launch.min_timelock(), 30 * day);
+        assert_eq!(config.launch_fee(&launch, Some(&game)).unwrap(), 1_000);
+        assert!(launch.preview_relock(31 * day, Cluster::Mainnet, 0, 0).is_ok());
+
+        params.launch_class = Some(1);
+        let mut launch = TokenLaunch::default();
+        assert!(launch.initialize(Pubkey::new_unique(), Pubkey::new_unique(), params.clone(), 0, Cluster::Mainnet, Some(meme)).is_err());
+        params.timelock_duration = 180 * day;
+        launch.initialize(Pubkey::new_unique(), Pubkey::new_unique(), params.clone(), 0, Cluster::Mainnet, Some(meme)).unwrap();
+        assert_eq!(launch.bond, meme.bond);
+
+        // Launches without a class keep the global minimum
+        params.launch_class = None;
+        params.timelock_duration = 30 * day;
+        let mut launch = TokenLaunch::default();
+        assert!(launch.initialize(Pubkey::new_unique(), Pubkey::new_unique(), params, 0, Cluster::Mainnet, None).is_err());
+        assert_eq!(TokenLaunch::default().min_timelock(), MIN_TIMELOCK_DURATION);
+    }
+
+    #[test]
+    fn test_referral_split() {
+        let mut config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![],
+            referral_bps: 2_000,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: false,
+        };
+        assert_eq!(config.referral_split(20_000_000).unwrap(), (4_000_000, 16_000_000));
+        assert_eq!(config.referral_split(7).unwrap(), (1, 6)); // Dust stays with the treasury
+
+        config.referral_bps = 0;
+        assert_eq!(config.referral_split(20_000_000).unwrap(), (0, 20_000_000));
+
+        // Configs written before referrals read a zero share from padding
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        data.truncate(data.len() - 2);
+        data.resize(data.len() + 61, 0);
+        assert_eq!(GlobalConfig::try_deserialize(&mut data.as_slice()).unwrap().referral_bps, 0);
+    }
+
+    #[test]
+    fn test_staking_rewards() {
+        let mut pool = StakingPool {
+            launch: Pubkey::new_unique(),
+            stake_vault: Pubkey::new_unique(),
+            reward_vault: Pubkey::new_unique(),
+            reward_rate: 100,
+            start_time: 0,
+            end_time: 1_000,
+            last_update: 0,
+            acc_reward_per_share: 0,
+            total_staked: 0,
+            min_lock_duration: 0,
+            bump: 255,
+        };
+        let position = |owner| StakePosition {
+            pool: Pubkey::default(),
+            owner,
+            amount: 0,
+            reward_debt: 0,
+            pending_rewards: 0,
+            locked_until: 0,
+            bump: 255,
+        };
+        let (mut alice, mut bob) = (position(Pubkey::new_unique()), position(Pubkey::new_unique()));
+
+        // Nothing staked for the first 100s: those rewards are not emitted
+        pool.update(100).unwrap();
+        alice.settle(&pool).unwrap();
+        alice.amount = 100;
+        alice.checkpoint(&pool);
+        pool.total_staked = 100;
+
+        // Alice alone for 100s earns 10_000
+        pool.update(200).unwrap();
+        bob.settle(&pool).unwrap();
+        bob.amount = 300;
+        bob.checkpoint(&pool);
+        pool.total_staked = 400;
+
+        // Then 1:3 until the period ends; updates past the end emit nothing
+        pool.update(1_500).unwrap();
+        alice.settle(&pool).unwrap();
+        bob.settle(&pool).unwrap();
+        assert_eq!(alice.pending_rewards, 10_000 + 20_000);
+        assert_eq!(bob.pending_rewards, 60_000);
+
+        // Settling twice does not double count
+        alice.checkpoint(&pool);
+        alice.settle(&pool).unwrap();
+        assert_eq!(alice.pending_rewards, 30_000);
+    }
+
+    #[test]
+    fn test_rescue_foreign_assets_guards() {
+        let mut launch = TokenLaunch {
+            token_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let recipient = Pubkey::new_unique();
+
+        // Native launch tokens are never rescuable
+        let native = GovernanceAction::RescueForeignAssets { mint: Some(launch.token_mint), recipient };
+        assert!(launch.validate_governance_action(&native).is_err());
+        let foreign = GovernanceAction::RescueForeignAssets { mint: Some(Pubkey::new_unique()), recipient };
+        assert!(launch.validate_governance_action(&foreign).is_ok());
+        let sol = GovernanceAction::RescueForeignAssets { mint: None, recipient: Pubkey::default() };
+        assert!(launch.validate_governance_action(&sol).is_err());
+
+        // Only lamports above rent and the posted bond are rescuable
+        launch.bond = 5_000;
+        assert_eq!(launch.rescuable_lamports(10_000, 2_000), 3_000);
+        assert_eq!(launch.rescuable_lamports(6_000, 2_000), 0);
+        assert_eq!(launch.rescuable_lamports(1_000, 2_000), 0);
+    }
+
+    #[test]
+    fn test_launch_webhook_validation() {
+        assert!(LaunchMetadata::validate_webhook("https://hooks.example.com/sold").is_ok());
+        assert!(LaunchMetadata::validate_webhook("").is_ok());
+        assert!(LaunchMetadata::validate_webhook("http://hooks.example.com").is_err());
+        assert!(LaunchMetadata::validate_webhook(&format!("https://{}", "a".repeat(MAX_URI_LEN))).is_err());
+
+        let mut metadata = LaunchMetadata {
+            token_mint: Pubkey::new_unique(),
+            webhook_uri: String::new(),
+            webhook_hash: [0u8; 32],
+            updated_at: 0,
+            bump: 255,
+        };
+        assert!(!metadata.has_webhook());
+        metadata.webhook_hash = [7u8; 32];
+        assert!(metadata.has_webhook());
+    }
+
+    #[test]
+    fn test_dividend_accumulator() {
+        let mut pool = DividendPool {
+            launch: Pubkey::new_unique(),
+            acc_per_token: 0,
+            undistributed: 0,
+            total_reflected: 0,
+            total_paid: 0,
+            bump: 255,
+        };
+
+        // 1_000 lamports over 3_000 eligible tokens: dust carries over
+        pool.record_reflection(1_000).unwrap();
+        assert_eq!(pool.fold(3_000).unwrap(), 999);
+        assert_eq!(pool.undistributed, 1);
+        assert_eq!(pool.owed(1_000, 0).unwrap(), 333);
+        assert_eq!(pool.owed(2_000, 0).unwrap(), 666);
+
+        // A claim registered now is owed nothing until the next fold
+        let checkpoint = pool.acc_per_token;
+        assert_eq!(pool.owed(2_000, checkpoint).unwrap(), 0);
+        pool.record_reflection(2_999).unwrap();
+        assert_eq!(pool.fold(3_000).unwrap(), 3_000);
+        assert_eq!(pool.owed(2_000, checkpoint).unwrap(), 2_000);
+
+        // Nothing folds while no tokens are eligible
+        pool.record_reflection(500).unwrap();
+        assert_eq!(pool.fold(0).unwrap(), 0);
+        assert_eq!(pool.undistributed, 500);
+    }
+
+    #[test]
+    fn test_unlock_condition() {
+        let mut launch = TokenLaunch::default();
+        assert!(launch.check_unlock_condition().is_ok());
+        assert!(!launch.observe_unlock_condition(true, 10));
+
+        let condition = UnlockCondition::PythPriceAbove {
+            feed: Pubkey::new_unique(),
+            threshold: 150,
+            expo: 0,
+            min_slots: 100,
+        };
+        assert!(condition.is_valid());
+        assert!(!UnlockCondition::PythPriceAbove { feed: Pubkey::new_unique(), threshold: 150, expo: 0, min_slots: 0 }.is_valid());
+        launch.set_unlock_condition(Some(condition)).unwrap();
+        assert!(launch.check_unlock_condition().is_err());
+
+        // A dip below the threshold restarts the run
+        assert!(!launch.observe_unlock_condition(true, 1_000));
+        assert!(!launch.observe_unlock_condition(true, 1_099));
+        assert!(!launch.observe_unlock_condition(false, 1_100));
+        assert_eq!(launch.condition_since_slot, 0);
+        assert!(!launch.observe_unlock_condition(true, 1_200));
+        assert!(launch.check_unlock_condition().is_err());
+
+        // Held for min_slots: latches, and later dips no longer matter
+        assert!(launch.observe_unlock_condition(true, 1_300));
+        assert!(launch.check_unlock_condition().is_ok());
+        assert!(!launch.observe_unlock_condition(false, 1_400));
+        assert!(launch.condition_met());
+    }
+
+    #[test]
+    fn test_crank_transitions() {
+        // 30% per 10-day period: tranches at +0, +10 and +20 days, then the
+        // fourth releases the final 10% and no more come due
+        let schedule = UnlockSchedule { percent_per_period: 30, period_days: 10 };
+        let day = constants::SECONDS_PER_DAY;
+        assert_eq!(schedule.tranches_due(1_000, 999), 0);
+        assert_eq!(schedule.tranches_due(1_000, 1_000), 1);
+        assert_eq!(schedule.tranches_due(1_000, 1_000 + 10 * day), 2);
+        assert_eq!(schedule.tranches_due(1_000, 1_000 + 30 * day), 4);
+        assert_eq!(schedule.tranches_due(1_000, 1_000 + 365 * day), 4);
+
+        // The incentive comes only out of the launch's own undistributed fees
+        let mut launch = TokenLaunch::default();
+        assert_eq!(launch.crank_reward(), 0);
+        launch.fees_collected = 20_000;
+        assert_eq!(launch.crank_reward(), 20_000);
+        launch.fees_collected = 10 * CRANK_REWARD_LAMPORTS;
+        launch.fees_distributed = CRANK_REWARD_LAMPORTS;
+        assert_eq!(launch.crank_reward(), CRANK_REWARD_LAMPORTS);
+    }
+
+    #[test]
+    fn test_bootstrap_guard() {
+        let config = BootstrapConfig {
+            window_slots: 100,
+            max_buy_per_slot: 1_000,
+            cooldown_slots: 10,
+            fee_multiplier: 5,
+        };
+        assert!(config.is_valid());
+        assert!(!BootstrapConfig { fee_multiplier: 0, ..config }.is_valid());
+        assert!(!BootstrapConfig { cooldown_slots: 101, ..config }.is_valid());
+
+        let mut guard = BootstrapGuard {
+            launch: Pubkey::new_unique(),
+            start_slot: 0,
+            current_slot: 0,
+            slot_volume: 0,
+            recent_buyers: [RecentBuyer::default(); BOOTSTRAP_RING_SIZE],
+            cursor: 0,
+            bump: 255,
+        };
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // The window starts with the first transfer and caps each slot's volume
+        assert!(guard.is_open(&config, 5_000));
+        guard.record_transfer(&config, alice, 600, 5_000).unwrap();
+        assert_eq!(guard.start_slot, 5_000);
+        assert!(guard.record_transfer(&config, bob, 500, 5_000).is_err());
+        guard.record_transfer(&config, bob, 400, 5_000).unwrap();
+
+        // A new slot resets the volume, but each wallet waits out its cooldown
+        assert!(guard.record_transfer(&config, alice, 100, 5_005).is_err());
+        guard.record_transfer(&config, alice, 100, 5_010).unwrap();
+
+        // The ring buffer overwrites its oldest entry once full
+        for _ in 0..BOOTSTRAP_RING_SIZE {
+            guard.record_transfer(&config, Pubkey::new_unique(), 1, 5_020).unwrap();
+        }
+        assert!(!guard.recent_buyers.iter().any(|entry| entry.wallet == alice));
+
+        assert!(guard.is_open(&config, 5_099));
+        assert!(!guard.is_open(&config, 5_100));
+    }
+
+    #[test]
+    fn test_transfer_tax() {
+        let mut launch = TokenLaunch::default();
+        let untaxed = launch.preview_transfer_tax(1_000).unwrap();
+        assert_eq!((untaxed.tax, untaxed.net_amount), (0, 1_000));
+
+        launch.transfer_tax_bps = MAX_TRANSFER_FEE_BPS;
+        let quote = launch.preview_transfer_tax(1_000).unwrap();
+        assert_eq!(quote.tax_bps, 500);
+        assert_eq!((quote.tax, quote.net_amount), (50, 950));
+
+        // Rounds in the sender's favour
+        launch.transfer_tax_bps = 30;
+        let quote = launch.preview_transfer_tax(333).unwrap();
+        assert_eq!((quote.tax, quote.net_amount), (0, 333));
+        assert_eq!(launch.preview_transfer_tax(u64::MAX).unwrap().tax, (u64::MAX as u128 * 30 / 10_000) as u64);
+    }
+
+    #[test]
+    fn test_kyc_gate() {
+        let attestor = Pubkey::new_unique();
+        let mut config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![],
+            referral_bps: 0,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: false,
+        };
+        let attestation = KycAttestation {
+            creator: Pubkey::new_unique(),
+            attestor,
+            attested_at: 1_000,
+            expires_at: 5_000,
+            bump: 255,
+        };
+
+        // No attestor registered: no gate
+        assert!(config.check_kyc(None, 2_000).is_ok());
+
+        config.kyc_attestor = Some(attestor);
+        assert!(config.check_kyc(None, 2_000).is_err());
+        assert!(config.check_kyc(Some(&attestation), 2_000).is_ok());
+        assert!(config.check_kyc(Some(&attestation), 5_000).is_err());
+        assert!(config.check_kyc(Some(&KycAttestation { expires_at: 0, ..attestation.clone() }), i64::MAX).is_ok());
+
+        // Rotating the attestor invalidates the old attestor's statements
+        config.kyc_attestor = Some(Pubkey::new_unique());
+        assert!(config.check_kyc(Some(&attestation), 2_000).is_err());
+    }
+
+    #[test]
+    fn test_raise_state_machine() {
+        let params = RaiseParams {
+            token_allocation: 1_000_000,
+            soft_cap: 10_000,
+            hard_cap: 30_000,
+            start_time: 100,
+            end_time: 200,
+        };
+        assert!(params.is_valid(50));
+        assert!(!params.is_valid(200));
+        assert!(!RaiseParams { hard_cap: 9_999, ..params }.is_valid(50));
+
+        let mut raise = Raise {
+            launch: Pubkey::new_unique(),
+            token_allocation: params.token_allocation,
+            soft_cap: params.soft_cap,
+            hard_cap: params.hard_cap,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            total_contributed: 0,
+            contributor_count: 0,
+            state: RaiseState::Open,
+            bump: 255,
+        };
+        assert!(!raise.is_open(99));
+        assert!(raise.is_open(100));
+        assert!(!raise.is_open(200));
+
+        raise.total_contributed = 9_999;
+        assert_eq!(raise.outcome(), RaiseState::Failed);
+
+        // Tokens split pro-rata, dust stays in the vault
+        raise.total_contributed = 30_000;
+        assert_eq!(raise.outcome(), RaiseState::Succeeded);
+        assert_eq!(raise.tokens_for(10_000).unwrap(), 333_333);
+        assert_eq!(raise.tokens_for(20_000).unwrap(), 666_666);
+        assert_eq!(raise.tokens_for(30_000).unwrap(), 1_000_000);
+
+        raise.state = RaiseState::Succeeded;
+        assert!(!raise.is_open(150));
+    }
+
+    #[test]
+    fn test_team_allocation_vesting() {
+        let mut allocation = TeamAllocation {
+            launch: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            total: 1_000_000,
+            claimed: 0,
+            start: 0,
+            cliff_end: 1_000,
+            end: 4_000,
+            bump: 255,
+        };
+        assert_eq!(allocation.claimable(999), 0);
+        // Vesting runs from start, so the cliff releases the accrued share
+        assert_eq!(allocation.claimable(1_000), 250_000);
+        allocation.claimed = 250_000;
+        assert_eq!(allocation.claimable(2_000), 250_000);
+        assert_eq!(allocation.claimable(10_000), 750_000);
+    }
+
+    #[test]
+    fn test_compressed_logo_leaf() {
+        let logo = CompressedLogo { tree: Pubkey::new_unique(), leaf_index: 7 };
+        let bubblegum = BUBBLEGUM_PROGRAM_ID;
+        let asset_id = logo.asset_id(&bubblegum);
+        assert_ne!(asset_id, CompressedLogo { leaf_index: 8, ..logo }.asset_id(&bubblegum));
+
+        // The leaf commits to the owner, so only the creator's cNFT verifies
+        let (creator, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let leaf = logo.leaf_hash(&asset_id, &creator, &creator, &[1; 32], &[2; 32]);
+        assert_eq!(leaf, logo.leaf_hash(&asset_id, &creator, &creator, &[1; 32], &[2; 32]));
+        assert_ne!(leaf, logo.leaf_hash(&asset_id, &other, &creator, &[1; 32], &[2; 32]));
+
+        // A compressed logo is charged the logo fee like a regular one
+        let mut launch = TokenLaunch::default();
+        launch.set_logo_cnft(Some(logo)).unwrap();
+        assert_eq!(
+            FeeSchedule::default().launch_fee(&launch).unwrap(),
+            constants::BASE_FEE_LAMPORTS + constants::LOGO_FEE_LAMPORTS
+        );
     }
-    
-    pub fn get_remaining_insurance_limit(&self) -> u64 {
-        let max_withdraw = (self.total_supply * self.insurance_limit as u64) / 100;
-        max_withdraw.saturating_sub(self.total_withdrawn)
+
+    #[test]
+    fn test_fee_recipient_split() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let recipients = vec![
+            FeeRecipient { wallet: a, bps: 5_000 },
+            FeeRecipient { wallet: b, bps: 3_333 },
+            FeeRecipient { wallet: c, bps: 1_667 },
+        ];
+        assert!(FeeRecipient::is_valid_set(&recipients));
+        assert!(FeeRecipient::is_valid_set(&[]));
+        assert!(!FeeRecipient::is_valid_set(&recipients[..2]));
+        assert!(!FeeRecipient::is_valid_set(&[
+            FeeRecipient { wallet: a, bps: 5_000 },
+            FeeRecipient { wallet: a, bps: 5_000 },
+        ]));
+        assert!(!FeeRecipient::is_valid_set(&vec![FeeRecipient { wallet: a, bps: 1_000 }; 10]));
+
+        let mut config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![],
+            referral_bps: 0,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: false,
+        };
+        assert_eq!(config.protocol_shares(1_000), vec![(config.fee_recipient, 1_000)]);
+
+        // Dust from rounding goes to the first recipient
+        config.fee_recipients = recipients;
+        assert_eq!(config.protocol_shares(1_000), vec![(a, 501), (b, 333), (c, 166)]);
     }
-    
-    pub fn calculate_launch_fee(&self) -> u64 {
-        let base_fee = 10_000_000; // 0.01 SOL
-        let insurance_fee = (self.insurance_wallets.len() as u64) * 10_000_000;
-        let logo_fee = if self.logo_nft.is_some() { 5_000_000 } else { 0 };
-        
-        base_fee + insurance_fee + logo_fee
+
+    #[test]
+    fn test_launch_registry_paging() {
+        let mut registry = LaunchRegistry { launch_count: 0, bump: 0 };
+        let mut page = RegistryPage { page: 0, entries: vec![], bump: 0 };
+        let entry = |created_at| RegistryEntry {
+            token_mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            created_at,
+        };
+
+        for i in 0..REGISTRY_PAGE_SIZE {
+            registry.register(&mut page, 254, 253, entry(i as i64)).unwrap();
+        }
+        assert_eq!(registry.launch_count, REGISTRY_PAGE_SIZE);
+        assert_eq!(page.entries.len() as u64, REGISTRY_PAGE_SIZE);
+        assert_eq!((registry.bump, page.bump), (254, 253));
+
+        // A full page can't take more; the next launch opens page 1
+        assert_eq!(registry.next_page(), 1);
+        assert!(registry.register(&mut page, 254, 253, entry(0)).is_err());
+        let mut next = RegistryPage { page: 0, entries: vec![], bump: 0 };
+        registry.register(&mut next, 254, 252, entry(99)).unwrap();
+        assert_eq!(next.page, 1);
+        assert_eq!(LaunchRegistry::page_of(REGISTRY_PAGE_SIZE), 1);
+        assert!(RegistryPage::space() <= 10_240); // Creatable in one instruction
     }
-    
-    pub fn is_high_risk(&self) -> bool {
-        self.fraud_score > 0.7
+
+    #[test]
+    fn test_insurance_change_rules() {
+        let (kept, lost, fresh) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut launch = TokenLaunch {
+            withdrawal_threshold: 1,
+            ..Default::default()
+        };
+        launch.set_insurance_wallets(&[kept, lost]).unwrap();
+
+        // Rotating a lost key: add the replacement, then remove the old one
+        let add = InsuranceChange::Add { wallet: fresh }.as_governance_action();
+        assert!(launch.validate_governance_action(&add).is_ok());
+        launch.apply_governance_action(&add).unwrap();
+        let remove = InsuranceChange::Remove { wallet: lost }.as_governance_action();
+        assert!(launch.validate_governance_action(&remove).is_ok());
+        launch.apply_governance_action(&remove).unwrap();
+        assert_eq!(launch.insurance_wallets(), vec![kept, fresh]);
+
+        // Removals can't drop coverage below the withdrawal threshold
+        launch.withdrawal_threshold = 2;
+        let remove = InsuranceChange::Remove { wallet: kept }.as_governance_action();
+        assert!(launch.validate_governance_action(&remove).is_err());
+        assert_eq!(PendingInsuranceChange::SIZE, InsuranceChange::Add { wallet: kept }.try_to_vec().unwrap().len() + 8);
     }
-    
-    pub fn days_until_unlock(&self, current_timestamp: i64) -> i64 {
-        if self.is_timelock_expired(current_timestamp) {
-            0
-        } else {
-            (self.timelock_end - current_timestamp) / 86400
-        }
+
+    #[test]
+    fn test_recovery_accounts() {
+        let (safe, backup, fresh) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut launch = TokenLaunch {
+            creator: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert!(launch.check_recovery_account(&safe).is_err()); // None registered
+
+        // Launches registering none fall back to the creator's own token account
+        let token_program = anchor_spl::token_2022::ID;
+        launch.set_recovery_accounts(Vec::new(), &token_program).unwrap();
+        let creator_account =
+            get_associated_token_address_with_program_id(&launch.creator, &launch.token_mint, &token_program);
+        assert!(launch.check_recovery_account(&creator_account).is_ok());
+
+        assert!(launch.set_recovery_accounts(vec![safe, safe], &token_program).is_err());
+        assert!(launch.set_recovery_accounts(vec![Pubkey::default()], &token_program).is_err());
+        assert!(launch
+            .set_recovery_accounts(vec![Pubkey::new_unique(); MAX_RECOVERY_ACCOUNTS + 1], &token_program)
+            .is_err());
+        launch.set_recovery_accounts(vec![safe], &token_program).unwrap();
+        assert!(launch.check_recovery_account(&creator_account).is_err());
+        assert!(launch.check_recovery_account(&safe).is_ok());
+        assert!(launch.check_recovery_account(&fresh).is_err());
+
+        // Changes go through the delayed insurance-change flow
+        let add = InsuranceChange::AddRecoveryAccount { account: backup }.as_governance_action();
+        assert!(launch.validate_governance_action(&add).is_ok());
+        launch.apply_governance_action(&add).unwrap();
+        assert!(launch.validate_governance_action(&add).is_err()); // Already registered
+        let remove = InsuranceChange::RemoveRecoveryAccount { account: safe }.as_governance_action();
+        assert!(launch.validate_governance_action(&remove).is_ok());
+        launch.apply_governance_action(&remove).unwrap();
+        assert_eq!(launch.recovery_accounts(), vec![backup]);
+        assert!(launch.check_recovery_account(&safe).is_err());
+        assert!(launch.validate_governance_action(&remove).is_err());
     }
-}
 
-// Security Validations
-pub fn validate_wallet_authority(
-    wallet: &Pubkey,
-    authorized_wallets: &[Pubkey],
-) -> Result<()> {
-    require!(
-        authorized_wallets.contains(wallet),
-        TokenLaunchError::UnauthorizedInsurance
-    );
-    Ok(())
-}
+    #[test]
+    fn test_trading_pause_limits() {
+        let policy = PausePolicy { max_pauses: 2, max_pause_secs: 3_600, cooldown_secs: 86_400 };
+        assert!(policy.is_valid());
+        assert!(!PausePolicy { max_pause_secs: MAX_TRADING_PAUSE + 1, ..policy }.is_valid());
 
-pub fn validate_fee_payment(expected: u64, paid: u64) -> Result<()> {
-    require!(paid >= expected, TokenLaunchError::InsufficientFee);
-    Ok(())
-}
+        let mut launch = TokenLaunch::default();
+        assert!(launch.pause_trading(&PausePolicy::default(), 60, 0).is_err()); // Disabled
+        assert!(launch.pause_trading(&policy, 3_601, 0).is_err());
 
-// Event Logging
-#[event]
-pub struct LaunchCreated {
-    pub token_mint: Pubkey,
-    pub creator: Pubkey,
-    pub token_name: String,
-    pub token_symbol: String,
-    pub total_supply: u64,
-    pub timelock_end: i64,
-    pub fraud_score: f32,
-    pub fee_paid: u64,
-}
+        launch.pause_trading(&policy, 3_600, 1_000).unwrap();
+        assert!(launch.is_trading_paused(4_599));
+        assert!(!launch.is_trading_paused(4_600));
 
-#[event]
-pub struct TokensTransferred {
-    pub token_mint: Pubkey,
-    pub from: Pubkey,
-    pub to: Pubkey,
-    pub amount: u64,
-    pub fee_paid: u64,
-}
+        // Resuming early starts the cooldown from the resume
+        launch.resume_trading(2_000).unwrap();
+        assert!(!launch.is_trading_paused(2_000));
+        assert!(launch.resume_trading(2_000).is_err());
+        assert!(launch.pause_trading(&policy, 60, 2_000 + 86_399).is_err());
+        launch.pause_trading(&policy, 60, 2_000 + 86_400).unwrap();
 
-#[event] 
-pub struct EmergencyWithdrawal {
-    pub token_mint: Pubkey,
-    pub insurance_wallet: Pubkey,
-    pub amount: u64,
-    pub justification: String,
-    pub remaining_limit: u64,
-}
+        // Out of pauses
+        assert!(launch.pause_trading(&policy, 60, 1_000_000).is_err());
+        assert_eq!(launch.pause_count, 2);
+    }
 
-#[event]
-pub struct TokensRelocked {
-    pub token_mint: Pubkey,
-    pub old_timelock_end: i64,
-    pub new_timelock_end: i64,
-    pub reason: String,
-    pub relock_count: u32,
-}
+    #[test]
+    fn test_fee_tiers() {
+        let fees = FeeSchedule::default();
+        let mut launch = TokenLaunch { fraud_score: 1_000, ..Default::default() };
 
-#[event]
-pub struct FraudScoreUpdated {
-    pub token_mint: Pubkey,
-    pub old_score: f32,
-    pub new_score: f32,
-    pub auto_suspended: bool,
-}
+        // Creator-reported scores earn no discount
+        assert_eq!(launch.fee_tier_bps(), 10_000);
+        launch.score_model_version = 1;
+        assert_eq!(launch.fee_tier_bps(), 9_000);
 
-#[event]
-pub struct LaunchSuspended {
-    pub token_mint: Pubkey,
-    pub reason: String,
-    pub suspended_at: i64,
-}
+        let mut profile = CreatorProfile { creator: Pubkey::default(), launches_created: 0, flagged_launches: 0, bump: 0 };
+        assert!(!profile.is_clean_repeat());
+        profile.record_launch(Pubkey::new_unique(), 255).unwrap();
+        assert!(profile.is_clean_repeat());
+        launch.set_repeat_creator(profile.is_clean_repeat());
+        assert_eq!(launch.fee_tier_bps(), 8_000);
+        assert_eq!(fees.tiered(launch.fee_tier_bps()).unwrap().trading_fee, fees.trading_fee * 8 / 10);
 
-// Constants for easy reference
-pub mod constants {
-    pub const SECONDS_PER_DAY: i64 = 86_400;
-    pub const MIN_TIMELOCK_DAYS: i64 = 100;
-    pub const BASE_FEE_LAMPORTS: u64 = 10_000_000;      // 0.01 SOL
-    pub const INSURANCE_FEE_LAMPORTS: u64 = 10_000_000;  // 0.01 SOL per wallet
-    pub const LOGO_FEE_LAMPORTS: u64 = 5_000_000;       // 0.005 SOL
-    pub const RELOCK_FEE_LAMPORTS: u64 = 20_000_000;    // 0.02 SOL
-    pub const TRADING_FEE_LAMPORTS: u64 = 10_000;       // ~0.00001 SOL
-    pub const EMERGENCY_FEE_LAMPORTS: u64 = 50_000;     // 0.00005 SOL
-}
+        // High risk overrides every discount
+        launch.fraud_score = HIGH_RISK_SCORE_BPS + 1;
+        assert_eq!(launch.fee_tier_bps(), 15_000);
+        assert_eq!(fees.tiered(launch.fee_tier_bps()).unwrap().emergency_fee, fees.emergency_fee * 3 / 2);
 
-// Testing utilities (conditional compilation)
-#[cfg(test)]
-pub mod tests {
-    use super::*;
-    use anchor_lang::prelude::*;
-    
-    pub fn create_test_launch_params() -> LaunchParams {
-        LaunchParams {
-            token_name: "TestToken".to_string(),
-            token_symbol: "TEST".to_string(),
-            total_supply: 1_000_000_000,
-            timelock_duration: constants::MIN_TIMELOCK_DAYS * constants::SECONDS_PER_DAY,
-            insurance_wallets: vec![],
-            insurance_limit: 5,
-            logo_nft: None,
-            fraud_score: 0.1,
-        }
+        profile.record_flag().unwrap();
+        assert!(!profile.is_clean_repeat());
     }
-    
+
     #[test]
-    fn test_fee_calculation() {
-        let mut launch = TokenLaunch {
-            creator: Pubkey::default(),
-            token_mint: Pubkey::default(),
-            token_name: "Test".to_string(),
-            token_symbol: "TST".to_string(),
-            total_supply: 1000000,
-            timelock_end: 0,
-            insurance_wallets: vec![Pubkey::default(), Pubkey::default()], // 2 wallets
-            insurance_limit: 10,
-            logo_nft: Some(Pubkey::default()), // Has logo
-            fraud_score: 0.0,
-            fees_collected: 0,
-            is_active: true,
-            relock_count: 0,
-            total_withdrawn: 0,
+    fn test_fee_estimate() {
+        let config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![],
+            referral_bps: 1_000,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: false,
         };
-        
-        let expected_fee = 10_000_000 + (2 * 10_000_000) + 5_000_000; // Base + Insurance + Logo
-        assert_eq!(launch.calculate_launch_fee(), expected_fee);
-    }
-    
-    #[test] 
-    fn test_timelock_expiry() {
-        let launch = TokenLaunch {
-            timelock_end: 1000,
-            ..Default::default()
+        let rent = Rent::default();
+        let first_launch = NewLaunchAccounts {
+            creator_index: true,
+            creator_profile: true,
+            launch_registry: false,
+            registry_page: false,
+            vault_len: spl_token::state::Account::LEN,
+            token_account_len: spl_token::state::Account::LEN,
         };
-        
-        assert!(!launch.is_timelock_expired(999));  // Not expired
-        assert!(launch.is_timelock_expired(1000));  // Exactly expired
-        assert!(launch.is_timelock_expired(1001));  // Past expiry
+        let mut launch = TokenLaunch::default();
+        launch.set_insurance_wallets(&[Pubkey::new_unique(), Pubkey::new_unique()]).unwrap();
+
+        let estimate = FeeEstimate::new(&config, &launch, None, false, &rent, &first_launch).unwrap();
+        assert_eq!(estimate.launch_fee, config.launch_fee(&launch, None).unwrap());
+        assert_eq!(estimate.insurance_fee, 2 * config.fees.insurance_fee);
+        assert_eq!(estimate.logo_fee, 0);
+        assert_eq!(estimate.referral_fee, 0);
+        assert_eq!(estimate.registry_rent, 0);
+        assert_eq!(estimate.bootstrap_trading_fee, None);
+        assert_eq!(
+            estimate.total,
+            estimate.launch_fee + estimate.launch_rent + estimate.creator_accounts_rent + estimate.vault_rent
+        );
+
+        // A referred repeat creator's launch in a bonded class
+        let meme = LaunchClass { min_timelock: 180 * constants::SECONDS_PER_DAY, bond: 5_000_000_000, base_fee: 50_000_000 };
+        launch.set_repeat_creator(true);
+        launch.bond = meme.bond;
+        launch.set_bootstrap(Some(BootstrapConfig { window_slots: 100, max_buy_per_slot: 1, cooldown_slots: 0, fee_multiplier: 5 })).unwrap();
+        let repeat = NewLaunchAccounts { creator_index: false, creator_profile: false, ..first_launch };
+        let estimate = FeeEstimate::new(&config, &launch, Some(&meme), true, &rent, &repeat).unwrap();
+        assert_eq!(estimate.fee_tier_bps, 10_000 - REPEAT_CREATOR_DISCOUNT_BPS);
+        assert_eq!(estimate.base_fee, 45_000_000);
+        assert_eq!(estimate.launch_fee, config.launch_fee(&launch, Some(&meme)).unwrap());
+        assert_eq!(estimate.referral_fee, estimate.launch_fee / 10);
+        assert_eq!(estimate.creator_accounts_rent, 0);
+        assert_eq!(estimate.bootstrap_trading_fee, Some(estimate.trading_fee * 5));
+        assert!(estimate.total > meme.bond + estimate.launch_fee + estimate.bootstrap_guard_rent);
     }
-    
+
     #[test]
-    fn test_insurance_limit() {
+    fn test_dex_allowlist_window() {
+        let (pool, alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let day = constants::SECONDS_PER_DAY;
         let mut launch = TokenLaunch {
-            total_supply: 1000,
-            insurance_limit: 10, // 10%
-            total_withdrawn: 50,
+            timelock_end: 1_000,
             ..Default::default()
         };
-        
-        assert_eq!(launch.get_remaining_insurance_limit(), 50); // 100 - 50 = 50
-        
-        launch.total_withdrawn = 100;
-        assert_eq!(launch.get_remaining_insurance_limit(), 0); // Fully withdrawn
+        launch.set_dex_allowlist(&[pool]).unwrap();
+
+        // Off by default
+        assert!(launch.check_dex_counterparty(&alice, &bob, 1_000).is_ok());
+
+        launch.dex_allowlist_days = 3;
+        assert!(!launch.is_dex_window(999));
+        assert!(launch.check_dex_counterparty(&alice, &bob, 1_000).is_err());
+        assert!(launch.check_dex_counterparty(&alice, &pool, 1_000).is_ok()); // Sell into the pool
+        assert!(launch.check_dex_counterparty(&pool, &bob, 1_000 + day).is_ok());
+        assert!(launch.check_dex_counterparty(&alice, &bob, 1_000 + 3 * day).is_ok());
     }
-}
 
-// Default implementation for testing
-#[cfg(test)]
-impl Default for TokenLaunch {
-    fn default() -> Self {
-        Self {
-            creator: Pubkey::default(),
-            token_mint: Pubkey::default(),
-            token_name: String::new(),
-            token_symbol: String::new(),
-            total_supply: 0,
-            timelock_end: 0,
-            insurance_wallets: Vec::new(),
-            insurance_limit: 0,
-            logo_nft: None,
-            fraud_score: 0.0,
-            fees_collected: 0,
-            is_active: true,
-            relock_count: 0,
-            total_withdrawn: 0,
-        }
+    #[test]
+    fn test_delegation_allowance() {
+        let bot = Pubkey::new_unique();
+        let mut delegation = Delegation {
+            owner: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            delegate: bot,
+            remaining: 1_000,
+            expires_at: 500,
+            bump: 255,
+        };
+
+        assert!(delegation.spend(&Pubkey::new_unique(), 1, 0).is_err());
+        delegation.spend(&bot, 600, 0).unwrap();
+        assert!(delegation.spend(&bot, 401, 0).is_err()); // Over the cap
+        delegation.spend(&bot, 400, 499).unwrap();
+        assert_eq!(delegation.remaining, 0);
+
+        delegation.remaining = 10;
+        assert!(delegation.spend(&bot, 1, 500).is_err()); // Expired
     }
-}, TokenLaunch>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub from_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub to_token_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: Fee recipient validated in instruction
-    #[account(mut, address = FEE_RECIPIENT.parse().unwrap())]
-    pub fee_recipient: AccountInfo<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
 
-#[derive(Accounts)]
-pub struct RelockTokens<'info> {
-    #[account(mut)]
-    pub escrow_authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    /// CHECK: Fee recipient validated in instruction
-    #[account(mut, address = FEE_RECIPIENT.parse().unwrap())]
-    pub fee_recipient: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    #[test]
+    fn test_arbitration_quorum() {
+        let arbiters: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        assert!(ArbiterPanel::is_valid(&arbiters, 2));
+        assert!(ArbiterPanel::is_valid(&[], 0));
+        assert!(!ArbiterPanel::is_valid(&arbiters, 4));
+        assert!(!ArbiterPanel::is_valid(&[arbiters[0], arbiters[0]], 1));
 
-#[derive(Accounts)]
-pub struct UpdateFraudScore<'info> {
-    /// CHECK: AI service authority (validated off-chain)
-    pub ai_authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"launch", token_mint.key().as_ref()],
-        bump
-    )]
-    pub token_launch: Account<'info
\ No newline at end of file
+        let launch = Pubkey::new_unique();
+        let relock = ArbitrationAction::Relock { new_duration: 200 * 86_400 };
+        let mut proposal = ArbitrationProposal {
+            proposal_id: 0,
+            launch,
+            action: relock,
+            proposer: arbiters[0],
+            approvals: vec![arbiters[0]],
+            threshold: 2,
+            expires_at: 1_000,
+            executed: false,
+            bump: 255,
+        };
+        let mut config = GlobalConfig {
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            escrow_authority: Pubkey::new_unique(),
+            fees: FeeSchedule::default(),
+            bump: 255,
+            insurance_pool: Pubkey::new_unique(),
+            treasury_split: TreasurySplit { protocol_bps: 10_000, insurance_pool_bps: 0, creator_rebate_bps: 0 },
+            cluster: Cluster::Mainnet,
+            launch_classes: vec![],
+            referral_bps: 0,
+            kyc_attestor: None,
+            fee_recipients: vec![],
+            pause_policy: PausePolicy::default(),
+            arbitration_required: true,
+        };
+        let escrow = config.escrow_authority;
+        let check = |config: &GlobalConfig, proposal: Option<&ArbitrationProposal>, action| {
+            config.check_escrow(&escrow, proposal, &launch, &action, 0, TokenLaunchError::UnauthorizedRelock)
+        };
+
+        // The escrow key alone no longer suffices, nor does one approval
+        assert!(check(&config, None, relock).is_err());
+        assert!(check(&config, Some(&proposal), relock).is_err());
+
+        assert!(proposal.approve(arbiters[0], 0).is_err()); // Duplicate
+        proposal.approve(arbiters[1], 0).unwrap();
+        assert!(check(&config, Some(&proposal), relock).is_ok());
+        assert!(check(&config, Some(&proposal), ArbitrationAction::Suspend).is_err());
+        assert!(proposal.check_approved(&launch, &relock, 1_000).is_err()); // Expired
+
+        config.arbitration_required = false;
+        assert!(check(&config, None, relock).is_ok());
+    }
+
+    #[test]
+    fn test_launch_params_validation() {
+        assert!(validation::launch_params(&create_test_launch_params()).is_ok());
+
+        let check = |edit: fn(&mut LaunchParams)| {
+            let mut params = create_test_launch_params();
+            edit(&mut params);
+            validation::launch_params(&params)
+        };
+        assert!(check(|p| p.token_name = String::new()).is_err());
+        assert!(check(|p| p.token_name = " Padded".to_string()).is_err());
+        assert!(check(|p| p.token_name = "Line\nbreak".to_string()).is_err());
+        assert!(check(|p| p.token_name = "x".repeat(MAX_TOKEN_NAME_LEN + 1)).is_err());
+        assert!(check(|p| p.token_name = "Sold Token 2".to_string()).is_ok());
+        assert!(check(|p| p.token_symbol = "TOOLONGSYMB".to_string()).is_err());
+        assert!(check(|p| p.token_symbol = "$SOLD".to_string()).is_err());
+        assert!(check(|p| p.total_supply = 0).is_err());
+
+        let wallet = Pubkey::new_unique();
+        let mut params = create_test_launch_params();
+        params.insurance_wallets = vec![wallet, Pubkey::new_unique(), wallet];
+        assert!(validation::launch_params(&params).is_err());
+
+        assert!(validation::decimals(MAX_TOKEN_DECIMALS).is_ok());
+        assert!(validation::decimals(MAX_TOKEN_DECIMALS + 1).is_err());
+    }
+
+    #[test]
+    fn test_metadata_update_rules() {
+        let mut update = MetadataUpdate {
+            launch: Pubkey::new_unique(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            pending: false,
+            effective_at: 0,
+            last_proposed_at: 0,
+            bump: 255,
+        };
+        let now = 1_000_000;
+        let propose = |update: &mut MetadataUpdate, name: &str, symbol: &str, at: i64| {
+            update.propose(name.to_string(), symbol.to_string(), "https://example.com/x.json".to_string(), at)
+        };
+
+        // Name and symbol must fit the launch's fields
+        assert!(propose(&mut update, "", "SOLD", now).is_err());
+        assert!(propose(&mut update, &"x".repeat(MAX_TOKEN_NAME_LEN + 1), "SOLD", now).is_err());
+        assert!(propose(&mut update, "Sold", &"X".repeat(MAX_TOKEN_SYMBOL_LEN + 1), now).is_err());
+        assert!(update.check_applicable(now).is_err());
+
+        // Applicable only once the veto window has passed
+        assert!(propose(&mut update, "Sold", "SOLD", now).is_ok());
+        assert!(update.check_applicable(now + METADATA_VETO_WINDOW - 1).is_err());
+        assert!(update.check_applicable(now + METADATA_VETO_WINDOW).is_ok());
+
+        // Rate limited from the last proposal, even if it was vetoed
+        update.pending = false;
+        assert!(propose(&mut update, "Sold v2", "SOLD", now + METADATA_UPDATE_INTERVAL - 1).is_err());
+        assert!(propose(&mut update, "Sold v2", "SOLD", now + METADATA_UPDATE_INTERVAL).is_ok());
+        assert_eq!(update.name, "Sold v2");
+    }
+
+    #[test]
+    fn test_launch_params_v2() {
+        let v1 = create_test_launch_params();
+        let v2 = |options: Vec<LaunchOption>| LaunchParamsV2 {
+            token_name: v1.token_name.clone(),
+            token_symbol: v1.token_symbol.clone(),
+            total_supply: v1.total_supply,
+            timelock_duration: v1.timelock_duration,
+            insurance_wallets: v1.insurance_wallets.clone(),
+            insurance_limit: v1.insurance_limit,
+            fraud_score: v1.fraud_score,
+            withdrawal_threshold: v1.withdrawal_threshold,
+            metadata_uri: v1.metadata_uri.clone(),
+            options,
+        };
+
+        // No options lowers to the plain V1 defaults
+        let params = v2(vec![]).into_params().unwrap();
+        assert_eq!(params.try_to_vec().unwrap(), v1.try_to_vec().unwrap());
+
+        let referrer = Pubkey::new_unique();
+        let params = v2(vec![
+            LaunchOption::TransferTax { bps: 100 },
+            LaunchOption::AntiWhale { max_tx_amount: Some(5_000), max_wallet_pct: Some(2) },
+            LaunchOption::Referrer(referrer),
+        ])
+        .into_params()
+        .unwrap();
+        assert_eq!(params.transfer_tax_bps, 100);
+        assert_eq!(params.max_tx_amount, Some(5_000));
+        assert_eq!(params.max_wallet_pct, Some(2));
+        assert_eq!(params.referrer, Some(referrer));
+        assert!(params.unlock_schedule.is_none());
+
+        // Each feature may appear once
+        assert!(v2(vec![LaunchOption::TransferTax { bps: 100 }, LaunchOption::TransferTax { bps: 0 }])
+            .into_params()
+            .is_err());
+
+        // Recovery accounts are registered on the launch, not lowered to V1
+        let safe = Pubkey::new_unique();
+        let params = v2(vec![LaunchOption::RecoveryAccounts(vec![safe])]);
+        assert_eq!(params.recovery_accounts(), vec![safe]);
+        assert_eq!(params.into_params().unwrap().try_to_vec().unwrap(), v1.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_launch_template() {
+        let v1 = create_test_launch_params();
+        let terms = TemplateParams {
+            timelock_duration: v1.timelock_duration,
+            insurance_wallets: vec![Pubkey::new_unique(); MAX_INSURANCE_WALLETS],
+            insurance_limit: v1.insurance_limit,
+            withdrawal_threshold: 2,
+            recovery_accounts: vec![Pubkey::new_unique(); MAX_RECOVERY_ACCOUNTS],
+            max_tx_amount: Some(5_000),
+            max_wallet_pct: Some(2),
+            relock_consent_days: Some(30),
+            launch_class: Some(1),
+            transfer_tax_bps: 100,
+        };
+        let mut template = LaunchTemplate {
+            owner: Pubkey::new_unique(),
+            template_id: 7,
+            version: 0,
+            params: TemplateParams::default(),
+            updated_at: 0,
+            bump: 255,
+        };
+        template.set(terms.clone(), 1_000).unwrap();
+        assert_eq!(template.version, 1);
+        let mut data = Vec::new();
+        template.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), LaunchTemplate::space()); // Full lists fill the account
+
+        // Updates bump the version; rejected terms leave it alone
+        template.set(TemplateParams { transfer_tax_bps: 0, ..terms.clone() }, 2_000).unwrap();
+        assert_eq!((template.version, template.updated_at), (2, 2_000));
+        assert!(template.set(TemplateParams { withdrawal_threshold: 11, ..terms.clone() }, 3_000).is_err());
+        assert!(template.set(TemplateParams { max_wallet_pct: Some(0), ..terms.clone() }, 3_000).is_err());
+        assert_eq!(template.version, 2);
+
+        let referrer = Pubkey::new_unique();
+        let params = template.launch_params(TemplateLaunchParams {
+            template_version: 2,
+            token_name: v1.token_name.clone(),
+            token_symbol: v1.token_symbol.clone(),
+            total_supply: v1.total_supply,
+            fraud_score: v1.fraud_score,
+            metadata_uri: v1.metadata_uri.clone(),
+            referrer: Some(referrer),
+        });
+        assert_eq!(params.token_name, v1.token_name);
+        assert_eq!(params.insurance_wallets, terms.insurance_wallets);
+        assert_eq!(params.max_wallet_pct, Some(2));
+        assert_eq!(params.launch_class, Some(1));
+        assert_eq!(params.transfer_tax_bps, 0);
+        assert_eq!(params.referrer, Some(referrer));
+    }
+}
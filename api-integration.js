@@ -10,6 +10,8 @@ const { Program, AnchorProvider, Wallet } = require('@project-serum/anchor');
 const axios = require('axios');
 const fs = require('fs').promises;
 const path = require('path');
+const { LaunchIndexer, riskHistoryToCsv } = require('./launch-indexer');
+const { findLaunch } = require('./seeds');
 
 // Initialize Express app
 const app = express();
@@ -52,6 +54,7 @@ class SolDOrchestrator {
     constructor() {
         this.soldParser = null;
         this.anchorProgram = null;
+        this.indexer = null;
         this.initializeComponents();
     }
 
@@ -68,6 +71,19 @@ class SolDOrchestrator {
             const provider = new AnchorProvider(connection, wallet, {});
             this.anchorProgram = new Program(idl, programId, provider);
 
+            // Launch search index (optional; requires Postgres with pg_trgm)
+            if (process.env.DATABASE_URL) {
+                this.indexer = new LaunchIndexer({
+                    connection,
+                    program: this.anchorProgram,
+                    databaseUrl: process.env.DATABASE_URL,
+                    ingestion: process.env.INDEXER_INGESTION || 'ws',
+                    grpcEndpoint: process.env.YELLOWSTONE_GRPC_URL,
+                    grpcToken: process.env.YELLOWSTONE_GRPC_TOKEN
+                });
+                await this.indexer.start();
+            }
+
             console.log('✅ SolD components initialized successfully');
         } catch (error) {
             console.error('❌ Failed to initialize components:', error);
@@ -106,7 +122,7 @@ class SolDOrchestrator {
     }
 
     // Main orchestration method
-    async processLaunchRequest(soldCode, network = 'DEVNET') {
+    async processLaunchRequest(soldCode, network = 'DEVNET', options = {}) {
         const startTime = Date.now();
         const result = {
             success: false,
@@ -164,7 +180,8 @@ class SolDOrchestrator {
             console.log('🚀 Step 4: Deploying to Solana...');
             const deployStart = Date.now();
             
-            const deploymentResult = await this.deployToSolana(config, fraudAnalysis.fraud_score);
+            const mintKeypair = await this.loadMintKeypair(options.mintKeypairPath || process.env.MINT_KEYPAIR_PATH);
+            const deploymentResult = await this.deployToSolana(config, fraudAnalysis.fraud_score, mintKeypair);
             result.steps.deployment.success = true;
             result.steps.deployment.timeMs = Date.now() - deployStart;
             
@@ -283,20 +300,27 @@ no-idl = []
 cpi = ["no-entrypoint"]`;
     }
 
-    async deployToSolana(config, fraudScore) {
+    // Load a pre-ground vanity mint keypair (see `sold-parser grind-mint`)
+    async loadMintKeypair(keypairPath) {
+        if (!keypairPath) return null;
+
+        const secretKey = JSON.parse(await fs.readFile(keypairPath, 'utf8'));
+        const keypair = Keypair.fromSecretKey(Uint8Array.from(secretKey));
+        console.log(`🎯 Using vanity mint ${keypair.publicKey.toString()}`);
+        return keypair;
+    }
+
+    async deployToSolana(config, fraudScore, mintKeypair = null) {
         // Simulate deployment - in real implementation, this would:
         // 1. Build the Anchor program
         // 2. Deploy to Solana
         // 3. Initialize the token launch PDA
         
         const mockProgramId = new PublicKey(PROGRAM_ID);
-        const mockTokenMint = Keypair.generate().publicKey;
+        const mockTokenMint = (mintKeypair || Keypair.generate()).publicKey;
         
         // Generate launch PDA
-        const [launchPDA] = await PublicKey.findProgramAddress(
-            [Buffer.from('launch'), mockTokenMint.toBuffer()],
-            mockProgramId
-        );
+        const [launchPDA] = findLaunch(mockTokenMint, mockProgramId);
 
         // Mock transaction signature
         const mockTxSignature = 'mock_tx_' + Date.now().toString(36) + Math.random().toString(36);
@@ -337,6 +361,7 @@ app.get('/health', (req, res) => {
         services: {
             soldParser: !!orchestrator.soldParser,
             anchorProgram: !!orchestrator.anchorProgram,
+            indexer: !!orchestrator.indexer,
             fraudAPI: AI_FRAUD_API
         }
     });
@@ -414,6 +439,55 @@ app.post('/validate', async (req, res) => {
     }
 });
 
+// Search launches by name, symbol or creator
+app.get('/launches/search', async (req, res) => {
+    try {
+        const { q, limit = 20, active } = req.query;
+
+        if (!q || q.length < 2) {
+            return res.status(400).json({ error: 'Query parameter q must be at least 2 characters' });
+        }
+        if (!orchestrator.indexer) {
+            return res.status(503).json({ error: 'Launch search is not enabled (set DATABASE_URL)' });
+        }
+
+        const results = await orchestrator.indexer.search(q, {
+            limit: parseInt(limit),
+            activeOnly: active === 'true'
+        });
+        res.json({ query: q, count: results.length, results });
+
+    } catch (error) {
+        res.status(500).json({ error: error.message });
+    }
+});
+
+// Fraud-score history for a launch (JSON, or CSV with ?format=csv)
+app.get('/launch/:launchPDA/risk-history', async (req, res) => {
+    try {
+        const { launchPDA } = req.params;
+        const { from, to, format = 'json', backfill } = req.query;
+
+        if (!orchestrator.indexer) {
+            return res.status(503).json({ error: 'Risk history is not enabled (set DATABASE_URL)' });
+        }
+        if (backfill === 'true') {
+            await orchestrator.indexer.backfillRiskHistory(launchPDA);
+        }
+
+        const points = await orchestrator.indexer.riskHistory(launchPDA, { from, to });
+        if (format === 'csv') {
+            res.type('text/csv');
+            res.attachment(`risk-history-${launchPDA}.csv`);
+            return res.send(riskHistoryToCsv(points));
+        }
+        res.json({ launchPDA, count: points.length, points });
+
+    } catch (error) {
+        res.status(500).json({ error: error.message });
+    }
+});
+
 // Get launch status
 app.get('/launch/:launchPDA', async (req, res) => {
     try {
@@ -479,6 +553,8 @@ app.use((req, res) => {
             'POST /launch',
             'POST /validate', 
             'GET /launch/:launchPDA',
+            'GET /launches/search',
+            'GET /launch/:launchPDA/risk-history',
             'POST /calculate-fee'
         ]
     });
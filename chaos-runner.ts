@@ -0,0 +1,453 @@
+// SolD Chaos Runner - adversarial instruction sequences against bankrun
+// Replays hostile sequences against a built launch program and asserts
+// cross-instruction invariants after every step.
+//
+// Usage: anchor build && ts-node chaos-runner.ts [scenario...]
+
+import * as fs from 'fs';
+import { startAnchor, ProgramTestContext, Clock } from 'solana-bankrun';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Program, BN, Idl } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import {
+  AccountLayout,
+  MintLayout,
+  ACCOUNT_SIZE,
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID
+} from '@solana/spl-token';
+// eslint-disable-next-line @typescript-eslint/no-var-requires
+const seeds = require('./seeds');
+
+const PROGRAM_ID = new PublicKey('So1DLaunchProgram11111111111111111111111111');
+const FEE_RECIPIENT = new PublicKey('GR8TuDpbnDvuLzW4JBCLjbeLvGFs1p21XBytLx6rA7XD');
+const CONFIG = seeds.findConfig(PROGRAM_ID);
+const TREASURY = seeds.findTreasury(PROGRAM_ID);
+const ORACLE_REGISTRY = seeds.findOracleRegistry(PROGRAM_ID);
+const MODEL_VERSION = 1;
+const MODEL_HASH = Array.from(Buffer.alloc(32, 1));
+const SCORING_MODEL = seeds.findScoringModel(MODEL_VERSION, PROGRAM_ID);
+const IDL_PATH = 'target/idl/sold_token_launch.json';
+const SECONDS_PER_DAY = 86400;
+const WITHDRAWAL_COOLDOWN = 86400;
+const SUPPLY = 1_000_000_000;
+const MAX_FRAUD_SCORE_BPS = 10_000;
+const AUTO_SUSPEND_SCORE_BPS = 9_000;
+
+interface Harness {
+  context: ProgramTestContext;
+  program: Program;
+  creator: Keypair;
+  insurance: Keypair[];
+  escrow: Keypair;
+  mint: PublicKey;
+  launch: PublicKey;
+  vault: PublicKey;
+  destination: PublicKey;
+}
+
+interface Scenario {
+  name: string;
+  run(h: Harness): Promise<void>;
+}
+
+// Account fixtures
+
+function fund(context: ProgramTestContext, key: PublicKey, lamports = 100_000_000_000) {
+  context.setAccount(key, {
+    lamports,
+    data: Buffer.alloc(0),
+    owner: SystemProgram.programId,
+    executable: false
+  });
+}
+
+function setMint(context: ProgramTestContext, mint: PublicKey, authority: PublicKey) {
+  const data = Buffer.alloc(MINT_SIZE);
+  MintLayout.encode({
+    mintAuthorityOption: 1,
+    mintAuthority: authority,
+    supply: BigInt(SUPPLY),
+    decimals: 6,
+    isInitialized: true,
+    freezeAuthorityOption: 0,
+    freezeAuthority: PublicKey.default
+  }, data);
+  context.setAccount(mint, { lamports: 1_000_000_000, data, owner: TOKEN_PROGRAM_ID, executable: false });
+}
+
+function setTokenAccount(
+  context: ProgramTestContext,
+  address: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  amount: number
+) {
+  const data = Buffer.alloc(ACCOUNT_SIZE);
+  AccountLayout.encode({
+    mint,
+    owner,
+    amount: BigInt(amount),
+    delegateOption: 0,
+    delegate: PublicKey.default,
+    state: 1,
+    isNativeOption: 0,
+    isNative: BigInt(0),
+    delegatedAmount: BigInt(0),
+    closeAuthorityOption: 0,
+    closeAuthority: PublicKey.default
+  }, data);
+  context.setAccount(address, { lamports: 1_000_000_000, data, owner: TOKEN_PROGRAM_ID, executable: false });
+}
+
+async function warp(context: ProgramTestContext, seconds: number) {
+  const clock = await context.banksClient.getClock();
+  context.setClock(new Clock(
+    clock.slot + BigInt(1),
+    clock.epochStartTimestamp,
+    clock.epoch,
+    clock.leaderScheduleEpoch,
+    clock.unixTimestamp + BigInt(seconds)
+  ));
+}
+
+async function setup(insuranceLimit: number, withdrawalThreshold: number): Promise<Harness> {
+  const context = await startAnchor('.', [], []);
+  const provider = new BankrunProvider(context);
+  const idl = JSON.parse(fs.readFileSync(IDL_PATH, 'utf8')) as Idl;
+  const program = new Program(idl, PROGRAM_ID, provider);
+
+  const creator = Keypair.generate();
+  const insurance = [Keypair.generate(), Keypair.generate()];
+  const escrow = Keypair.generate();
+  [creator, ...insurance, escrow].forEach(k => fund(context, k.publicKey));
+  fund(context, FEE_RECIPIENT);
+
+  // Global config is injected rather than initialized, since that requires
+  // the program's upgrade authority
+  const configData = await program.coder.accounts.encode('GlobalConfig', {
+    admin: creator.publicKey,
+    feeRecipient: FEE_RECIPIENT,
+    escrowAuthority: escrow.publicKey,
+    fees: {
+      baseFee: new BN(10_000_000),
+      insuranceFee: new BN(10_000_000),
+      logoFee: new BN(5_000_000),
+      relockFee: new BN(20_000_000),
+      tradingFee: new BN(10_000),
+      emergencyFee: new BN(50_000)
+    },
+    bump: CONFIG[1],
+    insurancePool: FEE_RECIPIENT,
+    treasurySplit: { protocolBps: 10000, insurancePoolBps: 0, creatorRebateBps: 0 },
+    cluster: { localnet: {} },
+    launchClasses: [],
+    referralBps: 0,
+    kycAttestor: null,
+    feeRecipients: [],
+    pausePolicy: { maxPauses: 0, maxPauseSecs: new BN(0), cooldownSecs: new BN(0) },
+    arbitrationRequired: false
+  });
+  context.setAccount(CONFIG[0], {
+    lamports: 1_000_000_000,
+    data: Buffer.concat([configData, Buffer.alloc(63)]),
+    owner: PROGRAM_ID,
+    executable: false
+  });
+  const treasuryData = await program.coder.accounts.encode('Treasury', {
+    totalCollected: new BN(0),
+    totalDistributed: new BN(0),
+    bump: TREASURY[1]
+  });
+  context.setAccount(TREASURY[0], { lamports: 1_000_000_000, data: treasuryData, owner: PROGRAM_ID, executable: false });
+
+  // The creator doubles as the registered fraud oracle
+  const registryData = await program.coder.accounts.encode('OracleRegistry', {
+    oracles: [creator.publicKey],
+    bump: ORACLE_REGISTRY[1]
+  });
+  context.setAccount(ORACLE_REGISTRY[0], {
+    lamports: 1_000_000_000,
+    data: Buffer.concat([registryData, Buffer.alloc(32 * 7)]),
+    owner: PROGRAM_ID,
+    executable: false
+  });
+  const modelData = await program.coder.accounts.encode('ScoringModel', {
+    version: MODEL_VERSION,
+    modelHash: MODEL_HASH,
+    registeredAt: new BN(0),
+    bump: SCORING_MODEL[1]
+  });
+  context.setAccount(SCORING_MODEL[0], { lamports: 1_000_000_000, data: modelData, owner: PROGRAM_ID, executable: false });
+
+  const mint = Keypair.generate().publicKey;
+  const [launch] = seeds.findLaunch(mint, PROGRAM_ID);
+  const [vault] = seeds.findVault(mint, PROGRAM_ID);
+  const destination = Keypair.generate().publicKey;
+  setMint(context, mint, launch);
+
+  await program.methods
+    .initializeLaunch({
+      tokenName: 'Chaos',
+      tokenSymbol: 'CHAOS',
+      totalSupply: new BN(SUPPLY),
+      timelockDuration: new BN(100 * SECONDS_PER_DAY),
+      insuranceWallets: insurance.map(k => k.publicKey),
+      insuranceLimit,
+      logoNft: null,
+      fraudScore: 1_000,
+      withdrawalThreshold,
+      metadataUri: '',
+      creatorMultisig: null,
+      maxTxAmount: null,
+      maxWalletPct: null,
+      presale: null,
+      unlockSchedule: null,
+      relockConsentDays: null,
+      launchClass: null,
+      referrer: null,
+      unlockCondition: null,
+      bootstrap: null,
+      transferTaxBps: 0,
+      logoCnft: null
+    })
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch: launch,
+      creatorIndex: seeds.findCreatorIndex(creator.publicKey, PROGRAM_ID)[0],
+      creatorProfile: seeds.findCreatorProfile(creator.publicKey, PROGRAM_ID)[0],
+      launchRegistry: seeds.findLaunchRegistry(PROGRAM_ID)[0],
+      registryPage: seeds.findRegistryPage(0, PROGRAM_ID)[0],
+      tokenMint: mint,
+      config: CONFIG[0],
+      treasury: TREASURY[0],
+      referrer: null,
+      kycAttestation: null,
+      bootstrapGuard: null,
+      systemProgram: SystemProgram.programId
+    })
+    .signers([creator])
+    .rpc();
+
+  // Minting goes through Metaplex, so the funded vault is injected directly
+  setTokenAccount(context, vault, mint, launch, SUPPLY);
+  setTokenAccount(context, destination, mint, insurance[0].publicKey, 0);
+
+  return { context, program, creator, insurance, escrow, mint, launch, vault, destination };
+}
+
+// Invariants
+
+async function checkInvariants(h: Harness, label: string) {
+  const state: any = await h.program.account.tokenLaunch.fetch(h.launch);
+  const maxWithdraw = Math.floor(SUPPLY * state.insuranceLimit / 100);
+
+  assert(state.totalWithdrawn.toNumber() <= maxWithdraw,
+    `${label}: total withdrawn ${state.totalWithdrawn} exceeds insurance limit ${maxWithdraw}`);
+  assert(state.fraudScore >= 0 && state.fraudScore <= MAX_FRAUD_SCORE_BPS,
+    `${label}: fraud score ${state.fraudScore} out of range`);
+  assert(!(state.fraudScore > AUTO_SUSPEND_SCORE_BPS && state.isActive),
+    `${label}: launch active with fraud score ${state.fraudScore}`);
+
+  const vault = AccountLayout.decode((await h.context.banksClient.getAccount(h.vault))!.data);
+  assert(Number(vault.amount) + state.totalWithdrawn.toNumber() === SUPPLY,
+    `${label}: vault balance ${vault.amount} does not reconcile with withdrawals`);
+}
+
+function assert(condition: boolean, message: string) {
+  if (!condition) throw new Error(`Invariant violated - ${message}`);
+}
+
+async function expectFailure(action: Promise<unknown>, errorName: string, label: string) {
+  try {
+    await action;
+  } catch (error) {
+    if (String(error).includes(errorName)) return;
+    throw new Error(`${label}: expected ${errorName}, got ${error}`);
+  }
+  throw new Error(`${label}: expected ${errorName}, but the instruction succeeded`);
+}
+
+// Instruction wrappers
+
+function updateFraudScore(h: Harness, score: number) {
+  return h.program.methods
+    .updateFraudScore(score, MODEL_VERSION, MODEL_HASH)
+    .accounts({
+      aiAuthority: h.creator.publicKey,
+      oracleRegistry: ORACLE_REGISTRY[0],
+      scoringModel: SCORING_MODEL[0],
+      tokenLaunch: h.launch,
+      tokenMint: h.mint,
+      creatorProfile: seeds.findCreatorProfile(h.creator.publicKey, PROGRAM_ID)[0]
+    })
+    .signers([h.creator])
+    .rpc();
+}
+
+async function emergencyWithdraw(h: Harness, wallet: Keypair, amount: number) {
+  const state: any = await h.program.account.tokenLaunch.fetch(h.launch);
+  return h.program.methods
+    .emergencyWithdraw(new BN(amount), 'chaos')
+    .accounts({
+      authority: wallet.publicKey,
+      tokenLaunch: h.launch,
+      walletRecord: seeds.findInsuranceWallet(h.launch, wallet.publicKey, PROGRAM_ID)[0],
+      withdrawalRecord: seeds.findWithdrawalRecord(h.launch, state.withdrawalRecordCount, PROGRAM_ID)[0],
+      tokenMint: h.mint,
+      vault: h.vault,
+      toTokenAccount: h.destination,
+      config: CONFIG[0],
+      treasury: TREASURY[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId
+    })
+    .signers([wallet])
+    .rpc();
+}
+
+function proposalPda(h: Harness, id: number) {
+  return seeds.findWithdrawalProposal(h.launch, id, PROGRAM_ID)[0] as PublicKey;
+}
+
+// Scenarios
+
+const scenarios: Scenario[] = [
+  {
+    name: 'oracle-flip',
+    async run(h) {
+      // A flapping oracle must never re-activate a suspended launch
+      let suspended = false;
+      for (let i = 0; i < 40; i++) {
+        const score = i % 2 === 0 ? 9_500 : 500;
+        await updateFraudScore(h, score);
+        const state: any = await h.program.account.tokenLaunch.fetch(h.launch);
+        suspended = suspended || score > AUTO_SUSPEND_SCORE_BPS;
+        assert(!(suspended && state.isActive), `flip ${i}: launch re-activated by a low score`);
+        await checkInvariants(h, `flip ${i}`);
+      }
+      await expectFailure(updateFraudScore(h, MAX_FRAUD_SCORE_BPS + 1), 'InvalidFraudScore', 'out-of-range score');
+    }
+  },
+  {
+    name: 'insurance-drain-boundary',
+    async run(h) {
+      // 10% of supply split across two wallets
+      const cap = SUPPLY * 10 / 100 / 2;
+      const [first, second] = h.insurance;
+
+      await emergencyWithdraw(h, first, cap - 1);
+      await checkInvariants(h, 'first withdrawal');
+      await expectFailure(emergencyWithdraw(h, first, 1), 'WithdrawalCooldownActive', 'inside cooldown');
+
+      await warp(h.context, WITHDRAWAL_COOLDOWN);
+      await expectFailure(emergencyWithdraw(h, first, 2), 'ExceedsWalletLimit', 'past wallet cap');
+      await emergencyWithdraw(h, first, 1);
+      await emergencyWithdraw(h, second, cap);
+      await checkInvariants(h, 'drained to limit');
+
+      await warp(h.context, WITHDRAWAL_COOLDOWN);
+      await expectFailure(emergencyWithdraw(h, second, 1), 'ExceedsInsuranceLimit', 'past launch limit');
+      await checkInvariants(h, 'after drain attempts');
+    }
+  },
+  {
+    name: 'relock-during-pending-withdrawal',
+    async run(h) {
+      const [first, second] = h.insurance;
+      const proposal = proposalPda(h, 0);
+      const before: any = await h.program.account.tokenLaunch.fetch(h.launch);
+
+      await h.program.methods
+        .proposeWithdrawal(new BN(1_000), 'chaos')
+        .accounts({
+          proposer: first.publicKey,
+          tokenLaunch: h.launch,
+          proposal,
+          tokenMint: h.mint,
+          toTokenAccount: h.destination,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([first])
+        .rpc();
+
+      await h.program.methods
+        .relockTokens(new BN(200 * SECONDS_PER_DAY), 'chaos')
+        .accounts({
+          escrowAuthority: h.escrow.publicKey,
+          creator: null,
+          tokenLaunch: h.launch,
+          tokenMint: h.mint,
+          config: CONFIG[0],
+          treasury: TREASURY[0],
+          arbitration: null,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([h.escrow])
+        .rpc();
+
+      await h.program.methods
+        .approveWithdrawal(new BN(0))
+        .accounts({ approver: second.publicKey, tokenLaunch: h.launch, proposal, tokenMint: h.mint })
+        .signers([second])
+        .rpc();
+
+      const execute = async () => h.program.methods
+        .executeWithdrawal(new BN(0))
+        .accounts({
+          authority: second.publicKey,
+          tokenLaunch: h.launch,
+          proposal,
+          walletRecord: seeds.findInsuranceWallet(h.launch, first.publicKey, PROGRAM_ID)[0],
+          withdrawalRecord: seeds.findWithdrawalRecord(
+            h.launch,
+            ((await h.program.account.tokenLaunch.fetch(h.launch)) as any).withdrawalRecordCount,
+            PROGRAM_ID
+          )[0],
+          tokenMint: h.mint,
+          vault: h.vault,
+          toTokenAccount: h.destination,
+          config: CONFIG[0],
+          treasury: TREASURY[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([second])
+        .rpc();
+
+      await execute();
+      await expectFailure(execute(), 'ProposalAlreadyExecuted', 'double execution');
+
+      const after: any = await h.program.account.tokenLaunch.fetch(h.launch);
+      assert(after.timelockEnd.gt(before.timelockEnd), 'relock did not extend the timelock');
+      assert(after.totalWithdrawn.toNumber() === 1_000, 'pending withdrawal not applied exactly once');
+      await checkInvariants(h, 'after relock and execution');
+    }
+  }
+];
+
+async function main() {
+  const selected = process.argv.slice(2);
+  const toRun = selected.length ? scenarios.filter(s => selected.includes(s.name)) : scenarios;
+  let failures = 0;
+
+  for (const scenario of toRun) {
+    const threshold = scenario.name === 'relock-during-pending-withdrawal' ? 2 : 1;
+    const h = await setup(10, threshold);
+    try {
+      await scenario.run(h);
+      console.log(`✅ ${scenario.name}`);
+    } catch (error) {
+      failures++;
+      console.error(`❌ ${scenario.name}: ${error.message}`);
+    }
+  }
+
+  process.exit(failures ? 1 : 0);
+}
+
+if (require.main === module) {
+  main();
+}
+
+export { scenarios, setup, checkInvariants };
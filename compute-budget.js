@@ -0,0 +1,87 @@
+// SolD compute budget
+// ComputeBudget instructions for the services' transactions. Without a
+// priority fee they rarely land during congestion; a unit limit near what
+// the transaction uses keeps that fee from being paid on the default 200k.
+
+const { ComputeBudgetProgram, PublicKey, TransactionMessage, VersionedTransaction } = require('@solana/web3.js');
+
+// Per-transaction ceiling in the runtime, used while simulating
+const MAX_COMPUTE_UNITS = 1400000;
+
+// Headroom over simulated usage, since state can change before landing
+const UNIT_MARGIN = 1.2;
+
+// getRecentPrioritizationFees accepts at most this many accounts
+const MAX_FEE_ACCOUNTS = 128;
+
+function percentile(values, p) {
+    if (!values.length) return 0;
+    const sorted = [...values].sort((a, b) => a - b);
+    return sorted[Math.min(sorted.length - 1, Math.floor(sorted.length * p / 100))];
+}
+
+// Units the instructions use when simulated now, plus UNIT_MARGIN
+async function estimateComputeUnits(connection, payer, instructions) {
+    const message = new TransactionMessage({
+        payerKey: payer,
+        recentBlockhash: PublicKey.default.toBase58(),
+        instructions: [ComputeBudgetProgram.setComputeUnitLimit({ units: MAX_COMPUTE_UNITS }), ...instructions]
+    }).compileToV0Message();
+    const { value } = await connection.simulateTransaction(new VersionedTransaction(message), {
+        sigVerify: false,
+        replaceRecentBlockhash: true
+    });
+    if (value.err) {
+        const logs = (value.logs || []).slice(-5).join('\n    ');
+        throw new Error(`Simulation failed: ${JSON.stringify(value.err)}${logs ? `\n    ${logs}` : ''}`);
+    }
+    return Math.min(MAX_COMPUTE_UNITS, Math.ceil(value.unitsConsumed * UNIT_MARGIN));
+}
+
+// The p-th percentile of recent fees (micro-lamports per unit) paid by
+// transactions that wrote any of `accounts`
+async function estimatePriorityFee(connection, accounts, p = 75) {
+    const recent = await connection.getRecentPrioritizationFees({
+        lockedWritableAccounts: accounts.slice(0, MAX_FEE_ACCOUNTS)
+    });
+    return percentile(recent.map(fee => fee.prioritizationFee), p);
+}
+
+function writableAccounts(instructions) {
+    const keys = new Map();
+    for (const instruction of instructions) {
+        for (const { pubkey, isWritable } of instruction.keys) {
+            if (isWritable) keys.set(pubkey.toBase58(), pubkey);
+        }
+    }
+    return [...keys.values()];
+}
+
+// Instructions to prepend to `instructions`. `units` and `microLamports`
+// are numbers, "auto" to estimate them (by simulation, and from recent
+// fees on the accounts written), or unset to keep the runtime's defaults.
+async function computeBudgetInstructions(connection, payer, instructions, { units, microLamports, percentile: p = 75 } = {}) {
+    const budget = [];
+    const limit = units === 'auto' ? await estimateComputeUnits(connection, payer, instructions) : units;
+    if (limit) budget.push(ComputeBudgetProgram.setComputeUnitLimit({ units: limit }));
+    const price = microLamports === 'auto'
+        ? await estimatePriorityFee(connection, writableAccounts(instructions), p)
+        : microLamports;
+    if (price) budget.push(ComputeBudgetProgram.setComputeUnitPrice({ microLamports: price }));
+    return budget;
+}
+
+// Validates a config's compute_unit_limit / priority_fee style setting
+function checkBudgetSetting(name, value) {
+    if (value === undefined || value === 'auto' || (Number.isInteger(value) && value >= 0)) return value;
+    throw new Error(`${name} must be a non-negative integer or "auto"`);
+}
+
+module.exports = {
+    MAX_COMPUTE_UNITS,
+    computeBudgetInstructions,
+    estimateComputeUnits,
+    estimatePriorityFee,
+    checkBudgetSetting,
+    percentile
+};
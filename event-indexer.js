@@ -0,0 +1,561 @@
+// SolD Event Indexer
+// Decodes the program's events from transaction logs into a pluggable store
+
+const fs = require('fs');
+const { EventEmitter } = require('events');
+const { Connection, PublicKey, Keypair } = require('@solana/web3.js');
+const { Program, AnchorProvider, Wallet, EventParser, utils } = require('@project-serum/anchor');
+
+const PROGRAM_ID = 'So1DLaunchProgram11111111111111111111111111';
+const SOLANA_NETWORK = process.env.SOLANA_NETWORK || 'devnet';
+
+// How often confirmed rows are checked against the finalized slot
+const SETTLE_INTERVAL_MS = 10000;
+// getSignatureStatuses accepts at most this many signatures per call
+const STATUS_BATCH = 256;
+// Transactions read on a fresh store before following the live stream
+const DEFAULT_BACKFILL_LIMIT = 1000;
+
+const GRPC_PING_INTERVAL_MS = 15000;
+const GRPC_STALL_MS = 30000;
+const GRPC_MAX_RETRY_MS = 30000;
+
+const POSTGRES_SCHEMA = `
+CREATE TABLE IF NOT EXISTS program_events (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    slot            BIGINT NOT NULL,
+    block_time      TIMESTAMPTZ,
+    name            TEXT NOT NULL,
+    token_mint      TEXT,
+    data            JSONB NOT NULL,
+    finalized       BOOLEAN NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX IF NOT EXISTS program_events_mint_slot ON program_events (token_mint, slot);
+CREATE INDEX IF NOT EXISTS program_events_name_slot ON program_events (name, slot);
+CREATE INDEX IF NOT EXISTS program_events_pending ON program_events (slot) WHERE NOT finalized;
+`;
+
+const SQLITE_SCHEMA = `
+CREATE TABLE IF NOT EXISTS program_events (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    block_time      INTEGER,
+    name            TEXT NOT NULL,
+    token_mint      TEXT,
+    data            TEXT NOT NULL,
+    finalized       INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX IF NOT EXISTS program_events_mint_slot ON program_events (token_mint, slot);
+CREATE INDEX IF NOT EXISTS program_events_name_slot ON program_events (name, slot);
+CREATE INDEX IF NOT EXISTS program_events_pending ON program_events (slot) WHERE NOT finalized;
+`;
+
+// Event stores share one interface so the indexer can run against either:
+//   migrate()                                 create the schema if missing
+//   insertEvents(rows)                        idempotent on (signature, eventIndex)
+//   finalize([{ signature, slot }])           mark a transaction's rows final, at the slot it finalized in
+//   discard(signatures)                       drop the rows of transactions a fork abandoned
+//   pendingSignatures(maxSlot)                unfinalized [{ signature, slot }] at or below a slot
+//   latestFinalizedSignature()                newest final signature, where a gap backfill stops
+//   events({ name, tokenMint, fromSlot, limit })
+//   close()
+// A row is { signature, eventIndex, slot, blockTime, name, tokenMint, data, finalized }
+// with blockTime in unix seconds (or null) and data the decoded event as JSON.
+class PostgresEventStore {
+    constructor(databaseUrl) {
+        const { Pool } = require('pg');
+        this.pool = new Pool({ connectionString: databaseUrl });
+    }
+
+    async migrate() {
+        await this.pool.query(POSTGRES_SCHEMA);
+    }
+
+    async insertEvents(rows) {
+        const client = await this.pool.connect();
+        try {
+            await client.query('BEGIN');
+            for (const row of rows) {
+                await client.query(
+                    `INSERT INTO program_events (signature, event_index, slot, block_time, name, token_mint, data, finalized)
+                     VALUES ($1, $2, $3, to_timestamp($4), $5, $6, $7, $8)
+                     ON CONFLICT (signature, event_index) DO UPDATE SET
+                         slot = EXCLUDED.slot,
+                         finalized = program_events.finalized OR EXCLUDED.finalized`,
+                    [row.signature, row.eventIndex, row.slot, row.blockTime, row.name,
+                     row.tokenMint, JSON.stringify(row.data), row.finalized]
+                );
+            }
+            await client.query('COMMIT');
+        } catch (error) {
+            await client.query('ROLLBACK');
+            throw error;
+        } finally {
+            client.release();
+        }
+    }
+
+    async finalize(transactions) {
+        for (const { signature, slot } of transactions) {
+            await this.pool.query(
+                'UPDATE program_events SET finalized = true, slot = $2 WHERE signature = $1',
+                [signature, slot]
+            );
+        }
+    }
+
+    async discard(signatures) {
+        if (!signatures.length) return;
+        await this.pool.query('DELETE FROM program_events WHERE signature = ANY($1)', [signatures]);
+    }
+
+    async pendingSignatures(maxSlot) {
+        const { rows } = await this.pool.query(
+            `SELECT DISTINCT signature, slot FROM program_events
+             WHERE NOT finalized AND slot <= $1 ORDER BY slot`,
+            [maxSlot]
+        );
+        return rows.map(row => ({ signature: row.signature, slot: Number(row.slot) }));
+    }
+
+    async latestFinalizedSignature() {
+        const { rows } = await this.pool.query(
+            'SELECT signature FROM program_events WHERE finalized ORDER BY slot DESC, signature LIMIT 1'
+        );
+        return rows[0]?.signature || null;
+    }
+
+    async events({ name, tokenMint, fromSlot, limit = 100 } = {}) {
+        const { rows } = await this.pool.query(
+            `SELECT signature, event_index, slot, extract(epoch FROM block_time) AS block_time,
+                    name, token_mint, data, finalized
+             FROM program_events
+             WHERE ($1::text IS NULL OR name = $1)
+               AND ($2::text IS NULL OR token_mint = $2)
+               AND ($3::bigint IS NULL OR slot >= $3)
+             ORDER BY slot, signature, event_index
+             LIMIT $4`,
+            [name || null, tokenMint || null, fromSlot ?? null, limit]
+        );
+        return rows.map(row => ({
+            signature: row.signature,
+            eventIndex: row.event_index,
+            slot: Number(row.slot),
+            blockTime: row.block_time === null ? null : Number(row.block_time),
+            name: row.name,
+            tokenMint: row.token_mint,
+            data: row.data,
+            finalized: row.finalized
+        }));
+    }
+
+    async close() {
+        await this.pool.end();
+    }
+}
+
+class SqliteEventStore {
+    constructor(filename) {
+        const Database = require('better-sqlite3');
+        this.db = new Database(filename);
+        this.db.pragma('journal_mode = WAL');
+    }
+
+    async migrate() {
+        this.db.exec(SQLITE_SCHEMA);
+    }
+
+    async insertEvents(rows) {
+        const insert = this.db.prepare(
+            `INSERT INTO program_events (signature, event_index, slot, block_time, name, token_mint, data, finalized)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT (signature, event_index) DO UPDATE SET
+                 slot = excluded.slot,
+                 finalized = program_events.finalized OR excluded.finalized`
+        );
+        this.db.transaction(() => {
+            for (const row of rows) {
+                insert.run(row.signature, row.eventIndex, row.slot, row.blockTime, row.name,
+                    row.tokenMint, JSON.stringify(row.data), row.finalized ? 1 : 0);
+            }
+        })();
+    }
+
+    async finalize(transactions) {
+        const update = this.db.prepare('UPDATE program_events SET finalized = 1, slot = ? WHERE signature = ?');
+        this.db.transaction(() => {
+            for (const { signature, slot } of transactions) update.run(slot, signature);
+        })();
+    }
+
+    async discard(signatures) {
+        const remove = this.db.prepare('DELETE FROM program_events WHERE signature = ?');
+        this.db.transaction(() => signatures.forEach(signature => remove.run(signature)))();
+    }
+
+    async pendingSignatures(maxSlot) {
+        return this.db.prepare(
+            `SELECT DISTINCT signature, slot FROM program_events
+             WHERE NOT finalized AND slot <= ? ORDER BY slot`
+        ).all(maxSlot);
+    }
+
+    async latestFinalizedSignature() {
+        const row = this.db.prepare(
+            'SELECT signature FROM program_events WHERE finalized ORDER BY slot DESC, signature LIMIT 1'
+        ).get();
+        return row ? row.signature : null;
+    }
+
+    async events({ name, tokenMint, fromSlot, limit = 100 } = {}) {
+        const rows = this.db.prepare(
+            `SELECT signature, event_index, slot, block_time, name, token_mint, data, finalized
+             FROM program_events
+             WHERE (@name IS NULL OR name = @name)
+               AND (@tokenMint IS NULL OR token_mint = @tokenMint)
+               AND (@fromSlot IS NULL OR slot >= @fromSlot)
+             ORDER BY slot, signature, event_index
+             LIMIT @limit`
+        ).all({ name: name || null, tokenMint: tokenMint || null, fromSlot: fromSlot ?? null, limit });
+        return rows.map(row => ({
+            signature: row.signature,
+            eventIndex: row.event_index,
+            slot: row.slot,
+            blockTime: row.block_time,
+            name: row.name,
+            tokenMint: row.token_mint,
+            data: JSON.parse(row.data),
+            finalized: row.finalized === 1
+        }));
+    }
+
+    async close() {
+        this.db.close();
+    }
+}
+
+// "postgres://..." or "sqlite:path/to/events.db"
+function createEventStore(url) {
+    if (/^postgres(ql)?:\/\//.test(url)) {
+        return new PostgresEventStore(url);
+    }
+    if (url.startsWith('sqlite:')) {
+        return new SqliteEventStore(url.slice('sqlite:'.length));
+    }
+    throw new Error(`Unsupported event store URL: ${url} (use postgres://... or sqlite:<file>)`);
+}
+
+// Decoded event fields as plain JSON: public keys in base58, numbers that may
+// exceed 2^53 (BN) as decimal strings
+function toJson(value) {
+    if (value instanceof PublicKey) return value.toBase58();
+    if (value && typeof value.toTwos === 'function') return value.toString();
+    if (Buffer.isBuffer(value) || value instanceof Uint8Array) return Array.from(value);
+    if (Array.isArray(value)) return value.map(toJson);
+    if (value && typeof value === 'object') {
+        return Object.fromEntries(Object.entries(value).map(([key, field]) => [key, toJson(field)]));
+    }
+    return value;
+}
+
+class EventIndexer extends EventEmitter {
+    // commitment: 'confirmed' stores events as soon as their slot confirms
+    // and settles them once it finalizes; 'finalized' stores only final ones.
+    // ingestion: 'ws' (logsSubscribe) or 'grpc' (Yellowstone Geyser plugin
+    // at grpcEndpoint, authenticated with grpcToken).
+    constructor({ connection, program, store, commitment = 'confirmed', ingestion = 'ws',
+                  grpcEndpoint, grpcToken, backfillLimit = DEFAULT_BACKFILL_LIMIT }) {
+        super();
+        if (commitment !== 'confirmed' && commitment !== 'finalized') {
+            throw new Error(`Unsupported indexer commitment: ${commitment}`);
+        }
+        if (ingestion !== 'ws' && ingestion !== 'grpc') {
+            throw new Error(`Unknown indexer ingestion mode: ${ingestion}`);
+        }
+        if (ingestion === 'grpc' && !grpcEndpoint) {
+            throw new Error('gRPC ingestion requires grpcEndpoint');
+        }
+        this.connection = connection;
+        this.program = program;
+        this.store = store;
+        this.commitment = commitment;
+        this.ingestion = ingestion;
+        this.grpcEndpoint = grpcEndpoint;
+        this.grpcToken = grpcToken;
+        this.backfillLimit = backfillLimit;
+        this.parser = new EventParser(program.programId, program.coder);
+        this.subscriptionId = null;
+        this.grpc = null;
+        this.settleTimer = null;
+        this.stopped = false;
+    }
+
+    // Catch up from the newest stored final event, then follow the stream
+    async start() {
+        await this.store.migrate();
+
+        if (this.ingestion === 'grpc') {
+            await this.startGrpc();
+        } else {
+            this.subscriptionId = this.connection.onLogs(
+                this.program.programId,
+                ({ signature, err, logs }, { slot }) => {
+                    if (err) return;
+                    this.ingest(signature, slot, null, logs, this.commitment === 'finalized').catch(error =>
+                        console.error(`Failed to index ${signature}:`, error.message)
+                    );
+                },
+                this.commitment
+            );
+        }
+        // Subscribe first so nothing lands between the backfill and the stream
+        await this.backfill();
+
+        if (this.commitment === 'confirmed') {
+            this.settleTimer = setInterval(() => {
+                this.settle().catch(error => console.error('Settling events failed:', error.message));
+            }, SETTLE_INTERVAL_MS);
+        }
+        console.log(`✅ Event indexer running (${this.ingestion}, ${this.commitment})`);
+    }
+
+    async stop() {
+        this.stopped = true;
+        clearInterval(this.settleTimer);
+        this.closeGrpc();
+        if (this.subscriptionId !== null) {
+            await this.connection.removeOnLogsListener(this.subscriptionId);
+        }
+        await this.store.close();
+    }
+
+    // Events the program emitted in one successful transaction, in log
+    // order. Events from programs it invoked, or that invoked it, are skipped.
+    decode(signature, slot, blockTime, logs, finalized) {
+        const rows = [];
+        for (const event of this.parser.parseLogs(logs)) {
+            rows.push({
+                signature,
+                eventIndex: rows.length,
+                slot,
+                blockTime,
+                name: event.name,
+                tokenMint: event.data.tokenMint ? event.data.tokenMint.toBase58() : null,
+                data: toJson(event.data),
+                finalized
+            });
+        }
+        return rows;
+    }
+
+    async ingest(signature, slot, blockTime, logs, finalized) {
+        const rows = this.decode(signature, slot, blockTime, logs, finalized);
+        if (!rows.length) return 0;
+        await this.store.insertEvents(rows);
+        rows.forEach(row => this.emit('event', row));
+        return rows.length;
+    }
+
+    // Read the program's transactions since the newest final one stored (or
+    // the last backfillLimit on a fresh store), oldest first
+    async backfill() {
+        const until = await this.store.latestFinalizedSignature();
+        const signatures = [];
+        let before;
+        while (until || signatures.length < this.backfillLimit) {
+            const page = await this.connection.getSignaturesForAddress(
+                this.program.programId,
+                { before, until: until || undefined, limit: 1000 },
+                this.commitment
+            );
+            signatures.push(...page.filter(entry => !entry.err));
+            if (page.length < 1000) break;
+            before = page[page.length - 1].signature;
+        }
+        if (!until) signatures.splice(this.backfillLimit);
+
+        let indexed = 0;
+        for (const entry of signatures.reverse()) {
+            const tx = await this.connection.getTransaction(entry.signature, {
+                commitment: this.commitment,
+                maxSupportedTransactionVersion: 0
+            });
+            if (!tx || tx.meta?.err) continue;
+            indexed += await this.ingest(
+                entry.signature,
+                tx.slot,
+                tx.blockTime ?? null,
+                tx.meta?.logMessages || [],
+                entry.confirmationStatus === 'finalized'
+            );
+        }
+        console.log(`📚 Indexed ${indexed} events from ${signatures.length} transactions`);
+    }
+
+    // Settle confirmed rows at or below the finalized slot. A transaction
+    // the finalized fork kept is marked final at the slot it landed in
+    // there; one it dropped is discarded. One re-included in a later slot
+    // that is not final yet stays pending.
+    async settle() {
+        const finalizedSlot = await this.connection.getSlot('finalized');
+        const pending = await this.store.pendingSignatures(finalizedSlot);
+        const final = [];
+        const dropped = [];
+        for (let i = 0; i < pending.length; i += STATUS_BATCH) {
+            const batch = pending.slice(i, i + STATUS_BATCH).map(entry => entry.signature);
+            const { value } = await this.connection.getSignatureStatuses(batch, { searchTransactionHistory: true });
+            value.forEach((status, j) => {
+                if (!status || status.err) {
+                    dropped.push(batch[j]);
+                } else if (status.confirmationStatus === 'finalized') {
+                    final.push({ signature: batch[j], slot: status.slot });
+                }
+            });
+        }
+        await this.store.finalize(final);
+        await this.store.discard(dropped);
+        dropped.forEach(signature => this.emit('discard', signature));
+        if (dropped.length) {
+            console.warn(`⚠️ Discarded events from ${dropped.length} transactions dropped by a fork`);
+        }
+    }
+
+    // Stream the program's successful transactions at the indexer's
+    // commitment. Any reconnect backfills from RPC to cover the gap.
+    async startGrpc() {
+        const { default: Client, CommitmentLevel } = require('@triton-one/yellowstone-grpc');
+        const client = new Client(this.grpcEndpoint, this.grpcToken, undefined);
+        if (client.connect) await client.connect();
+        const stream = await client.subscribe();
+
+        let lastMessageAt = Date.now();
+        const grpc = { stream, timers: [] };
+        this.grpc = grpc;
+
+        stream.on('data', data => {
+            lastMessageAt = Date.now();
+            this.handleGrpcMessage(data).catch(error =>
+                console.error('gRPC message handling failed:', error.message)
+            );
+        });
+        const dropped = reason => {
+            if (this.grpc !== grpc) return;
+            this.closeGrpc();
+            if (!this.stopped) this.reconnectGrpc(reason);
+        };
+        stream.on('error', error => dropped(error.message));
+        stream.on('end', () => dropped('stream ended'));
+
+        await new Promise((resolve, reject) => {
+            stream.write({
+                accounts: {},
+                slots: {},
+                transactions: {
+                    events: {
+                        vote: false,
+                        failed: false,
+                        accountInclude: [this.program.programId.toBase58()],
+                        accountExclude: [],
+                        accountRequired: []
+                    }
+                },
+                transactionsStatus: {},
+                blocks: {},
+                blocksMeta: {},
+                entry: {},
+                accountsDataSlice: [],
+                commitment: this.commitment === 'finalized' ? CommitmentLevel.FINALIZED : CommitmentLevel.CONFIRMED
+            }, error => (error ? reject(error) : resolve()));
+        });
+
+        grpc.timers.push(setInterval(() => {
+            stream.write({ ping: { id: 1 } }, () => {});
+            if (Date.now() - lastMessageAt > GRPC_STALL_MS) dropped('stream stalled');
+        }, GRPC_PING_INTERVAL_MS));
+    }
+
+    closeGrpc() {
+        if (!this.grpc) return;
+        this.grpc.timers.forEach(clearInterval);
+        this.grpc.stream.end();
+        this.grpc = null;
+    }
+
+    async reconnectGrpc(reason, attempt = 0) {
+        const delay = Math.min(1000 * 2 ** attempt, GRPC_MAX_RETRY_MS);
+        console.warn(`⚠️ gRPC stream dropped (${reason}); reconnecting in ${delay}ms`);
+        await new Promise(resolve => setTimeout(resolve, delay));
+        if (this.stopped) return;
+        try {
+            await this.startGrpc();
+            // Transactions during the outage were never streamed; read them from RPC
+            await this.backfill();
+        } catch (error) {
+            this.closeGrpc();
+            return this.reconnectGrpc(error.message, attempt + 1);
+        }
+    }
+
+    async handleGrpcMessage(data) {
+        if (!data.transaction) return;
+        const { transaction, slot } = data.transaction;
+        const signature = utils.bytes.bs58.encode(Buffer.from(transaction.signature));
+        await this.ingest(
+            signature,
+            Number(slot),
+            null,
+            transaction.meta?.logMessages || [],
+            this.commitment === 'finalized'
+        );
+    }
+
+    async events(query) {
+        return this.store.events(query);
+    }
+}
+
+async function main() {
+    const store = createEventStore(process.env.EVENT_STORE_URL || 'sqlite:events.db');
+    const connection = new Connection(
+        process.env.SOLANA_RPC_URL || (SOLANA_NETWORK === 'mainnet'
+            ? 'https://api.mainnet-beta.solana.com'
+            : 'https://api.devnet.solana.com'),
+        'confirmed'
+    );
+    const provider = new AnchorProvider(connection, new Wallet(Keypair.generate()), {});
+    const idl = JSON.parse(fs.readFileSync(process.env.PROGRAM_IDL || 'idl/sold_token_launch.json', 'utf8'));
+    const program = new Program(idl, new PublicKey(PROGRAM_ID), provider);
+
+    const indexer = new EventIndexer({
+        connection,
+        program,
+        store,
+        commitment: process.env.INDEXER_COMMITMENT || 'confirmed',
+        ingestion: process.env.INDEXER_INGESTION || 'ws',
+        grpcEndpoint: process.env.YELLOWSTONE_GRPC_URL,
+        grpcToken: process.env.YELLOWSTONE_GRPC_TOKEN
+    });
+    await indexer.start();
+
+    const shutdown = async () => {
+        await indexer.stop();
+        process.exit(0);
+    };
+    process.on('SIGTERM', shutdown);
+    process.on('SIGINT', shutdown);
+}
+
+if (require.main === module) {
+    main().catch(error => {
+        console.error('❌ Event indexer failed to start:', error.message);
+        process.exit(1);
+    });
+}
+
+module.exports = { EventIndexer, PostgresEventStore, SqliteEventStore, createEventStore, toJson };
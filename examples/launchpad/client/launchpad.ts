@@ -0,0 +1,433 @@
+// SolD example launchpad - client steps
+// Each step drives the launch program through the Anchor-generated client
+// and prints one JSON result line; the axum backend shells out to this.
+//
+// Usage: ts-node launchpad.ts <step> '<json args>'
+// Steps: deploy, presale, finalize, unlock, allocate, claim, status
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { AnchorProvider, BN, Program, Wallet } from '@coral-xyz/anchor';
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, createMint, getOrCreateAssociatedTokenAccount } from '@solana/spl-token';
+import { keccak_256 } from 'js-sha3';
+import { SoldTokenLaunch } from '../../../target/types/sold_token_launch';
+import { SolDParser } from '../../../sold-parser';
+// eslint-disable-next-line @typescript-eslint/no-var-requires
+const seeds = require('../../../seeds');
+
+const IDL_PATH = path.join(__dirname, '..', '..', '..', 'target', 'idl', 'sold_token_launch.json');
+const METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const RPC_URL = process.env.LAUNCHPAD_RPC || 'https://api.devnet.solana.com';
+const WORK_DIR = process.env.LAUNCHPAD_WORKDIR || path.join(__dirname, '..', 'work');
+const DECIMALS = 6;
+const WALLET_FUNDING = 2 * LAMPORTS_PER_SOL;
+
+interface PresaleBuyer {
+  wallet: string;
+  allocation: string;
+}
+
+interface PresaleArgs {
+  buyers: PresaleBuyer[];
+  priceLamports: string;
+  durationSecs: number;
+}
+
+// Per-launch state the later steps need (presale tree, creator wallet)
+interface LaunchRecord {
+  mint: string;
+  launch: string;
+  creator: string;
+  presale?: { buyers: { wallet: string; pubkey: string; allocation: string }[] };
+}
+
+const connection = new Connection(RPC_URL, 'confirmed');
+const idl = JSON.parse(fs.readFileSync(IDL_PATH, 'utf8'));
+const programId = new PublicKey(idl.metadata?.address || seeds.PROGRAM_ID);
+const [configPda] = seeds.findConfig(programId);
+const [treasuryPda] = seeds.findTreasury(programId);
+
+// Named devnet wallets, created and funded on first use
+async function wallet(name: string): Promise<Keypair> {
+  const file = path.join(WORK_DIR, 'wallets', `${name}.json`);
+  if (fs.existsSync(file)) {
+    return Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(file, 'utf8'))));
+  }
+  const keypair = Keypair.generate();
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  fs.writeFileSync(file, JSON.stringify(Array.from(keypair.secretKey)));
+  const signature = await connection.requestAirdrop(keypair.publicKey, WALLET_FUNDING);
+  await connection.confirmTransaction(signature, 'confirmed');
+  return keypair;
+}
+
+// Spec wallets are either addresses or launchpad wallet names
+async function resolveWallet(nameOrAddress: string): Promise<PublicKey> {
+  try {
+    return new PublicKey(nameOrAddress);
+  } catch (error) {
+    return (await wallet(nameOrAddress)).publicKey;
+  }
+}
+
+function program(signer: Keypair): Program<SoldTokenLaunch> {
+  const provider = new AnchorProvider(connection, new Wallet(signer), { commitment: 'confirmed' });
+  return new Program(idl, programId, provider) as unknown as Program<SoldTokenLaunch>;
+}
+
+function recordPath(mint: string): string {
+  return path.join(WORK_DIR, 'launches', `${mint}.json`);
+}
+
+function loadRecord(mint: string): LaunchRecord {
+  return JSON.parse(fs.readFileSync(recordPath(mint), 'utf8'));
+}
+
+function saveRecord(record: LaunchRecord): void {
+  fs.mkdirSync(path.dirname(recordPath(record.mint)), { recursive: true });
+  fs.writeFileSync(recordPath(record.mint), JSON.stringify(record, null, 2));
+}
+
+// Presale Merkle tree matching presale_leaf / verify_merkle_proof on-chain:
+// keccak(buyer || allocation LE) leaves, sorted-pair keccak nodes
+function presaleLeaf(buyer: PublicKey, allocation: string): Buffer {
+  const amount = Buffer.alloc(8);
+  amount.writeBigUInt64LE(BigInt(allocation));
+  return Buffer.from(keccak_256.arrayBuffer(Buffer.concat([buyer.toBuffer(), amount])));
+}
+
+function hashPair(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return Buffer.from(keccak_256.arrayBuffer(Buffer.concat([left, right])));
+}
+
+function merkleLevels(leaves: Buffer[]): Buffer[][] {
+  const levels = [leaves];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+    }
+    levels.push(next);
+  }
+  return levels;
+}
+
+function merkleProof(levels: Buffer[][], index: number): number[][] {
+  const proof: number[][] = [];
+  for (const level of levels.slice(0, -1)) {
+    const sibling = index ^ 1;
+    if (sibling < level.length) proof.push(Array.from(level[sibling]));
+    index >>= 1;
+  }
+  return proof;
+}
+
+function presaleTree(record: LaunchRecord): Buffer[][] {
+  const buyers = record.presale?.buyers || [];
+  return merkleLevels(buyers.map(b => presaleLeaf(new PublicKey(b.pubkey), b.allocation)));
+}
+
+function launchAccounts(mint: PublicKey) {
+  const [tokenLaunch] = seeds.findLaunch(mint, programId);
+  const [vault] = seeds.findVault(mint, programId);
+  return { tokenLaunch, vault };
+}
+
+// Spec -> initialize_launch + create_token. The spec must target DEVNET.
+async function deploy(args: { spec: string; presale?: PresaleArgs }) {
+  const parser = new SolDParser();
+  const config = parser.parse(fs.readFileSync(args.spec, 'utf8'));
+  if (config.network !== 'DEVNET') {
+    throw new Error(`Example launchpad only deploys DEVNET specs, got ${config.network}`);
+  }
+
+  const creator = await wallet('creator');
+  const mint = await createMint(connection, creator, creator.publicKey, null, DECIMALS);
+  const { tokenLaunch, vault } = launchAccounts(mint);
+  const totalSupply = new BN(config.token.supply).mul(new BN(10).pow(new BN(DECIMALS)));
+
+  let presale = null;
+  let presaleBuyers: NonNullable<LaunchRecord['presale']>['buyers'] = [];
+  if (args.presale) {
+    presaleBuyers = await Promise.all(args.presale.buyers.map(async b => ({
+      wallet: b.wallet,
+      pubkey: (await wallet(b.wallet)).publicKey.toBase58(),
+      allocation: b.allocation
+    })));
+    const levels = merkleLevels(presaleBuyers.map(b => presaleLeaf(new PublicKey(b.pubkey), b.allocation)));
+    presale = {
+      merkleRoot: Array.from(levels[levels.length - 1][0]),
+      priceLamports: new BN(args.presale.priceLamports),
+      allocation: presaleBuyers.reduce((sum, b) => sum.add(new BN(b.allocation)), new BN(0)),
+      endTime: new BN(Math.floor(Date.now() / 1000) + args.presale.durationSecs)
+    };
+  }
+
+  const insuranceWallets = await Promise.all(config.insurance.wallets.map(resolveWallet));
+  const launchProgram = program(creator);
+  const [launchRegistry] = seeds.findLaunchRegistry(programId);
+  const registry = await launchProgram.account.launchRegistry.fetchNullable(launchRegistry);
+  const registryPage = seeds.registryPageOf(registry ? registry.launchCount : 0);
+  const initialize = await launchProgram.methods
+    .initializeLaunch({
+      tokenName: config.token.name,
+      tokenSymbol: config.token.symbol,
+      totalSupply,
+      timelockDuration: new BN(parser.parseDuration(config.timelock.duration)),
+      insuranceWallets,
+      insuranceLimit: config.insurance.limit,
+      logoNft: null,
+      fraudScore: 0,
+      withdrawalThreshold: 1,
+      metadataUri: '',
+      creatorMultisig: null,
+      maxTxAmount: null,
+      maxWalletPct: null,
+      presale,
+      unlockSchedule: null,
+      relockConsentDays: null,
+      launchClass: null,
+      referrer: null,
+      unlockCondition: null,
+      bootstrap: null,
+      transferTaxBps: 0,
+      logoCnft: null
+    })
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch,
+      creatorIndex: seeds.findCreatorIndex(creator.publicKey, programId)[0],
+      creatorProfile: seeds.findCreatorProfile(creator.publicKey, programId)[0],
+      launchRegistry: seeds.findLaunchRegistry(programId)[0],
+      registryPage: seeds.findRegistryPage(registryPage, programId)[0],
+      tokenMint: mint,
+      config: configPda,
+      treasury: treasuryPda,
+      referrer: null,
+      kycAttestation: null,
+      bootstrapGuard: null,
+      systemProgram: SystemProgram.programId
+    })
+    .rpc();
+
+  const [metadata] = PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    METADATA_PROGRAM_ID
+  );
+  const createToken = await launchProgram.methods
+    .createToken(DECIMALS)
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch,
+      tokenMint: mint,
+      vault,
+      metadata,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      metadataProgram: METADATA_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY
+    })
+    .rpc();
+
+  saveRecord({
+    mint: mint.toBase58(),
+    launch: tokenLaunch.toBase58(),
+    creator: 'creator',
+    presale: args.presale ? { buyers: presaleBuyers } : undefined
+  });
+  const launch = await launchProgram.account.tokenLaunch.fetch(tokenLaunch);
+  return {
+    mint: mint.toBase58(),
+    launch: tokenLaunch.toBase58(),
+    timelockEnd: launch.timelockEnd.toNumber(),
+    signatures: [initialize, createToken]
+  };
+}
+
+async function presaleBuy(args: { mint: string; buyer: string; amount: string }) {
+  const record = loadRecord(args.mint);
+  const index = (record.presale?.buyers || []).findIndex(b => b.wallet === args.buyer);
+  if (index < 0) throw new Error(`${args.buyer} is not on the presale list`);
+  const entry = record.presale!.buyers[index];
+
+  const buyer = await wallet(args.buyer);
+  const mint = new PublicKey(args.mint);
+  const { tokenLaunch, vault } = launchAccounts(mint);
+  const buyerTokenAccount = await getOrCreateAssociatedTokenAccount(connection, buyer, mint, buyer.publicKey);
+
+  const signature = await program(buyer).methods
+    .presaleBuy(new BN(args.amount), new BN(entry.allocation), merkleProof(presaleTree(record), index))
+    .accounts({
+      buyer: buyer.publicKey,
+      tokenLaunch,
+      purchase: seeds.findPresalePurchase(tokenLaunch, buyer.publicKey, programId)[0],
+      tokenMint: mint,
+      vault,
+      buyerTokenAccount: buyerTokenAccount.address,
+      solVault: seeds.findSolVault(mint, programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId
+    })
+    .rpc();
+  return { signature };
+}
+
+async function finalize(args: { mint: string }) {
+  const creator = await wallet(loadRecord(args.mint).creator);
+  const mint = new PublicKey(args.mint);
+  const signature = await program(creator).methods
+    .finalizePresale()
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch: launchAccounts(mint).tokenLaunch,
+      tokenMint: mint,
+      solVault: seeds.findSolVault(mint, programId)[0]
+    })
+    .rpc();
+  return { signature };
+}
+
+// release_tokens from the vault to a named wallet (after the timelock)
+async function unlock(args: { mint: string; to: string; amount: string }) {
+  const creator = await wallet(loadRecord(args.mint).creator);
+  const recipient = await wallet(args.to);
+  const mint = new PublicKey(args.mint);
+  const { tokenLaunch, vault } = launchAccounts(mint);
+  const toTokenAccount = await getOrCreateAssociatedTokenAccount(connection, creator, mint, recipient.publicKey);
+
+  const signature = await program(creator).methods
+    .releaseTokens(new BN(args.amount))
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch,
+      tokenMint: mint,
+      vault,
+      toTokenAccount: toTokenAccount.address,
+      toBlacklist: seeds.findBlacklist(tokenLaunch, recipient.publicKey, programId)[0],
+      config: configPda,
+      treasury: treasuryPda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId
+    })
+    .rpc();
+  return { signature };
+}
+
+// create_allocation; vestingSecs 0 makes an immediately claimable airdrop
+async function allocate(args: { mint: string; beneficiary: string; amount: string; cliffSecs?: number; vestingSecs?: number }) {
+  const creator = await wallet(loadRecord(args.mint).creator);
+  const beneficiary = (await wallet(args.beneficiary)).publicKey;
+  const mint = new PublicKey(args.mint);
+  const { tokenLaunch, vault } = launchAccounts(mint);
+  const vesting = (args.vestingSecs || 0) > 0;
+
+  const signature = await program(creator).methods
+    .createAllocation(
+      beneficiary,
+      vesting ? { vesting: {} } : { airdrop: {} },
+      new BN(args.amount),
+      new BN(args.cliffSecs || 0),
+      new BN(args.vestingSecs || 0)
+    )
+    .accounts({
+      creator: creator.publicKey,
+      tokenLaunch,
+      tokenMint: mint,
+      vault,
+      allocationVault: seeds.findAllocationVault(mint, programId)[0],
+      allocation: seeds.findAllocation(tokenLaunch, beneficiary, vesting ? 'vesting' : 'airdrop', programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId
+    })
+    .rpc();
+  return { signature };
+}
+
+// claim_all over whichever allocations the beneficiary holds
+async function claim(args: { mint: string; beneficiary: string }) {
+  const beneficiary = await wallet(args.beneficiary);
+  const mint = new PublicKey(args.mint);
+  const { tokenLaunch } = launchAccounts(mint);
+  const launchProgram = program(beneficiary);
+  const destination = await getOrCreateAssociatedTokenAccount(connection, beneficiary, mint, beneficiary.publicKey);
+
+  const kinds: object[] = [];
+  const remainingAccounts = [];
+  for (const kind of ['vesting', 'airdrop']) {
+    const [allocation] = seeds.findAllocation(tokenLaunch, beneficiary.publicKey, kind, programId);
+    if (!(await connection.getAccountInfo(allocation))) continue;
+    kinds.push({ [kind]: {} });
+    remainingAccounts.push(
+      { pubkey: allocation, isSigner: false, isWritable: true },
+      { pubkey: destination.address, isSigner: false, isWritable: true }
+    );
+  }
+  if (!kinds.length) throw new Error(`${args.beneficiary} has no allocations`);
+
+  const signature = await launchProgram.methods
+    .claimAll(kinds as any)
+    .accounts({
+      beneficiary: beneficiary.publicKey,
+      tokenLaunch,
+      tokenMint: mint,
+      allocationVault: seeds.findAllocationVault(mint, programId)[0],
+      feePayer: null,
+      sponsorPool: null,
+      sponsoredWallet: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId
+    })
+    .remainingAccounts(remainingAccounts)
+    .rpc();
+  const balance = await connection.getTokenAccountBalance(destination.address);
+  return { signature, balance: balance.value.amount };
+}
+
+async function status(args: { mint: string }) {
+  const mint = new PublicKey(args.mint);
+  const { tokenLaunch, vault } = launchAccounts(mint);
+  const launch = await program(Keypair.generate()).account.tokenLaunch.fetch(tokenLaunch);
+  const vaultBalance = await connection.getTokenAccountBalance(vault);
+  return {
+    mint: args.mint,
+    launch: tokenLaunch.toBase58(),
+    // Zero-copy slots: fixed-width bytes with the used length beside them
+    name: Buffer.from(launch.tokenName.slice(0, launch.tokenNameLen)).toString('utf8'),
+    symbol: Buffer.from(launch.tokenSymbol.slice(0, launch.tokenSymbolLen)).toString('utf8'),
+    isActive: launch.isActive !== 0,
+    timelockEnd: launch.timelockEnd.toNumber(),
+    presaleSold: launch.presaleSold.toString(),
+    presaleFinalized: launch.presaleFinalized !== 0,
+    unlockedSoFar: launch.unlockedSoFar.toString(),
+    vaultBalance: vaultBalance.value.amount,
+    now: Math.floor(Date.now() / 1000)
+  };
+}
+
+const STEPS: Record<string, (args: any) => Promise<object>> = {
+  deploy,
+  presale: presaleBuy,
+  finalize,
+  unlock,
+  allocate,
+  claim,
+  status
+};
+
+if (require.main === module) {
+  const [,, step, json] = process.argv;
+  const run = STEPS[step];
+  if (!run) {
+    console.error(`Unknown step '${step}'. Steps: ${Object.keys(STEPS).join(', ')}`);
+    process.exit(2);
+  }
+  run(JSON.parse(json || '{}'))
+    .then(result => console.log(JSON.stringify(result)))
+    .catch(error => {
+      console.error(error.message);
+      process.exit(1);
+    });
+}
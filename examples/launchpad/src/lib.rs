@@ -0,0 +1,148 @@
+//! Example launchpad backend. Each route runs one stage of a launch against
+//! devnet: spec -> deploy -> presale -> unlock -> claims.
+
+pub mod toolchain;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub use toolchain::Toolchain;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub toolchain: Toolchain,
+}
+
+pub fn app(toolchain: Toolchain) -> Router {
+    Router::new()
+        .route("/launches", post(create_launch))
+        .route("/launches/:mint", get(launch_status))
+        .route("/launches/:mint/presale", post(presale_buy))
+        .route("/launches/:mint/presale/finalize", post(finalize_presale))
+        .route("/launches/:mint/unlock", post(unlock))
+        .route("/launches/:mint/allocations", post(allocate))
+        .route("/launches/:mint/claims", post(claim))
+        .with_state(AppState { toolchain })
+}
+
+pub struct AppError(anyhow::Error);
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": format!("{:#}", self.0) }));
+        (StatusCode::UNPROCESSABLE_ENTITY, body).into_response()
+    }
+}
+
+impl<E: Into<anyhow::Error>> From<E> for AppError {
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+
+type ApiResult = Result<Json<Value>, AppError>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLaunch {
+    /// SolD source; must target DEVNET
+    pub spec: String,
+    /// Optional whitelist presale, passed through to the deploy step
+    pub presale: Option<Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PresaleBuy {
+    pub buyer: String,
+    pub amount: String,
+}
+
+#[derive(Deserialize)]
+pub struct Unlock {
+    pub to: String,
+    pub amount: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocate {
+    pub beneficiary: String,
+    pub amount: String,
+    #[serde(default)]
+    pub cliff_secs: i64,
+    /// Zero makes an immediately claimable airdrop allocation
+    #[serde(default)]
+    pub vesting_secs: i64,
+}
+
+#[derive(Deserialize)]
+pub struct Claim {
+    pub beneficiary: String,
+}
+
+async fn create_launch(State(state): State<AppState>, Json(body): Json<CreateLaunch>) -> ApiResult {
+    let name = format!("spec-{}", std::time::UNIX_EPOCH.elapsed()?.as_millis());
+    let spec = state.toolchain.build_spec(&name, &body.spec).await?;
+    let deployed = state
+        .toolchain
+        .step("deploy", json!({ "spec": spec, "presale": body.presale }))
+        .await?;
+    Ok(Json(deployed))
+}
+
+async fn launch_status(State(state): State<AppState>, Path(mint): Path<String>) -> ApiResult {
+    Ok(Json(state.toolchain.step("status", json!({ "mint": mint })).await?))
+}
+
+async fn presale_buy(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    Json(body): Json<PresaleBuy>,
+) -> ApiResult {
+    let args = json!({ "mint": mint, "buyer": body.buyer, "amount": body.amount });
+    Ok(Json(state.toolchain.step("presale", args).await?))
+}
+
+async fn finalize_presale(State(state): State<AppState>, Path(mint): Path<String>) -> ApiResult {
+    Ok(Json(state.toolchain.step("finalize", json!({ "mint": mint })).await?))
+}
+
+async fn unlock(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    Json(body): Json<Unlock>,
+) -> ApiResult {
+    let args = json!({ "mint": mint, "to": body.to, "amount": body.amount });
+    Ok(Json(state.toolchain.step("unlock", args).await?))
+}
+
+async fn allocate(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    Json(body): Json<Allocate>,
+) -> ApiResult {
+    let args = json!({
+        "mint": mint,
+        "beneficiary": body.beneficiary,
+        "amount": body.amount,
+        "cliffSecs": body.cliff_secs,
+        "vestingSecs": body.vesting_secs,
+    });
+    Ok(Json(state.toolchain.step("allocate", args).await?))
+}
+
+async fn claim(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    Json(body): Json<Claim>,
+) -> ApiResult {
+    let args = json!({ "mint": mint, "beneficiary": body.beneficiary });
+    Ok(Json(state.toolchain.step("claim", args).await?))
+}
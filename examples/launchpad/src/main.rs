@@ -0,0 +1,17 @@
+use sold_launchpad_example::{app, Toolchain};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let toolchain = Toolchain::from_env();
+    if !toolchain.has_program_build() {
+        tracing::warn!("no program IDL under target/idl; run `anchor build` at the repo root first");
+    }
+
+    let addr = std::env::var("LAUNCHPAD_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    tracing::info!("launchpad listening on {addr} (rpc {})", toolchain.rpc_url);
+    axum::serve(listener, app(toolchain)).await?;
+    Ok(())
+}
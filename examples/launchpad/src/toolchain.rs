@@ -0,0 +1,96 @@
+//! Drives the SolD toolchain: the `sold` compiler for specs and the
+//! launchpad client steps, which talk to the program through the
+//! Anchor-generated TypeScript client.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use tokio::process::Command;
+
+#[derive(Clone, Debug)]
+pub struct Toolchain {
+    /// Repository root, holding `sold-parser.ts`, `seeds.js` and `target/`
+    pub repo_root: PathBuf,
+    /// Scratch space for compiled specs, wallets and launch records
+    pub work_dir: PathBuf,
+    pub rpc_url: String,
+}
+
+impl Toolchain {
+    /// Configure from `LAUNCHPAD_REPO`, `LAUNCHPAD_WORKDIR` and
+    /// `LAUNCHPAD_RPC`, defaulting to this checkout and devnet
+    pub fn from_env() -> Self {
+        let example_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let repo_root = std::env::var("LAUNCHPAD_REPO")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| example_dir.join("../.."));
+        let work_dir = std::env::var("LAUNCHPAD_WORKDIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| example_dir.join("work"));
+        let rpc_url = std::env::var("LAUNCHPAD_RPC")
+            .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
+        Self { repo_root, work_dir, rpc_url }
+    }
+
+    fn client_script(&self) -> PathBuf {
+        self.repo_root.join("examples/launchpad/client/launchpad.ts")
+    }
+
+    /// Compile a spec with `sold build`, returning the spec file the
+    /// deploy step reads. Fails if the spec does not validate.
+    pub async fn build_spec(&self, name: &str, spec: &str) -> Result<PathBuf> {
+        let spec_dir = self.work_dir.join("specs").join(name);
+        tokio::fs::create_dir_all(&spec_dir).await?;
+        let spec_file = spec_dir.join("launch.sold");
+        tokio::fs::write(&spec_file, spec).await?;
+
+        let output = Command::new("npx")
+            .arg("ts-node")
+            .arg(self.repo_root.join("sold-parser.ts"))
+            .arg("build")
+            .arg(&spec_file)
+            .arg(spec_dir.join("out"))
+            .arg("--idl")
+            .arg(self.repo_root.join("target/idl/sold_token_launch.json"))
+            .current_dir(&self.repo_root)
+            .output()
+            .await
+            .context("failed to run sold build")?;
+        if !output.status.success() {
+            bail!("sold build failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(spec_file)
+    }
+
+    /// Run one client step and return its JSON result
+    pub async fn step(&self, step: &str, args: Value) -> Result<Value> {
+        let output = Command::new("npx")
+            .arg("ts-node")
+            .arg(self.client_script())
+            .arg(step)
+            .arg(args.to_string())
+            .env("LAUNCHPAD_RPC", &self.rpc_url)
+            .env("LAUNCHPAD_WORKDIR", &self.work_dir)
+            .current_dir(self.repo_root.join("examples/launchpad/client"))
+            .output()
+            .await
+            .with_context(|| format!("failed to run client step {step}"))?;
+        if !output.status.success() {
+            bail!("{step} failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        last_json_line(&output.stdout).ok_or_else(|| anyhow!("{step} printed no result"))
+    }
+
+    pub fn has_program_build(&self) -> bool {
+        self.repo_root.join("target/idl/sold_token_launch.json").exists()
+    }
+}
+
+/// Client steps may log before their result; the result is the last line
+fn last_json_line(stdout: &[u8]) -> Option<Value> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line).ok())
+}
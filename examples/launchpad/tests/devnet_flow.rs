@@ -0,0 +1,87 @@
+//! Whole-toolchain regression: drives the launchpad routes through a full
+//! launch on devnet. Needs `anchor build` output at the repo root, the
+//! client's npm dependencies and devnet airdrops, and waits out the spec's
+//! 1 hour timelock, so it only runs when asked:
+//!
+//!     cd examples/launchpad && cargo test -- --ignored
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sold_launchpad_example::{app, Toolchain};
+use tower::ServiceExt;
+
+const SPEC: &str = include_str!("../launch.sold");
+
+async fn call(app: &Router, method: &str, uri: &str, body: Option<Value>) -> Value {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let value: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(status, StatusCode::OK, "{method} {uri}: {value}");
+    value
+}
+
+#[tokio::test]
+#[ignore = "runs against devnet and waits out the timelock"]
+async fn spec_to_claims_on_devnet() {
+    let toolchain = Toolchain::from_env();
+    assert!(toolchain.has_program_build(), "run `anchor build` at the repo root first");
+    let app = app(toolchain);
+
+    // Spec -> deploy, with a two-buyer whitelist presale
+    let launch = call(&app, "POST", "/launches", Some(json!({
+        "spec": SPEC,
+        "presale": {
+            "buyers": [
+                { "wallet": "alice", "allocation": "5000000000" },
+                { "wallet": "bob", "allocation": "2000000000" }
+            ],
+            "priceLamports": "1000",
+            "durationSecs": 120
+        }
+    })))
+    .await;
+    let mint = launch["mint"].as_str().unwrap().to_string();
+
+    // Presale, then close it once the sale window ends
+    call(&app, "POST", &format!("/launches/{mint}/presale"), Some(json!({ "buyer": "alice", "amount": "5000000000" }))).await;
+    call(&app, "POST", &format!("/launches/{mint}/presale"), Some(json!({ "buyer": "bob", "amount": "1000000000" }))).await;
+    tokio::time::sleep(Duration::from_secs(125)).await;
+    call(&app, "POST", &format!("/launches/{mint}/presale/finalize"), None).await;
+
+    let status = call(&app, "GET", &format!("/launches/{mint}"), None).await;
+    assert_eq!(status["presaleSold"], "6000000000");
+    assert_eq!(status["presaleFinalized"], true);
+
+    // Wait out the timelock
+    loop {
+        let status = call(&app, "GET", &format!("/launches/{mint}"), None).await;
+        let remaining = status["timelockEnd"].as_i64().unwrap() - status["now"].as_i64().unwrap();
+        if remaining < 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(remaining.clamp(5, 60) as u64)).await;
+    }
+
+    // Unlock -> allocations -> claims
+    call(&app, "POST", &format!("/launches/{mint}/unlock"), Some(json!({ "to": "treasury-ops", "amount": "1000000" }))).await;
+    call(&app, "POST", &format!("/launches/{mint}/allocations"), Some(json!({ "beneficiary": "carol", "amount": "3000000" }))).await;
+    let claimed = call(&app, "POST", &format!("/launches/{mint}/claims"), Some(json!({ "beneficiary": "carol" }))).await;
+    assert_eq!(claimed["balance"], "3000000");
+
+    let status = call(&app, "GET", &format!("/launches/{mint}"), None).await;
+    assert_eq!(status["unlockedSoFar"], "4000000");
+}
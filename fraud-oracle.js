@@ -0,0 +1,409 @@
+// SolD Fraud Oracle
+// Scores watched launches and submits update_fraud_score as a registered oracle
+
+const fs = require('fs');
+const axios = require('axios');
+const TOML = require('@iarna/toml');
+const { Connection, PublicKey, Keypair, ComputeBudgetProgram, SystemProgram } = require('@solana/web3.js');
+const { Program, AnchorProvider, Wallet, AnchorError, ProgramError } = require('@project-serum/anchor');
+const {
+    findLaunch, findOracleRegistry, findScoringModel, findCreatorProfile, findFraudScoreHistory, findTreasury
+} = require('./seeds');
+const { parseDuration } = require('./keeper');
+const { LaunchQuery, normalizeLaunch } = require('./queries');
+const { estimateComputeUnits, estimatePriorityFee, checkBudgetSetting } = require('./compute-budget');
+
+const PROGRAM_ID = 'So1DLaunchProgram11111111111111111111111111';
+const SOLANA_NETWORK = process.env.SOLANA_NETWORK || 'devnet';
+
+// Scores are basis points on-chain (MAX_FRAUD_SCORE_BPS in the program)
+const FRAUD_SCORE_BPS = 10000;
+const toBps = score => Math.round(Math.min(1, Math.max(0, score)) * FRAUD_SCORE_BPS);
+
+// Parameters alone never reach the program's auto-suspend threshold
+// (AUTO_SUSPEND_SCORE_BPS); that takes a model that sees wallet history
+const HEURISTIC_MAX_SCORE = 0.85;
+
+// The program suspends on the time-weighted score over FRAUD_SCORE_WINDOW
+// seconds, once the history spans FRAUD_SCORE_MIN_SPAN
+const AUTO_SUSPEND_SCORE_BPS = 9000;
+const FRAUD_SCORE_WINDOW = 6 * 3600;
+const FRAUD_SCORE_MIN_SPAN = 3600;
+
+const RETRY_BASE_MS = 1000;
+
+const DEFAULTS = {
+    interval: '5m',
+    min_delta_bps: 250,
+    launches: [],
+    submit: {
+        compute_unit_limit: 100000,
+        priority_fee_percentile: 75,
+        min_priority_fee: 1000,
+        max_priority_fee: 1000000,
+        max_retries: 4
+    }
+};
+
+// Scorers share one interface, so the oracle can use any of them:
+//   name                                  recorded in logs
+//   score(launch, { tokenMint, now })     -> { score (0-1), reasons: string[] }
+// launch is the decoded TokenLaunch account.
+
+// The AI engine's /analyze-launch, fed from the launch account
+class HttpScorer {
+    constructor({ url, timeout = '10s' }) {
+        if (!url) {
+            throw new Error('The http scorer needs a url');
+        }
+        this.name = `http(${url})`;
+        this.url = url.replace(/\/$/, '');
+        this.timeoutMs = parseDuration(timeout) * 1000;
+    }
+
+    async score(launch) {
+        const { data } = await axios.post(`${this.url}/analyze-launch`, {
+            creator_wallet: launch.creator.toBase58(),
+            insurance_wallets: launch.insuranceWallets.map(wallet => wallet.toBase58()),
+            supply: launch.totalSupply.toString(),
+            timelock_duration: launch.timelockDuration.toNumber(),
+            network: SOLANA_NETWORK.toUpperCase()
+        }, { timeout: this.timeoutMs });
+
+        if (typeof data.fraud_score !== 'number') {
+            throw new Error(`${this.url} returned no fraud_score`);
+        }
+        return { score: data.fraud_score, reasons: data.risk_factors || [] };
+    }
+}
+
+// Red flags in the launch's own parameters and history, weighted like the
+// AI engine's parameter analysis. Needs no service, but sees no wallet history.
+class HeuristicScorer {
+    constructor() {
+        this.name = 'heuristic';
+    }
+
+    async score(launch) {
+        const reasons = [];
+        let score = 0;
+        const flag = (weight, reason) => {
+            score += weight;
+            reasons.push(reason);
+        };
+
+        const timelockDays = launch.timelockDuration.toNumber() / 86400;
+        if (timelockDays < 100) {
+            flag(0.5, `timelock of ${timelockDays.toFixed(0)} days is below the 100 day minimum`);
+        } else if (timelockDays < 200) {
+            flag(0.2, `timelock of ${timelockDays.toFixed(0)} days is short`);
+        }
+
+        const insuranceCount = launch.insuranceWallets.length;
+        if (insuranceCount === 0) {
+            flag(0.4, 'no insurance wallets');
+        } else if (insuranceCount > 5) {
+            flag(0.3, `${insuranceCount} insurance wallets`);
+        }
+
+        if (launch.insuranceLimit > 30) {
+            flag(0.2, `insurance may withdraw ${launch.insuranceLimit}% of supply`);
+        }
+
+        // Share of the insurance allowance already withdrawn
+        const allowance = BigInt(launch.totalSupply.toString()) * BigInt(launch.insuranceLimit) / 100n;
+        const withdrawn = BigInt(launch.totalWithdrawn.toString());
+        if (allowance > 0n && withdrawn * 2n > allowance) {
+            flag(0.3, 'over half the insurance allowance withdrawn');
+        }
+
+        if (!launch.creatorMultisig) {
+            flag(0.1, 'creator is a single key');
+        }
+
+        return { score: Math.min(HEURISTIC_MAX_SCORE, score), reasons };
+    }
+}
+
+function createScorer(config = {}) {
+    switch (config.kind || 'http') {
+        case 'http':
+            return new HttpScorer(config);
+        case 'heuristic':
+            return new HeuristicScorer();
+        default:
+            throw new Error(`Unknown scorer kind: ${config.kind}`);
+    }
+}
+
+function loadOracleConfig(filePath) {
+    const raw = TOML.parse(fs.readFileSync(filePath, 'utf8'));
+    const config = {
+        ...DEFAULTS,
+        ...raw,
+        submit: { ...DEFAULTS.submit, ...(raw.submit || {}) }
+    };
+    if (!config.model || !Number.isInteger(config.model.version)) {
+        throw new Error(`${filePath}: [model] version must be set to a registered scoring model`);
+    }
+    if (config.model.hash && !/^[0-9a-f]{64}$/i.test(config.model.hash)) {
+        throw new Error(`${filePath}: [model] hash must be 32 bytes of hex`);
+    }
+    const { priority_fee_percentile: percentile, min_priority_fee: min, max_priority_fee: max } = config.submit;
+    if (percentile < 0 || percentile > 100) {
+        throw new Error(`${filePath}: priority_fee_percentile must be between 0 and 100`);
+    }
+    if (min > max) {
+        throw new Error(`${filePath}: min_priority_fee is above max_priority_fee`);
+    }
+    try {
+        checkBudgetSetting('compute_unit_limit', config.submit.compute_unit_limit);
+    } catch (error) {
+        throw new Error(`${filePath}: ${error.message}`);
+    }
+    return { ...config, interval: parseDuration(config.interval) };
+}
+
+// Program errors are the program refusing the update; retrying cannot help
+const isProgramError = error =>
+    error instanceof AnchorError || error instanceof ProgramError || Boolean(error.error?.errorCode);
+
+class FraudOracle {
+    constructor({ connection, program, oracle, scorer, config }) {
+        this.connection = connection;
+        this.program = program;
+        this.oracle = oracle;
+        this.scorer = scorer;
+        this.config = config;
+        this.modelHash = null;
+        this.timer = null;
+        this.running = false;
+    }
+
+    // Refuse to start unless the chain will accept this oracle's updates
+    async start() {
+        const [registry] = findOracleRegistry(this.program.programId);
+        const { oracles } = await this.program.account.oracleRegistry.fetch(registry);
+        if (!oracles.some(key => key.equals(this.oracle.publicKey))) {
+            throw new Error(`${this.oracle.publicKey.toBase58()} is not a registered fraud oracle`);
+        }
+
+        const version = this.config.model.version;
+        const [model] = findScoringModel(version, this.program.programId);
+        const scoringModel = await this.program.account.scoringModel.fetchNullable(model);
+        if (!scoringModel) {
+            throw new Error(`Scoring model v${version} is not registered`);
+        }
+        this.modelHash = Buffer.from(scoringModel.modelHash);
+        if (this.config.model.hash && this.config.model.hash.toLowerCase() !== this.modelHash.toString('hex')) {
+            throw new Error(`Scoring model v${version} is registered with a different hash`);
+        }
+
+        const round = () => {
+            if (this.running) return;
+            this.running = true;
+            this.runRound()
+                .catch(error => console.error('❌ Oracle round failed:', error.message))
+                .finally(() => { this.running = false; });
+        };
+        this.timer = setInterval(round, this.config.interval * 1000);
+        round();
+        console.log(`🔮 Oracle ${this.oracle.publicKey.toBase58()} scoring with ${this.scorer.name} (model v${version})`);
+    }
+
+    stop() {
+        clearInterval(this.timer);
+        this.timer = null;
+    }
+
+    // The configured launches, or every active one
+    async watchedLaunches() {
+        if (this.config.launches.length) {
+            const mints = this.config.launches.map(mint => new PublicKey(mint));
+            const addresses = mints.map(mint => findLaunch(mint, this.program.programId)[0]);
+            const accounts = await this.program.account.tokenLaunch.fetchMultiple(addresses);
+            return accounts.flatMap((launch, i) => {
+                if (!launch) {
+                    console.warn(`⚠️  No launch for ${mints[i].toBase58()}`);
+                    return [];
+                }
+                return [{ tokenMint: mints[i], tokenLaunch: addresses[i], launch: normalizeLaunch(this.program.coder, launch) }];
+            });
+        }
+        const launches = await new LaunchQuery(this.program).active().all();
+        return launches.map(({ publicKey, account }) => ({ tokenMint: account.tokenMint, tokenLaunch: publicKey, launch: account }));
+    }
+
+    async runRound(now = Math.floor(Date.now() / 1000)) {
+        const updates = [];
+        for (const entry of await this.watchedLaunches()) {
+            try {
+                const update = await this.scoreLaunch(entry, now);
+                if (update) updates.push(update);
+            } catch (error) {
+                console.error(`❌ Scoring ${entry.tokenMint.toBase58()} failed:`, error.message);
+            }
+        }
+        return updates;
+    }
+
+    // Submit a launch's new score when it moved enough, or when the launch
+    // was last scored by another model
+    async scoreLaunch(entry, now) {
+        const { tokenMint, tokenLaunch, launch } = entry;
+        const { score, reasons } = await this.scorer.score(launch, { tokenMint, now });
+        const scoreBps = toBps(score);
+        const sameModel = launch.scoreModelVersion === this.config.model.version;
+        if (sameModel && Math.abs(scoreBps - launch.fraudScore) < this.config.min_delta_bps) {
+            await this.suspendIfSustained(entry, now);
+            return null;
+        }
+
+        const signature = await this.submitScore(tokenMint, tokenLaunch, launch.creator, scoreBps);
+        console.log(`📝 ${tokenMint.toBase58()}: ${launch.fraudScore} -> ${scoreBps} bps (${signature})` +
+            (reasons.length ? `\n    ${reasons.join('; ')}` : ''));
+        return { tokenMint: tokenMint.toBase58(), oldScore: launch.fraudScore, newScore: scoreBps, reasons, signature };
+    }
+
+    // A score that stays high is not resubmitted, so no update re-checks
+    // it; once its time-weighted average passes the threshold, suspend the
+    // launch through crank_launch instead
+    async suspendIfSustained({ tokenMint, tokenLaunch, launch }, now) {
+        if (!launch.isActive) return null;
+        const [scoreHistory] = findFraudScoreHistory(tokenLaunch, this.program.programId);
+        const history = await this.program.account.fraudScoreHistory.fetchNullable(scoreHistory);
+        const smoothed = history && smoothedFraudScore(history, now);
+        if (smoothed === null || smoothed <= AUTO_SUSPEND_SCORE_BPS) return null;
+
+        const signature = await this.program.methods
+            .crankLaunch({ suspendOnScore: {} })
+            .accounts({
+                cranker: this.oracle.publicKey,
+                tokenLaunch,
+                tokenMint,
+                treasury: findTreasury(this.program.programId)[0],
+                proposal: null,
+                proposer: null,
+                appeal: null,
+                appellant: null,
+                scoreHistory
+            })
+            .rpc({ commitment: 'confirmed' });
+        console.log(`🛑 ${tokenMint.toBase58()}: suspended at a time-weighted ${smoothed} bps (${signature})`);
+        return signature;
+    }
+
+    // Priority fee for an attempt: a percentile of recent fees paid to write
+    // the launch account, doubled per retry, within the configured bounds
+    async priorityFee(tokenLaunch, attempt) {
+        const { priority_fee_percentile: p, min_priority_fee: min, max_priority_fee: max } = this.config.submit;
+        const base = Math.max(min, await estimatePriorityFee(this.connection, [tokenLaunch], p));
+        return Math.min(max, base * 2 ** attempt);
+    }
+
+    async submitScore(tokenMint, tokenLaunch, creator, scoreBps) {
+        const { compute_unit_limit: unitLimit, max_retries: maxRetries } = this.config.submit;
+        const version = this.config.model.version;
+        const [creatorProfile] = findCreatorProfile(creator, this.program.programId);
+        const hasProfile = (await this.connection.getAccountInfo(creatorProfile)) !== null;
+        const update = () => this.program.methods
+            .updateFraudScore(scoreBps, version, [...this.modelHash])
+            .accounts({
+                aiAuthority: this.oracle.publicKey,
+                oracleRegistry: findOracleRegistry(this.program.programId)[0],
+                scoringModel: findScoringModel(version, this.program.programId)[0],
+                tokenLaunch,
+                tokenMint,
+                creatorProfile: hasProfile ? creatorProfile : null,
+                scoreHistory: findFraudScoreHistory(tokenLaunch, this.program.programId)[0],
+                systemProgram: SystemProgram.programId
+            });
+        const units = unitLimit === 'auto'
+            ? await estimateComputeUnits(this.connection, this.oracle.publicKey, [await update().instruction()])
+            : unitLimit;
+
+        for (let attempt = 0; ; attempt++) {
+            const microLamports = await this.priorityFee(tokenLaunch, attempt);
+            try {
+                return await update()
+                    .preInstructions([
+                        ComputeBudgetProgram.setComputeUnitLimit({ units }),
+                        ComputeBudgetProgram.setComputeUnitPrice({ microLamports })
+                    ])
+                    .rpc({ commitment: 'confirmed' });
+            } catch (error) {
+                if (isProgramError(error) || attempt >= maxRetries) throw error;
+                const delay = RETRY_BASE_MS * 2 ** attempt;
+                console.warn(`⚠️  Update for ${tokenMint.toBase58()} failed (${error.message}); ` +
+                    `retrying in ${delay}ms at a higher priority fee`);
+                await new Promise(resolve => setTimeout(resolve, delay));
+            }
+        }
+    }
+}
+
+// Time-weighted score the program suspends on (FraudScoreHistory::
+// smoothed_score): each update weighted by how long it stood within the
+// last FRAUD_SCORE_WINDOW, or null until the history spans FRAUD_SCORE_MIN_SPAN
+function smoothedFraudScore({ samples, count, cursor }, now) {
+    const oldest = count < samples.length ? 0 : cursor;
+    const ordered = Array.from({ length: count }, (_, i) => samples[(oldest + i) % samples.length]);
+    const windowStart = now - FRAUD_SCORE_WINDOW;
+    let weighted = 0;
+    let span = 0;
+    ordered.forEach((sample, i) => {
+        const end = i + 1 < count ? Math.min(ordered[i + 1].timestamp.toNumber(), now) : now;
+        const start = Math.max(sample.timestamp.toNumber(), windowStart);
+        if (end > start) {
+            weighted += sample.score * (end - start);
+            span += end - start;
+        }
+    });
+    return span >= FRAUD_SCORE_MIN_SPAN ? Math.floor(weighted / span) : null;
+}
+
+function loadKeypair(filePath) {
+    const secret = JSON.parse(fs.readFileSync(filePath, 'utf8'));
+    return Keypair.fromSecretKey(Uint8Array.from(secret));
+}
+
+async function main() {
+    const configPath = process.argv[2] || process.env.ORACLE_CONFIG || 'oracle.toml';
+    const config = loadOracleConfig(configPath);
+
+    const keypairPath = process.env.ORACLE_KEYPAIR || config.keypair;
+    if (!keypairPath) {
+        throw new Error('No oracle keypair configured (keypair or ORACLE_KEYPAIR)');
+    }
+    const oracle = loadKeypair(keypairPath);
+
+    const connection = new Connection(
+        process.env.SOLANA_RPC_URL || (SOLANA_NETWORK === 'mainnet'
+            ? 'https://api.mainnet-beta.solana.com'
+            : 'https://api.devnet.solana.com'),
+        'confirmed'
+    );
+    const provider = new AnchorProvider(connection, new Wallet(oracle), { commitment: 'confirmed' });
+    const idl = JSON.parse(fs.readFileSync(process.env.PROGRAM_IDL || 'idl/sold_token_launch.json', 'utf8'));
+    const program = new Program(idl, new PublicKey(PROGRAM_ID), provider);
+
+    const scorer = createScorer(config.scorer);
+    const fraudOracle = new FraudOracle({ connection, program, oracle, scorer, config });
+    await fraudOracle.start();
+
+    const shutdown = () => {
+        fraudOracle.stop();
+        process.exit(0);
+    };
+    process.on('SIGTERM', shutdown);
+    process.on('SIGINT', shutdown);
+}
+
+if (require.main === module) {
+    main().catch(error => {
+        console.error('❌ Oracle failed to start:', error.message);
+        process.exit(1);
+    });
+}
+
+module.exports = { FraudOracle, HttpScorer, HeuristicScorer, createScorer, loadOracleConfig, smoothedFraudScore };
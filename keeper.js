@@ -0,0 +1,317 @@
+// SolD Keeper - Alerting Rules Engine
+// Evaluates declarative alert rules (TOML) against the program event stream
+
+const fs = require('fs');
+const crypto = require('crypto');
+const axios = require('axios');
+const TOML = require('@iarna/toml');
+const { Connection, PublicKey, Keypair, Transaction } = require('@solana/web3.js');
+const { Program, AnchorProvider, Wallet } = require('@project-serum/anchor');
+const { findConfig, findLaunch, findLaunchMetadata } = require('./seeds');
+const { computeBudgetInstructions, checkBudgetSetting } = require('./compute-budget');
+const { normalizeLaunch } = require('./queries');
+
+const PROGRAM_ID = 'So1DLaunchProgram11111111111111111111111111';
+const SOLANA_NETWORK = process.env.SOLANA_NETWORK || 'devnet';
+
+const DURATION_UNITS = { s: 1, m: 60, h: 3600, d: 86400 };
+
+const OPERATORS = {
+    '>': (a, b) => a > b,
+    '>=': (a, b) => a >= b,
+    '<': (a, b) => a < b,
+    '<=': (a, b) => a <= b,
+    '==': (a, b) => a === b
+};
+
+const ACTIONS = ['notify', 'pause'];
+
+// How long a launch's on-chain webhook registration is trusted before refetching
+const WEBHOOK_CACHE_SECS = 60;
+const ZERO_HASH = Buffer.alloc(32);
+
+const sha256 = value => crypto.createHash('sha256').update(value).digest();
+
+// On-chain fraud scores are basis points; rules compare fractions (0-1)
+const FRAUD_SCORE_BPS = 10000;
+const toScore = bps => bps / FRAUD_SCORE_BPS;
+
+// "90s", "30m", "1h", "7d" -> seconds
+function parseDuration(value) {
+    const match = /^(\d+)([smhd])$/.exec(String(value).trim());
+    if (!match) {
+        throw new Error(`Invalid duration: ${value}`);
+    }
+    return parseInt(match[1], 10) * DURATION_UNITS[match[2]];
+}
+
+// Metrics a rule can compare against its threshold. Each receives the
+// event, the per-mint history of matching events inside the rule's window,
+// and the keeper (for account lookups).
+const METRICS = {
+    // Latest fraud score
+    score: (event) => toScore(event.newScore),
+
+    // Rise of the fraud score over the window, from its lowest point
+    score_delta: (event, history) => {
+        const scores = history
+            .flatMap(h => [h.event.oldScore, h.event.newScore])
+            .concat(event.oldScore);
+        return toScore(event.newScore - Math.min(...scores));
+    },
+
+    // Withdrawn amount as a fraction of the launch's total insurance limit
+    withdrawal_limit_pct: async (event, history, keeper) => {
+        const launch = await keeper.fetchLaunch(event.tokenMint);
+        const limit = launch.totalSupply.toNumber() * launch.insuranceLimit / 100;
+        return limit === 0 ? 0 : event.amount.toNumber() / limit;
+    },
+
+    // Number of matching events for the mint inside the window
+    event_count: (event, history) => history.length + 1
+};
+
+function validateRule(rule) {
+    const required = ['name', 'event', 'metric', 'op', 'threshold', 'actions'];
+    for (const key of required) {
+        if (rule[key] === undefined) {
+            throw new Error(`Rule ${rule.name || '<unnamed>'} is missing "${key}"`);
+        }
+    }
+    if (!METRICS[rule.metric]) {
+        throw new Error(`Rule ${rule.name}: unknown metric "${rule.metric}"`);
+    }
+    if (!OPERATORS[rule.op]) {
+        throw new Error(`Rule ${rule.name}: unknown operator "${rule.op}"`);
+    }
+    for (const action of rule.actions) {
+        if (!ACTIONS.includes(action)) {
+            throw new Error(`Rule ${rule.name}: unknown action "${action}"`);
+        }
+    }
+
+    return {
+        ...rule,
+        window: rule.window ? parseDuration(rule.window) : 0,
+        cooldown: rule.cooldown ? parseDuration(rule.cooldown) : 0
+    };
+}
+
+function loadRules(filePath) {
+    const config = TOML.parse(fs.readFileSync(filePath, 'utf8'));
+    const rules = (config.rules || []).map(validateRule);
+    if (rules.length === 0) {
+        throw new Error(`No [[rules]] defined in ${filePath}`);
+    }
+    // Pauses matter most during congestion, so they pay for priority
+    const submit = { compute_unit_limit: 'auto', priority_fee: 'auto', priority_fee_percentile: 90, ...config.submit };
+    checkBudgetSetting('[submit] compute_unit_limit', submit.compute_unit_limit);
+    checkBudgetSetting('[submit] priority_fee', submit.priority_fee);
+    return { rules, notify: config.notify || {}, guardian: config.guardian || {}, submit };
+}
+
+class AlertKeeper {
+    constructor({ connection, program, rules, notify = {}, guardian = null, submit = {} }) {
+        this.connection = connection;
+        this.program = program;
+        this.rules = rules;
+        this.notify = notify;
+        this.guardian = guardian;
+        this.submit = submit;
+        this.listeners = [];
+        this.history = new Map();   // `${rule}:${mint}` -> [{ at, event }]
+        this.lastFired = new Map(); // `${rule}:${mint}` -> unix seconds
+        this.webhooks = new Map();  // mint -> { at, url }
+        // Private endpoints launches may register by hash: sha256(url) hex -> url
+        this.endpointsByHash = new Map(
+            Object.values(notify.endpoints || {}).map(url => [sha256(url).toString('hex'), url])
+        );
+    }
+
+    start() {
+        const events = [...new Set(this.rules.map(rule => rule.event))];
+        for (const name of events) {
+            const id = this.program.addEventListener(name, (event, slot) => {
+                this.handleEvent(name, event, slot).catch(error =>
+                    console.error(`❌ Failed to evaluate ${name}:`, error.message)
+                );
+            });
+            this.listeners.push(id);
+        }
+        console.log(`👀 Keeper watching ${events.join(', ')} with ${this.rules.length} rules`);
+    }
+
+    async stop() {
+        for (const id of this.listeners) {
+            await this.program.removeEventListener(id);
+        }
+        this.listeners = [];
+    }
+
+    async handleEvent(name, event, slot, now = Math.floor(Date.now() / 1000)) {
+        const fired = [];
+        for (const rule of this.rules.filter(r => r.event === name)) {
+            const key = `${rule.name}:${event.tokenMint.toBase58()}`;
+            const history = (this.history.get(key) || []).filter(h => now - h.at <= rule.window);
+
+            const value = await METRICS[rule.metric](event, history, this);
+            history.push({ at: now, event });
+            this.history.set(key, history);
+
+            if (!OPERATORS[rule.op](value, rule.threshold)) continue;
+            if (now - (this.lastFired.get(key) || 0) < rule.cooldown) continue;
+            this.lastFired.set(key, now);
+
+            const alert = {
+                rule: rule.name,
+                event: name,
+                tokenMint: event.tokenMint.toBase58(),
+                metric: rule.metric,
+                value,
+                threshold: rule.threshold,
+                slot,
+                timestamp: new Date(now * 1000).toISOString()
+            };
+            await this.runActions(rule, alert);
+            fired.push(alert);
+        }
+        return fired;
+    }
+
+    async runActions(rule, alert) {
+        for (const action of rule.actions) {
+            try {
+                if (action === 'notify') {
+                    await this.sendNotification(alert);
+                } else if (action === 'pause') {
+                    await this.pauseLaunch(alert);
+                }
+            } catch (error) {
+                console.error(`❌ Rule ${rule.name} action ${action} failed:`, error.message);
+            }
+        }
+    }
+
+    async sendNotification(alert) {
+        const message = `🚨 [${alert.rule}] ${alert.tokenMint}: ${alert.metric} = ` +
+            `${alert.value.toFixed(4)} (threshold ${alert.threshold})`;
+        console.warn(message);
+
+        if (this.notify.webhook_url) {
+            await axios.post(this.notify.webhook_url, { text: message, alert });
+        }
+
+        const launchWebhook = await this.launchWebhook(alert.tokenMint);
+        if (launchWebhook && launchWebhook !== this.notify.webhook_url) {
+            await axios.post(launchWebhook, { text: message, alert });
+        }
+    }
+
+    // Where the launch's creator asked for callbacks (LaunchMetadata), if
+    // anywhere: its public URI, or a registered endpoint matching its hash
+    async launchWebhook(tokenMint, now = Math.floor(Date.now() / 1000)) {
+        const cached = this.webhooks.get(tokenMint);
+        if (cached && now - cached.at < WEBHOOK_CACHE_SECS) {
+            return cached.url;
+        }
+
+        const [address] = findLaunchMetadata(new PublicKey(tokenMint), this.program.programId);
+        const metadata = await this.program.account.launchMetadata.fetchNullable(address);
+        let url = null;
+        if (metadata && metadata.webhookUri) {
+            url = metadata.webhookUri;
+        } else if (metadata && !Buffer.from(metadata.webhookHash).equals(ZERO_HASH)) {
+            url = this.endpointsByHash.get(Buffer.from(metadata.webhookHash).toString('hex')) || null;
+            if (!url) {
+                console.warn(`⚠️  ${tokenMint} registered an unknown webhook hash`);
+            }
+        }
+
+        this.webhooks.set(tokenMint, { at: now, url });
+        return url;
+    }
+
+    // Suspend the launch on-chain. The guardian key must be the configured
+    // escrow authority, which is the only signer suspend_launch accepts
+    // unless the config has an arbiter panel (then arbiters must vote).
+    async pauseLaunch(alert) {
+        if (!this.guardian) {
+            throw new Error('No guardian key configured; cannot auto-pause');
+        }
+
+        const tokenMint = new PublicKey(alert.tokenMint);
+        const [tokenLaunch] = findLaunch(tokenMint, this.program.programId);
+        const [config] = findConfig(this.program.programId);
+
+        const instruction = await this.program.methods
+            .suspendLaunch(`keeper rule: ${alert.rule}`)
+            .accounts({
+                authority: this.guardian.publicKey,
+                config,
+                tokenLaunch,
+                tokenMint,
+                arbitration: null
+            })
+            .instruction();
+        const budget = await computeBudgetInstructions(this.connection, this.guardian.publicKey, [instruction], {
+            units: this.submit.compute_unit_limit,
+            microLamports: this.submit.priority_fee,
+            percentile: this.submit.priority_fee_percentile
+        });
+        const signature = await this.program.provider.sendAndConfirm(
+            new Transaction().add(...budget, instruction), [this.guardian]);
+
+        console.warn(`⏸️  Suspended ${alert.tokenMint} (${signature})`);
+    }
+
+    async fetchLaunch(tokenMint) {
+        const [tokenLaunch] = findLaunch(tokenMint, this.program.programId);
+        return normalizeLaunch(this.program.coder, await this.program.account.tokenLaunch.fetch(tokenLaunch));
+    }
+}
+
+function loadKeypair(filePath) {
+    const secret = JSON.parse(fs.readFileSync(filePath, 'utf8'));
+    return Keypair.fromSecretKey(Uint8Array.from(secret));
+}
+
+async function main() {
+    const rulesPath = process.argv[2] || process.env.KEEPER_RULES || 'keeper-rules.toml';
+    const { rules, notify, guardian, submit } = loadRules(rulesPath);
+
+    const keypairPath = process.env.GUARDIAN_KEYPAIR || guardian.keypair;
+    const guardianKey = keypairPath ? loadKeypair(keypairPath) : null;
+    if (!guardianKey && rules.some(rule => rule.actions.includes('pause'))) {
+        console.warn('⚠️  Rules use "pause" but no guardian keypair is configured');
+    }
+
+    const connection = new Connection(
+        SOLANA_NETWORK === 'mainnet'
+            ? 'https://api.mainnet-beta.solana.com'
+            : 'https://api.devnet.solana.com',
+        'confirmed'
+    );
+    const wallet = new Wallet(guardianKey || Keypair.generate());
+    const provider = new AnchorProvider(connection, wallet, {});
+    const idl = JSON.parse(fs.readFileSync(process.env.PROGRAM_IDL || 'idl/sold_token_launch.json', 'utf8'));
+    const program = new Program(idl, new PublicKey(PROGRAM_ID), provider);
+
+    const keeper = new AlertKeeper({ connection, program, rules, notify, guardian: guardianKey, submit });
+    keeper.start();
+
+    const shutdown = async () => {
+        await keeper.stop();
+        process.exit(0);
+    };
+    process.on('SIGTERM', shutdown);
+    process.on('SIGINT', shutdown);
+}
+
+if (require.main === module) {
+    main().catch(error => {
+        console.error('❌ Keeper failed to start:', error.message);
+        process.exit(1);
+    });
+}
+
+module.exports = { AlertKeeper, loadRules, validateRule, parseDuration, METRICS };
@@ -0,0 +1,430 @@
+// SolD Launch Indexer
+// Mirrors on-chain TokenLaunch accounts into Postgres for discovery queries
+
+const { Pool } = require('pg');
+const { normalizeLaunch } = require('./queries');
+
+const SCHEMA = `
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE TABLE IF NOT EXISTS launches (
+    launch_pda      TEXT PRIMARY KEY,
+    token_mint      TEXT NOT NULL,
+    creator         TEXT NOT NULL,
+    token_name      TEXT NOT NULL,
+    token_symbol    TEXT NOT NULL,
+    total_supply    NUMERIC NOT NULL,
+    timelock_end    BIGINT NOT NULL,
+    fraud_score     REAL NOT NULL,
+    is_active       BOOLEAN NOT NULL,
+    metadata_uri    TEXT,
+    slot            BIGINT,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS launches_name_trgm ON launches USING GIN (token_name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS launches_symbol_trgm ON launches USING GIN (token_symbol gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS launches_creator_trgm ON launches USING GIN (creator gin_trgm_ops);
+
+CREATE TABLE IF NOT EXISTS risk_history (
+    launch_pda      TEXT NOT NULL REFERENCES launches (launch_pda),
+    fraud_score     REAL NOT NULL,
+    is_active       BOOLEAN,
+    slot            BIGINT,
+    signature       TEXT,
+    recorded_at     TIMESTAMPTZ NOT NULL,
+    UNIQUE (launch_pda, signature)
+);
+
+CREATE INDEX IF NOT EXISTS risk_history_launch_time ON risk_history (launch_pda, recorded_at);
+
+ALTER TABLE risk_history ADD COLUMN IF NOT EXISTS model_version INTEGER;
+
+-- gRPC ingestion writes confirmed updates first and flips this once their
+-- slot finalizes; WS and backfill rows are treated as final
+ALTER TABLE launches ADD COLUMN IF NOT EXISTS finalized BOOLEAN NOT NULL DEFAULT true;
+`;
+
+// Log lines emitted by update_fraud_score and batch_update_fraud_scores.
+// Scores are basis points on-chain ("... -> 7500 bps"); launches scored
+// before the migration logged fractions ("... -> 0.75"). Updates since
+// model registration also name the scoring model ("... bps (model v3)").
+const SCORE_LOG = /Fraud score (?:for (\w+) )?updated: [\d.]+ -> ([\d.]+)( bps)?(?: \(model v(\d+)\))?/;
+const FRAUD_SCORE_BPS = 10000;
+
+// Stored scores stay fractions (0-1) so existing API consumers are unaffected
+const scoreFromBps = bps => bps / FRAUD_SCORE_BPS;
+
+const MAX_SEARCH_RESULTS = 50;
+
+// Yellowstone slot status for finalized slots (CommitmentLevel in the geyser proto)
+const SLOT_FINALIZED = 2;
+
+const GRPC_PING_INTERVAL_MS = 15000;
+// Treat a silent stream as dropped; slots arrive every ~400ms
+const GRPC_STALL_MS = 30000;
+const GRPC_MAX_RETRY_MS = 30000;
+
+class LaunchIndexer {
+    // ingestion: 'ws' (RPC websocket subscription) or 'grpc' (Yellowstone
+    // Geyser plugin at grpcEndpoint, authenticated with grpcToken)
+    constructor({ connection, program, databaseUrl, ingestion = 'ws', grpcEndpoint, grpcToken }) {
+        if (ingestion !== 'ws' && ingestion !== 'grpc') {
+            throw new Error(`Unknown indexer ingestion mode: ${ingestion}`);
+        }
+        if (ingestion === 'grpc' && !grpcEndpoint) {
+            throw new Error('gRPC ingestion requires grpcEndpoint');
+        }
+        this.connection = connection;
+        this.program = program;
+        this.pool = new Pool({ connectionString: databaseUrl });
+        this.ingestion = ingestion;
+        this.grpcEndpoint = grpcEndpoint;
+        this.grpcToken = grpcToken;
+        this.subscriptionId = null;
+        this.grpc = null;
+        this.stopped = false;
+        // Launch PDAs written per confirmed slot, awaiting finalization
+        this.pendingSlots = new Map();
+    }
+
+    async migrate() {
+        await this.pool.query(SCHEMA);
+    }
+
+    // Load every existing launch, then follow account changes
+    async start() {
+        await this.migrate();
+        await this.backfill();
+
+        if (this.ingestion === 'grpc') {
+            await this.startGrpc();
+            console.log('✅ Launch indexer running (Yellowstone gRPC)');
+            return;
+        }
+
+        this.subscriptionId = this.connection.onProgramAccountChange(
+            this.program.programId,
+            async ({ accountId, accountInfo }, { slot }) => {
+                try {
+                    const launch = this.decodeLaunch(accountInfo.data);
+                    await this.upsertLaunch(accountId.toBase58(), launch, slot);
+                } catch (error) {
+                    // Not a TokenLaunch account
+                }
+            },
+            'confirmed'
+        );
+
+        console.log('✅ Launch indexer running');
+    }
+
+    async stop() {
+        this.stopped = true;
+        this.closeGrpc();
+        if (this.subscriptionId !== null) {
+            await this.connection.removeProgramAccountChangeListener(this.subscriptionId);
+        }
+        await this.pool.end();
+    }
+
+    async backfill() {
+        const launches = await this.program.account.tokenLaunch.all();
+        for (const { publicKey, account } of launches) {
+            await this.upsertLaunch(publicKey.toBase58(), normalizeLaunch(this.program.coder, account), null);
+        }
+        console.log(`📚 Indexed ${launches.length} launches`);
+    }
+
+    // Stream launch account writes at confirmed commitment alongside slot
+    // status updates. Rows land as unfinalized and are settled when their
+    // slot finalizes; any reconnect backfills from RPC to cover the gap.
+    async startGrpc() {
+        const { default: Client, CommitmentLevel } = require('@triton-one/yellowstone-grpc');
+        const client = new Client(this.grpcEndpoint, this.grpcToken, undefined);
+        if (client.connect) await client.connect();
+        const stream = await client.subscribe();
+
+        let lastMessageAt = Date.now();
+        const grpc = { stream, timers: [] };
+        this.grpc = grpc;
+
+        stream.on('data', data => {
+            lastMessageAt = Date.now();
+            this.handleGrpcMessage(data).catch(error =>
+                console.error('gRPC message handling failed:', error.message)
+            );
+        });
+        const dropped = reason => {
+            if (this.grpc !== grpc) return;
+            this.closeGrpc();
+            if (!this.stopped) this.reconnectGrpc(reason);
+        };
+        stream.on('error', error => dropped(error.message));
+        stream.on('end', () => dropped('stream ended'));
+
+        await new Promise((resolve, reject) => {
+            stream.write({
+                accounts: {
+                    launches: { account: [], owner: [this.program.programId.toBase58()], filters: [] }
+                },
+                slots: { slots: { filterByCommitment: false } },
+                transactions: {},
+                transactionsStatus: {},
+                blocks: {},
+                blocksMeta: {},
+                entry: {},
+                accountsDataSlice: [],
+                commitment: CommitmentLevel.CONFIRMED
+            }, error => (error ? reject(error) : resolve()));
+        });
+
+        grpc.timers.push(setInterval(() => {
+            stream.write({ ping: { id: 1 } }, () => {});
+            if (Date.now() - lastMessageAt > GRPC_STALL_MS) dropped('stream stalled');
+        }, GRPC_PING_INTERVAL_MS));
+    }
+
+    closeGrpc() {
+        if (!this.grpc) return;
+        this.grpc.timers.forEach(clearInterval);
+        this.grpc.stream.end();
+        this.grpc = null;
+    }
+
+    async reconnectGrpc(reason, attempt = 0) {
+        const delay = Math.min(1000 * 2 ** attempt, GRPC_MAX_RETRY_MS);
+        console.warn(`⚠️ gRPC stream dropped (${reason}); reconnecting in ${delay}ms`);
+        await new Promise(resolve => setTimeout(resolve, delay));
+        if (this.stopped) return;
+        try {
+            await this.startGrpc();
+            // Updates during the outage were never streamed; reload from RPC
+            await this.backfill();
+            await this.settlePendingSlots(Infinity);
+        } catch (error) {
+            this.closeGrpc();
+            return this.reconnectGrpc(error.message, attempt + 1);
+        }
+    }
+
+    async handleGrpcMessage(data) {
+        if (data.account) {
+            const { account, slot } = data.account;
+            let launch;
+            try {
+                launch = this.decodeLaunch(Buffer.from(account.data));
+            } catch (error) {
+                return; // Not a TokenLaunch account
+            }
+            const { PublicKey } = require('@solana/web3.js');
+            const launchPda = new PublicKey(account.pubkey).toBase58();
+            const slotNumber = Number(slot);
+            await this.upsertLaunch(launchPda, launch, slotNumber, { finalized: false });
+            if (!this.pendingSlots.has(slotNumber)) this.pendingSlots.set(slotNumber, new Set());
+            this.pendingSlots.get(slotNumber).add(launchPda);
+        } else if (data.slot && data.slot.status === SLOT_FINALIZED) {
+            await this.settlePendingSlots(Number(data.slot.slot));
+        }
+    }
+
+    // Settle confirmed writes at or below a finalized slot. Writes in the
+    // finalized slot itself are final; older pending slots were skipped by
+    // the finalized fork, so those launches are re-read at finalized.
+    async settlePendingSlots(finalizedSlot) {
+        const refetch = new Set();
+        for (const [slot, launches] of this.pendingSlots) {
+            if (slot > finalizedSlot) continue;
+            this.pendingSlots.delete(slot);
+            if (slot === finalizedSlot) {
+                await this.pool.query(
+                    'UPDATE launches SET finalized = true WHERE launch_pda = ANY($1) AND slot = $2',
+                    [[...launches], slot]
+                );
+            } else {
+                launches.forEach(launchPda => refetch.add(launchPda));
+            }
+        }
+        await this.refetchFinalized([...refetch]);
+    }
+
+    async refetchFinalized(launchPdas) {
+        if (!launchPdas.length) return;
+        const { PublicKey } = require('@solana/web3.js');
+        const keys = launchPdas.map(launchPda => new PublicKey(launchPda));
+        const { context, value } = await this.connection.getMultipleAccountsInfoAndContext(keys, 'finalized');
+        for (let i = 0; i < keys.length; i++) {
+            if (!value[i]) continue;
+            const launch = this.decodeLaunch(value[i].data);
+            await this.upsertLaunch(launchPdas[i], launch, context.slot);
+        }
+    }
+
+    decodeLaunch(data) {
+        return normalizeLaunch(this.program.coder, this.program.coder.accounts.decode('TokenLaunch', data));
+    }
+
+    async upsertLaunch(launchPda, launch, slot, { finalized = true } = {}) {
+        const { rows: previous } = await this.pool.query(
+            'SELECT fraud_score, is_active FROM launches WHERE launch_pda = $1',
+            [launchPda]
+        );
+
+        await this.pool.query(
+            `INSERT INTO launches (launch_pda, token_mint, creator, token_name, token_symbol,
+                                   total_supply, timelock_end, fraud_score, is_active, metadata_uri, slot, finalized)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             ON CONFLICT (launch_pda) DO UPDATE SET
+                 creator = EXCLUDED.creator,
+                 token_name = EXCLUDED.token_name,
+                 token_symbol = EXCLUDED.token_symbol,
+                 timelock_end = EXCLUDED.timelock_end,
+                 fraud_score = EXCLUDED.fraud_score,
+                 is_active = EXCLUDED.is_active,
+                 metadata_uri = EXCLUDED.metadata_uri,
+                 slot = EXCLUDED.slot,
+                 finalized = EXCLUDED.finalized,
+                 updated_at = now()
+             WHERE launches.slot IS NULL OR EXCLUDED.slot IS NULL OR EXCLUDED.slot >= launches.slot`,
+            [
+                launchPda,
+                launch.tokenMint.toBase58(),
+                launch.creator.toBase58(),
+                launch.tokenName,
+                launch.tokenSymbol,
+                launch.totalSupply.toString(),
+                launch.timelockEnd.toNumber(),
+                scoreFromBps(launch.fraudScore),
+                launch.isActive,
+                launch.metadataUri,
+                slot,
+                finalized
+            ]
+        );
+
+        // Record a risk history point whenever the score or status changes
+        const changed = !previous.length
+            || Math.abs(previous[0].fraud_score - scoreFromBps(launch.fraudScore)) > 1e-6
+            || previous[0].is_active !== launch.isActive;
+        if (changed) {
+            await this.recordRisk(
+                launchPda,
+                scoreFromBps(launch.fraudScore),
+                launch.isActive,
+                slot,
+                null,
+                new Date(),
+                launch.scoreModelVersion || null
+            );
+        }
+    }
+
+    async recordRisk(launchPda, fraudScore, isActive, slot, signature, recordedAt, modelVersion = null) {
+        await this.pool.query(
+            `INSERT INTO risk_history (launch_pda, fraud_score, is_active, slot, signature, recorded_at, model_version)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (launch_pda, signature) DO NOTHING`,
+            [launchPda, fraudScore, isActive, slot, signature, recordedAt, modelVersion]
+        );
+    }
+
+    // Rebuild score history from the launch's transaction logs, for launches
+    // whose updates predate the indexer
+    async backfillRiskHistory(launchPda, { maxSignatures = 1000 } = {}) {
+        const { PublicKey } = require('@solana/web3.js');
+        const signatures = await this.connection.getSignaturesForAddress(
+            new PublicKey(launchPda),
+            { limit: maxSignatures }
+        );
+
+        const { rows } = await this.pool.query('SELECT token_mint FROM launches WHERE launch_pda = $1', [launchPda]);
+        const tokenMint = rows[0]?.token_mint;
+        let recorded = 0;
+
+        for (const { signature, slot, blockTime, err } of signatures) {
+            if (err) continue;
+            const tx = await this.connection.getTransaction(signature, { maxSupportedTransactionVersion: 0 });
+            for (const line of tx?.meta?.logMessages || []) {
+                const match = line.match(SCORE_LOG);
+                // Batch updates name the mint; skip lines for other launches
+                if (!match || (match[1] && match[1] !== tokenMint)) continue;
+                await this.recordRisk(
+                    launchPda,
+                    match[3] ? scoreFromBps(parseInt(match[2], 10)) : parseFloat(match[2]),
+                    null,
+                    slot,
+                    signature,
+                    new Date((blockTime || 0) * 1000),
+                    match[4] ? parseInt(match[4], 10) : null
+                );
+                recorded++;
+            }
+        }
+        return recorded;
+    }
+
+    async riskHistory(launchPda, { from, to } = {}) {
+        const { rows } = await this.pool.query(
+            `SELECT fraud_score, is_active, slot, signature, recorded_at, model_version
+             FROM risk_history
+             WHERE launch_pda = $1
+               AND ($2::timestamptz IS NULL OR recorded_at >= $2)
+               AND ($3::timestamptz IS NULL OR recorded_at <= $3)
+             ORDER BY recorded_at, slot`,
+            [launchPda, from || null, to || null]
+        );
+
+        return rows.map(row => ({
+            timestamp: row.recorded_at.toISOString(),
+            fraudScore: row.fraud_score,
+            isActive: row.is_active,
+            slot: row.slot === null ? null : Number(row.slot),
+            signature: row.signature,
+            modelVersion: row.model_version
+        }));
+    }
+
+    // Fuzzy search across name, symbol and creator, best matches first.
+    // Exact symbol and creator matches always rank above fuzzy ones.
+    async search(query, { limit = 20, activeOnly = false } = {}) {
+        const { rows } = await this.pool.query(
+            `SELECT launch_pda, token_mint, creator, token_name, token_symbol,
+                    total_supply, timelock_end, fraud_score, is_active, metadata_uri,
+                    GREATEST(similarity(token_name, $1),
+                             similarity(token_symbol, $1),
+                             similarity(creator, $1),
+                             CASE WHEN upper(token_symbol) = upper($1) OR creator = $1 THEN 1 ELSE 0 END) AS rank
+             FROM launches
+             WHERE (token_name % $1 OR token_symbol % $1 OR creator % $1
+                    OR token_name ILIKE '%' || $1 || '%' OR upper(token_symbol) = upper($1) OR creator = $1)
+               AND ($3::boolean = false OR is_active)
+             ORDER BY rank DESC, token_name
+             LIMIT $2`,
+            [query, Math.min(limit, MAX_SEARCH_RESULTS), activeOnly]
+        );
+
+        return rows.map(row => ({
+            launchPDA: row.launch_pda,
+            tokenMint: row.token_mint,
+            creator: row.creator,
+            name: row.token_name,
+            symbol: row.token_symbol,
+            totalSupply: row.total_supply,
+            timelockEnd: Number(row.timelock_end),
+            fraudScore: row.fraud_score,
+            isActive: row.is_active,
+            metadataUri: row.metadata_uri,
+            rank: Number(row.rank)
+        }));
+    }
+}
+
+function riskHistoryToCsv(points) {
+    const header = 'timestamp,fraud_score,is_active,slot,signature';
+    const lines = points.map(p =>
+        [p.timestamp, p.fraudScore, p.isActive ?? '', p.slot ?? '', p.signature ?? ''].join(',')
+    );
+    return [header, ...lines].join('\n') + '\n';
+}
+
+module.exports = { LaunchIndexer, riskHistoryToCsv };
@@ -0,0 +1,343 @@
+// SolD Notifier
+// Posts program events to webhooks as they land, selected per webhook by a
+// small filter language
+
+const fs = require('fs');
+const crypto = require('crypto');
+const axios = require('axios');
+const TOML = require('@iarna/toml');
+const { Connection, PublicKey, Keypair } = require('@solana/web3.js');
+const { Program, AnchorProvider, Wallet } = require('@project-serum/anchor');
+const { EventIndexer, createEventStore } = require('./event-indexer');
+const { parseDuration } = require('./keeper');
+
+const PROGRAM_ID = 'So1DLaunchProgram11111111111111111111111111';
+const SOLANA_NETWORK = process.env.SOLANA_NETWORK || 'devnet';
+
+const RETRY_BASE_MS = 1000;
+// Recently delivered transactions, remembered to drop duplicates and to
+// retract deliveries whose transaction a fork abandons
+const DELIVERY_MEMORY = 10000;
+
+const DEFAULTS = {
+    ingestion: 'ws',
+    store: 'sqlite:notifier.db',
+    timeout: '10s',
+    max_retries: 3,
+    webhooks: []
+};
+
+// Filter language. A filter is a boolean expression over one event:
+//
+//   event in (LaunchSuspended, FraudScoreUpdated) and mint == 7xKX...
+//   event == EmergencyWithdrawal and (amount >= 1000000 or insurance_wallet == 9aB...)
+//   not event == TokensTransferred
+//
+// Fields are `event` (its name), `mint`, `slot`, or any field of the event
+// itself, in snake_case or camelCase. Operators are == != > >= < <= and
+// `in (a, b, ...)`, combined with and / or / not and parentheses. Values are
+// bare words, numbers or quoted strings. Ordering compares numbers only, and
+// a comparison on a field the event lacks is false.
+const TOKEN = /\s*(?:(\(|\)|,)|(==|!=|>=|<=|>|<)|"([^"]*)"|'([^']*)'|([A-Za-z0-9_.+\-]+))/y;
+const KEYWORDS = ['and', 'or', 'not', 'in'];
+
+function tokenize(source) {
+    const tokens = [];
+    TOKEN.lastIndex = 0;
+    while (TOKEN.lastIndex < source.length) {
+        const at = TOKEN.lastIndex;
+        const match = TOKEN.exec(source);
+        if (!match) {
+            const rest = source.slice(at);
+            if (!rest.trim()) break;
+            const column = at + rest.length - rest.trimStart().length + 1;
+            throw new Error(`Unexpected character at column ${column}: ${rest.trim()[0]}`);
+        }
+        const [, punct, op, double, single, word] = match;
+        const column = at + match[0].length - match[0].trimStart().length + 1;
+        if (punct) tokens.push({ kind: punct, column });
+        else if (op) tokens.push({ kind: 'op', value: op, column });
+        else if (double !== undefined || single !== undefined) tokens.push({ kind: 'value', value: double ?? single, column });
+        else if (KEYWORDS.includes(word.toLowerCase())) tokens.push({ kind: word.toLowerCase(), column });
+        else tokens.push({ kind: 'word', value: word, column });
+    }
+    return tokens;
+}
+
+const camelCase = name => name.replace(/_([a-z0-9])/g, (_, c) => c.toUpperCase());
+
+function fieldOf(row, field) {
+    if (field === 'event') return row.name;
+    if (field === 'mint') return row.tokenMint;
+    if (field === 'slot') return row.slot;
+    const value = row.data[camelCase(field)];
+    return value === undefined || value === null ? undefined : value;
+}
+
+const INTEGER = /^-?\d+$/;
+const NUMBER = /^-?\d+(\.\d+)?$/;
+
+// Integers compare exactly (u64 fields arrive as strings), then other numbers
+function compare(value, op, literal) {
+    if (value === undefined) return false;
+    const text = String(value);
+    if (op === '==') return text === literal;
+    if (op === '!=') return text !== literal;
+    let order;
+    if (INTEGER.test(text) && INTEGER.test(literal)) {
+        order = BigInt(text) < BigInt(literal) ? -1 : BigInt(text) > BigInt(literal) ? 1 : 0;
+    } else if (NUMBER.test(text) && NUMBER.test(literal)) {
+        order = Math.sign(Number(text) - Number(literal));
+    } else {
+        return false;
+    }
+    return { '>': order > 0, '>=': order >= 0, '<': order < 0, '<=': order <= 0 }[op];
+}
+
+// Compile a filter into a predicate over indexer rows, or throw with the
+// column of the first error
+function compileFilter(source) {
+    const tokens = tokenize(source);
+    let pos = 0;
+    const peek = () => tokens[pos] || { kind: 'end', column: source.length + 1 };
+    const fail = (expected) => {
+        const token = peek();
+        const found = token.kind === 'end' ? 'end of filter' : (token.value ?? token.kind);
+        throw new Error(`Expected ${expected} at column ${token.column}, found ${found}`);
+    };
+    const take = (kind, expected = kind) => {
+        if (peek().kind !== kind) fail(expected);
+        return tokens[pos++];
+    };
+    const literal = () => (peek().kind === 'word' || peek().kind === 'value') ? tokens[pos++].value : fail('a value');
+
+    const or = () => {
+        let left = and();
+        while (peek().kind === 'or') {
+            pos++;
+            const a = left, b = and();
+            left = row => a(row) || b(row);
+        }
+        return left;
+    };
+    const and = () => {
+        let left = unary();
+        while (peek().kind === 'and') {
+            pos++;
+            const a = left, b = unary();
+            left = row => a(row) && b(row);
+        }
+        return left;
+    };
+    const unary = () => {
+        if (peek().kind === 'not') {
+            pos++;
+            const inner = unary();
+            return row => !inner(row);
+        }
+        if (peek().kind === '(') {
+            pos++;
+            const inner = or();
+            take(')');
+            return inner;
+        }
+        const field = take('word', 'a field').value;
+        if (peek().kind === 'in') {
+            pos++;
+            take('(');
+            const values = [literal()];
+            while (peek().kind === ',') {
+                pos++;
+                values.push(literal());
+            }
+            take(')');
+            return row => values.some(value => compare(fieldOf(row, field), '==', value));
+        }
+        const op = take('op', 'an operator').value;
+        const value = literal();
+        return row => compare(fieldOf(row, field), op, value);
+    };
+
+    const predicate = or();
+    if (peek().kind !== 'end') fail('and, or or the end of the filter');
+    return predicate;
+}
+
+function loadNotifierConfig(filePath) {
+    const raw = TOML.parse(fs.readFileSync(filePath, 'utf8'));
+    const config = { ...DEFAULTS, ...raw };
+    if (!config.webhooks.length) {
+        throw new Error(`${filePath}: no [[webhooks]] defined`);
+    }
+    const webhooks = config.webhooks.map((webhook, i) => {
+        const name = webhook.name || `webhook ${i + 1}`;
+        if (!/^https?:\/\//.test(webhook.url || '')) {
+            throw new Error(`${filePath}: ${name} needs an http(s) url`);
+        }
+        try {
+            // Without a filter a webhook receives every event
+            return { ...webhook, name, matches: webhook.filter ? compileFilter(webhook.filter) : () => true };
+        } catch (error) {
+            throw new Error(`${filePath}: ${name} filter: ${error.message}`);
+        }
+    });
+    return { ...config, webhooks, timeout: parseDuration(config.timeout) };
+}
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+// Client errors other than rate limiting will not succeed on a retry
+const isPermanent = error =>
+    error.response && error.response.status >= 400 && error.response.status < 500 && error.response.status !== 429;
+
+class Notifier {
+    constructor({ indexer, webhooks, timeout = 10, maxRetries = 3 }) {
+        this.indexer = indexer;
+        this.webhooks = webhooks;
+        this.timeout = timeout;
+        this.maxRetries = maxRetries;
+        this.delivered = new Map(); // `${signature}` -> Set of `${eventIndex}:${webhook}`
+        this.onEvent = row => this.handleEvent(row);
+        this.onDiscard = signature => this.handleDiscard(signature);
+    }
+
+    start() {
+        this.indexer.on('event', this.onEvent);
+        this.indexer.on('discard', this.onDiscard);
+        console.log(`🔔 Notifier delivering to ${this.webhooks.map(webhook => webhook.name).join(', ')}`);
+    }
+
+    stop() {
+        this.indexer.off('event', this.onEvent);
+        this.indexer.off('discard', this.onDiscard);
+    }
+
+    // Post the event to every webhook whose filter matches it. The same
+    // transaction can reach the indexer from both the stream and a backfill,
+    // so each (event, webhook) pair is delivered once.
+    handleEvent(row) {
+        const sent = this.delivered.get(row.signature) || new Set();
+        const deliveries = [];
+        for (const webhook of this.webhooks) {
+            const key = `${row.eventIndex}:${webhook.name}`;
+            if (sent.has(key) || !webhook.matches(row)) continue;
+            sent.add(key);
+            deliveries.push(this.deliver(webhook, {
+                type: 'event',
+                webhook: webhook.name,
+                event: row.name,
+                tokenMint: row.tokenMint,
+                signature: row.signature,
+                eventIndex: row.eventIndex,
+                slot: row.slot,
+                blockTime: row.blockTime,
+                finalized: row.finalized,
+                data: row.data
+            }));
+        }
+        if (sent.size) this.remember(row.signature, sent);
+        return Promise.all(deliveries);
+    }
+
+    // A fork dropped a transaction whose events went out unfinalized; tell
+    // the webhooks that received them
+    handleDiscard(signature) {
+        const sent = this.delivered.get(signature);
+        if (!sent) return Promise.resolve([]);
+        this.delivered.delete(signature);
+        const names = new Set([...sent].map(key => key.slice(key.indexOf(':') + 1)));
+        return Promise.all(this.webhooks
+            .filter(webhook => names.has(webhook.name))
+            .map(webhook => this.deliver(webhook, { type: 'discard', webhook: webhook.name, signature })));
+    }
+
+    remember(signature, sent) {
+        this.delivered.delete(signature);
+        this.delivered.set(signature, sent);
+        if (this.delivered.size > DELIVERY_MEMORY) {
+            this.delivered.delete(this.delivered.keys().next().value);
+        }
+    }
+
+    // JSON POST, signed with the webhook's secret when it has one
+    // (X-SolD-Signature: sha256=<hex HMAC of the body>), retried with backoff
+    async deliver(webhook, payload) {
+        const body = JSON.stringify(payload);
+        const headers = { 'Content-Type': 'application/json' };
+        if (webhook.secret) {
+            headers['X-SolD-Signature'] = 'sha256=' +
+                crypto.createHmac('sha256', webhook.secret).update(body).digest('hex');
+        }
+        for (let attempt = 0; ; attempt++) {
+            try {
+                await axios.post(webhook.url, body, { headers, timeout: this.timeout * 1000 });
+                console.log(`📨 ${payload.type === 'event' ? payload.event : 'discard'} ${payload.signature} → ${webhook.name}`);
+                return true;
+            } catch (error) {
+                if (attempt >= this.maxRetries || isPermanent(error)) {
+                    console.error(`❌ Delivery to ${webhook.name} failed:`, error.message);
+                    return false;
+                }
+                await sleep(RETRY_BASE_MS * 2 ** attempt);
+            }
+        }
+    }
+}
+
+async function main() {
+    const configPath = process.argv[2] || process.env.NOTIFIER_CONFIG || 'notifier.toml';
+    const config = loadNotifierConfig(configPath);
+
+    const connection = new Connection(
+        process.env.SOLANA_RPC_URL || (SOLANA_NETWORK === 'mainnet'
+            ? 'https://api.mainnet-beta.solana.com'
+            : 'https://api.devnet.solana.com'),
+        'confirmed'
+    );
+    const provider = new AnchorProvider(connection, new Wallet(Keypair.generate()), {});
+    const idl = JSON.parse(fs.readFileSync(process.env.PROGRAM_IDL || 'idl/sold_token_launch.json', 'utf8'));
+    const program = new Program(idl, new PublicKey(PROGRAM_ID), provider);
+
+    // The notifier keeps its own store so that, after a restart, it catches
+    // up on events it missed. A fresh store skips history instead of
+    // replaying it to every webhook.
+    const store = createEventStore(process.env.NOTIFIER_STORE_URL || config.store);
+    await store.migrate();
+    const fresh = !(await store.latestFinalizedSignature());
+    const indexer = new EventIndexer({
+        connection,
+        program,
+        store,
+        commitment: 'confirmed',
+        ingestion: process.env.INDEXER_INGESTION || config.ingestion,
+        grpcEndpoint: process.env.YELLOWSTONE_GRPC_URL,
+        grpcToken: process.env.YELLOWSTONE_GRPC_TOKEN,
+        backfillLimit: fresh ? 0 : undefined
+    });
+
+    const notifier = new Notifier({
+        indexer,
+        webhooks: config.webhooks,
+        timeout: config.timeout,
+        maxRetries: config.max_retries
+    });
+    notifier.start();
+    await indexer.start();
+
+    const shutdown = async () => {
+        notifier.stop();
+        await indexer.stop();
+        process.exit(0);
+    };
+    process.on('SIGTERM', shutdown);
+    process.on('SIGINT', shutdown);
+}
+
+if (require.main === module) {
+    main().catch(error => {
+        console.error('❌ Notifier failed to start:', error.message);
+        process.exit(1);
+    });
+}
+
+module.exports = { Notifier, compileFilter, loadNotifierConfig };
@@ -0,0 +1,309 @@
+// SolD account queries
+// getProgramAccounts filters built from the IDL account layout, so byte
+// offsets follow the account when its fields change instead of being
+// counted by hand.
+
+const crypto = require('crypto');
+const { PublicKey } = require('@solana/web3.js');
+const { utils, BN } = require('@project-serum/anchor');
+
+const DISCRIMINATOR_LEN = 8;
+
+// getMultipleAccountsInfo accepts at most this many accounts per call
+const MAX_MULTIPLE_ACCOUNTS = 100;
+
+const FIXED_SIZES = {
+    bool: 1, u8: 1, i8: 1, u16: 2, i16: 2, u32: 4, i32: 4, f32: 4,
+    u64: 8, i64: 8, f64: 8, u128: 16, i128: 16, publicKey: 32
+};
+
+const camelCase = name => name.replace(/_([a-z0-9])/g, (_, c) => c.toUpperCase());
+
+// Borsh size of an IDL type, or null when it varies: strings, vecs, options
+// and enums whose variants differ in size
+function fixedSize(idl, type) {
+    if (typeof type === 'string') return FIXED_SIZES[type] ?? null;
+    if (type.array) {
+        const inner = fixedSize(idl, type.array[0]);
+        return inner === null ? null : inner * type.array[1];
+    }
+    if (!type.defined) return null;
+
+    const def = (idl.types || []).find(candidate => candidate.name === type.defined);
+    if (!def) throw new Error(`Unknown IDL type ${type.defined}`);
+    if (def.type.kind === 'struct') {
+        return sumSizes(idl, def.type.fields.map(field => field.type));
+    }
+    const sizes = def.type.variants.map(variant => sumSizes(idl, (variant.fields || []).map(field => field.type || field)));
+    return sizes.every(size => size === sizes[0]) && sizes[0] !== null ? 1 + sizes[0] : null;
+}
+
+function sumSizes(idl, types) {
+    let total = 0;
+    for (const type of types) {
+        const size = fixedSize(idl, type);
+        if (size === null) return null;
+        total += size;
+    }
+    return total;
+}
+
+// Little-endian bytes of `value` as an IDL primitive, for memcmp
+function encodeValue(type, value) {
+    if (type === 'publicKey') return new PublicKey(value).toBuffer();
+    if (type === 'bool') return Buffer.from([value ? 1 : 0]);
+    if (type.array && type.array[0] === 'u8') return Buffer.from(value);
+    const size = FIXED_SIZES[type];
+    if (!size || type.startsWith('f')) {
+        throw new Error(`Cannot match ${JSON.stringify(type)} fields by bytes`);
+    }
+    let n = BigInt(value.toString());
+    if (n < 0n) n += 1n << BigInt(size * 8);
+    const buffer = Buffer.alloc(size);
+    for (let i = 0; i < size; i++) {
+        buffer[i] = Number(n & 0xffn);
+        n >>= 8n;
+    }
+    return buffer;
+}
+
+// Byte layout of one account type: where each field starts, up to the
+// first field whose size varies. Later fields cannot be matched on-chain.
+class AccountLayout {
+    constructor(idl, accountName) {
+        const def = idl.accounts.find(account => account.name === accountName);
+        if (!def) throw new Error(`IDL has no account ${accountName}`);
+        this.name = accountName;
+        this.fields = new Map();
+        this.variableField = null;
+
+        let offset = DISCRIMINATOR_LEN;
+        for (const field of def.type.fields) {
+            this.fields.set(field.name, { type: field.type, offset });
+            const size = fixedSize(idl, field.type);
+            if (size === null) {
+                this.variableField = field.name;
+                offset = null;
+                break;
+            }
+            offset += size;
+        }
+        for (const field of def.type.fields) {
+            if (!this.fields.has(field.name)) this.fields.set(field.name, { type: field.type, offset: null });
+        }
+        this.discriminator = crypto.createHash('sha256').update(`account:${accountName}`).digest().subarray(0, DISCRIMINATOR_LEN);
+    }
+
+    field(name) {
+        const field = this.fields.get(camelCase(name));
+        if (!field) throw new Error(`${this.name} has no field ${name}`);
+        return field;
+    }
+
+    // Offset of `name`, or null when it follows a variable-size field
+    offsetOf(name) {
+        return this.field(name).offset;
+    }
+}
+
+// Filter builders. Each returns one getProgramAccounts filter.
+const filters = {
+    // Accounts of the layout's type (its Anchor discriminator)
+    account: layout => ({ memcmp: { offset: 0, bytes: utils.bytes.bs58.encode(layout.discriminator) } }),
+
+    // `name` equal to `value`; throws for fields without a fixed offset
+    field: (layout, name, value) => {
+        const { type, offset } = layout.field(name);
+        if (offset === null) {
+            throw new Error(`${layout.name}.${name} follows ${layout.variableField}, whose size varies; ` +
+                'filter it after fetching instead');
+        }
+        return { memcmp: { offset, bytes: utils.bytes.bs58.encode(encodeValue(type, value)) } };
+    },
+
+    dataSize: size => ({ dataSize: size })
+};
+
+// Query over one account type. Conditions on fixed-offset fields become
+// memcmp filters; the rest are checked against decoded accounts. Results
+// come back in address order, fetched a page at a time.
+class AccountQuery {
+    constructor(program, accountName) {
+        this.program = program;
+        this.accountName = accountName;
+        this.layout = new AccountLayout(program.idl, accountName);
+        this.serverFilters = [filters.account(this.layout)];
+        this.predicates = [];
+    }
+
+    // `name` equal to `value`, matched on-chain when the field's offset is fixed
+    where(name, value) {
+        if (this.layout.offsetOf(name) !== null) {
+            this.serverFilters.push(filters.field(this.layout, name, value));
+        } else {
+            const key = camelCase(name);
+            const expected = toComparable(value);
+            this.predicates.push(account => toComparable(account[key]) === expected);
+        }
+        return this;
+    }
+
+    // Client-side condition on the decoded account
+    filter(predicate) {
+        this.predicates.push(predicate);
+        return this;
+    }
+
+    dataSize(size) {
+        this.serverFilters.push(filters.dataSize(size));
+        return this;
+    }
+
+    filters() {
+        return [...this.serverFilters];
+    }
+
+    // Matching addresses, without account data, in address order
+    async keys() {
+        const accounts = await this.program.provider.connection.getProgramAccounts(this.program.programId, {
+            filters: this.filters(),
+            dataSlice: { offset: 0, length: 0 }
+        });
+        return accounts.map(({ pubkey }) => pubkey).sort((a, b) => a.toBase58().localeCompare(b.toBase58()));
+    }
+
+    // Yields arrays of { publicKey, account }, reading at most pageSize
+    // accounts per call. Pages can come back short when client-side
+    // conditions drop accounts.
+    async *pages(pageSize = MAX_MULTIPLE_ACCOUNTS) {
+        const keys = await this.keys();
+        for (let start = 0; start < keys.length; start += pageSize) {
+            const batch = keys.slice(start, start + pageSize);
+            const infos = [];
+            for (let i = 0; i < batch.length; i += MAX_MULTIPLE_ACCOUNTS) {
+                infos.push(...await this.program.provider.connection.getMultipleAccountsInfo(
+                    batch.slice(i, i + MAX_MULTIPLE_ACCOUNTS)));
+            }
+            const page = [];
+            infos.forEach((info, i) => {
+                if (!info) return; // Closed since the key scan
+                let account;
+                try {
+                    account = this.decode(info.data);
+                } catch (error) {
+                    console.warn(`⚠️  Skipping ${batch[i].toBase58()}: ${error.message}`);
+                    return;
+                }
+                if (this.predicates.every(predicate => predicate(account))) {
+                    page.push({ publicKey: batch[i], account });
+                }
+            });
+            yield page;
+        }
+    }
+
+    decode(data) {
+        return this.program.coder.accounts.decode(this.accountName, data);
+    }
+
+    async all() {
+        const results = [];
+        for await (const page of this.pages()) results.push(...page);
+        return results;
+    }
+}
+
+// TokenLaunch is a zero-copy account: names and key lists sit in
+// fixed-width slots with a length beside them, flags are u8, and optional
+// settings are Borsh Options padded to their largest encoding. Rebuild the
+// values the program's accessors return, so consumers read strings, key
+// lists, booleans and null for settings that are unset.
+const LAUNCH_STRINGS = ['tokenName', 'tokenSymbol', 'metadataUri'];
+const LAUNCH_KEY_LISTS = ['insuranceWallets', 'dexAllowlist', 'recoveryAccounts'];
+const LAUNCH_FLAGS = ['isActive', 'presaleFinalized', 'conditionMet', 'logoVerified', 'repeatCreator'];
+const LAUNCH_OPTIONS = {
+    logoNft: 'publicKey',
+    creatorMultisig: 'CreatorMultisig',
+    pendingCreator: 'publicKey',
+    maxTxAmount: 'u64',
+    maxWalletPct: 'u8',
+    presale: 'PresaleConfig',
+    unlockSchedule: 'UnlockSchedule',
+    relockConsentDays: 'u16',
+    launchClass: 'u8',
+    unlockCondition: 'UnlockCondition',
+    bootstrap: 'BootstrapConfig',
+    logoCnft: 'CompressedLogo',
+    pendingInsuranceChange: 'PendingInsuranceChange'
+};
+
+function decodeOptionSlot(coder, type, slot) {
+    const bytes = Buffer.from(slot);
+    if (bytes[0] === 0) return null;
+    const value = bytes.subarray(1);
+    switch (type) {
+        case 'publicKey': return new PublicKey(value.subarray(0, 32));
+        case 'u64': return new BN(value.subarray(0, 8), 'le');
+        case 'u16': return value.readUInt16LE(0);
+        case 'u8': return value[0];
+        default: return coder.types.decode(type, value);
+    }
+}
+
+// A decoded TokenLaunch with its slots unpacked
+function normalizeLaunch(coder, raw) {
+    const launch = { ...raw };
+    for (const name of LAUNCH_STRINGS) {
+        launch[name] = Buffer.from(raw[name].slice(0, raw[`${name}Len`])).toString('utf8');
+    }
+    for (const name of LAUNCH_KEY_LISTS) {
+        launch[name] = raw[name].slice(0, raw[`${name}Len`]);
+    }
+    for (const name of LAUNCH_FLAGS) {
+        launch[name] = raw[name] !== 0;
+    }
+    for (const [name, type] of Object.entries(LAUNCH_OPTIONS)) {
+        launch[name] = decodeOptionSlot(coder, type, raw[name]);
+    }
+    return launch;
+}
+
+function toComparable(value) {
+    if (value instanceof PublicKey) return value.toBase58();
+    if (value && typeof value.toTwos === 'function') return value.toString();
+    if (typeof value === 'bigint' || typeof value === 'number') return value.toString();
+    return value;
+}
+
+// TokenLaunch accounts. Every field sits at a fixed offset in the
+// zero-copy layout, so creator, mint and status all match on-chain; the
+// score range is checked on the decoded launch. Results carry the
+// unpacked launch (see normalizeLaunch).
+class LaunchQuery extends AccountQuery {
+    constructor(program) {
+        super(program, 'TokenLaunch');
+    }
+
+    decode(data) {
+        return normalizeLaunch(this.program.coder, super.decode(data));
+    }
+
+    byCreator(creator) {
+        return this.where('creator', creator);
+    }
+
+    byMint(tokenMint) {
+        return this.where('token_mint', tokenMint);
+    }
+
+    active(isActive = true) {
+        return this.where('is_active', isActive ? 1 : 0);
+    }
+
+    // Fraud score within [min, max], in basis points
+    fraudScore({ min = 0, max = Infinity } = {}) {
+        return this.filter(launch => launch.fraudScore >= min && launch.fraudScore <= max);
+    }
+}
+
+module.exports = { AccountLayout, AccountQuery, LaunchQuery, filters, fixedSize, normalizeLaunch };
@@ -0,0 +1,341 @@
+// SolD Replay Harness - re-run a cluster transaction under bankrun
+// Rebuilds the accounts a program transaction touched as they were before it
+// landed, replays it against the locally built program at the incident's
+// slot and clock, and diffs the outcome against what the cluster recorded:
+// success or error, emitted events, logs and compute units.
+//
+// Pre-state comes from, in order:
+//   1. --snapshots DIR: `<address>.json` dumps in `solana account --output json`
+//      format, e.g. taken from an archival node or before a disputed action
+//   2. RPC: the account's current state, if nothing has written it since the
+//      incident. Read-only accounts are then exact; writable system and token
+//      accounts are rolled back with the transaction's recorded pre-balances.
+// Program-owned accounts the incident wrote (the launch, proposals, ...) need
+// a snapshot, as their pre-incident data is gone from a regular RPC node.
+//
+// The replay is a simulation: signatures cannot be re-verified against a
+// local blockhash, so the message is re-stamped and run without sigverify.
+//
+// Usage: anchor build && ts-node replay-harness.ts <signature> [--rpc URL]
+//        [--snapshots DIR] [--out report.json]
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { startAnchor, ProgramTestContext, Clock } from 'solana-bankrun';
+import { BorshCoder, EventParser, Idl } from '@coral-xyz/anchor';
+import {
+  AccountInfo,
+  Connection,
+  PublicKey,
+  VersionedMessage,
+  VersionedTransaction,
+  VersionedTransactionResponse
+} from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+
+const PROGRAM_ID = new PublicKey('So1DLaunchProgram11111111111111111111111111');
+const IDL_PATH = 'target/idl/sold_token_launch.json';
+const DEFAULT_RPC = 'https://api.mainnet-beta.solana.com';
+const TOKEN_AMOUNT_OFFSET = 64; // mint (32) + owner (32)
+
+type Source = 'snapshot' | 'rpc' | 'rpc-rolled-back';
+
+interface PreState {
+  address: PublicKey;
+  account: AccountInfo<Buffer> | null; // null: created by the incident
+  source: Source;
+}
+
+interface Outcome {
+  error: string | null;
+  logs: string[];
+  events: { name: string; data: any }[];
+  computeUnits: number | null;
+}
+
+interface Report {
+  signature: string;
+  slot: number;
+  blockTime: number | null;
+  accounts: { address: string; writable: boolean; source: Source }[];
+  recorded: Outcome;
+  replayed: Outcome;
+  differences: string[];
+}
+
+function parseArgs(argv: string[]) {
+  const args = { signature: '', rpc: process.env.REPLAY_RPC || DEFAULT_RPC, snapshots: '', out: '' };
+  for (let i = 0; i < argv.length; i++) {
+    const flag = argv[i];
+    if (flag === '--rpc') args.rpc = argv[++i];
+    else if (flag === '--snapshots') args.snapshots = argv[++i];
+    else if (flag === '--out') args.out = argv[++i];
+    else args.signature = flag;
+  }
+  if (!args.signature) {
+    throw new Error('Usage: ts-node replay-harness.ts <signature> [--rpc URL] [--snapshots DIR] [--out FILE]');
+  }
+  return args;
+}
+
+// Accounts, in message order, with their writability in the incident
+function incidentAccounts(tx: VersionedTransactionResponse) {
+  const message = tx.transaction.message;
+  const keys = message.getAccountKeys({ accountKeysFromLookups: tx.meta?.loadedAddresses });
+  const accounts: { address: PublicKey; writable: boolean; index: number }[] = [];
+  for (let index = 0; index < keys.length; index++) {
+    accounts.push({ address: keys.get(index)!, writable: message.isAccountWritable(index), index });
+  }
+  // Lookup tables must exist locally for a v0 message to resolve
+  for (const lookup of message.addressTableLookups) {
+    accounts.push({ address: lookup.accountKey, writable: false, index: -1 });
+  }
+  return accounts;
+}
+
+function loadSnapshot(dir: string, address: PublicKey): AccountInfo<Buffer> | null {
+  const file = path.join(dir, `${address.toBase58()}.json`);
+  if (!dir || !fs.existsSync(file)) {
+    return null;
+  }
+  const { account } = JSON.parse(fs.readFileSync(file, 'utf8'));
+  return {
+    lamports: account.lamports,
+    data: Buffer.from(account.data[0], account.data[1] || 'base64'),
+    owner: new PublicKey(account.owner),
+    executable: account.executable,
+    rentEpoch: account.rentEpoch
+  };
+}
+
+// Undo the incident's own balance changes on a post-incident account.
+// Returns null when its data cannot be rolled back.
+function rollBack(
+  tx: VersionedTransactionResponse,
+  index: number,
+  account: AccountInfo<Buffer>
+): AccountInfo<Buffer> | null {
+  const meta = tx.meta!;
+  const owner = account.owner;
+  const data = Buffer.from(account.data);
+
+  if (owner.equals(TOKEN_PROGRAM_ID) || owner.equals(TOKEN_2022_PROGRAM_ID)) {
+    const pre = meta.preTokenBalances?.find(balance => balance.accountIndex === index);
+    const post = meta.postTokenBalances?.find(balance => balance.accountIndex === index);
+    if (pre && post) {
+      data.writeBigUInt64LE(BigInt(pre.uiTokenAmount.amount), TOKEN_AMOUNT_OFFSET);
+    } else if (pre || post) {
+      return null; // token account opened or closed by the incident
+    }
+  } else if (data.length > 0) {
+    return null; // program state the transaction may have rewritten
+  }
+
+  return { ...account, data, lamports: meta.preBalances[index] };
+}
+
+async function reconstructPreState(
+  connection: Connection,
+  tx: VersionedTransactionResponse,
+  signature: string,
+  snapshots: string
+): Promise<{ state: PreState[]; writable: Map<string, boolean> }> {
+  const accounts = incidentAccounts(tx);
+  const state: PreState[] = [];
+  const missing: string[] = [];
+  const writable = new Map<string, boolean>();
+
+  for (const { address, writable: isWritable, index } of accounts) {
+    writable.set(address.toBase58(), isWritable);
+
+    const snapshot = loadSnapshot(snapshots, address);
+    if (snapshot) {
+      state.push({ address, account: snapshot, source: 'snapshot' });
+      continue;
+    }
+
+    const current = await connection.getAccountInfo(address);
+    if (current?.executable) {
+      continue; // programs come from the local build or bankrun's builtins
+    }
+    if (index >= 0 && tx.meta!.preBalances[index] === 0) {
+      state.push({ address, account: null, source: 'rpc' });
+      continue;
+    }
+
+    const later = await connection.getSignaturesForAddress(address, { until: signature, limit: 1 });
+    const drifted = later.length > 0;
+    if (!isWritable && !drifted && current) {
+      state.push({ address, account: current, source: 'rpc' });
+    } else if (isWritable && !drifted && current) {
+      const rolledBack = rollBack(tx, index, current);
+      if (rolledBack) {
+        state.push({ address, account: rolledBack, source: 'rpc-rolled-back' });
+      } else {
+        missing.push(`${address.toBase58()} (written by the incident)`);
+      }
+    } else {
+      missing.push(`${address.toBase58()} (${current ? 'written since the incident' : 'closed since the incident'})`);
+    }
+  }
+
+  if (missing.length) {
+    throw new Error(
+      `No pre-incident state for:\n  ${missing.join('\n  ')}\n` +
+      'Provide `solana account <address> --output json` dumps from before the incident via --snapshots'
+    );
+  }
+  return { state, writable };
+}
+
+function decodeEvents(parser: EventParser, logs: string[]) {
+  return [...parser.parseLogs(logs)].map(event => ({ name: event.name, data: event.data }));
+}
+
+// Event fields as comparable JSON (BN and PublicKey stringify to base 10 / base 58)
+function canonical(value: any): string {
+  return JSON.stringify(value, (_key, v) =>
+    v && typeof v === 'object' && typeof v.toBase58 === 'function' ? v.toBase58()
+      : v && typeof v === 'object' && v.constructor?.name === 'BN' ? v.toString()
+        : v
+  );
+}
+
+function diffOutcomes(recorded: Outcome, replayed: Outcome): string[] {
+  const differences: string[] = [];
+  // Error codes are compared through the AnchorError log lines below
+  if ((recorded.error === null) !== (replayed.error === null)) {
+    differences.push(`result: recorded ${recorded.error ?? 'success'}, replayed ${replayed.error ?? 'success'}`);
+  }
+
+  const events = Math.max(recorded.events.length, replayed.events.length);
+  for (let i = 0; i < events; i++) {
+    const before = recorded.events[i];
+    const after = replayed.events[i];
+    if (!before || !after || before.name !== after.name) {
+      differences.push(`event #${i}: recorded ${before?.name ?? 'none'}, replayed ${after?.name ?? 'none'}`);
+      continue;
+    }
+    for (const field of Object.keys({ ...before.data, ...after.data })) {
+      const [a, b] = [canonical(before.data[field]), canonical(after.data[field])];
+      if (a !== b) {
+        differences.push(`event #${i} ${before.name}.${field}: recorded ${a}, replayed ${b}`);
+      }
+    }
+  }
+
+  const programLogs = (logs: string[]) => logs.filter(line => line.startsWith('Program log:'));
+  const [a, b] = [programLogs(recorded.logs), programLogs(replayed.logs)];
+  for (let i = 0; i < Math.max(a.length, b.length); i++) {
+    if (a[i] !== b[i]) {
+      differences.push(`log line ${i}: recorded "${a[i] ?? ''}", replayed "${b[i] ?? ''}"`);
+    }
+  }
+  return differences;
+}
+
+async function replay(
+  context: ProgramTestContext,
+  tx: VersionedTransactionResponse,
+  state: PreState[],
+  parser: EventParser
+): Promise<Outcome> {
+  for (const { address, account } of state) {
+    if (account) {
+      context.setAccount(address, {
+        lamports: account.lamports,
+        data: account.data,
+        owner: account.owner,
+        executable: account.executable
+      });
+    }
+  }
+
+  // Run at the incident's slot and clock so timelocks and cooldowns agree
+  context.warpToSlot(BigInt(tx.slot));
+  const clock = await context.banksClient.getClock();
+  context.setClock(new Clock(
+    BigInt(tx.slot),
+    clock.epochStartTimestamp,
+    clock.epoch,
+    clock.leaderScheduleEpoch,
+    BigInt(tx.blockTime ?? Number(clock.unixTimestamp))
+  ));
+
+  const message = VersionedMessage.deserialize(tx.transaction.message.serialize());
+  message.recentBlockhash = context.lastBlockhash;
+  const result = await context.banksClient.simulateTransaction(new VersionedTransaction(message));
+  const logs = result.meta?.logMessages ?? [];
+  return {
+    error: result.result,
+    logs,
+    events: decodeEvents(parser, logs),
+    computeUnits: result.meta ? Number(result.meta.computeUnitsConsumed) : null
+  };
+}
+
+async function main() {
+  const args = parseArgs(process.argv.slice(2));
+  const connection = new Connection(args.rpc, 'confirmed');
+  const idl = JSON.parse(fs.readFileSync(IDL_PATH, 'utf8')) as Idl;
+  const parser = new EventParser(PROGRAM_ID, new BorshCoder(idl));
+
+  const tx = await connection.getTransaction(args.signature, { maxSupportedTransactionVersion: 0 });
+  if (!tx || !tx.meta) {
+    throw new Error(`Transaction ${args.signature} not found (is the RPC node archival?)`);
+  }
+  const keys = incidentAccounts(tx).map(account => account.address);
+  if (!keys.some(key => key.equals(PROGRAM_ID))) {
+    throw new Error(`Transaction ${args.signature} does not involve ${PROGRAM_ID.toBase58()}`);
+  }
+
+  const { state, writable } = await reconstructPreState(connection, tx, args.signature, args.snapshots);
+  const recordedLogs = tx.meta.logMessages ?? [];
+  const recorded: Outcome = {
+    error: tx.meta.err ? JSON.stringify(tx.meta.err) : null,
+    logs: recordedLogs,
+    events: decodeEvents(parser, recordedLogs),
+    computeUnits: tx.meta.computeUnitsConsumed ?? null
+  };
+
+  const context = await startAnchor('.', [], []);
+  const replayed = await replay(context, tx, state, parser);
+
+  const report: Report = {
+    signature: args.signature,
+    slot: tx.slot,
+    blockTime: tx.blockTime ?? null,
+    accounts: state.map(({ address, source }) => ({
+      address: address.toBase58(),
+      writable: writable.get(address.toBase58()) ?? false,
+      source
+    })),
+    recorded,
+    replayed,
+    differences: diffOutcomes(recorded, replayed)
+  };
+
+  if (args.out) {
+    fs.writeFileSync(args.out, JSON.stringify(report, (_key, v) => (typeof v === 'bigint' ? v.toString() : v), 2));
+  }
+
+  console.log(`🔁 Replayed ${args.signature} at slot ${tx.slot} with ${state.length} accounts`);
+  console.log(`   recorded: ${recorded.error ?? 'success'} (${recorded.computeUnits ?? '?'} CU, ${recorded.events.length} events)`);
+  console.log(`   replayed: ${replayed.error ?? 'success'} (${replayed.computeUnits ?? '?'} CU, ${replayed.events.length} events)`);
+  if (report.differences.length === 0) {
+    console.log('✅ Replay matches the recorded outcome');
+    return;
+  }
+  console.log(`❌ ${report.differences.length} differences:`);
+  report.differences.forEach(line => console.log(`   - ${line}`));
+  process.exitCode = 1;
+}
+
+if (require.main === module) {
+  main().catch(error => {
+    console.error('❌ Replay failed:', error.message);
+    process.exit(2);
+  });
+}
+
+export { reconstructPreState, rollBack, diffOutcomes, replay };
@@ -0,0 +1,237 @@
+// SolD PDA seed registry
+// Mirrors the `seeds` module in anchor-program.rs (also published as IDL
+// constants). Derive launch accounts through here rather than hand-copying
+// seed strings.
+
+const { PublicKey } = require('@solana/web3.js');
+
+const PROGRAM_ID = new PublicKey('So1DLaunchProgram11111111111111111111111111');
+// Separate program registered as the transfer hook of Token-2022 launch mints
+const TRANSFER_HOOK_PROGRAM_ID = new PublicKey('So1DTransferHook111111111111111111111111111');
+
+const SEEDS = {
+    LAUNCH: 'launch',
+    VAULT: 'vault',
+    SOL_VAULT: 'sol_vault',
+    WSOL_VAULT: 'wsol_vault',
+    CONFIG: 'config',
+    TREASURY: 'treasury',
+    ORACLE_REGISTRY: 'oracle_registry',
+    CREATOR_INDEX: 'creator_index',
+    INSURANCE_WALLET: 'insurance_wallet',
+    INSURANCE_COLLATERAL_POOL: 'insurance_collateral_pool',
+    INSURANCE_COLLATERAL: 'insurance_collateral',
+    WITHDRAWAL_PROPOSAL: 'withdrawal_proposal',
+    GOVERNANCE_PROPOSAL: 'governance_proposal',
+    VOTE_ESCROW: 'vote_escrow',
+    VOTE_RECORD: 'vote_record',
+    PRESALE_PURCHASE: 'presale_purchase',
+    BONDING_CURVE: 'bonding_curve',
+    CURVE_RESERVE: 'curve_reserve',
+    CURVE_VAULT: 'curve_vault',
+    LP_VAULT: 'lp_vault',
+    LIQUIDITY_LOCK: 'liquidity_lock',
+    ANNOTATIONS: 'annotations',
+    EXTRA_ACCOUNT_METAS: 'extra-account-metas',
+    YIELD_VAULT: 'yield_vault',
+    YIELD_POSITION: 'yield_position',
+    APPEAL: 'appeal',
+    UNDERWRITING: 'underwriting',
+    UNDERWRITER: 'underwriter',
+    RECOVERY: 'recovery',
+    ALLOCATION_VAULT: 'allocation_vault',
+    ALLOCATION: 'allocation',
+    ARCHIVE: 'archive',
+    SPONSOR_POOL: 'sponsor_pool',
+    SPONSORED_WALLET: 'sponsored_wallet',
+    FEE_SHARD: 'fee_shard',
+    LAUNCH_STATS: 'launch_stats',
+    SCORING_MODEL: 'scoring_model',
+    FRAUD_SCORE_HISTORY: 'fraud_score_history',
+    LAUNCH_TEMPLATE: 'launch_template',
+    WITHDRAWAL_RECORD: 'withdrawal_record',
+    BLACKLIST: 'blacklist',
+    STAKING_POOL: 'staking_pool',
+    STAKE_VAULT: 'stake_vault',
+    REWARD_VAULT: 'reward_vault',
+    STAKE_POSITION: 'stake_position',
+    LAUNCH_METADATA: 'launch_metadata',
+    KYC_ATTESTATION: 'kyc_attestation',
+    TEAM_ALLOCATION: 'team_allocation',
+    TEAM_VAULT: 'team_vault',
+    RAISE: 'raise',
+    CONTRIBUTION: 'contribution',
+    BOOTSTRAP_GUARD: 'bootstrap_guard',
+    DIVIDEND_POOL: 'dividend_pool',
+    DIVIDEND_CLAIM: 'dividend_claim',
+    CREATOR_PROFILE: 'creator_profile',
+    DELEGATION: 'delegation',
+    METADATA_UPDATE: 'metadata_update',
+    ARBITER_PANEL: 'arbiter_panel',
+    ARBITRATION_PROPOSAL: 'arbitration_proposal',
+    LAUNCH_REGISTRY: 'launch_registry',
+    REGISTRY_PAGE: 'registry_page'
+};
+
+// Number of per-launch trading fee shards (FEE_SHARDS in the program)
+const FEE_SHARDS = 8;
+
+// Launches per registry page (REGISTRY_PAGE_SIZE in the program)
+const REGISTRY_PAGE_SIZE = 64;
+
+// ClaimKind discriminants, as used in the allocation seed
+const CLAIM_KINDS = { vesting: 0, airdrop: 1 };
+
+function u64Le(value) {
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigUInt64LE(BigInt(value.toString()));
+    return buffer;
+}
+
+function find(seeds, programId = PROGRAM_ID) {
+    return PublicKey.findProgramAddressSync(seeds, programId);
+}
+
+const seed = name => Buffer.from(SEEDS[name]);
+
+const findLaunch = (tokenMint, programId) => find([seed('LAUNCH'), tokenMint.toBuffer()], programId);
+const findVault = (tokenMint, programId) => find([seed('VAULT'), tokenMint.toBuffer()], programId);
+const findSolVault = (tokenMint, programId) => find([seed('SOL_VAULT'), tokenMint.toBuffer()], programId);
+const findConfig = programId => find([seed('CONFIG')], programId);
+const findTreasury = programId => find([seed('TREASURY')], programId);
+const findOracleRegistry = programId => find([seed('ORACLE_REGISTRY')], programId);
+const findCreatorIndex = (creator, programId) => find([seed('CREATOR_INDEX'), creator.toBuffer()], programId);
+const findCreatorProfile = (creator, programId) => find([seed('CREATOR_PROFILE'), creator.toBuffer()], programId);
+const findDelegation = (tokenAccount, programId) => find([seed('DELEGATION'), tokenAccount.toBuffer()], programId);
+const findMetadataUpdate = (launch, programId) => find([seed('METADATA_UPDATE'), launch.toBuffer()], programId);
+const findArbiterPanel = programId => find([seed('ARBITER_PANEL')], programId);
+const findArbitrationProposal = (proposalId, programId) =>
+    find([seed('ARBITRATION_PROPOSAL'), u64Le(proposalId)], programId);
+const findLaunchRegistry = programId => find([seed('LAUNCH_REGISTRY')], programId);
+const findRegistryPage = (page, programId) => find([seed('REGISTRY_PAGE'), u64Le(page)], programId);
+
+const findInsuranceWallet = (launch, wallet, programId) =>
+    find([seed('INSURANCE_WALLET'), launch.toBuffer(), wallet.toBuffer()], programId);
+const findInsuranceCollateralPool = (launch, programId) =>
+    find([seed('INSURANCE_COLLATERAL_POOL'), launch.toBuffer()], programId);
+const findInsuranceCollateral = (launch, wallet, programId) =>
+    find([seed('INSURANCE_COLLATERAL'), launch.toBuffer(), wallet.toBuffer()], programId);
+const findWithdrawalProposal = (launch, proposalId, programId) =>
+    find([seed('WITHDRAWAL_PROPOSAL'), launch.toBuffer(), u64Le(proposalId)], programId);
+const findGovernanceProposal = (launch, proposalId, programId) =>
+    find([seed('GOVERNANCE_PROPOSAL'), launch.toBuffer(), u64Le(proposalId)], programId);
+const findVoteRecord = (proposal, voter, programId) =>
+    find([seed('VOTE_RECORD'), proposal.toBuffer(), voter.toBuffer()], programId);
+const findPresalePurchase = (launch, buyer, programId) =>
+    find([seed('PRESALE_PURCHASE'), launch.toBuffer(), buyer.toBuffer()], programId);
+const findAllocationVault = (tokenMint, programId) => find([seed('ALLOCATION_VAULT'), tokenMint.toBuffer()], programId);
+const findAllocation = (launch, beneficiary, kind, programId) =>
+    find([seed('ALLOCATION'), launch.toBuffer(), beneficiary.toBuffer(), Buffer.from([CLAIM_KINDS[kind]])], programId);
+const findWithdrawalRecord = (launch, seq, programId) =>
+    find([seed('WITHDRAWAL_RECORD'), launch.toBuffer(), u64Le(seq)], programId);
+const findArchive = (tokenMint, programId) => find([seed('ARCHIVE'), tokenMint.toBuffer()], programId);
+const findSponsorPool = (launch, programId) => find([seed('SPONSOR_POOL'), launch.toBuffer()], programId);
+const findSponsoredWallet = (launch, wallet, programId) =>
+    find([seed('SPONSORED_WALLET'), launch.toBuffer(), wallet.toBuffer()], programId);
+const findBlacklist = (launch, wallet, programId) =>
+    find([seed('BLACKLIST'), launch.toBuffer(), wallet.toBuffer()], programId);
+const findLaunchMetadata = (tokenMint, programId) => find([seed('LAUNCH_METADATA'), tokenMint.toBuffer()], programId);
+const findTeamAllocation = (launch, beneficiary, programId) =>
+    find([seed('TEAM_ALLOCATION'), launch.toBuffer(), beneficiary.toBuffer()], programId);
+const findTeamVault = (teamAllocation, programId) => find([seed('TEAM_VAULT'), teamAllocation.toBuffer()], programId);
+const findRaise = (tokenMint, programId) => find([seed('RAISE'), tokenMint.toBuffer()], programId);
+const findContribution = (raise, contributor, programId) =>
+    find([seed('CONTRIBUTION'), raise.toBuffer(), contributor.toBuffer()], programId);
+const findKycAttestation = (creator, programId) => find([seed('KYC_ATTESTATION'), creator.toBuffer()], programId);
+const findBootstrapGuard = (launch, programId) => find([seed('BOOTSTRAP_GUARD'), launch.toBuffer()], programId);
+const findDividendPool = (launch, programId) => find([seed('DIVIDEND_POOL'), launch.toBuffer()], programId);
+const findDividendClaim = (launch, holder, programId) =>
+    find([seed('DIVIDEND_CLAIM'), launch.toBuffer(), holder.toBuffer()], programId);
+const findStakingPool = (launch, programId) => find([seed('STAKING_POOL'), launch.toBuffer()], programId);
+const findStakeVault = (tokenMint, programId) => find([seed('STAKE_VAULT'), tokenMint.toBuffer()], programId);
+const findRewardVault = (tokenMint, programId) => find([seed('REWARD_VAULT'), tokenMint.toBuffer()], programId);
+const findStakePosition = (pool, owner, programId) =>
+    find([seed('STAKE_POSITION'), pool.toBuffer(), owner.toBuffer()], programId);
+const findFeeShard = (launch, shard, programId) =>
+    find([seed('FEE_SHARD'), launch.toBuffer(), Buffer.from([shard])], programId);
+// Shards 0..FEE_SHARDS-1 follow the fee shards; FEE_SHARDS holds presale and curve trades
+const findLaunchStats = (launch, shard, programId) =>
+    find([seed('LAUNCH_STATS'), launch.toBuffer(), Buffer.from([shard])], programId);
+// Derived under the transfer hook program, not the launch program
+const findExtraAccountMetas = (tokenMint, hookProgramId = TRANSFER_HOOK_PROGRAM_ID) =>
+    find([seed('EXTRA_ACCOUNT_METAS'), tokenMint.toBuffer()], hookProgramId);
+
+function u32Le(value) {
+    const buffer = Buffer.alloc(4);
+    buffer.writeUInt32LE(value);
+    return buffer;
+}
+
+const findScoringModel = (version, programId) => find([seed('SCORING_MODEL'), u32Le(version)], programId);
+const findFraudScoreHistory = (launch, programId) => find([seed('FRAUD_SCORE_HISTORY'), launch.toBuffer()], programId);
+const findLaunchTemplate = (owner, templateId, programId) =>
+    find([seed('LAUNCH_TEMPLATE'), owner.toBuffer(), u64Le(templateId)], programId);
+
+// Registry page holding the launch at a registry index (0-based)
+const registryPageOf = index => Math.floor(Number(index.toString()) / REGISTRY_PAGE_SIZE);
+
+// Shard a payer's transfers land in, spreading fee writes across shards
+const feeShardFor = payer => payer.toBuffer()[0] % FEE_SHARDS;
+
+module.exports = {
+    PROGRAM_ID,
+    TRANSFER_HOOK_PROGRAM_ID,
+    SEEDS,
+    CLAIM_KINDS,
+    FEE_SHARDS,
+    REGISTRY_PAGE_SIZE,
+    findLaunch,
+    findVault,
+    findSolVault,
+    findConfig,
+    findTreasury,
+    findOracleRegistry,
+    findCreatorIndex,
+    findCreatorProfile,
+    findDelegation,
+    findMetadataUpdate,
+    findArbiterPanel,
+    findArbitrationProposal,
+    findLaunchRegistry,
+    findRegistryPage,
+    findInsuranceWallet,
+    findInsuranceCollateralPool,
+    findInsuranceCollateral,
+    findWithdrawalProposal,
+    findGovernanceProposal,
+    findVoteRecord,
+    findPresalePurchase,
+    findAllocationVault,
+    findAllocation,
+    findWithdrawalRecord,
+    findArchive,
+    findSponsorPool,
+    findSponsoredWallet,
+    findBlacklist,
+    findLaunchMetadata,
+    findTeamAllocation,
+    findTeamVault,
+    findRaise,
+    findContribution,
+    findKycAttestation,
+    findBootstrapGuard,
+    findDividendPool,
+    findDividendClaim,
+    findStakingPool,
+    findStakeVault,
+    findRewardVault,
+    findStakePosition,
+    findFeeShard,
+    findLaunchStats,
+    findExtraAccountMetas,
+    findScoringModel,
+    findFraudScoreHistory,
+    findLaunchTemplate,
+    feeShardFor,
+    registryPageOf
+};
@@ -3,6 +3,18 @@
 
 import * as fs from 'fs';
 import * as path from 'path';
+import * as crypto from 'crypto';
+import * as zlib from 'zlib';
+import { spawnSync } from 'child_process';
+import {
+  AccountInfo, ComputeBudgetProgram, Connection, Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, TransactionInstruction,
+  TransactionMessage, VersionedTransaction, sendAndConfirmTransaction
+} from '@solana/web3.js';
+import { BN, BorshAccountsCoder, BorshCoder } from '@coral-xyz/anchor';
+import * as nacl from 'tweetnacl';
+import { keccak_256 } from '@noble/hashes/sha3';
+import { findExtraAccountMetas, findLaunchStats, TRANSFER_HOOK_PROGRAM_ID } from './seeds';
+import { normalizeLaunch } from './queries';
 
 // Type definitions
 interface SolDTypes {
@@ -51,6 +63,71 @@ interface FeesConfig {
   trading?: string;
 }
 
+// Linear vesting for allocations escrowed after launch. Nothing vests
+// before `cliff`; after it, tokens vest in whole `interval` steps until
+// `duration` has passed. All three are measured from the timelock end.
+interface VestingConfig {
+  cliff: string;
+  duration: string;
+  interval: string;
+}
+
+// `invariant <expression>`: a boolean expression over one generated
+// account's numeric fields, program constants and integer literals
+interface InvariantConfig {
+  expression: string;
+}
+
+// `event <Name> on <instruction> { field, field = source, ... }`: an Anchor
+// event the instruction emits. A field takes one of the instruction's args,
+// the key of one of its accounts, or `timestamp`; a bare field names its
+// source.
+interface EventConfig {
+  name: string;
+  instruction: string;
+  fields: { name: string; source: string }[];
+}
+
+// Who may call an instruction: the launch's creator, one of its insurance
+// wallets, the relock escrow, a wallet granted the oracle role, or anyone
+type AccessRole = 'creator' | 'insurance' | 'escrow' | 'oracle' | 'anyone';
+
+// Opt-in instruction groups, declared with `modules = [staking, ...]`
+type SpecModule = 'staking' | 'governance' | 'presale';
+
+// Who may upgrade the deployed program: the deploying wallet by default,
+// another key, or the vault of a Squads (v4) multisig
+interface DeployConfig {
+  authority?: string;
+  squads?: string;
+  vault: number; // Squads vault index
+}
+
+// Operator-tunable values, set in the spec's `config { }` block and baked
+// into the generated program's `constants` module. Fees are in lamports.
+interface ProgramConstants {
+  feeRecipient: string;
+  launchFee: number;
+  insuranceWalletFee: number;
+  logoFee: number;
+  tradingFee: number;
+  relockFee: number;
+  minTimelock: string; // enforced on MAINNET specs
+  maxInsuranceWallets: number;
+  maxInsuranceLimit: number; // percent of supply
+  maxNameLen: number; // bytes TokenLaunch reserves for token_name
+  maxSymbolLen: number; // bytes TokenLaunch reserves for token_symbol
+  stakeLockup: string; // staking module
+  votingPeriod: string; // governance module
+  quorumBps: number; // governance module, share of supply that must vote
+  presalePrice: number; // presale module, lamports per token unit
+  presaleDuration: string; // presale module
+  autoSuspendThreshold: number; // fraud score, 0-1
+  tokenStandard: 'spl' | 'token2022'; // picks the program's token backend
+  transferFeeBps: number; // Token-2022 transfer fee extension
+  maxTransferFee: string; // token base units, a u64
+}
+
 interface SolDConfig {
   network: SolDTypes['network'];
   token: TokenConfig;
@@ -60,21 +137,573 @@ interface SolDConfig {
   transfer: TransferConfig;
   relock: RelockConfig;
   fees: FeesConfig;
+  budget?: BudgetConfig;
+  vesting?: VestingConfig;
+  deploy?: DeployConfig;
+  invariants: InvariantConfig[];
+  events: EventConfig[];
+  modules: SpecModule[];
+  access: Record<string, AccessRole[]>; // role-gated instruction => roles
+  constants: ProgramConstants;
+}
+
+const DEFAULT_CONSTANTS: ProgramConstants = {
+  feeRecipient: 'GR8TuDpbnDvuLzW4JBCLjbeLvGFs1p21XBytLx6rA7XD',
+  launchFee: 10000000, // 0.01 SOL
+  insuranceWalletFee: 10000000, // 0.01 SOL per wallet
+  logoFee: 5000000, // 0.005 SOL
+  tradingFee: 5000,
+  relockFee: 20000000, // 0.02 SOL
+  minTimelock: '100d',
+  maxInsuranceWallets: 10,
+  maxInsuranceLimit: 50,
+  maxNameLen: 32, // Metaplex metadata limits
+  maxSymbolLen: 10,
+  stakeLockup: '7d',
+  votingPeriod: '3d',
+  quorumBps: 1000,
+  presalePrice: 1000,
+  presaleDuration: '14d',
+  autoSuspendThreshold: 0.9,
+  tokenStandard: 'spl',
+  transferFeeBps: 0,
+  maxTransferFee: '0'
+};
+
+// The role-gated instructions and who may call them unless the spec's
+// `access { }` block says otherwise
+const DEFAULT_ACCESS: Record<string, AccessRole[]> = {
+  transfer_tokens: ['anyone'],
+  emergency_withdraw: ['insurance'],
+  relock_tokens: ['escrow'],
+  create_vesting: ['creator']
+};
+const ACCESS_ROLES: AccessRole[] = ['creator', 'insurance', 'escrow', 'oracle', 'anyone'];
+
+// Roles held through a grant account the launch creator issues, rather
+// than a key stored on the launch or fixed by the spec
+const PDA_ROLES: AccessRole[] = ['oracle'];
+
+// The error a caller without the role gets, when it is the only one allowed
+const ROLE_ERRORS: Record<string, { name: string; msg: string }> = {
+  creator: { name: 'UnauthorizedCreator', msg: 'Caller is not the launch creator' },
+  insurance: { name: 'UnauthorizedInsurance', msg: 'Caller is not authorized insurance wallet' },
+  escrow: { name: 'UnauthorizedRelock', msg: 'Caller is not authorized to relock' },
+  oracle: { name: 'UnauthorizedOracle', msg: 'Caller does not hold the oracle role' }
+};
+
+// Modules in the order they are generated, and the config keys only a
+// module uses
+const MODULES: SpecModule[] = ['staking', 'governance', 'presale'];
+const MODULE_KEYS: Record<string, SpecModule> = {
+  stake_lockup: 'staking',
+  voting_period: 'governance',
+  quorum_bps: 'governance',
+  presale_price: 'presale',
+  presale_duration: 'presale'
+};
+const MODULE_ERRORS: Record<SpecModule, { name: string; msg: string }[]> = {
+  staking: [
+    { name: 'StakeLocked', msg: 'Stake is still locked' },
+    { name: 'InsufficientStake', msg: 'Amount exceeds the staked balance' }
+  ],
+  governance: [
+    { name: 'NoStake', msg: 'Only stakers may propose or vote' },
+    { name: 'DescriptionTooLong', msg: 'Proposal description is too long' },
+    { name: 'VotingClosed', msg: 'Voting on this proposal has ended' },
+    { name: 'VotingOpen', msg: 'Voting on this proposal is still open' },
+    { name: 'ProposalFinalized', msg: 'Proposal is already finalized' }
+  ],
+  presale: [
+    { name: 'PresaleEnded', msg: 'Presale has ended' },
+    { name: 'PresaleOpen', msg: 'Presale is still open' },
+    { name: 'PresaleSoldOut', msg: 'Not enough presale tokens left' }
+  ]
+};
+
+// Anchor workspace layout for `sold build`
+const DEFAULT_PROGRAM_ID = 'Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS';
+const ANCHOR_CLUSTERS = ['localnet', 'devnet', 'testnet', 'mainnet'];
+
+// RPC endpoints `sold deploy` checks the program against, per cluster
+const CLUSTER_URLS: Record<string, string> = {
+  localnet: 'http://127.0.0.1:8899',
+  devnet: 'https://api.devnet.solana.com',
+  testnet: 'https://api.testnet.solana.com',
+  mainnet: 'https://api.mainnet-beta.solana.com'
+};
+
+const SQUADS_PROGRAM_ID = new PublicKey('SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf');
+
+// The Squads v4 vault PDA that signs for a multisig
+function squadsVault(multisig: string, index: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('multisig'), new PublicKey(multisig).toBuffer(), Buffer.from('vault'), Buffer.from([index])],
+    SQUADS_PROGRAM_ID
+  )[0];
+}
+
+// One step of a `sold simulate` scenario. A warp without seconds goes to
+// the launch's timelock end.
+type ScenarioStep =
+  | { line: number; kind: 'launch' }
+  | { line: number; kind: 'warp'; seconds?: number }
+  | { line: number; kind: 'transfer' | 'withdraw'; amount: string };
+
+interface DeployOptions {
+  programKeypair?: string;
+  rpc?: string;
+  yes?: boolean; // required for mainnet
+  force?: boolean; // upgrade despite breaking changes
+}
+
+interface WorkspaceOptions {
+  programId?: string;
+  cluster?: string;
+  migrateFrom?: string; // IDL of the deployed program, see `sold diff`
+}
+
+// Anchor (0.29) IDL model for the generated program
+type IdlType = string | { vec: IdlType } | { option: IdlType } | { defined: string };
+
+interface IdlField {
+  name: string;
+  type: IdlType;
+}
+
+type IdlSeed =
+  | { kind: 'const'; type: 'string'; value: string }
+  | { kind: 'account'; type: 'publicKey'; path: string };
+
+interface IdlAccountItem {
+  name: string;
+  isMut: boolean;
+  isSigner: boolean;
+  isOptional?: boolean;
+  pda?: { seeds: IdlSeed[] };
+}
+
+interface IdlInstruction {
+  name: string;
+  accounts: IdlAccountItem[];
+  args: IdlField[];
+}
+
+interface IdlTypeDef {
+  name: string;
+  type: { kind: 'struct'; fields: IdlField[] };
+}
+
+interface IdlErrorCode {
+  code: number;
+  name: string;
+  msg: string;
+}
+
+// The token program a generated program is written against: account types,
+// the transfer CPI and any instructions only that backend has
+interface TokenBackend {
+  programId: string;
+  imports: string;
+  mint: string;
+  tokenAccount: string;
+  tokenProgram: string;
+  transfer(from: string, to: string, authority: string, amount: string, signerSeeds?: string): string;
+  instructions: string;
+  accounts: string;
+}
+
+// Role checks for a generated account struct, by instruction and the name
+// of its acting signer
+interface AccessChecks {
+  signer(instruction: string, name: string, mut?: boolean): string;
+  launch(instruction: string, signerName: string): string;
+  grants(instruction: string, signerName: string): string;
+}
+
+// Fees the generated program charges, in lamports
+interface FeeSchedule {
+  launch: number;
+  trading: number;
+  relock: number;
+}
+
+interface Idl {
+  version: string;
+  name: string;
+  constants: { name: string; type: string; value: string }[];
+  instructions: IdlInstruction[];
+  accounts: IdlTypeDef[];
+  events: { name: string; fields: (IdlField & { index: boolean })[] }[];
+  errors: IdlErrorCode[];
+  metadata: { address: string };
+}
+
+interface ConfigEntry {
+  key: string;
+  value: string;
+  line: number;   // 1-based
+  column: number; // 1-based
+}
+
+// Entry syntax of each block: config takes `key = value`, several to a
+// line split by commas; access takes one `instruction => roles` per line
+const BLOCK_SYNTAX: Record<'config' | 'access', { split?: string; entry: RegExp }> = {
+  config: { split: ',', entry: /^(\w+)\s*=\s*(?:"([^"]*)"|(\S+))$/ },
+  access: { entry: /^(\w+)\s*=>\s*(.+)$/ }
+};
+
+// Pull a `config { ... }` or `access { ... }` block out of a spec. The
+// block's lines are blanked in `rest` so tag parsing and line numbers are
+// unaffected.
+function extractBlock(soldCode: string, name: 'config' | 'access'): { entries: ConfigEntry[]; rest: string; unclosed?: { line: number; column: number } } {
+  const syntax = BLOCK_SYNTAX[name];
+  const lines = soldCode.split('\n');
+  const entries: ConfigEntry[] = [];
+  const start = lines.findIndex(line => new RegExp(`^\\s*${name}\\s*\\{`).test(line));
+  if (start === -1) {
+    return { entries, rest: soldCode };
+  }
+  const end = lines.findIndex((line, i) => i > start ? line.includes('}') : i === start && line.indexOf('}') > line.indexOf('{'));
+  if (end === -1) {
+    return { entries, rest: soldCode, unclosed: { line: start + 1, column: lines[start].indexOf(name) + 1 } };
+  }
+
+  for (let i = start; i <= end; i++) {
+    const text = lines[i];
+    const from = i === start ? text.indexOf('{') + 1 : 0;
+    const to = i === end ? text.lastIndexOf('}') : text.length;
+    let offset = from;
+    const body = text.slice(from, to);
+    for (const segment of syntax.split ? body.split(syntax.split) : [body]) {
+      const trimmed = segment.trim();
+      if (trimmed) {
+        const column = offset + segment.indexOf(trimmed) + 1;
+        const match = trimmed.match(syntax.entry);
+        entries.push(match
+          ? { key: match[1], value: (match[2] ?? match[3]).trim(), line: i + 1, column }
+          : { key: '', value: trimmed, line: i + 1, column });
+      }
+      offset += segment.length + 1;
+    }
+    lines[i] = '';
+  }
+  return { entries, rest: lines.join('\n') };
+}
+
+// Apply one config block entry, throwing on an unknown key or a bad value
+function applyConstant(constants: ProgramConstants, key: string, value: string): void {
+  const lamports = (): number => {
+    if (!/^\d+$/.test(value) || !Number.isSafeInteger(Number(value))) {
+      throw new Error(`${key} must be a whole number of lamports, got "${value}"`);
+    }
+    return Number(value);
+  };
+  const duration = (example: string): string => {
+    if (!/^\d+[smhd]$/.test(value)) {
+      throw new Error(`${key} must be a duration like "${example}", got "${value}"`);
+    }
+    return value;
+  };
+  const between = (min: number, max: number): number => {
+    if (!/^\d+$/.test(value) || Number(value) < min || Number(value) > max) {
+      throw new Error(`${key} must be a whole number from ${min} to ${max}, got "${value}"`);
+    }
+    return Number(value);
+  };
+
+  switch (key) {
+    case '':
+      throw new Error(`Expected key = value in config block, got "${value}"`);
+    case 'fee_recipient':
+      try {
+        new PublicKey(value);
+      } catch {
+        throw new Error(`fee_recipient "${value}" is not a valid base58 public key`);
+      }
+      constants.feeRecipient = value;
+      break;
+    case 'launch_fee':
+      constants.launchFee = lamports();
+      break;
+    case 'insurance_wallet_fee':
+      constants.insuranceWalletFee = lamports();
+      break;
+    case 'logo_fee':
+      constants.logoFee = lamports();
+      break;
+    case 'trading_fee':
+      constants.tradingFee = lamports();
+      break;
+    case 'relock_fee':
+      constants.relockFee = lamports();
+      break;
+    case 'min_timelock':
+      constants.minTimelock = duration('100d');
+      break;
+    case 'max_insurance_wallets':
+      // Keeps TokenLaunch within the 10KB an init can allocate
+      constants.maxInsuranceWallets = between(1, 255);
+      break;
+    case 'max_insurance_limit':
+      constants.maxInsuranceLimit = between(0, 100);
+      break;
+    case 'max_name_len':
+      constants.maxNameLen = between(1, 200);
+      break;
+    case 'max_symbol_len':
+      constants.maxSymbolLen = between(1, 32);
+      break;
+    case 'stake_lockup':
+      constants.stakeLockup = duration('7d');
+      break;
+    case 'voting_period':
+      constants.votingPeriod = duration('3d');
+      break;
+    case 'presale_duration':
+      constants.presaleDuration = duration('14d');
+      break;
+    case 'quorum_bps':
+      constants.quorumBps = between(0, 10000);
+      break;
+    case 'presale_price':
+      if (lamports() === 0) {
+        throw new Error('presale_price must be at least 1 lamport');
+      }
+      constants.presalePrice = lamports();
+      break;
+    case 'auto_suspend_threshold': {
+      const threshold = Number(value);
+      if (!(threshold > 0 && threshold <= 1)) {
+        throw new Error(`auto_suspend_threshold must be above 0 and at most 1, got "${value}"`);
+      }
+      constants.autoSuspendThreshold = threshold;
+      break;
+    }
+    case 'token_standard':
+      if (value !== 'spl' && value !== 'token2022') {
+        throw new Error(`token_standard must be "spl" or "token2022", got "${value}"`);
+      }
+      constants.tokenStandard = value;
+      break;
+    case 'transfer_fee_bps':
+      constants.transferFeeBps = between(0, 10000);
+      break;
+    case 'max_transfer_fee':
+      if (!/^\d+$/.test(value) || BigInt(value) > BigInt('18446744073709551615')) {
+        throw new Error(`max_transfer_fee must be a whole number of token units, got "${value}"`);
+      }
+      constants.maxTransferFee = value;
+      break;
+    default:
+      throw new Error(`Unknown config key "${key}"`);
+  }
+}
+
+// Apply one access block entry, throwing on an unknown instruction or role
+function applyAccess(access: Record<string, AccessRole[]>, instruction: string, value: string): void {
+  if (!instruction) {
+    throw new Error(`Expected instruction => roles in access block, got "${value}"`);
+  }
+  if (!(instruction in DEFAULT_ACCESS)) {
+    throw new Error(`Unknown instruction "${instruction}"; access covers ${Object.keys(DEFAULT_ACCESS).join(', ')}`);
+  }
+  const roles = value.split(',').map(role => role.trim());
+  for (const role of roles) {
+    if (!ACCESS_ROLES.includes(role as AccessRole)) {
+      throw new Error(`Unknown role "${role}" for ${instruction}; roles are ${ACCESS_ROLES.join(', ')}`);
+    }
+  }
+  if (new Set(roles).size !== roles.length) {
+    throw new Error(`${instruction} lists a role twice`);
+  }
+  if (roles.includes('anyone') && roles.length > 1) {
+    throw new Error(`${instruction} is open to anyone; drop the other roles`);
+  }
+  access[instruction] = roles as AccessRole[];
+}
+
+// Parse the list of a `modules = [...]` line, throwing on an unknown or
+// repeated module and on a module missing one it builds on
+function parseModules(list: string): SpecModule[] {
+  const modules = list.split(',').map(name => name.trim()).filter(Boolean);
+  for (const name of modules) {
+    if (!MODULES.includes(name as SpecModule)) {
+      throw new Error(`Unknown module "${name}"; modules are ${MODULES.join(', ')}`);
+    }
+  }
+  if (new Set(modules).size !== modules.length) {
+    throw new Error('modules lists a module twice');
+  }
+  if (modules.includes('governance') && !modules.includes('staking')) {
+    throw new Error('The governance module weights votes by stake and needs the staking module');
+  }
+  return MODULES.filter(name => modules.includes(name));
+}
+
+// Parse an `event` line, throwing on bad syntax or a field named twice
+function parseEvent(line: string): EventConfig {
+  const match = line.match(/^event\s+(\w+)\s+on\s+(\w+)\s*\{([^}]*)\}$/);
+  if (!match) {
+    throw new Error(`Expected event Name on instruction { field, ... }, got "${line}"`);
+  }
+  const [, name, instruction, list] = match;
+  if (!/^[A-Z][A-Za-z0-9]*$/.test(name)) {
+    throw new Error(`Event name ${name} must be PascalCase`);
+  }
+  const fields = list.split(',').map(entry => entry.trim()).filter(Boolean).map(entry => {
+    const field = entry.match(/^([a-z_]\w*)(?:\s*=\s*([a-z_]\w*))?$/);
+    if (!field) throw new Error(`Event ${name}: expected field or field = source, got "${entry}"`);
+    return { name: field[1], source: field[2] ?? field[1] };
+  });
+  if (!fields.length) {
+    throw new Error(`Event ${name} has no fields`);
+  }
+  const duplicate = fields.find((field, i) => fields.findIndex(f => f.name === field.name) !== i);
+  if (duplicate) {
+    throw new Error(`Event ${name} lists ${duplicate.name} twice`);
+  }
+  return { name, instruction, fields };
+}
+
+// Resolve an event's fields against the instructions the spec generates,
+// giving each its IDL type and the Rust expression the instruction builds
+// it from. Optional accounts may be absent, so they are not sources.
+function compileEvent(event: EventConfig, instructions: IdlInstruction[]): { name: string; type: IdlType; value: string }[] {
+  const ix = instructions.find(i => i.name === toCamelCase(event.instruction));
+  if (!ix || event.instruction !== toSnakeCase(ix.name)) {
+    throw new Error(`Event ${event.name}: unknown instruction ${event.instruction}`);
+  }
+  const accounts = ix.accounts.filter(a => !a.isOptional);
+  return event.fields.map(({ name, source }) => {
+    if (source === 'timestamp') {
+      return { name, type: 'i64', value: 'Clock::get()?.unix_timestamp' };
+    }
+    const arg = ix.args.find(a => a.name === toCamelCase(source));
+    if (arg) {
+      const copy = typeof arg.type === 'string' && arg.type !== 'string' && arg.type !== 'bytes';
+      return { name, type: arg.type, value: copy ? source : `${source}.clone()` };
+    }
+    if (accounts.some(a => a.name === toCamelCase(source))) {
+      return { name, type: 'publicKey', value: `ctx.accounts.${source}.key()` };
+    }
+    const sources = [...ix.args, ...accounts].map(item => toSnakeCase(item.name));
+    throw new Error(`Event ${event.name}: ${event.instruction} has no ${source}; fields can name ${[...sources, 'timestamp'].join(', ')}`);
+  });
+}
+
+// Numeric fields of the generated accounts, which invariants may name.
+// Mirrors the #[account] structs of generateAnchorProgram and generateVesting.
+const INVARIANT_FIELDS: Record<string, Record<string, string>> = {
+  TokenLaunch: { total_supply: 'u64', timelock_end: 'i64', insurance_limit: 'u8', fees_collected: 'u64', total_withdrawn: 'u64' },
+  VestingAccount: { total: 'u64', claimed: 'u64', start: 'i64' }
+};
+
+// Numeric constants of the generated `constants` module, with their types
+const INVARIANT_CONSTANTS: Record<string, string> = {
+  LAUNCH_FEE_LAMPORTS: 'u64', INSURANCE_WALLET_FEE_LAMPORTS: 'u64', LOGO_FEE_LAMPORTS: 'u64', TRADING_FEE_LAMPORTS: 'u64',
+  RELOCK_FEE_LAMPORTS: 'u64', MIN_TIMELOCK_DURATION: 'i64', MAX_INSURANCE_WALLETS: 'usize', MAX_INSURANCE_LIMIT: 'u8',
+  MAX_NAME_LEN: 'usize', MAX_SYMBOL_LEN: 'usize',
+  AUTO_SUSPEND_SCORE_BPS: 'u16', TRANSFER_FEE_BPS: 'u16', MAX_TRANSFER_FEE: 'u64'
+};
+
+// Translate an invariant into a Rust condition on the account it names.
+// Operands are widened to i128 so the spec's arithmetic cannot overflow
+// for any u64 values and signed fields compare correctly.
+function compileInvariant(expression: string, scope: { vesting: boolean; token2022: boolean }): { account: string; condition: string } {
+  const tokens = expression.match(/\d[\d_]*|[A-Za-z_]\w*|<=|>=|==|!=|&&|\|\||\S/g) || [];
+  const accounts = new Set<string>();
+  let pos = 0;
+
+  // `grouped` marks a parenthesized result, so redundant parens can be dropped
+  type Operand = { rust: string; bool: boolean; grouped?: boolean };
+  const numeric = (operand: Operand, op: string) => {
+    if (operand.bool) throw new Error(`"${op}" needs numbers on both sides`);
+    return operand.rust;
+  };
+  const boolean = (operand: Operand, op: string) => {
+    if (!operand.bool) throw new Error(`"${op}" needs comparisons on both sides`);
+    return operand.rust;
+  };
+
+  const atom = (): Operand => {
+    const token = tokens[pos++];
+    if (token === undefined) throw new Error('Invariant ends early');
+    if (token === '(') {
+      const inner = or();
+      if (tokens[pos++] !== ')') throw new Error('Invariant is missing a )');
+      return inner.grouped || !/[\s]/.test(inner.rust) ? inner : { ...inner, rust: `(${inner.rust})`, grouped: true };
+    }
+    if (/^\d/.test(token)) {
+      if (BigInt(token.replace(/_/g, '')) > BigInt('18446744073709551615')) throw new Error(`${token} does not fit in a u64`);
+      return { rust: token, bool: false };
+    }
+    if (/^[A-Z][A-Z0-9_]*$/.test(token)) {
+      const type = INVARIANT_CONSTANTS[token];
+      if (!type || (token.includes('TRANSFER_FEE') && !scope.token2022)) throw new Error(`Unknown constant ${token}`);
+      return type === 'usize'
+        ? { rust: `(constants::${token} as i128)`, bool: false, grouped: true }
+        : { rust: `i128::from(constants::${token})`, bool: false };
+    }
+    if (/^[a-z_]\w*$/.test(token)) {
+      const account = Object.keys(INVARIANT_FIELDS)
+        .filter(name => name !== 'VestingAccount' || scope.vesting)
+        .find(name => token in INVARIANT_FIELDS[name]);
+      if (!account) throw new Error(`Unknown field ${token}; invariants can name ${Object.keys(INVARIANT_FIELDS.TokenLaunch).join(', ')}`);
+      accounts.add(account);
+      return { rust: `i128::from(self.${token})`, bool: false };
+    }
+    throw new Error(`Unexpected "${token}" in invariant`);
+  };
+  const binary = (next: () => Operand, ops: string[], operand: (o: Operand, op: string) => string, bool: boolean) => (): Operand => {
+    let left = next();
+    while (ops.includes(tokens[pos])) {
+      const op = tokens[pos++];
+      const right = next();
+      left = { rust: `${operand(left, op)} ${op} ${operand(right, op)}`, bool };
+    }
+    return left;
+  };
+  const product = binary(atom, ['*', '/', '%'], numeric, false);
+  const sum = binary(product, ['+', '-'], numeric, false);
+  const comparison = (): Operand => {
+    const left = sum();
+    if (!['<', '<=', '>', '>=', '==', '!='].includes(tokens[pos])) return left;
+    const op = tokens[pos++];
+    return { rust: `${numeric(left, op)} ${op} ${numeric(sum(), op)}`, bool: true };
+  };
+  const and = binary(comparison, ['&&'], boolean, true);
+  const or = binary(and, ['||'], boolean, true);
+
+  const result = or();
+  if (pos < tokens.length) throw new Error(`Unexpected "${tokens[pos]}" in invariant`);
+  if (!result.bool) throw new Error('Invariant must be a comparison, e.g. claimed <= total');
+  if (accounts.size !== 1) {
+    throw new Error(accounts.size ? `Invariant mixes fields of ${[...accounts].join(' and ')}` : 'Invariant names no account field');
+  }
+  const condition = result.grouped ? result.rust.slice(1, -1) : result.rust;
+  return { account: [...accounts][0], condition };
 }
 
 class SolDParser {
-  private readonly FEE_RECIPIENT = 'GR8TuDpbnDvuLzW4JBCLjbeLvGFs1p21XBytLx6rA7XD';
-  private readonly MIN_TIMELOCK_DAYS = 100;
+  private readonly TEST_MIN_TIMELOCK_SECONDS = 3_600;
   private readonly MAX_SUPPLY = '18446744073709551615';
 
   parse(soldCode: string): SolDConfig {
-    const lines = soldCode.trim().split('\n').map(line => line.trim());
+    const block = extractBlock(soldCode, 'config');
+    const accessBlock = extractBlock(block.rest, 'access');
+    for (const [name, { unclosed }] of Object.entries({ config: block, access: accessBlock })) {
+      if (unclosed) {
+        throw new Error(`${name} block on line ${unclosed.line} is missing its closing }`);
+      }
+    }
+    const lines = accessBlock.rest.trim().split('\n').map(line => line.trim());
     
     // Parse network declaration
     const network = this.parseNetwork(lines[0]);
     
     // Parse HTML tags
-    const config: Partial<SolDConfig> = { network };
+    const config: Partial<SolDConfig> = { network, invariants: [], events: [], modules: [] };
     
     for (const line of lines.slice(1)) {
       if (line.startsWith('<token')) {
@@ -91,8 +720,50 @@ class SolDParser {
         config.relock = this.parseRelock(line);
       } else if (line.startsWith('<fees')) {
         config.fees = this.parseFees(line);
+      } else if (line.startsWith('<budget')) {
+        config.budget = this.parseBudget(line);
+      } else if (line.startsWith('<vesting')) {
+        config.vesting = this.parseVesting(line);
+      } else if (line.startsWith('<deploy')) {
+        config.deploy = this.parseDeploy(line);
+      } else if (line.startsWith('invariant ')) {
+        config.invariants!.push({ expression: line.slice('invariant '.length).trim() });
+      } else if (/^event\b/.test(line)) {
+        config.events!.push(parseEvent(line));
+      } else if (/^modules\b/.test(line)) {
+        const match = line.match(/^modules\s*=\s*\[([^\]]*)\]$/);
+        if (!match) {
+          throw new Error(`Expected modules = [module, ...], got "${line}"`);
+        }
+        if (config.modules!.length) {
+          throw new Error('A spec declares its modules once');
+        }
+        config.modules = parseModules(match[1]);
+      }
+    }
+
+    // The <fees trading> attribute predates the config block, which wins
+    const constants = { ...DEFAULT_CONSTANTS };
+    if (config.fees?.trading !== undefined) {
+      applyConstant(constants, 'trading_fee', config.fees.trading);
+    }
+    for (const entry of block.entries) {
+      applyConstant(constants, entry.key, entry.value);
+      const module = MODULE_KEYS[entry.key];
+      if (module && !config.modules!.includes(module)) {
+        throw new Error(`${entry.key} configures the ${module} module, which the spec does not declare`);
+      }
+    }
+    config.constants = constants;
+
+    const access = { ...DEFAULT_ACCESS };
+    for (const entry of accessBlock.entries) {
+      applyAccess(access, entry.key, entry.value);
+      if (entry.key === 'create_vesting' && !config.vesting) {
+        throw new Error('access lists create_vesting, which needs a <vesting> tag');
       }
     }
+    config.access = access;
 
     this.validateConfig(config as SolDConfig);
     return config as SolDConfig;
@@ -199,13 +870,65 @@ class SolDParser {
     };
   }
 
+  private parseBudget(line: string): BudgetConfig {
+    const cuMatch = line.match(/cu="(\d+)"/);
+    const txSizeMatch = line.match(/txsize="(\d+)"/);
+
+    return {
+      cu: cuMatch ? parseInt(cuMatch[1]) : DEFAULT_CU_BUDGET,
+      txSize: txSizeMatch ? parseInt(txSizeMatch[1]) : MAX_TX_SIZE
+    };
+  }
+
+  private parseVesting(line: string): VestingConfig {
+    const cliffMatch = line.match(/cliff="([^"]+)"/);
+    const durationMatch = line.match(/duration="([^"]+)"/);
+    const intervalMatch = line.match(/interval="([^"]+)"/);
+
+    if (!durationMatch || !intervalMatch) {
+      throw new Error('Vesting must specify duration and interval');
+    }
+
+    return {
+      cliff: cliffMatch ? cliffMatch[1] : '0s',
+      duration: durationMatch[1],
+      interval: intervalMatch[1]
+    };
+  }
+
+  private parseDeploy(line: string): DeployConfig {
+    const authorityMatch = line.match(/authority="([^"]+)"/);
+    const squadsMatch = line.match(/squads="([^"]+)"/);
+    const vaultMatch = line.match(/vault="(\d+)"/);
+
+    if (!authorityMatch && !squadsMatch) {
+      throw new Error('Deploy must specify an upgrade authority or squads multisig');
+    }
+
+    return {
+      authority: authorityMatch?.[1],
+      squads: squadsMatch?.[1],
+      vault: vaultMatch ? parseInt(vaultMatch[1]) : 0
+    };
+  }
+
+  // What the spec's invariants may name
+  invariantScope(config: SolDConfig): { vesting: boolean; token2022: boolean } {
+    return { vesting: !!config.vesting, token2022: config.constants.tokenStandard === 'token2022' };
+  }
+
   private validateConfig(config: SolDConfig): void {
     // Validate timelock minimum
     const duration = this.parseDuration(config.timelock.duration);
-    const minDuration = this.MIN_TIMELOCK_DAYS * 24 * 60 * 60; // 100 days in seconds
+    const minDuration = this.parseDuration(config.constants.minTimelock);
     
-    if (duration < minDuration) {
-      throw new Error(`Timelock duration must be at least ${this.MIN_TIMELOCK_DAYS} days`);
+    // Shorter test-mode locks are only accepted off mainnet, matching the
+    // program's cluster gating
+    if (duration < this.TEST_MIN_TIMELOCK_SECONDS) {
+      throw new Error('Timelock duration must be at least 1 hour');
+    }
+    if (duration < minDuration && config.network === 'MAINNET') {
+      throw new Error(`Timelock duration must be at least ${config.constants.minTimelock} on MAINNET`);
     }
 
     // Validate supply
@@ -216,18 +939,94 @@ class SolDParser {
       throw new Error(`Token supply cannot exceed ${this.MAX_SUPPLY}`);
     }
 
+    // Token identity must fit the space TokenLaunch reserves
+    if (Buffer.byteLength(config.token.name) > config.constants.maxNameLen) {
+      throw new Error(`Token name cannot exceed ${config.constants.maxNameLen} bytes`);
+    }
+    if (Buffer.byteLength(config.token.symbol) > config.constants.maxSymbolLen) {
+      throw new Error(`Token symbol cannot exceed ${config.constants.maxSymbolLen} bytes`);
+    }
+
     // Validate fee recipient
-    if (config.fees.recipient !== this.FEE_RECIPIENT) {
-      throw new Error(`Fee recipient must be ${this.FEE_RECIPIENT}`);
+    if (config.fees.recipient !== config.constants.feeRecipient) {
+      throw new Error(`Fee recipient must be ${config.constants.feeRecipient}`);
+    }
+
+    // Validate insurance limit and wallets
+    if (config.insurance.limit > config.constants.maxInsuranceLimit) {
+      throw new Error(`Insurance withdrawal limit cannot exceed ${config.constants.maxInsuranceLimit}%`);
+    }
+    if (config.insurance.wallets.length > config.constants.maxInsuranceWallets) {
+      throw new Error(`At most ${config.constants.maxInsuranceWallets} insurance wallets are allowed`);
+    }
+
+    // Transfer fees are a Token-2022 extension
+    if (config.constants.tokenStandard !== 'token2022' && (config.constants.transferFeeBps > 0 || config.constants.maxTransferFee !== '0')) {
+      throw new Error('transfer_fee_bps and max_transfer_fee need token_standard = "token2022"');
+    }
+    if (config.constants.transferFeeBps > 0 && config.constants.maxTransferFee === '0') {
+      throw new Error('transfer_fee_bps needs a non-zero max_transfer_fee, or every fee is capped at 0');
+    }
+
+    // The relock escrow is baked into the program as a key
+    try {
+      new PublicKey(config.relock.escrow);
+    } catch {
+      throw new Error(`Relock escrow "${config.relock.escrow}" is not a valid public key`);
+    }
+
+    // Validate the upgrade authority
+    if (config.deploy) {
+      if (config.deploy.authority && config.deploy.squads) {
+        throw new Error('Deploy takes either authority or squads, not both');
+      }
+      for (const key of [config.deploy.authority, config.deploy.squads]) {
+        if (key === undefined) continue;
+        try {
+          new PublicKey(key);
+        } catch {
+          throw new Error(`Deploy key "${key}" is not a valid public key`);
+        }
+      }
+      if (config.deploy.vault > 255) {
+        throw new Error('Squads vault index must be between 0 and 255');
+      }
+    }
+
+    // Validate invariants: each must name the fields of one generated account
+    for (const invariant of config.invariants) {
+      try {
+        compileInvariant(invariant.expression, this.invariantScope(config));
+      } catch (error) {
+        throw new Error(`invariant ${invariant.expression}: ${error.message}`);
+      }
+    }
+
+    // Validate vesting: the cliff and each interval fit inside the duration
+    if (config.vesting) {
+      const cliff = this.parseDuration(config.vesting.cliff);
+      const vestingDuration = this.parseDuration(config.vesting.duration);
+      const interval = this.parseDuration(config.vesting.interval);
+      if (vestingDuration === 0 || interval === 0) {
+        throw new Error('Vesting duration and interval must be non-zero');
+      }
+      if (interval > vestingDuration || cliff > vestingDuration) {
+        throw new Error('Vesting cliff and interval cannot exceed the vesting duration');
+      }
     }
 
-    // Validate insurance limit
-    if (config.insurance.limit > 50) {
-      throw new Error('Insurance withdrawal limit cannot exceed 50%');
+    // Validate events against the instructions the spec generates
+    for (const [i, event] of config.events.entries()) {
+      if (config.events.findIndex(e => e.name === event.name) !== i) {
+        throw new Error(`Event ${event.name} is declared twice`);
+      }
+    }
+    if (config.events.length) {
+      this.generateIdl(config);
     }
   }
 
-  private parseDuration(duration: string): number {
+  parseDuration(duration: string): number {
     const match = duration.match(/^(\d+)([smhd])$/);
     if (!match) {
       throw new Error('Invalid duration format. Use format like "100d", "24h", "60m", "3600s"');
@@ -246,18 +1045,421 @@ class SolDParser {
     return value * multipliers[unit];
   }
 
-  compile(config: SolDConfig): string {
-    return this.generateAnchorProgram(config);
+  // Parse a scenario for `sold simulate`, one step per line:
+  //   launch | warp <duration> | warp past timelock | transfer <amount> | withdraw <amount>
+  // `#` starts a comment; amounts are token base units
+  parseScenario(text: string): ScenarioStep[] {
+    const steps: ScenarioStep[] = [];
+    text.split('\n').forEach((raw, i) => {
+      const line = raw.replace(/#.*/, '').trim();
+      if (!line) return;
+      const [kind, ...args] = line.split(/\s+/);
+      const fail = (message: string) => {
+        throw new Error(`Scenario line ${i + 1}: ${message}`);
+      };
+      if (kind !== 'launch' && kind !== 'warp' && !steps.some(step => step.kind === 'launch')) {
+        fail(`${kind} before launch`);
+      }
+      if (kind === 'launch' && !args.length) {
+        if (steps.some(step => step.kind === 'launch')) fail('the scenario already launched');
+        steps.push({ line: i + 1, kind });
+      } else if (kind === 'warp' && args.join(' ') === 'past timelock') {
+        if (!steps.some(step => step.kind === 'launch')) fail('warp past timelock before launch');
+        steps.push({ line: i + 1, kind });
+      } else if (kind === 'warp' && args.length === 1) {
+        try {
+          steps.push({ line: i + 1, kind, seconds: this.parseDuration(args[0]) });
+        } catch (error) {
+          fail(error.message);
+        }
+      } else if ((kind === 'transfer' || kind === 'withdraw') && args.length === 1) {
+        const amount = args[0].replace(/_/g, '');
+        if (!/^\d+$/.test(amount) || BigInt(amount) > BigInt(this.MAX_SUPPLY)) fail(`${args[0]} is not a u64 amount`);
+        steps.push({ line: i + 1, kind, amount });
+      } else {
+        fail(`expected launch, warp <duration>, warp past timelock, transfer <amount> or withdraw <amount>, got "${line}"`);
+      }
+    });
+    return steps;
+  }
+
+  // With the IDL of a deployed earlier version, the program also gets a
+  // migrate_* instruction per account type whose layout changed since
+  compile(config: SolDConfig, programId = DEFAULT_PROGRAM_ID, migrateFrom?: Idl): string {
+    return this.generateAnchorProgram(config, programId, migrateFrom);
+  }
+
+  // The role-gated instructions the program has, each with its roles and
+  // the error a caller without one gets, the grant-held roles in use, and
+  // the errors access control adds to the program's own
+  accessControl(config: SolDConfig) {
+    const gated = Object.entries(config.access)
+      .filter(([instruction]) => instruction !== 'create_vesting' || config.vesting)
+      .map(([instruction, roles]) => ({
+        instruction,
+        roles,
+        error: roles.length > 1 ? 'Unauthorized' : ROLE_ERRORS[roles[0]]?.name
+      }));
+    const pdaRoles = PDA_ROLES.filter(role => gated.some(({ roles }) => roles.includes(role)));
+    const errors: { name: string; msg: string }[] = [];
+    // UnauthorizedCreator comes with vesting; grants and presales are the
+    // creator's to issue
+    const creatorOnly = pdaRoles.length || config.modules.includes('presale');
+    if (!config.vesting && (creatorOnly || gated.some(({ error }) => error === ROLE_ERRORS.creator.name))) {
+      errors.push(ROLE_ERRORS.creator);
+    }
+    if (gated.some(({ error }) => error === ROLE_ERRORS.oracle.name)) {
+      errors.push(ROLE_ERRORS.oracle);
+    }
+    if (gated.some(({ roles }) => roles.length > 1)) {
+      errors.push({ name: 'Unauthorized', msg: 'Caller holds none of the roles allowed to call this instruction' });
+    }
+    return { gated, pdaRoles, errors };
+  }
+
+  // The IDL `anchor build` would produce for the generated program, built
+  // from the spec alone. Mirrors generateAnchorProgram, generateVesting,
+  // generateAccess, generateModules, tokenBackend and generateMigrations,
+  // so changes to any of them must be reflected here.
+  generateIdl(config: SolDConfig, programId = DEFAULT_PROGRAM_ID, migrateFrom?: Idl): Idl {
+    // PDA seeds as anchor emits them with `seeds = true`
+    const constSeed = (value: string): IdlSeed => ({ kind: 'const', type: 'string', value });
+    const accountSeed = (path: string): IdlSeed => ({ kind: 'account', type: 'publicKey', path });
+    const pdas: Record<string, IdlSeed[]> = {
+      tokenLaunch: [constSeed('launch'), accountSeed('tokenMint')],
+      vesting: [constSeed('vesting'), accountSeed('tokenLaunch'), accountSeed('beneficiary')],
+      vestingVault: [constSeed('vesting_vault'), accountSeed('vesting')]
+    };
+    const account = (name: string, isMut = false, isSigner = false): IdlAccountItem =>
+      pdas[name] ? { name, isMut, isSigner, pda: { seeds: pdas[name] } } : { name, isMut, isSigner };
+    const field = (name: string, type: IdlType): IdlField => ({ name, type });
+    const seed = (name: string, value: string) =>
+      ({ name, type: 'bytes', value: `[${Array.from(Buffer.from(value)).join(', ')}]` });
+
+    const idl: Idl = {
+      version: '0.1.0',
+      name: config.token.symbol.toLowerCase() + '_launch',
+      constants: [seed('LAUNCH', 'launch')],
+      instructions: [
+        {
+          name: 'initializeLaunch',
+          accounts: [account('creator', true, true), account('tokenLaunch', true), account('tokenMint'),
+            account('feeRecipient', true), account('systemProgram')],
+          args: [field('tokenName', 'string'), field('tokenSymbol', 'string'), field('tokenSupply', 'u64'),
+            field('timelockDuration', 'i64'), field('insuranceLimit', 'u8')]
+        },
+        {
+          name: 'transferTokens',
+          accounts: [account('payer', true, true), account('tokenLaunch'), account('tokenMint'), account('from', true),
+            account('to', true), account('authority', false, true), account('feeRecipient', true),
+            account('tokenProgram'), account('systemProgram')],
+          args: [field('amount', 'u64')]
+        },
+        {
+          name: 'emergencyWithdraw',
+          accounts: [account('tokenLaunch', true), account('tokenMint'), account('from', true), account('to', true),
+            account('authority', false, true), account('tokenProgram')],
+          args: [field('amount', 'u64')]
+        },
+        {
+          name: 'relockTokens',
+          accounts: [account('authority', true, true), account('tokenLaunch', true), account('tokenMint'),
+            account('feeRecipient', true), account('systemProgram')],
+          args: [field('newDuration', 'i64')]
+        }
+      ],
+      accounts: [
+        {
+          name: 'TokenLaunch',
+          type: {
+            kind: 'struct',
+            fields: [field('creator', 'publicKey'), field('tokenMint', 'publicKey'), field('tokenName', 'string'),
+              field('tokenSymbol', 'string'), field('totalSupply', 'u64'), field('timelockEnd', 'i64'),
+              field('insuranceWallets', { vec: 'publicKey' }), field('insuranceLimit', 'u8'),
+              field('feesCollected', 'u64'), field('totalWithdrawn', 'u64')]
+          }
+        }
+      ],
+      // The spec's events, once every instruction is in
+      events: [],
+      errors: [
+        { code: 6000, name: 'TimelockActive', msg: 'Timelock is still active' },
+        { code: 6001, name: 'UnauthorizedInsurance', msg: 'Caller is not authorized insurance wallet' },
+        { code: 6002, name: 'ExceedsInsuranceLimit', msg: 'Amount exceeds insurance withdrawal limit' },
+        { code: 6003, name: 'UnauthorizedRelock', msg: 'Caller is not authorized to relock' },
+        { code: 6004, name: 'NameTooLong', msg: 'Token name is longer than the account holds' },
+        { code: 6005, name: 'SymbolTooLong', msg: 'Token symbol is longer than the account holds' }
+      ],
+      metadata: { address: programId }
+    };
+
+    if (config.constants.tokenStandard === 'token2022') {
+      idl.instructions.push({
+        name: 'createMint',
+        accounts: [account('creator', true, true), account('tokenMint', true, true), account('tokenProgram'),
+          account('systemProgram')],
+        args: [field('decimals', 'u8')]
+      });
+    }
+
+    if (config.vesting) {
+      idl.constants.push(seed('VESTING', 'vesting'), seed('VESTING_VAULT', 'vesting_vault'));
+      idl.instructions.push(
+        {
+          name: 'createVesting',
+          accounts: [account('creator', true, true), account('tokenLaunch'), account('tokenMint'), account('beneficiary'),
+            account('vesting', true), account('vestingVault', true), account('creatorTokens', true),
+            account('tokenProgram'), account('systemProgram'), account('rent')],
+          args: [field('amount', 'u64')]
+        },
+        {
+          name: 'claimVested',
+          accounts: [account('beneficiary', false, true), account('tokenLaunch'), account('tokenMint'),
+            account('vesting', true), account('vestingVault', true), account('beneficiaryTokens', true),
+            account('tokenProgram')],
+          args: []
+        }
+      );
+      idl.accounts.push({
+        name: 'VestingAccount',
+        type: {
+          kind: 'struct',
+          fields: [field('launch', 'publicKey'), field('beneficiary', 'publicKey'), field('total', 'u64'),
+            field('claimed', 'u64'), field('start', 'i64'), field('bump', 'u8')]
+        }
+      });
+      for (const { name, msg } of [ROLE_ERRORS.creator, { name: 'NothingToClaim', msg: 'No vested tokens to claim' }]) {
+        idl.errors.push({ code: 6000 + idl.errors.length, name, msg });
+      }
+    }
+
+    // Grant accounts are optional on the instructions their role may call;
+    // granting and revoking them is the creator's
+    const { gated, pdaRoles, errors } = this.accessControl(config);
+    for (const { instruction, roles } of gated) {
+      const ix = idl.instructions.find(i => i.name === toCamelCase(instruction))!;
+      for (const role of roles.filter(role => PDA_ROLES.includes(role))) {
+        ix.accounts.push({ name: `${role}Role`, isMut: false, isSigner: false, isOptional: true });
+      }
+    }
+    for (const role of pdaRoles) {
+      pdas[`${role}Role`] = [constSeed(`${role}_role`), accountSeed('tokenLaunch'), accountSeed('member')];
+      idl.constants.push(seed(`${role.toUpperCase()}_ROLE`, `${role}_role`));
+      idl.instructions.push(
+        {
+          name: `grant${toPascalCase(role)}`,
+          accounts: [account('creator', true, true), account('tokenLaunch'), account('tokenMint'), account('member'),
+            account(`${role}Role`, true), account('systemProgram')],
+          args: []
+        },
+        {
+          name: `revoke${toPascalCase(role)}`,
+          accounts: [account('creator', true, true), account('tokenLaunch'), account('tokenMint'), account('member'),
+            account(`${role}Role`, true)],
+          args: []
+        }
+      );
+    }
+    if (pdaRoles.length) {
+      idl.accounts.push({
+        name: 'RoleGrant',
+        type: { kind: 'struct', fields: [field('launch', 'publicKey'), field('member', 'publicKey'), field('bump', 'u8')] }
+      });
+    }
+    for (const { name, msg } of errors) {
+      idl.errors.push({ code: 6000 + idl.errors.length, name, msg });
+    }
+
+    // Module instruction groups, as generateModules emits them
+    const pda = (name: string, isMut: boolean, ...seeds: IdlSeed[]): IdlAccountItem => ({ name, isMut, isSigner: false, pda: { seeds } });
+    const stake = (owner: string, isMut = false) => pda('stake', isMut, constSeed('stake'), accountSeed('tokenLaunch'), accountSeed(owner));
+    if (config.modules.includes('staking')) {
+      const vault = pda('stakeVault', true, constSeed('stake_vault'), accountSeed('stake'));
+      idl.constants.push(seed('STAKE', 'stake'), seed('STAKE_VAULT', 'stake_vault'));
+      idl.instructions.push(
+        {
+          name: 'openStake',
+          accounts: [account('owner', true, true), account('tokenLaunch'), account('tokenMint'), stake('owner', true), vault,
+            account('tokenProgram'), account('systemProgram'), account('rent')],
+          args: []
+        },
+        ...['stake', 'unstake'].map(name => ({
+          name,
+          accounts: [account('owner', false, true), account('tokenLaunch'), account('tokenMint'), stake('owner', true), vault,
+            account('ownerTokens', true), account('tokenProgram')],
+          args: [field('amount', 'u64')]
+        }))
+      );
+      idl.accounts.push({
+        name: 'StakePosition',
+        type: {
+          kind: 'struct',
+          fields: [field('launch', 'publicKey'), field('owner', 'publicKey'), field('amount', 'u64'),
+            field('lockedUntil', 'i64'), field('bump', 'u8')]
+        }
+      });
+    }
+    if (config.modules.includes('governance')) {
+      idl.constants.push(seed('VOTE', 'vote'));
+      idl.instructions.push(
+        {
+          name: 'createProposal',
+          accounts: [account('proposer', true, true), account('tokenLaunch'), account('tokenMint'), stake('proposer'),
+            account('proposal', true, true), account('systemProgram')],
+          args: [field('description', 'string')]
+        },
+        {
+          name: 'vote',
+          accounts: [account('voter', true, true), account('tokenLaunch'), account('tokenMint'), account('proposal', true),
+            stake('voter', true), pda('voteRecord', true, constSeed('vote'), accountSeed('proposal'), accountSeed('voter')),
+            account('systemProgram')],
+          args: [field('approve', 'bool')]
+        },
+        {
+          name: 'finalizeProposal',
+          accounts: [account('tokenLaunch'), account('tokenMint'), account('proposal', true)],
+          args: []
+        }
+      );
+      idl.accounts.push(
+        {
+          name: 'Proposal',
+          type: {
+            kind: 'struct',
+            fields: [field('launch', 'publicKey'), field('proposer', 'publicKey'), field('description', 'string'),
+              field('yesVotes', 'u64'), field('noVotes', 'u64'), field('votingEnds', 'i64'), field('finalized', 'bool'),
+              field('passed', 'bool')]
+          }
+        },
+        {
+          name: 'VoteRecord',
+          type: {
+            kind: 'struct',
+            fields: [field('proposal', 'publicKey'), field('voter', 'publicKey'), field('weight', 'u64'),
+              field('approve', 'bool'), field('bump', 'u8')]
+          }
+        }
+      );
+    }
+    if (config.modules.includes('presale')) {
+      const presale = (isMut: boolean) => pda('presale', isMut, constSeed('presale'), accountSeed('tokenLaunch'));
+      const vault = pda('presaleVault', true, constSeed('presale_vault'), accountSeed('presale'));
+      idl.constants.push(seed('PRESALE', 'presale'), seed('PRESALE_VAULT', 'presale_vault'));
+      idl.instructions.push(
+        {
+          name: 'openPresale',
+          accounts: [account('creator', true, true), account('tokenLaunch'), account('tokenMint'), presale(true), vault,
+            account('creatorTokens', true), account('tokenProgram'), account('systemProgram'), account('rent')],
+          args: [field('amount', 'u64')]
+        },
+        {
+          name: 'buyPresale',
+          accounts: [account('buyer', true, true), account('tokenLaunch'), account('tokenMint'), account('creator', true),
+            presale(true), vault, account('buyerTokens', true), account('tokenProgram'), account('systemProgram')],
+          args: [field('amount', 'u64')]
+        },
+        {
+          name: 'closePresale',
+          accounts: [account('creator', false, true), account('tokenLaunch'), account('tokenMint'), presale(false), vault,
+            account('creatorTokens', true), account('tokenProgram')],
+          args: []
+        }
+      );
+      idl.accounts.push({
+        name: 'Presale',
+        type: {
+          kind: 'struct',
+          fields: [field('launch', 'publicKey'), field('sold', 'u64'), field('ends', 'i64'), field('bump', 'u8')]
+        }
+      });
+    }
+    for (const { name, msg } of config.modules.flatMap(module => MODULE_ERRORS[module])) {
+      idl.errors.push({ code: 6000 + idl.errors.length, name, msg });
+    }
+
+    if (config.invariants.length) {
+      idl.errors.push({ code: 6000 + idl.errors.length, name: 'InvariantViolated', msg: 'Account violates a spec invariant' });
+    }
+
+    const c = config.constants;
+    idl.constants.push(
+      { name: 'FEE_RECIPIENT', type: 'publicKey', value: c.feeRecipient },
+      { name: 'RELOCK_ESCROW', type: 'publicKey', value: config.relock.escrow },
+      { name: 'LAUNCH_FEE_LAMPORTS', type: 'u64', value: String(c.launchFee) },
+      { name: 'INSURANCE_WALLET_FEE_LAMPORTS', type: 'u64', value: String(c.insuranceWalletFee) },
+      { name: 'LOGO_FEE_LAMPORTS', type: 'u64', value: String(c.logoFee) },
+      { name: 'TRADING_FEE_LAMPORTS', type: 'u64', value: String(c.tradingFee) },
+      { name: 'RELOCK_FEE_LAMPORTS', type: 'u64', value: String(c.relockFee) },
+      { name: 'MIN_TIMELOCK_DURATION', type: 'i64', value: String(this.parseDuration(c.minTimelock)) },
+      { name: 'MAX_INSURANCE_WALLETS', type: 'usize', value: String(c.maxInsuranceWallets) },
+      { name: 'MAX_INSURANCE_LIMIT', type: 'u8', value: String(c.maxInsuranceLimit) },
+      { name: 'MAX_NAME_LEN', type: 'usize', value: String(c.maxNameLen) },
+      { name: 'MAX_SYMBOL_LEN', type: 'usize', value: String(c.maxSymbolLen) },
+      { name: 'AUTO_SUSPEND_SCORE_BPS', type: 'u16', value: String(Math.round(c.autoSuspendThreshold * 10000)) },
+      { name: 'TOKEN_PROGRAM', type: 'publicKey', value: this.tokenBackend(config).programId }
+    );
+    if (c.tokenStandard === 'token2022') {
+      idl.constants.push(
+        { name: 'TRANSFER_FEE_BPS', type: 'u16', value: String(c.transferFeeBps) },
+        { name: 'MAX_TRANSFER_FEE', type: 'u64', value: c.maxTransferFee }
+      );
+    }
+    if (config.modules.includes('staking')) {
+      idl.constants.push({ name: 'STAKE_LOCKUP', type: 'i64', value: String(this.parseDuration(c.stakeLockup)) });
+    }
+    if (config.modules.includes('governance')) {
+      idl.constants.push(
+        { name: 'VOTING_PERIOD', type: 'i64', value: String(this.parseDuration(c.votingPeriod)) },
+        { name: 'QUORUM_BPS', type: 'u16', value: String(c.quorumBps) }
+      );
+    }
+    if (config.modules.includes('presale')) {
+      idl.constants.push(
+        { name: 'PRESALE_PRICE_LAMPORTS', type: 'u64', value: String(c.presalePrice) },
+        { name: 'PRESALE_DURATION', type: 'i64', value: String(this.parseDuration(c.presaleDuration)) }
+      );
+    }
+
+    for (const event of config.events) {
+      const fields = compileEvent(event, idl.instructions);
+      idl.events.push({ name: event.name, fields: fields.map(({ name, type }) => ({ name: toCamelCase(name), type, index: false })) });
+    }
+
+    if (migrateFrom) {
+      for (const { account: name } of new IdlDiffer().migrations(migrateFrom, idl)) {
+        idl.instructions.push({
+          name: `migrate${name}`,
+          accounts: [account('payer', true, true), account('target', true), account('systemProgram')],
+          args: []
+        });
+      }
+    }
+
+    return idl;
   }
 
-  private generateAnchorProgram(config: SolDConfig): string {
+  private generateAnchorProgram(config: SolDConfig, programId: string, migrateFrom?: Idl): string {
     const programName = config.token.symbol.toLowerCase() + '_launch';
+    const token = this.tokenBackend(config);
+    const invariants = this.generateInvariants(config);
+    const access = this.generateAccess(config, token);
+    const vesting = this.generateVesting(config, token, invariants.check, access);
+    const modules = this.generateModules(config, token);
+    const migrations = this.generateMigrations(config, programId, migrateFrom);
+    const events = this.generateEvents(config, this.generateIdl(config, programId, migrateFrom).instructions);
+    const c = config.constants;
+    const walletCount = config.insurance.wallets.length;
+    const launchFee = [
+      'constants::LAUNCH_FEE_LAMPORTS',
+      ...(walletCount ? [`${walletCount > 1 ? `${walletCount} * ` : ''}constants::INSURANCE_WALLET_FEE_LAMPORTS`] : []),
+      ...(config.logo.nft ? ['constants::LOGO_FEE_LAMPORTS'] : [])
+    ].join(' + ');
     
-    return `use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint};
+    return events.emit(`use anchor_lang::prelude::*;
+${token.imports}
 use anchor_spl::associated_token::AssociatedToken;
 
-declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+declare_id!("${programId}");
 
 #[program]
 pub mod ${programName} {
@@ -271,6 +1473,9 @@ pub mod ${programName} {
         timelock_duration: i64,
         insurance_limit: u8,
     ) -> Result<()> {
+        require!(token_name.len() <= constants::MAX_NAME_LEN, ErrorCode::NameTooLong);
+        require!(token_symbol.len() <= constants::MAX_SYMBOL_LEN, ErrorCode::SymbolTooLong);
+
         let launch = &mut ctx.accounts.token_launch;
         launch.creator = ctx.accounts.creator.key();
         launch.token_mint = ctx.accounts.token_mint.key();
@@ -281,9 +1486,10 @@ pub mod ${programName} {
         launch.insurance_limit = insurance_limit;
         launch.insurance_wallets = vec![${config.insurance.wallets.map(w => `"${w}".parse().unwrap()`).join(', ')}];
         launch.fees_collected = 0;
+        launch.total_withdrawn = 0;
         
-        // Collect launch fee
-        let fee_amount = ${this.calculateLaunchFee(config)};
+        // Collect launch fee: base, per insurance wallet${config.logo.nft ? ' and logo' : ''}
+        let fee_amount = ${launchFee};
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
@@ -295,7 +1501,7 @@ pub mod ${programName} {
         
         launch.fees_collected = fee_amount;
         
-        Ok(())
+${invariants.check('TokenLaunch', 'launch')}        Ok(())
     }
 
     pub fn transfer_tokens(
@@ -311,7 +1517,7 @@ pub mod ${programName} {
         }
         
         // Collect trading fee
-        let trading_fee = ${config.fees.trading || '5000'}; // lamports
+        let trading_fee = constants::TRADING_FEE_LAMPORTS;
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
@@ -322,14 +1528,7 @@ pub mod ${programName} {
         anchor_lang::system_program::transfer(cpi_context, trading_fee)?;
 
         // Transfer tokens
-        let cpi_accounts = token::Transfer {
-            from: ctx.accounts.from.to_account_info(),
-            to: ctx.accounts.to.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+${token.transfer('ctx.accounts.from', 'ctx.accounts.to', 'ctx.accounts.authority', 'amount')}
 
         Ok(())
     }
@@ -338,31 +1537,20 @@ pub mod ${programName} {
         ctx: Context<EmergencyWithdraw>,
         amount: u64,
     ) -> Result<()> {
-        let launch = &ctx.accounts.token_launch;
-        
-        // Verify caller is authorized insurance wallet
-        let caller = ctx.accounts.authority.key();
-        if !launch.insurance_wallets.contains(&caller) {
-            return Err(ErrorCode::UnauthorizedInsurance.into());
-        }
+        let launch = &mut ctx.accounts.token_launch;
         
-        // Check withdrawal limit
+        // Check withdrawal limit, across every withdrawal so far
         let max_withdraw = (launch.total_supply * launch.insurance_limit as u64) / 100;
-        if amount > max_withdraw {
+        let total_withdrawn = launch.total_withdrawn.saturating_add(amount);
+        if total_withdrawn > max_withdraw {
             return Err(ErrorCode::ExceedsInsuranceLimit.into());
         }
+        launch.total_withdrawn = total_withdrawn;
 
         // Execute withdrawal
-        let cpi_accounts = token::Transfer {
-            from: ctx.accounts.from.to_account_info(),
-            to: ctx.accounts.to.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+${token.transfer('ctx.accounts.from', 'ctx.accounts.to', 'ctx.accounts.authority', 'amount')}
 
-        Ok(())
+${invariants.check('TokenLaunch', 'ctx.accounts.token_launch')}        Ok(())
     }
 
     pub fn relock_tokens(
@@ -371,16 +1559,11 @@ pub mod ${programName} {
     ) -> Result<()> {
         let launch = &mut ctx.accounts.token_launch;
         
-        // Only escrow can relock
-        if ctx.accounts.authority.key() != "${config.relock.escrow}".parse().unwrap() {
-            return Err(ErrorCode::UnauthorizedRelock.into());
-        }
-        
         // Extend timelock
         launch.timelock_end = Clock::get()?.unix_timestamp + new_duration;
         
         // Collect relock fee
-        let relock_fee = 20000000; // 0.02 SOL in lamports
+        let relock_fee = constants::RELOCK_FEE_LAMPORTS;
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
@@ -390,9 +1573,9 @@ pub mod ${programName} {
         );
         anchor_lang::system_program::transfer(cpi_context, relock_fee)?;
         
-        Ok(())
+${invariants.check('TokenLaunch', 'ctx.accounts.token_launch')}        Ok(())
     }
-}
+${token.instructions}${vesting.instructions}${access.instructions}${modules.instructions}${migrations.instructions}}
 
 #[derive(Accounts)]
 pub struct InitializeLaunch<'info> {
@@ -403,15 +1586,15 @@ pub struct InitializeLaunch<'info> {
         init,
         payer = creator,
         space = TokenLaunch::space(),
-        seeds = [b"launch", token_mint.key().as_ref()],
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
         bump
     )]
     pub token_launch: Account<'info, TokenLaunch>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: ${token.mint},
     
     /// CHECK: Fee recipient address is validated in instruction
-    #[account(mut, address = "${this.FEE_RECIPIENT}".parse().unwrap())]
+    #[account(mut, address = constants::FEE_RECIPIENT)]
     pub fee_recipient: AccountInfo<'info>,
     
     pub system_program: Program<'info, System>,
@@ -423,99 +1606,115 @@ pub struct TransferTokens<'info> {
     pub payer: Signer<'info>,
     
     #[account(
-        seeds = [b"launch", token_mint.key().as_ref()],
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
         bump
     )]
     pub token_launch: Account<'info, TokenLaunch>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: ${token.mint},
     
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: ${token.tokenAccount},
     
     #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    pub to: ${token.tokenAccount},
     
-    pub authority: Signer<'info>,
+${access.signer('transfer_tokens', 'authority')}
     
     /// CHECK: Fee recipient address is validated
-    #[account(mut, address = "${this.FEE_RECIPIENT}".parse().unwrap())]
+    #[account(mut, address = constants::FEE_RECIPIENT)]
     pub fee_recipient: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: ${token.tokenProgram},
     pub system_program: Program<'info, System>,
-}
+${access.grants('transfer_tokens', 'authority')}}
 
 #[derive(Accounts)]
 pub struct EmergencyWithdraw<'info> {
     #[account(
-        seeds = [b"launch", token_mint.key().as_ref()],
+        mut,
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
         bump
     )]
     pub token_launch: Account<'info, TokenLaunch>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: ${token.mint},
     
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: ${token.tokenAccount},
     
     #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    pub to: ${token.tokenAccount},
     
-    pub authority: Signer<'info>,
+${access.signer('emergency_withdraw', 'authority')}
     
-    pub token_program: Program<'info, Token>,
-}
+    pub token_program: ${token.tokenProgram},
+${access.grants('emergency_withdraw', 'authority')}}
 
 #[derive(Accounts)]
 pub struct RelockTokens<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+${access.signer('relock_tokens', 'authority', true)}
     
     #[account(
         mut,
-        seeds = [b"launch", token_mint.key().as_ref()],
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
         bump
     )]
     pub token_launch: Account<'info, TokenLaunch>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: ${token.mint},
     
     /// CHECK: Fee recipient address is validated
-    #[account(mut, address = "${this.FEE_RECIPIENT}".parse().unwrap())]
+    #[account(mut, address = constants::FEE_RECIPIENT)]
     pub fee_recipient: AccountInfo<'info>,
     
     pub system_program: Program<'info, System>,
-}
-
+${access.grants('relock_tokens', 'authority')}}
+${token.accounts}
 #[account]
+#[derive(InitSpace)]
 pub struct TokenLaunch {
     pub creator: Pubkey,
     pub token_mint: Pubkey,
+    #[max_len(${c.maxNameLen})]
     pub token_name: String,
+    #[max_len(${c.maxSymbolLen})]
     pub token_symbol: String,
     pub total_supply: u64,
     pub timelock_end: i64,
+    #[max_len(${c.maxInsuranceWallets})]
     pub insurance_wallets: Vec<Pubkey>,
     pub insurance_limit: u8,
     pub fees_collected: u64,
+    pub total_withdrawn: u64,
 }
-
+${vesting.accounts}${access.accounts}${modules.accounts}${migrations.accounts}
 impl TokenLaunch {
     pub fn space() -> usize {
-        8 + // discriminator
-        32 + // creator
-        32 + // token_mint  
-        (4 + 32) + // token_name (String)
-        (4 + 10) + // token_symbol (String)
-        8 + // total_supply
-        8 + // timelock_end
-        (4 + 32 * 10) + // insurance_wallets (max 10)
-        1 + // insurance_limit
-        8 // fees_collected
+        8 + Self::INIT_SPACE // discriminator + fields
     }
 }
 
+// #[max_len] takes literals, so tie them to the constants initialize_launch
+// validates against; a config change that misses one fails to compile
+const _: () = assert!(constants::MAX_NAME_LEN == ${c.maxNameLen});
+const _: () = assert!(constants::MAX_SYMBOL_LEN == ${c.maxSymbolLen});
+const _: () = assert!(constants::MAX_INSURANCE_WALLETS == ${c.maxInsuranceWallets});
+${invariants.impls}
+// PDA seed registry, published in the IDL as constants
+pub mod seeds {
+    use anchor_lang::prelude::*;
+
+    /// TokenLaunch: [LAUNCH, token_mint]
+    #[constant]
+    pub const LAUNCH: &[u8] = b"launch";
+
+    pub fn find_launch(token_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[LAUNCH, token_mint.as_ref()], &crate::ID)
+    }
+${vesting.seeds}${access.seeds}${modules.seeds}}
+${events.module}
+${this.generateConstants(config)}${migrations.module}
 #[error_code]
 pub enum ErrorCode {
     #[msg("Timelock is still active")]
@@ -526,109 +1725,5791 @@ pub enum ErrorCode {
     ExceedsInsuranceLimit,
     #[msg("Caller is not authorized to relock")]
     UnauthorizedRelock,
-}`;
+    #[msg("Token name is longer than the account holds")]
+    NameTooLong,
+    #[msg("Token symbol is longer than the account holds")]
+    SymbolTooLong,
+${vesting.errors}${access.errors}${modules.errors}${invariants.error}}
+${vesting.tests}`);
   }
 
-  private calculateLaunchFee(config: SolDConfig): number {
-    const baseFee = 10000000; // 0.01 SOL in lamports
-    const insuranceFee = config.insurance.wallets.length * 10000000; // 0.01 SOL per wallet
-    const logoFee = config.logo.nft ? 5000000 : 0; // 0.005 SOL for logo
+  // Role checks as account constraints: the acting signer gets a
+  // constraint (or has_one on the launch, for a creator-only signer named
+  // creator), and grant-held roles an optional grant account. Grant-held
+  // roles also get creator-only grant_<role>/revoke_<role> instructions.
+  private generateAccess(config: SolDConfig, token: TokenBackend) {
+    const { gated, pdaRoles, errors } = this.accessControl(config);
+    const rolesOf = (instruction: string) => gated.find(g => g.instruction === instruction)!;
+    const check = (role: AccessRole, signer: string) => ({
+      creator: `token_launch.creator == ${signer}.key()`,
+      insurance: `token_launch.insurance_wallets.contains(&${signer}.key())`,
+      escrow: `${signer}.key() == constants::RELOCK_ESCROW`,
+      oracle: `oracle_role.is_some()`,
+      anyone: ''
+    })[role];
+    const attribute = (items: string[]) => {
+      if (!items.length) return '';
+      return items.join(', ').length <= 80
+        ? `    #[account(${items.join(', ')})]\n`
+        : `    #[account(\n${items.map(item => `        ${item}`).join(',\n')}\n    )]\n`;
+    };
+
+    const signer = (instruction: string, name: string, mut = false) => {
+      const { roles, error } = rolesOf(instruction);
+      const items = mut ? ['mut'] : [];
+      if (roles.length === 1 && roles[0] === 'escrow') {
+        items.push(`address = constants::RELOCK_ESCROW @ ErrorCode::${error}`);
+      } else if (roles[0] !== 'anyone' && !(roles.length === 1 && roles[0] === 'creator' && name === 'creator')) {
+        items.push(`constraint = ${roles.map(role => check(role, name)).join(' || ')} @ ErrorCode::${error}`);
+      }
+      return `${attribute(items)}    pub ${name}: Signer<'info>,`;
+    };
+    const launch = (instruction: string, signerName: string) => {
+      const { roles } = rolesOf(instruction);
+      return roles.length === 1 && roles[0] === 'creator' && signerName === 'creator'
+        ? ',\n        has_one = creator @ ErrorCode::UnauthorizedCreator'
+        : '';
+    };
+    const grants = (instruction: string, signerName: string) => rolesOf(instruction).roles
+      .filter(role => PDA_ROLES.includes(role))
+      .map(role => `    
+    #[account(
+        seeds = [seeds::${role.toUpperCase()}_ROLE, token_launch.key().as_ref(), ${signerName}.key().as_ref()],
+        bump = ${role}_role.bump
+    )]
+    pub ${role}_role: Option<Account<'info, RoleGrant>>,
+`).join('');
+
+    const instructions = pdaRoles.map(role => `
+    // Give a wallet the ${role} role on this launch (creator only)
+    pub fn grant_${role}(ctx: Context<Grant${toPascalCase(role)}>) -> Result<()> {
+        let grant = &mut ctx.accounts.${role}_role;
+        grant.launch = ctx.accounts.token_launch.key();
+        grant.member = ctx.accounts.member.key();
+        grant.bump = ctx.bumps.${role}_role;
+        Ok(())
+    }
+
+    // Take the ${role} role back; closing the grant refunds its rent
+    pub fn revoke_${role}(_ctx: Context<Revoke${toPascalCase(role)}>) -> Result<()> {
+        Ok(())
+    }
+`).join('');
+
+    const creatorLaunch = `    #[account(mut)]
+    pub creator: Signer<'info>,
     
-    return baseFee + insuranceFee + logoFee;
-  }
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        has_one = creator @ ErrorCode::UnauthorizedCreator
+    )]
+    pub token_launch: Account<'info, TokenLaunch>,
+    
+    pub token_mint: ${token.mint},
+    `;
+    const accounts = pdaRoles.map(role => `
+#[derive(Accounts)]
+pub struct Grant${toPascalCase(role)}<'info> {
+${creatorLaunch}
+    /// CHECK: Any wallet can hold a role
+    pub member: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = RoleGrant::space(),
+        seeds = [seeds::${role.toUpperCase()}_ROLE, token_launch.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub ${role}_role: Account<'info, RoleGrant>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-// CLI Interface
-export class SolDCLI {
-  private parser = new SolDParser();
+#[derive(Accounts)]
+pub struct Revoke${toPascalCase(role)}<'info> {
+${creatorLaunch}
+    /// CHECK: The wallet losing the role
+    pub member: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = creator,
+        seeds = [seeds::${role.toUpperCase()}_ROLE, token_launch.key().as_ref(), member.key().as_ref()],
+        bump = ${role}_role.bump
+    )]
+    pub ${role}_role: Account<'info, RoleGrant>,
+}
+`).join('') + (pdaRoles.length ? `
+// One wallet's grant of a role on one launch; the grant existing is the role
+#[account]
+#[derive(InitSpace)]
+pub struct RoleGrant {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub bump: u8,
+}
 
-  async compile(inputFile: string, outputDir: string): Promise<void> {
-    try {
-      const soldCode = fs.readFileSync(inputFile, 'utf8');
-      const config = this.parser.parse(soldCode);
-      const anchorCode = this.parser.compile(config);
-      
-      // Create output directory structure
-      const programDir = path.join(outputDir, 'programs', config.token.symbol.toLowerCase() + '_launch');
-      fs.mkdirSync(programDir, { recursive: true });
-      
-      // Write lib.rs
-      fs.writeFileSync(path.join(programDir, 'src', 'lib.rs'), anchorCode);
-      
-      // Write Cargo.toml
-      const cargoToml = this.generateCargoToml(config);
-      fs.writeFileSync(path.join(programDir, 'Cargo.toml'), cargoToml);
-      
-      // Write Anchor.toml
-      const anchorToml = this.generateAnchorToml(config);
-      fs.writeFileSync(path.join(outputDir, 'Anchor.toml'), anchorToml);
-      
-      console.log(`✅ Successfully compiled SolD to Anchor program`);
-      console.log(`📁 Output directory: ${outputDir}`);
-      console.log(`🚀 Run 'anchor build' to compile the program`);
-      
-    } catch (error) {
-      console.error('❌ Compilation failed:', error.message);
-      process.exit(1);
+impl RoleGrant {
+    pub fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
     }
-  }
+}
+` : '');
 
-  private generateCargoToml(config: SolDConfig): string {
-    return `[package]
-name = "${config.token.symbol.toLowerCase()}_launch"
-version = "0.1.0"
-description = "Generated by SolD - ${config.token.name} token launch"
-edition = "2021"
+    const seeds = pdaRoles.map(role => `
+    /// RoleGrant of the ${role} role: [${role.toUpperCase()}_ROLE, launch, member]
+    #[constant]
+    pub const ${role.toUpperCase()}_ROLE: &[u8] = b"${role}_role";
+`).join('');
 
-[lib]
-crate-type = ["cdylib", "lib"]
-name = "${config.token.symbol.toLowerCase()}_launch"
+    return {
+      signer,
+      launch,
+      grants,
+      instructions,
+      accounts,
+      seeds,
+      errors: errors.map(({ name, msg }) => `    #[msg("${msg}")]\n    ${name},\n`).join('')
+    };
+  }
 
-[features]
-no-entrypoint = []
-no-idl = []
-no-log-ix-name = []
-cpi = ["no-entrypoint"]
-default = []
+  // check_invariants() for each account the spec's invariants name, and
+  // the call that ends every instruction writing one of those accounts
+  private generateInvariants(config: SolDConfig) {
+    const scope = this.invariantScope(config);
+    const checks: Record<string, string[]> = {};
+    for (const { expression } of config.invariants) {
+      const { account, condition } = compileInvariant(expression, scope);
+      (checks[account] = checks[account] || []).push(`        // ${expression}
+        require!(${condition}, ErrorCode::InvariantViolated);
+`);
+    }
 
-[dependencies]
-anchor-lang = "0.28.0"
-anchor-spl = "0.28.0"`;
+    const impls = Object.entries(checks).map(([account, requires]) => `
+impl ${account} {
+    /// The spec's invariants, checked by every instruction that writes the account
+    pub fn check_invariants(&self) -> Result<()> {
+${requires.join('')}        Ok(())
+    }
+}
+`).join('');
+    return {
+      impls,
+      error: config.invariants.length ? '    #[msg("Account violates a spec invariant")]\n    InvariantViolated,\n' : '',
+      check: (account: string, target: string) => checks[account] ? `        ${target}.check_invariants()?;\n\n` : ''
+    };
   }
 
-  private generateAnchorToml(config: SolDConfig): string {
-    const network = config.network.toLowerCase();
-    return `[features]
-seeds = false
-skip-lint = false
+  // Vesting accounts, create/claim instructions and unit tests, emitted
+  // only when the spec has a <vesting> tag. The schedule is baked into the
+  // program as constants so it cannot change after deploy.
+  private generateVesting(config: SolDConfig, token: TokenBackend, checkInvariants: (account: string, target: string) => string,
+    access: AccessChecks) {
+    if (!config.vesting) {
+      return { instructions: '', accounts: '', seeds: '', errors: '', tests: '' };
+    }
+    const cliff = this.parseDuration(config.vesting.cliff);
+    const duration = this.parseDuration(config.vesting.duration);
+    const interval = this.parseDuration(config.vesting.interval);
 
-[programs.${network}]
-${config.token.symbol.toLowerCase()}_launch = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+    const instructions = `
+    // Escrow a beneficiary's allocation; it vests from the timelock end
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        amount: u64,
+    ) -> Result<()> {
+        let launch = &ctx.accounts.token_launch;
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.launch = launch.key();
+        vesting.beneficiary = ctx.accounts.beneficiary.key();
+        vesting.total = amount;
+        vesting.claimed = 0;
+        vesting.start = launch.timelock_end;
+        vesting.bump = ctx.bumps.vesting;
 
-[registry]
-url = "https://api.apr.dev"
+${token.transfer('ctx.accounts.creator_tokens', 'ctx.accounts.vesting_vault', 'ctx.accounts.creator', 'amount')}
 
-[provider]
-cluster = "${network}"
-wallet = "~/.config/solana/id.json"
+${checkInvariants('VestingAccount', 'ctx.accounts.vesting')}        Ok(())
+    }
 
-[scripts]
-test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"`;
-  }
+    // Release everything vested so far to the beneficiary
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let vesting = &mut ctx.accounts.vesting;
+        let claimable = vesting.vested_amount(Clock::get()?.unix_timestamp) - vesting.claimed;
+        if claimable == 0 {
+            return Err(ErrorCode::NothingToClaim.into());
+        }
+        vesting.claimed += claimable;
+
+        let launch_key = vesting.launch;
+        let beneficiary_key = vesting.beneficiary;
+        let bump = [vesting.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::VESTING, launch_key.as_ref(), beneficiary_key.as_ref(), &bump]];
+${token.transfer('ctx.accounts.vesting_vault', 'ctx.accounts.beneficiary_tokens', 'vesting', 'claimable', 'signer_seeds')}
+
+${checkInvariants('VestingAccount', 'ctx.accounts.vesting')}        Ok(())
+    }
+`;
+
+    const accounts = `
+#[derive(Accounts)]
+pub struct CreateVesting<'info> {
+${access.signer('create_vesting', 'creator', true)}
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump${access.launch('create_vesting', 'creator')}
+    )]
+    pub token_launch: Account<'info, TokenLaunch>,
+    
+    pub token_mint: ${token.mint},
+    
+    /// CHECK: Any wallet can be a beneficiary
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = VestingAccount::space(),
+        seeds = [seeds::VESTING, token_launch.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = vesting,
+        seeds = [seeds::VESTING_VAULT, vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = creator)]
+    pub creator_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+${access.grants('create_vesting', 'creator')}}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub beneficiary: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_launch: Account<'info, TokenLaunch>,
+    
+    pub token_mint: ${token.mint},
+    
+    #[account(
+        mut,
+        seeds = [seeds::VESTING, token_launch.key().as_ref(), beneficiary.key().as_ref()],
+        bump = vesting.bump,
+        has_one = beneficiary
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VESTING_VAULT, vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = beneficiary)]
+    pub beneficiary_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
 }
 
-// Export for use
-export { SolDParser, SolDCLI };
+#[account]
+#[derive(InitSpace)]
+pub struct VestingAccount {
+    pub launch: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start: i64,
+    pub bump: u8,
+}
 
-// Example usage
-if (require.main === module) {
-  const cli = new SolDCLI();
-  const [,, inputFile, outputDir] = process.argv;
-  
-  if (!inputFile || !outputDir) {
-    console.log('Usage: node sold-parser.js <input.sold> <output-directory>');
-    process.exit(1);
+impl VestingAccount {
+    pub const CLIFF: i64 = ${cliff}; // ${config.vesting.cliff}
+    pub const DURATION: i64 = ${duration}; // ${config.vesting.duration}
+    pub const INTERVAL: i64 = ${interval}; // ${config.vesting.interval}
+
+    pub fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    // Nothing before the cliff, then linear in whole intervals, all of it
+    // once the duration has passed
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let elapsed = now - self.start;
+        if elapsed < 0 || elapsed < Self::CLIFF {
+            return 0;
+        }
+        if elapsed >= Self::DURATION {
+            return self.total;
+        }
+        let vested_secs = elapsed / Self::INTERVAL * Self::INTERVAL;
+        (self.total as u128 * vested_secs as u128 / Self::DURATION as u128) as u64
+    }
+}
+`;
+
+    const seeds = `
+    /// VestingAccount: [VESTING, launch, beneficiary]
+    #[constant]
+    pub const VESTING: &[u8] = b"vesting";
+
+    /// Vesting vault token account: [VESTING_VAULT, vesting]
+    #[constant]
+    pub const VESTING_VAULT: &[u8] = b"vesting_vault";
+`;
+
+    const errors = `    #[msg("${ROLE_ERRORS.creator.msg}")]
+    ${ROLE_ERRORS.creator.name},
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+`;
+
+    const tests = `
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vesting(total: u64) -> VestingAccount {
+        VestingAccount {
+            launch: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            total,
+            claimed: 0,
+            start: 1_000,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_cliff() {
+        let v = vesting(1_000_000);
+        assert_eq!(v.vested_amount(v.start - 1), 0);
+        if VestingAccount::CLIFF > 0 {
+            assert_eq!(v.vested_amount(v.start + VestingAccount::CLIFF - 1), 0);
+        }
+    }
+
+    #[test]
+    fn vests_in_whole_intervals() {
+        let v = vesting(1_000_000);
+        let at = v.start + VestingAccount::CLIFF.max(VestingAccount::INTERVAL);
+        if at < v.start + VestingAccount::DURATION {
+            assert_eq!(v.vested_amount(at), v.vested_amount(at + VestingAccount::INTERVAL - 1));
+            assert!(v.vested_amount(at + VestingAccount::INTERVAL) >= v.vested_amount(at));
+        }
+    }
+
+    #[test]
+    fn fully_vested_after_duration() {
+        let v = vesting(1_000_000);
+        assert_eq!(v.vested_amount(v.start + VestingAccount::DURATION), 1_000_000);
+        assert_eq!(v.vested_amount(i64::MAX / 2), 1_000_000);
+    }
+}
+`;
+
+    return { instructions, accounts, seeds, errors, tests };
+  }
+
+  // The spec's opt-in modules, as instruction groups of the one program.
+  // Anchor 0.29 cannot feature-gate instructions, so the spec decides what
+  // is generated. Governance builds on staking: votes weigh a wallet's
+  // StakePosition and keep it locked until voting ends.
+  private generateModules(config: SolDConfig, token: TokenBackend) {
+    const launch = (signer?: string) => `    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump${signer ? `,
+        has_one = ${signer} @ ErrorCode::${ROLE_ERRORS.creator.name}` : ''}
+    )]
+    pub token_launch: Account<'info, TokenLaunch>,
+    
+    pub token_mint: ${token.mint},
+`;
+    const space = (name: string) => `
+impl ${name} {
+    pub fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
+`;
+    const governance = config.modules.includes('governance');
+
+    const fragments: Record<SpecModule, { instructions: string; accounts: string; seeds: string }> = {
+      staking: {
+        instructions: `
+    // Open a wallet's stake position and the vault holding its stake
+    pub fn open_stake(ctx: Context<OpenStake>) -> Result<()> {
+        let stake = &mut ctx.accounts.stake;
+        stake.launch = ctx.accounts.token_launch.key();
+        stake.owner = ctx.accounts.owner.key();
+        stake.amount = 0;
+        stake.locked_until = 0;
+        stake.bump = ctx.bumps.stake;
+        Ok(())
+    }
+
+    // Lock tokens in the position for STAKE_LOCKUP from this deposit
+    pub fn stake(ctx: Context<UpdateStake>, amount: u64) -> Result<()> {
+        let before = ctx.accounts.stake_vault.amount;
+${token.transfer('ctx.accounts.owner_tokens', 'ctx.accounts.stake_vault', 'ctx.accounts.owner', 'amount')}
+
+        // Credit what reached the vault, net of any transfer fee
+        ctx.accounts.stake_vault.reload()?;
+        let received = ctx.accounts.stake_vault.amount - before;
+        let stake = &mut ctx.accounts.stake;
+        stake.amount += received;
+        stake.locked_until = stake.locked_until.max(Clock::get()?.unix_timestamp + constants::STAKE_LOCKUP);
+        Ok(())
+    }
+
+    // Withdraw staked tokens once the lockup${governance ? ' and any vote holding them' : ''} has ended
+    pub fn unstake(ctx: Context<UpdateStake>, amount: u64) -> Result<()> {
+        let stake = &mut ctx.accounts.stake;
+        require!(Clock::get()?.unix_timestamp >= stake.locked_until, ErrorCode::StakeLocked);
+        require!(amount <= stake.amount, ErrorCode::InsufficientStake);
+        stake.amount -= amount;
+
+        let launch_key = stake.launch;
+        let owner_key = stake.owner;
+        let bump = [stake.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::STAKE, launch_key.as_ref(), owner_key.as_ref(), &bump]];
+${token.transfer('ctx.accounts.stake_vault', 'ctx.accounts.owner_tokens', 'stake', 'amount', 'signer_seeds')}
+
+        Ok(())
+    }
+`,
+        accounts: `
+#[derive(Accounts)]
+pub struct OpenStake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+${launch()}    
+    #[account(
+        init,
+        payer = owner,
+        space = StakePosition::space(),
+        seeds = [seeds::STAKE, token_launch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, StakePosition>,
+    
+    #[account(
+        init,
+        payer = owner,
+        token::mint = token_mint,
+        token::authority = stake,
+        seeds = [seeds::STAKE_VAULT, stake.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStake<'info> {
+    pub owner: Signer<'info>,
+    
+${launch()}    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE, token_launch.key().as_ref(), owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner
+    )]
+    pub stake: Account<'info, StakePosition>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE_VAULT, stake.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = owner)]
+    pub owner_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+}
+
+// A wallet's staked tokens on one launch
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    pub launch: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_until: i64,
+    pub bump: u8,
+}
+${space('StakePosition')}`,
+        seeds: `
+    /// StakePosition: [STAKE, launch, owner]
+    #[constant]
+    pub const STAKE: &[u8] = b"stake";
+
+    /// Stake vault token account: [STAKE_VAULT, stake]
+    #[constant]
+    pub const STAKE_VAULT: &[u8] = b"stake_vault";
+`
+      },
+      governance: {
+        instructions: `
+    // Open a proposal for the launch's stakers to vote on
+    pub fn create_proposal(ctx: Context<CreateProposal>, description: String) -> Result<()> {
+        require!(description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::DescriptionTooLong);
+        require!(ctx.accounts.stake.amount > 0, ErrorCode::NoStake);
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.launch = ctx.accounts.token_launch.key();
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.description = description;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.voting_ends = Clock::get()?.unix_timestamp + constants::VOTING_PERIOD;
+        proposal.finalized = false;
+        proposal.passed = false;
+        Ok(())
+    }
+
+    // Vote with the whole stake. It stays locked until voting ends, so it
+    // cannot move to another wallet and vote again.
+    pub fn vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(Clock::get()?.unix_timestamp < proposal.voting_ends, ErrorCode::VotingClosed);
+        let stake = &mut ctx.accounts.stake;
+        require!(stake.amount > 0, ErrorCode::NoStake);
+        stake.locked_until = stake.locked_until.max(proposal.voting_ends);
+        if approve {
+            proposal.yes_votes = proposal.yes_votes.saturating_add(stake.amount);
+        } else {
+            proposal.no_votes = proposal.no_votes.saturating_add(stake.amount);
+        }
+
+        let record = &mut ctx.accounts.vote_record;
+        record.proposal = proposal.key();
+        record.voter = ctx.accounts.voter.key();
+        record.weight = stake.amount;
+        record.approve = approve;
+        record.bump = ctx.bumps.vote_record;
+        Ok(())
+    }
+
+    // Settle a proposal once voting ends: it passes on a majority of the
+    // votes cast, if at least QUORUM_BPS of supply voted
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(Clock::get()?.unix_timestamp >= proposal.voting_ends, ErrorCode::VotingOpen);
+        require!(!proposal.finalized, ErrorCode::ProposalFinalized);
+        let turnout = proposal.yes_votes as u128 + proposal.no_votes as u128;
+        let quorum = ctx.accounts.token_launch.total_supply as u128 * constants::QUORUM_BPS as u128 / 10_000;
+        proposal.finalized = true;
+        proposal.passed = turnout >= quorum && proposal.yes_votes > proposal.no_votes;
+        Ok(())
+    }
+`,
+        accounts: `
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+${launch()}    
+    #[account(
+        seeds = [seeds::STAKE, token_launch.key().as_ref(), proposer.key().as_ref()],
+        bump = stake.bump
+    )]
+    pub stake: Account<'info, StakePosition>,
+    
+    #[account(init, payer = proposer, space = Proposal::space())]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+${launch()}    
+    #[account(mut, constraint = proposal.launch == token_launch.key())]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE, token_launch.key().as_ref(), voter.key().as_ref()],
+        bump = stake.bump
+    )]
+    pub stake: Account<'info, StakePosition>,
+    
+    // One record per voter and proposal, so each stake votes once
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::space(),
+        seeds = [seeds::VOTE, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+${launch()}    
+    #[account(mut, constraint = proposal.launch == token_launch.key())]
+    pub proposal: Account<'info, Proposal>,
+}
+
+// Longest proposal description, in bytes; link to anything longer
+pub const MAX_DESCRIPTION_LEN: usize = 200;
+
+// A stake-weighted vote among the launch's stakers
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub launch: Pubkey,
+    pub proposer: Pubkey,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub voting_ends: i64,
+    pub finalized: bool,
+    pub passed: bool,
+}
+${space('Proposal')}
+// A wallet's vote on one proposal
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub approve: bool,
+    pub bump: u8,
+}
+${space('VoteRecord')}`,
+        seeds: `
+    /// VoteRecord: [VOTE, proposal, voter]
+    #[constant]
+    pub const VOTE: &[u8] = b"vote";
+`
+      },
+      presale: {
+        instructions: `
+    // Escrow the creator's tokens for sale until PRESALE_DURATION passes
+    pub fn open_presale(ctx: Context<OpenPresale>, amount: u64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale;
+        presale.launch = ctx.accounts.token_launch.key();
+        presale.sold = 0;
+        presale.ends = Clock::get()?.unix_timestamp + constants::PRESALE_DURATION;
+        presale.bump = ctx.bumps.presale;
+
+${token.transfer('ctx.accounts.creator_tokens', 'ctx.accounts.presale_vault', 'ctx.accounts.creator', 'amount')}
+
+        Ok(())
+    }
+
+    // Buy presale tokens at PRESALE_PRICE_LAMPORTS each, paid to the creator
+    pub fn buy_presale(ctx: Context<BuyPresale>, amount: u64) -> Result<()> {
+        let presale = &mut ctx.accounts.presale;
+        require!(Clock::get()?.unix_timestamp < presale.ends, ErrorCode::PresaleEnded);
+        require!(amount <= ctx.accounts.presale_vault.amount, ErrorCode::PresaleSoldOut);
+        presale.sold += amount;
+
+        let cost = amount.checked_mul(constants::PRESALE_PRICE_LAMPORTS).ok_or(ProgramError::ArithmeticOverflow)?;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.creator.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, cost)?;
+
+        let launch_key = presale.launch;
+        let bump = [presale.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::PRESALE, launch_key.as_ref(), &bump]];
+${token.transfer('ctx.accounts.presale_vault', 'ctx.accounts.buyer_tokens', 'presale', 'amount', 'signer_seeds')}
+
+        Ok(())
+    }
+
+    // Return unsold tokens to the creator once the presale has ended
+    pub fn close_presale(ctx: Context<ClosePresale>) -> Result<()> {
+        let presale = &ctx.accounts.presale;
+        require!(Clock::get()?.unix_timestamp >= presale.ends, ErrorCode::PresaleOpen);
+        let unsold = ctx.accounts.presale_vault.amount;
+
+        let launch_key = presale.launch;
+        let bump = [presale.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::PRESALE, launch_key.as_ref(), &bump]];
+${token.transfer('ctx.accounts.presale_vault', 'ctx.accounts.creator_tokens', 'presale', 'unsold', 'signer_seeds')}
+
+        Ok(())
+    }
+`,
+        accounts: `
+#[derive(Accounts)]
+pub struct OpenPresale<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+${launch('creator')}    
+    #[account(
+        init,
+        payer = creator,
+        space = Presale::space(),
+        seeds = [seeds::PRESALE, token_launch.key().as_ref()],
+        bump
+    )]
+    pub presale: Account<'info, Presale>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = presale,
+        seeds = [seeds::PRESALE_VAULT, presale.key().as_ref()],
+        bump
+    )]
+    pub presale_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = creator)]
+    pub creator_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyPresale<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+${launch()}    
+    /// CHECK: The launch's creator, paid for the tokens
+    #[account(mut, address = token_launch.creator)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PRESALE, token_launch.key().as_ref()],
+        bump = presale.bump
+    )]
+    pub presale: Account<'info, Presale>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PRESALE_VAULT, presale.key().as_ref()],
+        bump
+    )]
+    pub presale_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = buyer)]
+    pub buyer_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePresale<'info> {
+    pub creator: Signer<'info>,
+    
+${launch('creator')}    
+    #[account(
+        seeds = [seeds::PRESALE, token_launch.key().as_ref()],
+        bump = presale.bump
+    )]
+    pub presale: Account<'info, Presale>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PRESALE_VAULT, presale.key().as_ref()],
+        bump
+    )]
+    pub presale_vault: ${token.tokenAccount},
+    
+    #[account(mut, token::mint = token_mint, token::authority = creator)]
+    pub creator_tokens: ${token.tokenAccount},
+    
+    pub token_program: ${token.tokenProgram},
+}
+
+// A launch's presale of escrowed tokens at a fixed price
+#[account]
+#[derive(InitSpace)]
+pub struct Presale {
+    pub launch: Pubkey,
+    pub sold: u64,
+    pub ends: i64,
+    pub bump: u8,
+}
+${space('Presale')}`,
+        seeds: `
+    /// Presale: [PRESALE, launch]
+    #[constant]
+    pub const PRESALE: &[u8] = b"presale";
+
+    /// Presale vault token account: [PRESALE_VAULT, presale]
+    #[constant]
+    pub const PRESALE_VAULT: &[u8] = b"presale_vault";
+`
+      }
+    };
+    const join = (part: 'instructions' | 'accounts' | 'seeds') => config.modules.map(module => fragments[module][part]).join('');
+    const errors = config.modules.flatMap(module => MODULE_ERRORS[module])
+      .map(({ name, msg }) => `    #[msg("${msg}")]\n    ${name},\n`).join('');
+    return { instructions: join('instructions'), accounts: join('accounts'), seeds: join('seeds'), errors };
+  }
+
+  // One migrate_<account> instruction per account type whose layout changed
+  // since the deployed IDL. It reads the deployed layout, reallocates to
+  // the current size and rewrites the account; fields the deployed program
+  // did not have start at their defaults. Anyone may call it, as it only
+  // moves data into the new layout, and the payer funds any extra rent.
+  // The spec's events, in an `events` module so their names cannot clash
+  // with the program's own types. Each instruction builds its events as it
+  // starts, before its args are moved, and emits them once it succeeds.
+  private generateEvents(config: SolDConfig, instructions: IdlInstruction[]) {
+    const compiled = config.events.map(event => ({ event, fields: compileEvent(event, instructions) }));
+    const structs = compiled.map(({ event, fields }) => `
+    /// Emitted by ${event.instruction}
+    #[event]
+    pub struct ${event.name} {
+${fields.map(f => `        pub ${f.name}: ${idlRustType(f.type)},`).join('\n')}
+    }
+`).join('');
+
+    const emit = (program: string) => {
+      for (const { event, fields } of compiled) {
+        const start = program.indexOf(`\n    pub fn ${event.instruction}(`);
+        const open = program.indexOf(') -> Result<()> {\n', start) + ') -> Result<()> {'.length;
+        const end = program.indexOf('\n        Ok(())\n    }\n', open);
+        const local = `${toSnakeCase(event.name)}_event`;
+        const build = `
+        let ${local} = events::${event.name} {
+${fields.map(f => `            ${f.value === f.name ? f.name : `${f.name}: ${f.value}`},`).join('\n')}
+        };
+`;
+        const signature = program.slice(start, open);
+        program = program.slice(0, start)
+          + (fields.some(f => f.value.startsWith('ctx.')) ? signature.replace('(_ctx:', '(ctx:') : signature)
+          + build + program.slice(open, end + 1) + `        emit!(${local});\n` + program.slice(end + 1);
+      }
+      return program;
+    };
+
+    return {
+      module: structs ? `
+// Spec events, emitted by the instructions they are declared on
+pub mod events {
+    use anchor_lang::prelude::*;
+${structs}}
+` : '',
+      emit
+    };
+  }
+
+  private generateMigrations(config: SolDConfig, programId: string, migrateFrom?: Idl) {
+    const layouts = migrateFrom ? new IdlDiffer().migrations(migrateFrom, this.generateIdl(config, programId)) : [];
+    if (!layouts.length) {
+      return { instructions: '', accounts: '', module: '' };
+    }
+
+    const instructions = layouts.map(({ account: name, from, to }) => {
+      const fields = to.map(field => {
+        const rust = toSnakeCase(field.name);
+        return `            ${rust}: ${from.some(f => f.name === field.name) ? `old.${rust}` : 'Default::default()'},`;
+      });
+      return `
+    // Rewrite a ${name} written by the deployed program in the current layout
+    pub fn migrate_${toSnakeCase(name)}(ctx: Context<Migrate${name}>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+        let space = ${name}::space();
+        // Accounts already at the current size have been migrated
+        if target.data_len() == space {
+            return Ok(());
+        }
+        let old = {
+            let data = target.try_borrow_data()?;
+            if data.len() < 8 || data[..8] != <${name} as anchor_lang::Discriminator>::DISCRIMINATOR {
+                return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+            migrations::Deployed${name}::deserialize(&mut &data[8..])?
+        };
+        let migrated = ${name} {
+${fields.join('\n')}
+        };
+
+        let rent = Rent::get()?.minimum_balance(space);
+        if target.lamports() < rent {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: target.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, rent - target.lamports())?;
+        }
+        target.realloc(space, false)?;
+        migrated.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+`;
+    }).join('');
+
+    const accounts = layouts.map(({ account: name }) => `
+#[derive(Accounts)]
+pub struct Migrate${name}<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Still in the deployed layout; the discriminator is checked
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+`).join('');
+
+    const structs = layouts.map(({ account: name, from }) => `
+    #[derive(AnchorSerialize, AnchorDeserialize, Default)]
+    pub struct Deployed${name} {
+${from.map(field => `        pub ${toSnakeCase(field.name)}: ${idlRustType(field.type)},`).join('\n')}
+    }
+`).join('');
+    const module = `
+// Account layouts of the deployed program, read by the migrate_* instructions
+pub mod migrations {
+    use anchor_lang::prelude::*;
+${structs}}
+`;
+
+    return { instructions, accounts, module };
+  }
+
+  // SPL Token by default. With token_standard = "token2022" the program goes
+  // through token_interface, so it also accepts SPL mints, and gains
+  // create_mint: Token-2022 extensions must be initialized before the mint
+  // is, so the program creates the mint itself.
+  private tokenBackend(config: SolDConfig): TokenBackend {
+    const cpi = (signerSeeds?: string) =>
+      signerSeeds ? `CpiContext::new_with_signer(cpi_program, cpi_accounts, ${signerSeeds})` : 'CpiContext::new(cpi_program, cpi_accounts)';
+    if (config.constants.tokenStandard === 'spl') {
+      return {
+        programId: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',
+        imports: 'use anchor_spl::token::{self, Token, TokenAccount, Mint};',
+        mint: "Account<'info, Mint>",
+        tokenAccount: "Account<'info, TokenAccount>",
+        tokenProgram: "Program<'info, Token>",
+        transfer: (from, to, authority, amount, signerSeeds) => `        let cpi_accounts = token::Transfer {
+            from: ${from}.to_account_info(),
+            to: ${to}.to_account_info(),
+            authority: ${authority}.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(${cpi(signerSeeds)}, ${amount})?;`,
+        instructions: '',
+        accounts: ''
+      };
+    }
+
+    const transferFee = config.constants.transferFeeBps > 0;
+    const extensions = ['ExtensionType::MetadataPointer', ...(transferFee ? ['ExtensionType::TransferFeeConfig'] : [])];
+    const instructions = `
+    // Create the launch's Token-2022 mint with its extensions
+    pub fn create_mint(ctx: Context<CreateMint>, decimals: u8) -> Result<()> {
+        let mint = ctx.accounts.token_mint.to_account_info();
+        let creator = ctx.accounts.creator.key();
+        let token_program = ctx.accounts.token_program.key();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[${extensions.join(', ')}])?;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::CreateAccount {
+                from: ctx.accounts.creator.to_account_info(),
+                to: mint.clone(),
+            },
+        );
+        anchor_lang::system_program::create_account(cpi_context, Rent::get()?.minimum_balance(space), space as u64, &token_program)?;
+
+        // Metadata lives on the mint itself, under the creator's authority
+        invoke(
+            &metadata_pointer::instruction::initialize(&token_program, mint.key, Some(creator), Some(mint.key()))?,
+            std::slice::from_ref(&mint),
+        )?;
+${transferFee ? `
+        // No fee authority: the fee is fixed for the life of the mint.
+        // The creator can withdraw withheld fees.
+        invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program,
+                mint.key,
+                None,
+                Some(&creator),
+                constants::TRANSFER_FEE_BPS,
+                constants::MAX_TRANSFER_FEE,
+            )?,
+            std::slice::from_ref(&mint),
+        )?;
+` : ''}
+        let cpi_accounts = token_interface::InitializeMint2 { mint };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::initialize_mint2(CpiContext::new(cpi_program, cpi_accounts), decimals, &creator, None)?;
+
+        Ok(())
+    }
+`;
+    const accounts = `
+#[derive(Accounts)]
+pub struct CreateMint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+`;
+    return {
+      programId: 'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
+      imports: `use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::{metadata_pointer, ${transferFee ? 'transfer_fee, ' : ''}ExtensionType}};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};`,
+      mint: "InterfaceAccount<'info, Mint>",
+      tokenAccount: "InterfaceAccount<'info, TokenAccount>",
+      tokenProgram: "Interface<'info, TokenInterface>",
+      transfer: (from, to, authority, amount, signerSeeds) => `        let cpi_accounts = token_interface::TransferChecked {
+            from: ${from}.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ${to}.to_account_info(),
+            authority: ${authority}.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::transfer_checked(${cpi(signerSeeds)}, ${amount}, ctx.accounts.token_mint.decimals)?;`,
+      instructions,
+      accounts
+    };
+  }
+
+  // The spec's config block as a constants module, published in the IDL
+  private generateConstants(config: SolDConfig): string {
+    const c = config.constants;
+    return `// Operator-tuned values from the spec's config block
+pub mod constants {
+    use anchor_lang::prelude::*;
+
+    /// ${c.feeRecipient}
+    #[constant]
+    pub const FEE_RECIPIENT: Pubkey = Pubkey::new_from_array([${Array.from(new PublicKey(c.feeRecipient).toBytes()).join(', ')}]);
+    /// ${config.relock.escrow}, the relock escrow
+    #[constant]
+    pub const RELOCK_ESCROW: Pubkey = Pubkey::new_from_array([${Array.from(new PublicKey(config.relock.escrow).toBytes()).join(', ')}]);
+    #[constant]
+    pub const LAUNCH_FEE_LAMPORTS: u64 = ${c.launchFee};
+    #[constant]
+    pub const INSURANCE_WALLET_FEE_LAMPORTS: u64 = ${c.insuranceWalletFee};
+    #[constant]
+    pub const LOGO_FEE_LAMPORTS: u64 = ${c.logoFee};
+    #[constant]
+    pub const TRADING_FEE_LAMPORTS: u64 = ${c.tradingFee};
+    #[constant]
+    pub const RELOCK_FEE_LAMPORTS: u64 = ${c.relockFee};
+    /// Shortest timelock a mainnet spec may declare (${c.minTimelock})
+    #[constant]
+    pub const MIN_TIMELOCK_DURATION: i64 = ${this.parseDuration(c.minTimelock)};
+    #[constant]
+    pub const MAX_INSURANCE_WALLETS: usize = ${c.maxInsuranceWallets};
+    /// Percent of supply emergency_withdraw may move
+    #[constant]
+    pub const MAX_INSURANCE_LIMIT: u8 = ${c.maxInsuranceLimit};
+    /// Longest token_name and token_symbol initialize_launch accepts, in bytes
+    #[constant]
+    pub const MAX_NAME_LEN: usize = ${c.maxNameLen};
+    #[constant]
+    pub const MAX_SYMBOL_LEN: usize = ${c.maxSymbolLen};
+    /// Fraud score above which off-chain monitors suspend the launch
+    #[constant]
+    pub const AUTO_SUSPEND_SCORE_BPS: u16 = ${Math.round(c.autoSuspendThreshold * 10000)};
+    /// The token program launches go through (${c.tokenStandard})
+    #[constant]
+    pub const TOKEN_PROGRAM: Pubkey = ${c.tokenStandard === 'spl' ? 'anchor_spl::token::ID' : 'anchor_spl::token_2022::ID'};
+${c.tokenStandard === 'token2022' ? `    /// Token-2022 transfer fee set on the mint by create_mint
+    #[constant]
+    pub const TRANSFER_FEE_BPS: u16 = ${c.transferFeeBps};
+    #[constant]
+    pub const MAX_TRANSFER_FEE: u64 = ${c.maxTransferFee};
+` : ''}${config.modules.includes('staking') ? `    /// How long a deposit keeps staked tokens locked (${c.stakeLockup})
+    #[constant]
+    pub const STAKE_LOCKUP: i64 = ${this.parseDuration(c.stakeLockup)};
+` : ''}${config.modules.includes('governance') ? `    /// How long a proposal is open for votes (${c.votingPeriod})
+    #[constant]
+    pub const VOTING_PERIOD: i64 = ${this.parseDuration(c.votingPeriod)};
+    /// Share of supply, in basis points, that must vote for a proposal to pass
+    #[constant]
+    pub const QUORUM_BPS: u16 = ${c.quorumBps};
+` : ''}${config.modules.includes('presale') ? `    #[constant]
+    pub const PRESALE_PRICE_LAMPORTS: u64 = ${c.presalePrice};
+    /// How long a presale sells for once opened (${c.presaleDuration})
+    #[constant]
+    pub const PRESALE_DURATION: i64 = ${this.parseDuration(c.presaleDuration)};
+` : ''}}
+`;
+  }
+
+  // Lamport fees the generated program charges, for off-chain clients
+  feeSchedule(config: SolDConfig): FeeSchedule {
+    return {
+      launch: this.calculateLaunchFee(config),
+      trading: config.constants.tradingFee,
+      relock: config.constants.relockFee
+    };
+  }
+
+  private calculateLaunchFee(config: SolDConfig): number {
+    const c = config.constants;
+    const insuranceFee = config.insurance.wallets.length * c.insuranceWalletFee;
+    const logoFee = config.logo.nft ? c.logoFee : 0;
+
+    return c.launchFee + insuranceFee + logoFee;
+  }
+}
+
+// Typed TypeScript client for a generated program, derived from its IDL:
+// instruction builders, account decoders and fetchers, PDA helpers, error
+// lookup and event parsing, so launch teams need no anchor.workspace glue
+const KNOWN_PROGRAMS: Record<string, string> = {
+  systemProgram: 'SystemProgram.programId',
+  tokenProgram: 'TOKEN_PROGRAM_ID',
+  rent: 'SYSVAR_RENT_PUBKEY'
+};
+
+// SPL Token or Token-2022, whichever the program was generated against
+function tokenProgramId(idl: Idl): string {
+  return idl.constants.find(c => c.name === 'TOKEN_PROGRAM')?.value ?? 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA';
+}
+
+class TsClientGenerator {
+  generate(idl: Idl): string {
+    return [
+      this.header(idl),
+      this.pdaHelpers(idl),
+      ...idl.accounts.map(def => this.accountHelpers(def)),
+      ...idl.instructions.map(ix => this.instructionBuilder(ix)),
+      this.computeBudget(),
+      this.errors(idl),
+      this.events(idl)
+    ].join('\n');
+  }
+
+  private header(idl: Idl): string {
+    return `// Generated by \`sold gen ts\` for ${idl.name}. Do not edit; regenerate from the spec.
+import { BN, BorshCoder, EventParser, Idl } from '@coral-xyz/anchor';
+import {
+  ComputeBudgetProgram, Connection, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, TransactionInstruction,
+  TransactionMessage, VersionedTransaction
+} from '@solana/web3.js';
+
+export const PROGRAM_ID = new PublicKey('${idl.metadata.address}');
+const TOKEN_PROGRAM_ID = new PublicKey('${tokenProgramId(idl)}');
+
+export const IDL = ${JSON.stringify(idl, null, 2)};
+
+const coder = new BorshCoder(IDL as unknown as Idl);
+`;
+  }
+
+  // One find<Account>Address helper per distinct PDA in the IDL
+  private pdaHelpers(idl: Idl): string {
+    const seen = new Map<string, IdlSeed[]>();
+    for (const ix of idl.instructions) {
+      for (const account of ix.accounts) {
+        if (account.pda && !seen.has(account.name)) seen.set(account.name, account.pda.seeds);
+      }
+    }
+    return Array.from(seen).map(([name, seeds]) => {
+      const params = seeds.filter(seed => seed.kind === 'account').map(seed => `${(seed as any).path}: PublicKey`);
+      const buffers = seeds.map(seed => seed.kind === 'const' ? `Buffer.from('${seed.value}')` : `${seed.path}.toBuffer()`);
+      return `export function find${toPascalCase(name)}Address(${[...params, 'programId = PROGRAM_ID'].join(', ')}): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([${buffers.join(', ')}], programId);
+}
+`;
+    }).join('\n');
+  }
+
+  private accountHelpers(def: IdlTypeDef): string {
+    return `export interface ${def.name} {
+${def.type.fields.map(f => `  ${f.name}: ${this.tsType(f.type)};`).join('\n')}
+}
+
+export function decode${def.name}(data: Buffer): ${def.name} {
+  return coder.accounts.decode('${def.name}', data);
+}
+
+export async function fetch${def.name}(connection: Connection, address: PublicKey): Promise<${def.name} | null> {
+  const info = await connection.getAccountInfo(address);
+  return info ? decode${def.name}(info.data) : null;
+}
+`;
+  }
+
+  // Well-known program accounts are optional and default to their ids;
+  // an omitted optional account is passed as the program id, Anchor's None
+  private instructionBuilder(ix: IdlInstruction): string {
+    const name = toPascalCase(ix.name);
+    const accountFields = ix.accounts
+      .map(a => `  ${a.name}${KNOWN_PROGRAMS[a.name] || a.isOptional ? '?' : ''}: PublicKey;`).join('\n');
+    const keys = ix.accounts.map(a => {
+      const fallback = KNOWN_PROGRAMS[a.name] ?? (a.isOptional ? 'programId' : undefined);
+      const pubkey = fallback ? `accounts.${a.name} ?? ${fallback}` : `accounts.${a.name}`;
+      return `    { pubkey: ${pubkey}, isSigner: ${a.isSigner}, isWritable: ${a.isMut} },`;
+    }).join('\n');
+    const hasArgs = ix.args.length > 0;
+    const argsType = hasArgs
+      ? `export interface ${name}Args {
+${ix.args.map(arg => `  ${arg.name}: ${this.tsType(arg.type)};`).join('\n')}
+}
+
+`
+      : '';
+    const params = [`accounts: ${name}Accounts`, ...(hasArgs ? [`args: ${name}Args`] : []), 'programId = PROGRAM_ID'];
+    return `export interface ${name}Accounts {
+${accountFields}
+}
+
+${argsType}export function ${ix.name}(${params.join(', ')}): TransactionInstruction {
+  const keys = [
+${keys}
+  ];
+  const data = coder.instruction.encode('${ix.name}', ${hasArgs ? 'args' : '{}'});
+  return new TransactionInstruction({ programId, keys, data });
+}
+`;
+  }
+
+  // Instruction builders return bare instructions; this prepends the
+  // compute-unit limit and priority fee a congested cluster needs
+  private computeBudget(): string {
+    return `export interface ComputeBudgetOptions {
+  // Compute-unit limit, or 'auto' to simulate and add 20%
+  units?: number | 'auto';
+  // Priority fee in micro-lamports per unit, or 'auto' for a percentile of
+  // recent fees on the accounts the instructions write
+  microLamports?: number | 'auto';
+  percentile?: number;
+}
+
+export async function withComputeBudget(
+  connection: Connection, payer: PublicKey, instructions: TransactionInstruction[], options: ComputeBudgetOptions = {}
+): Promise<TransactionInstruction[]> {
+  const budget: TransactionInstruction[] = [];
+  const units = options.units === 'auto' ? await estimateComputeUnits(connection, payer, instructions) : options.units;
+  if (units) budget.push(ComputeBudgetProgram.setComputeUnitLimit({ units }));
+  const microLamports = options.microLamports === 'auto'
+    ? await estimatePriorityFee(connection, instructions, options.percentile ?? 75)
+    : options.microLamports;
+  if (microLamports) budget.push(ComputeBudgetProgram.setComputeUnitPrice({ microLamports }));
+  return [...budget, ...instructions];
+}
+
+export async function estimateComputeUnits(
+  connection: Connection, payer: PublicKey, instructions: TransactionInstruction[]
+): Promise<number> {
+  const message = new TransactionMessage({
+    payerKey: payer,
+    recentBlockhash: PublicKey.default.toBase58(),
+    instructions: [ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }), ...instructions]
+  }).compileToV0Message();
+  const { value } = await connection.simulateTransaction(new VersionedTransaction(message), {
+    sigVerify: false,
+    replaceRecentBlockhash: true
+  });
+  if (value.err) throw new Error(\`Simulation failed: \${JSON.stringify(value.err)}\`);
+  return Math.min(1_400_000, Math.ceil((value.unitsConsumed ?? 0) * 1.2));
+}
+
+export async function estimatePriorityFee(
+  connection: Connection, instructions: TransactionInstruction[], percentile = 75
+): Promise<number> {
+  const writable = new Map<string, PublicKey>();
+  instructions.forEach(ix => ix.keys.forEach(key => key.isWritable && writable.set(key.pubkey.toBase58(), key.pubkey)));
+  const recent = await connection.getRecentPrioritizationFees({ lockedWritableAccounts: [...writable.values()].slice(0, 128) });
+  const fees = recent.map(fee => fee.prioritizationFee).sort((a, b) => a - b);
+  return fees.length ? fees[Math.min(fees.length - 1, Math.floor(fees.length * percentile / 100))] : 0;
+}
+`;
+  }
+
+  private errors(idl: Idl): string {
+    const entries = idl.errors.map(e => `  ${e.code}: { name: '${e.name}', msg: ${JSON.stringify(e.msg)} },`).join('\n');
+    return `export const ERRORS: Record<number, { name: string; msg: string }> = {
+${entries}
+};
+
+export function errorFromCode(code: number): { name: string; msg: string } | null {
+  return ERRORS[code] ?? null;
+}
+`;
+  }
+
+  private events(idl: Idl): string {
+    const interfaces = idl.events.map(event => `export interface ${event.name} {
+${event.fields.map(f => `  ${f.name}: ${this.tsType(f.type)};`).join('\n')}
+}
+`).join('\n');
+    const union = idl.events.length
+      ? idl.events.map(event => `{ name: '${event.name}'; data: ${event.name} }`).join(' | ')
+      : 'never';
+    return `${interfaces ? interfaces + '\n' : ''}export type ProgramEvent = ${union};
+
+// Decode this program's events from a transaction's log messages
+export function parseEvents(logs: string[], programId = PROGRAM_ID): ProgramEvent[] {
+  return Array.from(new EventParser(programId, coder).parseLogs(logs)) as unknown as ProgramEvent[];
+}
+`;
+  }
+
+  private tsType(type: IdlType): string {
+    if (typeof type === 'string') {
+      if (['u64', 'i64', 'u128', 'i128'].includes(type)) return 'BN';
+      if (type === 'publicKey') return 'PublicKey';
+      if (type === 'bool') return 'boolean';
+      if (type === 'string') return 'string';
+      return 'number';
+    }
+    if ('vec' in type) return `${this.tsType(type.vec)}[]`;
+    if ('option' in type) return `${this.tsType(type.option)} | null`;
+    return type.defined;
+  }
+}
+
+// Standalone Rust client crate for a generated program: builders for every
+// instruction, PDA derivation, account deserializers, event decoders and fee
+// calculators. Depends only on solana-program, borsh and (for events)
+// base64, not the Anchor runtime.
+const RUST_KNOWN_PROGRAMS: Record<string, string> = {
+  systemProgram: 'system_program::ID',
+  tokenProgram: 'TOKEN_PROGRAM_ID',
+  rent: 'sysvar::rent::ID'
+};
+
+class RustClientGenerator {
+  generate(idl: Idl, fees: FeeSchedule): Record<string, string> {
+    return {
+      'Cargo.toml': this.cargoToml(idl),
+      'src/lib.rs': [
+        this.header(idl),
+        this.pdaHelpers(idl),
+        ...idl.accounts.map(def => this.accountType(def)),
+        ...idl.instructions.map(ix => this.instructionBuilder(ix)),
+        ...(idl.events.length ? [this.events(idl)] : []),
+        this.computeBudget(),
+        this.feeCalculators(fees)
+      ].join('\n')
+    };
+  }
+
+  private cargoToml(idl: Idl): string {
+    return `[package]
+name = "${idl.name}_client"
+version = "${idl.version}"
+description = "Off-chain client for the ${idl.name} program, generated by SolD"
+edition = "2021"
+
+[dependencies]
+${idl.events.length ? 'base64 = "0.21"\n' : ''}borsh = "0.10"
+solana-program = "1.17"
+`;
+  }
+
+  private header(idl: Idl): string {
+    return `//! Off-chain client for \`${idl.name}\`, generated by \`sold gen rust\`.
+//! Do not edit; regenerate from the spec.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+use solana_program::{pubkey, system_program, sysvar};
+
+pub const ID: Pubkey = pubkey!("${idl.metadata.address}");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("${tokenProgramId(idl)}");
+`;
+  }
+
+  private pdaSeeds(idl: Idl): Map<string, IdlSeed[]> {
+    const seen = new Map<string, IdlSeed[]>();
+    for (const ix of idl.instructions) {
+      for (const account of ix.accounts) {
+        if (account.pda && !seen.has(account.name)) seen.set(account.name, account.pda.seeds);
+      }
+    }
+    return seen;
+  }
+
+  private pdaHelpers(idl: Idl): string {
+    return Array.from(this.pdaSeeds(idl)).map(([name, seeds]) => {
+      const params = seeds.filter(seed => seed.kind === 'account')
+        .map(seed => `${toSnakeCase((seed as any).path)}: &Pubkey`);
+      const parts = seeds.map(seed => seed.kind === 'const' ? `b"${seed.value}"` : `${toSnakeCase(seed.path)}.as_ref()`);
+      return `pub fn find_${toSnakeCase(name)}_address(${params.join(', ')}) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[${parts.join(', ')}], &ID)
+}
+`;
+    }).join('\n');
+  }
+
+  private accountType(def: IdlTypeDef): string {
+    return `#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ${def.name} {
+${def.type.fields.map(f => `    pub ${toSnakeCase(f.name)}: ${this.rustType(f.type)},`).join('\n')}
+}
+
+impl ${def.name} {
+    pub const DISCRIMINATOR: [u8; 8] = ${this.discriminator(`account:${def.name}`)};
+
+    /// Deserialize raw account data, checking the Anchor discriminator
+    pub fn try_from_account_data(data: &[u8]) -> std::io::Result<Self> {
+        if data.len() < 8 || data[..8] != Self::DISCRIMINATOR {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a ${def.name} account"));
+        }
+        Self::deserialize(&mut &data[8..])
+    }
+}
+`;
+  }
+
+  // Builders fill well-known programs and any PDA whose seeds are set
+  private instructionBuilder(ix: IdlInstruction): string {
+    const name = toPascalCase(ix.name);
+    const fields = [
+      ...ix.accounts.map(a => `    ${toSnakeCase(a.name)}: Option<Pubkey>,`),
+      ...ix.args.map(arg => `    ${toSnakeCase(arg.name)}: Option<${this.rustType(arg.type)}>,`)
+    ];
+    const setters = [
+      ...ix.accounts.map(a => [toSnakeCase(a.name), 'Pubkey']),
+      ...ix.args.map(arg => [toSnakeCase(arg.name), this.rustType(arg.type)])
+    ].map(([field, type]) => `    pub fn ${field}(&mut self, ${field}: ${type}) -> &mut Self {
+        self.${field} = Some(${field});
+        self
+    }`);
+
+    const plain = ix.accounts.filter(a => !a.pda).map(a => {
+      const field = toSnakeCase(a.name);
+      if (RUST_KNOWN_PROGRAMS[a.name]) return `        let ${field} = self.${field}.unwrap_or(${RUST_KNOWN_PROGRAMS[a.name]});`;
+      // Anchor reads the program id in an optional account's place as None
+      if (a.isOptional) return `        let ${field} = self.${field}.unwrap_or(ID);`;
+      return `        let ${field} = self.${field}.expect("${field} is not set");`;
+    });
+    const derived = ix.accounts.filter(a => a.pda).map(a => {
+      const field = toSnakeCase(a.name);
+      const seeds = a.pda!.seeds.filter(seed => seed.kind === 'account').map(seed => `&${toSnakeCase((seed as any).path)}`);
+      return `        let ${field} = self.${field}.unwrap_or_else(|| find_${field}_address(${seeds.join(', ')}).0);`;
+    });
+    const metas = ix.accounts.map(a => {
+      const field = toSnakeCase(a.name);
+      return a.isMut ? `                AccountMeta::new(${field}, ${a.isSigner}),` : `                AccountMeta::new_readonly(${field}, ${a.isSigner}),`;
+    });
+    // Only heap-backed args need cloning out of the builder
+    const args = ix.args.map(arg => {
+      const field = toSnakeCase(arg.name);
+      const take = this.isCopy(arg.type) ? `self.${field}` : `self.${field}.clone()`;
+      return `            ${field}: ${take}.expect("${field} is not set"),`;
+    });
+    const argFields = ix.args.map(arg => `    ${toSnakeCase(arg.name)}: ${this.rustType(arg.type)},`);
+
+    return `#[derive(BorshSerialize)]
+struct ${name}Args {${argFields.length ? `\n${argFields.join('\n')}\n` : ''}}
+
+/// Builds a \`${toSnakeCase(ix.name)}\` instruction
+#[derive(Clone, Debug, Default)]
+pub struct ${name}Builder {
+${fields.join('\n')}
+}
+
+impl ${name}Builder {
+    pub const DISCRIMINATOR: [u8; 8] = ${this.discriminator(`global:${toSnakeCase(ix.name)}`)};
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+${setters.join('\n\n')}
+
+    /// Panics if a required account or argument is not set
+    pub fn instruction(&self) -> Instruction {
+${[...plain, ...derived].join('\n')}
+        let args = ${name}Args {${args.length ? `\n${args.join('\n')}\n        ` : ''}};
+        let mut data = Self::DISCRIMINATOR.to_vec();
+        data.extend(args.try_to_vec().expect("instruction args serialize"));
+        Instruction {
+            program_id: ID,
+            accounts: vec![
+${metas.join('\n')}
+            ],
+            data,
+        }
+    }
+}
+`;
+  }
+
+  // Decoders for the events the program emits, through `emit!` (a base64
+  // "Program data:" log line) or a self-CPI (`emit_cpi!` instruction data)
+  private events(idl: Idl): string {
+    const structs = idl.events.map(event => `    #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+    pub struct ${event.name} {
+${event.fields.map(f => `        pub ${toSnakeCase(f.name)}: ${this.rustType(f.type)},`).join('\n')}
+    }
+
+    impl ${event.name} {
+        pub const DISCRIMINATOR: [u8; 8] = ${this.discriminator(`event:${event.name}`)};
+    }
+`).join('\n');
+    return `/// Events the program emits
+pub mod events {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use solana_program::pubkey::Pubkey;
+
+    /// Prefix of the instruction data of an event emitted by self-CPI
+    pub const EVENT_IX_TAG: [u8; 8] = 0x1d9acb512ea545e4u64.to_le_bytes();
+
+${structs}
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ProgramEvent {
+${idl.events.map(event => `        ${event.name}(${event.name}),`).join('\n')}
+    }
+
+    impl ProgramEvent {
+        /// Decode an event from its discriminator and borsh data; None for
+        /// data that is not one of this program's events
+        pub fn decode(data: &[u8]) -> Option<Self> {
+            if data.len() < 8 {
+                return None;
+            }
+            let (discriminator, mut body) = data.split_at(8);
+${idl.events.map(event => `            if discriminator == ${event.name}::DISCRIMINATOR {
+                return ${event.name}::deserialize(&mut body).ok().map(Self::${event.name});
+            }`).join('\n')}
+            None
+        }
+
+        /// Decode the instruction data of a self-CPI event
+        pub fn from_cpi_data(data: &[u8]) -> Option<Self> {
+            data.strip_prefix(&EVENT_IX_TAG[..]).and_then(Self::decode)
+        }
+    }
+
+    /// Decode the events this program logged in a transaction, in order.
+    /// Tracks the invocation stack so data logged by other programs, or by
+    /// this program called from another, is attributed correctly.
+    pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramEvent> {
+        let program = crate::ID.to_string();
+        let mut stack: Vec<String> = Vec::new();
+        let mut events = Vec::new();
+        for line in logs.iter().map(AsRef::as_ref) {
+            if let Some(rest) = line.strip_prefix("Program data: ") {
+                if stack.last() == Some(&program) {
+                    events.extend(STANDARD.decode(rest).ok().as_deref().and_then(ProgramEvent::decode));
+                }
+            } else if let Some(rest) = line.strip_prefix("Program ") {
+                let mut words = rest.split(' ');
+                match (words.next(), words.next()) {
+                    (Some(id), Some("invoke")) => stack.push(id.to_string()),
+                    (Some(_), Some("success" | "failed:")) => {
+                        stack.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        events
+    }
+}
+`;
+  }
+
+  // solana-program has no ComputeBudget builders, so these encode its
+  // instructions directly. Estimating needs an RPC client, which this
+  // crate leaves to the caller: simulate, then pass the units consumed to
+  // \`units_with_margin\`.
+  private computeBudget(): string {
+    return `/// Compute-unit limit and priority fee, prepended to a transaction's
+/// instructions so it lands when the cluster is congested
+pub mod compute_budget {
+    use solana_program::instruction::Instruction;
+    use solana_program::pubkey;
+    use solana_program::pubkey::Pubkey;
+
+    pub const ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+    /// Most compute units one transaction may request
+    pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+    pub fn set_compute_unit_limit(units: u32) -> Instruction {
+        let mut data = vec![2];
+        data.extend_from_slice(&units.to_le_bytes());
+        Instruction { program_id: ID, accounts: vec![], data }
+    }
+
+    /// Priority fee in micro-lamports per compute unit
+    pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+        let mut data = vec![3];
+        data.extend_from_slice(&micro_lamports.to_le_bytes());
+        Instruction { program_id: ID, accounts: vec![], data }
+    }
+
+    /// A limit 20% above what a simulation consumed
+    pub fn units_with_margin(units_consumed: u64) -> u32 {
+        units_consumed.saturating_mul(6).div_ceil(5).min(MAX_COMPUTE_UNITS as u64) as u32
+    }
+
+    /// \`instructions\` preceded by a limit and price, where given
+    pub fn with_compute_budget(
+        instructions: Vec<Instruction>,
+        units: Option<u32>,
+        micro_lamports: Option<u64>,
+    ) -> Vec<Instruction> {
+        units
+            .map(set_compute_unit_limit)
+            .into_iter()
+            .chain(micro_lamports.map(set_compute_unit_price))
+            .chain(instructions)
+            .collect()
+    }
+}
+`;
+  }
+
+  private feeCalculators(fees: FeeSchedule): string {
+    return `/// Fees the program charges, in lamports
+pub mod fees {
+    /// Charged once by \`initialize_launch\`
+    pub const LAUNCH_FEE_LAMPORTS: u64 = ${fees.launch};
+    /// Charged to the payer on every \`transfer_tokens\`
+    pub const TRADING_FEE_LAMPORTS: u64 = ${fees.trading};
+    /// Charged to the escrow on every \`relock_tokens\`
+    pub const RELOCK_FEE_LAMPORTS: u64 = ${fees.relock};
+
+    /// Total trading fees for \`transfers\` calls to \`transfer_tokens\`
+    pub fn trading_fees(transfers: u64) -> Option<u64> {
+        TRADING_FEE_LAMPORTS.checked_mul(transfers)
+    }
+
+    /// Lamports a creator needs for the launch fee plus the launch account rent
+    pub fn launch_cost(rent_exempt_lamports: u64) -> Option<u64> {
+        LAUNCH_FEE_LAMPORTS.checked_add(rent_exempt_lamports)
+    }
+}
+`;
+  }
+
+  private discriminator(preimage: string): string {
+    const bytes = Array.from(crypto.createHash('sha256').update(preimage).digest().subarray(0, 8));
+    return `[${bytes.join(', ')}]`;
+  }
+
+  private isCopy(type: IdlType): boolean {
+    if (typeof type === 'string') return type !== 'string' && type !== 'bytes';
+    if ('option' in type) return this.isCopy(type.option);
+    return false;
+  }
+
+  private rustType(type: IdlType): string {
+    return idlRustType(type);
+  }
+}
+
+// Pluggable output targets. A CodeGenerator turns a parsed spec into files
+// under the build directory; register one with SolDCLI.use() or load it with
+// `build --plugin`. Template directories (`build --templates`) need no code.
+export interface GeneratedFile {
+  path: string; // relative to the build directory
+  contents: string;
+}
+
+export interface GeneratorContext {
+  programName: string;
+  programId: string;
+  idl: Idl;
+}
+
+export interface CodeGenerator {
+  name: string;
+  generate(config: SolDConfig, context: GeneratorContext): GeneratedFile[];
+}
+
+type TemplateNode =
+  | { kind: 'text'; text: string }
+  | { kind: 'value'; helper?: string; path: string }
+  | { kind: 'block'; name: string; path: string; body: TemplateNode[]; inverse: TemplateNode[] };
+
+// Handlebars-style templates: {{path}}, {{helper path}}, {{#each path}} with
+// {{this}}, {{@index}}, {{@first}} and {{@last}}, and {{#if}}/{{#unless}}
+// with {{else}}. Paths resolve from the innermost scope outwards, and block
+// tags alone on a line leave no blank line behind. Output is source code, so
+// values are not HTML-escaped.
+class TemplateEngine {
+  private helpers: Record<string, (value: any) => string> = {
+    snake: value => toSnakeCase(String(value)),
+    pascal: value => toPascalCase(toSnakeCase(String(value))),
+    camel: value => toCamelCase(toSnakeCase(String(value))),
+    upper: value => String(value).toUpperCase(),
+    lower: value => String(value).toLowerCase(),
+    json: value => JSON.stringify(value, null, 2)
+  };
+
+  registerHelper(name: string, helper: (value: any) => string): this {
+    this.helpers[name] = helper;
+    return this;
+  }
+
+  render(template: string, context: unknown): string {
+    return this.evaluate(this.parse(template), [context]);
+  }
+
+  private parse(template: string): TemplateNode[] {
+    template = template.replace(/^[ \t]*(\{\{\s*(?:[#/][^}]*|else\s*)\}\})[ \t]*\r?\n/gm, '$1');
+    const root: TemplateNode[] = [];
+    const open: { node: TemplateNode & { kind: 'block' }; inElse: boolean }[] = [];
+    const target = () => {
+      const top = open[open.length - 1];
+      return top ? (top.inElse ? top.node.inverse : top.node.body) : root;
+    };
+
+    const tag = /\{\{\s*([#/]?)\s*([^}]*?)\s*\}\}/g;
+    let last = 0;
+    let match: RegExpExecArray | null;
+    while ((match = tag.exec(template))) {
+      if (match.index > last) target().push({ kind: 'text', text: template.slice(last, match.index) });
+      last = tag.lastIndex;
+      const [, sigil, body] = match;
+      const [name, arg] = body.split(/\s+/);
+      if (sigil === '#') {
+        if (!['each', 'if', 'unless'].includes(name) || !arg) {
+          throw new Error(`Unsupported block {{#${body}}}`);
+        }
+        const node = { kind: 'block' as const, name, path: arg, body: [], inverse: [] };
+        target().push(node);
+        open.push({ node, inElse: false });
+      } else if (sigil === '/') {
+        if (open.pop()?.node.name !== name) throw new Error(`Unexpected {{/${name}}}`);
+      } else if (body === 'else') {
+        if (!open.length) throw new Error('{{else}} outside a block');
+        open[open.length - 1].inElse = true;
+      } else if (arg) {
+        if (!this.helpers[name]) throw new Error(`Unknown template helper "${name}"`);
+        target().push({ kind: 'value', helper: name, path: arg });
+      } else {
+        target().push({ kind: 'value', path: name });
+      }
+    }
+    if (open.length) throw new Error(`Unclosed {{#${open[open.length - 1].node.name}}}`);
+    if (last < template.length) root.push({ kind: 'text', text: template.slice(last) });
+    return root;
+  }
+
+  private evaluate(nodes: TemplateNode[], scopes: unknown[]): string {
+    return nodes.map(node => {
+      if (node.kind === 'text') return node.text;
+      const value = this.lookup(node.path, scopes);
+      if (node.kind === 'value') {
+        if (node.helper) return this.helpers[node.helper](value);
+        return value === undefined || value === null ? '' : String(value);
+      }
+      if (node.name === 'each') {
+        const items = Array.isArray(value) ? value : [];
+        return items.length
+          ? items.map((item, i) => this.evaluate(node.body,
+            [...scopes, { '@index': i, '@first': i === 0, '@last': i === items.length - 1 }, item])).join('')
+          : this.evaluate(node.inverse, scopes);
+      }
+      const truthy = Array.isArray(value) ? value.length > 0 : !!value;
+      return this.evaluate(truthy === (node.name === 'if') ? node.body : node.inverse, scopes);
+    }).join('');
+  }
+
+  private lookup(path: string, scopes: unknown[]): unknown {
+    if (path === 'this') return scopes[scopes.length - 1];
+    const [head, ...rest] = path.replace(/^this\./, '').split('.');
+    for (let i = scopes.length - 1; i >= 0; i--) {
+      const scope = scopes[i];
+      if (scope !== null && typeof scope === 'object' && head in scope) {
+        return rest.reduce((value: any, key) => (value === undefined || value === null ? undefined : value[key]),
+          (scope as Record<string, unknown>)[head]);
+      }
+    }
+    return undefined;
+  }
+}
+
+// A directory of templates as a generator. Files ending in .hbs are rendered
+// (their paths too, so `{{programName}}.md.hbs` works) with the spec, program
+// name and id, and IDL in scope; other files are copied as they are.
+class TemplateGenerator implements CodeGenerator {
+  readonly name: string;
+
+  constructor(private dir: string, private engine = new TemplateEngine()) {
+    this.name = path.basename(path.resolve(dir));
+  }
+
+  generate(config: SolDConfig, context: GeneratorContext): GeneratedFile[] {
+    const scope = { ...context, config };
+    return this.walk(this.dir).map(file => {
+      const relative = this.engine.render(path.relative(this.dir, file).split(path.sep).join('/'), scope);
+      const source = fs.readFileSync(file, 'utf8');
+      return relative.endsWith('.hbs')
+        ? { path: relative.slice(0, -'.hbs'.length), contents: this.engine.render(source, scope) }
+        : { path: relative, contents: source };
+    });
+  }
+
+  private walk(dir: string): string[] {
+    return fs.readdirSync(dir, { withFileTypes: true }).flatMap(entry =>
+      entry.isDirectory() ? this.walk(path.join(dir, entry.name)) : [path.join(dir, entry.name)]);
+  }
+}
+
+// Load a generator module: its default export, a `generator` export or the
+// module itself, as an instance or a class to construct
+function loadGenerator(modulePath: string): CodeGenerator {
+  const loaded = require(path.resolve(modulePath));
+  const exported = loaded.default ?? loaded.generator ?? loaded;
+  const generator = typeof exported === 'function' ? new exported() : exported;
+  if (!generator || typeof generator.generate !== 'function') {
+    throw new Error(`${modulePath} does not export a code generator`);
+  }
+  generator.name = generator.name || path.basename(modulePath).replace(/\.[jt]s$/, '');
+  return generator;
+}
+
+// Write generated files, refusing any path that leaves the build directory
+function writeGeneratedFiles(outputDir: string, files: GeneratedFile[]): void {
+  const root = path.resolve(outputDir);
+  for (const file of files) {
+    const target = path.resolve(root, file.path);
+    if (!target.startsWith(root + path.sep)) {
+      throw new Error(`Generated file ${file.path} is outside ${outputDir}`);
+    }
+    fs.mkdirSync(path.dirname(target), { recursive: true });
+    fs.writeFileSync(target, file.contents);
+  }
+}
+
+// How the program deployed from an earlier spec differs from what the spec
+// generates now. Instruction, error and event changes break existing
+// clients; account layout changes break existing accounts unless they are
+// migrated, which `sold build --migrate-from` generates code for.
+export interface IdlChange {
+  kind: 'breaking' | 'migration' | 'compatible';
+  item: string;
+  message: string;
+}
+
+// An account type whose stored layout changed and can be rewritten in place
+interface LayoutMigration {
+  account: string;
+  from: IdlField[];
+  to: IdlField[];
+}
+
+class IdlDiffer {
+  diff(deployed: Idl, current: Idl): IdlChange[] {
+    const changes: IdlChange[] = [];
+    const change = (kind: IdlChange['kind'], item: string, message: string) => changes.push({ kind, item, message });
+    const same = (a: unknown, b: unknown) => JSON.stringify(a) === JSON.stringify(b);
+    const byName = <T extends { name: string }>(items: T[]) => new Map(items.map(item => [item.name, item] as [string, T]));
+
+    const instructions = byName(current.instructions);
+    for (const old of deployed.instructions) {
+      const now = instructions.get(old.name);
+      if (!now) {
+        change('breaking', `instruction ${old.name}`, 'removed');
+      } else if (!same(old.args, now.args)) {
+        change('breaking', `instruction ${old.name}`, 'arguments changed; existing clients encode the old ones');
+      } else if (!same(old.accounts.map(a => [a.name, a.isMut, a.isSigner]), now.accounts.map(a => [a.name, a.isMut, a.isSigner]))) {
+        change('breaking', `instruction ${old.name}`, 'accounts changed; existing clients pass the old list');
+      }
+    }
+    for (const ix of current.instructions) {
+      if (!deployed.instructions.some(old => old.name === ix.name)) change('compatible', `instruction ${ix.name}`, 'added');
+    }
+
+    const migrations = this.migrations(deployed, current);
+    const accounts = byName(current.accounts);
+    for (const old of deployed.accounts) {
+      const now = accounts.get(old.name);
+      if (!now) {
+        change('breaking', `account ${old.name}`, 'removed; existing accounts can no longer be read');
+        continue;
+      }
+      const fields = byName(now.type.fields);
+      const retyped = old.type.fields.filter(f => fields.has(f.name) && !same(f.type, fields.get(f.name)!.type));
+      if (retyped.length) {
+        change('breaking', `account ${old.name}`, `${retyped.map(f => f.name).join(', ')} changed type; migrate by hand`);
+      } else if (migrations.some(m => m.account === old.name)) {
+        const added = now.type.fields.filter(f => !old.type.fields.some(o => o.name === f.name)).map(f => `+${f.name}`);
+        const removed = old.type.fields.filter(f => !fields.has(f.name)).map(f => `-${f.name}`);
+        change('migration', `account ${old.name}`, `layout changed (${[...added, ...removed].join(', ')}); migrate with migrate_${toSnakeCase(old.name)}`);
+      } else if (!same(old.type.fields, now.type.fields)) {
+        change('breaking', `account ${old.name}`, 'fields reordered; migrate by hand');
+      }
+    }
+    for (const def of current.accounts) {
+      if (!deployed.accounts.some(old => old.name === def.name)) change('compatible', `account ${def.name}`, 'added');
+    }
+
+    const errors = byName(current.errors);
+    for (const old of deployed.errors) {
+      const now = errors.get(old.name);
+      if (!now) {
+        change('breaking', `error ${old.name}`, 'removed');
+      } else if (now.code !== old.code) {
+        change('breaking', `error ${old.name}`, `code moved from ${old.code} to ${now.code}`);
+      }
+    }
+    for (const error of current.errors) {
+      if (!deployed.errors.some(old => old.name === error.name)) change('compatible', `error ${error.name}`, `added as ${error.code}`);
+    }
+
+    const events = byName(current.events);
+    for (const old of deployed.events) {
+      const now = events.get(old.name);
+      if (!now || !same(old.fields, now.fields)) {
+        change('breaking', `event ${old.name}`, now ? 'fields changed; indexers decode the old ones' : 'removed');
+      }
+    }
+    for (const event of current.events) {
+      if (!deployed.events.some(old => old.name === event.name)) change('compatible', `event ${event.name}`, 'added');
+    }
+
+    const constants = byName(current.constants);
+    for (const old of deployed.constants) {
+      const now = constants.get(old.name);
+      if (!now) {
+        change('compatible', `constant ${old.name}`, 'removed');
+      } else if (now.value !== old.value) {
+        change('compatible', `constant ${old.name}`, `${old.value} -> ${now.value}`);
+      }
+    }
+    for (const constant of current.constants) {
+      if (!deployed.constants.some(old => old.name === constant.name)) {
+        change('compatible', `constant ${constant.name}`, `added as ${constant.value}`);
+      }
+    }
+
+    return changes;
+  }
+
+  // Account types that gained or lost fields but kept the type of every
+  // field they still have. Migrated accounts are recognised by their size,
+  // so a reorder that keeps the field set is not migratable.
+  migrations(deployed: Idl, current: Idl): LayoutMigration[] {
+    return deployed.accounts.flatMap(old => {
+      const now = current.accounts.find(def => def.name === old.name);
+      if (!now) return [];
+      const from = old.type.fields;
+      const to = now.type.fields;
+      const names = (fields: IdlField[]) => fields.map(f => f.name).sort().join();
+      const retyped = from.some(f => to.some(t => t.name === f.name && JSON.stringify(t.type) !== JSON.stringify(f.type)));
+      return !retyped && names(from) !== names(to) ? [{ account: old.name, from, to }] : [];
+    });
+  }
+}
+
+// The IDL `anchor idl init` stored for a deployed program: zlib-compressed
+// JSON after the discriminator, authority and length
+async function fetchDeployedIdl(connection: Connection, programId: PublicKey): Promise<Idl | null> {
+  const [base] = PublicKey.findProgramAddressSync([], programId);
+  const address = await PublicKey.createWithSeed(base, 'anchor:idl', programId);
+  const info = await connection.getAccountInfo(address);
+  if (!info) return null;
+  const length = info.data.readUInt32LE(40);
+  return JSON.parse(zlib.inflateSync(info.data.subarray(44, 44 + length)).toString('utf8'));
+}
+
+// Semantic analysis: checks a spec against what the generated program can
+// hold and enforce, reporting every problem with its position instead of
+// stopping at the first one like `parse` does
+type Severity = 'error' | 'warning' | 'info';
+
+export interface Diagnostic {
+  severity: Severity;
+  code: string;
+  message: string;
+  line: number;    // 1-based
+  column: number;  // 1-based
+  hint?: string;
+}
+
+interface SpecValue {
+  value: string;
+  line: number;
+  column: number;
+}
+
+interface SpecTag {
+  name: string;
+  line: number;
+  column: number;
+  text: string;
+}
+
+const KNOWN_TAGS = ['token', 'logo', 'timelock', 'insurance', 'transfer', 'relock', 'fees', 'budget', 'vesting', 'deploy'];
+const REQUIRED_TAGS = ['token', 'timelock', 'insurance', 'relock', 'fees'];
+
+class SolDLinter {
+  private parser = new SolDParser();
+  private readonly MAX_SUPPLY = BigInt('18446744073709551615');
+
+  check(soldCode: string): Diagnostic[] {
+    const diagnostics: Diagnostic[] = [];
+    const report = (severity: Severity, code: string, at: { line: number; column: number }, message: string, hint?: string) =>
+      diagnostics.push({ severity, code, message, line: at.line, column: at.column, hint });
+
+    // Limits come from the config block, so read it before anything else
+    const block = extractBlock(soldCode, 'config');
+    const constants = { ...DEFAULT_CONSTANTS };
+    if (block.unclosed) {
+      report('error', 'SOLD013', block.unclosed, 'config block is missing its closing }');
+    }
+    for (const entry of block.entries) {
+      try {
+        applyConstant(constants, entry.key, entry.value);
+      } catch (error) {
+        report('error', 'SOLD013', entry, error.message, entry.key ? undefined : 'Write entries as key = value');
+      }
+    }
+    const transferFee = block.entries.find(entry => entry.key === 'transfer_fee_bps' || entry.key === 'max_transfer_fee');
+    if (transferFee && constants.tokenStandard !== 'token2022') {
+      report('error', 'SOLD013', transferFee, `${transferFee.key} is a Token-2022 extension`, 'Add token_standard = "token2022"');
+    }
+    const feeBps = block.entries.find(entry => entry.key === 'transfer_fee_bps');
+    if (feeBps && constants.transferFeeBps > 0 && constants.maxTransferFee === '0') {
+      report('error', 'SOLD013', feeBps, 'Transfer fee is capped at 0 without a max_transfer_fee', 'Set max_transfer_fee to the largest fee per transfer');
+    }
+
+    const accessBlock = extractBlock(block.rest, 'access');
+    const { network, tags, invariants, events, modules } = this.scan(accessBlock.rest);
+    if (!network || !['DEVNET', 'MAINNET', 'TESTNET'].includes(network.value)) {
+      report('error', 'SOLD001', network || { line: 1, column: 1 },
+        `Invalid network: ${network ? network.value : '(missing)'}`, 'Start the spec with DEVNET, TESTNET or MAINNET');
+    }
+
+    for (const tag of tags) {
+      if (!KNOWN_TAGS.includes(tag.name)) {
+        report('warning', 'SOLD009', tag, `Unknown tag <${tag.name}> is ignored`, `Known tags: ${KNOWN_TAGS.join(', ')}`);
+      }
+    }
+    for (const name of REQUIRED_TAGS) {
+      if (!tags.some(tag => tag.name === name)) {
+        report('error', 'SOLD002', { line: 1, column: 1 }, `Missing required <${name}> tag`);
+      }
+    }
+    const tag = (name: string) => tags.find(t => t.name === name);
+    const attr = (name: string, key: string) => {
+      const t = tag(name);
+      return t ? this.attr(t, key) : undefined;
+    };
+    const duration = (value: SpecValue): number | undefined => {
+      try {
+        return this.parser.parseDuration(value.value);
+      } catch {
+        report('error', 'SOLD007', value, `Invalid duration "${value.value}"`, 'Use a number with s, m, h or d, e.g. "100d"');
+        return undefined;
+      }
+    };
+
+    // Token identity must fit the space the generated account reserves
+    const name = attr('token', 'name');
+    if (name && Buffer.byteLength(name.value) > constants.maxNameLen) {
+      report('error', 'SOLD006', name, `Token name is ${Buffer.byteLength(name.value)} bytes; the account holds ${constants.maxNameLen}`,
+        'Shorten the name, raise max_name_len, or keep the full name in the metadata URI');
+    }
+    const symbol = attr('token', 'symbol');
+    if (symbol && Buffer.byteLength(symbol.value) > constants.maxSymbolLen) {
+      report('error', 'SOLD006', symbol, `Token symbol is ${Buffer.byteLength(symbol.value)} bytes; the account holds ${constants.maxSymbolLen}`);
+    }
+    const supply = attr('token', 'supply');
+    if (supply) {
+      if (!/^\d+$/.test(supply.value) || BigInt(supply.value) === BigInt(0) || BigInt(supply.value) > this.MAX_SUPPLY) {
+        report('error', 'SOLD010', supply, `Supply must be a whole number between 1 and ${this.MAX_SUPPLY}`);
+      }
+    }
+
+    // Timelock: 1 hour anywhere, the configured minimum on mainnet
+    const timelock = attr('timelock', 'duration');
+    const lockSecs = timelock && duration(timelock);
+    if (timelock && lockSecs !== undefined) {
+      const minTimelock = constants.minTimelock;
+      if (lockSecs < 3_600) {
+        report('error', 'SOLD004', timelock, 'Timelock must be at least 1 hour');
+      } else if (lockSecs < this.parser.parseDuration(minTimelock)) {
+        const onMainnet = network?.value === 'MAINNET';
+        report(onMainnet ? 'error' : 'warning', 'SOLD004', timelock,
+          `Timelock is shorter than ${minTimelock}${onMainnet ? '' : '; only accepted off mainnet'}`,
+          `Use duration="${minTimelock}" or longer before deploying to mainnet`);
+      }
+    }
+
+    // Insurance can drain at most the configured share of the supply
+    const limit = attr('insurance', 'limit');
+    if (limit && parseInt(limit.value) > constants.maxInsuranceLimit) {
+      report('error', 'SOLD003', limit, `Insurance limit ${limit.value}% exceeds ${constants.maxInsuranceLimit}%`,
+        `Set limit="${constants.maxInsuranceLimit}" or lower`);
+    }
+    const wallets = attr('insurance', 'wallets');
+    if (wallets) {
+      const count = wallets.value.split(',').filter(w => w.trim()).length;
+      if (count > constants.maxInsuranceWallets) {
+        report('error', 'SOLD011', wallets, `${count} insurance wallets; the account holds ${constants.maxInsuranceWallets}`);
+      }
+    }
+    if (limit && parseInt(limit.value) === 0) {
+      report('warning', 'SOLD008', limit, 'emergency_withdraw is unreachable with a 0% insurance limit',
+        'Raise the limit or drop the insurance wallets');
+    }
+
+    // Keys the generated program parses at runtime must be valid pubkeys
+    const recipient = attr('fees', 'recipient');
+    if (recipient) {
+      if (!this.isPubkey(recipient.value)) {
+        report('error', 'SOLD005', recipient, `Fee recipient "${recipient.value}" is not a valid base58 public key`);
+      } else if (recipient.value !== constants.feeRecipient) {
+        report('error', 'SOLD005', recipient, 'Fee recipient is not the configured fee address', `Use recipient="${constants.feeRecipient}"`);
+      }
+    }
+    const escrow = attr('relock', 'escrow');
+    if (escrow && !this.isPubkey(escrow.value)) {
+      report('error', 'SOLD005', escrow, `Relock escrow "${escrow.value}" is not a valid base58 public key`,
+        'relock_tokens would be unreachable: no signer can match an invalid escrow');
+    }
+    const relock = attr('relock', 'duration');
+    if (relock) duration(relock);
+
+    // The upgrade authority is one key: a wallet or a Squads multisig
+    const deploy = tag('deploy');
+    if (deploy) {
+      const [authority, squads, vault] = ['authority', 'squads', 'vault'].map(key => this.attr(deploy, key));
+      if (!authority && !squads) {
+        report('error', 'SOLD014', deploy, 'Deploy must specify an upgrade authority or squads multisig');
+      } else if (authority && squads) {
+        report('error', 'SOLD014', squads, 'Deploy takes either authority or squads, not both',
+          'A Squads multisig upgrades through its vault; drop authority');
+      }
+      for (const key of [authority, squads]) {
+        if (key && !this.isPubkey(key.value)) {
+          report('error', 'SOLD005', key, `Deploy key "${key.value}" is not a valid base58 public key`);
+        }
+      }
+      if (vault && (!/^\d+$/.test(vault.value) || parseInt(vault.value) > 255)) {
+        report('error', 'SOLD014', vault, 'Squads vault index must be between 0 and 255');
+      }
+      if (vault && !squads) {
+        report('warning', 'SOLD014', vault, 'vault only applies to a squads multisig');
+      }
+    }
+
+    const vesting = tag('vesting');
+    if (vesting) {
+      const [cliff, total, interval] = ['cliff', 'duration', 'interval'].map(key => this.attr(vesting, key));
+      const secs = [cliff, total, interval].map(value => value && duration(value));
+      if (total && interval && secs[1] !== undefined && secs[2] !== undefined) {
+        if (secs[1] === 0 || secs[2] === 0 || secs[2]! > secs[1]!) {
+          report('error', 'SOLD012', interval, 'Vesting interval must be non-zero and no longer than the duration');
+        }
+        if (cliff && secs[0] !== undefined && secs[0]! > secs[1]!) {
+          report('error', 'SOLD012', cliff, 'Vesting cliff is longer than the vesting duration');
+        }
+      } else if (!total || !interval) {
+        report('error', 'SOLD002', vesting, 'Vesting must specify duration and interval');
+      }
+    }
+
+    // Access entries name gated instructions and known roles. Opening an
+    // instruction that defaults to a role to anyone is allowed, but flagged.
+    if (accessBlock.unclosed) {
+      report('error', 'SOLD016', accessBlock.unclosed, 'access block is missing its closing }');
+    }
+    for (const entry of accessBlock.entries) {
+      try {
+        applyAccess({ ...DEFAULT_ACCESS }, entry.key, entry.value);
+      } catch (error) {
+        report('error', 'SOLD016', entry, error.message, entry.key ? undefined : 'Write entries as instruction => role, role');
+        continue;
+      }
+      if (entry.key === 'create_vesting' && !vesting) {
+        report('error', 'SOLD016', entry, 'create_vesting only exists with a <vesting> tag');
+      } else if (entry.value === 'anyone' && !DEFAULT_ACCESS[entry.key].includes('anyone')) {
+        report('warning', 'SOLD016', entry, `${entry.key} is open to every wallet`,
+          `By default only the ${DEFAULT_ACCESS[entry.key].join(', ')} may call it`);
+      }
+    }
+
+    // Modules: one list of known modules, and config keys only for those
+    let declared: SpecModule[] = [];
+    for (const [i, line] of modules.entries()) {
+      const match = line.value.match(/^modules\s*=\s*\[([^\]]*)\]$/);
+      if (i > 0) {
+        report('error', 'SOLD017', line, 'A spec declares its modules once', 'Merge the lists into one modules line');
+      } else if (!match) {
+        report('error', 'SOLD017', line, 'Expected modules = [module, ...]', `Modules: ${MODULES.join(', ')}`);
+      } else {
+        try {
+          declared = parseModules(match[1]);
+        } catch (error) {
+          report('error', 'SOLD017', line, error.message);
+          declared = MODULES.filter(module => match[1].split(',').some(name => name.trim() === module));
+        }
+      }
+    }
+    for (const entry of block.entries) {
+      const module = MODULE_KEYS[entry.key];
+      if (module && !declared.includes(module)) {
+        report('error', 'SOLD017', entry, `${entry.key} configures the ${module} module, which the spec does not declare`,
+          `Add ${module} to the spec's modules`);
+      }
+    }
+
+    // Invariants compile against the accounts this spec generates
+    const scope = { vesting: !!vesting, token2022: constants.tokenStandard === 'token2022' };
+    for (const invariant of invariants) {
+      try {
+        compileInvariant(invariant.value, scope);
+      } catch (error) {
+        report('error', 'SOLD015', invariant, error.message);
+      }
+    }
+
+    // Events name the args and accounts of an instruction; which exist
+    // depends on the whole spec, so they resolve once the rest parses
+    const declaredEvents: { at: SpecValue; event: EventConfig }[] = [];
+    for (const line of events) {
+      try {
+        const event = parseEvent(line.value);
+        if (declaredEvents.some(({ event: other }) => other.name === event.name)) {
+          report('error', 'SOLD018', line, `Event ${event.name} is declared twice`);
+        } else {
+          declaredEvents.push({ at: line, event });
+        }
+      } catch (error) {
+        report('error', 'SOLD018', line, error.message);
+      }
+    }
+    if (declaredEvents.length) {
+      let instructions: IdlInstruction[] | undefined;
+      try {
+        const spec = soldCode.split('\n').map(text => /^\s*event\b/.test(text) ? '' : text).join('\n');
+        instructions = this.parser.generateIdl(this.parser.parse(spec)).instructions;
+      } catch {
+        // The spec's other errors are reported by their own rules
+      }
+      for (const { at, event } of instructions ? declaredEvents : []) {
+        try {
+          compileEvent(event, instructions!);
+        } catch (error) {
+          report('error', 'SOLD018', at, error.message);
+        }
+      }
+    }
+
+    return diagnostics.sort((a, b) => a.line - b.line || a.column - b.column);
+  }
+
+  private scan(soldCode: string): { network?: SpecValue; tags: SpecTag[]; invariants: SpecValue[]; events: SpecValue[]; modules: SpecValue[] } {
+    const lines = soldCode.split('\n');
+    let network: SpecValue | undefined;
+    const tags: SpecTag[] = [];
+    const invariants: SpecValue[] = [];
+    const events: SpecValue[] = [];
+    const modules: SpecValue[] = [];
+    lines.forEach((text, i) => {
+      const trimmed = text.trim();
+      if (!trimmed) return;
+      const column = text.indexOf(trimmed) + 1;
+      if (trimmed.startsWith('invariant ')) {
+        const expression = trimmed.slice('invariant '.length);
+        invariants.push({ value: expression.trim(), line: i + 1, column: column + trimmed.indexOf(expression.trim()) });
+        return;
+      }
+      if (network && /^modules\b/.test(trimmed)) {
+        modules.push({ value: trimmed, line: i + 1, column });
+        return;
+      }
+      if (network && /^event\b/.test(trimmed)) {
+        events.push({ value: trimmed, line: i + 1, column });
+        return;
+      }
+      if (!network && tags.length === 0 && !trimmed.startsWith('<')) {
+        network = { value: trimmed, line: i + 1, column };
+        return;
+      }
+      const match = trimmed.match(/^<(\w+)/);
+      if (match) tags.push({ name: match[1], line: i + 1, column, text });
+    });
+    return { network, tags, invariants, events, modules };
+  }
+
+  // Position of an attribute's value, pointing just inside the quote or bracket
+  private attr(tag: SpecTag, key: string): SpecValue | undefined {
+    const match = new RegExp(`\\b${key}=(?:"([^"]*)"|\\[([^\\]]*)\\])`).exec(tag.text);
+    if (!match) return undefined;
+    const value = match[1] ?? match[2];
+    return { value, line: tag.line, column: match.index + key.length + 3 };
+  }
+
+  private isPubkey(value: string): boolean {
+    try {
+      new PublicKey(value);
+      return true;
+    } catch {
+      return false;
+    }
+  }
+}
+
+function formatDiagnostic(file: string, diagnostic: Diagnostic): string {
+  const { severity, code, message, line, column, hint } = diagnostic;
+  return `${file}:${line}:${column}: ${severity}[${code}]: ${message}` + (hint ? `\n  hint: ${hint}` : '');
+}
+
+// Vanity mint grinding
+const BASE58_ALPHABET = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+
+interface GrindOptions {
+  suffix?: string;
+  prefix?: string;
+  seed?: string;          // hex seed; the same seed always yields the same keypair
+  maxAttempts?: number;
+}
+
+interface GrindResult {
+  keypair: Keypair;
+  attempts: number;
+  seed: string;
+  nonce: number;
+}
+
+class MintGrinder {
+  grind(options: GrindOptions): GrindResult {
+    const suffix = options.suffix || '';
+    const prefix = options.prefix || '';
+    if (!suffix && !prefix) {
+      throw new Error('grind-mint requires --suffix and/or --prefix');
+    }
+    for (const char of prefix + suffix) {
+      if (!BASE58_ALPHABET.includes(char)) {
+        throw new Error(`Invalid character '${char}': mint addresses are base58 (no 0, O, I or l)`);
+      }
+    }
+
+    const seed = options.seed || crypto.randomBytes(32).toString('hex');
+    const maxAttempts = options.maxAttempts || 10_000_000;
+
+    // Keypairs are derived as sha256(seed || nonce) so a grind run with
+    // the same --seed reproduces the same keypair
+    for (let nonce = 0; nonce < maxAttempts; nonce++) {
+      const keypair = this.deriveKeypair(seed, nonce);
+      const address = keypair.publicKey.toBase58();
+      if (address.endsWith(suffix) && address.startsWith(prefix)) {
+        return { keypair, attempts: nonce + 1, seed, nonce };
+      }
+    }
+
+    throw new Error(`No matching mint found after ${maxAttempts} attempts`);
+  }
+
+  deriveKeypair(seed: string, nonce: number): Keypair {
+    const nonceBytes = Buffer.alloc(8);
+    nonceBytes.writeBigUInt64LE(BigInt(nonce));
+    const digest = crypto.createHash('sha256')
+      .update(Buffer.from(seed, 'hex'))
+      .update(nonceBytes)
+      .digest();
+    return Keypair.fromSeed(digest);
+  }
+}
+
+// Compute unit and transaction size budgets
+const MAX_TX_SIZE = 1232;
+const DEFAULT_CU_BUDGET = 200000;
+const ASSUMED_DYNAMIC_LEN = 64; // bytes assumed for strings and vecs in size estimates
+
+interface BudgetConfig {
+  cu: number;
+  txSize: number;
+}
+
+interface InstructionBudget {
+  instruction: string;
+  accounts: number;
+  signers: number;
+  estimatedTxSize: number;
+  measuredCu?: number;
+  overBudget: boolean;
+}
+
+class BudgetReporter {
+  // Estimate the serialized size of a legacy transaction carrying only
+  // this instruction, from its IDL account list and argument types
+  estimateTxSize(ix: any): { size: number; accounts: number; signers: number } {
+    const accounts = this.flattenAccounts(ix.accounts);
+    const signers = Math.max(1, accounts.filter((a: any) => a.isSigner).length);
+    const dataLen = 8 + ix.args.reduce((sum: number, arg: any) => sum + this.typeSize(arg.type), 0);
+    const keys = accounts.length + 1; // + program id
+
+    const size =
+      1 + signers * 64 +                       // signatures
+      3 +                                      // message header
+      1 + keys * 32 +                          // account keys
+      32 +                                     // recent blockhash
+      1 + 1 + 1 + accounts.length + 2 + dataLen; // instruction: count, program index, accounts, data
+    return { size, accounts: accounts.length, signers };
+  }
+
+  report(idl: any, budget: BudgetConfig, measuredCu: Record<string, number>): InstructionBudget[] {
+    return idl.instructions.map((ix: any) => {
+      const { size, accounts, signers } = this.estimateTxSize(ix);
+      const cu = measuredCu[ix.name];
+      return {
+        instruction: ix.name,
+        accounts,
+        signers,
+        estimatedTxSize: size,
+        measuredCu: cu,
+        overBudget: size > budget.txSize || (cu !== undefined && cu > budget.cu)
+      };
+    });
+  }
+
+  private flattenAccounts(accounts: any[]): any[] {
+    return accounts.flatMap(a => a.accounts ? this.flattenAccounts(a.accounts) : [a]);
+  }
+
+  private typeSize(type: any): number {
+    const fixed: Record<string, number> = {
+      bool: 1, u8: 1, i8: 1, u16: 2, i16: 2, u32: 4, i32: 4, f32: 4,
+      u64: 8, i64: 8, f64: 8, u128: 16, i128: 16, publicKey: 32, pubkey: 32
+    };
+    if (typeof type === 'string') {
+      return fixed[type] ?? 4 + ASSUMED_DYNAMIC_LEN;
+    }
+    if (type.option) return 1 + this.typeSize(type.option);
+    if (type.array) return type.array[1] * this.typeSize(type.array[0]);
+    if (type.vec) return 4 + ASSUMED_DYNAMIC_LEN;
+    return ASSUMED_DYNAMIC_LEN; // user-defined structs
+  }
+}
+
+// Tokenomics chart export
+interface ReleaseEvent {
+  day: number;
+  amount: bigint;
+  label: string;
+}
+
+interface TokenomicsPoint {
+  day: number;
+  circulating: string;
+  locked: string;
+  maxInsuranceRelease: string;
+}
+
+interface TokenomicsOptions {
+  stepDays?: number;
+  horizonDays?: number;
+}
+
+class TokenomicsExporter {
+  private parser: SolDParser;
+
+  constructor(parser: SolDParser) {
+    this.parser = parser;
+  }
+
+  // Release schedule as the program enforces it: the full supply is minted
+  // into the launch vault and only leaves it once the timelock expires.
+  // Supply is fixed, so there is no inflation term.
+  schedule(config: SolDConfig): ReleaseEvent[] {
+    const unlockDay = Math.ceil(this.parser.parseDuration(config.timelock.duration) / 86400);
+    return [{ day: unlockDay, amount: BigInt(config.token.supply), label: 'timelock unlock' }];
+  }
+
+  export(config: SolDConfig, options: TokenomicsOptions = {}) {
+    const supply = BigInt(config.token.supply);
+    const events = this.schedule(config);
+    const lastEvent = Math.max(...events.map(e => e.day));
+    const stepDays = options.stepDays || 7;
+    const horizonDays = options.horizonDays || lastEvent + 30;
+
+    // Insurance wallets may pull up to `limit`% of supply before unlock
+    const insuranceCap = supply * BigInt(config.insurance.limit) / BigInt(100);
+
+    const days = new Set<number>();
+    for (let day = 0; day <= horizonDays; day += stepDays) days.add(day);
+    events.filter(e => e.day <= horizonDays).forEach(e => days.add(e.day));
+
+    const series: TokenomicsPoint[] = Array.from(days).sort((a, b) => a - b).map(day => {
+      const released = events
+        .filter(e => e.day <= day)
+        .reduce((sum, e) => sum + e.amount, BigInt(0));
+      const circulating = released > supply ? supply : released;
+      const locked = supply - circulating;
+      return {
+        day,
+        circulating: circulating.toString(),
+        locked: locked.toString(),
+        maxInsuranceRelease: (locked < insuranceCap ? locked : insuranceCap).toString()
+      };
+    });
+
+    return {
+      token: config.token.symbol,
+      totalSupply: supply.toString(),
+      inflation: '0',
+      events: events.map(e => ({ ...e, amount: e.amount.toString() })),
+      series
+    };
+  }
+}
+
+// CLI Interface
+// Launch archival export
+const ARCHIVE_FORMAT_VERSION = 1;
+const LAUNCH_PROGRAM_ID = 'So1DLaunchProgram11111111111111111111111111';
+const ARCHIVE_SEEDS = ['vault', 'sol_vault', 'bonding_curve', 'liquidity_lock', 'annotations',
+  'yield_position', 'allocation_vault', 'archive'];
+const ARCHIVE_LAUNCH_SEEDS = ['appeal', 'underwriting', 'recovery', 'sponsor_pool'];
+
+interface ArchivedAccount {
+  address: string;
+  owner: string;
+  lamports: number;
+  data: string;        // base64
+  type?: string;
+  decoded?: unknown;
+}
+
+interface ArchivedEvent {
+  signature: string;
+  slot: number;
+  blockTime: number | null;
+  name: string;
+  data: unknown;
+}
+
+interface LaunchArchive {
+  version: number;
+  programId: string;
+  tokenMint: string;
+  launch: string;
+  slot: number;
+  createdAt: string;
+  accounts: ArchivedAccount[];
+  events: ArchivedEvent[];
+  merkleRoots: { accounts: string; events: string };
+  archiveHash: string;
+  signer: string;
+  signature: string;
+}
+
+class LaunchArchiver {
+  private connection: Connection;
+  private programId: PublicKey;
+  private coder: BorshCoder;
+  private idl: any;
+
+  constructor(rpcUrl: string, idl: any, programId = LAUNCH_PROGRAM_ID) {
+    this.connection = new Connection(rpcUrl, 'confirmed');
+    this.programId = new PublicKey(programId);
+    this.coder = new BorshCoder(idl);
+    this.idl = idl;
+  }
+
+  // Snapshot every account tied to the launch and its full event history,
+  // commit to both with merkle roots, and sign the resulting archive hash.
+  // The hash is what close_launch records on-chain.
+  async export(tokenMint: PublicKey, signer: Keypair): Promise<LaunchArchive> {
+    const [launch] = PublicKey.findProgramAddressSync([Buffer.from('launch'), tokenMint.toBuffer()], this.programId);
+    const slot = await this.connection.getSlot();
+
+    const accounts: ArchivedAccount[] = (await this.collectAccounts(tokenMint, launch)).map(toPlain);
+    const events: ArchivedEvent[] = (await this.collectEvents(launch)).map(toPlain);
+
+    const merkleRoots = {
+      accounts: merkleRoot(accounts.map(account => canonicalJson(account))),
+      events: merkleRoot(events.map(event => canonicalJson(event)))
+    };
+    const archiveHash = archiveDigest(this.programId.toBase58(), tokenMint.toBase58(), slot, merkleRoots);
+
+    return {
+      version: ARCHIVE_FORMAT_VERSION,
+      programId: this.programId.toBase58(),
+      tokenMint: tokenMint.toBase58(),
+      launch: launch.toBase58(),
+      slot,
+      createdAt: new Date().toISOString(),
+      accounts,
+      events,
+      merkleRoots,
+      archiveHash,
+      signer: signer.publicKey.toBase58(),
+      signature: Buffer.from(nacl.sign.detached(Buffer.from(archiveHash, 'hex'), signer.secretKey)).toString('hex')
+    };
+  }
+
+  private async collectAccounts(tokenMint: PublicKey, launch: PublicKey): Promise<ArchivedAccount[]> {
+    const derive = (seed: string, key: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from(seed), key.toBuffer()], this.programId)[0];
+    const addresses = [
+      launch,
+      tokenMint,
+      ...ARCHIVE_SEEDS.map(seed => derive(seed, tokenMint)),
+      ...ARCHIVE_LAUNCH_SEEDS.map(seed => derive(seed, launch))
+    ];
+
+    const infos = await this.connection.getMultipleAccountsInfo(addresses);
+    const archived = addresses
+      .map((address, i) => ({ address, info: infos[i] }))
+      .filter(({ info }) => info !== null)
+      .map(({ address, info }) => this.archiveAccount(address, info!));
+
+    // Per-wallet and per-proposal records all lead with the launch key
+    const records = await this.connection.getProgramAccounts(this.programId, {
+      filters: [{ memcmp: { offset: 8, bytes: launch.toBase58() } }]
+    });
+    for (const { pubkey, account } of records) {
+      archived.push(this.archiveAccount(pubkey, account));
+    }
+
+    return archived.sort((a, b) => a.address.localeCompare(b.address));
+  }
+
+  private archiveAccount(address: PublicKey, info: AccountInfo<Buffer>): ArchivedAccount {
+    const account: ArchivedAccount = {
+      address: address.toBase58(),
+      owner: info.owner.toBase58(),
+      lamports: info.lamports,
+      data: info.data.toString('base64')
+    };
+    if (info.owner.equals(this.programId)) {
+      for (const def of this.idl.accounts || []) {
+        const discriminator = BorshAccountsCoder.accountDiscriminator(def.name);
+        if (info.data.subarray(0, 8).equals(discriminator)) {
+          account.type = def.name;
+          account.decoded = this.coder.accounts.decode(def.name, info.data);
+          break;
+        }
+      }
+    }
+    return account;
+  }
+
+  private async collectEvents(launch: PublicKey): Promise<ArchivedEvent[]> {
+    const events: ArchivedEvent[] = [];
+    let before: string | undefined;
+
+    // Page back through the launch's whole history, oldest events first
+    for (;;) {
+      const page = await this.connection.getSignaturesForAddress(launch, { before, limit: 1000 });
+      if (page.length === 0) break;
+      for (const { signature, slot, blockTime, err } of page) {
+        if (err) continue;
+        const tx = await this.connection.getTransaction(signature, { maxSupportedTransactionVersion: 0 });
+        for (const line of tx?.meta?.logMessages || []) {
+          if (!line.startsWith('Program data: ')) continue;
+          const event = this.coder.events.decode(line.slice('Program data: '.length));
+          if (event) {
+            events.push({ signature, slot, blockTime: blockTime ?? null, name: event.name, data: event.data });
+          }
+        }
+      }
+      before = page[page.length - 1].signature;
+    }
+
+    return events.reverse();
+  }
+}
+
+// Plain JSON form of decoded program data: sorted keys, keys and big
+// numbers as strings, bytes as hex. Archives store this form so hashes
+// recomputed from the written file match the ones taken at export.
+function toPlain(v: any): any {
+  if (v === null || v === undefined) return null;
+  if (typeof v === 'bigint') return v.toString();
+  if (v instanceof PublicKey) return v.toBase58();
+  if (BN.isBN(v)) return v.toString();
+  if (v instanceof Uint8Array) return Buffer.from(v).toString('hex');
+  if (Array.isArray(v)) return v.map(toPlain);
+  if (typeof v === 'object') {
+    return Object.keys(v).sort().reduce((out: any, key) => {
+      out[key] = toPlain(v[key]);
+      return out;
+    }, {});
+  }
+  return v;
+}
+
+function canonicalJson(value: unknown): string {
+  return JSON.stringify(toPlain(value));
+}
+
+function sha256(...parts: (string | Buffer)[]): Buffer {
+  const hash = crypto.createHash('sha256');
+  parts.forEach(part => hash.update(part));
+  return hash.digest();
+}
+
+// Binary sha256 merkle tree; an odd node is carried up unchanged
+function merkleRoot(leaves: string[]): string {
+  if (leaves.length === 0) return sha256('').toString('hex');
+  let level = leaves.map(leaf => sha256(leaf));
+  while (level.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? sha256(level[i], level[i + 1]) : level[i]);
+    }
+    level = next;
+  }
+  return level[0].toString('hex');
+}
+
+function archiveDigest(programId: string, tokenMint: string, slot: number, roots: { accounts: string; events: string }): string {
+  return sha256(canonicalJson({ version: ARCHIVE_FORMAT_VERSION, programId, tokenMint, slot, merkleRoots: roots })).toString('hex');
+}
+
+// Recompute roots and hash from the archive's contents and check the signature
+function verifyArchive(archive: LaunchArchive): { valid: boolean; reason?: string } {
+  const roots = {
+    accounts: merkleRoot(archive.accounts.map(account => canonicalJson(account))),
+    events: merkleRoot(archive.events.map(event => canonicalJson(event)))
+  };
+  if (roots.accounts !== archive.merkleRoots.accounts) return { valid: false, reason: 'account root mismatch' };
+  if (roots.events !== archive.merkleRoots.events) return { valid: false, reason: 'event root mismatch' };
+
+  const hash = archiveDigest(archive.programId, archive.tokenMint, archive.slot, roots);
+  if (hash !== archive.archiveHash) return { valid: false, reason: 'archive hash mismatch' };
+
+  const signed = nacl.sign.detached.verify(
+    Buffer.from(hash, 'hex'),
+    Buffer.from(archive.signature, 'hex'),
+    new PublicKey(archive.signer).toBytes()
+  );
+  return signed ? { valid: true } : { valid: false, reason: 'bad signature' };
+}
+
+// Launch fee quotes, computed by simulating the program's
+// estimate_launch_fees view, so they always match what initialize_launch
+// charges under the deployed config
+class LaunchFeeEstimator {
+  private connection: Connection;
+  private programId: PublicKey;
+  private coder: BorshCoder;
+  private idl: any;
+
+  constructor(rpcUrl: string, idl: any, programId = LAUNCH_PROGRAM_ID) {
+    this.connection = new Connection(rpcUrl, 'confirmed');
+    this.programId = new PublicKey(programId);
+    this.coder = new BorshCoder(idl);
+    this.idl = idl;
+  }
+
+  // `params` is LaunchParams as plain JSON, with the IDL's field names.
+  // The creator pays for the simulation, so it must hold some SOL.
+  async estimate(params: unknown, creator: PublicKey, tokenMint?: PublicKey): Promise<any> {
+    const ix = this.idl.instructions.find((ix: any) => ix.name === 'estimateLaunchFees');
+    if (!ix) {
+      throw new Error('IDL has no estimateLaunchFees instruction; regenerate it from the current program');
+    }
+    const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, this.programId)[0];
+    const accounts = [
+      creator,
+      pda(Buffer.from('creator_index'), creator.toBuffer()),
+      pda(Buffer.from('creator_profile'), creator.toBuffer()),
+      pda(Buffer.from('launch_registry')),
+      tokenMint ?? this.programId, // An omitted optional account is passed as the program id
+      pda(Buffer.from('config'))
+    ];
+    const instruction = new TransactionInstruction({
+      programId: this.programId,
+      keys: accounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })),
+      data: this.coder.instruction.encode(ix.name, { params: fromPlain(this.idl, ix.args[0].type, params) })
+    });
+
+    const { blockhash } = await this.connection.getLatestBlockhash();
+    const message = new TransactionMessage({ payerKey: creator, recentBlockhash: blockhash, instructions: [instruction] });
+    const { value } = await this.connection.simulateTransaction(new VersionedTransaction(message.compileToV0Message()), {
+      sigVerify: false,
+      replaceRecentBlockhash: true
+    });
+    if (value.err || !value.returnData) {
+      const logs = (value.logs || []).filter(line => line.includes('Error')).join('\n   ');
+      throw new Error(`estimate_launch_fees failed: ${JSON.stringify(value.err)}${logs ? `\n   ${logs}` : ''}`);
+    }
+    return this.coder.types.decode('FeeEstimate', Buffer.from(value.returnData.data[0], 'base64'));
+  }
+}
+
+const SPL_TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const SPL_MINT_LEN = 82;
+// REGISTRY_PAGE_SIZE and FEE_SHARDS in the program
+const REGISTRY_PAGE_SIZE = 64;
+const FEE_SHARDS = 8;
+const MAX_COMPUTE_UNITS = 1_400_000;
+// FRAUD_SCORE_WINDOW and FRAUD_SCORE_MIN_SPAN in the program
+const FRAUD_SCORE_WINDOW = 6 * 3600;
+const FRAUD_SCORE_MIN_SPAN = 3600;
+
+// Compute budget for the client's transactions. Each is a number, 'auto'
+// to estimate it, or unset to keep the runtime default.
+interface ComputeBudgetOptions {
+  units?: number | 'auto';          // 'auto': simulated usage plus 20%
+  microLamports?: number | 'auto';  // 'auto': a percentile of recent fees on the written accounts
+  percentile?: number;
+}
+
+async function withComputeBudget(connection: Connection, payer: PublicKey, instructions: TransactionInstruction[],
+                                 options: ComputeBudgetOptions = {}): Promise<TransactionInstruction[]> {
+  const budget: TransactionInstruction[] = [];
+  let units = options.units;
+  if (units === 'auto') {
+    const message = new TransactionMessage({
+      payerKey: payer,
+      recentBlockhash: PublicKey.default.toBase58(),
+      instructions: [ComputeBudgetProgram.setComputeUnitLimit({ units: MAX_COMPUTE_UNITS }), ...instructions]
+    }).compileToV0Message();
+    const { value } = await connection.simulateTransaction(new VersionedTransaction(message), {
+      sigVerify: false,
+      replaceRecentBlockhash: true
+    });
+    if (value.err) throw new Error(`Simulation failed: ${JSON.stringify(value.err)}\n${(value.logs || []).slice(-5).join('\n')}`);
+    units = Math.min(MAX_COMPUTE_UNITS, Math.ceil((value.unitsConsumed ?? 0) * 1.2));
+  }
+  if (units) budget.push(ComputeBudgetProgram.setComputeUnitLimit({ units }));
+
+  let microLamports = options.microLamports;
+  if (microLamports === 'auto') {
+    const writable = new Map<string, PublicKey>();
+    instructions.forEach(ix => ix.keys.forEach(key => key.isWritable && writable.set(key.pubkey.toBase58(), key.pubkey)));
+    const recent = await connection.getRecentPrioritizationFees({ lockedWritableAccounts: [...writable.values()].slice(0, 128) });
+    const fees = recent.map(fee => fee.prioritizationFee).sort((a, b) => a - b);
+    microLamports = fees.length ? fees[Math.min(fees.length - 1, Math.floor(fees.length * (options.percentile ?? 75) / 100))] : 0;
+  }
+  if (microLamports) budget.push(ComputeBudgetProgram.setComputeUnitPrice({ microLamports }));
+  return [...budget, ...instructions];
+}
+
+// Operator commands against a deployed launch program: builds each
+// instruction from the IDL, naming accounts rather than ordering them by
+// hand, and sends it signed by a local keypair
+class LaunchClient {
+  private connection: Connection;
+  private programId: PublicKey;
+  private coder: BorshCoder;
+  private idl: any;
+  private signer: Keypair;
+  private budget: ComputeBudgetOptions;
+
+  constructor(rpcUrl: string, idl: any, signer: Keypair, budget: ComputeBudgetOptions = {}, programId = LAUNCH_PROGRAM_ID) {
+    this.connection = new Connection(rpcUrl, 'confirmed');
+    this.programId = new PublicKey(programId);
+    this.coder = new BorshCoder(idl);
+    this.idl = idl;
+    this.signer = signer;
+    this.budget = budget;
+  }
+
+  // Create the mint if it does not exist yet (creator as mint authority),
+  // then initialize_launch and create_token, which mints the supply into
+  // the launch vault
+  // With `template`, `params` holds only the per-launch fields
+  // (TemplateLaunchParams); the template version defaults to its current one
+  async init(params: any, mint: Keypair, decimals: number, template?: PublicKey): Promise<string[]> {
+    const creator = this.signer.publicKey;
+    const tokenMint = mint.publicKey;
+    const tokenLaunch = this.pda('launch', tokenMint);
+    const signatures: string[] = [];
+
+    const mintInfo = await this.connection.getAccountInfo(tokenMint);
+    if (!mintInfo) {
+      const lamports = await this.connection.getMinimumBalanceForRentExemption(SPL_MINT_LEN);
+      const initializeMint = Buffer.concat([Buffer.from([20, decimals]), creator.toBuffer(), Buffer.from([0])]);
+      signatures.push(await this.send([
+        SystemProgram.createAccount({
+          fromPubkey: creator, newAccountPubkey: tokenMint, lamports, space: SPL_MINT_LEN, programId: SPL_TOKEN_PROGRAM_ID
+        }),
+        new TransactionInstruction({
+          programId: SPL_TOKEN_PROGRAM_ID,
+          keys: [{ pubkey: tokenMint, isSigner: false, isWritable: true }],
+          data: initializeMint
+        })
+      ], [mint]));
+    }
+    const tokenProgram = mintInfo?.owner ?? SPL_TOKEN_PROGRAM_ID;
+
+    const registry = await this.fetch('LaunchRegistry', this.pda('launch_registry'));
+    const nextPage = registry ? Math.floor(registry.launchCount.toNumber() / REGISTRY_PAGE_SIZE) : 0;
+    const kycAttestation = this.pda('kyc_attestation', creator);
+    const terms = template ? await this.fetch('LaunchTemplate', template) : null;
+    if (template && !terms) throw new Error(`No launch template at ${template.toBase58()}`);
+    const [name, type, plain] = terms
+      ? ['initializeFromTemplate', 'TemplateLaunchParams', { templateVersion: terms.version, ...params }]
+      : ['initializeLaunch', 'LaunchParams', params];
+    const initialize = this.instruction(name, { params: fromPlain(this.idl, { defined: type }, plain) }, {
+      creator,
+      tokenLaunch,
+      creatorIndex: this.pda('creator_index', creator),
+      creatorProfile: this.pda('creator_profile', creator),
+      launchRegistry: this.pda('launch_registry'),
+      registryPage: this.pda('registry_page', new BN(nextPage).toArrayLike(Buffer, 'le', 8)),
+      tokenMint,
+      config: this.pda('config'),
+      treasury: this.pda('treasury'),
+      referrer: params.referrer ? new PublicKey(params.referrer) : null,
+      kycAttestation: await this.connection.getAccountInfo(kycAttestation) ? kycAttestation : null,
+      bootstrapGuard: params.bootstrap ? this.pda('bootstrap_guard', tokenLaunch) : null
+    }, template ? [template] : []);
+    const createToken = this.instruction('createToken', { decimals }, {
+      creator,
+      tokenLaunch,
+      tokenMint,
+      vault: this.pda('vault', tokenMint),
+      metadata: PublicKey.findProgramAddressSync(
+        [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), tokenMint.toBuffer()], TOKEN_METADATA_PROGRAM_ID)[0],
+      tokenProgram,
+      metadataProgram: TOKEN_METADATA_PROGRAM_ID
+    });
+    signatures.push(await this.send([initialize]));
+    signatures.push(await this.send([createToken]));
+    return signatures;
+  }
+
+  // Transfer `amount` (in whole tokens, decimals allowed) from the
+  // signer's associated account to the recipient's, creating it if needed
+  async transfer(tokenMint: PublicKey, recipient: PublicKey, amount: string, shard?: number): Promise<string> {
+    const owner = this.signer.publicKey;
+    const tokenLaunch = this.pda('launch', tokenMint);
+    const launch = await this.launch(tokenMint);
+    const { tokenProgram, decimals } = await this.mint(tokenMint);
+    const treasury = this.pda('treasury');
+    const feeShard = shard ?? owner.toBuffer()[0] % FEE_SHARDS;
+
+    const instructions = [this.createAta(recipient, tokenMint, tokenProgram)];
+    if (launch.transferTaxBps > 0) instructions.push(this.createAta(treasury, tokenMint, tokenProgram));
+    instructions.push(this.instruction('transferTokens', {
+      amount: toBaseUnits(amount, decimals),
+      shard: feeShard
+    }, {
+      payer: owner,
+      tokenLaunch,
+      tokenMint,
+      fromTokenAccount: this.ata(owner, tokenMint, tokenProgram),
+      toTokenAccount: this.ata(recipient, tokenMint, tokenProgram),
+      fromBlacklist: this.pda('blacklist', tokenLaunch, owner),
+      toBlacklist: this.pda('blacklist', tokenLaunch, recipient),
+      authority: owner,
+      config: this.pda('config'),
+      feeShard: this.pda('fee_shard', tokenLaunch, Buffer.from([feeShard])),
+      launchStats: findLaunchStats(tokenLaunch, feeShard, this.programId)[0],
+      treasuryTokenAccount: launch.transferTaxBps > 0 ? this.ata(treasury, tokenMint, tokenProgram) : null,
+      bootstrapGuard: launch.bootstrap ? this.pda('bootstrap_guard', tokenLaunch) : null,
+      delegation: null,
+      tokenProgram
+    }, this.hookAccounts(tokenMint, tokenProgram)));
+    return this.send(instructions);
+  }
+
+  // Withdraw from the launch vault as one of its insurance wallets, into
+  // one of the launch's registered recovery accounts (the first unless
+  // `destination` names another)
+  async emergencyWithdraw(tokenMint: PublicKey, amount: string, justification: string, destination?: PublicKey): Promise<string> {
+    const authority = this.signer.publicKey;
+    const tokenLaunch = this.pda('launch', tokenMint);
+    const launch = await this.launch(tokenMint);
+    if (launch.withdrawalThreshold > 1) {
+      throw new Error(`Launch needs ${launch.withdrawalThreshold} insurance approvals; use propose_withdrawal instead`);
+    }
+    const recoveryAccounts: PublicKey[] = launch.recoveryAccounts ?? [];
+    const toTokenAccount = destination ?? recoveryAccounts[0];
+    if (!toTokenAccount) {
+      throw new Error('Launch has no registered recovery accounts; add one through propose_insurance_change');
+    }
+    if (!recoveryAccounts.some(account => account.equals(toTokenAccount))) {
+      throw new Error(`${toTokenAccount.toBase58()} is not a registered recovery account`);
+    }
+    const { tokenProgram, decimals } = await this.mint(tokenMint);
+
+    const instructions: TransactionInstruction[] = [];
+    instructions.push(this.instruction('emergencyWithdraw', { amount: toBaseUnits(amount, decimals), justification }, {
+      authority,
+      tokenLaunch,
+      walletRecord: this.pda('insurance_wallet', tokenLaunch, authority),
+      insuranceCollateral: this.pda('insurance_collateral', tokenLaunch, authority),
+      withdrawalRecord: this.pda('withdrawal_record', tokenLaunch, launch.withdrawalRecordCount.toArrayLike(Buffer, 'le', 8)),
+      tokenMint,
+      vault: this.pda('vault', tokenMint),
+      toTokenAccount,
+      config: this.pda('config'),
+      treasury: this.pda('treasury'),
+      tokenProgram
+    }, this.hookAccounts(tokenMint, tokenProgram)));
+    return this.send(instructions);
+  }
+
+  // Stake `sol` as insurance collateral for the signer, one of the
+  // launch's insurance wallets
+  async stakeCollateral(tokenMint: PublicKey, sol: string): Promise<string> {
+    const wallet = this.signer.publicKey;
+    const tokenLaunch = this.pda('launch', tokenMint);
+    return this.send([this.instruction('stakeInsuranceCollateral', { amount: toBaseUnits(sol, 9) }, {
+      wallet,
+      tokenLaunch,
+      tokenMint,
+      collateralPool: this.pda('insurance_collateral_pool', tokenLaunch),
+      insuranceCollateral: this.pda('insurance_collateral', tokenLaunch, wallet)
+    })]);
+  }
+
+  // Create the signer's template `templateId`, or update it to the next
+  // version when it exists. Returns its address.
+  async saveTemplate(templateId: number, terms: any): Promise<{ template: PublicKey; signature: string }> {
+    const owner = this.signer.publicKey;
+    const template = this.pda('launch_template', owner, new BN(templateId).toArrayLike(Buffer, 'le', 8));
+    const params = fromPlain(this.idl, { defined: 'TemplateParams' }, terms);
+    const signature = await this.send([await this.connection.getAccountInfo(template)
+      ? this.instruction('updateTemplate', { params }, { owner, template })
+      : this.instruction('createTemplate', { templateId: new BN(templateId), params }, { owner, template })]);
+    return { template, signature };
+  }
+
+  async launch(tokenMint: PublicKey): Promise<any> {
+    const launch = await this.fetch('TokenLaunch', this.pda('launch', tokenMint));
+    if (!launch) throw new Error(`No launch for mint ${tokenMint.toBase58()}`);
+    return normalizeLaunch(this.coder, launch);
+  }
+
+  // LaunchStats summed over the transfer shards and the sale shard
+  // (SALE_STATS_SHARD = FEE_SHARDS); shards nobody has traded on are skipped
+  async stats(tokenMint: PublicKey): Promise<{ volume: BN; transferCount: BN; saleCount: BN; holders: BN; lastTradeAt: number }> {
+    const tokenLaunch = this.pda('launch', tokenMint);
+    const addresses = Array.from({ length: FEE_SHARDS + 1 }, (_, shard) => findLaunchStats(tokenLaunch, shard, this.programId)[0]);
+    const shards = (await this.connection.getMultipleAccountsInfo(addresses))
+      .filter((info): info is AccountInfo<Buffer> => info !== null)
+      .map(info => this.coder.accounts.decode('LaunchStats', info.data));
+    const sum = (field: string) => shards.reduce((total, shard) => total.add(shard[field]), new BN(0));
+    const holders = sum('holdersAdded').sub(sum('holdersRemoved'));
+    return {
+      volume: sum('volume'),
+      transferCount: sum('transferCount'),
+      saleCount: sum('saleCount'),
+      holders: BN.max(holders, new BN(0)),
+      lastTradeAt: Math.max(0, ...shards.map(shard => shard.lastTradeAt.toNumber()))
+    };
+  }
+
+  // Time-weighted fraud score the program auto-suspends on, or null
+  // before the launch has enough score history
+  async smoothedFraudScore(tokenMint: PublicKey, now = Math.floor(Date.now() / 1000)): Promise<number | null> {
+    const history = await this.fetch('FraudScoreHistory', this.pda('fraud_score_history', this.pda('launch', tokenMint)));
+    return history ? smoothedFraudScore(history, now) : null;
+  }
+
+  private async fetch(account: string, address: PublicKey): Promise<any | null> {
+    const info = await this.connection.getAccountInfo(address);
+    return info ? this.coder.accounts.decode(account, info.data) : null;
+  }
+
+  private async mint(tokenMint: PublicKey): Promise<{ tokenProgram: PublicKey; decimals: number }> {
+    const info = await this.connection.getAccountInfo(tokenMint);
+    if (!info) throw new Error(`Mint ${tokenMint.toBase58()} does not exist`);
+    return { tokenProgram: info.owner, decimals: info.data[44] };
+  }
+
+  private pda(seed: string, ...keys: (PublicKey | Buffer)[]): PublicKey {
+    const seeds = [Buffer.from(seed), ...keys.map(key => key instanceof PublicKey ? key.toBuffer() : key)];
+    return PublicKey.findProgramAddressSync(seeds, this.programId)[0];
+  }
+
+  private ata(owner: PublicKey, tokenMint: PublicKey, tokenProgram: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [owner.toBuffer(), tokenProgram.toBuffer(), tokenMint.toBuffer()], ASSOCIATED_TOKEN_PROGRAM_ID)[0];
+  }
+
+  // CreateIdempotent: a no-op when the account already exists
+  private createAta(owner: PublicKey, tokenMint: PublicKey, tokenProgram: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: ASSOCIATED_TOKEN_PROGRAM_ID,
+      keys: [
+        { pubkey: this.signer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: this.ata(owner, tokenMint, tokenProgram), isSigner: false, isWritable: true },
+        { pubkey: owner, isSigner: false, isWritable: false },
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false }
+      ],
+      data: Buffer.from([1])
+    });
+  }
+
+  // Token-2022 launch mints run the SolD transfer hook program, which needs
+  // its validation account, this program and the launch PDA passed along
+  private hookAccounts(tokenMint: PublicKey, tokenProgram: PublicKey): PublicKey[] {
+    if (tokenProgram.equals(SPL_TOKEN_PROGRAM_ID)) return [];
+    return [
+      findExtraAccountMetas(tokenMint)[0],
+      this.programId,
+      this.pda('launch', tokenMint),
+      TRANSFER_HOOK_PROGRAM_ID
+    ];
+  }
+
+  // Account metas in the IDL's order. Programs and sysvars are filled in,
+  // and omitted optional accounts are passed as the program id.
+  private instruction(name: string, args: Record<string, unknown>, accounts: Record<string, PublicKey | null>,
+                      remaining: PublicKey[] = []): TransactionInstruction {
+    const ix = this.idl.instructions.find((candidate: any) => candidate.name === name);
+    if (!ix) throw new Error(`IDL has no ${name} instruction`);
+    const known: Record<string, PublicKey> = {
+      systemProgram: SystemProgram.programId,
+      tokenProgram: SPL_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY
+    };
+    const keys = ix.accounts.map((account: any) => {
+      const pubkey = accounts[account.name] ?? (account.name in accounts ? null : known[account.name]);
+      if (!pubkey && !account.isOptional) throw new Error(`${name} needs the ${account.name} account`);
+      return { pubkey: pubkey ?? this.programId, isSigner: Boolean(pubkey) && account.isSigner, isWritable: Boolean(pubkey) && account.isMut };
+    });
+    keys.push(...remaining.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })));
+    return new TransactionInstruction({ programId: this.programId, keys, data: this.coder.instruction.encode(name, args) });
+  }
+
+  private async send(instructions: TransactionInstruction[], extraSigners: Keypair[] = []): Promise<string> {
+    const budgeted = await withComputeBudget(this.connection, this.signer.publicKey, instructions, this.budget);
+    const transaction = new Transaction().add(...budgeted);
+    return sendAndConfirmTransaction(this.connection, transaction, [this.signer, ...extraSigners], { commitment: 'confirmed' });
+  }
+}
+
+interface LaunchCommandOptions {
+  keypair?: string;
+  rpc?: string;
+  idl?: string;
+  mint?: string;
+  decimals?: number;
+  shard?: number;
+  justification?: string;
+  destination?: string;
+  template?: string;
+  computeUnits?: number | 'auto';
+  priorityFee?: number | 'auto';
+  json?: boolean;
+}
+
+function readKeypair(file: string): Keypair {
+  return Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(file, 'utf8'))));
+}
+
+// "1.5" tokens at 9 decimals -> 1500000000 base units
+// FraudScoreHistory::smoothed_score: each update weighted by how long it
+// stood within the last FRAUD_SCORE_WINDOW, null under FRAUD_SCORE_MIN_SPAN
+function smoothedFraudScore(history: { samples: any[]; count: number; cursor: number }, now: number): number | null {
+  const { samples, count, cursor } = history;
+  const oldest = count < samples.length ? 0 : cursor;
+  const ordered = Array.from({ length: count }, (_, i) => samples[(oldest + i) % samples.length]);
+  let weighted = 0;
+  let span = 0;
+  ordered.forEach((sample, i) => {
+    const end = i + 1 < count ? Math.min(ordered[i + 1].timestamp.toNumber(), now) : now;
+    const start = Math.max(sample.timestamp.toNumber(), now - FRAUD_SCORE_WINDOW);
+    if (end > start) {
+      weighted += sample.score * (end - start);
+      span += end - start;
+    }
+  });
+  return span >= FRAUD_SCORE_MIN_SPAN ? Math.floor(weighted / span) : null;
+}
+
+function toBaseUnits(amount: string, decimals: number): BN {
+  const match = /^(\d+)(?:\.(\d+))?$/.exec(amount);
+  if (!match || (match[2] || '').length > decimals) {
+    throw new Error(`Invalid amount "${amount}" for a mint with ${decimals} decimals`);
+  }
+  return new BN(match[1] + (match[2] || '').padEnd(decimals, '0'));
+}
+
+// Whole tokens from base units: 1500000000 at 9 decimals -> "1.5"
+function fromBaseUnits(amount: BN, decimals: number): string {
+  const digits = amount.toString().padStart(decimals + 1, '0');
+  const fraction = digits.slice(digits.length - decimals).replace(/0+$/, '');
+  return digits.slice(0, digits.length - decimals) + (fraction ? `.${fraction}` : '');
+}
+
+// SPL token account layout: mint, owner, then the u64 amount. Token-2022
+// accounts share it and append their extensions.
+const TOKEN_ACCOUNT_OWNER_OFFSET = 32;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET = 64;
+const SPL_TOKEN_ACCOUNT_LEN = 165;
+
+interface SnapshotHolder {
+  owner: string;
+  amount: string;      // base units, the value in the holder's Merkle leaf
+  accounts: string[];
+  proof: string[];     // hex, for verify_merkle_proof
+}
+
+interface HolderSnapshotResult {
+  tokenMint: string;
+  slot: number;
+  decimals: number;
+  minAmount: string;
+  totalAmount: string;
+  excluded: { dust: number; programOwned: number };
+  merkleRoot: string;
+  holders: SnapshotHolder[];
+}
+
+class HolderSnapshot {
+  private connection: Connection;
+
+  constructor(rpcUrl: string) {
+    this.connection = new Connection(rpcUrl, 'finalized');
+  }
+
+  // Every wallet holding `tokenMint`, balances summed across its token
+  // accounts. Holdings under `minBalance` (whole tokens) are dropped as
+  // dust, and accounts owned by PDAs (the launch vault, pools, treasury)
+  // are left out. The RPC only serves current state: `slot` waits for the
+  // node to reach it, and the result records the slot actually read.
+  async take(tokenMint: PublicKey, options: { minBalance?: string; slot?: number } = {}): Promise<HolderSnapshotResult> {
+    const mintInfo = await this.connection.getAccountInfo(tokenMint);
+    if (!mintInfo) throw new Error(`Mint ${tokenMint.toBase58()} does not exist`);
+    const decimals = mintInfo.data[44];
+    const minAmount = BN.max(toBaseUnits(options.minBalance || '0', decimals), new BN(1));
+
+    const filters: any[] = [{ memcmp: { offset: 0, bytes: tokenMint.toBase58() } }];
+    if (mintInfo.owner.equals(SPL_TOKEN_PROGRAM_ID)) filters.push({ dataSize: SPL_TOKEN_ACCOUNT_LEN });
+    const { context, value } = await this.connection.getProgramAccounts(mintInfo.owner, {
+      filters,
+      withContext: true,
+      minContextSlot: options.slot
+    });
+
+    const balances = new Map<string, { amount: BN; accounts: string[] }>();
+    const excluded = { dust: 0, programOwned: 0 };
+    for (const { pubkey, account } of value) {
+      const amount = new BN(account.data.subarray(TOKEN_ACCOUNT_AMOUNT_OFFSET, TOKEN_ACCOUNT_AMOUNT_OFFSET + 8), 'le');
+      if (amount.isZero()) continue;
+      const owner = new PublicKey(account.data.subarray(TOKEN_ACCOUNT_OWNER_OFFSET, TOKEN_ACCOUNT_OWNER_OFFSET + 32));
+      if (!PublicKey.isOnCurve(owner.toBuffer())) {
+        excluded.programOwned++;
+        continue;
+      }
+      const entry = balances.get(owner.toBase58()) ?? { amount: new BN(0), accounts: [] };
+      entry.amount = entry.amount.add(amount);
+      entry.accounts.push(pubkey.toBase58());
+      balances.set(owner.toBase58(), entry);
+    }
+
+    const holders = [...balances.entries()].filter(([, { amount }]) => amount.gte(minAmount));
+    excluded.dust = balances.size - holders.length;
+    holders.sort(([a], [b]) => a.localeCompare(b));
+    const tree = keccakMerkleTree(holders.map(([owner, { amount }]) => holderLeaf(new PublicKey(owner), amount)));
+
+    return {
+      tokenMint: tokenMint.toBase58(),
+      slot: context.slot,
+      decimals,
+      minAmount: minAmount.toString(),
+      totalAmount: holders.reduce((total, [, { amount }]) => total.add(amount), new BN(0)).toString(),
+      excluded,
+      merkleRoot: tree.root.toString('hex'),
+      holders: holders.map(([owner, { amount, accounts }], i) => ({
+        owner,
+        amount: amount.toString(),
+        accounts: accounts.sort(),
+        proof: tree.proofs[i].map(node => node.toString('hex'))
+      }))
+    };
+  }
+}
+
+function keccak(...parts: Buffer[]): Buffer {
+  return Buffer.from(keccak_256(Buffer.concat(parts)));
+}
+
+// Same leaf as presale_leaf in the program: keccak(owner || amount LE)
+function holderLeaf(owner: PublicKey, amount: BN): Buffer {
+  return keccak(owner.toBuffer(), amount.toArrayLike(Buffer, 'le', 8));
+}
+
+// Sorted-pair keccak tree, as verify_merkle_proof checks it. An odd node
+// is carried up unchanged, so its proof skips that level.
+function keccakMerkleTree(leaves: Buffer[]): { root: Buffer; proofs: Buffer[][] } {
+  const proofs: Buffer[][] = leaves.map(() => []);
+  if (leaves.length === 0) return { root: Buffer.alloc(32), proofs };
+  let level = leaves.map((hash, i) => ({ hash, members: [i] }));
+  while (level.length > 1) {
+    const next: { hash: Buffer; members: number[] }[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      const [left, right] = [level[i], level[i + 1]];
+      if (!right) {
+        next.push(left);
+        continue;
+      }
+      left.members.forEach(member => proofs[member].push(right.hash));
+      right.members.forEach(member => proofs[member].push(left.hash));
+      const hash = Buffer.compare(left.hash, right.hash) <= 0 ? keccak(left.hash, right.hash) : keccak(right.hash, left.hash);
+      next.push({ hash, members: [...left.members, ...right.members] });
+    }
+    level = next;
+  }
+  return { root: level[0].hash, proofs };
+}
+
+// Inverse of toPlain for one IDL type: base58 strings to keys, decimal
+// strings or numbers to BN, hex to bytes, and unit enum variants by name
+function fromPlain(idl: any, type: any, value: any): any {
+  if (type.option || type.coption) {
+    return value === null || value === undefined ? null : fromPlain(idl, type.option || type.coption, value);
+  }
+  if (value === undefined) {
+    throw new Error(`Missing ${JSON.stringify(type)} value`);
+  }
+  switch (type) {
+    case 'publicKey': return new PublicKey(value);
+    case 'u64': case 'i64': case 'u128': case 'i128': return new BN(String(value));
+    case 'bytes': return Buffer.from(value, 'hex');
+  }
+  if (typeof type === 'string') return value;
+  if (type.vec) return value.map((item: any) => fromPlain(idl, type.vec, item));
+  if (type.array) {
+    const [inner] = type.array;
+    return inner === 'u8' && typeof value === 'string'
+      ? Array.from(Buffer.from(value, 'hex'))
+      : value.map((item: any) => fromPlain(idl, inner, item));
+  }
+
+  const def = (idl.types || []).find((candidate: any) => candidate.name === type.defined);
+  if (!def) throw new Error(`Unknown IDL type ${type.defined}`);
+  const fields = (fieldDefs: any[], plain: any) => Object.fromEntries(fieldDefs.map((field: any) => {
+    try {
+      return [field.name, fromPlain(idl, field.type, plain[field.name])];
+    } catch (error) {
+      throw new Error(`${def.name}.${field.name}: ${error.message}`);
+    }
+  }));
+  if (def.type.kind === 'struct') return fields(def.type.fields, value);
+
+  const [name, inner] = typeof value === 'string' ? [value, {}] : Object.entries(value)[0] as [string, any];
+  const variant = def.type.variants.find((candidate: any) => candidate.name.toLowerCase() === name.toLowerCase());
+  if (!variant) throw new Error(`${def.name} has no variant ${name}`);
+  // The coder keys enum variants by their camelCase name
+  if (!variant.fields) return { [toCamelCase(variant.name)]: {} };
+  return {
+    [toCamelCase(variant.name)]: typeof variant.fields[0] === 'object' && 'name' in variant.fields[0]
+      ? fields(variant.fields, inner)
+      : variant.fields.map((field: any, i: number) => fromPlain(idl, field, inner[i]))
+  };
+}
+
+// Localizable message catalog, generated from the program IDL. Errors keep
+// their `#[msg]` text as the default English template; events get a
+// template naming each field as a `{placeholder}`. Translations are
+// layered on per locale and may only use the placeholders the entry lists.
+const CATALOG_FORMAT_VERSION = 1;
+
+interface CatalogEntry {
+  name: string;
+  template: string;
+  params: { name: string; type: string }[];
+}
+
+interface MessageCatalog {
+  version: number;
+  program: string;
+  locale: string;
+  errors: Record<string, CatalogEntry>;
+  events: Record<string, CatalogEntry>;
+}
+
+// Translated templates keyed by error or event name
+type Translations = Record<string, string>;
+
+class MessageCatalogGenerator {
+  generate(idl: any, locale = 'en', translations: Translations = {}): MessageCatalog {
+    const errors: Record<string, CatalogEntry> = {};
+    for (const error of idl.errors || []) {
+      errors[error.code] = this.localize({ name: error.name, template: error.msg || humanize(error.name), params: [] }, translations);
+    }
+
+    const events: Record<string, CatalogEntry> = {};
+    for (const event of idl.events || []) {
+      const params = event.fields.map((field: any) => ({ name: field.name, type: typeName(field.type) }));
+      const template = `${humanize(event.name)}: ` +
+        params.map((p: { name: string }) => `${humanize(p.name).toLowerCase()} {${p.name}}`).join(', ');
+      events[event.name] = this.localize({ name: event.name, template, params }, translations);
+    }
+
+    return { version: CATALOG_FORMAT_VERSION, program: idl.name, locale, errors, events };
+  }
+
+  private localize(entry: CatalogEntry, translations: Translations): CatalogEntry {
+    const translated = translations[entry.name];
+    if (translated === undefined) return entry;
+
+    const known = new Set(entry.params.map(p => p.name));
+    for (const [, placeholder] of translated.matchAll(/\{(\w+)\}/g)) {
+      if (!known.has(placeholder)) {
+        throw new Error(`Translation for ${entry.name} uses unknown placeholder {${placeholder}}`);
+      }
+    }
+    return { ...entry, template: translated };
+  }
+}
+
+// Fill a catalog template, leaving unknown placeholders visible
+function formatMessage(template: string, params: Record<string, unknown>): string {
+  return template.replace(/\{(\w+)\}/g, (match, name) =>
+    params[name] === undefined ? match : String(params[name]));
+}
+
+// "LaunchCreated" / "tokenMint" / "token_mint" -> "Launch created" / "Token mint"
+function humanize(name: string): string {
+  const words = name.replace(/_/g, ' ').replace(/([a-z0-9])([A-Z])/g, '$1 $2').toLowerCase().trim();
+  return words.charAt(0).toUpperCase() + words.slice(1);
+}
+
+function typeName(type: any): string {
+  if (typeof type === 'string') return type;
+  if (type.option) return `option<${typeName(type.option)}>`;
+  if (type.vec) return `vec<${typeName(type.vec)}>`;
+  if (type.array) return `[${typeName(type.array[0])}; ${type.array[1]}]`;
+  if (type.defined) return typeof type.defined === 'string' ? type.defined : type.defined.name;
+  return 'unknown';
+}
+
+export class SolDCLI {
+  private parser = new SolDParser();
+  private generators: CodeGenerator[] = [];
+
+  // Add an output target; compile runs it after the built-in ones
+  use(generator: CodeGenerator): this {
+    this.generators.push(generator);
+    return this;
+  }
+
+  // Compile a spec into a complete Anchor workspace: the program crate
+  // under programs/<name> with its integration tests, workspace Cargo.toml,
+  // Anchor.toml and a tests/ directory wired to `anchor test`
+  async compile(inputFile: string, outputDir: string, options: WorkspaceOptions = {}): Promise<void> {
+    try {
+      const soldCode = fs.readFileSync(inputFile, 'utf8');
+      const errors = new SolDLinter().check(soldCode).filter(d => d.severity === 'error');
+      if (errors.length) {
+        throw new Error(`${errors.length} error(s) in spec\n` + errors.map(d => formatDiagnostic(inputFile, d)).join('\n'));
+      }
+      const config = this.parser.parse(soldCode);
+      const programName = config.token.symbol.toLowerCase() + '_launch';
+      const programId = options.programId || DEFAULT_PROGRAM_ID;
+      const cluster = options.cluster || this.clusterFor(config.network);
+
+      new PublicKey(programId); // throws on a malformed program id
+      if (!ANCHOR_CLUSTERS.includes(cluster)) {
+        throw new Error(`Invalid cluster: ${cluster}. Must be one of ${ANCHOR_CLUSTERS.join(', ')}`);
+      }
+
+      const migrateFrom: Idl | undefined = options.migrateFrom ? JSON.parse(fs.readFileSync(options.migrateFrom, 'utf8')) : undefined;
+      const idl = this.parser.generateIdl(config, programId, migrateFrom);
+      const anchorCode = this.parser.compile(config, programId, migrateFrom);
+      
+      // Create output directory structure
+      const programDir = path.join(outputDir, 'programs', programName);
+      fs.mkdirSync(path.join(programDir, 'src'), { recursive: true });
+      fs.mkdirSync(path.join(programDir, 'tests'), { recursive: true });
+      fs.mkdirSync(path.join(outputDir, 'tests'), { recursive: true });
+      
+      // Write the program crate
+      fs.writeFileSync(path.join(programDir, 'src', 'lib.rs'), anchorCode);
+      fs.writeFileSync(path.join(programDir, 'Cargo.toml'), this.generateCargoToml(config));
+      fs.writeFileSync(path.join(programDir, 'tests', 'integration.rs'), this.generateIntegrationTests(config, programName, migrateFrom));
+      
+      // Write the workspace files
+      fs.writeFileSync(path.join(outputDir, 'Cargo.toml'), this.generateWorkspaceToml());
+      fs.writeFileSync(path.join(outputDir, 'Anchor.toml'), this.generateAnchorToml(config, programId, cluster));
+      fs.writeFileSync(path.join(outputDir, 'package.json'), this.generatePackageJson(programName));
+      fs.writeFileSync(path.join(outputDir, 'tsconfig.json'), this.generateTsConfig());
+      fs.writeFileSync(path.join(outputDir, 'tests', `${programName}.ts`), this.generateTestSuite(config, programName, programId));
+      this.writeRustClient(path.join(outputDir, 'clients', `${programName}_client`), config, idl);
+      fs.mkdirSync(path.join(outputDir, 'client'), { recursive: true });
+      fs.writeFileSync(path.join(outputDir, 'client', `${programName}.ts`), new TsClientGenerator().generate(idl));
+      fs.mkdirSync(path.join(outputDir, 'scripts'), { recursive: true });
+      fs.writeFileSync(path.join(outputDir, 'scripts', 'deploy.sh'), this.generateDeployScript(config, programName, programId, cluster), { mode: 0o755 });
+      fs.writeFileSync(path.join(outputDir, 'scripts', 'upgrade.sh'), this.generateUpgradeScript(config, programName, programId, cluster), { mode: 0o755 });
+
+      const context = { programName, programId, idl };
+      for (const generator of this.generators) {
+        const files = generator.generate(config, context);
+        writeGeneratedFiles(outputDir, files);
+        console.log(`🧩 ${generator.name}: ${files.length} file(s)`);
+      }
+      
+      console.log(`✅ Successfully compiled SolD to Anchor workspace`);
+      console.log(`📁 Output directory: ${outputDir}`);
+      console.log(`🆔 Program id: ${programId} (${cluster})`);
+      const migrations = idl.instructions.filter(ix => ix.name.startsWith('migrate')).map(ix => toSnakeCase(ix.name));
+      if (migrations.length) {
+        console.log(`🔁 Migrations: ${migrations.join(', ')}; run them on every existing account after upgrading`);
+      }
+      console.log(`🚀 Run 'anchor build' in ${outputDir} to compile the program, 'cargo test' to run its integration tests`);
+      
+    } catch (error) {
+      console.error('❌ Compilation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Write the program IDL derived from the spec, without `anchor build`
+  idl(inputFile: string, options: WorkspaceOptions, outFile?: string): void {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const idl = this.parser.generateIdl(config, options.programId);
+      const json = JSON.stringify(idl, null, 2);
+
+      if (outFile) {
+        fs.writeFileSync(outFile, json);
+        console.error(`📜 ${idl.instructions.length} instructions and ${idl.accounts.length} accounts written to ${outFile}`);
+      } else {
+        console.log(json);
+      }
+
+    } catch (error) {
+      console.error('❌ IDL generation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Generate the typed TypeScript client for the spec's program
+  genTs(inputFile: string, options: WorkspaceOptions, outFile?: string): void {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const client = new TsClientGenerator().generate(this.parser.generateIdl(config, options.programId));
+      const file = outFile || `${config.token.symbol.toLowerCase()}_launch.ts`;
+      fs.writeFileSync(file, client);
+      console.log(`🧩 TypeScript client written to ${file}`);
+
+    } catch (error) {
+      console.error('❌ Client generation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Generate the standalone Rust client crate for the spec's program
+  genRust(inputFile: string, options: WorkspaceOptions, outDir?: string): void {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const dir = outDir || `${config.token.symbol.toLowerCase()}_launch_client`;
+      this.writeRustClient(dir, config, this.parser.generateIdl(config, options.programId));
+      console.log(`🦀 Rust client crate written to ${dir}`);
+
+    } catch (error) {
+      console.error('❌ Client generation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  private writeRustClient(dir: string, config: SolDConfig, idl: Idl): void {
+    const files = new RustClientGenerator().generate(idl, this.parser.feeSchedule(config));
+    for (const [file, contents] of Object.entries(files)) {
+      fs.mkdirSync(path.dirname(path.join(dir, file)), { recursive: true });
+      fs.writeFileSync(path.join(dir, file), contents);
+    }
+  }
+
+  // Lint a spec without generating anything; fails on any error
+  check(inputFile: string, options: { json?: boolean } = {}): void {
+    const diagnostics = new SolDLinter().check(fs.readFileSync(inputFile, 'utf8'));
+    if (options.json) {
+      console.log(JSON.stringify(diagnostics, null, 2));
+    } else {
+      diagnostics.forEach(d => console.log(formatDiagnostic(inputFile, d)));
+      const errors = diagnostics.filter(d => d.severity === 'error').length;
+      console.log(`${errors ? '❌' : '✅'} ${errors} error(s), ${diagnostics.length - errors} warning(s)`);
+    }
+    if (diagnostics.some(d => d.severity === 'error')) {
+      process.exit(1);
+    }
+  }
+
+  grindMint(options: GrindOptions, outFile: string): void {
+    try {
+      const started = Date.now();
+      const result = new MintGrinder().grind(options);
+
+      // Same format as solana-keygen so it can be passed straight to launch tooling.
+      // The file is owner-only and never replaces an existing keypair; the seed
+      // and nonce reproduce the secret key, so they are not logged.
+      fs.writeFileSync(outFile, JSON.stringify(Array.from(result.keypair.secretKey)), { mode: 0o600, flag: 'wx' });
+
+      console.log(`✅ Found mint ${result.keypair.publicKey.toBase58()}`);
+      console.log(`🔁 Attempts: ${result.attempts} (${Date.now() - started}ms)`);
+      console.log(`🔑 Keypair written to ${outFile}`);
+      console.log(`🚀 Pass it to the launch with MINT_KEYPAIR_PATH=${outFile}`);
+
+    } catch (error) {
+      console.error('❌ Mint grinding failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Compile, then report per-instruction transaction size estimates and
+  // measured CU from the bench harness output, failing on budget overruns
+  async build(inputFile: string, outputDir: string, options: WorkspaceOptions & { idl?: string; bench?: string }): Promise<void> {
+    await this.compile(inputFile, outputDir, options);
+
+    const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+    const programName = config.token.symbol.toLowerCase() + '_launch';
+    const idlPath = options.idl || path.join(outputDir, 'target', 'idl', `${programName}.json`);
+    const benchPath = options.bench || path.join(outputDir, 'target', 'bench', 'cu.json');
+    const budget = config.budget || { cu: DEFAULT_CU_BUDGET, txSize: MAX_TX_SIZE };
+
+    // Before the program compiles, size the transactions from the spec's IDL
+    let idl: any;
+    if (fs.existsSync(idlPath)) {
+      idl = JSON.parse(fs.readFileSync(idlPath, 'utf8'));
+    } else {
+      console.log(`⚠️  No IDL at ${idlPath}; using the IDL generated from the spec`);
+      const migrateFrom = options.migrateFrom ? JSON.parse(fs.readFileSync(options.migrateFrom, 'utf8')) : undefined;
+      idl = this.parser.generateIdl(config, options.programId, migrateFrom);
+    }
+    const measuredCu = fs.existsSync(benchPath) ? JSON.parse(fs.readFileSync(benchPath, 'utf8')) : {};
+
+    const report = new BudgetReporter().report(idl, budget, measuredCu);
+    fs.writeFileSync(path.join(outputDir, 'budget-report.json'), JSON.stringify({ budget, report }, null, 2));
+    fs.writeFileSync(path.join(outputDir, 'messages.en.json'), JSON.stringify(new MessageCatalogGenerator().generate(idl), null, 2));
+
+    console.log(`📊 Budget report (limits: ${budget.cu} CU, ${budget.txSize} bytes)`);
+    for (const entry of report) {
+      const cu = entry.measuredCu !== undefined ? `${entry.measuredCu} CU` : 'CU not measured';
+      console.log(`${entry.overBudget ? '❌' : '✅'} ${entry.instruction}: ~${entry.estimatedTxSize} bytes, ${cu}`);
+    }
+
+    const over = report.filter(entry => entry.overBudget);
+    if (over.length) {
+      console.error(`❌ ${over.length} instruction(s) exceed the configured budget`);
+      process.exit(1);
+    }
+  }
+
+  catalog(options: { idl?: string; locale?: string; translations?: string }, outFile?: string): void {
+    try {
+      const idl = JSON.parse(fs.readFileSync(options.idl || 'target/idl/sold_token_launch.json', 'utf8'));
+      const translations = options.translations ? JSON.parse(fs.readFileSync(options.translations, 'utf8')) : {};
+      const catalog = new MessageCatalogGenerator().generate(idl, options.locale, translations);
+      const json = JSON.stringify(catalog, null, 2);
+
+      if (outFile) {
+        fs.writeFileSync(outFile, json);
+        console.error(`🌐 ${Object.keys(catalog.errors).length} errors and ${Object.keys(catalog.events).length} events written to ${outFile}`);
+      } else {
+        console.log(json);
+      }
+
+    } catch (error) {
+      console.error('❌ Catalog generation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  tokenomics(inputFile: string, options: TokenomicsOptions, outFile?: string): void {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const json = JSON.stringify(new TokenomicsExporter(this.parser).export(config, options), null, 2);
+
+      if (outFile) {
+        fs.writeFileSync(outFile, json);
+        console.error(`📈 Tokenomics series written to ${outFile}`);
+      } else {
+        console.log(json);
+      }
+
+    } catch (error) {
+      console.error('❌ Tokenomics export failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  async archive(tokenMint: string, options: { idl?: string; keypair?: string; rpc?: string }, outFile?: string): Promise<void> {
+    try {
+      const idl = JSON.parse(fs.readFileSync(options.idl || 'target/idl/sold_token_launch.json', 'utf8'));
+      const keypairPath = options.keypair || path.join(process.env.HOME || '', '.config', 'solana', 'id.json');
+      const signer = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(keypairPath, 'utf8'))));
+      const rpcUrl = options.rpc || 'https://api.devnet.solana.com';
+
+      const archive = await new LaunchArchiver(rpcUrl, idl).export(new PublicKey(tokenMint), signer);
+      const file = outFile || `${tokenMint}.archive.json`;
+      fs.writeFileSync(file, JSON.stringify(archive, null, 2));
+
+      console.log(`🗄️  Archived ${archive.accounts.length} accounts and ${archive.events.length} events to ${file}`);
+      console.log(`🔐 Archive hash: ${archive.archiveHash}`);
+      console.log(`   Commit it with close_launch(archive_hash) before reclaiming rent`);
+
+    } catch (error) {
+      console.error('❌ Archive export failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  async snapshot(tokenMint: string, options: { minBalance?: string; slot?: number; format?: string; rpc?: string }, outFile?: string): Promise<void> {
+    try {
+      const format = options.format || 'json';
+      if (format !== 'json' && format !== 'csv') throw new Error(`Unknown format "${format}" (json, csv)`);
+      const rpcUrl = options.rpc || 'https://api.devnet.solana.com';
+
+      const snapshot = await new HolderSnapshot(rpcUrl).take(new PublicKey(tokenMint), options);
+      const file = outFile || `${tokenMint}.snapshot.${format}`;
+      if (format === 'csv') {
+        const rows = snapshot.holders.map(holder =>
+          `${holder.owner},${holder.amount},${fromBaseUnits(new BN(holder.amount), snapshot.decimals)}`);
+        fs.writeFileSync(file, ['owner,amount,balance', ...rows].join('\n') + '\n');
+      } else {
+        fs.writeFileSync(file, JSON.stringify(snapshot, null, 2));
+      }
+
+      console.log(`📸 ${snapshot.holders.length} holders of ${tokenMint} at slot ${snapshot.slot} written to ${file}`);
+      console.log(`   Skipped ${snapshot.excluded.dust} dust and ${snapshot.excluded.programOwned} program-owned accounts`);
+      console.log(`🌳 Merkle root: ${snapshot.merkleRoot}`);
+
+    } catch (error) {
+      console.error('❌ Snapshot failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Operate on a deployed launch: init, transfer, emergency-withdraw,
+  // stake-collateral or status, signing with a local keypair
+  async launch(command: string, args: string[], options: LaunchCommandOptions): Promise<void> {
+    try {
+      const idl = JSON.parse(fs.readFileSync(options.idl || 'target/idl/sold_token_launch.json', 'utf8'));
+      const signer = readKeypair(options.keypair || path.join(process.env.HOME || '', '.config', 'solana', 'id.json'));
+      const client = new LaunchClient(options.rpc || 'https://api.devnet.solana.com', idl, signer, {
+        units: options.computeUnits,
+        microLamports: options.priorityFee
+      });
+
+      switch (command) {
+        case 'init': {
+          if (!args[0] || !options.mint) throw new Error('Usage: launch init <params.json> --mint <mint-keypair.json>');
+          const params = JSON.parse(fs.readFileSync(args[0], 'utf8'));
+          const mint = readKeypair(options.mint);
+          const template = options.template ? new PublicKey(options.template) : undefined;
+          const signatures = await client.init(params, mint, options.decimals ?? 9, template);
+          console.log(`🚀 Launched ${params.tokenName} (${params.tokenSymbol}) at mint ${mint.publicKey.toBase58()}`);
+          signatures.forEach(signature => console.log(`   ${signature}`));
+          break;
+        }
+        case 'transfer': {
+          const [mint, recipient, amount] = args;
+          if (!amount) throw new Error('Usage: launch transfer <mint> <recipient> <amount>');
+          const signature = await client.transfer(new PublicKey(mint), new PublicKey(recipient), amount, options.shard);
+          console.log(`💸 Sent ${amount} to ${recipient} (${signature})`);
+          break;
+        }
+        case 'emergency-withdraw': {
+          const [mint, amount] = args;
+          if (!amount || !options.justification) {
+            throw new Error('Usage: launch emergency-withdraw <mint> <amount> --justification "..."');
+          }
+          const destination = options.destination ? new PublicKey(options.destination) : undefined;
+          const signature = await client.emergencyWithdraw(new PublicKey(mint), amount, options.justification, destination);
+          console.log(`🛟 Withdrew ${amount} from the ${mint} vault (${signature})`);
+          break;
+        }
+        case 'template': {
+          const [id, file] = args;
+          if (!file) throw new Error('Usage: launch template <id> <terms.json>');
+          const terms = JSON.parse(fs.readFileSync(file, 'utf8'));
+          const { template, signature } = await client.saveTemplate(parseInt(id), terms);
+          console.log(`📋 Saved launch template ${id} at ${template.toBase58()} (${signature})`);
+          break;
+        }
+        case 'stake-collateral': {
+          const [mint, sol] = args;
+          if (!sol) throw new Error('Usage: launch stake-collateral <mint> <sol>');
+          const signature = await client.stakeCollateral(new PublicKey(mint), sol);
+          console.log(`🔐 Staked ${sol} SOL of insurance collateral on ${mint} (${signature})`);
+          break;
+        }
+        case 'status': {
+          if (!args[0]) throw new Error('Usage: launch status <mint>');
+          const launch = await client.launch(new PublicKey(args[0]));
+          if (options.json) {
+            console.log(JSON.stringify(toPlain(launch), null, 2));
+            break;
+          }
+          const now = Math.floor(Date.now() / 1000);
+          const timelockEnd = launch.timelockEnd.toNumber();
+          const pausedUntil = launch.pausedUntil.toNumber();
+          console.log(`🚀 ${launch.tokenName} (${launch.tokenSymbol}) — ${args[0]}`);
+          console.log(`   Creator: ${launch.creator.toBase58()}`);
+          console.log(`   Status: ${launch.isActive ? 'active' : 'suspended'}` +
+            (pausedUntil > now ? `, trading paused until ${new Date(pausedUntil * 1000).toISOString()}` : ''));
+          console.log(`   Timelock: ${timelockEnd > now
+            ? `ends ${new Date(timelockEnd * 1000).toISOString()} (${Math.ceil((timelockEnd - now) / 86400)} days left)`
+            : 'expired'}`);
+          const smoothed = await client.smoothedFraudScore(new PublicKey(args[0]), now);
+          console.log(`   Fraud score: ${(launch.fraudScore / 100).toFixed(2)}% ` +
+            `(${launch.scoreModelVersion ? `model v${launch.scoreModelVersion}` : 'creator-reported'})` +
+            (smoothed !== null ? `, ${(smoothed / 100).toFixed(2)}% time-weighted` : ''));
+          console.log(`   Insurance: ${launch.insuranceWallets.length} wallets, limit ${launch.insuranceLimit}% of supply, ` +
+            `${launch.totalWithdrawn.toString()} withdrawn, ${(launch.recoveryAccounts ?? []).length} recovery accounts`);
+          console.log(`   Fees collected: ${(launch.feesCollected.toNumber() / 1e9).toString()} SOL`);
+          if (launch.transferTaxBps) console.log(`   Transfer tax: ${launch.transferTaxBps / 100}%`);
+          const stats = await client.stats(new PublicKey(args[0]));
+          console.log(`   Activity: ${stats.transferCount.toString()} transfers, ${stats.saleCount.toString()} sales, ` +
+            `${stats.volume.toString()} traded, ~${stats.holders.toString()} holders` +
+            (stats.lastTradeAt ? `, last trade ${new Date(stats.lastTradeAt * 1000).toISOString()}` : ''));
+          break;
+        }
+        default:
+          throw new Error(`Unknown launch command "${command}" (init, transfer, emergency-withdraw, stake-collateral, template, status)`);
+      }
+
+    } catch (error) {
+      console.error(`❌ launch ${command} failed:`, error.message);
+      process.exit(1);
+    }
+  }
+
+  // Quote what launching with the parameters in `paramsFile` costs the
+  // creator, and what each transfer of the launch's tokens will pay
+  async fees(paramsFile: string, options: { creator?: string; mint?: string; idl?: string; rpc?: string; json?: boolean }): Promise<void> {
+    try {
+      const idl = JSON.parse(fs.readFileSync(options.idl || 'target/idl/sold_token_launch.json', 'utf8'));
+      const params = JSON.parse(fs.readFileSync(paramsFile, 'utf8'));
+      const creator = options.creator
+        ? new PublicKey(options.creator)
+        : Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(
+            path.join(process.env.HOME || '', '.config', 'solana', 'id.json'), 'utf8')))).publicKey;
+      const rpcUrl = options.rpc || 'https://api.devnet.solana.com';
+
+      const estimate = await new LaunchFeeEstimator(rpcUrl, idl)
+        .estimate(params, creator, options.mint ? new PublicKey(options.mint) : undefined);
+      if (options.json) {
+        console.log(JSON.stringify(toPlain(estimate), null, 2));
+        return;
+      }
+
+      const sol = (lamports: BN) => `${(lamports.toNumber() / 1e9).toFixed(9).replace(/\.?0+$/, '')} SOL`;
+      console.log(`💰 Launch fee: ${sol(estimate.launchFee)} at ${estimate.feeTierBps / 100}% of the base schedule`);
+      console.log(`   base ${sol(estimate.baseFee)}, insurance ${sol(estimate.insuranceFee)}, logo ${sol(estimate.logoFee)}`);
+      if (!estimate.referralFee.isZero()) console.log(`   of which the referrer receives ${sol(estimate.referralFee)}`);
+      if (!estimate.bond.isZero()) console.log(`🔒 Class bond: ${sol(estimate.bond)}, returned when the launch closes`);
+      console.log(`🏦 Rent: launch ${sol(estimate.launchRent)}, vault ${sol(estimate.vaultRent)}, ` +
+        `creator accounts ${sol(estimate.creatorAccountsRent)}, registry ${sol(estimate.registryRent)}, ` +
+        `bootstrap guard ${sol(estimate.bootstrapGuardRent)}`);
+      console.log(`🧾 Total to launch: ${sol(estimate.total)} plus network fees`);
+      console.log(`🔁 Per transfer: ${sol(estimate.tradingFee)} trading fee` +
+        (estimate.bootstrapTradingFee ? ` (${sol(estimate.bootstrapTradingFee)} in the bootstrap window)` : '') +
+        (estimate.transferTaxBps ? `, ${estimate.transferTaxBps / 100}% token tax` : ''));
+      console.log(`   ${sol(estimate.tokenAccountRent)} rent for a new holder's token account, ` +
+        `${sol(estimate.feeShardRent.add(estimate.launchStatsRent))} once per fee shard and its stats`);
+
+    } catch (error) {
+      console.error('❌ Fee estimate failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Compare the program deployed under a program id with what the spec now
+  // generates. Fails on changes that break existing clients or accounts;
+  // layout changes are fine once migrated (see build --migrate-from).
+  async diff(inputFile: string, options: WorkspaceOptions & { idl?: string; rpc?: string; json?: boolean }, outFile?: string): Promise<void> {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const programId = new PublicKey(options.programId || DEFAULT_PROGRAM_ID);
+      const connection = new Connection(options.rpc || 'https://api.devnet.solana.com', 'confirmed');
+      const deployed: Idl | null = options.idl
+        ? JSON.parse(fs.readFileSync(options.idl, 'utf8'))
+        : await fetchDeployedIdl(connection, programId);
+      if (!deployed) {
+        throw new Error(`No IDL account for ${programId.toBase58()}; publish one with anchor idl init or pass --idl`);
+      }
+      if (outFile) {
+        fs.writeFileSync(outFile, JSON.stringify(deployed, null, 2));
+      }
+
+      const differ = new IdlDiffer();
+      const current = this.parser.generateIdl(config, programId.toBase58());
+      const changes = differ.diff(deployed, current);
+
+      // Count the accounts each migration has to rewrite, unless working offline from --idl
+      const accounts: Record<string, number> = {};
+      if (!options.idl || options.rpc) {
+        const coder = new BorshAccountsCoder(deployed as any);
+        for (const { account } of differ.migrations(deployed, current)) {
+          const found = await connection.getProgramAccounts(programId, {
+            dataSlice: { offset: 0, length: 0 },
+            filters: [{ memcmp: coder.memcmp(account) }]
+          });
+          accounts[account] = found.length;
+        }
+      }
+
+      if (options.json) {
+        console.log(JSON.stringify({ programId: programId.toBase58(), changes, accounts }, null, 2));
+      } else {
+        const icons = { breaking: '❌', migration: '🔁', compatible: '✅' };
+        for (const change of changes) {
+          const account = change.item.replace(/^account /, '');
+          const onChain = change.kind === 'migration' && account in accounts ? ` (${accounts[account]} on chain)` : '';
+          console.log(`${icons[change.kind]} ${change.item}: ${change.message}${onChain}`);
+        }
+        if (!changes.length) {
+          console.log(`✅ ${programId.toBase58()} matches the spec`);
+        } else if (changes.some(change => change.kind === 'migration')) {
+          console.log(`   Save the deployed IDL with --out and build with --migrate-from to generate the migrations`);
+        }
+      }
+
+      if (changes.some(change => change.kind === 'breaking')) {
+        process.exit(1);
+      }
+
+    } catch (error) {
+      console.error('❌ Diff failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Deploy the spec's program to a cluster, or upgrade it when the program
+  // id is already live. The program keypair is kept in the workspace's
+  // target/deploy; upgrades are checked against the on-chain IDL first and
+  // pick up migrations for changed account layouts.
+  async deploy(inputFile: string, outputDir: string, options: WorkspaceOptions & DeployOptions): Promise<void> {
+    try {
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const programName = config.token.symbol.toLowerCase() + '_launch';
+      const cluster = options.cluster || this.clusterFor(config.network);
+      if (!ANCHOR_CLUSTERS.includes(cluster)) {
+        throw new Error(`Invalid cluster: ${cluster}. Must be one of ${ANCHOR_CLUSTERS.join(', ')}`);
+      }
+      if (cluster === 'mainnet' && config.network !== 'MAINNET') {
+        throw new Error(`Spec targets ${config.network}; only MAINNET specs are held to mainnet's safety constraints`);
+      }
+      if (cluster === 'mainnet' && !options.yes) {
+        throw new Error('Deploying to mainnet spends real SOL; pass --yes to confirm');
+      }
+
+      const keypairFile = path.join(outputDir, 'target', 'deploy', `${programName}-keypair.json`);
+      const programId = options.programId
+        ? new PublicKey(options.programId)
+        : this.programKeypair(keypairFile, options.programKeypair).publicKey;
+
+      const connection = new Connection(options.rpc || CLUSTER_URLS[cluster], 'confirmed');
+      const live = (await connection.getAccountInfo(programId)) !== null;
+      let migrateFrom: string | undefined;
+      if (live) {
+        const deployed = await fetchDeployedIdl(connection, programId);
+        if (!deployed) {
+          console.log(`⚠️  No IDL on chain for ${programId.toBase58()}; upgrading without a compatibility check`);
+        } else {
+          const changes = new IdlDiffer().diff(deployed, this.parser.generateIdl(config, programId.toBase58()));
+          const breaking = changes.filter(change => change.kind === 'breaking');
+          breaking.forEach(change => console.log(`❌ ${change.item}: ${change.message}`));
+          if (breaking.length && !options.force) {
+            throw new Error(`${breaking.length} breaking change(s) against the deployed program; pass --force to upgrade anyway`);
+          }
+          if (changes.some(change => change.kind === 'migration')) {
+            fs.mkdirSync(outputDir, { recursive: true });
+            migrateFrom = path.join(outputDir, 'deployed-idl.json');
+            fs.writeFileSync(migrateFrom, JSON.stringify(deployed, null, 2));
+          }
+        }
+      } else if (options.programId && !fs.existsSync(keypairFile)) {
+        throw new Error(`${programId.toBase58()} is not deployed on ${cluster}; a first deploy needs its keypair at ${keypairFile}`);
+      }
+
+      await this.compile(inputFile, outputDir, { programId: programId.toBase58(), cluster, migrateFrom });
+
+      const script = path.join(outputDir, 'scripts', live ? 'upgrade.sh' : 'deploy.sh');
+      console.log(`${live ? '🔁 Upgrading' : '🚀 Deploying'} ${programId.toBase58()} on ${cluster}`);
+      const args = options.rpc ? [script, cluster, options.rpc] : [script, cluster];
+      const result = spawnSync('bash', args, { stdio: 'inherit' });
+      if (result.status !== 0) {
+        throw new Error(`${path.relative(outputDir, script)} exited with ${result.status ?? result.signal}`);
+      }
+      console.log(`✅ ${programName} ${live ? 'upgraded' : 'deployed'} at ${programId.toBase58()}`);
+
+    } catch (error) {
+      console.error('❌ Deploy failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // Run a scenario against the spec's program in an in-process bank, with
+  // no validator or client code. The scenario compiles to a test beside the
+  // workspace's integration tests that prints the state after each step
+  async simulate(inputFile: string, scenarioFile: string, outputDir: string, options: WorkspaceOptions): Promise<void> {
+    try {
+      const steps = this.parser.parseScenario(fs.readFileSync(scenarioFile, 'utf8'));
+      await this.compile(inputFile, outputDir, options);
+
+      const config = this.parser.parse(fs.readFileSync(inputFile, 'utf8'));
+      const programName = config.token.symbol.toLowerCase() + '_launch';
+      const programDir = path.join(outputDir, 'programs', programName);
+      const scenario = this.generateScenario(config, programName, steps, path.basename(scenarioFile));
+      fs.writeFileSync(path.join(programDir, 'tests', 'scenario.rs'), scenario);
+
+      console.log(`🧪 Simulating ${steps.length} step(s) from ${scenarioFile}`);
+      // program-test logs every instruction at debug level unless told otherwise
+      const env = { ...process.env, RUST_LOG: process.env.RUST_LOG ?? 'error' };
+      const result = spawnSync('cargo', ['test', '--quiet', '--test', 'scenario', '--', '--nocapture'], { cwd: programDir, stdio: 'inherit', env });
+      if (result.status !== 0) {
+        throw new Error(`cargo test exited with ${result.status ?? result.signal}`);
+      }
+    } catch (error) {
+      console.error('❌ Simulation failed:', error.message);
+      process.exit(1);
+    }
+  }
+
+  // The keypair anchor build and the deploy script expect in target/deploy:
+  // copied from --program-keypair, or created on the first deploy. Losing
+  // it does not block upgrades, which only need the program id.
+  private programKeypair(file: string, from?: string): Keypair {
+    const source = from || file;
+    let keypair: Keypair;
+    if (fs.existsSync(source)) {
+      keypair = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(source, 'utf8'))));
+      if (source === file) return keypair;
+    } else if (from) {
+      throw new Error(`No program keypair at ${from}`);
+    } else {
+      keypair = Keypair.generate();
+      console.log(`🔑 New program id ${keypair.publicKey.toBase58()}; back up ${file}`);
+    }
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    fs.writeFileSync(file, JSON.stringify(Array.from(keypair.secretKey)), { mode: 0o600 });
+    return keypair;
+  }
+
+  verifyArchive(file: string): void {
+    const archive: LaunchArchive = JSON.parse(fs.readFileSync(file, 'utf8'));
+    const result = verifyArchive(archive);
+    if (!result.valid) {
+      console.error(`❌ Archive ${file} failed verification: ${result.reason}`);
+      process.exit(1);
+    }
+    console.log(`✅ Archive verified: hash ${archive.archiveHash} signed by ${archive.signer}`);
+    console.log(`   Compare against LaunchArchive.archive_hash on-chain for ${archive.tokenMint}`);
+  }
+
+  private generateCargoToml(config: SolDConfig): string {
+    return `[package]
+name = "${config.token.symbol.toLowerCase()}_launch"
+version = "0.1.0"
+description = "Generated by SolD - ${config.token.name} token launch"
+edition = "2021"
+
+[lib]
+crate-type = ["cdylib", "lib"]
+name = "${config.token.symbol.toLowerCase()}_launch"
+
+[features]
+no-entrypoint = []
+no-idl = []
+no-log-ix-name = []
+cpi = ["no-entrypoint"]
+default = []
+idl-build = ["anchor-lang/idl-build", "anchor-spl/idl-build"]
+
+[dependencies]
+anchor-lang = "0.29.0"
+anchor-spl = "0.29.0"
+
+[dev-dependencies]
+solana-program-test = "1.17"
+solana-sdk = "1.17"
+${config.constants.tokenStandard === 'token2022'
+  ? 'spl-token-2022 = { version = "0.9", features = ["no-entrypoint"] }'
+  : 'spl-token = { version = "4", features = ["no-entrypoint"] }'}
+tokio = { version = "1", features = ["macros"] }${config.invariants.length ? '\nproptest = "1"' : ''}`;
+  }
+
+  private generateWorkspaceToml(): string {
+    return `[workspace]
+members = ["programs/*", "clients/*"]
+resolver = "2"
+
+[profile.release]
+overflow-checks = true
+lto = "fat"
+codegen-units = 1
+
+[profile.release.build-override]
+opt-level = 3
+incremental = false
+codegen-units = 1`;
+  }
+
+  private generateAnchorToml(config: SolDConfig, programId: string, cluster: string): string {
+    return `[toolchain]
+anchor_version = "0.29.0"
+
+[features]
+seeds = false
+skip-lint = false
+
+[programs.${cluster}]
+${config.token.symbol.toLowerCase()}_launch = "${programId}"
+
+[registry]
+url = "https://api.apr.dev"
+
+[provider]
+cluster = "${cluster}"
+wallet = "~/.config/solana/id.json"
+
+[scripts]
+test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"`;
+  }
+
+  private generatePackageJson(programName: string): string {
+    return JSON.stringify({
+      name: programName.replace(/_/g, '-'),
+      private: true,
+      scripts: { test: 'anchor test' },
+      dependencies: { '@coral-xyz/anchor': '^0.29.0' },
+      devDependencies: {
+        '@types/chai': '^4.3.0',
+        '@types/mocha': '^9.0.0',
+        chai: '^4.3.4',
+        mocha: '^9.0.3',
+        'ts-mocha': '^10.0.0',
+        typescript: '^4.3.5'
+      }
+    }, null, 2);
+  }
+
+  private generateTsConfig(): string {
+    return JSON.stringify({
+      compilerOptions: {
+        types: ['mocha', 'chai'],
+        typeRoots: ['./node_modules/@types'],
+        lib: ['es2015'],
+        module: 'commonjs',
+        target: 'es6',
+        esModuleInterop: true
+      }
+    }, null, 2);
+  }
+
+  // Smoke test that the built program deploys under the declared id
+  // Shared prologue: run from the workspace root against a cluster given as
+  // the first argument, optionally through the RPC url given as the second
+  private scriptHeader(script: string, summary: string, programName: string, programId: string, cluster: string): string {
+    return `#!/usr/bin/env bash
+# ${summary}
+# Generated by sold from the spec; regenerate rather than edit.
+# Usage: scripts/${script} [localnet|devnet|testnet|mainnet] [rpc-url]
+# PRIORITY_FEE (micro-lamports per compute unit) prices the program writes.
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+PROGRAM="${programName}"
+PROGRAM_ID="${programId}"
+CLUSTER="\${1:-${cluster}}"
+case "$CLUSTER" in
+  mainnet) URL="mainnet-beta" ;;
+  localnet) URL="localhost" ;;
+  *) URL="$CLUSTER" ;;
+esac
+URL="\${2:-$URL}"
+PROVIDER="\${2:-$CLUSTER}"
+PRICE="\${PRIORITY_FEE:+--with-compute-unit-price $PRIORITY_FEE}"
+`;
+  }
+
+  // First deployment under the program keypair in target/deploy, then the
+  // IDL, then the hand-over to the spec's <deploy> upgrade authority
+  private generateDeployScript(config: SolDConfig, programName: string, programId: string, cluster: string): string {
+    const deploy = config.deploy;
+    const authority = deploy?.squads ? squadsVault(deploy.squads, deploy.vault).toBase58() : deploy?.authority;
+    const handOver = authority ? `
+# The IDL stays with the deploying wallet so scripts/upgrade.sh can publish it
+solana program set-upgrade-authority "$PROGRAM_ID" --new-upgrade-authority "${authority}" \\
+  --skip-new-upgrade-authority-signer-check --url "$URL"
+echo "Upgrade authority is now ${deploy!.squads ? `the vault of Squads multisig ${deploy!.squads}: ` : ''}${authority}"
+` : '';
+
+    return this.scriptHeader('deploy.sh', `First deployment of ${programName} (${config.token.name})`, programName, programId, cluster) + `KEYPAIR="target/deploy/$PROGRAM-keypair.json"
+
+if [ "$(solana-keygen pubkey "$KEYPAIR")" != "$PROGRAM_ID" ]; then
+  echo "$KEYPAIR is not the keypair for $PROGRAM_ID; deploy with sold deploy to manage it" >&2
+  exit 1
+fi
+
+anchor build
+solana program deploy "target/deploy/$PROGRAM.so" --program-id "$KEYPAIR" --url "$URL" $PRICE
+anchor idl init "$PROGRAM_ID" --filepath "target/idl/$PROGRAM.json" --provider.cluster "$PROVIDER"
+${handOver}`;
+  }
+
+  // Upgrade through a buffer: write the new build, grow the program data
+  // account if needed, then upgrade - or hand the buffer to the Squads vault
+  // and leave the upgrade to a multisig proposal
+  private generateUpgradeScript(config: SolDConfig, programName: string, programId: string, cluster: string): string {
+    const deploy = config.deploy;
+    // Fail before paying for a buffer the upgrade could not use
+    const preflight = deploy?.authority
+      ? `: "\${UPGRADE_AUTHORITY:?set UPGRADE_AUTHORITY to the keypair file of ${deploy.authority}}"\n`
+      : '';
+    let upgrade: string;
+    if (deploy?.squads) {
+      const vault = squadsVault(deploy.squads, deploy.vault).toBase58();
+      upgrade = `solana program set-buffer-authority "$BUFFER" --new-buffer-authority "${vault}" --url "$URL"
+echo "Buffer $BUFFER now belongs to the Squads vault ${vault}."
+echo "Propose the upgrade from multisig ${deploy.squads}: Developers > Programs > $PROGRAM_ID > Add upgrade,"
+echo "with buffer $BUFFER and spill address ${vault}. Once it executes, publish the IDL:"
+echo "  anchor idl upgrade $PROGRAM_ID --filepath target/idl/$PROGRAM.json --provider.cluster $PROVIDER"
+`;
+    } else {
+      const signer = deploy?.authority ? ' --upgrade-authority "$UPGRADE_AUTHORITY"' : '';
+      upgrade = `solana program upgrade "$BUFFER" "$PROGRAM_ID" --url "$URL"${signer}
+anchor idl upgrade "$PROGRAM_ID" --filepath "target/idl/$PROGRAM.json" --provider.cluster "$PROVIDER"
+`;
+    }
+
+    return this.scriptHeader('upgrade.sh', `Upgrade the deployed ${programName} (${config.token.name}) to this build`, programName, programId, cluster) + `
+${preflight}anchor build
+SO="target/deploy/$PROGRAM.so"
+
+# An upgrade cannot grow the program data account, so extend it first
+SO_LEN=$(wc -c < "$SO")
+DATA_LEN=$(solana program show "$PROGRAM_ID" --url "$URL" --output json | sed -n 's/.*"dataLen": *\\([0-9]*\\).*/\\1/p')
+if [ "$SO_LEN" -gt "$DATA_LEN" ]; then
+  solana program extend "$PROGRAM_ID" $((SO_LEN - DATA_LEN)) --url "$URL"
+fi
+
+BUFFER=$(solana program write-buffer "$SO" --url "$URL" $PRICE --output json | sed -n 's/.*"buffer": *"\\([^"]*\\)".*/\\1/p')
+echo "Wrote $SO to buffer $BUFFER"
+${upgrade}`;
+  }
+
+  private generateTestSuite(config: SolDConfig, programName: string, programId: string): string {
+    return `import * as anchor from '@coral-xyz/anchor';
+import { expect } from 'chai';
+
+describe('${programName}', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.${toPascalCase(programName)};
+
+  it('deploys ${config.token.name} under the declared program id', async () => {
+    expect(program.programId.toBase58()).to.equal('${programId}');
+    const info = await program.provider.connection.getAccountInfo(program.programId);
+    expect(info?.executable).to.equal(true);
+  });
+});
+`;
+  }
+
+  // Rust integration tests on solana-program-test. Launch state that needs
+  // a key the spec fixes (an insurance wallet) is seeded directly, so each
+  // error path and the fees charged can be asserted without those keys
+  private generateIntegrationTests(config: SolDConfig, programName: string, migrateFrom?: Idl): string {
+    const fees = this.parser.feeSchedule(config);
+    const token2022 = config.constants.tokenStandard === 'token2022';
+    const splToken = token2022 ? 'spl_token_2022' : 'spl_token';
+    const transferFee = token2022 && config.constants.transferFeeBps > 0;
+    const { gated, pdaRoles } = this.parser.accessControl(config);
+    const access = (instruction: string) => gated.find(g => g.instruction === instruction)!;
+    const open = (instruction: string) => access(instruction).roles.includes('anyone');
+    // Optional grant accounts, left out (None) unless a test passes one
+    const grantFields = (instruction: string) => access(instruction).roles
+      .filter(role => PDA_ROLES.includes(role)).map(role => `            ${role}_role: None,\n`).join('');
+    // How a test gets `wallet` past an instruction's access check: as the
+    // launch's creator or insurance wallet, or with a grant seeded for it.
+    // Undefined when only the spec's escrow may call the instruction.
+    const caller = (instruction: string, wallet: string) => {
+      const { roles } = access(instruction);
+      const role = (['anyone', 'insurance', 'creator', ...PDA_ROLES] as AccessRole[]).find(r => roles.includes(r));
+      if (!role) return undefined;
+      const grant = PDA_ROLES.includes(role);
+      return {
+        grant,
+        launch: (creator: string, insurance: string, timelockEnd: string) =>
+          `${grant ? 'let launch = ' : ''}add_launch(&mut test, mint, ${role === 'creator' ? `${wallet}.pubkey()` : creator}, ${role === 'insurance' ? `${wallet}.pubkey()` : insurance}, ${timelockEnd});${grant ? `
+    let grant = add_role_grant(&mut test, seeds::${role.toUpperCase()}_ROLE, launch, ${wallet}.pubkey());` : ''}`,
+        ix: (ix: string) => grant ? `with_role(${ix}, grant)` : ix
+      };
+    };
+    // The fee test trades on a launch from initialize_launch, open to anyone
+    const feeTransfer = transferFee && open('transfer_tokens');
+    const withdrawer = caller('emergency_withdraw', 'insurance');
+    const holder = open('transfer_tokens') ? undefined : caller('transfer_tokens', 'holder');
+    const vestingCreator = config.vesting ? caller('create_vesting', 'creator') : undefined;
+    const seededGrants = [withdrawer, holder, vestingCreator].some(c => c?.grant);
+    // Module accounts the tests read back
+    const moduleImports = [
+      ...(config.modules.includes('staking') ? ['StakePosition'] : []),
+      ...(config.modules.includes('governance') ? ['Proposal', 'MAX_DESCRIPTION_LEN'] : []),
+      ...(config.modules.includes('presale') ? ['Presale'] : [])
+    ].map(name => `${name}, `).join('');
+    const rejects = (instruction: string, fallback: string) =>
+      config.access[instruction].join() === DEFAULT_ACCESS[instruction].join() ? fallback : `${instruction.replace('_tokens', '')}_rejects_callers_without_a_role`;
+
+    const roles = pdaRoles.length ? `
+fn role_address(seed: &[u8], launch: Pubkey, member: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seed, launch.as_ref(), member.as_ref()], &ID).0
+}
+${seededGrants ? `
+// A grant issued before the test starts
+fn add_role_grant(test: &mut ProgramTest, seed: &[u8], launch: Pubkey, member: Pubkey) -> Pubkey {
+    let (address, bump) = Pubkey::find_program_address(&[seed, launch.as_ref(), member.as_ref()], &ID);
+    let mut data = Vec::new();
+    RoleGrant { launch, member, bump }.try_serialize(&mut data).unwrap();
+    test.add_account(address, Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    });
+    address
+}
+
+// Pass a grant where the instruction left its optional account out; the
+// client puts the program id there, Anchor's None
+fn with_role(mut ix: Instruction, grant: Pubkey) -> Instruction {
+    let slot = ix.accounts.iter_mut().find(|meta| meta.pubkey == ID).expect("instruction takes no role grant");
+    slot.pubkey = grant;
+    ix
+}
+` : ''}${pdaRoles.map(role => `
+fn grant_${role}(creator: Pubkey, mint: Pubkey, member: Pubkey) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::Grant${toPascalCase(role)} {
+            creator,
+            token_launch,
+            token_mint: mint,
+            member,
+            ${role}_role: role_address(seeds::${role.toUpperCase()}_ROLE, token_launch, member),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Grant${toPascalCase(role)} {}.data(),
+    }
+}
+
+fn revoke_${role}(creator: Pubkey, mint: Pubkey, member: Pubkey) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::Revoke${toPascalCase(role)} {
+            creator,
+            token_launch,
+            token_mint: mint,
+            member,
+            ${role}_role: role_address(seeds::${role.toUpperCase()}_ROLE, token_launch, member),
+        }
+        .to_account_metas(None),
+        data: instruction::Revoke${toPascalCase(role)} {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn ${role}_role_is_granted_and_revoked_by_creator() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let creator = add_wallet(&mut test);
+    let intruder = add_wallet(&mut test);
+    let member = Pubkey::new_unique();
+    let launch = add_launch(&mut test, mint, creator.pubkey(), Pubkey::new_unique(), 0);
+    let mut context = test.start_with_context().await;
+    let grant = role_address(seeds::${role.toUpperCase()}_ROLE, launch, member);
+
+    let result = send(&mut context, grant_${role}(intruder.pubkey(), mint, member), &[&intruder]).await;
+    assert_error(result, ErrorCode::UnauthorizedCreator);
+
+    send(&mut context, grant_${role}(creator.pubkey(), mint, member), &[&creator]).await.unwrap();
+    let account = context.banks_client.get_account(grant).await.unwrap().unwrap();
+    let state = <RoleGrant as anchor_lang::AccountDeserialize>::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!((state.launch, state.member), (launch, member));
+
+    send(&mut context, revoke_${role}(creator.pubkey(), mint, member), &[&creator]).await.unwrap();
+    assert!(context.banks_client.get_account(grant).await.unwrap().is_none());
+}
+`).join('')}` : '';
+
+    const wallets = config.vesting || pdaRoles.length || config.modules.length ? `
+fn add_wallet(test: &mut ProgramTest) -> Keypair {
+    let wallet = Keypair::new();
+    test.add_account(wallet.pubkey(), Account { lamports: 1_000_000_000, ..Account::default() });
+    wallet
+}
+` : '';
+    const vesting = config.vesting ? `
+fn create_vesting(creator: Pubkey, mint: Pubkey, beneficiary: Pubkey, creator_tokens: Pubkey, amount: u64) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    let vesting = vesting_address(token_launch, beneficiary);
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateVesting {
+            creator,
+            token_launch,
+            token_mint: mint,
+            beneficiary,
+            vesting,
+            vesting_vault: Pubkey::find_program_address(&[seeds::VESTING_VAULT, vesting.as_ref()], &ID).0,
+            creator_tokens,
+            token_program: ${splToken}::id(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+${grantFields('create_vesting')}        }
+        .to_account_metas(None),
+        data: instruction::CreateVesting { amount }.data(),
+    }
+}
+
+fn claim_vested(mint: Pubkey, beneficiary: Pubkey, beneficiary_tokens: Pubkey) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    let vesting = vesting_address(token_launch, beneficiary);
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClaimVested {
+            beneficiary,
+            token_launch,
+            token_mint: mint,
+            vesting,
+            vesting_vault: Pubkey::find_program_address(&[seeds::VESTING_VAULT, vesting.as_ref()], &ID).0,
+            beneficiary_tokens,
+            token_program: ${splToken}::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimVested {}.data(),
+    }
+}
+
+fn vesting_address(token_launch: Pubkey, beneficiary: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::VESTING, token_launch.as_ref(), beneficiary.as_ref()], &ID).0
+}
+
+${open('create_vesting') ? '' : `#[tokio::test]
+async fn ${rejects('create_vesting', 'create_vesting_rejects_non_creator')}() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let intruder = add_wallet(&mut test);
+    add_launch(&mut test, mint, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    let intruder_tokens = add_token_account(&mut test, mint, intruder.pubkey(), 1_000);
+    let mut context = test.start_with_context().await;
+
+    let ix = create_vesting(intruder.pubkey(), mint, Pubkey::new_unique(), intruder_tokens, 1_000);
+    assert_error(send(&mut context, ix, &[&intruder]).await, ErrorCode::${access('create_vesting').error});
+}
+`}${vestingCreator ? `
+#[tokio::test]
+async fn vested_tokens_claim_on_schedule() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let creator = add_wallet(&mut test);
+    let beneficiary = Keypair::new();
+    // Vesting starts at the timelock end, well after genesis
+    let timelock_end = 4_000_000_000;
+    ${vestingCreator.launch('creator.pubkey()', 'Pubkey::new_unique()', 'timelock_end')}
+    let creator_tokens = add_token_account(&mut test, mint, creator.pubkey(), 1_000);
+    let beneficiary_tokens = add_token_account(&mut test, mint, beneficiary.pubkey(), 0);
+    let mut context = test.start_with_context().await;
+
+    let ix = ${vestingCreator.ix('create_vesting(creator.pubkey(), mint, beneficiary.pubkey(), creator_tokens, 1_000)')};
+    send(&mut context, ix, &[&creator]).await.unwrap();
+    assert_eq!(token_balance(&mut context, creator_tokens).await, 0);
+
+    let result = send(&mut context, claim_vested(mint, beneficiary.pubkey(), beneficiary_tokens), &[&beneficiary]).await;
+    assert_error(result, ErrorCode::NothingToClaim);
+
+    warp_to(&mut context, timelock_end + VestingAccount::DURATION).await;
+    send(&mut context, claim_vested(mint, beneficiary.pubkey(), beneficiary_tokens), &[&beneficiary]).await.unwrap();
+    assert_eq!(token_balance(&mut context, beneficiary_tokens).await, 1_000);
+}
+` : ''}` : '';
+
+    return `//! Exercises every instruction against a local runtime: happy paths, each
+//! error code and the fees charged. Generated by \`sold build\` from the spec;
+//! do not edit, regenerate instead.
+
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    ${feeTransfer ? 'system_instruction, system_program' : 'system_program'},
+    ${config.vesting || config.modules.length ? 'sysvar::{self, clock::Clock}' : 'sysvar::clock::Clock'},
+    transaction::{Transaction, TransactionError},
+};
+${config.invariants.length ? 'use proptest::prelude::*;\n' : ''}use ${programName}::{accounts, constants, instruction, seeds, ErrorCode, ${pdaRoles.length ? 'RoleGrant, ' : ''}TokenLaunch, ${config.vesting ? 'VestingAccount, ' : ''}${moduleImports}ID};
+${token2022 ? `use spl_token_2022::extension::{metadata_pointer::MetadataPointer, ${transferFee ? 'transfer_fee::TransferFeeConfig, ' : ''}${feeTransfer ? 'ExtensionType, ' : ''}BaseStateWithExtensions, StateWithExtensions};
+` : ''}
+// Values from the spec
+const TOKEN_NAME: &str = "${config.token.name}";
+const TOKEN_SYMBOL: &str = "${config.token.symbol}";
+const TOTAL_SUPPLY: u64 = ${config.token.supply};
+const TIMELOCK_SECS: i64 = ${this.parser.parseDuration(config.timelock.duration)}; // ${config.timelock.duration}
+const INSURANCE_LIMIT: u8 = ${config.insurance.limit};
+const LAUNCH_FEE: u64 = ${fees.launch};
+const TRADING_FEE: u64 = ${fees.trading};
+const FEE_RECIPIENT: &str = "${config.fees.recipient}";
+
+// Anchor's entry wants account infos that outlive the call
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    ${programName}::entry(program_id, accounts, data)
+}
+
+fn fee_recipient() -> Pubkey {
+    FEE_RECIPIENT.parse().unwrap()
+}
+
+fn program_test() -> ProgramTest {
+    let mut test = ProgramTest::new("${programName}", ID, processor!(process_instruction));
+    test.add_account(fee_recipient(), Account { lamports: 1_000_000_000, ..Account::default() });
+    test
+}
+
+fn add_packed<T: Pack>(test: &mut ProgramTest, address: Pubkey, state: T) {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    test.add_account(address, Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        data,
+        owner: ${splToken}::id(),
+        ..Account::default()
+    });
+}
+
+fn add_mint(test: &mut ProgramTest) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let state = ${splToken}::state::Mint {
+        mint_authority: COption::None,
+        supply: TOTAL_SUPPLY,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    add_packed(test, mint, state);
+    mint
+}
+
+fn add_token_account(test: &mut ProgramTest, mint: Pubkey, owner: Pubkey, amount: u64) -> Pubkey {
+    let address = Pubkey::new_unique();
+    let state = ${splToken}::state::Account {
+        mint,
+        owner,
+        amount,
+        state: ${splToken}::state::AccountState::Initialized,
+        ..${splToken}::state::Account::default()
+    };
+    add_packed(test, address, state);
+    address
+}
+
+// A launch already past initialize_launch
+fn add_launch(test: &mut ProgramTest, mint: Pubkey, creator: Pubkey, insurance: Pubkey, timelock_end: i64) -> Pubkey {
+    let (address, _) = seeds::find_launch(&mint);
+    let launch = TokenLaunch {
+        creator,
+        token_mint: mint,
+        token_name: TOKEN_NAME.to_string(),
+        token_symbol: TOKEN_SYMBOL.to_string(),
+        total_supply: TOTAL_SUPPLY,
+        timelock_end,
+        insurance_wallets: vec![insurance],
+        insurance_limit: INSURANCE_LIMIT,
+        fees_collected: LAUNCH_FEE,
+        total_withdrawn: 0,
+    };
+    let mut data = Vec::new();
+    launch.try_serialize(&mut data).unwrap();
+    data.resize(TokenLaunch::space(), 0);
+    test.add_account(address, Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    });
+    address
+}
+
+async fn send(context: &mut ProgramTestContext, ix: Instruction, signers: &[&Keypair]) -> Result<(), BanksClientError> {
+    // A fresh blockhash, so repeating an instruction is not a duplicate
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all: Vec<&Keypair> = vec![&context.payer];
+    all.extend(signers);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &all, blockhash);
+    context.banks_client.process_transaction(tx).await
+}
+
+fn assert_error(result: Result<(), BanksClientError>, expected: ErrorCode) {
+    match result.expect_err("instruction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(expected), "expected {expected:?}");
+        }
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
+
+async fn warp_to(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+async fn now(context: &mut ProgramTestContext) -> i64 {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    ${token2022 ? 'StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap().base.amount' : 'spl_token::state::Account::unpack(&account.data).unwrap().amount'}
+}
+
+async fn fetch_launch(context: &mut ProgramTestContext, address: Pubkey) -> TokenLaunch {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn initialize_launch_ix(creator: Pubkey, mint: Pubkey, token_name: &str) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::InitializeLaunch {
+            creator,
+            token_launch: seeds::find_launch(&mint).0,
+            token_mint: mint,
+            fee_recipient: fee_recipient(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::InitializeLaunch {
+            token_name: token_name.to_string(),
+            token_symbol: TOKEN_SYMBOL.to_string(),
+            token_supply: TOTAL_SUPPLY,
+            timelock_duration: TIMELOCK_SECS,
+            insurance_limit: INSURANCE_LIMIT,
+        }
+        .data(),
+    }
+}
+
+async fn initialize_launch(context: &mut ProgramTestContext, mint: Pubkey) -> Pubkey {
+    let ix = initialize_launch_ix(context.payer.pubkey(), mint, TOKEN_NAME);
+    send(context, ix, &[]).await.unwrap();
+    seeds::find_launch(&mint).0
+}
+
+fn transfer_tokens(payer: Pubkey, mint: Pubkey, from: Pubkey, to: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::TransferTokens {
+            payer,
+            token_launch: seeds::find_launch(&mint).0,
+            token_mint: mint,
+            from,
+            to,
+            authority,
+            fee_recipient: fee_recipient(),
+            token_program: ${splToken}::id(),
+            system_program: system_program::ID,
+${grantFields('transfer_tokens')}        }
+        .to_account_metas(None),
+        data: instruction::TransferTokens { amount }.data(),
+    }
+}
+
+fn emergency_withdraw(mint: Pubkey, from: Pubkey, to: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::EmergencyWithdraw {
+            token_launch: seeds::find_launch(&mint).0,
+            token_mint: mint,
+            from,
+            to,
+            authority,
+            token_program: ${splToken}::id(),
+${grantFields('emergency_withdraw')}        }
+        .to_account_metas(None),
+        data: instruction::EmergencyWithdraw { amount }.data(),
+    }
+}
+
+#[tokio::test]
+async fn initialize_launch_charges_launch_fee() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let mut context = test.start_with_context().await;
+
+    let before = lamports(&mut context, fee_recipient()).await;
+    let launch = initialize_launch(&mut context, mint).await;
+    assert_eq!(lamports(&mut context, fee_recipient()).await - before, LAUNCH_FEE);
+
+    let state = fetch_launch(&mut context, launch).await;
+    assert_eq!(state.creator, context.payer.pubkey());
+    assert_eq!(state.total_supply, TOTAL_SUPPLY);
+    assert_eq!(state.insurance_limit, INSURANCE_LIMIT);
+    assert_eq!(state.fees_collected, LAUNCH_FEE);
+    assert_eq!(state.total_withdrawn, 0);
+    assert_eq!(state.timelock_end, now(&mut context).await + TIMELOCK_SECS);
+}
+
+#[tokio::test]
+async fn initialize_launch_rejects_names_the_account_cannot_hold() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let mut context = test.start_with_context().await;
+
+    let name = "N".repeat(constants::MAX_NAME_LEN + 1);
+    let ix = initialize_launch_ix(context.payer.pubkey(), mint, &name);
+    assert_error(send(&mut context, ix, &[]).await, ErrorCode::NameTooLong);
+
+    // The longest name allowed fits the space allocated for it
+    let name = "N".repeat(constants::MAX_NAME_LEN);
+    let ix = initialize_launch_ix(context.payer.pubkey(), mint, &name);
+    send(&mut context, ix, &[]).await.unwrap();
+    let launch = context.banks_client.get_account(seeds::find_launch(&mint).0).await.unwrap().unwrap();
+    assert_eq!(launch.data.len(), TokenLaunch::space());
+}
+
+${open('transfer_tokens') || holder ? `#[tokio::test]
+async fn transfer_is_blocked_until_timelock_ends() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let holder = Keypair::new();
+    let from = add_token_account(&mut test, mint, holder.pubkey(), 1_000);
+    let to = add_token_account(&mut test, mint, Pubkey::new_unique(), 0);${holder ? `
+    // A launch the holder may trade on, locked until well after genesis
+    let timelock_end = 4_000_000_000;
+    ${holder.launch('Pubkey::new_unique()', 'Pubkey::new_unique()', 'timelock_end')}` : ''}
+    let mut context = test.start_with_context().await;${holder ? '' : `
+    let launch = initialize_launch(&mut context, mint).await;`}
+
+    let payer = context.payer.pubkey();
+    let ix = ${holder ? holder.ix('transfer_tokens(payer, mint, from, to, holder.pubkey(), 400)') : 'transfer_tokens(payer, mint, from, to, holder.pubkey(), 400)'};
+    let result = send(&mut context, ix.clone(), &[&holder]).await;
+    assert_error(result, ErrorCode::TimelockActive);
+
+${holder ? '' : `    let timelock_end = fetch_launch(&mut context, launch).await.timelock_end;
+`}    warp_to(&mut context, timelock_end).await;
+    let before = lamports(&mut context, fee_recipient()).await;
+    send(&mut context, ix, &[&holder]).await.unwrap();
+
+    assert_eq!(lamports(&mut context, fee_recipient()).await - before, TRADING_FEE);
+    assert_eq!(token_balance(&mut context, from).await, 600);
+    assert_eq!(token_balance(&mut context, to).await, 400);
+}
+` : ''}${open('transfer_tokens') ? '' : `
+#[tokio::test]
+async fn ${rejects('transfer_tokens', '')}() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let intruder = Keypair::new();
+    add_launch(&mut test, mint, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    let from = add_token_account(&mut test, mint, intruder.pubkey(), 1_000);
+    let to = add_token_account(&mut test, mint, Pubkey::new_unique(), 0);
+    let mut context = test.start_with_context().await;
+
+    let payer = context.payer.pubkey();
+    let result = send(&mut context, transfer_tokens(payer, mint, from, to, intruder.pubkey(), 400), &[&intruder]).await;
+    assert_error(result, ErrorCode::${access('transfer_tokens').error});
+}
+`}${open('emergency_withdraw') ? '' : `
+#[tokio::test]
+async fn emergency_withdraw_rejects_other_wallets() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let insurance = Keypair::new();
+    let intruder = Keypair::new();
+    add_launch(&mut test, mint, Pubkey::new_unique(), insurance.pubkey(), 0);
+    let from = add_token_account(&mut test, mint, intruder.pubkey(), TOTAL_SUPPLY);
+    let to = add_token_account(&mut test, mint, intruder.pubkey(), 0);
+    let mut context = test.start_with_context().await;
+
+    let result = send(&mut context, emergency_withdraw(mint, from, to, intruder.pubkey(), 1), &[&intruder]).await;
+    assert_error(result, ErrorCode::${access('emergency_withdraw').error});
+}
+`}${withdrawer ? `
+#[tokio::test]
+async fn emergency_withdraw_is_capped_at_insurance_limit() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let insurance = Keypair::new();
+    ${withdrawer.launch('Pubkey::new_unique()', 'insurance.pubkey()', '0')}
+    let from = add_token_account(&mut test, mint, insurance.pubkey(), TOTAL_SUPPLY);
+    let to = add_token_account(&mut test, mint, insurance.pubkey(), 0);
+    let mut context = test.start_with_context().await;
+    let limit = TOTAL_SUPPLY * INSURANCE_LIMIT as u64 / 100;
+
+    let result = send(&mut context, ${withdrawer.ix('emergency_withdraw(mint, from, to, insurance.pubkey(), limit + 1)')}, &[&insurance]).await;
+    assert_error(result, ErrorCode::ExceedsInsuranceLimit);
+
+    send(&mut context, ${withdrawer.ix('emergency_withdraw(mint, from, to, insurance.pubkey(), limit)')}, &[&insurance]).await.unwrap();
+    assert_eq!(token_balance(&mut context, to).await, limit);
+
+    // The limit covers all withdrawals together, not each one
+    let result = send(&mut context, ${withdrawer.ix('emergency_withdraw(mint, from, to, insurance.pubkey(), 1)')}, &[&insurance]).await;
+    assert_error(result, ErrorCode::ExceedsInsuranceLimit);
+}
+` : ''}${open('relock_tokens') ? '' : `
+#[tokio::test]
+async fn ${rejects('relock_tokens', 'relock_rejects_everyone_but_escrow')}() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let mut context = test.start_with_context().await;
+    let launch = initialize_launch(&mut context, mint).await;
+${access('relock_tokens').roles.join() === 'escrow' ? `
+    // The escrow key is fixed by the spec, so only the rejection can run here` : ''}
+    let intruder = Keypair::new();
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::RelockTokens {
+            authority: intruder.pubkey(),
+            token_launch: launch,
+            token_mint: mint,
+            fee_recipient: fee_recipient(),
+            system_program: system_program::ID,
+${grantFields('relock_tokens')}        }
+        .to_account_metas(None),
+        data: instruction::RelockTokens { new_duration: TIMELOCK_SECS }.data(),
+    };
+    assert_error(send(&mut context, ix, &[&intruder]).await, ErrorCode::${access('relock_tokens').error});
+}
+`}${token2022 ? this.token2022Tests(transferFee, feeTransfer) : ''}${wallets}${vesting}${roles}${config.modules.length ? this.moduleTests(config, splToken) : ''}${migrateFrom ? this.migrationTests(config, programName, migrateFrom) : ''}${config.invariants.length ? this.invariantTests(config) : ''}`;
+  }
+
+  // Each module's instructions end to end, against seeded launches
+  private moduleTests(config: SolDConfig, splToken: string): string {
+    const sections: Record<SpecModule, string> = {
+      staking: `
+fn stake_address(token_launch: Pubkey, owner: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::STAKE, token_launch.as_ref(), owner.as_ref()], &ID).0
+}
+
+fn stake_vault(stake: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::STAKE_VAULT, stake.as_ref()], &ID).0
+}
+
+fn open_stake(owner: Pubkey, mint: Pubkey) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    let stake = stake_address(token_launch, owner);
+    Instruction {
+        program_id: ID,
+        accounts: accounts::OpenStake {
+            owner,
+            token_launch,
+            token_mint: mint,
+            stake,
+            stake_vault: stake_vault(stake),
+            token_program: ${splToken}::id(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::OpenStake {}.data(),
+    }
+}
+
+fn update_stake(owner: Pubkey, mint: Pubkey, owner_tokens: Pubkey) -> Vec<solana_sdk::instruction::AccountMeta> {
+    let token_launch = seeds::find_launch(&mint).0;
+    let stake = stake_address(token_launch, owner);
+    accounts::UpdateStake {
+        owner,
+        token_launch,
+        token_mint: mint,
+        stake,
+        stake_vault: stake_vault(stake),
+        owner_tokens,
+        token_program: ${splToken}::id(),
+    }
+    .to_account_metas(None)
+}
+
+fn stake(owner: Pubkey, mint: Pubkey, owner_tokens: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: update_stake(owner, mint, owner_tokens),
+        data: instruction::Stake { amount }.data(),
+    }
+}
+
+fn unstake(owner: Pubkey, mint: Pubkey, owner_tokens: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: update_stake(owner, mint, owner_tokens),
+        data: instruction::Unstake { amount }.data(),
+    }
+}
+
+#[tokio::test]
+async fn staked_tokens_unlock_after_the_lockup() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let owner = add_wallet(&mut test);
+    let launch = add_launch(&mut test, mint, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    let owner_tokens = add_token_account(&mut test, mint, owner.pubkey(), 1_000);
+    let mut context = test.start_with_context().await;
+
+    send(&mut context, open_stake(owner.pubkey(), mint), &[&owner]).await.unwrap();
+    send(&mut context, stake(owner.pubkey(), mint, owner_tokens, 1_000), &[&owner]).await.unwrap();
+    let position = fetch::<StakePosition>(&mut context, stake_address(launch, owner.pubkey())).await;
+    assert_eq!(position.amount, 1_000);
+    assert_eq!(position.locked_until, now(&mut context).await + constants::STAKE_LOCKUP);
+    assert_eq!(token_balance(&mut context, owner_tokens).await, 0);
+
+    let result = send(&mut context, unstake(owner.pubkey(), mint, owner_tokens, 1_000), &[&owner]).await;
+    assert_error(result, ErrorCode::StakeLocked);
+
+    warp_to(&mut context, position.locked_until).await;
+    let result = send(&mut context, unstake(owner.pubkey(), mint, owner_tokens, 1_001), &[&owner]).await;
+    assert_error(result, ErrorCode::InsufficientStake);
+    send(&mut context, unstake(owner.pubkey(), mint, owner_tokens, 1_000), &[&owner]).await.unwrap();
+    assert_eq!(token_balance(&mut context, owner_tokens).await, 1_000);
+}
+`,
+      governance: `
+// A stake position seeded before the test starts
+fn add_stake(test: &mut ProgramTest, launch: Pubkey, owner: Pubkey, amount: u64) {
+    let (address, bump) = Pubkey::find_program_address(&[seeds::STAKE, launch.as_ref(), owner.as_ref()], &ID);
+    let mut data = Vec::new();
+    StakePosition { launch, owner, amount, locked_until: 0, bump }.try_serialize(&mut data).unwrap();
+    test.add_account(address, Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    });
+}
+
+fn create_proposal(proposer: Pubkey, mint: Pubkey, proposal: Pubkey, description: &str) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateProposal {
+            proposer,
+            token_launch,
+            token_mint: mint,
+            stake: stake_address(token_launch, proposer),
+            proposal,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateProposal { description: description.to_string() }.data(),
+    }
+}
+
+fn vote(voter: Pubkey, mint: Pubkey, proposal: Pubkey, approve: bool) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CastVote {
+            voter,
+            token_launch,
+            token_mint: mint,
+            proposal,
+            stake: stake_address(token_launch, voter),
+            vote_record: Pubkey::find_program_address(&[seeds::VOTE, proposal.as_ref(), voter.as_ref()], &ID).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Vote { approve }.data(),
+    }
+}
+
+fn finalize_proposal(mint: Pubkey, proposal: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::FinalizeProposal {
+            token_launch: seeds::find_launch(&mint).0,
+            token_mint: mint,
+            proposal,
+        }
+        .to_account_metas(None),
+        data: instruction::FinalizeProposal {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn staked_votes_decide_proposals() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let yes = add_wallet(&mut test);
+    let no = add_wallet(&mut test);
+    let outsider = add_wallet(&mut test);
+    let launch = add_launch(&mut test, mint, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    // Between them the two voters meet quorum, with the yes side ahead
+    let quorum = (TOTAL_SUPPLY as u128 * constants::QUORUM_BPS as u128 / 10_000) as u64;
+    add_stake(&mut test, launch, yes.pubkey(), quorum / 2 + 2);
+    add_stake(&mut test, launch, no.pubkey(), quorum / 2 + 1);
+    add_stake(&mut test, launch, outsider.pubkey(), 0);
+    let mut context = test.start_with_context().await;
+    let proposal = Keypair::new();
+
+    let ix = create_proposal(outsider.pubkey(), mint, proposal.pubkey(), "Fund an audit");
+    assert_error(send(&mut context, ix, &[&outsider, &proposal]).await, ErrorCode::NoStake);
+    let ix = create_proposal(yes.pubkey(), mint, proposal.pubkey(), &"x".repeat(MAX_DESCRIPTION_LEN + 1));
+    assert_error(send(&mut context, ix, &[&yes, &proposal]).await, ErrorCode::DescriptionTooLong);
+    let ix = create_proposal(yes.pubkey(), mint, proposal.pubkey(), "Fund an audit");
+    send(&mut context, ix, &[&yes, &proposal]).await.unwrap();
+
+    send(&mut context, vote(yes.pubkey(), mint, proposal.pubkey(), true), &[&yes]).await.unwrap();
+    send(&mut context, vote(no.pubkey(), mint, proposal.pubkey(), false), &[&no]).await.unwrap();
+    // Each stake votes once, and stays locked until voting ends
+    assert!(send(&mut context, vote(yes.pubkey(), mint, proposal.pubkey(), true), &[&yes]).await.is_err());
+    let state = fetch::<Proposal>(&mut context, proposal.pubkey()).await;
+    let position = fetch::<StakePosition>(&mut context, stake_address(launch, yes.pubkey())).await;
+    assert_eq!(position.locked_until, state.voting_ends);
+
+    let result = send(&mut context, finalize_proposal(mint, proposal.pubkey()), &[]).await;
+    assert_error(result, ErrorCode::VotingOpen);
+    warp_to(&mut context, state.voting_ends).await;
+    let result = send(&mut context, vote(outsider.pubkey(), mint, proposal.pubkey(), false), &[&outsider]).await;
+    assert_error(result, ErrorCode::VotingClosed);
+
+    send(&mut context, finalize_proposal(mint, proposal.pubkey()), &[]).await.unwrap();
+    let state = fetch::<Proposal>(&mut context, proposal.pubkey()).await;
+    assert_eq!((state.yes_votes, state.no_votes), (quorum / 2 + 2, quorum / 2 + 1));
+    assert!(state.finalized && state.passed);
+    let result = send(&mut context, finalize_proposal(mint, proposal.pubkey()), &[]).await;
+    assert_error(result, ErrorCode::ProposalFinalized);
+}
+`,
+      presale: `
+fn presale_address(token_launch: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::PRESALE, token_launch.as_ref()], &ID).0
+}
+
+fn presale_vault(mint: Pubkey) -> Pubkey {
+    let presale = presale_address(seeds::find_launch(&mint).0);
+    Pubkey::find_program_address(&[seeds::PRESALE_VAULT, presale.as_ref()], &ID).0
+}
+
+fn open_presale(creator: Pubkey, mint: Pubkey, creator_tokens: Pubkey, amount: u64) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::OpenPresale {
+            creator,
+            token_launch,
+            token_mint: mint,
+            presale: presale_address(token_launch),
+            presale_vault: presale_vault(mint),
+            creator_tokens,
+            token_program: ${splToken}::id(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::OpenPresale { amount }.data(),
+    }
+}
+
+fn buy_presale(buyer: Pubkey, mint: Pubkey, creator: Pubkey, buyer_tokens: Pubkey, amount: u64) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::BuyPresale {
+            buyer,
+            token_launch,
+            token_mint: mint,
+            creator,
+            presale: presale_address(token_launch),
+            presale_vault: presale_vault(mint),
+            buyer_tokens,
+            token_program: ${splToken}::id(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::BuyPresale { amount }.data(),
+    }
+}
+
+fn close_presale(creator: Pubkey, mint: Pubkey, creator_tokens: Pubkey) -> Instruction {
+    let token_launch = seeds::find_launch(&mint).0;
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClosePresale {
+            creator,
+            token_launch,
+            token_mint: mint,
+            presale: presale_address(token_launch),
+            presale_vault: presale_vault(mint),
+            creator_tokens,
+            token_program: ${splToken}::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::ClosePresale {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn presale_sells_until_it_ends() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let creator = add_wallet(&mut test);
+    let buyer = add_wallet(&mut test);
+    let launch = add_launch(&mut test, mint, creator.pubkey(), Pubkey::new_unique(), 0);
+    let creator_tokens = add_token_account(&mut test, mint, creator.pubkey(), 1_000);
+    let buyer_tokens = add_token_account(&mut test, mint, buyer.pubkey(), 0);
+    let mut context = test.start_with_context().await;
+
+    let result = send(&mut context, open_presale(buyer.pubkey(), mint, buyer_tokens, 1_000), &[&buyer]).await;
+    assert_error(result, ErrorCode::UnauthorizedCreator);
+    send(&mut context, open_presale(creator.pubkey(), mint, creator_tokens, 1_000), &[&creator]).await.unwrap();
+
+    // The buyer pays the creator and gets tokens from the vault
+    let before = lamports(&mut context, creator.pubkey()).await;
+    let ix = buy_presale(buyer.pubkey(), mint, creator.pubkey(), buyer_tokens, 400);
+    send(&mut context, ix, &[&buyer]).await.unwrap();
+    assert_eq!(token_balance(&mut context, buyer_tokens).await, 400);
+    assert_eq!(lamports(&mut context, creator.pubkey()).await - before, 400 * constants::PRESALE_PRICE_LAMPORTS);
+    let ix = buy_presale(buyer.pubkey(), mint, creator.pubkey(), buyer_tokens, 601);
+    assert_error(send(&mut context, ix, &[&buyer]).await, ErrorCode::PresaleSoldOut);
+
+    let result = send(&mut context, close_presale(creator.pubkey(), mint, creator_tokens), &[&creator]).await;
+    assert_error(result, ErrorCode::PresaleOpen);
+    let presale = fetch::<Presale>(&mut context, presale_address(launch)).await;
+    assert_eq!(presale.sold, 400);
+
+    warp_to(&mut context, presale.ends).await;
+    let ix = buy_presale(buyer.pubkey(), mint, creator.pubkey(), buyer_tokens, 100);
+    assert_error(send(&mut context, ix, &[&buyer]).await, ErrorCode::PresaleEnded);
+    send(&mut context, close_presale(creator.pubkey(), mint, creator_tokens), &[&creator]).await.unwrap();
+    assert_eq!(token_balance(&mut context, creator_tokens).await, 600);
+}
+`
+    };
+    return `
+async fn fetch<T: anchor_lang::AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+${config.modules.map(module => sections[module]).join('')}`;
+  }
+
+  // A proptest over random instruction sequences: every step may fail, but
+  // none may trip an invariant or leave an account that breaks one
+  private invariantTests(config: SolDConfig): string {
+    const scope = this.parser.invariantScope(config);
+    const accounts = new Set(config.invariants.map(({ expression }) => compileInvariant(expression, scope).account));
+    const vesting = !!config.vesting;
+    const checkLaunch = accounts.has('TokenLaunch');
+    const checkVesting = accounts.has('VestingAccount');
+
+    return `
+// Vesting runs from here, so warps always move forward from the timelock end
+const FUZZ_TIMELOCK_END: i64 = 4_000_000_000;
+
+#[derive(Debug, Clone)]
+enum Step {
+    Withdraw(u64),
+    Warp(i64),${vesting ? `
+    CreateVesting(usize, u64),
+    Claim(usize),` : ''}
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    let limit = TOTAL_SUPPLY * INSURANCE_LIMIT as u64 / 100;
+    prop_oneof![
+        (0..=limit).prop_map(Step::Withdraw),
+        (0..=30 * 86_400i64).prop_map(Step::Warp),${vesting ? `
+        (0..2usize, 1..=1_000u64).prop_map(|(i, amount)| Step::CreateVesting(i, amount)),
+        (0..2usize).prop_map(Step::Claim),` : ''}
+    ]
+}
+
+fn run_steps(steps: &[Step]) -> Result<(), TestCaseError> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    runtime.block_on(async {
+        let mut test = program_test();
+        let mint = add_mint(&mut test);
+        let insurance = Keypair::new();${vesting ? `
+        let creator = add_wallet(&mut test);` : ''}
+        ${checkLaunch || vesting ? 'let launch = ' : ''}add_launch(&mut test, mint, ${vesting ? 'creator.pubkey()' : 'Pubkey::new_unique()'}, insurance.pubkey(), FUZZ_TIMELOCK_END);
+        let from = add_token_account(&mut test, mint, insurance.pubkey(), TOTAL_SUPPLY);
+        let to = add_token_account(&mut test, mint, insurance.pubkey(), 0);${vesting ? `
+        let creator_tokens = add_token_account(&mut test, mint, creator.pubkey(), TOTAL_SUPPLY);
+        let beneficiaries = (0..2)
+            .map(|_| {
+                let beneficiary = Keypair::new();
+                let tokens = add_token_account(&mut test, mint, beneficiary.pubkey(), 0);
+                (beneficiary, tokens)
+            })
+            .collect::<Vec<_>>();` : ''}
+        let mut context = test.start_with_context().await;
+        warp_to(&mut context, FUZZ_TIMELOCK_END).await;
+
+        for step in steps {
+            let result = match *step {
+                Step::Withdraw(amount) => {
+                    send(&mut context, emergency_withdraw(mint, from, to, insurance.pubkey(), amount), &[&insurance]).await
+                }
+                Step::Warp(secs) => {
+                    let time = now(&mut context).await + secs;
+                    warp_to(&mut context, time).await;
+                    Ok(())
+                }${vesting ? `
+                Step::CreateVesting(i, amount) => {
+                    let ix = create_vesting(creator.pubkey(), mint, beneficiaries[i].0.pubkey(), creator_tokens, amount);
+                    send(&mut context, ix, &[&creator]).await
+                }
+                Step::Claim(i) => {
+                    let (beneficiary, tokens) = &beneficiaries[i];
+                    send(&mut context, claim_vested(mint, beneficiary.pubkey(), *tokens), &[beneficiary]).await
+                }` : ''}
+            };
+            if let Err(error) = result {
+                let tripped = matches!(error.unwrap(), TransactionError::InstructionError(_, InstructionError::Custom(code))
+                    if code == u32::from(ErrorCode::InvariantViolated));
+                prop_assert!(!tripped, "{step:?} passed the instruction's own checks but broke an invariant");
+            }${checkLaunch ? `
+            let state = fetch_launch(&mut context, launch).await;
+            prop_assert!(state.check_invariants().is_ok(), "launch breaks an invariant after {step:?}");` : ''}${checkVesting ? `
+            for (beneficiary, _) in &beneficiaries {
+                let address = vesting_address(launch, beneficiary.pubkey());
+                if let Some(account) = context.banks_client.get_account(address).await.unwrap() {
+                    let state = <VestingAccount as anchor_lang::AccountDeserialize>::try_deserialize(&mut account.data.as_slice()).unwrap();
+                    prop_assert!(state.check_invariants().is_ok(), "vesting breaks an invariant after {step:?}");
+                }
+            }` : ''}
+        }
+        Ok(())
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn instruction_sequences_keep_invariants(steps in prop::collection::vec(step(), 1..12)) {
+        run_steps(&steps)?;
+    }
+}
+`;
+  }
+
+  // Each migrate_* instruction on an account in the deployed layout, twice
+  private migrationTests(config: SolDConfig, programName: string, migrateFrom: Idl): string {
+    const layouts = new IdlDiffer().migrations(migrateFrom, this.parser.generateIdl(config));
+    return layouts.map(({ account: name }) => `
+#[tokio::test]
+async fn migrate_${toSnakeCase(name)}_rewrites_the_deployed_layout() {
+    let mut test = program_test();
+    let target = Pubkey::new_unique();
+    let mut data = <${programName}::${name} as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+    data.extend(anchor_lang::AnchorSerialize::try_to_vec(&${programName}::migrations::Deployed${name}::default()).unwrap());
+    test.add_account(target, Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    });
+    let mut context = test.start_with_context().await;
+
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts::Migrate${name} {
+            payer: context.payer.pubkey(),
+            target,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Migrate${name} {}.data(),
+    };
+    send(&mut context, ix.clone(), &[]).await.unwrap();
+    let account = context.banks_client.get_account(target).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), ${programName}::${name}::space());
+    let migrated: Result<${programName}::${name}, _> = anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice());
+    assert!(migrated.is_ok());
+
+    // A migrated account is left as it is
+    send(&mut context, ix, &[]).await.unwrap();
+    assert_eq!(context.banks_client.get_account(target).await.unwrap().unwrap(), account);
+}
+`).join('');
+  }
+
+  // create_mint, and the transfer fee when the spec sets one
+  private token2022Tests(transferFee: boolean, feeTransfer: boolean): string {
+    const withFee = feeTransfer ? `
+// A Token-2022 account with room for the transfer fee extension
+async fn create_token_account(context: &mut ProgramTestContext, mint: Pubkey, owner: Pubkey) -> Pubkey {
+    let account = Keypair::new();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[ExtensionType::TransferFeeAmount]).unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer = context.payer.pubkey();
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(&payer, &account.pubkey(), rent.minimum_balance(space), space as u64, &spl_token_2022::id()),
+        spl_token_2022::instruction::initialize_account3(&spl_token_2022::id(), &account.pubkey(), &mint, &owner).unwrap(),
+    ];
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer), &[&context.payer, &account], blockhash);
+    context.banks_client.process_transaction(tx).await.unwrap();
+    account.pubkey()
+}
+
+#[tokio::test]
+async fn transfer_withholds_the_transfer_fee() {
+    let mut context = program_test().start_with_context().await;
+    let mint = Keypair::new();
+    let creator = context.payer.pubkey();
+    send(&mut context, create_mint(creator, mint.pubkey()), &[&mint]).await.unwrap();
+    let holder = Keypair::new();
+    let from = create_token_account(&mut context, mint.pubkey(), holder.pubkey()).await;
+    let to = create_token_account(&mut context, mint.pubkey(), Pubkey::new_unique()).await;
+    let mint_to = spl_token_2022::instruction::mint_to(&spl_token_2022::id(), &mint.pubkey(), &from, &creator, &[], 1_000_000).unwrap();
+    send(&mut context, mint_to, &[]).await.unwrap();
+
+    let launch = initialize_launch(&mut context, mint.pubkey()).await;
+    let timelock_end = fetch_launch(&mut context, launch).await.timelock_end;
+    warp_to(&mut context, timelock_end).await;
+    send(&mut context, transfer_tokens(creator, mint.pubkey(), from, to, holder.pubkey(), 1_000_000), &[&holder]).await.unwrap();
+
+    let fee = (1_000_000 * constants::TRANSFER_FEE_BPS as u64).div_ceil(10_000).min(constants::MAX_TRANSFER_FEE);
+    assert_eq!(token_balance(&mut context, from).await, 0);
+    assert_eq!(token_balance(&mut context, to).await, 1_000_000 - fee);
+}
+` : '';
+    return `
+fn create_mint(creator: Pubkey, mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateMint {
+            creator,
+            token_mint: mint,
+            token_program: spl_token_2022::id(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateMint { decimals: 9 }.data(),
+    }
+}
+
+#[tokio::test]
+async fn create_mint_initializes_extensions() {
+    let mut context = program_test().start_with_context().await;
+    let mint = Keypair::new();
+    let creator = context.payer.pubkey();
+    send(&mut context, create_mint(creator, mint.pubkey()), &[&mint]).await.unwrap();
+
+    let account = context.banks_client.get_account(mint.pubkey()).await.unwrap().unwrap();
+    assert_eq!(account.owner, spl_token_2022::id());
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap();
+    assert_eq!(state.base.mint_authority, COption::Some(creator));
+    assert_eq!(state.base.decimals, 9);
+    let pointer = state.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(pointer.metadata_address), Some(mint.pubkey()));${transferFee ? `
+    let fee = state.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(u16::from(fee.newer_transfer_fee.transfer_fee_basis_points), constants::TRANSFER_FEE_BPS);
+    assert_eq!(u64::from(fee.newer_transfer_fee.maximum_fee), constants::MAX_TRANSFER_FEE);
+    assert_eq!(Option::<Pubkey>::from(fee.transfer_fee_config_authority), None);` : ''}
+}
+${withFee}`;
+  }
+
+  // A `sold simulate` scenario as a test sharing the integration tests'
+  // helpers. The holder starts with half the supply and the insurance
+  // reserve with the rest; a keypair stands in for the spec's first
+  // insurance wallet, whose key the simulation does not have.
+  private generateScenario(config: SolDConfig, programName: string, steps: ScenarioStep[], scenarioFile: string): string {
+    const tests = this.generateIntegrationTests(config, programName);
+    const helpers = tests.slice(tests.indexOf('\nuse '), tests.indexOf('\n#[tokio::test]'));
+    const errors = this.parser.generateIdl(config).errors.map(e => `    (${e.code}, "${e.name}"),`).join('\n');
+    const body = steps.map(step => {
+      const label = `line ${step.line}: ${step.kind}${'amount' in step ? ` ${step.amount}` : ''}`;
+      switch (step.kind) {
+        case 'launch':
+          return `    let ix = initialize_launch_ix(context.payer.pubkey(), world.mint, TOKEN_NAME);
+    run(&mut context, &world, "${label}", ix, &[]).await;
+    stand_in_insurance(&mut context, &world).await;`;
+        case 'warp':
+          return step.seconds === undefined
+            ? `    let timelock_end = fetch_launch(&mut context, seeds::find_launch(&world.mint).0).await.timelock_end;
+    warp(&mut context, &world, "line ${step.line}: warp past timelock", timelock_end).await;`
+            : `    let to = now(&mut context).await + ${step.seconds};
+    warp(&mut context, &world, "line ${step.line}: warp ${step.seconds}s", to).await;`;
+        case 'transfer':
+          return `    let ix = transfer_tokens(context.payer.pubkey(), world.mint, world.holder_tokens, world.recipient_tokens, world.holder.pubkey(), ${step.amount});
+    run(&mut context, &world, "${label}", ix, &[&world.holder]).await;`;
+        case 'withdraw':
+          return `    let ix = emergency_withdraw(world.mint, world.reserve, world.withdrawn, world.insurance.pubkey(), ${step.amount});
+    run(&mut context, &world, "${label}", ix, &[&world.insurance]).await;`;
+      }
+    }).join('\n\n');
+
+    return `//! Scenario \`${scenarioFile}\` against the program in an in-process bank.
+//! Generated by \`sold simulate\`; do not edit, rerun it instead.
+#![allow(dead_code, unused_imports)]
+${helpers}
+// The program's error codes, from its IDL
+const ERRORS: &[(u32, &str)] = &[
+${errors}
+];
+
+struct World {
+    mint: Pubkey,
+    holder: Keypair,
+    holder_tokens: Pubkey,
+    recipient_tokens: Pubkey,
+    insurance: Keypair,
+    reserve: Pubkey,
+    withdrawn: Pubkey,
+}
+
+fn describe(error: BanksClientError) -> String {
+    match error {
+        BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code))) => ERRORS
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map_or_else(|| format!("custom error {code}"), |(_, name)| name.to_string()),
+        other => other.to_string(),
+    }
+}
+
+async fn report(context: &mut ProgramTestContext, world: &World) {
+    let address = seeds::find_launch(&world.mint).0;
+    if context.banks_client.get_account(address).await.unwrap().is_some() {
+        let launch = fetch_launch(context, address).await;
+        let now = now(context).await;
+        let lock = if now < launch.timelock_end { format!("{}s left", launch.timelock_end - now) } else { "unlocked".to_string() };
+        println!(
+            "    launch: timelock_end {} ({lock}), fees_collected {}, total_withdrawn {}",
+            launch.timelock_end, launch.fees_collected, launch.total_withdrawn
+        );
+    }
+    println!(
+        "    tokens: holder {}, recipient {}, insurance reserve {}, withdrawn {}",
+        token_balance(context, world.holder_tokens).await,
+        token_balance(context, world.recipient_tokens).await,
+        token_balance(context, world.reserve).await,
+        token_balance(context, world.withdrawn).await
+    );
+}
+
+// Send a step's instruction and print its outcome, the fees it paid and
+// the state it left; a failing step does not end the scenario
+async fn run(context: &mut ProgramTestContext, world: &World, label: &str, ix: Instruction, signers: &[&Keypair]) {
+    let before = lamports(context, fee_recipient()).await;
+    let outcome = match send(context, ix, signers).await {
+        Ok(()) => "ok".to_string(),
+        Err(error) => format!("failed: {}", describe(error)),
+    };
+    let fees = lamports(context, fee_recipient()).await - before;
+    println!("{label}: {outcome}, {fees} lamports in fees");
+    report(context, world).await;
+}
+
+async fn warp(context: &mut ProgramTestContext, world: &World, label: &str, unix_timestamp: i64) {
+    warp_to(context, unix_timestamp).await;
+    println!("{label}: clock at {unix_timestamp}");
+    report(context, world).await;
+}
+
+// Put the stand-in keypair in place of the spec's first insurance wallet
+async fn stand_in_insurance(context: &mut ProgramTestContext, world: &World) {
+    let address = seeds::find_launch(&world.mint).0;
+    let Some(mut account) = context.banks_client.get_account(address).await.unwrap() else { return };
+    let mut launch = fetch_launch(context, address).await;
+    if let Some(first) = launch.insurance_wallets.first_mut() {
+        *first = world.insurance.pubkey();
+    }
+    let mut data = Vec::new();
+    launch.try_serialize(&mut data).unwrap();
+    data.resize(account.data.len(), 0);
+    account.data = data;
+    context.set_account(&address, &account.into());
+}
+
+#[tokio::test]
+async fn scenario() {
+    let mut test = program_test();
+    let mint = add_mint(&mut test);
+    let holder = Keypair::new();
+    let insurance = Keypair::new();
+    let world = World {
+        mint,
+        holder_tokens: add_token_account(&mut test, mint, holder.pubkey(), TOTAL_SUPPLY / 2),
+        recipient_tokens: add_token_account(&mut test, mint, Pubkey::new_unique(), 0),
+        reserve: add_token_account(&mut test, mint, insurance.pubkey(), TOTAL_SUPPLY - TOTAL_SUPPLY / 2),
+        withdrawn: add_token_account(&mut test, mint, insurance.pubkey(), 0),
+        holder,
+        insurance,
+    };
+    let mut context = test.start_with_context().await;
+
+${body}
+}
+`;
+  }
+
+  private clusterFor(network: SolDTypes['network']): string {
+    return network.toLowerCase();
+  }
+}
+
+// "abc_launch" -> "AbcLaunch", the key anchor.workspace exposes
+// "tokenMint" -> "token_mint"
+function toSnakeCase(name: string): string {
+  return name.replace(/([a-z0-9])([A-Z])/g, '$1_$2').toLowerCase();
+}
+
+function toPascalCase(name: string): string {
+  return name.split('_').map(part => part.charAt(0).toUpperCase() + part.slice(1)).join('');
+}
+
+function toCamelCase(name: string): string {
+  const pascal = toPascalCase(name);
+  return pascal.charAt(0).toLowerCase() + pascal.slice(1);
+}
+
+// The Rust type an IDL type was generated from
+function idlRustType(type: IdlType): string {
+  if (typeof type === 'string') {
+    if (type === 'publicKey') return 'Pubkey';
+    if (type === 'string') return 'String';
+    return type;
+  }
+  if ('vec' in type) return `Vec<${idlRustType(type.vec)}>`;
+  if ('option' in type) return `Option<${idlRustType(type.option)}>`;
+  return type.defined;
+}
+
+// Export for use
+export {
+  SolDParser, SolDCLI, SolDLinter, formatDiagnostic, TsClientGenerator, RustClientGenerator, TemplateEngine, TemplateGenerator,
+  loadGenerator, MintGrinder, TokenomicsExporter, BudgetReporter, LaunchArchiver, verifyArchive,
+  MessageCatalogGenerator, formatMessage
+};
+
+// Example usage
+function parseFlags(args: string[]): Record<string, string> {
+  const flags: Record<string, string> = {};
+  for (let i = 0; i < args.length; i++) {
+    if (args[i].startsWith('--')) {
+      flags[args[i].slice(2)] = args[i + 1];
+      i++;
+    }
+  }
+  return flags;
+}
+
+// --compute-units / --priority-fee: a number or "auto"
+function budgetFlag(value?: string): number | 'auto' | undefined {
+  if (value === undefined || value === 'auto') return value;
+  const parsed = parseInt(value);
+  if (!Number.isInteger(parsed) || parsed < 0) {
+    console.error(`❌ Expected a number or "auto", got "${value}"`);
+    process.exit(1);
+  }
+  return parsed;
+}
+
+if (require.main === module) {
+  const cli = new SolDCLI();
+
+  if (process.argv[2] === 'grind-mint') {
+    const flags = parseFlags(process.argv.slice(3));
+    cli.grindMint({
+      suffix: flags.suffix,
+      prefix: flags.prefix,
+      seed: flags.seed,
+      maxAttempts: flags['max-attempts'] ? parseInt(flags['max-attempts']) : undefined
+    }, flags.out || 'mint-keypair.json');
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'tokenomics') {
+    const flags = parseFlags(process.argv.slice(4));
+    cli.tokenomics(process.argv[3], {
+      stepDays: flags['step-days'] ? parseInt(flags['step-days']) : undefined,
+      horizonDays: flags['horizon-days'] ? parseInt(flags['horizon-days']) : undefined
+    }, flags.out);
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'gen' && process.argv[3] === 'rust') {
+    const flags = parseFlags(process.argv.slice(5));
+    cli.genRust(process.argv[4], { programId: flags['program-id'] }, flags['out-dir']);
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'gen' && process.argv[3] === 'ts') {
+    const flags = parseFlags(process.argv.slice(5));
+    cli.genTs(process.argv[4], { programId: flags['program-id'] }, flags.out);
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'idl') {
+    const flags = parseFlags(process.argv.slice(4));
+    cli.idl(process.argv[3], { programId: flags['program-id'] }, flags.out);
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'check') {
+    cli.check(process.argv[3], { json: process.argv.includes('--json') });
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'catalog') {
+    const flags = parseFlags(process.argv.slice(3));
+    cli.catalog({ idl: flags.idl, locale: flags.locale, translations: flags.translations }, flags.out);
+    process.exit(0);
+  }
+
+  if (process.argv[2] === 'archive') {
+    if (process.argv[3] === 'verify') {
+      cli.verifyArchive(process.argv[4]);
+      process.exit(0);
+    }
+    const flags = parseFlags(process.argv.slice(4));
+    cli.archive(process.argv[3], { idl: flags.idl, keypair: flags.keypair, rpc: flags.rpc }, flags.out)
+      .then(() => process.exit(0));
+  } else if (process.argv[2] === 'snapshot') {
+    const flags = parseFlags(process.argv.slice(4));
+    cli.snapshot(process.argv[3], {
+      minBalance: flags['min-balance'],
+      slot: flags.slot ? parseInt(flags.slot) : undefined,
+      format: flags.format,
+      rpc: flags.rpc
+    }, flags.out).then(() => process.exit(0));
+  } else if (process.argv[2] === 'launch') {
+    const args = process.argv.slice(4).filter(arg => arg !== '--json');
+    const firstFlag = args.findIndex(arg => arg.startsWith('--'));
+    const positional = firstFlag === -1 ? args : args.slice(0, firstFlag);
+    const flags = parseFlags(args.slice(positional.length));
+    cli.launch(process.argv[3], positional, {
+      keypair: flags.keypair,
+      rpc: flags.rpc,
+      idl: flags.idl,
+      mint: flags.mint,
+      decimals: flags.decimals ? parseInt(flags.decimals) : undefined,
+      shard: flags.shard ? parseInt(flags.shard) : undefined,
+      justification: flags.justification,
+      destination: flags.destination,
+      template: flags.template,
+      computeUnits: budgetFlag(flags['compute-units']),
+      priorityFee: budgetFlag(flags['priority-fee']),
+      json: process.argv.includes('--json')
+    }).then(() => process.exit(0));
+  } else if (process.argv[2] === 'fees') {
+    const flags = parseFlags(process.argv.slice(4).filter(arg => arg !== '--json'));
+    cli.fees(process.argv[3], { creator: flags.creator, mint: flags.mint, idl: flags.idl, rpc: flags.rpc, json: process.argv.includes('--json') })
+      .then(() => process.exit(0));
+  } else if (process.argv[2] === 'diff') {
+    const flags = parseFlags(process.argv.slice(4).filter(arg => arg !== '--json'));
+    cli.diff(process.argv[3], { programId: flags['program-id'], idl: flags.idl, rpc: flags.rpc, json: process.argv.includes('--json') }, flags.out)
+      .then(() => process.exit(0));
+  } else if (process.argv[2] === 'deploy') {
+    const flags = parseFlags(process.argv.slice(4).filter(arg => arg !== '--yes' && arg !== '--force'));
+    cli.deploy(process.argv[3], flags['out-dir'] || 'build', {
+      cluster: flags.cluster,
+      programId: flags['program-id'],
+      programKeypair: flags['program-keypair'],
+      rpc: flags.rpc,
+      yes: process.argv.includes('--yes'),
+      force: process.argv.includes('--force')
+    }).then(() => process.exit(0));
+  } else if (process.argv[2] === 'simulate') {
+    const flags = parseFlags(process.argv.slice(5));
+    cli.simulate(process.argv[3], process.argv[4], flags['out-dir'] || 'build', { programId: flags['program-id'] })
+      .then(() => process.exit(0));
+  } else if (process.argv[2] === 'build') {
+    // The output directory may be given positionally or as --out-dir
+    const positionalOut = process.argv[4] && !process.argv[4].startsWith('--') ? process.argv[4] : undefined;
+    const flags = parseFlags(process.argv.slice(positionalOut ? 5 : 4));
+    try {
+      for (const plugin of (flags.plugin || '').split(',').filter(Boolean)) cli.use(loadGenerator(plugin));
+      for (const dir of (flags.templates || '').split(',').filter(Boolean)) cli.use(new TemplateGenerator(dir));
+    } catch (error) {
+      console.error('❌ Plugin loading failed:', error.message);
+      process.exit(1);
+    }
+    cli.build(process.argv[3], flags['out-dir'] || positionalOut || 'build', {
+      idl: flags.idl,
+      bench: flags.bench,
+      programId: flags['program-id'],
+      cluster: flags.cluster,
+      migrateFrom: flags['migrate-from']
+    });
+  } else {
+    const [,, inputFile, outputDir] = process.argv;
+  
+    if (!inputFile || !outputDir) {
+      console.log('Usage: node sold-parser.js <input.sold> <output-directory>');
+      console.log('       node sold-parser.js check <input.sold> [--json]');
+      console.log('       node sold-parser.js idl <input.sold> [--program-id ID] [--out idl.json]');
+      console.log('       node sold-parser.js gen ts <input.sold> [--program-id ID] [--out client.ts]');
+      console.log('       node sold-parser.js gen rust <input.sold> [--program-id ID] [--out-dir dir]');
+      console.log('       node sold-parser.js grind-mint --suffix XYZ [--prefix ABC] [--seed HEX] [--out mint-keypair.json]');
+      console.log('       node sold-parser.js tokenomics <input.sold> [--step-days 7] [--horizon-days N] [--out series.json]');
+      console.log('       node sold-parser.js build <input.sold> [--out-dir dir] [--program-id ID] [--cluster devnet] [--idl path] [--bench cu.json]');
+      console.log('         [--plugin gen.js,...] [--templates dir,...] [--migrate-from deployed-idl.json]');
+      console.log('       node sold-parser.js diff <input.sold> [--program-id ID] [--rpc url] [--idl deployed.json] [--out deployed.json] [--json]');
+      console.log('       node sold-parser.js deploy <input.sold> --cluster devnet|mainnet [--out-dir dir] [--program-keypair path]');
+      console.log('         [--program-id ID] [--rpc url] [--force] [--yes]');
+      console.log('       node sold-parser.js simulate <input.sold> <scenario> [--out-dir dir] [--program-id ID]');
+      console.log('       node sold-parser.js archive <mint> [--idl path] [--keypair path] [--rpc url] [--out file]');
+      console.log('       node sold-parser.js archive verify <file.archive.json>');
+      console.log('       node sold-parser.js snapshot <mint> [--min-balance AMOUNT] [--slot N] [--format json|csv] [--rpc url] [--out file]');
+      console.log('       node sold-parser.js launch init <params.json> --mint <mint-keypair.json> [--decimals 9] [--template ADDRESS]');
+      console.log('       node sold-parser.js launch transfer <mint> <recipient> <amount> [--shard N]');
+      console.log('       node sold-parser.js launch emergency-withdraw <mint> <amount> --justification TEXT [--destination ACCOUNT]');
+      console.log('       node sold-parser.js launch stake-collateral <mint> <sol>');
+      console.log('       node sold-parser.js launch template <id> <terms.json>');
+      console.log('       node sold-parser.js launch status <mint> [--json]');
+      console.log('         (launch commands take [--keypair path] [--rpc url] [--idl path] [--compute-units N|auto] [--priority-fee N|auto])');
+      console.log('       node sold-parser.js fees <params.json> [--creator KEY] [--mint KEY] [--idl path] [--rpc url] [--json]');
+      console.log('       node sold-parser.js catalog [--idl path] [--locale en] [--translations file.json] [--out messages.json]');
+      process.exit(1);
+    }
+  
+    cli.compile(inputFile, outputDir);
   }
-  
-  cli.compile(inputFile, outputDir);
 }
\ No newline at end of file
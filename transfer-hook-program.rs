@@ -0,0 +1,181 @@
+// SolD transfer hook program
+// Token-2022 launch mints register this program as their transfer hook. It is
+// deployed apart from sold_token_launch: the launch program signs transfers of
+// its own mints, and a hook in the same program would re-enter it through
+// Token-2022, which the runtime rejects.
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+use sold_token_launch::{seeds, TokenLaunch, TokenLaunchError};
+
+declare_id!("So1DTransferHook111111111111111111111111111");
+
+// Execute passes source, mint, destination, owner and the validation account,
+// so the extra accounts start at index 5
+const LAUNCH_PROGRAM_INDEX: u8 = 5;
+
+#[program]
+pub mod sold_transfer_hook {
+    use super::*;
+
+    /// Register the extra accounts the transfer hook needs (the launch
+    /// program and launch PDA). Signed by the launch's creator.
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        ctx.accounts.token_launch.load()?.verify_creator(&ctx.accounts.creator.key())?;
+
+        let metas = extra_account_metas()?;
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+
+        msg!("Transfer hook accounts registered for {}", ctx.accounts.token_mint.key());
+        Ok(())
+    }
+
+    /// Token-2022 transfer hook: block transfers of launch tokens until the
+    /// timelock expires or while the launch is suspended. Vault releases
+    /// signed by the launch PDA are already validated by the launch program.
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        let launch = &ctx.accounts.token_launch.load()?;
+
+        // Only accept calls made by Token-2022 during an actual transfer
+        let source_info = ctx.accounts.source_token.to_account_info();
+        let source_data = source_info.try_borrow_data()?;
+        let source = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&source_data)?;
+        let transferring = source
+            .get_extension::<TransferHookAccount>()
+            .map(|extension| bool::from(extension.transferring))
+            .unwrap_or(false);
+        require!(transferring, TokenLaunchError::NotTransferring);
+
+        if ctx.accounts.owner.key() != ctx.accounts.token_launch.key() {
+            let clock = Clock::get()?;
+            require!(launch.is_active(), TokenLaunchError::LaunchInactive);
+            require!(
+                launch.is_timelock_expired(clock.unix_timestamp),
+                TokenLaunchError::TimelockActive
+            );
+            launch.check_dex_counterparty(
+                &ctx.accounts.source_token.owner,
+                &ctx.accounts.destination_token.owner,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        msg!("Transfer hook approved {} tokens", amount);
+        Ok(())
+    }
+
+    /// Route the SPL transfer-hook `Execute` instruction, whose discriminator
+    /// differs from Anchor's, to `transfer_hook`
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        let instruction = TransferHookInstruction::unpack(data)?;
+        match instruction {
+            TransferHookInstruction::Execute { amount } => {
+                let amount_bytes = amount.to_le_bytes();
+                __private::__global::transfer_hook(program_id, accounts, &amount_bytes)
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        seeds::program = sold_token_launch::ID
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: TLV account initialized with ExtraAccountMetaList in the instruction
+    #[account(
+        init,
+        payer = creator,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [seeds::EXTRA_ACCOUNT_METAS, token_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account order is fixed by the SPL transfer-hook interface, followed by the
+// extra accounts in `extra_account_metas` order
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = token_mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = token_mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, may be the launch PDA
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Validated by seeds
+    #[account(
+        seeds = [seeds::EXTRA_ACCOUNT_METAS, token_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    /// CHECK: The launch program, which owns the launch PDA
+    #[account(address = sold_token_launch::ID)]
+    pub launch_program: AccountInfo<'info>,
+
+    #[account(
+        seeds = [seeds::LAUNCH, token_mint.key().as_ref()],
+        bump,
+        seeds::program = sold_token_launch::ID
+    )]
+    pub token_launch: AccountLoader<'info, TokenLaunch>,
+}
+
+/// Extra accounts Token-2022 passes to `transfer_hook`: the launch program,
+/// then the launch PDA it derives from the mint (account index 1 in the
+/// Execute instruction)
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&sold_token_launch::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            LAUNCH_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: seeds::LAUNCH.to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false, // is_signer
+            false, // is_writable
+        )?,
+    ])
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_hook_program() {
+        // Launch mints register this program by the ID the launch program knows
+        assert_eq!(crate::ID, sold_token_launch::TRANSFER_HOOK_PROGRAM_ID);
+        assert_eq!(extra_account_metas().unwrap().len(), 2);
+    }
+}